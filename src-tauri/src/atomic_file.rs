@@ -0,0 +1,102 @@
+// src-tauri/src/atomic_file.rs
+// 原子写文件：先写同目录临时文件并刷盘，再改名覆盖目标
+//
+// 直接 std::fs::write 会先截断目标文件再写入，写到一半进程崩溃、断电或被杀，
+// 留下的就是半截 JSON（配置、导出清单都遇到过）。改名在同一文件系统内是原子的：
+// 目标文件要么是旧内容，要么是完整的新内容。
+//
+// 临时文件必须和目标在同一目录，跨卷改名会退化为复制。
+
+use std::fs::{self, File};
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+
+/// 目标文件旁的临时文件路径：`.<文件名>.<pid>.tmp`
+fn temp_path_for(path: &Path) -> PathBuf {
+    let name = path
+        .file_name()
+        .map(|n| n.to_string_lossy().to_string())
+        .unwrap_or_default();
+    path.with_file_name(format!(".{}.{}.tmp", name, std::process::id()))
+}
+
+/// 原子写入文件（失败时删除临时文件，目标文件保持原样）
+pub fn write(path: impl AsRef<Path>, contents: impl AsRef<[u8]>) -> io::Result<()> {
+    let path = path.as_ref();
+    let temp = temp_path_for(path);
+
+    let result = (|| {
+        let mut file = File::create(&temp)?;
+        file.write_all(contents.as_ref())?;
+        file.sync_all()?;
+        drop(file);
+        // Windows 上 rename 同样会替换已存在的目标（MoveFileEx + REPLACE_EXISTING）
+        fs::rename(&temp, path)
+    })();
+
+    if result.is_err() {
+        let _ = fs::remove_file(&temp);
+        return result;
+    }
+
+    // 刷新目录项，确保改名本身落盘（Windows 不支持打开目录，跳过）
+    #[cfg(unix)]
+    if let Some(parent) = path.parent().filter(|p| !p.as_os_str().is_empty()) {
+        if let Ok(dir) = File::open(parent) {
+            let _ = dir.sync_all();
+        }
+    }
+    Ok(())
+}
+
+/// `write` 的异步版本，在阻塞线程池中执行
+pub async fn write_async(path: impl Into<PathBuf>, contents: impl Into<Vec<u8>>) -> io::Result<()> {
+    let path = path.into();
+    let contents = contents.into();
+    tokio::task::spawn_blocking(move || write(&path, contents))
+        .await
+        .map_err(io::Error::other)?
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn temp_dir(name: &str) -> PathBuf {
+        let dir =
+            std::env::temp_dir().join(format!("picnexus_atomic_{}_{}", name, std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    #[test]
+    fn write_replaces_existing_file_without_leaving_temp() {
+        let dir = temp_dir("replace");
+        let target = dir.join("配置.json");
+        fs::write(&target, b"{\"old\":true}").unwrap();
+
+        write(&target, b"{\"new\":true}").unwrap();
+
+        assert_eq!(fs::read(&target).unwrap(), b"{\"new\":true}");
+        let entries: Vec<_> = fs::read_dir(&dir).unwrap().flatten().collect();
+        assert_eq!(entries.len(), 1, "临时文件应已改名为目标文件");
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn failed_write_keeps_original_file() {
+        let dir = temp_dir("fail");
+        let target = dir.join("history.json");
+        fs::write(&target, b"original").unwrap();
+
+        // 目标位置是目录，改名失败
+        let blocked = dir.join("blocked");
+        fs::create_dir_all(blocked.join("child")).unwrap();
+        assert!(write(&blocked, b"new").is_err());
+
+        assert_eq!(fs::read(&target).unwrap(), b"original");
+        assert!(!temp_path_for(&blocked).exists());
+        let _ = fs::remove_dir_all(&dir);
+    }
+}
@@ -7,8 +7,24 @@
 //
 // Typora 自定义命令模式：
 //   picnexus.exe --profile typora /path/to/img.jpg
+//   picnexus.exe --typora /path/to/img.jpg   （stdout 输出 Upload Success: + URL 列表）
+//
+// 自动化（macOS 快捷指令「运行 Shell 脚本」/ AppleScript do shell script）：
+//   picnexus.exe --latest-link [--json]      （stdout 输出最近一次上传的链接）
+//
+// 编辑器扩展（见 editor_protocol.rs）：
+//   picnexus.exe --stdio                     （JSON Lines 请求 / 响应，带 progress 帧）
+//
+// Git pre-commit 钩子（见 git_hook.rs）：
+//   picnexus hook --staged [--service r2] [--dry-run]
 
+use crate::commands::post_upload_action::{self, PostUploadAction};
+use crate::deep_link::is_deep_link_arg;
+use crate::editor_protocol::EDITOR_STDIO_ARG;
+use crate::native_messaging::{is_native_messaging_launch, INSTALL_NATIVE_HOST_ARG};
 use crate::portable;
+use crate::quick_actions::{QuickAction, QUICK_ACTION_ARG};
+use crate::server::api_handler::{query_history, HistoryQuery};
 use crate::server::upload_handler::{upload_single_file, ServerUploadConfig};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
@@ -16,9 +32,11 @@ use std::collections::HashMap;
 /// Tauri 的 bundle identifier（与 tauri.conf.json 中的 identifier 一致）
 const APP_IDENTIFIER: &str = "us.picnex.app";
 const TYPORA_PROFILE: &str = "typora";
+const LATEST_LINK_ARG: &str = "--latest-link";
+const GIT_HOOK_SUBCOMMAND: &str = "hook";
 
 /// 获取应用数据目录（与 Tauri 使用的路径一致）
-fn get_app_data_dir() -> Option<std::path::PathBuf> {
+pub(crate) fn get_app_data_dir() -> Option<std::path::PathBuf> {
     if let Some(dir) = portable::portable_data_dir() {
         return Some(dir);
     }
@@ -52,26 +70,81 @@ fn get_app_data_dir() -> Option<std::path::PathBuf> {
     }
 }
 
+/// 历史记录数据库路径（与 portable::history_db_path 一致：tauri-plugin-sql 以 app_config_dir 为根）
+fn get_history_db_path() -> Option<std::path::PathBuf> {
+    // Windows / macOS 的 app_config_dir 与 app_data_dir 相同，便携版也共用数据目录
+    #[cfg(any(target_os = "windows", target_os = "macos"))]
+    {
+        get_app_data_dir().map(|dir| dir.join("history.db"))
+    }
+    #[cfg(not(any(target_os = "windows", target_os = "macos")))]
+    {
+        if let Some(dir) = portable::portable_data_dir() {
+            return Some(dir.join("history.db"));
+        }
+        std::env::var("XDG_CONFIG_HOME")
+            .ok()
+            .map(std::path::PathBuf::from)
+            .or_else(|| {
+                std::env::var("HOME")
+                    .ok()
+                    .map(|p| std::path::PathBuf::from(p).join(".config"))
+            })
+            .map(|p| p.join(APP_IDENTIFIER).join("history.db"))
+    }
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum CliProfile {
     Cli,
     Typora,
 }
 
+/// stdout 输出格式
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CliOutput {
+    /// 每行一个 URL
+    Text,
+    /// --json：结构化结果
+    Json,
+    /// --typora：Typora 自定义命令格式（Upload Success: + 每行一个 URL）
+    Typora,
+}
+
 /// CLI 参数解析结果
 #[derive(Debug, PartialEq, Eq)]
 pub enum CliAction {
     /// 正常上传文件
     Upload {
         files: Vec<String>,
-        json_output: bool,
+        output: CliOutput,
         service_id: Option<String>,
         profile: CliProfile,
     },
+    /// 输出最近一次上传的链接（供快捷指令 / AppleScript 调用）
+    LatestLink(CliOutput),
+    /// Git pre-commit 钩子：上传暂存区新增图片并改写 Markdown 引用
+    GitHook {
+        service_id: Option<String>,
+        dry_run: bool,
+    },
     /// 显示帮助
     Help,
     /// 显示版本
     Version,
+    /// 平台快捷操作（跳转列表 / 菜单），继续启动 GUI 后执行
+    QuickAction(QuickAction),
+    /// 仅传入文件路径（「打开方式」/ 拖到 exe 上），启动 GUI 并加入上传队列；
+    /// 已有实例运行时由 single-instance 转发给主实例
+    OpenFiles(Vec<String>),
+    /// 由浏览器作为 native messaging host 启动
+    NativeMessaging,
+    /// 作为编辑器扩展的 stdio 后端运行
+    EditorStdio,
+    /// 为指定扩展 ID 注册 native messaging host
+    InstallNativeHost(String),
+    /// picnexus:// 链接启动（由 tauri-plugin-deep-link 从 argv 读取），继续启动 GUI
+    DeepLink,
     /// 参数错误
     Error(String),
     /// 不是 CLI 模式，继续启动 GUI
@@ -106,10 +179,25 @@ where
         return CliAction::None;
     }
 
-    let mut json_output = false;
+    // 浏览器启动 host 时传入 origin / manifest 路径，不能当作待上传文件
+    if is_native_messaging_launch(&args) {
+        return CliAction::NativeMessaging;
+    }
+
+    // 系统打开 picnexus:// 链接时只传入这一个参数，不能当作待上传文件
+    if args.len() == 1 && is_deep_link_arg(&args[0]) {
+        return CliAction::DeepLink;
+    }
+
+    if args[0] == GIT_HOOK_SUBCOMMAND {
+        return parse_git_hook_args(&args[1..]);
+    }
+
+    let mut output = CliOutput::Text;
     let mut service_id: Option<String> = None;
     let mut profile = CliProfile::Cli;
     let mut files: Vec<String> = Vec::new();
+    let mut latest_link = false;
     let mut parsing_options = true;
     let mut idx = 0;
 
@@ -126,8 +214,48 @@ where
             match arg.as_str() {
                 "--help" | "-h" => return CliAction::Help,
                 "--version" | "-V" => return CliAction::Version,
-                "--json" => {
-                    json_output = true;
+                QUICK_ACTION_ARG => {
+                    let Some(value) = args.get(idx + 1) else {
+                        return CliAction::Error("--quick-action 需要操作名".to_string());
+                    };
+                    return match QuickAction::from_id(value) {
+                        Some(action) => CliAction::QuickAction(action),
+                        None => CliAction::Error(format!("未知快捷操作: {}", value)),
+                    };
+                }
+                INSTALL_NATIVE_HOST_ARG => {
+                    return match args.get(idx + 1) {
+                        Some(id) if !id.starts_with('-') => {
+                            CliAction::InstallNativeHost(id.clone())
+                        }
+                        _ => CliAction::Error(format!(
+                            "{} 需要浏览器扩展 ID",
+                            INSTALL_NATIVE_HOST_ARG
+                        )),
+                    };
+                }
+                EDITOR_STDIO_ARG => {
+                    return if args.len() == 1 {
+                        CliAction::EditorStdio
+                    } else {
+                        CliAction::Error(format!("{} 不能与其他参数一起使用", EDITOR_STDIO_ARG))
+                    };
+                }
+                LATEST_LINK_ARG => {
+                    latest_link = true;
+                    idx += 1;
+                    continue;
+                }
+                "--json" | "--typora" => {
+                    let requested = if arg == "--json" {
+                        CliOutput::Json
+                    } else {
+                        CliOutput::Typora
+                    };
+                    if output != CliOutput::Text && output != requested {
+                        return CliAction::Error("--json 与 --typora 不能同时使用".to_string());
+                    }
+                    output = requested;
                     idx += 1;
                     continue;
                 }
@@ -170,21 +298,77 @@ where
         idx += 1;
     }
 
+    if latest_link {
+        if !files.is_empty()
+            || service_id.is_some()
+            || profile != CliProfile::Cli
+            || output == CliOutput::Typora
+        {
+            return CliAction::Error(format!("{} 只能与 --json 一起使用", LATEST_LINK_ARG));
+        }
+        return CliAction::LatestLink(output);
+    }
+
+    // --typora 未指定图床时默认使用 Typora 专用配置
+    if output == CliOutput::Typora && service_id.is_none() {
+        profile = CliProfile::Typora;
+    }
+
+    let gui_mode = output == CliOutput::Text && service_id.is_none() && profile == CliProfile::Cli;
+
     if files.is_empty() {
-        if json_output || service_id.is_some() || profile != CliProfile::Cli {
+        if !gui_mode {
             return CliAction::Error("请提供文件路径".to_string());
         }
         return CliAction::None;
     }
 
+    // 没有任何 CLI 选项时按系统「打开方式」处理，交给 GUI 上传队列
+    if gui_mode {
+        return CliAction::OpenFiles(files);
+    }
+
     CliAction::Upload {
         files,
-        json_output,
+        output,
         service_id,
         profile,
     }
 }
 
+/// 解析 `hook` 子命令参数；目前只支持 --staged（处理暂存区）
+fn parse_git_hook_args(args: &[String]) -> CliAction {
+    let mut staged = false;
+    let mut dry_run = false;
+    let mut service_id = None;
+    let mut idx = 0;
+
+    while idx < args.len() {
+        match args[idx].as_str() {
+            "--staged" => staged = true,
+            "--dry-run" => dry_run = true,
+            "--service" | "-s" => match args.get(idx + 1) {
+                Some(value) if !value.starts_with('-') => {
+                    service_id = Some(value.clone());
+                    idx += 1;
+                }
+                _ => return CliAction::Error("--service 需要图床名".to_string()),
+            },
+            "--help" | "-h" => return CliAction::Help,
+            other => return CliAction::Error(format!("hook 不支持的参数: {}", other)),
+        }
+        idx += 1;
+    }
+
+    if !staged {
+        return CliAction::Error("hook 需要 --staged（处理 Git 暂存区中的新增图片）".to_string());
+    }
+    CliAction::GitHook {
+        service_id,
+        dry_run,
+    }
+}
+
 /// 显示帮助信息
 pub fn print_help() {
     let version = env!("CARGO_PKG_VERSION");
@@ -194,6 +378,14 @@ pub fn print_help() {
     eprintln!("  picnexus --service <图床名> <文件路径...>       上传图片到指定图床");
     eprintln!("  picnexus --service <图床名> --json <文件...>    以 JSON 格式输出结果");
     eprintln!("  picnexus --profile typora <文件...>             Typora 专用上传配置");
+    eprintln!("  picnexus --typora <文件...>                     Typora 自定义命令输出格式");
+    eprintln!("  picnexus <文件...>                              在 PicNexus 窗口中加入上传队列");
+    eprintln!("  picnexus --latest-link [--json]                 输出最近一次上传的链接");
+    eprintln!("  picnexus hook --staged [--service <图床名>]     Git pre-commit：上传新增图片并改写 Markdown");
+    eprintln!(
+        "  picnexus --stdio                                编辑器扩展 stdio 协议（JSON Lines）"
+    );
+    eprintln!("  picnexus --install-native-host <扩展 ID>        注册浏览器扩展转存功能");
     eprintln!("  picnexus --help                                显示帮助信息");
     eprintln!("  picnexus --version                             显示版本号");
     eprintln!();
@@ -211,17 +403,60 @@ pub fn print_version() {
     println!("PicNexus v{}", env!("CARGO_PKG_VERSION"));
 }
 
-/// JSON 输出结构
+/// JSON 输出结构（供 Raycast / Alfred / uTools 等启动器工作流解析）
 #[derive(Serialize)]
 struct JsonResult {
     success: bool,
+    /// 实际使用的图床（配置读取失败时为空）
+    service: Option<String>,
+    total: usize,
+    succeeded: usize,
+    failed: usize,
     results: Vec<JsonFileResult>,
+    /// 与具体文件无关的错误（如缺少 --service、配置文件不存在）
+    errors: Vec<String>,
+}
+
+impl JsonResult {
+    fn new(service: Option<String>, results: Vec<JsonFileResult>) -> Self {
+        let succeeded = results.iter().filter(|r| r.url.is_some()).count();
+        let failed = results.len() - succeeded;
+        Self {
+            success: failed == 0,
+            service,
+            total: results.len(),
+            succeeded,
+            failed,
+            results,
+            errors: Vec::new(),
+        }
+    }
+
+    fn fatal(error: String) -> Self {
+        Self {
+            success: false,
+            service: None,
+            total: 0,
+            succeeded: 0,
+            failed: 0,
+            results: Vec::new(),
+            errors: vec![error],
+        }
+    }
 }
 
 #[derive(Serialize)]
 struct JsonFileResult {
+    /// 文件名
     file: String,
+    /// 命令行传入的原始路径
+    path: String,
     url: Option<String>,
+    /// 与 GUI 复制格式一致的 Markdown 链接
+    markdown: Option<String>,
+    /// 原文件大小（字节），文件无法读取时为空
+    size: Option<u64>,
+    host: String,
     error: Option<String>,
 }
 
@@ -240,7 +475,46 @@ fn parse_cli_config_json(config_json: &str) -> Result<LoadedCliConfig, String> {
     Ok(LoadedCliConfig::Legacy(legacy))
 }
 
-fn service_id_for_config(config: &ServerUploadConfig) -> &'static str {
+/// 读取 GUI 导出的 cli-config.json（供非命令行入口复用）
+fn load_cli_config() -> Result<LoadedCliConfig, String> {
+    let config_path = get_app_data_dir()
+        .map(|dir| dir.join("cli-config.json"))
+        .ok_or_else(|| "无法确定应用数据目录".to_string())?;
+    if !config_path.exists() {
+        return Err("未找到图床配置，请先打开 PicNexus，在设置中配置图床并保存".to_string());
+    }
+    let config_json =
+        std::fs::read_to_string(&config_path).map_err(|e| format!("读取配置文件失败: {}", e))?;
+    parse_cli_config_json(&config_json)
+}
+
+/// 已导出给 CLI 的可用图床列表
+pub(crate) fn configured_services() -> Result<Vec<String>, String> {
+    load_cli_config().map(|config| available_services(&config))
+}
+
+/// 按图床名解析上传配置；未指定且只有一个可用图床时自动选择
+pub(crate) fn resolve_service_config(
+    service_id: Option<&str>,
+) -> Result<(String, ServerUploadConfig), String> {
+    let loaded = load_cli_config()?;
+    let service_id = match service_id {
+        Some(id) => id.to_string(),
+        None => match available_services(&loaded).as_slice() {
+            [only] => only.clone(),
+            _ => {
+                return Err(format!(
+                    "请指定图床（service）。\n{}",
+                    format_available_services(&loaded)
+                ))
+            }
+        },
+    };
+    let config = resolve_upload_config(&loaded, CliProfile::Cli, Some(&service_id))?;
+    Ok((service_id, config))
+}
+
+pub(crate) fn service_id_for_config(config: &ServerUploadConfig) -> &'static str {
     match config {
         ServerUploadConfig::Jd => "jd",
         ServerUploadConfig::Github { .. } => "github",
@@ -348,6 +622,50 @@ fn resolve_upload_config(
     }
 }
 
+/// 与前端 linkFormatter 的 markdown 格式一致：转义文件名中的 [] 和 URL 中的 ()
+pub(crate) fn format_markdown_link(file_name: &str, url: &str) -> String {
+    let escape = |value: &str, special: &[char]| {
+        let mut escaped = String::with_capacity(value.len());
+        for c in value.chars() {
+            if special.contains(&c) {
+                escaped.push('\\');
+            }
+            escaped.push(c);
+        }
+        escaped
+    };
+    format!(
+        "![{}]({})",
+        escape(file_name, &['[', ']']),
+        escape(url, &['(', ')'])
+    )
+}
+
+/// 启动阶段的致命错误：文本模式写 stderr，--json 模式额外在 stdout 输出结构化错误
+fn exit_with_error(output: CliOutput, lines: &[String]) -> ! {
+    for line in lines {
+        eprintln!("[PicNexus] {}", line);
+    }
+    if output == CliOutput::Json {
+        let result = JsonResult::fatal(lines.join("\n"));
+        println!(
+            "{}",
+            serde_json::to_string_pretty(&result).unwrap_or_default()
+        );
+    }
+    std::process::exit(1);
+}
+
+/// Typora 自定义命令要求的 stdout 格式：首行 Upload Success:，随后每行一个 URL（与图片顺序一致）
+fn format_typora_output(urls: &[String]) -> String {
+    let mut output = String::from("Upload Success:");
+    for url in urls {
+        output.push('\n');
+        output.push_str(url);
+    }
+    output
+}
+
 fn format_upload_failure_message(
     idx: usize,
     total: usize,
@@ -365,10 +683,97 @@ fn format_upload_failure_message(
     )
 }
 
+/// --latest-link --json 输出结构
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct LatestLinkJson {
+    success: bool,
+    link: Option<String>,
+    markdown: Option<String>,
+    file_name: Option<String>,
+    host: Option<String>,
+    timestamp: Option<i64>,
+    error: Option<String>,
+}
+
+impl LatestLinkJson {
+    fn failure(error: String) -> Self {
+        Self {
+            success: false,
+            link: None,
+            markdown: None,
+            file_name: None,
+            host: None,
+            timestamp: None,
+            error: Some(error),
+        }
+    }
+}
+
+/// 读取历史记录中最近一次上传的链接（只读，不依赖 GUI 是否运行）
+pub fn run_cli_latest_link(output: CliOutput) {
+    let runtime = match tokio::runtime::Builder::new_current_thread()
+        .enable_all()
+        .build()
+    {
+        Ok(rt) => rt,
+        Err(e) => {
+            eprintln!("[PicNexus] 初始化失败: {}", e);
+            std::process::exit(1);
+        }
+    };
+
+    let latest = runtime.block_on(async {
+        let db_path = get_history_db_path().ok_or_else(|| "无法确定应用数据目录".to_string())?;
+        let query = HistoryQuery {
+            limit: Some(1),
+            ..Default::default()
+        };
+        let response = query_history(&db_path, &query).await?;
+        response
+            .items
+            .into_iter()
+            .next()
+            .ok_or_else(|| "暂无上传记录".to_string())
+    });
+
+    match latest {
+        Ok(item) => {
+            if output == CliOutput::Json {
+                let result = LatestLinkJson {
+                    success: true,
+                    markdown: Some(format_markdown_link(&item.file_name, &item.link)),
+                    link: Some(item.link),
+                    file_name: Some(item.file_name),
+                    host: Some(item.primary_service),
+                    timestamp: Some(item.timestamp),
+                    error: None,
+                };
+                println!(
+                    "{}",
+                    serde_json::to_string_pretty(&result).unwrap_or_default()
+                );
+            } else {
+                println!("{}", item.link);
+            }
+        }
+        Err(e) => {
+            eprintln!("[PicNexus] {}", e);
+            if output == CliOutput::Json {
+                println!(
+                    "{}",
+                    serde_json::to_string_pretty(&LatestLinkJson::failure(e)).unwrap_or_default()
+                );
+            }
+            std::process::exit(1);
+        }
+    }
+}
+
 /// CLI 上传模式主入口
 pub fn run_cli_upload(
     file_paths: Vec<String>,
-    json_output: bool,
+    output: CliOutput,
     service_id: Option<String>,
     profile: CliProfile,
 ) {
@@ -386,49 +791,39 @@ pub fn run_cli_upload(
     runtime.block_on(async {
         let config_path = match get_app_data_dir() {
             Some(dir) => dir.join("cli-config.json"),
-            None => {
-                eprintln!("[PicNexus] 无法确定应用数据目录");
-                std::process::exit(1);
-            }
+            None => exit_with_error(output, &["无法确定应用数据目录".to_string()]),
         };
 
         if profile == CliProfile::Cli && service_id.is_none() {
-            eprintln!(
-                "[PicNexus] {}",
-                format_missing_service_message(&config_path)
-            );
-            std::process::exit(1);
+            exit_with_error(output, &[format_missing_service_message(&config_path)]);
         }
 
         if !config_path.exists() {
-            eprintln!("[PicNexus] 未找到配置文件: {}", config_path.display());
-            eprintln!("[PicNexus] 请先打开 PicNexus，在设置中配置图床并保存。");
-            std::process::exit(1);
+            exit_with_error(
+                output,
+                &[
+                    format!("未找到配置文件: {}", config_path.display()),
+                    "请先打开 PicNexus，在设置中配置图床并保存。".to_string(),
+                ],
+            );
         }
 
         let config_json = match std::fs::read_to_string(&config_path) {
             Ok(s) => s,
-            Err(e) => {
-                eprintln!("[PicNexus] 读取配置文件失败: {}", e);
-                std::process::exit(1);
-            }
+            Err(e) => exit_with_error(output, &[format!("读取配置文件失败: {}", e)]),
         };
 
         let loaded_config = match parse_cli_config_json(&config_json) {
             Ok(c) => c,
-            Err(e) => {
-                eprintln!("[PicNexus] {}", e);
-                eprintln!("[PicNexus] 请在 PicNexus 设置中重新保存图床配置");
-                std::process::exit(1);
-            }
+            Err(e) => exit_with_error(
+                output,
+                &[e, "请在 PicNexus 设置中重新保存图床配置".to_string()],
+            ),
         };
 
         let config = match resolve_upload_config(&loaded_config, profile, service_id.as_deref()) {
             Ok(c) => c,
-            Err(e) => {
-                eprintln!("[PicNexus] {}", e);
-                std::process::exit(1);
-            }
+            Err(e) => exit_with_error(output, &[e]),
         };
 
         let service_label = match profile {
@@ -437,6 +832,11 @@ pub fn run_cli_upload(
                 .unwrap_or(service_id_for_config(&config)),
             CliProfile::Typora => TYPORA_PROFILE,
         };
+        // Typora 配置下 host 仍报告实际图床，而不是 profile 名
+        let host = match profile {
+            CliProfile::Cli => service_label,
+            CliProfile::Typora => service_id_for_config(&config),
+        };
         let total = file_paths.len();
         let mut any_failed = false;
         let mut json_results: Vec<JsonFileResult> = Vec::new();
@@ -447,7 +847,7 @@ pub fn run_cli_upload(
                 .map(|n| n.to_string_lossy().to_string())
                 .unwrap_or_else(|| file_path.clone());
 
-            if !json_output {
+            if output != CliOutput::Json {
                 eprintln!(
                     "[PicNexus] 正在上传 ({}/{}): {} -> {} ...",
                     idx + 1,
@@ -457,15 +857,22 @@ pub fn run_cli_upload(
                 );
             }
 
+            let size = std::fs::metadata(file_path).ok().map(|meta| meta.len());
             match upload_single_file(file_path, &config).await {
                 Ok(url) => {
-                    if !json_output {
+                    if output != CliOutput::Json {
                         eprintln!("[PicNexus] ✓ 成功 ({}/{}): {}", idx + 1, total, file_name);
+                    }
+                    if output == CliOutput::Text {
                         println!("{}", url);
                     }
                     json_results.push(JsonFileResult {
+                        markdown: Some(format_markdown_link(&file_name, &url)),
                         file: file_name,
+                        path: file_path.clone(),
                         url: Some(url),
+                        size,
+                        host: host.to_string(),
                         error: None,
                     });
                 }
@@ -476,7 +883,11 @@ pub fn run_cli_upload(
                     );
                     json_results.push(JsonFileResult {
                         file: file_name,
+                        path: file_path.clone(),
                         url: None,
+                        markdown: None,
+                        size,
+                        host: host.to_string(),
                         error: Some(e),
                     });
                     any_failed = true;
@@ -484,15 +895,32 @@ pub fn run_cli_upload(
             }
         }
 
-        if json_output {
-            let result = JsonResult {
-                success: !any_failed,
-                results: json_results,
-            };
-            println!(
-                "{}",
-                serde_json::to_string_pretty(&result).unwrap_or_default()
-            );
+        // 上传后操作只对手动执行的 CLI 生效：Typora 自己插入链接，不应再复制或打开浏览器
+        if profile == CliProfile::Cli {
+            let urls: Vec<String> = json_results.iter().filter_map(|r| r.url.clone()).collect();
+            match post_upload_action::run_for_cli(&urls).await {
+                Ok(PostUploadAction::CopyLink) if output != CliOutput::Json => {
+                    eprintln!("[PicNexus] 链接已复制到剪贴板");
+                }
+                Ok(_) => {}
+                Err(e) => eprintln!("[PicNexus] 上传后操作失败: {}", e),
+            }
+        }
+
+        match output {
+            CliOutput::Json => {
+                let result = JsonResult::new(Some(host.to_string()), json_results);
+                println!(
+                    "{}",
+                    serde_json::to_string_pretty(&result).unwrap_or_default()
+                );
+            }
+            // Typora 按顺序把 URL 对应到图片，部分失败时不输出，避免错位替换
+            CliOutput::Typora if !any_failed => {
+                let urls: Vec<String> = json_results.into_iter().filter_map(|r| r.url).collect();
+                println!("{}", format_typora_output(&urls));
+            }
+            _ => {}
         }
 
         if any_failed {
@@ -517,17 +945,12 @@ mod tests {
     }
 
     #[test]
-    fn parse_upload_requires_explicit_service_for_cli_profile() {
-        let action = parse_cli_args_from(["image.png"]);
+    fn parse_bare_files_open_in_gui() {
+        let action = parse_cli_args_from(["image.png", "b.jpg"]);
 
         assert_eq!(
             action,
-            CliAction::Upload {
-                files: vec!["image.png".to_string()],
-                json_output: false,
-                service_id: None,
-                profile: CliProfile::Cli,
-            }
+            CliAction::OpenFiles(vec!["image.png".to_string(), "b.jpg".to_string()])
         );
     }
 
@@ -539,7 +962,7 @@ mod tests {
             action,
             CliAction::Upload {
                 files: vec!["a.png".to_string(), "b.jpg".to_string()],
-                json_output: true,
+                output: CliOutput::Json,
                 service_id: Some("smms".to_string()),
                 profile: CliProfile::Cli,
             }
@@ -554,7 +977,7 @@ mod tests {
             action,
             CliAction::Upload {
                 files: vec!["a.png".to_string()],
-                json_output: false,
+                output: CliOutput::Text,
                 service_id: Some("r2".to_string()),
                 profile: CliProfile::Cli,
             }
@@ -569,13 +992,101 @@ mod tests {
             action,
             CliAction::Upload {
                 files: vec!["a.png".to_string()],
-                json_output: false,
+                output: CliOutput::Text,
+                service_id: None,
+                profile: CliProfile::Typora,
+            }
+        );
+    }
+
+    #[test]
+    fn parse_typora_flag_defaults_to_typora_profile() {
+        let action = parse_cli_args_from(["--typora", "a.png", "b.png"]);
+
+        assert_eq!(
+            action,
+            CliAction::Upload {
+                files: vec!["a.png".to_string(), "b.png".to_string()],
+                output: CliOutput::Typora,
                 service_id: None,
                 profile: CliProfile::Typora,
             }
         );
     }
 
+    #[test]
+    fn parse_typora_flag_keeps_explicit_service() {
+        let action = parse_cli_args_from(["--typora", "--service", "r2", "a.png"]);
+
+        assert_eq!(
+            action,
+            CliAction::Upload {
+                files: vec!["a.png".to_string()],
+                output: CliOutput::Typora,
+                service_id: Some("r2".to_string()),
+                profile: CliProfile::Cli,
+            }
+        );
+    }
+
+    #[test]
+    fn parse_rejects_json_with_typora() {
+        assert!(matches!(
+            parse_cli_args_from(["--json", "--typora", "a.png"]),
+            CliAction::Error(message) if message.contains("不能同时使用")
+        ));
+    }
+
+    #[test]
+    fn typora_output_lists_urls_after_success_header() {
+        let urls = vec![
+            "https://img.example.com/a.png".to_string(),
+            "https://img.example.com/b.png".to_string(),
+        ];
+
+        assert_eq!(
+            format_typora_output(&urls),
+            "Upload Success:\nhttps://img.example.com/a.png\nhttps://img.example.com/b.png"
+        );
+    }
+
+    #[test]
+    fn markdown_link_escapes_brackets_and_parens() {
+        assert_eq!(
+            format_markdown_link("shot[1].png", "https://img.example.com/a_(1).png"),
+            "![shot\\[1\\].png](https://img.example.com/a_\\(1\\).png)"
+        );
+    }
+
+    #[test]
+    fn json_result_counts_successes_and_failures() {
+        let file = |url: Option<&str>| JsonFileResult {
+            file: "a.png".to_string(),
+            path: "./a.png".to_string(),
+            url: url.map(str::to_string),
+            markdown: None,
+            size: Some(42),
+            host: "r2".to_string(),
+            error: url.is_none().then(|| "timeout".to_string()),
+        };
+        let result = JsonResult::new(
+            Some("r2".to_string()),
+            vec![file(Some("https://img.example.com/a.png")), file(None)],
+        );
+
+        let value = serde_json::to_value(&result).unwrap();
+        assert_eq!(value["success"], false);
+        assert_eq!(value["total"], 2);
+        assert_eq!(value["succeeded"], 1);
+        assert_eq!(value["failed"], 1);
+        assert_eq!(value["results"][0]["host"], "r2");
+        assert_eq!(value["results"][0]["size"], 42);
+
+        let fatal = serde_json::to_value(JsonResult::fatal("缺少 --service".to_string())).unwrap();
+        assert_eq!(fatal["success"], false);
+        assert_eq!(fatal["errors"][0], "缺少 --service");
+    }
+
     #[test]
     fn parse_rejects_missing_service_value() {
         assert!(matches!(
@@ -592,6 +1103,102 @@ mod tests {
         ));
     }
 
+    #[test]
+    fn parse_latest_link_with_optional_json() {
+        assert!(matches!(
+            parse_cli_args_from(["--latest-link"]),
+            CliAction::LatestLink(CliOutput::Text)
+        ));
+        assert!(matches!(
+            parse_cli_args_from(["--json", "--latest-link"]),
+            CliAction::LatestLink(CliOutput::Json)
+        ));
+    }
+
+    #[test]
+    fn parse_latest_link_rejects_upload_options() {
+        assert!(matches!(
+            parse_cli_args_from(["--latest-link", "a.png"]),
+            CliAction::Error(message) if message.contains("--latest-link")
+        ));
+        assert!(matches!(
+            parse_cli_args_from(["--latest-link", "--service", "r2"]),
+            CliAction::Error(_)
+        ));
+        assert!(matches!(
+            parse_cli_args_from(["--latest-link", "--typora"]),
+            CliAction::Error(_)
+        ));
+    }
+
+    #[test]
+    fn parse_git_hook_staged_with_service() {
+        assert_eq!(
+            parse_cli_args_from(["hook", "--staged", "--service", "r2", "--dry-run"]),
+            CliAction::GitHook {
+                service_id: Some("r2".to_string()),
+                dry_run: true,
+            }
+        );
+    }
+
+    #[test]
+    fn parse_git_hook_requires_staged() {
+        assert!(matches!(
+            parse_cli_args_from(["hook"]),
+            CliAction::Error(message) if message.contains("--staged")
+        ));
+        assert!(matches!(
+            parse_cli_args_from(["hook", "--staged", "post.md"]),
+            CliAction::Error(_)
+        ));
+    }
+
+    #[test]
+    fn parse_editor_stdio_must_be_alone() {
+        assert_eq!(parse_cli_args_from(["--stdio"]), CliAction::EditorStdio);
+        assert!(matches!(
+            parse_cli_args_from(["--stdio", "a.png"]),
+            CliAction::Error(_)
+        ));
+    }
+
+    #[test]
+    fn parse_quick_action_continues_to_gui() {
+        assert_eq!(
+            parse_cli_args_from(["--quick-action", "upload_clipboard"]),
+            CliAction::QuickAction(QuickAction::UploadClipboard)
+        );
+        assert!(matches!(
+            parse_cli_args_from(["--quick-action", "shutdown"]),
+            CliAction::Error(message) if message.contains("未知快捷操作")
+        ));
+    }
+
+    #[test]
+    fn parse_deep_link_continues_to_gui() {
+        assert_eq!(
+            parse_cli_args_from(["picnexus://upload?path=%2Ftmp%2Fa.png"]),
+            CliAction::DeepLink
+        );
+    }
+
+    #[test]
+    fn parse_native_messaging_arguments() {
+        assert_eq!(
+            parse_cli_args_from(["chrome-extension://abcdefghijklmnopabcdefghijklmnop/"]),
+            CliAction::NativeMessaging
+        );
+        assert_eq!(
+            parse_cli_args_from(["--install-native-host", "abcdefghijklmnopabcdefghijklmnop"]),
+            CliAction::InstallNativeHost("abcdefghijklmnopabcdefghijklmnop".to_string())
+        );
+        assert!(matches!(
+            parse_cli_args_from(["--install-native-host"]),
+            CliAction::Error(message) if message.contains("扩展 ID")
+        ));
+    }
+
     #[test]
     fn resolve_multi_config_by_service() {
         let raw = r#"{
@@ -0,0 +1,333 @@
+// src-tauri/src/commands/alt_text.rs
+// 替代文本建议：上传后为未填写 alt 的图片生成一条建议，写入历史记录，
+// 复制 Markdown / HTML 链接时代替文件名作为 alt
+//
+// 建议来源按顺序尝试，取第一个有结果的：
+//   - 文件名：去掉扩展名、时间戳、哈希与相机 / 截图前缀后仍有可读词语时直接采用
+//   - OCR：调用本机 tesseract 识别图中文字（未安装时跳过）
+//
+// 新增来源（如本地模型）时实现 AltTextSource 并加入 sources() 即可。
+
+use std::path::Path;
+use std::process::Stdio;
+use std::time::Duration;
+
+use futures::future::BoxFuture;
+use serde::Serialize;
+
+use crate::error::AppError;
+use crate::log_utils::{safe_path, summarize_text};
+
+#[cfg(windows)]
+const CREATE_NO_WINDOW: u32 = 0x08000000;
+
+/// alt 文本长度上限（字符），超出部分截断，读屏软件一般建议不超过 125 字符
+const MAX_ALT_CHARS: usize = 125;
+
+/// OCR 超时
+const OCR_TIMEOUT_SECS: u64 = 20;
+
+/// 识别语言（简体中文 + 英文）；语言包缺失时退回 tesseract 默认语言
+const OCR_LANGUAGES: &str = "chi_sim+eng";
+
+/// 相机、截图工具与聊天软件自动生成的文件名片段
+const NOISE_WORDS: &[&str] = &[
+    "img",
+    "image",
+    "dsc",
+    "dscn",
+    "dcim",
+    "pxl",
+    "mvimg",
+    "photo",
+    "screenshot",
+    "screen",
+    "shot",
+    "snipaste",
+    "capture",
+    "wechatimg",
+    "untitled",
+    "copy",
+    "截图",
+    "屏幕截图",
+    "微信图片",
+    "未命名",
+    "副本",
+];
+
+/// 单独出现时没有意义的词（如 "Screenshot 2024-01-01 at 10.30.22" 剩下的 at）
+const STOP_WORDS: &[&str] = &["at", "of", "on", "the", "a", "an"];
+
+/// 一条 alt 文本建议
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AltTextSuggestion {
+    pub text: String,
+    /// 来源：filename / ocr
+    pub source: &'static str,
+}
+
+/// alt 文本建议来源
+pub trait AltTextSource: Send + Sync {
+    /// 来源 ID，写入 AltTextSuggestion.source
+    fn id(&self) -> &'static str;
+
+    /// 为图片生成建议；无法给出时返回 None
+    fn suggest<'a>(&'a self, path: &'a Path) -> BoxFuture<'a, Result<Option<String>, AppError>>;
+}
+
+/// 是否像自动生成的编号：纯数字、或 8 位以上且含数字的十六进制串（哈希 / UUID 片段）
+fn is_generated_token(token: &str) -> bool {
+    token.chars().all(|c| c.is_ascii_digit())
+        || (token.len() >= 8
+            && token.chars().all(|c| c.is_ascii_hexdigit())
+            && token.chars().any(|c| c.is_ascii_digit()))
+}
+
+/// 自动生成的片段或单个字母
+fn is_noise_word(word: &str) -> bool {
+    NOISE_WORDS.contains(&word.to_lowercase().as_str())
+        || is_generated_token(word)
+        || (word.len() == 1 && word.is_ascii())
+}
+
+/// 在 camelCase 与字母 / 数字交界处插入空格
+fn split_camel_case(word: &str) -> String {
+    let mut out = String::with_capacity(word.len() + 8);
+    let mut prev: Option<char> = None;
+    for c in word.chars() {
+        if let Some(p) = prev {
+            let boundary = (p.is_ascii_lowercase() && c.is_ascii_uppercase())
+                || (p.is_ascii_alphabetic() && c.is_ascii_digit())
+                || (p.is_ascii_digit() && c.is_ascii_alphabetic());
+            if boundary {
+                out.push(' ');
+            }
+        }
+        out.push(c);
+        prev = Some(c);
+    }
+    out
+}
+
+/// 从文件名提取可读的 alt 文本；只剩编号、时间戳等自动生成片段时返回 None
+fn filename_alt_text(file_name: &str) -> Option<String> {
+    let stem = Path::new(file_name).file_stem()?.to_string_lossy();
+    // 先按分隔符切分并去掉哈希，再拆 camelCase，避免把哈希拆成零散字母
+    let split: Vec<String> = stem
+        .split(|c: char| c.is_whitespace() || "-_.+()[]".contains(c))
+        .filter(|token| !token.is_empty() && !is_generated_token(token))
+        .map(split_camel_case)
+        .collect();
+    let words: Vec<&str> = split
+        .iter()
+        .flat_map(|token| token.split_whitespace())
+        .filter(|word| !is_noise_word(word))
+        .collect();
+    if words
+        .iter()
+        .all(|word| STOP_WORDS.contains(&word.to_lowercase().as_str()))
+    {
+        return None;
+    }
+    Some(truncate_chars(&words.join(" ")))
+}
+
+/// 按字符截断到 MAX_ALT_CHARS
+fn truncate_chars(text: &str) -> String {
+    if text.chars().count() <= MAX_ALT_CHARS {
+        return text.to_string();
+    }
+    let mut truncated: String = text.chars().take(MAX_ALT_CHARS - 1).collect();
+    truncated.push('…');
+    truncated
+}
+
+/// 整理 OCR 输出：丢弃以符号为主的噪声行，合并为一行；有效文字过少时返回 None
+fn ocr_alt_text(output: &str) -> Option<String> {
+    let lines: Vec<String> = output
+        .lines()
+        .map(|line| line.split_whitespace().collect::<Vec<_>>().join(" "))
+        .filter(|line| {
+            let visible = line.chars().filter(|c| !c.is_whitespace()).count();
+            let readable = line.chars().filter(|c| c.is_alphanumeric()).count();
+            readable >= 2 && readable * 10 >= visible * 6
+        })
+        .collect();
+    let text = lines.join(" ");
+    if text.chars().filter(|c| c.is_alphanumeric()).count() < 3 {
+        return None;
+    }
+    Some(truncate_chars(&text))
+}
+
+/// 文件名启发式
+struct FileNameSource;
+
+impl AltTextSource for FileNameSource {
+    fn id(&self) -> &'static str {
+        "filename"
+    }
+
+    fn suggest<'a>(&'a self, path: &'a Path) -> BoxFuture<'a, Result<Option<String>, AppError>> {
+        Box::pin(async move {
+            Ok(path
+                .file_name()
+                .and_then(|name| filename_alt_text(&name.to_string_lossy())))
+        })
+    }
+}
+
+/// 本机 tesseract OCR
+struct TesseractSource;
+
+impl TesseractSource {
+    async fn run(path: &Path, languages: Option<&str>) -> Result<Option<String>, AppError> {
+        let mut command = tokio::process::Command::new("tesseract");
+        command.arg(path).arg("stdout");
+        if let Some(languages) = languages {
+            command.args(["-l", languages]);
+        }
+        command
+            .stdin(Stdio::null())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .kill_on_drop(true);
+
+        #[cfg(windows)]
+        command.creation_flags(CREATE_NO_WINDOW);
+
+        let child = match command.spawn() {
+            Ok(child) => child,
+            // 未安装 tesseract：跳过 OCR
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(None),
+            Err(e) => return Err(AppError::external(format!("启动 tesseract 失败: {}", e))),
+        };
+        let output = tokio::time::timeout(
+            Duration::from_secs(OCR_TIMEOUT_SECS),
+            child.wait_with_output(),
+        )
+        .await
+        .map_err(|_| AppError::external(format!("OCR 超时（{} 秒）", OCR_TIMEOUT_SECS)))?
+        .map_err(|e| AppError::external(format!("等待 tesseract 结束失败: {}", e)))?;
+
+        if !output.status.success() {
+            return Err(AppError::external(format!(
+                "tesseract 退出码 {}: {}",
+                output.status.code().unwrap_or(-1),
+                summarize_text(String::from_utf8_lossy(&output.stderr).trim())
+            )));
+        }
+        Ok(ocr_alt_text(&String::from_utf8_lossy(&output.stdout)))
+    }
+}
+
+impl AltTextSource for TesseractSource {
+    fn id(&self) -> &'static str {
+        "ocr"
+    }
+
+    fn suggest<'a>(&'a self, path: &'a Path) -> BoxFuture<'a, Result<Option<String>, AppError>> {
+        Box::pin(async move {
+            match Self::run(path, Some(OCR_LANGUAGES)).await {
+                Ok(text) => Ok(text),
+                Err(e) => {
+                    log::debug!(
+                        "[替代文本] 按 {} 识别失败，改用默认语言: {}",
+                        OCR_LANGUAGES,
+                        e
+                    );
+                    Self::run(path, None).await
+                }
+            }
+        })
+    }
+}
+
+/// 建议来源（按优先级排列）
+fn sources(ocr: bool) -> Vec<Box<dyn AltTextSource>> {
+    let mut sources: Vec<Box<dyn AltTextSource>> = vec![Box::new(FileNameSource)];
+    if ocr {
+        sources.push(Box::new(TesseractSource));
+    }
+    sources
+}
+
+/// 为本地图片生成 alt 文本建议（`ocr` 默认开启）；各来源均无结果时返回 None
+#[tauri::command]
+pub async fn suggest_alt_text(
+    path: String,
+    ocr: Option<bool>,
+) -> Result<Option<AltTextSuggestion>, AppError> {
+    let file = Path::new(&path);
+    if !file.is_file() {
+        return Err(AppError::validation(format!(
+            "文件不存在: {}",
+            safe_path(&path)
+        )));
+    }
+
+    for source in sources(ocr.unwrap_or(true)) {
+        match source.suggest(file).await {
+            Ok(Some(text)) => {
+                log::info!(
+                    "[替代文本] {} 来自 {} 的建议: {}",
+                    safe_path(&path),
+                    source.id(),
+                    summarize_text(&text)
+                );
+                return Ok(Some(AltTextSuggestion {
+                    text,
+                    source: source.id(),
+                }));
+            }
+            Ok(None) => {}
+            // 单个来源失败不影响后续来源
+            Err(e) => log::warn!("[替代文本] {} 来源失败: {}", source.id(), e),
+        }
+    }
+    Ok(None)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn filename_keeps_readable_words_only() {
+        assert_eq!(
+            filename_alt_text("system-architecture_diagram-v2.png").as_deref(),
+            Some("system architecture diagram")
+        );
+        assert_eq!(
+            filename_alt_text("loginPageError.jpg").as_deref(),
+            Some("login Page Error")
+        );
+        assert_eq!(
+            filename_alt_text("产品路线图-2024.png").as_deref(),
+            Some("产品路线图")
+        );
+        assert_eq!(filename_alt_text("IMG_20240101_123045.jpg"), None);
+        assert_eq!(
+            filename_alt_text("Screenshot 2024-01-01 at 10.30.22.png"),
+            None
+        );
+        assert_eq!(filename_alt_text("微信图片_20240101123045.jpg"), None);
+        assert_eq!(filename_alt_text("3f9a2c1be07d4e58a1c2.webp"), None);
+    }
+
+    #[test]
+    fn ocr_output_drops_noise_and_truncates() {
+        let output = "  Build   failed\n|~_=/ .\n\nerror[E0425]: cannot find value\n\x0c";
+        assert_eq!(
+            ocr_alt_text(output).as_deref(),
+            Some("Build failed error[E0425]: cannot find value")
+        );
+        assert_eq!(ocr_alt_text("~ | .\n\n"), None);
+
+        let long = "字".repeat(200);
+        let text = ocr_alt_text(&long).unwrap();
+        assert_eq!(text.chars().count(), MAX_ALT_CHARS);
+        assert!(text.ends_with('…'));
+    }
+}
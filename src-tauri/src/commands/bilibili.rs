@@ -6,7 +6,11 @@ use reqwest::multipart;
 use serde::{Deserialize, Serialize};
 use tauri::Window;
 
-use super::utils::read_file_bytes;
+use super::host_capabilities;
+use super::net_limiter::NetworkLimiter;
+use super::request_headers::{resolve_request_headers, RequestHeaderTemplate, RequestHeadersExt};
+use super::task_registry::TaskRegistry;
+use super::utils::{read_file_bytes, remember_uploaded_source};
 use crate::error::{AppError, IntoAppError};
 use crate::log_utils::{safe_path, safe_url, summarize_text};
 
@@ -124,28 +128,39 @@ pub async fn test_bilibili_connection(bilibili_cookie: String) -> Result<String,
 #[tauri::command]
 pub async fn upload_to_bilibili(
     _window: Window,
-    _id: String,
+    id: String,
     file_path: String,
     bilibili_cookie: String,
+    request_headers: Option<RequestHeaderTemplate>,
+    limiter: tauri::State<'_, NetworkLimiter>,
+    tasks: tauri::State<'_, TaskRegistry>,
 ) -> Result<BilibiliUploadResult, AppError> {
+    tasks
+        .run(
+            Some(&id),
+            upload_to_bilibili_inner(file_path.clone(), bilibili_cookie, request_headers, limiter),
+        )
+        .await
+        .inspect(|_| remember_uploaded_source(&file_path))
+}
+
+async fn upload_to_bilibili_inner(
+    file_path: String,
+    bilibili_cookie: String,
+    request_headers: Option<RequestHeaderTemplate>,
+    limiter: tauri::State<'_, NetworkLimiter>,
+) -> Result<BilibiliUploadResult, AppError> {
+    let _permit = limiter.acquire("bilibili").await;
     log::info!("[Bilibili] 开始上传文件: {}", safe_path(&file_path));
 
     // 1. 提取 SESSDATA 和 csrf
     let (sessdata, csrf) = extract_bilibili_cookies(&bilibili_cookie)?;
 
     // 2. 读取文件
-    const MAX_SIZE: u64 = 10 * 1024 * 1024; // 10MB
-    let (buffer, file_size) = read_file_bytes(&file_path, MAX_SIZE).await?;
-
-    // 3. 检查文件大小（哔哩哔哩限制 10MB）
-    if file_size > MAX_SIZE {
-        return Err(AppError::validation(format!(
-            "文件大小 ({:.2}MB) 超过哔哩哔哩限制 (10MB)",
-            file_size as f64 / 1024.0 / 1024.0
-        )));
-    }
+    let (buffer, file_size) =
+        read_file_bytes(&file_path, host_capabilities::read_limit("bilibili")).await?;
 
-    // 4. 获取文件名和扩展名
+    // 3. 获取文件名和扩展名
     let file_name = std::path::Path::new(&file_path)
         .file_name()
         .and_then(|n| n.to_str())
@@ -157,12 +172,8 @@ pub async fn upload_to_bilibili(
         .ok_or_else(|| AppError::validation("无法获取文件扩展名"))?
         .to_lowercase();
 
-    // 5. 验证文件类型
-    if !["jpg", "jpeg", "png", "gif", "webp"].contains(&ext.as_str()) {
-        return Err(AppError::validation(
-            "只支持 JPG、PNG、GIF、WebP 格式的图片",
-        ));
-    }
+    // 4. 按图床能力表校验文件类型与大小
+    host_capabilities::check_admission("bilibili", &ext, file_size)?;
 
     // 6. 确定 MIME 类型
     let mime_type = match ext.as_str() {
@@ -189,6 +200,7 @@ pub async fn upload_to_bilibili(
         .header("Referer", "https://mall.bilibili.com/")
         .header("Origin", "https://mall.bilibili.com")
         .header("User-Agent", "Mozilla/5.0 (Windows NT 10.0; Win64; x64) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/142.0.0.0 Safari/537.36")
+        .with_request_headers(&resolve_request_headers(request_headers.as_ref()))
         .multipart(form)
         .timeout(std::time::Duration::from_secs(30))
         .send()
@@ -0,0 +1,288 @@
+// src-tauri/src/commands/cache_manager.rs
+// 缓存管理：统计并清理应用占用的磁盘空间（缩略图、临时文件、下载缓存、日志）
+//
+// 设置页「存储」分区调用 get_cache_usage 展示各类占用，用户点清理时调用 clear_cache(kind)，
+// 不必再去 AppData / Library 里手动翻找。
+//
+// - thumbnails：缓存目录下的 thumbnails（与 clear_thumbnail_cache 相同，同时清空内存预览）
+// - temp：应用临时目录中除下载外的文件（剪贴板图片、二维码等）与压缩产物目录
+// - downloads：应用临时目录中 URL 下载与失效图片重新上传的下载文件
+// - logs：日志目录中的日志文件（保留正在写入的最新一个）
+//
+// 临时文件可能还在上传队列中等待使用：被任务引用的文件和最近修改过的文件一律保留。
+
+use std::ffi::OsStr;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime};
+
+use serde::{Deserialize, Serialize};
+use tauri::Manager;
+
+use super::link_checker::{TEMP_FILE_PREFIX, URL_DOWNLOAD_PREFIX};
+use super::{tempfiles, thumbnail_cache};
+use crate::error::AppError;
+
+/// 压缩产物目录名（位于系统临时目录下，见 image_compress）
+const COMPRESS_DIR_NAME: &str = "picnexus_compress";
+
+/// 最近修改过的临时文件视为仍在使用，手动清理时保留
+const IN_USE_GRACE: Duration = Duration::from_secs(10 * 60);
+
+/// 缓存类别
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum CacheKind {
+    Thumbnails,
+    Temp,
+    Downloads,
+    Logs,
+}
+
+impl CacheKind {
+    const ALL: [CacheKind; 4] = [
+        CacheKind::Thumbnails,
+        CacheKind::Temp,
+        CacheKind::Downloads,
+        CacheKind::Logs,
+    ];
+}
+
+/// 单个类别的占用
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CacheUsage {
+    pub kind: CacheKind,
+    /// 所在目录（供「在文件管理器中显示」）；目录不可用时为 None
+    pub path: Option<String>,
+    pub files: usize,
+    pub bytes: u64,
+}
+
+/// clear_cache 的返回值
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ClearedCache {
+    pub kind: CacheKind,
+    pub files: usize,
+    pub freed_bytes: u64,
+}
+
+fn is_download(name: &OsStr) -> bool {
+    let name = name.to_string_lossy();
+    name.starts_with(URL_DOWNLOAD_PREFIX) || name.starts_with(TEMP_FILE_PREFIX)
+}
+
+/// 与启动时的过期日志清理使用相同规则：*.log 与轮转后的 *.log.*
+fn is_log_file(path: &Path) -> bool {
+    path.extension().and_then(|e| e.to_str()) == Some("log")
+        || path.to_string_lossy().contains(".log.")
+}
+
+/// 目录中（不含子目录）满足条件的文件，附带元数据
+fn list_files(dir: &Path, filter: impl Fn(&Path) -> bool) -> Vec<(PathBuf, std::fs::Metadata)> {
+    std::fs::read_dir(dir)
+        .into_iter()
+        .flatten()
+        .flatten()
+        .filter_map(|entry| {
+            let path = entry.path();
+            let metadata = entry.metadata().ok()?;
+            (metadata.is_file() && filter(&path)).then_some((path, metadata))
+        })
+        .collect()
+}
+
+fn total(files: &[(PathBuf, std::fs::Metadata)]) -> (usize, u64) {
+    (files.len(), files.iter().map(|(_, m)| m.len()).sum())
+}
+
+/// 删除文件，返回 (删除数, 释放字节)
+fn remove_all(files: Vec<(PathBuf, std::fs::Metadata)>) -> (usize, u64) {
+    files
+        .into_iter()
+        .filter(|(path, _)| std::fs::remove_file(path).is_ok())
+        .fold((0, 0), |(count, bytes), (_, m)| {
+            (count + 1, bytes + m.len())
+        })
+}
+
+/// 日志文件中最近修改的一个（正在写入，不删除）
+fn newest(files: &[(PathBuf, std::fs::Metadata)]) -> Option<PathBuf> {
+    files
+        .iter()
+        .max_by_key(|(_, m)| m.modified().unwrap_or(SystemTime::UNIX_EPOCH))
+        .map(|(path, _)| path.clone())
+}
+
+fn compress_dir(app: &tauri::AppHandle) -> Option<PathBuf> {
+    app.path()
+        .temp_dir()
+        .ok()
+        .map(|dir| dir.join(COMPRESS_DIR_NAME))
+}
+
+fn usage_of(app: &tauri::AppHandle, kind: CacheKind) -> CacheUsage {
+    let (dir, (files, bytes)) = match kind {
+        CacheKind::Thumbnails => {
+            let dir = thumbnail_cache::cache_dir().map(Path::to_path_buf);
+            let usage = dir
+                .as_deref()
+                .map_or((0, 0), |d| total(&list_files(d, |_| true)));
+            (dir, usage)
+        }
+        CacheKind::Temp => {
+            let root = tempfiles::root();
+            let (mut files, mut bytes) = total(&list_files(root, |p| {
+                p.file_name().is_some_and(|n| !is_download(n))
+            }));
+            if let Some(dir) = compress_dir(app) {
+                let (f, b) = total(&list_files(&dir, |_| true));
+                files += f;
+                bytes += b;
+            }
+            (Some(root.to_path_buf()), (files, bytes))
+        }
+        CacheKind::Downloads => {
+            let root = tempfiles::root();
+            let usage = total(&list_files(root, |p| {
+                p.file_name().is_some_and(is_download)
+            }));
+            (Some(root.to_path_buf()), usage)
+        }
+        CacheKind::Logs => {
+            let dir = crate::portable::log_dir(app).ok();
+            let usage = dir
+                .as_deref()
+                .map_or((0, 0), |d| total(&list_files(d, is_log_file)));
+            (dir, usage)
+        }
+    };
+    CacheUsage {
+        kind,
+        path: dir.map(|d| d.to_string_lossy().to_string()),
+        files,
+        bytes,
+    }
+}
+
+fn clear_blocking(app: &tauri::AppHandle, kind: CacheKind) -> (usize, u64) {
+    match kind {
+        // 缩略图单独处理（需要同步重置 LRU 索引）
+        CacheKind::Thumbnails => (0, 0),
+        CacheKind::Temp => {
+            let (mut files, mut bytes) = tempfiles::clear(|n| !is_download(n), IN_USE_GRACE);
+            if let Some(dir) = compress_dir(app) {
+                let cutoff = SystemTime::now() - IN_USE_GRACE;
+                let settled = list_files(&dir, |_| true)
+                    .into_iter()
+                    .filter(|(_, m)| m.modified().is_ok_and(|t| t <= cutoff))
+                    .collect();
+                let (f, b) = remove_all(settled);
+                files += f;
+                bytes += b;
+            }
+            (files, bytes)
+        }
+        CacheKind::Downloads => tempfiles::clear(is_download, IN_USE_GRACE),
+        CacheKind::Logs => {
+            let Ok(dir) = crate::portable::log_dir(app) else {
+                return (0, 0);
+            };
+            let mut logs = list_files(&dir, is_log_file);
+            if let Some(active) = newest(&logs) {
+                logs.retain(|(path, _)| *path != active);
+            }
+            remove_all(logs)
+        }
+    }
+}
+
+/// 各类缓存的磁盘占用
+#[tauri::command]
+pub async fn get_cache_usage(app: tauri::AppHandle) -> Result<Vec<CacheUsage>, AppError> {
+    tokio::task::spawn_blocking(move || {
+        CacheKind::ALL
+            .into_iter()
+            .map(|kind| usage_of(&app, kind))
+            .collect()
+    })
+    .await
+    .map_err(|e| AppError::file_io(format!("统计缓存占用失败: {}", e)))
+}
+
+/// 清理指定类别的缓存，返回删除的文件数与释放的字节数
+#[tauri::command]
+pub async fn clear_cache(app: tauri::AppHandle, kind: CacheKind) -> Result<ClearedCache, AppError> {
+    let (files, freed_bytes) = if kind == CacheKind::Thumbnails {
+        let before = usage_of(&app, kind).files;
+        (before, thumbnail_cache::clear().await?)
+    } else {
+        tokio::task::spawn_blocking(move || clear_blocking(&app, kind))
+            .await
+            .map_err(|e| AppError::file_io(format!("清理缓存失败: {}", e)))?
+    };
+    log::info!(
+        "[缓存管理] 已清理 {:?}：{} 个文件，{} 字节",
+        kind,
+        files,
+        freed_bytes
+    );
+    Ok(ClearedCache {
+        kind,
+        files,
+        freed_bytes,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn classifies_download_and_log_files() {
+        assert!(is_download(OsStr::new("picnexus_url_abc.png")));
+        assert!(is_download(OsStr::new("weibo_reupload_abc.part")));
+        assert!(!is_download(OsStr::new("clipboard_image_abc.png")));
+
+        assert!(is_log_file(Path::new("/logs/picnexus.log")));
+        assert!(is_log_file(Path::new("/logs/picnexus.log.2026-10-01")));
+        assert!(!is_log_file(Path::new("/logs/crash-20261001.txt")));
+    }
+
+    #[test]
+    fn remove_all_keeps_newest_log() {
+        let dir = std::env::temp_dir().join(format!("picnexus_cache_test_{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        std::fs::write(dir.join("app.log.1"), b"old").unwrap();
+        std::fs::write(dir.join("note.txt"), b"keep").unwrap();
+        let old = std::fs::File::options()
+            .write(true)
+            .open(dir.join("app.log.1"))
+            .unwrap();
+        old.set_modified(SystemTime::now() - Duration::from_secs(3600))
+            .unwrap();
+        std::fs::write(dir.join("app.log"), b"active").unwrap();
+
+        let mut logs = list_files(&dir, is_log_file);
+        assert_eq!(total(&logs), (2, 9));
+        let active = newest(&logs).unwrap();
+        assert!(active.ends_with("app.log"));
+        logs.retain(|(path, _)| *path != active);
+        assert_eq!(remove_all(logs), (1, 3));
+
+        assert!(dir.join("app.log").exists());
+        assert!(dir.join("note.txt").exists());
+        assert!(!dir.join("app.log.1").exists());
+        let _ = std::fs::remove_dir_all(dir);
+    }
+
+    #[test]
+    fn kinds_serialize_lowercase() {
+        assert_eq!(
+            serde_json::to_value(CacheKind::ALL).unwrap(),
+            serde_json::json!(["thumbnails", "temp", "downloads", "logs"])
+        );
+        let kind: CacheKind = serde_json::from_str("\"downloads\"").unwrap();
+        assert_eq!(kind, CacheKind::Downloads);
+    }
+}
@@ -5,7 +5,11 @@ use reqwest::multipart;
 use serde::{Deserialize, Serialize};
 use tauri::Window;
 
-use super::utils::read_file_bytes;
+use super::host_capabilities;
+use super::net_limiter::NetworkLimiter;
+use super::request_headers::{resolve_request_headers, RequestHeaderTemplate, RequestHeadersExt};
+use super::task_registry::TaskRegistry;
+use super::utils::{read_file_bytes, remember_uploaded_source};
 use crate::error::{AppError, IntoAppError};
 use crate::log_utils::{safe_path, safe_url, summarize_text};
 
@@ -101,10 +105,29 @@ pub async fn test_chaoxing_connection(chaoxing_cookie: String) -> Result<String,
 #[tauri::command]
 pub async fn upload_to_chaoxing(
     _window: Window,
-    _id: String,
+    id: String,
     file_path: String,
     chaoxing_cookie: String,
+    request_headers: Option<RequestHeaderTemplate>,
+    limiter: tauri::State<'_, NetworkLimiter>,
+    tasks: tauri::State<'_, TaskRegistry>,
 ) -> Result<ChaoxingUploadResult, AppError> {
+    tasks
+        .run(
+            Some(&id),
+            upload_to_chaoxing_inner(file_path.clone(), chaoxing_cookie, request_headers, limiter),
+        )
+        .await
+        .inspect(|_| remember_uploaded_source(&file_path))
+}
+
+async fn upload_to_chaoxing_inner(
+    file_path: String,
+    chaoxing_cookie: String,
+    request_headers: Option<RequestHeaderTemplate>,
+    limiter: tauri::State<'_, NetworkLimiter>,
+) -> Result<ChaoxingUploadResult, AppError> {
+    let _permit = limiter.acquire("chaoxing").await;
     log::info!("[Chaoxing] 开始上传文件: {}", safe_path(&file_path));
 
     // 1. 检查 Cookie
@@ -113,18 +136,10 @@ pub async fn upload_to_chaoxing(
     }
 
     // 2. 读取文件
-    const MAX_SIZE: u64 = 200 * 1024 * 1024; // 200MB
-    let (buffer, file_size) = read_file_bytes(&file_path, MAX_SIZE).await?;
-
-    // 3. 检查文件大小（超星限制 200MB）
-    if file_size > MAX_SIZE {
-        return Err(AppError::validation(format!(
-            "文件大小 ({:.2}MB) 超过超星限制 (200MB)",
-            file_size as f64 / 1024.0 / 1024.0
-        )));
-    }
+    let (buffer, file_size) =
+        read_file_bytes(&file_path, host_capabilities::read_limit("chaoxing")).await?;
 
-    // 4. 获取文件名和扩展名
+    // 3. 获取文件名和扩展名
     let file_name = std::path::Path::new(&file_path)
         .file_name()
         .and_then(|n| n.to_str())
@@ -136,12 +151,8 @@ pub async fn upload_to_chaoxing(
         .ok_or_else(|| AppError::validation("无法获取文件扩展名"))?
         .to_lowercase();
 
-    // 5. 验证文件类型
-    if !["jpg", "jpeg", "png", "gif", "webp", "bmp"].contains(&ext.as_str()) {
-        return Err(AppError::validation(
-            "只支持 JPG、PNG、GIF、WebP、BMP 格式的图片",
-        ));
-    }
+    // 4. 按图床能力表校验文件类型与大小
+    host_capabilities::check_admission("chaoxing", &ext, file_size)?;
 
     // 6. 确定 MIME 类型
     let mime_type = match ext.as_str() {
@@ -154,7 +165,7 @@ pub async fn upload_to_chaoxing(
     };
 
     // 7. 构建 multipart form（超星使用 attrFile 作为字段名）
-    let part = multipart::Part::bytes(buffer)
+    let part = multipart::Part::stream_with_length(buffer, file_size)
         .file_name(file_name.to_string())
         .mime_str(mime_type)
         .into_validation_err_with("无法设置 MIME 类型")?;
@@ -169,6 +180,7 @@ pub async fn upload_to_chaoxing(
         .header("Referer", "https://notice.chaoxing.com/")
         .header("Origin", "https://notice.chaoxing.com")
         .header("User-Agent", "Mozilla/5.0 (Windows NT 10.0; Win64; x64) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/142.0.0.0 Safari/537.36")
+        .with_request_headers(&resolve_request_headers(request_headers.as_ref()))
         .multipart(form)
         .timeout(std::time::Duration::from_secs(120))
         .send()
@@ -1,4 +1,5 @@
 use crate::error::AppError;
+use crate::path_utils;
 use serde::Serialize;
 use std::path::PathBuf;
 
@@ -257,8 +258,8 @@ mod unix_impl {
         };
 
         match (
-            std::fs::canonicalize(absolute_target),
-            std::fs::canonicalize(source),
+            path_utils::canonicalize(absolute_target),
+            path_utils::canonicalize(source),
         ) {
             (Ok(target), Ok(source)) => target == source,
             _ => false,
@@ -2,22 +2,130 @@
 // 剪贴板图片处理命令
 // v2.10: 迁移到 AppError 统一错误类型
 
-use arboard::Clipboard;
+use arboard::{Clipboard, ImageData};
+use bytes::Bytes;
 use image::ImageFormat;
+use std::collections::VecDeque;
 use std::io::Cursor;
 use std::path::Path;
-use std::sync::atomic::{AtomicU32, Ordering};
+use std::sync::{Arc, LazyLock, Mutex};
+use tauri::State;
 
+use super::memory_budget::{self, MemoryReservation};
+use super::{cpu_pool, tempfiles};
 use crate::error::AppError;
 use crate::log_utils::safe_path;
 
-const CLIPBOARD_TEMP_PREFIX: &str = "clipboard_image_";
+pub(super) const CLIPBOARD_TEMP_PREFIX: &str = "clipboard_image_";
 const CLIPBOARD_TEMP_EXTENSION: &str = "png";
 
-/// 剪贴板临时文件序号计数器
-/// Why: 毫秒级时间戳在用户高频粘贴/脚本驱动场景可能撞名（同毫秒生成同名文件后
-/// 写者覆盖前写者），与 image_compress.rs / link_checker.rs 的处理方式对齐。
-static CLIPBOARD_TEMP_COUNTER: AtomicU32 = AtomicU32::new(0);
+/// 保留内存副本的剪贴板临时文件数量上限（上传失败待重试时前端会保留临时文件）
+const HANDOFF_CAPACITY: usize = 8;
+
+/// 内存副本的总字节上限；超过时先淘汰最早的副本，单张超过上限的图片不保留副本
+const HANDOFF_MAX_BYTES: usize = 64 * 1024 * 1024;
+
+/// 一份剪贴板 PNG 的内存副本，连同它占用的内存预算
+struct HandoffEntry {
+    path: String,
+    bytes: Bytes,
+    _reservation: MemoryReservation<'static>,
+}
+
+/// 剪贴板临时文件路径 → 已编码的 PNG 字节
+///
+/// 粘贴后通常立即上传：上传命令按路径读取时直接取内存副本，省去一次重新读盘。
+/// 临时文件照常写入，供预览、重试与历史记录使用；临时文件被删除时副本一并丢弃。
+static CLIPBOARD_HANDOFF: LazyLock<Mutex<VecDeque<HandoffEntry>>> =
+    LazyLock::new(|| Mutex::new(VecDeque::new()));
+
+fn remember_handoff(path: String, bytes: Bytes) {
+    if bytes.len() > HANDOFF_MAX_BYTES {
+        return;
+    }
+    // 副本可以丢弃：内存预算紧张时不保留，上传时照常读盘
+    let Some(reservation) = memory_budget::reserve_for_cache(bytes.len() as u64) else {
+        log::debug!("[剪贴板] 内存预算不足，不保留图片副本");
+        return;
+    };
+    let mut handoff = CLIPBOARD_HANDOFF.lock().unwrap_or_else(|e| e.into_inner());
+    handoff.push_back(HandoffEntry {
+        path,
+        bytes,
+        _reservation: reservation,
+    });
+    let mut total: usize = handoff.iter().map(|entry| entry.bytes.len()).sum();
+    while handoff.len() > HANDOFF_CAPACITY || total > HANDOFF_MAX_BYTES {
+        let Some(evicted) = handoff.pop_front() else {
+            break;
+        };
+        total -= evicted.bytes.len();
+    }
+}
+
+/// 丢弃临时文件的内存副本（临时文件被删除时调用）
+pub(super) fn forget_handoff(path: &str) {
+    CLIPBOARD_HANDOFF
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+        .retain(|entry| entry.path != path);
+}
+
+/// 取剪贴板临时文件的内存副本（共享同一块内存，不复制）；不是本进程刚写入的剪贴板临时文件时返回 None
+pub(crate) fn handoff_bytes(path: &str) -> Option<Bytes> {
+    CLIPBOARD_HANDOFF
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+        .iter()
+        .find(|entry| entry.path == path)
+        .map(|entry| entry.bytes.clone())
+}
+
+/// 复用的剪贴板句柄（注册为 Tauri managed state，本地 Server 共享同一实例）
+///
+/// `Clipboard::new()` 在 Linux 上要建立 X11/Wayland 连接、在 macOS 上要获取 NSPasteboard，
+/// 高频按粘贴上传快捷键时每次新建会有明显延迟；句柄首次使用时创建，出错后丢弃并重连一次。
+#[derive(Clone, Default)]
+pub struct SharedClipboard(Arc<Mutex<Option<Clipboard>>>);
+
+impl SharedClipboard {
+    fn connect(slot: &mut Option<Clipboard>) -> Result<&mut Clipboard, arboard::Error> {
+        Ok(match slot {
+            Some(clipboard) => clipboard,
+            None => slot.insert(Clipboard::new()?),
+        })
+    }
+
+    /// 读取剪贴板内容；连接类错误时重建句柄后重试一次
+    fn read<T>(
+        &self,
+        op: impl Fn(&mut Clipboard) -> Result<T, arboard::Error>,
+    ) -> Result<T, arboard::Error> {
+        let mut slot = self.0.lock().unwrap_or_else(|e| e.into_inner());
+        match Self::connect(&mut slot).and_then(&op) {
+            // 内容类型不符 / 格式转换失败与句柄无关，直接返回
+            Err(e @ (arboard::Error::ContentNotAvailable | arboard::Error::ConversionFailure)) => {
+                Err(e)
+            }
+            Err(e) => {
+                log::warn!("[剪贴板] 句柄不可用，重新连接: {}", e);
+                *slot = None;
+                op(Self::connect(&mut slot)?)
+            }
+            ok => ok,
+        }
+    }
+
+    /// 读取剪贴板图片
+    pub(crate) fn get_image(&self) -> Result<ImageData<'static>, arboard::Error> {
+        self.read(|clipboard| clipboard.get_image())
+    }
+
+    /// 读取剪贴板文本
+    pub(crate) fn get_text(&self) -> Result<String, arboard::Error> {
+        self.read(|clipboard| clipboard.get_text())
+    }
+}
 
 fn is_clipboard_temp_path(path: &Path) -> bool {
     let Some(file_name) = path.file_name().and_then(|n| n.to_str()) else {
@@ -29,74 +137,49 @@ fn is_clipboard_temp_path(path: &Path) -> bool {
     let Some(ext) = path.extension().and_then(|e| e.to_str()) else {
         return false;
     };
-    if !ext.eq_ignore_ascii_case(CLIPBOARD_TEMP_EXTENSION) {
-        return false;
-    }
-
-    let Some(parent) = path.parent() else {
-        return false;
-    };
-    let Ok(temp_dir) = std::env::temp_dir().canonicalize() else {
-        return false;
-    };
-    let Ok(parent_dir) = parent.canonicalize() else {
-        return false;
-    };
-    parent_dir == temp_dir
+    ext.eq_ignore_ascii_case(CLIPBOARD_TEMP_EXTENSION) && tempfiles::is_managed(path)
 }
 
 /// 安全清理本次剪贴板图片临时文件。
 ///
-/// 只允许删除系统临时目录下由 read_clipboard_image 创建的
+/// 只允许删除应用临时目录下由 read_clipboard_image 创建的
 /// clipboard_image_*.png，避免前端传入任意路径造成误删。
 #[tauri::command]
 pub fn cleanup_clipboard_temp_file(path: String) -> Result<bool, AppError> {
+    forget_handoff(&path);
     let path = Path::new(&path);
     if !is_clipboard_temp_path(path) {
         return Err(AppError::validation(
             "只允许清理 PicNexus 创建的剪贴板临时图片",
         ));
     }
-    if !path.exists() {
-        return Ok(false);
-    }
-
-    std::fs::remove_file(path).map_err(|e| {
+    tempfiles::release(path).inspect_err(|e| {
         log::warn!(
             "[剪贴板] 删除临时文件失败 {}: {}",
             safe_path(&path.to_string_lossy()),
             e
         );
-        AppError::file_io(format!("删除剪贴板临时文件失败: {}", e))
-    })?;
-    Ok(true)
+    })
 }
 
 /// 检查剪贴板是否包含图片
+///
+/// arboard 只能通过完整读取图片来判断，大图转换 RGBA 较慢，放到 CPU 线程池中执行
 #[tauri::command]
-pub fn clipboard_has_image() -> Result<bool, AppError> {
-    let mut clipboard =
-        Clipboard::new().map_err(|e| AppError::clipboard(format!("无法访问剪贴板: {}", e)))?;
-
-    match clipboard.get_image() {
+pub async fn clipboard_has_image(clipboard: State<'_, SharedClipboard>) -> Result<bool, AppError> {
+    let clipboard = clipboard.inner().clone();
+    cpu_pool::spawn(move || match clipboard.get_image() {
         Ok(_) => Ok(true),
         Err(arboard::Error::ContentNotAvailable) => Ok(false),
         Err(e) => Err(AppError::clipboard(format!("检查剪贴板失败: {}", e))),
-    }
+    })
+    .await?
 }
 
-/// 从剪贴板读取图片并保存为临时文件
+/// 读取剪贴板图片并编码为 PNG 字节（read_clipboard_image 与本地 Server 共用）
 ///
-/// # 返回
-/// 返回临时文件的完整路径
-#[tauri::command]
-pub fn read_clipboard_image() -> Result<String, AppError> {
-    log::info!("[剪贴板] 正在读取剪贴板图片...");
-
-    // 获取剪贴板访问
-    let mut clipboard =
-        Clipboard::new().map_err(|e| AppError::clipboard(format!("无法访问剪贴板: {}", e)))?;
-
+/// 阻塞且 CPU 密集，调用方需放到 cpu_pool 中执行
+pub(crate) fn read_clipboard_png(clipboard: &SharedClipboard) -> Result<Vec<u8>, AppError> {
     // 读取图片数据
     let image_data = clipboard.get_image().map_err(|e| match e {
         arboard::Error::ContentNotAvailable => AppError::clipboard("剪贴板中没有图片"),
@@ -125,26 +208,32 @@ pub fn read_clipboard_image() -> Result<String, AppError> {
 
     let png_bytes = png_data.into_inner();
     log::debug!("[剪贴板] PNG 编码完成，大小: {} bytes", png_bytes.len());
+    Ok(png_bytes)
+}
 
-    // 创建临时文件路径（拼接原子序号消除同毫秒撞名）
-    let temp_dir = std::env::temp_dir();
-    let seq = CLIPBOARD_TEMP_COUNTER.fetch_add(1, Ordering::Relaxed);
-    let file_name = format!(
-        "{}{}_{}.{}",
-        CLIPBOARD_TEMP_PREFIX,
-        chrono::Local::now().format("%Y%m%d_%H%M%S_%3f"),
-        seq,
-        CLIPBOARD_TEMP_EXTENSION
-    );
-    let temp_path = temp_dir.join(&file_name);
+/// 从剪贴板读取图片并保存为临时文件
+///
+/// 读取与 PNG 编码在 CPU 线程池中进行，大图编码不会占用异步运行时
+///
+/// # 返回
+/// 返回临时文件的完整路径
+#[tauri::command]
+pub async fn read_clipboard_image(
+    clipboard: State<'_, SharedClipboard>,
+) -> Result<String, AppError> {
+    log::info!("[剪贴板] 正在读取剪贴板图片...");
 
-    // 写入文件
-    std::fs::write(&temp_path, png_bytes).map_err(|e| {
-        log::error!("[剪贴板] 写入临时文件失败: {}", e);
-        AppError::file_io(format!("写入临时文件失败: {}", e))
-    })?;
+    let clipboard = clipboard.inner().clone();
+    let png_bytes = cpu_pool::spawn(move || read_clipboard_png(&clipboard)).await??;
 
-    let path_str = temp_path.to_string_lossy().to_string();
+    // 写入应用临时目录，交给前端后由 cleanup_clipboard_temp_file 清理
+    let png_bytes = Bytes::from(png_bytes);
+    let temp_file = tempfiles::write(CLIPBOARD_TEMP_PREFIX, CLIPBOARD_TEMP_EXTENSION, &png_bytes)
+        .await
+        .inspect_err(|e| log::error!("[剪贴板] 写入临时文件失败: {}", e))?;
+
+    let path_str = temp_file.keep().to_string_lossy().to_string();
+    remember_handoff(path_str.clone(), png_bytes);
     log::info!("[剪贴板] 图片已保存到临时文件: {}", safe_path(&path_str));
 
     Ok(path_str)
@@ -152,17 +241,67 @@ pub fn read_clipboard_image() -> Result<String, AppError> {
 
 #[cfg(test)]
 mod tests {
-    use super::{cleanup_clipboard_temp_file, CLIPBOARD_TEMP_PREFIX};
+    use super::{
+        cleanup_clipboard_temp_file, forget_handoff, handoff_bytes, remember_handoff, tempfiles,
+        CLIPBOARD_TEMP_PREFIX, HANDOFF_CAPACITY, HANDOFF_MAX_BYTES,
+    };
+    use bytes::Bytes;
+    use std::time::Duration;
 
+    // 副本表是进程级全局状态，数量与字节上限放在同一个测试里顺序验证
     #[test]
-    fn cleanup_clipboard_temp_file_removes_only_owned_temp_png() {
-        let path = std::env::temp_dir().join(format!(
-            "{}test_{}_{}.png",
+    fn handoff_is_bounded_by_count_and_bytes() {
+        let key = |i: usize| {
+            format!(
+                "/tmp/{}handoff_{}_{}.png",
+                CLIPBOARD_TEMP_PREFIX,
+                std::process::id(),
+                i
+            )
+        };
+        for i in 0..=HANDOFF_CAPACITY {
+            remember_handoff(key(i), Bytes::from(vec![i as u8]));
+        }
+
+        assert!(handoff_bytes(&key(0)).is_none());
+        assert_eq!(handoff_bytes(&key(1)).as_deref(), Some(&[1u8][..]));
+
+        forget_handoff(&key(1));
+        assert!(handoff_bytes(&key(1)).is_none());
+
+        let half = HANDOFF_MAX_BYTES / 2 + 1;
+        remember_handoff(key(100), Bytes::from(vec![0u8; half]));
+        remember_handoff(key(101), Bytes::from(vec![1u8; half]));
+        assert!(handoff_bytes(&key(100)).is_none());
+        assert_eq!(handoff_bytes(&key(101)).map(|b| b.len()), Some(half));
+
+        remember_handoff(key(102), Bytes::from(vec![0u8; HANDOFF_MAX_BYTES + 1]));
+        assert!(handoff_bytes(&key(102)).is_none());
+        forget_handoff(&key(101));
+
+        // 临时文件被清理时副本一并丢弃
+        let name = format!(
+            "{}handoff_{}.png",
             CLIPBOARD_TEMP_PREFIX,
-            std::process::id(),
-            chrono::Utc::now().timestamp_millis()
-        ));
+            std::process::id()
+        );
+        let path = tempfiles::root().join(&name);
+        std::fs::create_dir_all(tempfiles::root()).expect("temp root should be creatable");
         std::fs::write(&path, b"temp").expect("test temp file should be writable");
+        let path_str = path.to_string_lossy().to_string();
+        remember_handoff(path_str.clone(), Bytes::from_static(b"temp"));
+
+        let (removed, _) = tempfiles::clear(|n| n == name.as_str(), Duration::ZERO);
+        assert_eq!(removed, 1);
+        assert!(handoff_bytes(&path_str).is_none());
+    }
+
+    #[tokio::test]
+    async fn cleanup_clipboard_temp_file_removes_only_owned_temp_png() {
+        let path = tempfiles::write(CLIPBOARD_TEMP_PREFIX, "png", b"temp")
+            .await
+            .expect("test temp file should be writable")
+            .keep();
 
         let removed = cleanup_clipboard_temp_file(path.to_string_lossy().to_string())
             .expect("owned clipboard temp file should be removable");
@@ -173,7 +312,8 @@ mod tests {
 
     #[test]
     fn cleanup_clipboard_temp_file_rejects_unowned_path() {
-        let path = std::env::temp_dir().join("not_clipboard_image.png");
+        // 前缀正确但不在应用临时目录中
+        let path = std::env::temp_dir().join(format!("{}unowned.png", CLIPBOARD_TEMP_PREFIX));
         std::fs::write(&path, b"keep").expect("test temp file should be writable");
 
         let result = cleanup_clipboard_temp_file(path.to_string_lossy().to_string());
@@ -0,0 +1,156 @@
+// src-tauri/src/commands/clipboard_rehost.rs
+// 剪贴板链接转存：读取剪贴板中的图片链接，下载后上传到自己的图床并返回新链接
+//
+// 工作流：
+//   rehost_clipboard_url(service?) -> 读取剪贴板文本 -> 提取图片链接
+//   -> download_url_image_to_temp（仅公网、非网页内容）-> 按 cli-config.json 中的图床上传
+//   -> 返回 { sourceUrl, service, url }
+//
+// 剪贴板内容支持裸链接、Markdown 图片语法与 <img src="..."> 片段（从网页 / 编辑器复制时常见）。
+// 与浏览器扩展的转存一样，下载 + 上传共用 REHOST_DEADLINE 总时限；
+// 复制新链接、写入历史记录由前端按链接输出设置处理。
+
+use serde::Serialize;
+use tauri::State;
+use url::Url;
+
+use super::clipboard::SharedClipboard;
+use super::deadline::{Deadline, REHOST_DEADLINE};
+use super::link_checker::download_url_image_to_temp;
+use super::net_limiter::NetworkLimiter;
+use crate::cli;
+use crate::error::AppError;
+use crate::log_utils::safe_url;
+use crate::server::upload_handler::upload_single_file;
+use crate::url_utils;
+
+/// 转存结果
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ClipboardRehostResult {
+    /// 剪贴板中的原链接（已规范化）
+    pub source_url: String,
+    /// 实际使用的图床 ID
+    pub service: String,
+    /// 上传后的新链接
+    pub url: String,
+}
+
+/// 从 Markdown 图片语法 `![alt](url "title")` 中取链接
+fn markdown_image_url(text: &str) -> Option<&str> {
+    let rest = &text[text.find("![")?..];
+    let start = rest.find("](")? + 2;
+    let inner = &rest[start..];
+    let inner = &inner[..inner.find(')')?];
+    inner.split_whitespace().next()
+}
+
+/// 从 HTML `<img src="...">` 片段中取链接
+fn html_image_src(text: &str) -> Option<&str> {
+    let lower = text.to_ascii_lowercase();
+    let tag = lower.find("<img")?;
+    let attr = tag + lower[tag..].find("src=")? + 4;
+    let rest = &text[attr..];
+    let quote = rest.chars().next().filter(|c| *c == '"' || *c == '\'')?;
+    let value = &rest[1..];
+    Some(&value[..value.find(quote)?])
+}
+
+/// 从剪贴板文本中提取图片链接
+fn extract_image_url(text: &str) -> Result<Url, AppError> {
+    let text = text.trim();
+    if text.is_empty() {
+        return Err(AppError::clipboard("剪贴板中没有链接"));
+    }
+    let candidate = markdown_image_url(text)
+        .or_else(|| html_image_src(text))
+        .unwrap_or(text);
+    url_utils::parse_and_normalize(candidate)
+        .map_err(|e| AppError::validation(format!("剪贴板内容不是有效的图片链接: {}", e)))
+}
+
+/// 读取剪贴板中的图片链接，转存到指定图床（缺省为 CLI 默认图床）并返回新链接
+#[tauri::command]
+pub async fn rehost_clipboard_url(
+    service: Option<String>,
+    clipboard: State<'_, SharedClipboard>,
+    limiter: State<'_, NetworkLimiter>,
+) -> Result<ClipboardRehostResult, AppError> {
+    let clipboard = clipboard.inner().clone();
+    let text = tokio::task::spawn_blocking(move || clipboard.get_text())
+        .await
+        .map_err(|e| AppError::clipboard(format!("读取剪贴板失败: {}", e)))?
+        .map_err(|e| match e {
+            arboard::Error::ContentNotAvailable => AppError::clipboard("剪贴板中没有链接"),
+            _ => AppError::clipboard(format!("读取剪贴板失败: {}", e)),
+        })?;
+
+    // 先校验链接，无效链接不必再读取图床配置
+    let source = extract_image_url(&text)?;
+    let (service_id, config) =
+        cli::resolve_service_config(service.as_deref()).map_err(AppError::config)?;
+    log::info!(
+        "[剪贴板转存] {} -> {}",
+        safe_url(source.as_str()),
+        service_id
+    );
+
+    let mut deadline = Deadline::new("转存剪贴板链接", REHOST_DEADLINE);
+    let downloaded = {
+        let _permit = deadline
+            .exclude(limiter.acquire(&url_utils::host_key(source.as_str())))
+            .await;
+        deadline
+            .run(download_url_image_to_temp(source.as_str()))
+            .await??
+    };
+
+    // 临时文件随 downloaded 一起 drop 删除
+    let _permit = deadline.exclude(limiter.acquire(&service_id)).await;
+    let url = deadline
+        .run(upload_single_file(&downloaded.file.path_string(), &config))
+        .await?
+        .map_err(|e| AppError::upload(service_id.as_str(), e))?;
+    log::info!("[剪贴板转存] 完成: {}", safe_url(&url));
+
+    Ok(ClipboardRehostResult {
+        source_url: source.to_string(),
+        service: service_id,
+        url,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn extracts_url_from_plain_markdown_and_html() {
+        assert_eq!(
+            extract_image_url("  https://example.com/a.png?utm_source=x  \n")
+                .unwrap()
+                .as_str(),
+            "https://example.com/a.png"
+        );
+        assert_eq!(
+            extract_image_url("![猫](https://example.com/cat.jpg \"标题\")")
+                .unwrap()
+                .as_str(),
+            "https://example.com/cat.jpg"
+        );
+        assert_eq!(
+            extract_image_url("<IMG alt='x' src='https://example.com/b.webp'>")
+                .unwrap()
+                .as_str(),
+            "https://example.com/b.webp"
+        );
+    }
+
+    #[test]
+    fn rejects_non_links() {
+        assert!(extract_image_url("").is_err());
+        assert!(extract_image_url("hello world").is_err());
+        assert!(extract_image_url("file:///etc/passwd").is_err());
+        assert!(extract_image_url("![broken](").is_err());
+    }
+}
@@ -0,0 +1,193 @@
+// src-tauri/src/commands/clock_skew.rs
+// S3 / OSS / COS 签名的时钟偏差校正
+//
+// S3 兼容存储的签名里带有请求时间，服务端只接受与自己时钟相差 15 分钟以内的请求。
+// 本机时钟漂移（虚拟机恢复快照、主板电池没电、手动改过时间）时，所有上传都会以
+// RequestTimeTooSkewed / SignatureDoesNotMatch 失败，错误信息还会误导用户去检查密钥。
+//
+// 签名错误时读取响应的 Date 头计算本机与服务器的时间差，超过容差就记下偏移量，
+// 之后所有 S3 客户端与 OSS 原生签名通过 `SkewAdjustedTime` 用校正后的时间签名，并重试一次；
+// 同时向前端发出 clock-skew-detected 事件，提示用户「系统时间偏差」。
+
+use std::future::Future;
+use std::sync::atomic::{AtomicI64, Ordering};
+use std::time::{Duration, SystemTime};
+
+use aws_sdk_s3::error::{ProvideErrorMetadata, SdkError};
+use aws_smithy_async::time::TimeSource;
+use serde::Serialize;
+use tauri::{AppHandle, Emitter};
+
+/// 时钟偏差事件名
+pub const CLOCK_SKEW_EVENT: &str = "clock-skew-detected";
+
+/// 时间差在此范围内不算时钟偏差（签名错误另有原因，如密钥填错）
+const SKEW_TOLERANCE_SECS: i64 = 60;
+
+/// 服务器时间 - 本机时间（秒），所有 S3 客户端共用
+static CLOCK_OFFSET_SECS: AtomicI64 = AtomicI64::new(0);
+
+/// clock-skew-detected 事件载荷
+#[derive(Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct ClockSkewDetected {
+    /// 服务器时间 - 本机时间（秒），正数表示本机时间偏慢
+    skew_seconds: i64,
+}
+
+/// 按服务器时间校正后的签名时间源
+#[derive(Debug)]
+pub struct SkewAdjustedTime;
+
+impl TimeSource for SkewAdjustedTime {
+    fn now(&self) -> SystemTime {
+        apply_offset(SystemTime::now(), CLOCK_OFFSET_SECS.load(Ordering::Relaxed))
+    }
+}
+
+fn apply_offset(now: SystemTime, offset_secs: i64) -> SystemTime {
+    let delta = Duration::from_secs(offset_secs.unsigned_abs());
+    if offset_secs >= 0 {
+        now + delta
+    } else {
+        now - delta
+    }
+}
+
+/// 可能由时钟偏差引起的签名错误码
+fn is_signature_error(code: Option<&str>) -> bool {
+    matches!(
+        code,
+        Some("RequestTimeTooSkewed" | "SignatureDoesNotMatch" | "RequestExpired")
+    )
+}
+
+/// 根据响应的 Date 头计算服务器与本机的时间差（秒），在容差内视为 0
+fn server_offset(date_header: &str, local: SystemTime) -> Option<i64> {
+    let server = chrono::DateTime::parse_from_rfc2822(date_header.trim()).ok()?;
+    let local = chrono::DateTime::<chrono::Utc>::from(local);
+    let offset = server.timestamp() - local.timestamp();
+    Some(if offset.abs() > SKEW_TOLERANCE_SECS {
+        offset
+    } else {
+        0
+    })
+}
+
+/// 检查 S3 错误是否由时钟偏差引起，是则记下新的偏移量并返回
+fn detect<E: ProvideErrorMetadata>(err: &SdkError<E>) -> Option<i64> {
+    let date = err.raw_response()?.headers().get("date");
+    detect_from_response(err.code(), date)
+}
+
+/// 按错误码与响应 Date 头判断签名错误是否由时钟偏差引起，是则记下新的偏移量并返回
+///
+/// 供不经过 AWS SDK、自行签名的请求（如阿里云 OSS）使用
+pub fn detect_from_response(code: Option<&str>, date_header: Option<&str>) -> Option<i64> {
+    if !is_signature_error(code) {
+        return None;
+    }
+    let offset = server_offset(date_header?, SystemTime::now())?;
+    // 已经按这个偏移量校正过仍然失败，说明不是时钟问题（如密钥错误），不再重试
+    let current = CLOCK_OFFSET_SECS.swap(offset, Ordering::Relaxed);
+    if (offset - current).abs() <= SKEW_TOLERANCE_SECS {
+        return None;
+    }
+    if offset == 0 {
+        log::info!("[时钟] 本机时间已恢复正常，取消签名时间校正");
+    } else {
+        log::warn!(
+            "[时钟] 本机时间与服务器相差 {} 秒，已按服务器时间校正签名",
+            offset
+        );
+    }
+    Some(offset)
+}
+
+/// 向前端发出时钟偏差提示；本机时间恢复正常（偏移归零）时不提示
+pub fn notify(app: Option<&AppHandle>, skew_seconds: i64) {
+    if let Some(app) = app.filter(|_| skew_seconds != 0) {
+        if let Err(e) = app.emit(CLOCK_SKEW_EVENT, ClockSkewDetected { skew_seconds }) {
+            log::warn!("[时钟] 发送时钟偏差事件失败: {}", e);
+        }
+    }
+}
+
+/// 发送 S3 请求，签名因时钟偏差失败时校正签名时间后重试一次
+///
+/// `send` 每次调用需构造新的请求（请求体只能消费一次）；
+/// 传入 `app` 时向前端发出时钟偏差提示
+pub async fn send_with_skew_retry<T, E, F, Fut>(
+    app: Option<&AppHandle>,
+    send: F,
+) -> Result<T, SdkError<E>>
+where
+    E: ProvideErrorMetadata,
+    F: Fn() -> Fut,
+    Fut: Future<Output = Result<T, SdkError<E>>>,
+{
+    let err = match send().await {
+        Ok(output) => return Ok(output),
+        Err(e) => e,
+    };
+    let Some(skew_seconds) = detect(&err) else {
+        return Err(err);
+    };
+    notify(app, skew_seconds);
+    send().await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn local_at(rfc2822: &str) -> SystemTime {
+        chrono::DateTime::parse_from_rfc2822(rfc2822)
+            .unwrap()
+            .into()
+    }
+
+    #[test]
+    fn server_offset_ignores_small_differences() {
+        let local = local_at("Sat, 17 Oct 2026 08:00:00 GMT");
+        assert_eq!(
+            server_offset("Sat, 17 Oct 2026 08:00:45 GMT", local),
+            Some(0)
+        );
+        assert_eq!(server_offset("not a date", local), None);
+    }
+
+    #[test]
+    fn server_offset_reports_skew_in_both_directions() {
+        let local = local_at("Sat, 17 Oct 2026 08:00:00 GMT");
+        assert_eq!(
+            server_offset("Sat, 17 Oct 2026 08:20:00 GMT", local),
+            Some(1200)
+        );
+        assert_eq!(
+            server_offset("Sat, 17 Oct 2026 07:00:00 GMT", local),
+            Some(-3600)
+        );
+    }
+
+    #[test]
+    fn only_signature_errors_are_treated_as_skew() {
+        assert!(is_signature_error(Some("RequestTimeTooSkewed")));
+        assert!(is_signature_error(Some("SignatureDoesNotMatch")));
+        assert!(!is_signature_error(Some("NoSuchBucket")));
+        assert!(!is_signature_error(None));
+        assert_eq!(
+            detect_from_response(Some("NoSuchBucket"), Some("Sat, 17 Oct 2026 08:00:00 GMT")),
+            None
+        );
+        assert_eq!(
+            detect_from_response(Some("RequestTimeTooSkewed"), None),
+            None
+        );
+        let now = local_at("Sat, 17 Oct 2026 08:00:00 GMT");
+        assert_eq!(
+            apply_offset(now, -30),
+            local_at("Sat, 17 Oct 2026 07:59:30 GMT")
+        );
+    }
+}
@@ -0,0 +1,404 @@
+// src-tauri/src/commands/connection_probe.rs
+// 图床连接测试：对已保存的图床配置做一次低成本的认证探测，返回耗时与具体失败原因
+//
+// 配置来自设置页保存时导出的 cli-config.json（与命令行 / 编辑器上传同源），
+// 探测方式按图床选择最便宜的一种：
+//   - 公开可用性：京东、七鱼只检测接口是否可用
+//   - 认证接口：GitHub / SM.MS / Imgur / 微博 / 知乎 / B 站等调用只读的账号或登录态接口
+//   - 写入测试：牛客没有只读接口，上传一张极小的测试图片
+//   - 存储桶：S3 类存储列出 1 个对象，又拍云列出存储桶根目录
+
+use std::time::{Duration, Instant};
+
+use serde::Serialize;
+use tauri::{AppHandle, Manager};
+
+use super::http_client::HttpClient;
+use super::s3_compatible::{self, S3TestConfig};
+use crate::error::{AppError, ErrorCode, IntoAppError};
+use crate::server::ServerUploadConfig;
+
+/// 单次探测的总时限（含牛客的测试上传）
+const PROBE_TIMEOUT: Duration = Duration::from_secs(20);
+
+/// 探测方式
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ProbeKind {
+    Availability,
+    Auth,
+    Write,
+    Bucket,
+}
+
+/// test_host_connection 的返回值；探测失败不返回 Err，而是填入失败原因
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ConnectionProbe {
+    pub profile_id: String,
+    pub ok: bool,
+    pub probe: ProbeKind,
+    pub latency_ms: u64,
+    /// 成功时为图床返回的提示，失败时为具体原因
+    pub message: String,
+    /// 失败时的错误码（E_AUTH、E_STORAGE、E_NETWORK_TIMEOUT 等）
+    pub error_code: Option<ErrorCode>,
+}
+
+fn probe_kind(config: &ServerUploadConfig) -> ProbeKind {
+    match config {
+        ServerUploadConfig::Jd | ServerUploadConfig::Qiyu => ProbeKind::Availability,
+        ServerUploadConfig::Nowcoder { .. } => ProbeKind::Write,
+        ServerUploadConfig::R2 { .. }
+        | ServerUploadConfig::Tencent { .. }
+        | ServerUploadConfig::Aliyun { .. }
+        | ServerUploadConfig::Qiniu { .. }
+        | ServerUploadConfig::Upyun { .. }
+        | ServerUploadConfig::CustomS3 { .. } => ProbeKind::Bucket,
+        _ => ProbeKind::Auth,
+    }
+}
+
+/// 按 HTTP 状态码归类认证接口的失败
+fn status_error(host: &str, status: reqwest::StatusCode) -> AppError {
+    match status.as_u16() {
+        401 | 403 => AppError::auth(format!("{} 认证失败：凭证无效或已过期", host)),
+        404 => AppError::config(format!("{} 资源不存在，请检查配置", host)),
+        429 => AppError::network(format!("{} 请求过于频繁，请稍后再试", host))
+            .with_code(ErrorCode::UploadRateLimited),
+        code => AppError::network(format!("{} 返回 HTTP {}", host, code)),
+    }
+}
+
+async fn probe_github(
+    client: &reqwest::Client,
+    token: &str,
+    owner: &str,
+    repo: &str,
+) -> Result<String, AppError> {
+    let response = client
+        .get(format!("https://api.github.com/repos/{}/{}", owner, repo))
+        .header("Authorization", format!("token {}", token))
+        .header("User-Agent", "PicNexus")
+        .header("Accept", "application/vnd.github.v3+json")
+        .send()
+        .await
+        .into_network_err_with("GitHub 请求失败")?;
+    let status = response.status();
+    if status == reqwest::StatusCode::NOT_FOUND {
+        return Err(AppError::config(format!(
+            "仓库 {}/{} 不存在或 Token 无权访问",
+            owner, repo
+        )));
+    }
+    if !status.is_success() {
+        return Err(status_error("GitHub", status));
+    }
+    let body: serde_json::Value = response
+        .json()
+        .await
+        .into_network_err_with("无法读取 GitHub 响应")?;
+    if body["permissions"]["push"] == false {
+        return Err(AppError::auth("Token 没有该仓库的写入权限"));
+    }
+    Ok("GitHub 连接成功！".to_string())
+}
+
+async fn probe_smms(client: &reqwest::Client, token: &str) -> Result<String, AppError> {
+    let response = client
+        .post("https://sm.ms/api/v2/profile")
+        .header("Authorization", token)
+        .send()
+        .await
+        .into_network_err_with("SM.MS 请求失败")?;
+    let status = response.status();
+    if !status.is_success() {
+        return Err(status_error("SM.MS", status));
+    }
+    let body: serde_json::Value = response
+        .json()
+        .await
+        .into_network_err_with("无法读取 SM.MS 响应")?;
+    if body["success"] != true {
+        let message = body["message"].as_str().unwrap_or("Token 无效");
+        return Err(AppError::auth(format!("SM.MS 认证失败：{}", message)));
+    }
+    Ok("SM.MS 连接成功！".to_string())
+}
+
+async fn probe_imgur(client: &reqwest::Client, client_id: &str) -> Result<String, AppError> {
+    let response = client
+        .get("https://api.imgur.com/3/credits")
+        .header("Authorization", format!("Client-ID {}", client_id))
+        .send()
+        .await
+        .into_network_err_with("Imgur 请求失败")?;
+    let status = response.status();
+    if !status.is_success() {
+        return Err(status_error("Imgur", status));
+    }
+    Ok("Imgur 连接成功！".to_string())
+}
+
+fn s3_config(config: &ServerUploadConfig) -> Option<(&'static str, S3TestConfig)> {
+    let some = |s: &String| Some(s.clone());
+    let probe = match config {
+        ServerUploadConfig::R2 {
+            account_id,
+            access_key_id,
+            secret_access_key,
+            bucket_name,
+            ..
+        } => (
+            "r2",
+            S3TestConfig {
+                account_id: some(account_id),
+                access_key_id: some(access_key_id),
+                secret_access_key: some(secret_access_key),
+                bucket_name: some(bucket_name),
+                ..Default::default()
+            },
+        ),
+        ServerUploadConfig::Tencent {
+            secret_id,
+            secret_key,
+            region,
+            bucket,
+            ..
+        } => (
+            "tencent",
+            S3TestConfig {
+                secret_id: some(secret_id),
+                secret_key: some(secret_key),
+                region: some(region),
+                bucket: some(bucket),
+                ..Default::default()
+            },
+        ),
+        ServerUploadConfig::Aliyun {
+            access_key_id,
+            access_key_secret,
+            region,
+            bucket,
+            ..
+        } => (
+            "aliyun",
+            S3TestConfig {
+                access_key_id: some(access_key_id),
+                access_key_secret: some(access_key_secret),
+                region: some(region),
+                bucket: some(bucket),
+                ..Default::default()
+            },
+        ),
+        ServerUploadConfig::Qiniu {
+            access_key,
+            secret_key,
+            region,
+            bucket,
+            ..
+        } => (
+            "qiniu",
+            S3TestConfig {
+                access_key: some(access_key),
+                secret_key: some(secret_key),
+                region: some(region),
+                bucket: some(bucket),
+                ..Default::default()
+            },
+        ),
+        ServerUploadConfig::Upyun {
+            operator,
+            password,
+            bucket,
+            ..
+        } => (
+            "upyun",
+            S3TestConfig {
+                operator: some(operator),
+                password: some(password),
+                bucket: some(bucket),
+                ..Default::default()
+            },
+        ),
+        ServerUploadConfig::CustomS3 {
+            endpoint,
+            access_key_id,
+            secret_access_key,
+            region,
+            bucket,
+            ..
+        } => (
+            "custom_s3",
+            S3TestConfig {
+                endpoint: some(endpoint),
+                access_key_id: some(access_key_id),
+                secret_access_key: some(secret_access_key),
+                region: some(region),
+                bucket: some(bucket),
+                ..Default::default()
+            },
+        ),
+        _ => return None,
+    };
+    Some(probe)
+}
+
+async fn probe(app: &AppHandle, config: ServerUploadConfig) -> Result<String, AppError> {
+    if let Some((service_id, s3)) = s3_config(&config) {
+        return s3_compatible::test_s3_connection(app.clone(), service_id.to_string(), s3).await;
+    }
+
+    let client = app.state::<HttpClient>().get();
+    match config {
+        ServerUploadConfig::Jd => super::jd::check_jd_available()
+            .await
+            .then(|| "京东图床可用".to_string())
+            .ok_or_else(|| AppError::network("京东图床暂不可用")),
+        ServerUploadConfig::Qiyu => super::qiyu_token::check_qiyu_available(app.clone())
+            .await
+            .then(|| "七鱼图床可用".to_string())
+            .ok_or_else(|| AppError::external("七鱼图床不可用，请确认已安装 Chrome 或 Edge")),
+        ServerUploadConfig::Github {
+            token, owner, repo, ..
+        } => probe_github(&client, &token, &owner, &repo).await,
+        ServerUploadConfig::Smms { token } => probe_smms(&client, &token).await,
+        ServerUploadConfig::Imgur { client_id } => probe_imgur(&client, &client_id).await,
+        ServerUploadConfig::Weibo { cookie } => {
+            let session =
+                super::upload::probe_weibo_session(cookie, app.state::<HttpClient>()).await?;
+            if session.logged_in {
+                Ok("微博 Cookie 有效".to_string())
+            } else {
+                Err(AppError::auth("微博 Cookie 已失效，请重新登录"))
+            }
+        }
+        ServerUploadConfig::Nowcoder { cookie } => {
+            super::nowcoder::test_nowcoder_cookie(cookie).await
+        }
+        ServerUploadConfig::Bilibili { cookie } => {
+            super::bilibili::test_bilibili_connection(cookie).await
+        }
+        ServerUploadConfig::Chaoxing { cookie } => {
+            super::chaoxing::test_chaoxing_connection(cookie).await
+        }
+        ServerUploadConfig::Zhihu { cookie, .. } => {
+            super::zhihu::test_zhihu_connection(cookie).await
+        }
+        ServerUploadConfig::Nami { cookie, auth_token } => {
+            super::nami::test_nami_connection(app.clone(), cookie, auth_token).await
+        }
+        // S3 类存储已在上面处理
+        _ => Err(AppError::config("该图床不支持连接测试")),
+    }
+}
+
+/// 测试已保存的图床配置能否连通
+///
+/// `profile_id` 为图床 ID（自定义 S3 为 `custom_s3:<id>`）；配置不存在时返回 Err，
+/// 探测失败时返回 ok = false 与具体原因
+#[tauri::command]
+pub async fn test_host_connection(
+    app: AppHandle,
+    profile_id: String,
+) -> Result<ConnectionProbe, AppError> {
+    let (profile_id, config) =
+        crate::cli::resolve_service_config(Some(&profile_id)).map_err(AppError::config)?;
+    let kind = probe_kind(&config);
+    log::info!("[连接测试] 开始测试 {}", profile_id);
+
+    let started = Instant::now();
+    let result = tokio::time::timeout(PROBE_TIMEOUT, probe(&app, config))
+        .await
+        .unwrap_or_else(|_| {
+            Err(AppError::network("连接超时，请检查网络或配置")
+                .with_code(ErrorCode::NetworkTimeout))
+        });
+    let latency_ms = started.elapsed().as_millis() as u64;
+
+    Ok(match result {
+        Ok(message) => {
+            log::info!("[连接测试] {} 连接成功，耗时 {}ms", profile_id, latency_ms);
+            ConnectionProbe {
+                profile_id,
+                ok: true,
+                probe: kind,
+                latency_ms,
+                message,
+                error_code: None,
+            }
+        }
+        Err(e) => {
+            log::warn!("[连接测试] {} 连接失败: {}", profile_id, e.message());
+            ConnectionProbe {
+                profile_id,
+                ok: false,
+                probe: kind,
+                latency_ms,
+                message: e.message().to_string(),
+                error_code: Some(e.code()),
+            }
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn picks_the_cheapest_probe_per_host() {
+        assert_eq!(probe_kind(&ServerUploadConfig::Jd), ProbeKind::Availability);
+        assert_eq!(
+            probe_kind(&ServerUploadConfig::Nowcoder {
+                cookie: "t=x".to_string()
+            }),
+            ProbeKind::Write
+        );
+        assert_eq!(
+            probe_kind(&ServerUploadConfig::Smms {
+                token: "t".to_string()
+            }),
+            ProbeKind::Auth
+        );
+        assert_eq!(
+            probe_kind(&ServerUploadConfig::Upyun {
+                operator: "op".to_string(),
+                password: "pw".to_string(),
+                bucket: "b".to_string(),
+                public_domain: String::new(),
+            }),
+            ProbeKind::Bucket
+        );
+    }
+
+    #[test]
+    fn maps_storage_configs_to_s3_test_config() {
+        let (service, config) = s3_config(&ServerUploadConfig::Qiniu {
+            access_key: "ak".to_string(),
+            secret_key: "sk".to_string(),
+            region: "cn-east-1".to_string(),
+            bucket: "pics".to_string(),
+            custom_domain: String::new(),
+            path: String::new(),
+        })
+        .unwrap();
+        assert_eq!(service, "qiniu");
+        assert_eq!(config.access_key.as_deref(), Some("ak"));
+        assert_eq!(config.secret_key.as_deref(), Some("sk"));
+        assert_eq!(config.bucket.as_deref(), Some("pics"));
+        assert!(s3_config(&ServerUploadConfig::Jd).is_none());
+    }
+
+    #[test]
+    fn status_errors_carry_specific_codes() {
+        let auth = status_error("Imgur", reqwest::StatusCode::FORBIDDEN);
+        assert_eq!(auth.code(), ErrorCode::Auth);
+        let limited = status_error("Imgur", reqwest::StatusCode::TOO_MANY_REQUESTS);
+        assert_eq!(limited.code(), ErrorCode::UploadRateLimited);
+        assert_eq!(
+            status_error("Imgur", reqwest::StatusCode::NOT_FOUND).code(),
+            ErrorCode::Config
+        );
+    }
+}
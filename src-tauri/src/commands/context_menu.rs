@@ -0,0 +1,416 @@
+// 系统右键菜单「使用 PicNexus 上传」
+//
+// - Windows：在 HKCU\Software\Classes\SystemFileAssociations\.<ext>\shell 下为每种图片格式注册菜单项
+// - macOS：在 ~/Library/Services 写入 Finder 快速操作（Automator workflow）
+//
+// 两者都以「picnexus <文件...>」启动可执行文件：已运行时由 single-instance 转发给主实例，
+// 冷启动时解析为 CliAction::OpenFiles，最终都进入 GUI 上传队列（见 open_files.rs）。
+
+use crate::error::AppError;
+use serde::Serialize;
+
+pub const CONTEXT_MENU_LABEL: &str = "使用 PicNexus 上传";
+
+#[derive(Debug, Clone, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct ContextMenuStatus {
+    pub supported: bool,
+    pub installed: bool,
+    pub menu_label: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub message: Option<String>,
+}
+
+#[cfg(any(target_os = "windows", target_os = "macos"))]
+impl ContextMenuStatus {
+    fn supported(installed: bool) -> Self {
+        Self {
+            supported: true,
+            installed,
+            menu_label: CONTEXT_MENU_LABEL.to_string(),
+            message: None,
+        }
+    }
+}
+
+#[cfg(any(target_os = "windows", target_os = "macos"))]
+fn current_exe() -> Result<std::path::PathBuf, AppError> {
+    std::env::current_exe()
+        .map_err(|e| AppError::file_io(format!("无法获取当前可执行文件路径: {}", e)))
+}
+
+#[cfg(any(target_os = "windows", test))]
+mod windows_menu {
+    /// 菜单项注册表键名
+    pub(super) const VERB_KEY: &str = "PicNexusUpload";
+
+    pub(super) fn shell_key(extension: &str) -> String {
+        format!(
+            "Software\\Classes\\SystemFileAssociations\\.{}\\shell\\{}",
+            extension.to_ascii_lowercase(),
+            VERB_KEY
+        )
+    }
+
+    /// 多选时 Explorer 会为每个文件各启动一次，由 single-instance 合并到同一队列
+    pub(super) fn command_line(exe: &std::path::Path) -> String {
+        format!("\"{}\" \"%1\"", exe.display())
+    }
+}
+
+#[cfg(target_os = "windows")]
+mod platform {
+    use super::windows_menu::{command_line, shell_key};
+    use super::*;
+    use crate::deep_link::DEEP_LINK_IMAGE_EXTENSIONS;
+
+    fn installed_command() -> Option<String> {
+        windows_registry::CURRENT_USER
+            .open(format!("{}\\command", shell_key("png")))
+            .and_then(|key| key.get_string(""))
+            .ok()
+    }
+
+    pub(super) fn status() -> Result<ContextMenuStatus, AppError> {
+        let expected = command_line(&current_exe()?);
+        let installed = installed_command().is_some_and(|command| command == expected);
+        let mut status = ContextMenuStatus::supported(installed);
+        if !installed && installed_command().is_some() {
+            status.message = Some("右键菜单指向其他位置的 PicNexus，重新启用即可更新".to_string());
+        }
+        Ok(status)
+    }
+
+    pub(super) fn install() -> Result<ContextMenuStatus, AppError> {
+        let exe = current_exe()?;
+        let command = command_line(&exe);
+        let icon = format!("\"{}\",0", exe.display());
+
+        for extension in DEEP_LINK_IMAGE_EXTENSIONS {
+            let key_path = shell_key(extension);
+            let key = windows_registry::CURRENT_USER
+                .create(&key_path)
+                .map_err(|e| AppError::config(format!("写入注册表失败 ({}): {}", key_path, e)))?;
+            key.set_string("", CONTEXT_MENU_LABEL)
+                .and_then(|_| key.set_string("Icon", &icon))
+                .and_then(|_| key.set_string("MultiSelectModel", "Player"))
+                .and_then(|_| key.create("command"))
+                .and_then(|command_key| command_key.set_string("", &command))
+                .map_err(|e| AppError::config(format!("写入注册表失败 ({}): {}", key_path, e)))?;
+        }
+
+        notify_shell_change();
+        status()
+    }
+
+    pub(super) fn uninstall() -> Result<ContextMenuStatus, AppError> {
+        for extension in DEEP_LINK_IMAGE_EXTENSIONS {
+            // 键不存在时 remove_tree 返回错误，视为已移除
+            let _ = windows_registry::CURRENT_USER.remove_tree(shell_key(extension));
+        }
+        notify_shell_change();
+        status()
+    }
+
+    /// 通知 Explorer 刷新文件关联，菜单无需重启即可生效
+    fn notify_shell_change() {
+        use windows_sys::Win32::UI::Shell::{SHChangeNotify, SHCNE_ASSOCCHANGED, SHCNF_IDLIST};
+        unsafe {
+            SHChangeNotify(
+                SHCNE_ASSOCCHANGED as i32,
+                SHCNF_IDLIST,
+                std::ptr::null(),
+                std::ptr::null(),
+            );
+        }
+    }
+}
+
+#[cfg(any(target_os = "macos", test))]
+mod macos_menu {
+    fn xml_escape(value: &str) -> String {
+        value
+            .replace('&', "&amp;")
+            .replace('<', "&lt;")
+            .replace('>', "&gt;")
+            .replace('"', "&quot;")
+    }
+
+    fn shell_quote(value: &str) -> String {
+        format!("'{}'", value.replace('\'', "'\\''"))
+    }
+
+    /// Run Shell Script 动作的脚本：以参数形式把选中的文件交给 PicNexus
+    pub(super) fn shell_command(exe: &std::path::Path) -> String {
+        format!(
+            "{} \"$@\" >/dev/null 2>&1 &",
+            shell_quote(&exe.to_string_lossy())
+        )
+    }
+
+    pub(super) fn info_plist(label: &str) -> String {
+        format!(
+            r#"<?xml version="1.0" encoding="UTF-8"?>
+<!DOCTYPE plist PUBLIC "-//Apple//DTD PLIST 1.0//EN" "http://www.apple.com/DTDs/PropertyList-1.0.dtd">
+<plist version="1.0">
+<dict>
+	<key>NSServices</key>
+	<array>
+		<dict>
+			<key>NSIconName</key>
+			<string>NSActionTemplate</string>
+			<key>NSMenuItem</key>
+			<dict>
+				<key>default</key>
+				<string>{label}</string>
+			</dict>
+			<key>NSMessage</key>
+			<string>runWorkflowAsService</string>
+			<key>NSRequiredContext</key>
+			<dict>
+				<key>NSApplicationIdentifier</key>
+				<string>com.apple.finder</string>
+			</dict>
+			<key>NSSendFileTypes</key>
+			<array>
+				<string>public.image</string>
+			</array>
+		</dict>
+	</array>
+</dict>
+</plist>
+"#,
+            label = xml_escape(label)
+        )
+    }
+
+    pub(super) fn document_wflow(exe: &std::path::Path) -> String {
+        format!(
+            r#"<?xml version="1.0" encoding="UTF-8"?>
+<!DOCTYPE plist PUBLIC "-//Apple//DTD PLIST 1.0//EN" "http://www.apple.com/DTDs/PropertyList-1.0.dtd">
+<plist version="1.0">
+<dict>
+	<key>AMApplicationBuild</key>
+	<string>523</string>
+	<key>AMApplicationVersion</key>
+	<string>2.10</string>
+	<key>AMDocumentVersion</key>
+	<string>2</string>
+	<key>actions</key>
+	<array>
+		<dict>
+			<key>action</key>
+			<dict>
+				<key>AMAccepts</key>
+				<dict>
+					<key>Container</key>
+					<string>List</string>
+					<key>Optional</key>
+					<true/>
+					<key>Types</key>
+					<array>
+						<string>com.apple.cocoa.path</string>
+					</array>
+				</dict>
+				<key>AMActionVersion</key>
+				<string>2.0.3</string>
+				<key>AMProvides</key>
+				<dict>
+					<key>Container</key>
+					<string>List</string>
+					<key>Types</key>
+					<array>
+						<string>com.apple.cocoa.string</string>
+					</array>
+				</dict>
+				<key>ActionBundlePath</key>
+				<string>/System/Library/Automator/Run Shell Script.action</string>
+				<key>ActionName</key>
+				<string>Run Shell Script</string>
+				<key>ActionParameters</key>
+				<dict>
+					<key>COMMAND_STRING</key>
+					<string>{command}</string>
+					<key>CheckedForUserDefaultShell</key>
+					<true/>
+					<key>inputMethod</key>
+					<integer>1</integer>
+					<key>shell</key>
+					<string>/bin/bash</string>
+					<key>source</key>
+					<string></string>
+				</dict>
+				<key>BundleIdentifier</key>
+				<string>com.apple.RunShellScript</string>
+				<key>CFBundleVersion</key>
+				<string>2.0.3</string>
+				<key>Class Name</key>
+				<string>RunShellScriptAction</string>
+			</dict>
+		</dict>
+	</array>
+	<key>connectors</key>
+	<dict/>
+	<key>workflowMetaData</key>
+	<dict>
+		<key>serviceApplicationBundleID</key>
+		<string>com.apple.finder</string>
+		<key>serviceInputTypeIdentifier</key>
+		<string>com.apple.Automator.fileSystemObject.image</string>
+		<key>serviceOutputTypeIdentifier</key>
+		<string>com.apple.Automator.nothing</string>
+		<key>serviceProcessesInput</key>
+		<integer>0</integer>
+		<key>workflowTypeIdentifier</key>
+		<string>com.apple.Automator.servicesMenu</string>
+	</dict>
+</dict>
+</plist>
+"#,
+            command = xml_escape(&shell_command(exe))
+        )
+    }
+}
+
+#[cfg(target_os = "macos")]
+mod platform {
+    use super::macos_menu::{document_wflow, info_plist};
+    use super::*;
+    use std::path::PathBuf;
+
+    /// 快速操作 bundle 名（Finder 菜单中显示 NSMenuItem，而不是 bundle 名）
+    const WORKFLOW_NAME: &str = "PicNexus Upload.workflow";
+
+    fn workflow_dir() -> Result<PathBuf, AppError> {
+        let home = std::env::var("HOME").map_err(|_| AppError::config("无法读取 HOME 环境变量"))?;
+        Ok(PathBuf::from(home)
+            .join("Library")
+            .join("Services")
+            .join(WORKFLOW_NAME))
+    }
+
+    fn document_path() -> Result<PathBuf, AppError> {
+        Ok(workflow_dir()?.join("Contents").join("document.wflow"))
+    }
+
+    /// 刷新 Services 缓存，快速操作无需注销即可出现
+    fn refresh_services() {
+        let _ = std::process::Command::new("/System/Library/CoreServices/pbs")
+            .arg("-update")
+            .status();
+    }
+
+    pub(super) fn status() -> Result<ContextMenuStatus, AppError> {
+        let expected = document_wflow(&current_exe()?);
+        let current = std::fs::read_to_string(document_path()?).ok();
+        let installed = current.as_deref() == Some(expected.as_str());
+        let mut status = ContextMenuStatus::supported(installed);
+        if !installed && current.is_some() {
+            status.message = Some("快速操作指向其他位置的 PicNexus，重新启用即可更新".to_string());
+        }
+        Ok(status)
+    }
+
+    pub(super) fn install() -> Result<ContextMenuStatus, AppError> {
+        let contents = workflow_dir()?.join("Contents");
+        let exe = current_exe()?;
+        std::fs::create_dir_all(&contents)
+            .map_err(|e| AppError::file_io(format!("无法创建 {}: {}", contents.display(), e)))?;
+        std::fs::write(contents.join("Info.plist"), info_plist(CONTEXT_MENU_LABEL))
+            .and_then(|_| std::fs::write(contents.join("document.wflow"), document_wflow(&exe)))
+            .map_err(|e| AppError::file_io(format!("写入快速操作失败: {}", e)))?;
+        refresh_services();
+        status()
+    }
+
+    pub(super) fn uninstall() -> Result<ContextMenuStatus, AppError> {
+        let dir = workflow_dir()?;
+        if dir.exists() {
+            std::fs::remove_dir_all(&dir)
+                .map_err(|e| AppError::file_io(format!("无法删除 {}: {}", dir.display(), e)))?;
+            refresh_services();
+        }
+        status()
+    }
+}
+
+#[cfg(not(any(target_os = "windows", target_os = "macos")))]
+mod platform {
+    use super::*;
+
+    fn unsupported() -> ContextMenuStatus {
+        ContextMenuStatus {
+            supported: false,
+            installed: false,
+            menu_label: CONTEXT_MENU_LABEL.to_string(),
+            message: Some("当前平台暂不支持右键菜单，请使用「打开方式」或拖到窗口上传".to_string()),
+        }
+    }
+
+    pub(super) fn status() -> Result<ContextMenuStatus, AppError> {
+        Ok(unsupported())
+    }
+
+    pub(super) fn install() -> Result<ContextMenuStatus, AppError> {
+        Ok(unsupported())
+    }
+
+    pub(super) fn uninstall() -> Result<ContextMenuStatus, AppError> {
+        Ok(unsupported())
+    }
+}
+
+#[tauri::command]
+pub fn get_context_menu_status() -> Result<ContextMenuStatus, AppError> {
+    platform::status()
+}
+
+#[tauri::command]
+pub fn install_context_menu() -> Result<ContextMenuStatus, AppError> {
+    platform::install()
+}
+
+#[tauri::command]
+pub fn uninstall_context_menu() -> Result<ContextMenuStatus, AppError> {
+    platform::uninstall()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::Path;
+
+    #[test]
+    fn windows_command_quotes_exe_and_file_argument() {
+        assert_eq!(
+            windows_menu::command_line(Path::new(r"C:\Program Files\PicNexus\picnexus.exe")),
+            r#""C:\Program Files\PicNexus\picnexus.exe" "%1""#
+        );
+        assert_eq!(
+            windows_menu::shell_key("PNG"),
+            r"Software\Classes\SystemFileAssociations\.png\shell\PicNexusUpload"
+        );
+    }
+
+    #[test]
+    fn macos_shell_command_quotes_exe_path() {
+        assert_eq!(
+            macos_menu::shell_command(Path::new(
+                "/Applications/Pic's Nexus.app/Contents/MacOS/PicNexus"
+            )),
+            r#"'/Applications/Pic'\''s Nexus.app/Contents/MacOS/PicNexus' "$@" >/dev/null 2>&1 &"#
+        );
+    }
+
+    #[test]
+    fn macos_workflow_escapes_xml() {
+        let document =
+            macos_menu::document_wflow(Path::new("/Applications/A&B.app/Contents/MacOS/PicNexus"));
+        assert!(document.contains("/Applications/A&amp;B.app/Contents/MacOS/PicNexus"));
+        assert!(document.contains("&quot;$@&quot; &gt;/dev/null 2&gt;&amp;1 &amp;"));
+
+        let info = macos_menu::info_plist(CONTEXT_MENU_LABEL);
+        assert!(info.contains("<string>使用 PicNexus 上传</string>"));
+        assert!(info.contains("<string>public.image</string>"));
+    }
+}
@@ -0,0 +1,74 @@
+// src-tauri/src/commands/cpu_pool.rs
+// CPU 密集任务专用线程池（图片编码、压缩、预览、哈希）
+//
+// tokio 的 spawn_blocking 线程池与文件 I/O、剪贴板访问共用，上百 MP 的全景图压缩会把它占满；
+// 单独的 rayon 池把计算限制在 (核数 - 1) 个线程内，给事件循环和其他命令留出一个核。
+
+use std::fmt;
+use std::panic::{self, AssertUnwindSafe};
+use std::sync::LazyLock;
+
+use crate::error::AppError;
+
+static CPU_POOL: LazyLock<rayon::ThreadPool> = LazyLock::new(|| {
+    let threads = std::thread::available_parallelism()
+        .map(|n| n.get().saturating_sub(1).max(1))
+        .unwrap_or(2);
+    rayon::ThreadPoolBuilder::new()
+        .num_threads(threads)
+        .thread_name(|i| format!("picnexus-cpu-{}", i))
+        .build()
+        .expect("创建 CPU 线程池失败")
+});
+
+/// 任务在线程池中 panic（用法与 tokio::task::JoinError 一致，可直接用 `?` 转成 E_TASK_PANICKED）
+#[derive(Debug)]
+pub struct CpuTaskPanicked;
+
+impl fmt::Display for CpuTaskPanicked {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("后台计算任务异常退出")
+    }
+}
+
+impl From<CpuTaskPanicked> for AppError {
+    fn from(_: CpuTaskPanicked) -> Self {
+        AppError::task_panicked()
+    }
+}
+
+/// 在 CPU 线程池中执行任务并异步等待结果
+///
+/// 任务内部的 rayon 并行（如 imagequant）同样留在该线程池内
+pub async fn spawn<F, T>(task: F) -> Result<T, CpuTaskPanicked>
+where
+    F: FnOnce() -> T + Send + 'static,
+    T: Send + 'static,
+{
+    let (tx, rx) = tokio::sync::oneshot::channel();
+    CPU_POOL.spawn(move || {
+        // rayon 默认在任务 panic 时中止进程，这里捕获后交回调用方
+        let _ = tx.send(panic::catch_unwind(AssertUnwindSafe(task)));
+    });
+    match rx.await {
+        Ok(Ok(value)) => Ok(value),
+        _ => Err(CpuTaskPanicked),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn returns_task_result() {
+        let sum = spawn(|| (1..=100u32).sum::<u32>()).await.unwrap();
+        assert_eq!(sum, 5050);
+    }
+
+    #[tokio::test]
+    async fn reports_panics_instead_of_aborting() {
+        let result = spawn(|| -> u32 { panic!("boom") }).await;
+        assert!(result.is_err());
+    }
+}
@@ -0,0 +1,270 @@
+// src-tauri/src/commands/crash_report.rs
+// 崩溃报告：panic hook 把 panic 信息与调用栈写入应用数据目录，下次启动时提示用户
+//
+// 后台任务 panic 时 tokio 只在 stderr 打印一行，日志里什么也没有，前端看到的只是「上传一直不结束」。
+// 现在任何线程 panic 都会生成 crash-reports/crash-*.txt（消息已脱敏），前端启动时读取未查看的报告，
+// 由用户选择打开目录或忽略；诊断包也会附带最近的报告。
+
+use std::any::Any;
+use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
+
+use serde::Serialize;
+use tauri::AppHandle;
+
+use crate::error::AppError;
+use crate::log_utils::{safe_path, sanitize_text};
+use crate::portable;
+
+/// 崩溃报告目录名（位于应用数据目录下）
+const DIR_NAME: &str = "crash-reports";
+
+/// 已查看的报告在文件名中带此后缀，不再提示
+const SEEN_SUFFIX: &str = ".seen.txt";
+
+/// 最多保留的报告数，超出时删除最旧的
+const MAX_REPORTS: usize = 20;
+
+static CRASH_DIR: OnceLock<PathBuf> = OnceLock::new();
+
+fn crash_dir(app: &AppHandle) -> Result<PathBuf, AppError> {
+    Ok(portable::user_data_dir(app)?.join(DIR_NAME))
+}
+
+/// 从 panic payload 中取出消息（panic!/expect 的参数）
+fn panic_message(payload: &(dyn Any + Send)) -> String {
+    if let Some(message) = payload.downcast_ref::<&str>() {
+        message.to_string()
+    } else if let Some(message) = payload.downcast_ref::<String>() {
+        message.clone()
+    } else {
+        "未知 panic".to_string()
+    }
+}
+
+fn render_report(message: &str, location: &str, thread: &str, backtrace: &str) -> String {
+    format!(
+        "PicNexus 崩溃报告\n\
+         时间: {}\n\
+         版本: {}\n\
+         系统: {} {}\n\
+         线程: {}\n\
+         位置: {}\n\
+         信息: {}\n\
+         \n\
+         调用栈:\n{}\n",
+        super::time_format::now_utc_rfc3339(),
+        env!("CARGO_PKG_VERSION"),
+        std::env::consts::OS,
+        std::env::consts::ARCH,
+        thread,
+        location,
+        sanitize_text(message),
+        backtrace
+    )
+}
+
+fn write_report(dir: &Path, report: &str) -> std::io::Result<PathBuf> {
+    std::fs::create_dir_all(dir)?;
+    let name = format!(
+        "crash-{}-{}.txt",
+        chrono::Utc::now().format("%Y%m%d-%H%M%S"),
+        &uuid::Uuid::new_v4().simple().to_string()[..8]
+    );
+    let path = dir.join(name);
+    std::fs::write(&path, report)?;
+    Ok(path)
+}
+
+/// 目录中的报告文件（新 → 旧）
+fn report_files(dir: &Path) -> Vec<PathBuf> {
+    let mut files: Vec<PathBuf> = std::fs::read_dir(dir)
+        .into_iter()
+        .flatten()
+        .flatten()
+        .map(|entry| entry.path())
+        .filter(|path| {
+            path.file_name()
+                .and_then(|n| n.to_str())
+                .is_some_and(|n| n.starts_with("crash-") && n.ends_with(".txt"))
+        })
+        .collect();
+    // 同一目录下的文件名以时间戳开头，按路径倒序即按时间倒序
+    files.sort();
+    files.reverse();
+    files
+}
+
+fn is_seen(path: &Path) -> bool {
+    path.to_string_lossy().ends_with(SEEN_SUFFIX)
+}
+
+/// 只保留最近的 `MAX_REPORTS` 份报告
+fn prune_reports(dir: &Path) {
+    for path in report_files(dir).into_iter().skip(MAX_REPORTS) {
+        let _ = std::fs::remove_file(path);
+    }
+}
+
+/// 安装 panic hook（应用启动时调用一次）
+///
+/// 报告写入失败时不影响原有的 panic 处理；原 hook 仍会执行（打印到 stderr）
+pub fn install_panic_hook(app: &AppHandle) {
+    let dir = match crash_dir(app) {
+        Ok(dir) => dir,
+        Err(e) => {
+            log::warn!("[崩溃报告] 无法确定报告目录，跳过安装: {}", e);
+            return;
+        }
+    };
+    prune_reports(&dir);
+    if CRASH_DIR.set(dir).is_err() {
+        return;
+    }
+
+    let previous = std::panic::take_hook();
+    std::panic::set_hook(Box::new(move |info| {
+        let message = panic_message(info.payload());
+        let location = info
+            .location()
+            .map(|l| format!("{}:{}:{}", l.file(), l.line(), l.column()))
+            .unwrap_or_else(|| "未知".to_string());
+        let thread = std::thread::current()
+            .name()
+            .unwrap_or("<unnamed>")
+            .to_string();
+        let backtrace = std::backtrace::Backtrace::force_capture().to_string();
+        let report = render_report(&message, &location, &thread, &backtrace);
+
+        if let Some(dir) = CRASH_DIR.get() {
+            match write_report(dir, &report) {
+                Ok(path) => log::error!(
+                    "[崩溃报告] 线程 {} panic（{}）: {}，报告已写入 {}",
+                    thread,
+                    location,
+                    sanitize_text(&message),
+                    safe_path(&path.to_string_lossy())
+                ),
+                Err(e) => log::error!(
+                    "[崩溃报告] 线程 {} panic（{}）: {}，报告写入失败: {}",
+                    thread,
+                    location,
+                    sanitize_text(&message),
+                    e
+                ),
+            }
+        }
+        previous(info);
+    }));
+}
+
+/// 最近的报告文件（诊断包使用）
+pub fn recent_reports(limit: usize) -> Vec<PathBuf> {
+    CRASH_DIR
+        .get()
+        .map(|dir| report_files(dir).into_iter().take(limit).collect())
+        .unwrap_or_default()
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CrashReportSummary {
+    /// 报告文件名
+    pub id: String,
+    pub path: String,
+    /// 报告中的「信息」行
+    pub message: String,
+    /// 报告中的「时间」行
+    pub created_at: String,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PendingCrashReports {
+    /// 报告目录（供前端打开）
+    pub dir: String,
+    pub reports: Vec<CrashReportSummary>,
+}
+
+fn summarize(path: &Path) -> Option<CrashReportSummary> {
+    let text = std::fs::read_to_string(path).ok()?;
+    let field = |prefix: &str| {
+        text.lines()
+            .find_map(|line| line.strip_prefix(prefix))
+            .unwrap_or_default()
+            .to_string()
+    };
+    Some(CrashReportSummary {
+        id: path.file_name()?.to_string_lossy().to_string(),
+        path: path.to_string_lossy().to_string(),
+        message: field("信息: "),
+        created_at: field("时间: "),
+    })
+}
+
+/// 获取尚未查看的崩溃报告
+#[tauri::command]
+pub fn get_pending_crash_reports(app: AppHandle) -> Result<PendingCrashReports, AppError> {
+    let dir = crash_dir(&app)?;
+    let reports = report_files(&dir)
+        .into_iter()
+        .filter(|path| !is_seen(path))
+        .filter_map(|path| summarize(&path))
+        .collect();
+    Ok(PendingCrashReports {
+        dir: dir.to_string_lossy().to_string(),
+        reports,
+    })
+}
+
+/// 把全部未查看的报告标记为已查看，返回标记的数量
+#[tauri::command]
+pub fn dismiss_crash_reports(app: AppHandle) -> Result<usize, AppError> {
+    let dir = crash_dir(&app)?;
+    let mut dismissed = 0;
+    for path in report_files(&dir).into_iter().filter(|path| !is_seen(path)) {
+        let stem = path.file_stem().unwrap_or_default().to_string_lossy();
+        let seen = path.with_file_name(format!("{}{}", stem, SEEN_SUFFIX));
+        std::fs::rename(&path, &seen)
+            .map_err(|e| AppError::file_io(format!("标记崩溃报告失败: {}", e)))?;
+        dismissed += 1;
+    }
+    Ok(dismissed)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn panic_message_handles_str_and_string_payloads() {
+        let payload: Box<dyn Any + Send> = Box::new("boom");
+        assert_eq!(panic_message(payload.as_ref()), "boom");
+        let payload: Box<dyn Any + Send> = Box::new(String::from("owned boom"));
+        assert_eq!(panic_message(payload.as_ref()), "owned boom");
+        let payload: Box<dyn Any + Send> = Box::new(42);
+        assert_eq!(panic_message(payload.as_ref()), "未知 panic");
+    }
+
+    #[test]
+    fn reports_are_summarized_and_pruned() {
+        let dir = std::env::temp_dir().join(format!("picnexus_crash_test_{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+
+        let report = render_report("index out of bounds", "src/x.rs:1:2", "main", "bt");
+        let path = write_report(&dir, &report).unwrap();
+        let summary = summarize(&path).unwrap();
+        assert_eq!(summary.message, "index out of bounds");
+        assert!(!summary.created_at.is_empty());
+        assert!(!is_seen(&path));
+
+        for i in 0..MAX_REPORTS {
+            std::fs::write(dir.join(format!("crash-00000000-{:02}.seen.txt", i)), "old").unwrap();
+        }
+        prune_reports(&dir);
+        let remaining = report_files(&dir);
+        assert_eq!(remaining.len(), MAX_REPORTS);
+        assert_eq!(remaining[0], path, "最新的报告应被保留");
+        let _ = std::fs::remove_dir_all(dir);
+    }
+}
@@ -0,0 +1,123 @@
+// src-tauri/src/commands/deadline.rs
+// 组合操作的总时限：重新上传 = 下载 + 处理 + 上传
+//
+// 每一步都有自己的请求超时（下载 30 秒、上传按图床设置），但某一步反复卡在超时边缘重试时，
+// 整个操作可能拖上十几分钟，批量迁移的并发名额也被一直占着。总时限独立于单次请求超时：
+// 到期后放弃整个操作并返回 E_DEADLINE，未完成的 future 被 drop，网络请求随之中断，
+// 临时文件由守卫在 drop 时清理。
+
+use std::future::Future;
+use std::time::Duration;
+
+use tokio::time::Instant;
+
+use crate::error::{AppError, ErrorCode};
+
+/// 单张图片重新上传（下载 + 上传）的总时限
+pub const REHOST_DEADLINE: Duration = Duration::from_secs(5 * 60);
+
+/// 组合操作的总时限，各阶段共用同一个截止时刻
+pub struct Deadline {
+    operation: &'static str,
+    limit: Duration,
+    expires_at: Instant,
+}
+
+impl Deadline {
+    pub fn new(operation: &'static str, limit: Duration) -> Self {
+        Self {
+            operation,
+            limit,
+            expires_at: Instant::now() + limit,
+        }
+    }
+
+    /// 在剩余时间内执行一个阶段，截止时刻已过返回 E_DEADLINE
+    pub async fn run<F: Future>(&self, fut: F) -> Result<F::Output, AppError> {
+        tokio::time::timeout_at(self.expires_at, fut)
+            .await
+            .map_err(|_| {
+                log::warn!(
+                    "[时限] {}超过 {} 秒仍未完成，已放弃",
+                    self.operation,
+                    self.limit.as_secs()
+                );
+                AppError::network(format!(
+                    "{}超过 {} 秒仍未完成，已放弃",
+                    self.operation,
+                    self.limit.as_secs()
+                ))
+                .with_code(ErrorCode::Deadline)
+            })
+    }
+
+    /// 执行不计入总时限的等待（排队等并发名额、上传限速间隔），截止时刻相应顺延
+    pub async fn exclude<F: Future>(&mut self, fut: F) -> F::Output {
+        let started = Instant::now();
+        let output = fut.await;
+        self.expires_at += started.elapsed();
+        output
+    }
+}
+
+/// 在总时限内执行整个组合操作，超时返回 E_DEADLINE
+///
+/// 内层结果原样返回，调用方按自己的错误类型处理
+pub async fn run<F: Future>(
+    operation: &'static str,
+    limit: Duration,
+    fut: F,
+) -> Result<F::Output, AppError> {
+    Deadline::new(operation, limit).run(fut).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn run_returns_deadline_error_when_stages_stall() {
+        let stalled = async {
+            // 每一步都在自己的超时之内完成，但合起来超过总时限
+            for _ in 0..10 {
+                tokio::time::sleep(Duration::from_millis(30)).await;
+            }
+            Ok::<_, String>("done")
+        };
+        let err = run("重新上传", Duration::from_millis(100), stalled)
+            .await
+            .unwrap_err();
+        assert_eq!(err.code(), ErrorCode::Deadline);
+        assert!(err.is_retriable());
+    }
+
+    #[tokio::test]
+    async fn stages_share_one_deadline_and_waits_are_excluded() {
+        let mut deadline = Deadline::new("迁移图片", Duration::from_millis(100));
+
+        // 排队等待不计入总时限
+        deadline
+            .exclude(tokio::time::sleep(Duration::from_millis(150)))
+            .await;
+        deadline
+            .run(tokio::time::sleep(Duration::from_millis(60)))
+            .await
+            .expect("第一阶段在总时限内");
+
+        // 第二阶段单独看没有超时，但两阶段合计超过总时限
+        let err = deadline
+            .run(tokio::time::sleep(Duration::from_millis(60)))
+            .await
+            .unwrap_err();
+        assert_eq!(err.code(), ErrorCode::Deadline);
+    }
+
+    #[tokio::test]
+    async fn run_passes_through_inner_result() {
+        let result = run("重新上传", REHOST_DEADLINE, async {
+            Err::<(), _>("上传失败")
+        })
+        .await;
+        assert_eq!(result.unwrap(), Err("上传失败"));
+    }
+}
@@ -0,0 +1,326 @@
+// src-tauri/src/commands/diagnostics.rs
+// 诊断包导出：把最近的日志、脱敏后的配置、队列状态、版本与系统信息、最近的错误打成一个 zip
+//
+// 用户反馈「上传失败了」时通常没有任何可排查的信息，设置页导出诊断包附在反馈里即可。
+// 最近的崩溃报告放在 crashes/ 下。
+// 配置中的令牌、密码、Cookie 一律替换为 [REDACTED]；日志写入时已做路径 / URL 脱敏，这里再过一遍。
+
+use std::collections::VecDeque;
+use std::io::{Read, Seek, SeekFrom, Write};
+use std::path::{Path, PathBuf};
+
+use serde_json::{json, Value};
+use tauri::AppHandle;
+use zip::write::SimpleFileOptions;
+use zip::{CompressionMethod, ZipWriter};
+
+use super::disk_space;
+use crate::error::AppError;
+use crate::log_utils::{safe_path, sanitize_text};
+use crate::portable;
+
+/// 打包最近的日志文件数
+const MAX_LOG_FILES: usize = 3;
+
+/// 每个日志文件只取末尾这么多字节
+const MAX_LOG_TAIL_BYTES: u64 = 5 * 1024 * 1024;
+
+/// 附带的最近崩溃报告数
+const MAX_CRASH_REPORTS: usize = 5;
+
+/// 汇总的最近错误条数
+const MAX_RECENT_ERRORS: usize = 50;
+
+/// 键名包含这些片段的配置值视为敏感信息
+const SENSITIVE_KEY_PARTS: &[&str] = &[
+    "token",
+    "secret",
+    "password",
+    "passwd",
+    "cookie",
+    "auth",
+    "key",
+    "sign",
+    "credential",
+    "session",
+];
+
+const REDACTED: &str = "[REDACTED]";
+
+fn is_sensitive_key(key: &str) -> bool {
+    let key = key.to_ascii_lowercase();
+    SENSITIVE_KEY_PARTS.iter().any(|part| key.contains(part))
+}
+
+/// 递归脱敏：敏感键的值（无论类型）替换为 [REDACTED]，其余字符串再做文本脱敏
+fn redact_json(value: Value) -> Value {
+    match value {
+        Value::Object(map) => Value::Object(
+            map.into_iter()
+                .map(|(key, value)| {
+                    let value = if is_sensitive_key(&key) && !value.is_null() {
+                        Value::String(REDACTED.to_string())
+                    } else {
+                        redact_json(value)
+                    };
+                    (key, value)
+                })
+                .collect(),
+        ),
+        Value::Array(items) => Value::Array(items.into_iter().map(redact_json).collect()),
+        Value::String(text) => Value::String(sanitize_text(&text)),
+        other => other,
+    }
+}
+
+/// 日志目录中最近修改的日志文件（新 → 旧）
+fn recent_log_files(log_dir: &Path) -> Vec<PathBuf> {
+    let mut files: Vec<(std::time::SystemTime, PathBuf)> = std::fs::read_dir(log_dir)
+        .into_iter()
+        .flatten()
+        .flatten()
+        .filter_map(|entry| {
+            let path = entry.path();
+            let is_log = path.extension().and_then(|e| e.to_str()) == Some("log")
+                || path.to_string_lossy().contains(".log.");
+            if !is_log {
+                return None;
+            }
+            let modified = entry.metadata().ok()?.modified().ok()?;
+            Some((modified, path))
+        })
+        .collect();
+    files.sort_by(|a, b| b.0.cmp(&a.0));
+    files
+        .into_iter()
+        .take(MAX_LOG_FILES)
+        .map(|(_, path)| path)
+        .collect()
+}
+
+/// 读取文件末尾最多 `max_bytes` 字节；截断时丢弃第一行残缺内容
+fn read_tail(path: &Path, max_bytes: u64) -> std::io::Result<String> {
+    let mut file = std::fs::File::open(path)?;
+    let len = file.metadata()?.len();
+    let truncated = len > max_bytes;
+    if truncated {
+        file.seek(SeekFrom::Start(len - max_bytes))?;
+    }
+    let mut bytes = Vec::new();
+    file.take(max_bytes).read_to_end(&mut bytes)?;
+    let text = String::from_utf8_lossy(&bytes).into_owned();
+    Ok(match text.split_once('\n') {
+        Some((_, rest)) if truncated => rest.to_string(),
+        _ => text,
+    })
+}
+
+/// 从日志文本中取最近的 ERROR 行（按时间顺序，最多 `limit` 条）
+fn collect_error_lines<'a>(logs: impl IntoIterator<Item = &'a str>, limit: usize) -> Vec<String> {
+    let mut errors = VecDeque::with_capacity(limit);
+    for text in logs {
+        for line in text.lines().filter(|line| line.contains("[ERROR]")) {
+            if errors.len() == limit {
+                errors.pop_front();
+            }
+            errors.push_back(line.to_string());
+        }
+    }
+    errors.into()
+}
+
+fn system_info() -> Value {
+    json!({
+        "appVersion": env!("CARGO_PKG_VERSION"),
+        "tauriVersion": tauri::VERSION,
+        "os": std::env::consts::OS,
+        "arch": std::env::consts::ARCH,
+        "family": std::env::consts::FAMILY,
+        "portable": portable::is_portable(),
+        "exportedAt": super::time_format::now_utc_rfc3339(),
+        "startupProfile": super::startup_profile::get_startup_profile(),
+        "logLevels": super::log_level::get_log_levels(),
+    })
+}
+
+/// 读取并脱敏图床配置（GUI 导出给 CLI 的 cli-config.json）
+fn redacted_config(data_dir: &Path) -> Value {
+    let path = data_dir.join("cli-config.json");
+    match std::fs::read_to_string(&path) {
+        Ok(text) => match serde_json::from_str::<Value>(&text) {
+            Ok(value) => redact_json(value),
+            Err(e) => json!({ "error": format!("配置文件格式错误: {}", e) }),
+        },
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => json!({ "error": "未找到配置文件" }),
+        Err(e) => json!({ "error": format!("读取配置文件失败: {}", e) }),
+    }
+}
+
+struct DiagnosticsInput {
+    log_dir: Option<PathBuf>,
+    data_dir: Option<PathBuf>,
+    queue_state: Option<Value>,
+    crash_reports: Vec<PathBuf>,
+}
+
+fn write_bundle(target: &Path, input: DiagnosticsInput) -> Result<(), AppError> {
+    let io_err = |e: std::io::Error| AppError::write_failed("写入诊断包", &e);
+    let zip_err = |e: zip::result::ZipError| match e {
+        zip::result::ZipError::Io(e) => io_err(e),
+        e => AppError::file_io(format!("写入诊断包失败: {}", e)),
+    };
+
+    // 日志尾部占大头，压缩前按日志上限预检
+    if let Some(dir) = target.parent() {
+        disk_space::preflight(dir, MAX_LOG_FILES as u64 * MAX_LOG_TAIL_BYTES)?;
+    }
+    let file = std::fs::File::create(target).map_err(io_err)?;
+    let mut zip = ZipWriter::new(file);
+    let options = SimpleFileOptions::default().compression_method(CompressionMethod::Deflated);
+    let mut add = |name: &str, bytes: &[u8]| -> Result<(), AppError> {
+        zip.start_file(name, options).map_err(zip_err)?;
+        zip.write_all(bytes).map_err(io_err)
+    };
+
+    let pretty = |value: &Value| serde_json::to_vec_pretty(value).unwrap_or_default();
+    add("system.json", &pretty(&system_info()))?;
+
+    let config = match &input.data_dir {
+        Some(dir) => redacted_config(dir),
+        None => json!({ "error": "无法确定应用数据目录" }),
+    };
+    add("config.redacted.json", &pretty(&config))?;
+
+    if let Some(queue) = input.queue_state {
+        add("queue.json", &pretty(&redact_json(queue)))?;
+    }
+
+    let mut logs = Vec::new();
+    if let Some(dir) = &input.log_dir {
+        for path in recent_log_files(dir) {
+            match read_tail(&path, MAX_LOG_TAIL_BYTES) {
+                Ok(text) => logs.push((path, sanitize_text(&text))),
+                Err(e) => log::warn!(
+                    "[诊断] 读取日志失败 {}: {}",
+                    safe_path(&path.to_string_lossy()),
+                    e
+                ),
+            }
+        }
+    }
+    // 日志按新 → 旧排列，错误汇总按时间顺序
+    let errors = collect_error_lines(
+        logs.iter().rev().map(|(_, text)| text.as_str()),
+        MAX_RECENT_ERRORS,
+    );
+    add("errors.txt", errors.join("\n").as_bytes())?;
+    for (path, text) in &logs {
+        let name = path
+            .file_name()
+            .map(|n| n.to_string_lossy().to_string())
+            .unwrap_or_else(|| "picnexus.log".to_string());
+        add(&format!("logs/{}", name), text.as_bytes())?;
+    }
+
+    for path in &input.crash_reports {
+        let (Some(name), Ok(text)) = (path.file_name(), std::fs::read_to_string(path)) else {
+            continue;
+        };
+        add(
+            &format!("crashes/{}", name.to_string_lossy()),
+            sanitize_text(&text).as_bytes(),
+        )?;
+    }
+
+    zip.finish().map_err(zip_err)?;
+    Ok(())
+}
+
+/// 导出诊断包到 `path`（zip），返回实际写入的路径
+///
+/// `queue_state` 为前端上传队列的快照，原样脱敏后写入 queue.json
+#[tauri::command]
+pub async fn export_diagnostics(
+    app: AppHandle,
+    path: String,
+    queue_state: Option<Value>,
+) -> Result<String, AppError> {
+    let path = path.trim();
+    if path.is_empty() {
+        return Err(AppError::validation("导出路径不能为空"));
+    }
+    let mut target = PathBuf::from(path);
+    if !target
+        .extension()
+        .is_some_and(|ext| ext.eq_ignore_ascii_case("zip"))
+    {
+        target.as_mut_os_string().push(".zip");
+    }
+    if !target.parent().is_some_and(Path::is_dir) {
+        return Err(AppError::validation("导出目录不存在"));
+    }
+
+    let input = DiagnosticsInput {
+        log_dir: portable::log_dir(&app).ok(),
+        data_dir: portable::user_data_dir(&app).ok(),
+        queue_state,
+        crash_reports: super::crash_report::recent_reports(MAX_CRASH_REPORTS),
+    };
+    let bundle_path = target.clone();
+    tokio::task::spawn_blocking(move || {
+        let result = write_bundle(&bundle_path, input);
+        if result.is_err() {
+            let _ = std::fs::remove_file(&bundle_path);
+        }
+        result
+    })
+    .await??;
+
+    let target = target.to_string_lossy().to_string();
+    log::info!("[诊断] 已导出诊断包: {}", safe_path(&target));
+    Ok(target)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn redact_json_hides_sensitive_values() {
+        let config = json!({
+            "services": {
+                "smms": { "token": "abc123", "enabled": true },
+                "r2": { "accessKeyId": "AK", "secretAccessKey": "SK", "bucket": "pics" },
+                "weibo": { "cookie": "SUB=xyz" }
+            },
+            "list": [{ "password": "p" }]
+        });
+        let redacted = redact_json(config).to_string();
+        for secret in ["abc123", "\"AK\"", "\"SK\"", "SUB=xyz", "\"p\""] {
+            assert!(!redacted.contains(secret), "{} leaked", secret);
+        }
+        assert!(redacted.contains("\"bucket\":\"pics\""));
+        assert!(redacted.contains("\"enabled\":true"));
+    }
+
+    #[test]
+    fn collect_error_lines_keeps_latest_in_order() {
+        let old = "[a][INFO] ok\n[a][ERROR] e1\n[a][ERROR] e2";
+        let new = "[b][ERROR] e3\n[b][WARN] w";
+        let errors = collect_error_lines([old, new], 2);
+        assert_eq!(errors, vec!["[a][ERROR] e2", "[b][ERROR] e3"]);
+    }
+
+    #[test]
+    fn read_tail_drops_partial_first_line() {
+        let path =
+            std::env::temp_dir().join(format!("picnexus_diag_tail_{}.log", std::process::id()));
+        std::fs::write(&path, "first line\nsecond line\nthird\n").unwrap();
+
+        let tail = read_tail(&path, 14).unwrap();
+        assert_eq!(tail, "third\n");
+        let full = read_tail(&path, 1024).unwrap();
+        assert!(full.starts_with("first line"));
+        let _ = std::fs::remove_file(path);
+    }
+}
@@ -0,0 +1,103 @@
+// src-tauri/src/commands/disk_space.rs
+// 写入前的磁盘空间 / 可写性预检
+//
+// 磁盘写满或目标目录只读时，写入会在中途失败，用户只能看到笼统的「写入文件失败」，
+// 还可能留下写了一半的文件。写临时文件、压缩结果、诊断包之前先检查目标卷的剩余空间
+// 和目录写权限，不满足时直接返回 E_DISK_FULL / E_READONLY，前端据此给出明确提示。
+
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+use std::sync::{LazyLock, Mutex};
+
+use crate::error::{AppError, ErrorCode};
+use crate::log_utils::safe_path;
+
+/// 预留余量（32MB）：剩余空间刚好够时，系统和其他程序的写入仍可能把磁盘写满
+const SPACE_MARGIN_BYTES: u64 = 32 * 1024 * 1024;
+
+/// 已确认可写的目录，避免每次写入都创建探测文件
+static WRITABLE_DIRS: LazyLock<Mutex<HashSet<PathBuf>>> =
+    LazyLock::new(|| Mutex::new(HashSet::new()));
+
+/// 向上找到第一个已存在的目录（目标目录可能还没创建）
+fn existing_ancestor(path: &Path) -> Option<&Path> {
+    path.ancestors().find(|p| p.is_dir())
+}
+
+/// 检查 `dir` 所在卷是否还有 `required` 字节（加上余量）的可用空间
+///
+/// 无法查询剩余空间时（网络盘、权限受限等）不拦截，交给实际写入报错
+pub fn check_space(dir: &Path, required: u64) -> Result<(), AppError> {
+    let Some(existing) = existing_ancestor(dir) else {
+        return Ok(());
+    };
+    let available = match fs4::available_space(existing) {
+        Ok(available) => available,
+        Err(e) => {
+            log::debug!(
+                "[磁盘预检] 无法查询剩余空间 {}: {}",
+                safe_path(&existing.to_string_lossy()),
+                e
+            );
+            return Ok(());
+        }
+    };
+    if available < required.saturating_add(SPACE_MARGIN_BYTES) {
+        return Err(AppError::file_io(format!(
+            "磁盘空间不足: 需要 {} MB，剩余 {} MB",
+            required.div_ceil(1024 * 1024),
+            available / 1024 / 1024
+        ))
+        .with_code(ErrorCode::DiskFull)
+        .with_context("required", required.to_string())
+        .with_context("available", available.to_string()));
+    }
+    Ok(())
+}
+
+/// 检查 `dir` 是否可写：创建并删除一个探测文件
+///
+/// 目录不存在时检查最近的已存在上级目录（之后要在其中创建目录）
+pub fn check_writable(dir: &Path) -> Result<(), AppError> {
+    let Some(existing) = existing_ancestor(dir) else {
+        return Ok(());
+    };
+    let mut writable = WRITABLE_DIRS.lock().unwrap_or_else(|e| e.into_inner());
+    if writable.contains(existing) {
+        return Ok(());
+    }
+    let probe = existing.join(format!(".picnexus_probe_{}", uuid::Uuid::new_v4().simple()));
+    std::fs::write(&probe, b"").map_err(|e| {
+        AppError::write_failed("写入", &e)
+            .with_context("path", safe_path(&existing.to_string_lossy()))
+    })?;
+    let _ = std::fs::remove_file(&probe);
+    writable.insert(existing.to_path_buf());
+    Ok(())
+}
+
+/// 写入前预检：先查写权限，再查剩余空间
+pub fn preflight(dir: &Path, required: u64) -> Result<(), AppError> {
+    check_writable(dir)?;
+    check_space(dir, required)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn temp_dir_passes_small_preflight() {
+        let dir = std::env::temp_dir()
+            .join("picnexus_disk_space_test")
+            .join("nested");
+        assert!(preflight(&dir, 1024).is_ok());
+        assert!(!dir.exists());
+    }
+
+    #[test]
+    fn rejects_requirement_beyond_available_space() {
+        let err = check_space(&std::env::temp_dir(), u64::MAX).unwrap_err();
+        assert_eq!(err.code(), ErrorCode::DiskFull);
+    }
+}
@@ -0,0 +1,64 @@
+// src-tauri/src/commands/drag_out.rs
+// 从历史记录拖出图片：以本地归档副本发起系统原生拖拽
+//
+// 网页内的 HTML5 拖拽只能带出链接文本，拖到聊天软件或文件夹里得不到图片文件。
+// 这里在主线程调用系统拖拽接口（macOS NSDraggingSession、Windows DoDragDrop、
+// Linux GTK drag），拖拽内容是真实文件，目标应用看到的和从文件管理器拖出来的一样。
+
+use std::path::PathBuf;
+
+use drag::{DragItem, DragResult, Image, Options};
+use tauri::WebviewWindow;
+
+use crate::error::AppError;
+
+/// 在主线程发起拖拽（系统拖拽接口只能在 UI 线程调用）
+fn begin(window: &WebviewWindow, file: PathBuf, preview: PathBuf) -> Result<(), AppError> {
+    #[cfg(target_os = "linux")]
+    let handle = window
+        .gtk_window()
+        .map_err(|e| AppError::external(format!("无法获取窗口句柄: {}", e)))?;
+    #[cfg(not(target_os = "linux"))]
+    let handle = window.clone();
+
+    let name = file.display().to_string();
+    drag::start_drag(
+        &handle,
+        DragItem::Files(vec![file]),
+        Image::File(preview),
+        move |result, _| match result {
+            DragResult::Dropped => log::info!("[拖出] 已拖出: {}", name),
+            DragResult::Cancel => log::debug!("[拖出] 拖拽已取消: {}", name),
+        },
+        Options::default(),
+    )
+    .map_err(|e| AppError::external(format!("无法发起拖拽: {}", e)))
+}
+
+/// 以本地文件发起原生拖拽（历史记录的本地归档副本）
+///
+/// `preview_path` 为拖拽时跟随光标的预览图（通常是缩略图），缺省或无效时使用原文件
+#[tauri::command]
+pub async fn start_drag_out(
+    window: WebviewWindow,
+    path: String,
+    preview_path: Option<String>,
+) -> Result<(), AppError> {
+    let file = crate::validate_open_file_path(&path)?;
+    if !file.is_file() {
+        return Err(AppError::validation("只能拖出文件"));
+    }
+    let preview = preview_path
+        .and_then(|p| crate::validate_open_file_path(&p).ok())
+        .filter(|p| p.is_file())
+        .unwrap_or_else(|| file.clone());
+
+    let (tx, rx) = tokio::sync::oneshot::channel();
+    let target = window.clone();
+    window
+        .run_on_main_thread(move || {
+            let _ = tx.send(begin(&target, file, preview));
+        })
+        .map_err(|e| AppError::external(format!("无法发起拖拽: {}", e)))?;
+    rx.await.map_err(|_| AppError::external("拖拽已中断"))?
+}
@@ -0,0 +1,151 @@
+// src-tauri/src/commands/file_cache.rs
+// 按 (路径, 文件大小, 修改时间) 缓存基于本地文件计算的结果
+//
+// 重新打开同一文件夹、Justified Layout 重新排版时，前端会对同一批文件反复调用
+// get_image_metadata / 预览缩略图；文件未变化时直接命中缓存，不再重复 stat + 解析。
+// 文件被覆盖（大小或 mtime 变化）时缓存自动失效。
+
+use std::collections::{HashMap, VecDeque};
+use std::fs::Metadata;
+use std::hash::Hash;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::time::SystemTime;
+
+/// 文件指纹：大小 + 修改时间，任一变化即视为新文件
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FileStamp {
+    pub size: u64,
+    pub modified: Option<SystemTime>,
+}
+
+impl FileStamp {
+    pub fn from_metadata(metadata: &Metadata) -> Self {
+        Self {
+            size: metadata.len(),
+            modified: metadata.modified().ok(),
+        }
+    }
+
+    /// 读取文件指纹；文件不存在或无法 stat 时返回 None
+    pub fn of(path: &Path) -> Option<Self> {
+        std::fs::metadata(path)
+            .ok()
+            .map(|m| Self::from_metadata(&m))
+    }
+}
+
+struct CacheState<K, V> {
+    entries: HashMap<K, (FileStamp, V)>,
+    /// 插入顺序，超过容量时淘汰最早的条目
+    order: VecDeque<K>,
+}
+
+/// 容量有限的文件结果缓存
+pub struct FileStampCache<K, V> {
+    capacity: usize,
+    state: Mutex<CacheState<K, V>>,
+}
+
+impl<K: Eq + Hash + Clone, V: Clone> FileStampCache<K, V> {
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            capacity: capacity.max(1),
+            state: Mutex::new(CacheState {
+                entries: HashMap::new(),
+                order: VecDeque::new(),
+            }),
+        }
+    }
+
+    /// 指纹一致时返回缓存值；指纹不一致的旧条目顺带移除
+    pub fn get(&self, key: &K, stamp: FileStamp) -> Option<V> {
+        let mut state = self.state.lock().unwrap_or_else(|e| e.into_inner());
+        match state.entries.get(key) {
+            Some((cached, value)) if *cached == stamp => Some(value.clone()),
+            Some(_) => {
+                state.entries.remove(key);
+                state.order.retain(|k| k != key);
+                None
+            }
+            None => None,
+        }
+    }
+
+    pub fn insert(&self, key: K, stamp: FileStamp, value: V) {
+        let mut state = self.state.lock().unwrap_or_else(|e| e.into_inner());
+        if state.entries.insert(key.clone(), (stamp, value)).is_none() {
+            state.order.push_back(key);
+        }
+        while state.entries.len() > self.capacity {
+            let Some(oldest) = state.order.pop_front() else {
+                break;
+            };
+            state.entries.remove(&oldest);
+        }
+    }
+
+    pub fn clear(&self) {
+        let mut state = self.state.lock().unwrap_or_else(|e| e.into_inner());
+        state.entries.clear();
+        state.order.clear();
+    }
+}
+
+/// 以规范化路径为键的缓存（大多数场景）
+pub type PathCache<V> = FileStampCache<PathBuf, V>;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::{Duration, UNIX_EPOCH};
+
+    fn len<K, V>(cache: &FileStampCache<K, V>) -> usize {
+        cache.state.lock().unwrap().entries.len()
+    }
+
+    fn stamp(size: u64, secs: u64) -> FileStamp {
+        FileStamp {
+            size,
+            modified: Some(UNIX_EPOCH + Duration::from_secs(secs)),
+        }
+    }
+
+    #[test]
+    fn hit_requires_matching_stamp() {
+        let cache: PathCache<u32> = FileStampCache::new(4);
+        let key = PathBuf::from("/tmp/a.png");
+        cache.insert(key.clone(), stamp(10, 1), 42);
+
+        assert_eq!(cache.get(&key, stamp(10, 1)), Some(42));
+        // 文件被覆盖：mtime 变化即失效，并清掉旧条目
+        assert_eq!(cache.get(&key, stamp(10, 2)), None);
+        assert_eq!(len(&cache), 0);
+    }
+
+    #[test]
+    fn evicts_oldest_entries_beyond_capacity() {
+        let cache: FileStampCache<u32, u32> = FileStampCache::new(2);
+        cache.insert(1, stamp(1, 1), 1);
+        cache.insert(2, stamp(1, 1), 2);
+        cache.insert(1, stamp(1, 1), 10);
+        cache.insert(3, stamp(1, 1), 3);
+
+        assert_eq!(len(&cache), 2);
+        assert_eq!(cache.get(&1, stamp(1, 1)), None);
+        assert_eq!(cache.get(&2, stamp(1, 1)), Some(2));
+        assert_eq!(cache.get(&3, stamp(1, 1)), Some(3));
+    }
+
+    #[test]
+    fn reads_stamp_from_disk() {
+        let path =
+            std::env::temp_dir().join(format!("picnexus_file_cache_{}.bin", std::process::id()));
+        std::fs::write(&path, b"12345").unwrap();
+        let result = FileStamp::of(&path);
+        let _ = std::fs::remove_file(&path);
+
+        assert_eq!(result.map(|s| s.size), Some(5));
+        assert!(FileStamp::of(&path).is_none());
+    }
+}
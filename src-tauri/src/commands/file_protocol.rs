@@ -0,0 +1,224 @@
+// src-tauri/src/commands/file_protocol.rs
+// picnexus-file:// 自定义协议：让前端直接用 <img src> 显示本地图片，不再经 IPC 传 base64
+//
+// URL 形式（由前端 convertFileSrc(path, 'picnexus-file') 生成）：
+//   macOS / Linux: picnexus-file://localhost/<encodeURIComponent(路径)>?token=...&max=1200
+//   Windows:       http://picnexus-file.localhost/<encodeURIComponent(路径)>?token=...&max=1200
+//
+// - token：进程启动时随机生成，只能通过 get_file_protocol_token 命令获取，
+//   防止外部网页或注入的 <img> 借协议读取本机文件
+// - max：可选，长边超过该值时缩小后返回（预览用），不传则返回原文件
+
+use std::borrow::Cow;
+use std::path::PathBuf;
+use std::sync::OnceLock;
+
+use rand::Rng;
+use tauri::http::{header, Request, Response, StatusCode};
+
+use crate::log_utils::safe_path;
+
+pub const FILE_PROTOCOL_SCHEME: &str = "picnexus-file";
+
+/// 允许通过协议读取的扩展名（SVG 可携带脚本，不开放）
+const ALLOWED_EXTENSIONS: &[&str] = &[
+    "jpg", "jpeg", "png", "gif", "webp", "bmp", "avif", "ico", "tif", "tiff",
+];
+
+/// 缩放预览长边的取值范围
+pub(crate) const MIN_PREVIEW_SIDE: u32 = 16;
+pub(crate) const MAX_PREVIEW_SIDE: u32 = 4096;
+
+static FILE_PROTOCOL_TOKEN: OnceLock<String> = OnceLock::new();
+
+fn protocol_token() -> &'static str {
+    FILE_PROTOCOL_TOKEN.get_or_init(|| {
+        let bytes: [u8; 16] = rand::thread_rng().gen();
+        hex::encode(bytes)
+    })
+}
+
+/// 解析后的协议请求
+#[derive(Debug, PartialEq, Eq)]
+struct FileRequest {
+    path: PathBuf,
+    max_side: Option<u32>,
+}
+
+/// 校验并解析请求；失败时返回应答的状态码
+fn parse_file_request(
+    path: &str,
+    query: Option<&str>,
+    token: &str,
+) -> Result<FileRequest, StatusCode> {
+    let mut request_token: Option<Cow<'_, str>> = None;
+    let mut max_side = None;
+    for (key, value) in url::form_urlencoded::parse(query.unwrap_or("").as_bytes()) {
+        match key.as_ref() {
+            "token" => request_token = Some(value),
+            "max" => {
+                max_side = value
+                    .parse::<u32>()
+                    .ok()
+                    .map(|v| v.clamp(MIN_PREVIEW_SIDE, MAX_PREVIEW_SIDE))
+            }
+            _ => {}
+        }
+    }
+    if request_token.as_deref() != Some(token) {
+        return Err(StatusCode::FORBIDDEN);
+    }
+
+    let decoded =
+        urlencoding::decode(path.trim_start_matches('/')).map_err(|_| StatusCode::BAD_REQUEST)?;
+    let file_path = PathBuf::from(decoded.as_ref());
+    if !file_path.is_absolute() {
+        return Err(StatusCode::BAD_REQUEST);
+    }
+
+    let allowed = file_path
+        .extension()
+        .and_then(|e| e.to_str())
+        .is_some_and(|ext| ALLOWED_EXTENSIONS.contains(&ext.to_ascii_lowercase().as_str()));
+    if !allowed {
+        return Err(StatusCode::FORBIDDEN);
+    }
+
+    Ok(FileRequest {
+        path: file_path,
+        max_side,
+    })
+}
+
+fn status_response(status: StatusCode) -> Response<Vec<u8>> {
+    Response::builder()
+        .status(status)
+        .body(Vec::new())
+        .unwrap_or_default()
+}
+
+fn image_response(bytes: Vec<u8>, mime: &str) -> Response<Vec<u8>> {
+    Response::builder()
+        .status(StatusCode::OK)
+        .header(header::CONTENT_TYPE, mime)
+        // 本地文件可能被覆盖（压缩、重新截图），不让 WebView 长期缓存
+        .header(header::CACHE_CONTROL, "no-cache")
+        .body(bytes)
+        .unwrap_or_else(|_| status_response(StatusCode::INTERNAL_SERVER_ERROR))
+}
+
+async fn serve(file: FileRequest) -> Response<Vec<u8>> {
+    if !tokio::fs::metadata(&file.path)
+        .await
+        .is_ok_and(|m| m.is_file())
+    {
+        return status_response(StatusCode::NOT_FOUND);
+    }
+
+    match file.max_side {
+        Some(max_side) => {
+            match super::image_compress::render_preview(file.path.clone(), max_side).await {
+                Ok((bytes, mime)) => image_response(bytes.to_vec(), mime),
+                Err(e) => {
+                    log::warn!(
+                        "[FileProtocol] 生成预览失败 {}: {}",
+                        safe_path(&file.path.to_string_lossy()),
+                        e
+                    );
+                    status_response(StatusCode::UNPROCESSABLE_ENTITY)
+                }
+            }
+        }
+        None => match tokio::fs::read(&file.path).await {
+            Ok(bytes) => {
+                let mime = mime_guess::from_path(&file.path).first_or_octet_stream();
+                image_response(bytes, mime.essence_str())
+            }
+            Err(e) => {
+                log::warn!(
+                    "[FileProtocol] 读取文件失败 {}: {}",
+                    safe_path(&file.path.to_string_lossy()),
+                    e
+                );
+                status_response(StatusCode::NOT_FOUND)
+            }
+        },
+    }
+}
+
+/// 协议处理入口（在 main.rs 中通过 register_asynchronous_uri_scheme_protocol 注册）
+pub async fn handle_file_request(request: Request<Vec<u8>>) -> Response<Vec<u8>> {
+    let uri = request.uri();
+    match parse_file_request(uri.path(), uri.query(), protocol_token()) {
+        Ok(file) => serve(file).await,
+        Err(status) => status_response(status),
+    }
+}
+
+/// 获取 picnexus-file 协议的访问令牌
+#[tauri::command]
+pub fn get_file_protocol_token() -> String {
+    protocol_token().to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::Path;
+
+    fn sample_path() -> String {
+        let path: &Path = if cfg!(windows) {
+            Path::new(r"C:\Users\me\Pictures\a b.PNG")
+        } else {
+            Path::new("/home/me/Pictures/a b.PNG")
+        };
+        format!("/{}", urlencoding::encode(&path.to_string_lossy()))
+    }
+
+    #[test]
+    fn parses_encoded_path_token_and_max() {
+        let parsed =
+            parse_file_request(&sample_path(), Some("token=t0k&max=99999"), "t0k").unwrap();
+        assert!(parsed.path.ends_with("a b.PNG"));
+        assert_eq!(parsed.max_side, Some(MAX_PREVIEW_SIDE));
+
+        let original = parse_file_request(&sample_path(), Some("token=t0k"), "t0k").unwrap();
+        assert_eq!(original.max_side, None);
+    }
+
+    #[test]
+    fn rejects_missing_or_wrong_token() {
+        assert_eq!(
+            parse_file_request(&sample_path(), None, "t0k"),
+            Err(StatusCode::FORBIDDEN)
+        );
+        assert_eq!(
+            parse_file_request(&sample_path(), Some("token=nope"), "t0k"),
+            Err(StatusCode::FORBIDDEN)
+        );
+    }
+
+    #[test]
+    fn rejects_relative_paths_and_non_images() {
+        assert_eq!(
+            parse_file_request("/a.png", Some("token=t0k"), "t0k"),
+            Err(StatusCode::BAD_REQUEST)
+        );
+        let secret = if cfg!(windows) {
+            r"C:\Users\me\.ssh\id_rsa"
+        } else {
+            "/home/me/.ssh/id_rsa"
+        };
+        let path = format!("/{}", urlencoding::encode(secret));
+        assert_eq!(
+            parse_file_request(&path, Some("token=t0k"), "t0k"),
+            Err(StatusCode::FORBIDDEN)
+        );
+    }
+
+    #[test]
+    fn token_is_stable_within_process() {
+        assert_eq!(protocol_token().len(), 32);
+        assert_eq!(get_file_protocol_token(), protocol_token());
+    }
+}
@@ -5,7 +5,10 @@ use base64::{engine::general_purpose::STANDARD, Engine as _};
 use serde::{Deserialize, Serialize};
 use tauri::{Emitter, Window};
 
-use super::utils::read_file_bytes;
+use super::host_capabilities;
+use super::net_limiter::NetworkLimiter;
+use super::task_registry::TaskRegistry;
+use super::utils::{read_file_bytes, remember_uploaded_source};
 use crate::error::{AppError, IntoAppError};
 use crate::log_utils::{safe_path, safe_url, summarize_text};
 
@@ -41,9 +44,6 @@ struct GithubContent {
     sha: String,
 }
 
-/// 文件大小限制：25MB（GitHub API 限制）
-const MAX_FILE_SIZE: u64 = 25 * 1024 * 1024;
-
 /// 上传文件到 GitHub
 #[tauri::command]
 #[allow(clippy::too_many_arguments)] // Tauri IPC 参数与前端上传配置一一对应，保留现有调用面。
@@ -56,7 +56,41 @@ pub async fn upload_to_github(
     repo: String,
     branch: String,
     path: String,
+    limiter: tauri::State<'_, NetworkLimiter>,
+    tasks: tauri::State<'_, TaskRegistry>,
 ) -> Result<GithubUploadResult, AppError> {
+    tasks
+        .run(
+            Some(&id),
+            upload_to_github_inner(
+                window,
+                id.clone(),
+                file_path.clone(),
+                github_token,
+                owner,
+                repo,
+                branch,
+                path,
+                limiter,
+            ),
+        )
+        .await
+        .inspect(|_| remember_uploaded_source(&file_path))
+}
+
+#[allow(clippy::too_many_arguments)]
+async fn upload_to_github_inner(
+    window: Window,
+    id: String,
+    file_path: String,
+    github_token: String,
+    owner: String,
+    repo: String,
+    branch: String,
+    path: String,
+    limiter: tauri::State<'_, NetworkLimiter>,
+) -> Result<GithubUploadResult, AppError> {
+    let _permit = limiter.acquire("github").await;
     log::info!("[GitHub] 开始上传文件: {}", safe_path(&file_path));
 
     // 发送进度: 0% - 读取文件
@@ -73,15 +107,15 @@ pub async fn upload_to_github(
     );
 
     // 1. 读取文件
-    let (buffer, file_size) = read_file_bytes(&file_path, MAX_FILE_SIZE).await?;
-
-    // 2. 验证文件大小（限制 25MB）
-    if file_size > MAX_FILE_SIZE {
-        return Err(AppError::validation(format!(
-            "文件大小 ({:.2}MB) 超过 GitHub API 限制 (25MB)",
-            file_size as f64 / 1024.0 / 1024.0
-        )));
-    }
+    let (buffer, file_size) =
+        read_file_bytes(&file_path, host_capabilities::read_limit("github")).await?;
+
+    // 2. 按图床能力表校验文件大小（GitHub 不限制格式）
+    let ext = std::path::Path::new(&file_path)
+        .extension()
+        .and_then(|e| e.to_str())
+        .unwrap_or_default();
+    host_capabilities::check_admission("github", ext, file_size)?;
 
     // 3. 获取文件名
     let file_name = std::path::Path::new(&file_path)
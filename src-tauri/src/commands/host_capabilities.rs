@@ -0,0 +1,271 @@
+// src-tauri/src/commands/host_capabilities.rs
+// 图床能力注册表：各后端的上传限制与功能支持
+// 上传命令的准入校验（大小、格式）与前端展示都以此表为准，新增或调整限制只改这里
+
+use serde::Serialize;
+
+use crate::error::AppError;
+
+/// 未声明大小上限的图床读取文件时的兜底上限（防止一次性读入超大文件）
+pub const DEFAULT_READ_LIMIT: u64 = 50 * 1024 * 1024;
+
+const MB: u64 = 1024 * 1024;
+
+const FORMATS_BASIC: &[&str] = &["jpg", "jpeg", "png", "gif"];
+const FORMATS_WEBP: &[&str] = &["jpg", "jpeg", "png", "gif", "webp"];
+const FORMATS_WEBP_BMP: &[&str] = &["jpg", "jpeg", "png", "gif", "webp", "bmp"];
+
+/// 单个图床的能力描述
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct HostCapabilities {
+    /// 服务 ID（与前端 ServiceType 一致）
+    pub service: &'static str,
+    /// 显示名称（用于错误提示）
+    pub display_name: &'static str,
+    /// 单文件大小上限（字节），None 表示平台未公开限制
+    pub max_file_size: Option<u64>,
+    /// GIF 单独的大小上限，None 表示与普通图片相同
+    pub max_gif_size: Option<u64>,
+    /// 允许的扩展名（小写），空表示不限制
+    pub allowed_formats: &'static [&'static str],
+    /// 是否需要登录 Cookie
+    pub needs_cookie: bool,
+    /// 平台是否提供删除能力（删除链接或删除 API）
+    pub supports_delete: bool,
+    /// 是否走分片上传
+    pub supports_multipart: bool,
+}
+
+const fn host(service: &'static str, display_name: &'static str) -> HostCapabilities {
+    HostCapabilities {
+        service,
+        display_name,
+        max_file_size: None,
+        max_gif_size: None,
+        allowed_formats: &[],
+        needs_cookie: false,
+        supports_delete: false,
+        supports_multipart: false,
+    }
+}
+
+/// 内置图床能力表
+pub static HOST_CAPABILITIES: &[HostCapabilities] = &[
+    HostCapabilities {
+        needs_cookie: true,
+        ..host("weibo", "微博")
+    },
+    HostCapabilities {
+        supports_delete: true,
+        ..host("r2", "Cloudflare R2")
+    },
+    HostCapabilities {
+        max_file_size: Some(15 * MB),
+        allowed_formats: FORMATS_BASIC,
+        ..host("jd", "京东")
+    },
+    HostCapabilities {
+        allowed_formats: FORMATS_BASIC,
+        needs_cookie: true,
+        ..host("nowcoder", "牛客")
+    },
+    HostCapabilities {
+        allowed_formats: FORMATS_WEBP,
+        ..host("qiyu", "七鱼")
+    },
+    HostCapabilities {
+        allowed_formats: FORMATS_WEBP,
+        needs_cookie: true,
+        ..host("zhihu", "知乎")
+    },
+    HostCapabilities {
+        needs_cookie: true,
+        supports_multipart: true,
+        ..host("nami", "纳米")
+    },
+    HostCapabilities {
+        max_file_size: Some(10 * MB),
+        allowed_formats: FORMATS_WEBP,
+        needs_cookie: true,
+        ..host("bilibili", "哔哩哔哩")
+    },
+    HostCapabilities {
+        max_file_size: Some(200 * MB),
+        allowed_formats: FORMATS_WEBP_BMP,
+        needs_cookie: true,
+        ..host("chaoxing", "超星")
+    },
+    HostCapabilities {
+        max_file_size: Some(5 * MB),
+        allowed_formats: FORMATS_WEBP_BMP,
+        supports_delete: true,
+        ..host("smms", "SM.MS")
+    },
+    HostCapabilities {
+        max_file_size: Some(25 * MB),
+        supports_delete: true,
+        ..host("github", "GitHub API")
+    },
+    HostCapabilities {
+        max_file_size: Some(20 * MB),
+        max_gif_size: Some(200 * MB),
+        allowed_formats: &["jpg", "jpeg", "png", "gif", "apng", "tiff", "bmp", "webp"],
+        supports_delete: true,
+        ..host("imgur", "Imgur")
+    },
+    HostCapabilities {
+        supports_delete: true,
+        ..host("tencent", "腾讯云 COS")
+    },
+    HostCapabilities {
+        supports_delete: true,
+        ..host("aliyun", "阿里云 OSS")
+    },
+    HostCapabilities {
+        supports_delete: true,
+        ..host("qiniu", "七牛云")
+    },
+    HostCapabilities {
+        supports_delete: true,
+        ..host("upyun", "又拍云")
+    },
+];
+
+/// 按服务 ID 查找能力描述
+pub fn capabilities_for(service: &str) -> Option<&'static HostCapabilities> {
+    HOST_CAPABILITIES.iter().find(|c| c.service == service)
+}
+
+impl HostCapabilities {
+    /// 指定扩展名适用的大小上限
+    pub fn max_size_for(&self, ext: &str) -> Option<u64> {
+        if ext.eq_ignore_ascii_case("gif") {
+            self.max_gif_size.or(self.max_file_size)
+        } else {
+            self.max_file_size
+        }
+    }
+
+    /// 读取文件时的上限：取所有格式中最大的限制，未声明时使用兜底值
+    pub fn read_limit(&self) -> u64 {
+        self.max_gif_size
+            .into_iter()
+            .chain(self.max_file_size)
+            .max()
+            .unwrap_or(DEFAULT_READ_LIMIT)
+    }
+}
+
+/// 读取指定图床文件时的上限
+pub fn read_limit(service: &str) -> u64 {
+    capabilities_for(service).map_or(DEFAULT_READ_LIMIT, HostCapabilities::read_limit)
+}
+
+fn format_label(ext: &str) -> String {
+    match ext {
+        "webp" => "WebP".to_string(),
+        other => other.to_ascii_uppercase(),
+    }
+}
+
+/// 上传准入校验：格式与大小都必须符合图床限制
+///
+/// 未登记的服务不做限制
+pub fn check_admission(service: &str, ext: &str, file_size: u64) -> Result<(), AppError> {
+    let Some(caps) = capabilities_for(service) else {
+        return Ok(());
+    };
+
+    let ext = ext.to_ascii_lowercase();
+    if !caps.allowed_formats.is_empty() && !caps.allowed_formats.contains(&ext.as_str()) {
+        // jpeg 与 jpg 提示时合并
+        let labels: Vec<String> = caps
+            .allowed_formats
+            .iter()
+            .filter(|f| **f != "jpeg")
+            .map(|f| format_label(f))
+            .collect();
+        return Err(AppError::validation(format!(
+            "只支持 {} 格式的图片",
+            labels.join("、")
+        )));
+    }
+
+    if let Some(max) = caps.max_size_for(&ext) {
+        if file_size > max {
+            return Err(AppError::validation(format!(
+                "文件大小 ({:.2}MB) 超过{}限制 ({}MB)",
+                file_size as f64 / MB as f64,
+                caps.display_name,
+                max / MB
+            )));
+        }
+    }
+
+    Ok(())
+}
+
+/// 获取所有内置图床的能力描述
+#[tauri::command]
+pub fn get_host_capabilities() -> Vec<HostCapabilities> {
+    HOST_CAPABILITIES.to_vec()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn validation_message(result: Result<(), AppError>) -> String {
+        match result {
+            Err(AppError::Validation { message, .. }) => message,
+            other => panic!("应为 Validation 错误: {:?}", other),
+        }
+    }
+
+    #[test]
+    fn registry_has_unique_service_ids() {
+        let mut ids: Vec<_> = HOST_CAPABILITIES.iter().map(|c| c.service).collect();
+        let total = ids.len();
+        ids.sort_unstable();
+        ids.dedup();
+        assert_eq!(ids.len(), total);
+    }
+
+    #[test]
+    fn rejects_disallowed_format_with_readable_list() {
+        let message = validation_message(check_admission("jd", "webp", 1024));
+        assert_eq!(message, "只支持 JPG、PNG、GIF 格式的图片");
+        assert!(check_admission("jd", "JPEG", 1024).is_ok());
+    }
+
+    #[test]
+    fn rejects_oversized_file() {
+        let message = validation_message(check_admission("smms", "png", 6 * MB));
+        assert!(message.contains("SM.MS"));
+        assert!(message.contains("(5MB)"));
+        assert!(check_admission("smms", "png", 5 * MB).is_ok());
+    }
+
+    #[test]
+    fn gif_uses_its_own_limit() {
+        assert!(check_admission("imgur", "gif", 100 * MB).is_ok());
+        assert!(check_admission("imgur", "png", 100 * MB).is_err());
+        assert_eq!(read_limit("imgur"), 200 * MB);
+    }
+
+    #[test]
+    fn unknown_or_unrestricted_hosts_admit_everything() {
+        assert!(check_admission("custom_s3:abc", "exe", u64::MAX).is_ok());
+        assert!(check_admission("weibo", "heic", 500 * MB).is_ok());
+        assert_eq!(read_limit("weibo"), DEFAULT_READ_LIMIT);
+    }
+
+    #[test]
+    fn serializes_with_camel_case_fields() {
+        let value = serde_json::to_value(capabilities_for("nami").unwrap()).unwrap();
+        assert_eq!(value["needsCookie"], true);
+        assert_eq!(value["supportsMultipart"], true);
+        assert!(value["maxFileSize"].is_null());
+    }
+}
@@ -0,0 +1,213 @@
+// src-tauri/src/commands/host_health.rs
+// 图床健康记分板：按滚动窗口统计各图床的成功率、平均上传耗时与近期错误类型
+// 前端每次上传结束后上报结果，故障切换与设置页据此选择当前最健康的图床
+
+use serde::Serialize;
+use std::collections::{HashMap, VecDeque};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+/// 滚动窗口长度：只统计最近 1 小时内的上传
+const WINDOW: Duration = Duration::from_secs(60 * 60);
+/// 每个图床最多保留的样本数（窗口内上传特别多时只看最近的）
+const MAX_SAMPLES_PER_HOST: usize = 100;
+/// 返回的近期错误类型条数
+const RECENT_ERROR_LIMIT: usize = 5;
+
+struct Sample {
+    at: Instant,
+    ok: bool,
+    latency_ms: u64,
+    error_kind: Option<String>,
+}
+
+/// 单个图床的健康快照
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct HostHealth {
+    pub service: String,
+    /// 窗口内上传次数
+    pub attempts: usize,
+    pub successes: usize,
+    /// 成功率（0-1）
+    pub success_rate: f64,
+    /// 成功上传的平均耗时（毫秒），窗口内没有成功样本时为 None
+    pub avg_latency_ms: Option<f64>,
+    /// 近期错误类型，最新的在前
+    pub recent_errors: Vec<String>,
+}
+
+/// 健康记分板（Tauri State 管理）
+#[derive(Default)]
+pub struct HostHealthBoard {
+    samples: Mutex<HashMap<String, VecDeque<Sample>>>,
+}
+
+impl HostHealthBoard {
+    fn record_at(
+        &self,
+        service: &str,
+        ok: bool,
+        latency_ms: u64,
+        error_kind: Option<String>,
+        now: Instant,
+    ) {
+        let mut samples = self.samples.lock().unwrap_or_else(|e| e.into_inner());
+        let queue = samples.entry(service.to_string()).or_default();
+        queue.push_back(Sample {
+            at: now,
+            ok,
+            latency_ms,
+            error_kind: if ok { None } else { error_kind },
+        });
+        prune(queue, now);
+    }
+
+    /// 生成快照：按成功率降序、平均耗时升序排列，第一个即当前最健康的图床
+    fn snapshot_at(&self, now: Instant) -> Vec<HostHealth> {
+        let mut samples = self.samples.lock().unwrap_or_else(|e| e.into_inner());
+        samples.retain(|_, queue| {
+            prune(queue, now);
+            !queue.is_empty()
+        });
+
+        let mut result: Vec<HostHealth> = samples
+            .iter()
+            .map(|(service, queue)| summarize(service, queue))
+            .collect();
+        result.sort_by(|a, b| {
+            b.success_rate
+                .total_cmp(&a.success_rate)
+                .then_with(|| {
+                    let a_latency = a.avg_latency_ms.unwrap_or(f64::INFINITY);
+                    let b_latency = b.avg_latency_ms.unwrap_or(f64::INFINITY);
+                    a_latency.total_cmp(&b_latency)
+                })
+                .then_with(|| a.service.cmp(&b.service))
+        });
+        result
+    }
+}
+
+fn prune(queue: &mut VecDeque<Sample>, now: Instant) {
+    while queue
+        .front()
+        .is_some_and(|s| now.saturating_duration_since(s.at) > WINDOW)
+    {
+        queue.pop_front();
+    }
+    while queue.len() > MAX_SAMPLES_PER_HOST {
+        queue.pop_front();
+    }
+}
+
+fn summarize(service: &str, queue: &VecDeque<Sample>) -> HostHealth {
+    let attempts = queue.len();
+    let successes = queue.iter().filter(|s| s.ok).count();
+    let total_latency: u64 = queue.iter().filter(|s| s.ok).map(|s| s.latency_ms).sum();
+    let recent_errors = queue
+        .iter()
+        .rev()
+        .filter_map(|s| s.error_kind.clone())
+        .take(RECENT_ERROR_LIMIT)
+        .collect();
+
+    HostHealth {
+        service: service.to_string(),
+        attempts,
+        successes,
+        success_rate: if attempts == 0 {
+            0.0
+        } else {
+            successes as f64 / attempts as f64
+        },
+        avg_latency_ms: (successes > 0).then(|| total_latency as f64 / successes as f64),
+        recent_errors,
+    }
+}
+
+/// 上报一次上传结果
+#[tauri::command]
+pub fn record_host_outcome(
+    board: tauri::State<'_, HostHealthBoard>,
+    service: String,
+    ok: bool,
+    latency_ms: u64,
+    error_kind: Option<String>,
+) {
+    board.record_at(&service, ok, latency_ms, error_kind, Instant::now());
+}
+
+/// 获取各图床健康快照（最健康的在前）
+#[tauri::command]
+pub fn get_host_health(board: tauri::State<'_, HostHealthBoard>) -> Vec<HostHealth> {
+    board.snapshot_at(Instant::now())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn summarizes_success_rate_latency_and_recent_errors() {
+        let board = HostHealthBoard::default();
+        let now = Instant::now();
+        board.record_at("weibo", true, 1000, None, now);
+        board.record_at("weibo", true, 3000, None, now);
+        board.record_at("weibo", false, 500, Some("NETWORK_ERROR".into()), now);
+        board.record_at("weibo", false, 500, Some("RATE_LIMITED".into()), now);
+
+        let health = board.snapshot_at(now);
+        assert_eq!(health.len(), 1);
+        let weibo = &health[0];
+        assert_eq!(weibo.attempts, 4);
+        assert_eq!(weibo.successes, 2);
+        assert_eq!(weibo.success_rate, 0.5);
+        assert_eq!(weibo.avg_latency_ms, Some(2000.0));
+        assert_eq!(weibo.recent_errors, vec!["RATE_LIMITED", "NETWORK_ERROR"]);
+    }
+
+    #[test]
+    fn drops_samples_outside_the_window() {
+        let board = HostHealthBoard::default();
+        let start = Instant::now();
+        board.record_at("jd", false, 100, Some("UPLOAD_FAILED".into()), start);
+        board.record_at("jd", true, 100, None, start + WINDOW);
+
+        let health = board.snapshot_at(start + WINDOW + Duration::from_secs(1));
+        assert_eq!(health[0].attempts, 1);
+        assert_eq!(health[0].success_rate, 1.0);
+        assert!(health[0].recent_errors.is_empty());
+
+        assert!(board
+            .snapshot_at(start + WINDOW * 3)
+            .iter()
+            .all(|h| h.service != "jd"));
+    }
+
+    #[test]
+    fn caps_samples_per_host() {
+        let board = HostHealthBoard::default();
+        let now = Instant::now();
+        for _ in 0..MAX_SAMPLES_PER_HOST + 20 {
+            board.record_at("r2", true, 10, None, now);
+        }
+        assert_eq!(board.snapshot_at(now)[0].attempts, MAX_SAMPLES_PER_HOST);
+    }
+
+    #[test]
+    fn orders_healthiest_host_first() {
+        let board = HostHealthBoard::default();
+        let now = Instant::now();
+        board.record_at("weibo", false, 0, Some("COOKIE_EXPIRED".into()), now);
+        board.record_at("jd", true, 2000, None, now);
+        board.record_at("r2", true, 800, None, now);
+
+        let order: Vec<_> = board
+            .snapshot_at(now)
+            .into_iter()
+            .map(|h| h.service)
+            .collect();
+        assert_eq!(order, vec!["r2", "jd", "weibo"]);
+    }
+}
@@ -0,0 +1,617 @@
+// 图床间批量迁移：把历史记录中某个图床上的图片搬到另一个图床
+//
+// 工作流：
+//   migrate_images(filter, from_host, to_host) -> 只读查询历史记录，筛选出 from_host 上传成功的记录
+//   -> 优先使用本地原图（历史记录 file_path 仍存在），否则下载 from_host 链接到临时文件
+//   -> 按 CLI 导出的 to_host 配置限速上传 -> （verify 时）下载新链接与原图比对
+//   -> 返回旧→新链接映射
+//   -> export_migration_mapping 把映射导出为 CSV 或 sed 脚本，用于批量替换文章中的链接
+//
+// 历史记录写入始终由前端负责，这里只返回映射，由前端决定是否回写 results。
+
+use crate::atomic_file;
+use crate::cli;
+use crate::commands::deadline::{Deadline, REHOST_DEADLINE};
+use crate::commands::image_compare::compare_files;
+use crate::commands::link_checker::download_url_image_to_temp;
+use crate::commands::net_limiter::NetworkLimiter;
+use crate::error::AppError;
+use crate::log_utils::safe_path;
+use crate::server::upload_handler::{upload_single_file, ServerUploadConfig};
+use crate::url_utils;
+use futures::stream::{self, StreamExt};
+use serde::{Deserialize, Serialize};
+use sqlx::sqlite::SqliteConnectOptions;
+use sqlx::{ConnectOptions, Row};
+use std::path::Path;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+use tauri::{Emitter, Manager};
+use tokio::sync::Mutex as TokioMutex;
+use tokio::time::Instant;
+
+/// 默认同时迁移的图片数
+const DEFAULT_MIGRATE_CONCURRENCY: usize = 2;
+/// 两次上传之间的默认最小间隔，避免触发目标图床的频率限制
+const DEFAULT_MIGRATE_INTERVAL_MS: u64 = 1000;
+
+/// 迁移取消标志
+pub struct MigrationCancelFlag(pub Arc<AtomicBool>);
+
+/// 要迁移的历史记录范围（条件之间为 AND，均可省略）
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MigrateRecordsFilter {
+    /// 只迁移指定 ID 的记录
+    #[serde(default)]
+    pub history_ids: Option<Vec<String>>,
+    /// 按文件名模糊匹配（不区分大小写）
+    #[serde(default)]
+    pub keyword: Option<String>,
+    /// 上传时间下限（毫秒时间戳，含）
+    #[serde(default)]
+    pub since: Option<i64>,
+    /// 上传时间上限（毫秒时间戳，含）
+    #[serde(default)]
+    pub until: Option<i64>,
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MigrateOptions {
+    /// 同时迁移的图片数（1-5，默认 2）
+    #[serde(default)]
+    pub concurrency: Option<usize>,
+    /// 两次上传之间的最小间隔毫秒数（默认 1000）
+    #[serde(default)]
+    pub interval_ms: Option<u64>,
+    /// 上传后下载新链接与原图比对，不一致的记为失败（默认关闭，每张多一次下载）
+    #[serde(default)]
+    pub verify: bool,
+}
+
+/// 单条旧→新链接映射
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UrlMapping {
+    pub history_id: String,
+    pub old_url: String,
+    pub new_url: String,
+    /// 目标图床上已有成功上传，直接复用未重新上传
+    #[serde(default)]
+    pub reused: bool,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MigrationFailure {
+    pub history_id: String,
+    pub file_name: String,
+    pub old_url: String,
+    pub error: String,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MigrationResult {
+    pub from_host: String,
+    pub to_host: String,
+    pub total: usize,
+    pub mappings: Vec<UrlMapping>,
+    pub failures: Vec<MigrationFailure>,
+    pub cancelled: bool,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct MigrationProgress {
+    completed: usize,
+    total: usize,
+    history_id: String,
+    success: bool,
+}
+
+/// 待迁移的一条历史记录
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct MigrationCandidate {
+    history_id: String,
+    file_name: String,
+    local_path: Option<String>,
+    old_url: String,
+    /// 目标图床上已有的成功链接
+    existing_target_url: Option<String>,
+}
+
+/// 从 results JSON 中取出指定图床的成功链接
+fn service_url(results: &serde_json::Value, service_id: &str) -> Option<String> {
+    results.as_array()?.iter().find_map(|entry| {
+        if entry.get("serviceId")?.as_str()? != service_id
+            || entry.get("status")?.as_str()? != "success"
+        {
+            return None;
+        }
+        let url = entry.get("result")?.get("url")?.as_str()?;
+        (!url.is_empty()).then(|| url.to_string())
+    })
+}
+
+pub(crate) fn matches_filter(
+    filter: &MigrateRecordsFilter,
+    id: &str,
+    file_name: &str,
+    timestamp: i64,
+) -> bool {
+    if let Some(ids) = &filter.history_ids {
+        if !ids.iter().any(|wanted| wanted == id) {
+            return false;
+        }
+    }
+    if let Some(keyword) = filter
+        .keyword
+        .as_deref()
+        .map(str::trim)
+        .filter(|k| !k.is_empty())
+    {
+        if !file_name.to_lowercase().contains(&keyword.to_lowercase()) {
+            return false;
+        }
+    }
+    filter.since.is_none_or(|since| timestamp >= since)
+        && filter.until.is_none_or(|until| timestamp <= until)
+}
+
+/// 只读查询历史记录，返回 from_host 上传成功且满足筛选条件的记录
+async fn load_candidates(
+    db_path: &Path,
+    filter: &MigrateRecordsFilter,
+    from_host: &str,
+    to_host: &str,
+) -> Result<Vec<MigrationCandidate>, String> {
+    if !db_path.exists() {
+        return Ok(Vec::new());
+    }
+
+    // 只读连接：写入始终由前端 tauri-plugin-sql 负责
+    let mut conn = SqliteConnectOptions::new()
+        .filename(db_path)
+        .read_only(true)
+        .connect()
+        .await
+        .map_err(|e| format!("打开历史记录数据库失败: {}", e))?;
+
+    let rows = sqlx::query(
+        "SELECT id, timestamp, local_file_name, file_path, results \
+         FROM history_items ORDER BY timestamp ASC",
+    )
+    .fetch_all(&mut conn)
+    .await
+    .map_err(|e| format!("查询历史记录失败: {}", e))?;
+
+    let mut candidates = Vec::new();
+    for row in rows {
+        let id: String = row.try_get("id").map_err(|e| e.to_string())?;
+        let timestamp: i64 = row.try_get("timestamp").unwrap_or_default();
+        let file_name: String = row.try_get("local_file_name").unwrap_or_default();
+        if !matches_filter(filter, &id, &file_name, timestamp) {
+            continue;
+        }
+
+        let results: String = row.try_get("results").unwrap_or_default();
+        let results: serde_json::Value =
+            serde_json::from_str(&results).unwrap_or(serde_json::Value::Null);
+        let Some(old_url) = service_url(&results, from_host) else {
+            continue;
+        };
+
+        let local_path: Option<String> = row.try_get("file_path").ok().flatten();
+        candidates.push(MigrationCandidate {
+            history_id: id,
+            file_name,
+            local_path: local_path.filter(|p| !p.is_empty()),
+            old_url,
+            existing_target_url: service_url(&results, to_host),
+        });
+    }
+
+    Ok(candidates)
+}
+
+/// 上传节流：保证相邻两次上传的开始时间至少间隔 interval
+struct UploadPacer {
+    next_slot: TokioMutex<Instant>,
+    interval: Duration,
+}
+
+impl UploadPacer {
+    fn new(interval: Duration) -> Self {
+        Self {
+            next_slot: TokioMutex::new(Instant::now()),
+            interval,
+        }
+    }
+
+    async fn wait(&self) {
+        let slot = {
+            let mut next_slot = self.next_slot.lock().await;
+            let slot = (*next_slot).max(Instant::now());
+            *next_slot = slot + self.interval;
+            slot
+        };
+        tokio::time::sleep_until(slot).await;
+    }
+}
+
+/// 迁移单张图片：优先上传本地原图，本地文件不存在时下载旧链接
+///
+/// 下载 + 上传（+ 校验）共用 `REHOST_DEADLINE` 总时限（排队等并发名额、上传限速间隔不计入）
+async fn migrate_one(
+    candidate: &MigrationCandidate,
+    to_host: &str,
+    config: &ServerUploadConfig,
+    pacer: &UploadPacer,
+    limiter: &NetworkLimiter,
+    verify: bool,
+) -> Result<String, String> {
+    let local = candidate
+        .local_path
+        .as_deref()
+        .filter(|path| Path::new(path).is_file());
+
+    let mut deadline = Deadline::new("迁移图片", REHOST_DEADLINE);
+
+    // 下载的临时文件由守卫持有，上传结束（无论成败）后随 drop 删除
+    let (path, _downloaded) = match local {
+        Some(path) => (path.to_string(), None),
+        None => {
+            let _permit = deadline
+                .exclude(limiter.acquire(&url_utils::host_key(&candidate.old_url)))
+                .await;
+            let downloaded = deadline
+                .run(download_url_image_to_temp(&candidate.old_url))
+                .await
+                .and_then(|result| result)
+                .map_err(|e| e.to_string())?;
+            (downloaded.file.path_string(), Some(downloaded))
+        }
+    };
+
+    deadline.exclude(pacer.wait()).await;
+    let new_url = {
+        let _permit = deadline.exclude(limiter.acquire(to_host)).await;
+        deadline
+            .run(upload_single_file(&path, config))
+            .await
+            .map_err(|e| e.to_string())??
+    };
+    if !verify {
+        return Ok(new_url);
+    }
+
+    // 校验：下载新链接，与上传所用的文件比对（图床转码后哈希不同，按感知相似度判断）
+    let _permit = deadline
+        .exclude(limiter.acquire(&url_utils::host_key(&new_url)))
+        .await;
+    let uploaded = deadline
+        .run(download_url_image_to_temp(&new_url))
+        .await
+        .and_then(|result| result)
+        .map_err(|e| format!("校验下载失败: {}", e))?;
+    let comparison = compare_files(Path::new(&path), uploaded.file.path())
+        .await
+        .map_err(|e| format!("校验比对失败: {}", e))?;
+    if !comparison.matches {
+        return Err(format!(
+            "新链接的图片与原图不一致（{}x{} → {}x{}，相似度 {}）",
+            comparison.width_a,
+            comparison.height_a,
+            comparison.width_b,
+            comparison.height_b,
+            comparison
+                .similarity
+                .map_or("未知".to_string(), |s| format!("{:.0}%", s * 100.0))
+        ));
+    }
+    Ok(new_url)
+}
+
+/// 把 from_host 上的历史图片迁移到 to_host，返回旧→新链接映射
+#[tauri::command]
+pub async fn migrate_images(
+    app: tauri::AppHandle,
+    window: tauri::Window,
+    filter: Option<MigrateRecordsFilter>,
+    from_host: String,
+    to_host: String,
+    options: Option<MigrateOptions>,
+    cancel_flag: tauri::State<'_, MigrationCancelFlag>,
+) -> Result<MigrationResult, AppError> {
+    if from_host == to_host {
+        return Err(AppError::validation("源图床与目标图床不能相同"));
+    }
+    let filter = filter.unwrap_or_default();
+    let options = options.unwrap_or_default();
+    let (_, config) = cli::resolve_service_config(Some(&to_host)).map_err(AppError::config)?;
+
+    let db_path = crate::portable::history_db_path(&app)?;
+    let candidates = load_candidates(&db_path, &filter, &from_host, &to_host)
+        .await
+        .map_err(AppError::storage)?;
+
+    let cancel = cancel_flag.0.clone();
+    cancel.store(false, Ordering::SeqCst);
+
+    let total = candidates.len();
+    let concurrency = options
+        .concurrency
+        .unwrap_or(DEFAULT_MIGRATE_CONCURRENCY)
+        .clamp(1, 5);
+    let pacer = UploadPacer::new(Duration::from_millis(
+        options.interval_ms.unwrap_or(DEFAULT_MIGRATE_INTERVAL_MS),
+    ));
+    log::info!(
+        "[Migrate] 开始: {} -> {}, {} 条记录, 并发={}",
+        from_host,
+        to_host,
+        total,
+        concurrency
+    );
+
+    let mut mappings = Vec::new();
+    let mut failures = Vec::new();
+    let mut completed = 0;
+
+    let limiter = app.state::<NetworkLimiter>().inner().clone();
+    let verify = options.verify;
+    let (config, pacer, cancel_ref, limiter, target) =
+        (&config, &pacer, &cancel, &limiter, to_host.as_str());
+    let mut results = stream::iter(candidates)
+        .map(|candidate| async move {
+            if let Some(url) = &candidate.existing_target_url {
+                return (candidate.clone(), Ok(url.clone()), true);
+            }
+            if cancel_ref.load(Ordering::SeqCst) {
+                return (candidate, Err("已取消".to_string()), false);
+            }
+            let result = migrate_one(&candidate, target, config, pacer, limiter, verify).await;
+            (candidate, result, false)
+        })
+        .buffer_unordered(concurrency);
+
+    while let Some((candidate, result, reused)) = results.next().await {
+        completed += 1;
+        let _ = window.emit(
+            "migrate://progress",
+            MigrationProgress {
+                completed,
+                total,
+                history_id: candidate.history_id.clone(),
+                success: result.is_ok(),
+            },
+        );
+
+        match result {
+            Ok(new_url) => mappings.push(UrlMapping {
+                history_id: candidate.history_id,
+                old_url: candidate.old_url,
+                new_url,
+                reused,
+            }),
+            // 取消后剩余任务直接跳过，不计入失败
+            Err(_) if cancel.load(Ordering::SeqCst) => {}
+            Err(error) => {
+                log::warn!(
+                    "[Migrate] 迁移失败: {} - {}",
+                    safe_path(&candidate.file_name),
+                    error
+                );
+                failures.push(MigrationFailure {
+                    history_id: candidate.history_id,
+                    file_name: candidate.file_name,
+                    old_url: candidate.old_url,
+                    error,
+                });
+            }
+        }
+    }
+
+    let cancelled = cancel.load(Ordering::SeqCst);
+    log::info!(
+        "[Migrate] 完成: 成功 {}, 失败 {}, 取消={}",
+        mappings.len(),
+        failures.len(),
+        cancelled
+    );
+
+    Ok(MigrationResult {
+        from_host,
+        to_host,
+        total,
+        mappings,
+        failures,
+        cancelled,
+    })
+}
+
+/// 取消正在进行的迁移（已开始的上传会完成）
+#[tauri::command]
+pub async fn cancel_migration(
+    cancel_flag: tauri::State<'_, MigrationCancelFlag>,
+) -> Result<(), AppError> {
+    log::info!("[Migrate] 收到取消请求");
+    cancel_flag.0.store(true, Ordering::SeqCst);
+    Ok(())
+}
+
+#[derive(Debug, Clone, Copy, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum MappingFormat {
+    Csv,
+    Sed,
+}
+
+fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+/// CSV：history_id,old_url,new_url
+fn mappings_to_csv(mappings: &[UrlMapping]) -> String {
+    let mut out = String::from("history_id,old_url,new_url\n");
+    for mapping in mappings {
+        out.push_str(&format!(
+            "{},{},{}\n",
+            csv_field(&mapping.history_id),
+            csv_field(&mapping.old_url),
+            csv_field(&mapping.new_url)
+        ));
+    }
+    out
+}
+
+/// sed 基础正则中需要转义的字符（分隔符为 |）
+fn sed_escape_pattern(value: &str) -> String {
+    let mut out = String::with_capacity(value.len());
+    for c in value.chars() {
+        if matches!(c, '\\' | '|' | '.' | '*' | '[' | ']' | '^' | '$') {
+            out.push('\\');
+        }
+        out.push(c);
+    }
+    out
+}
+
+fn sed_escape_replacement(value: &str) -> String {
+    let mut out = String::with_capacity(value.len());
+    for c in value.chars() {
+        if matches!(c, '\\' | '|' | '&') {
+            out.push('\\');
+        }
+        out.push(c);
+    }
+    out
+}
+
+/// sed 脚本：每行一条 s|old|new|g，可直接 `sed -i -f mapping.sed *.md`
+fn mappings_to_sed(mappings: &[UrlMapping]) -> String {
+    mappings
+        .iter()
+        .filter(|mapping| !mapping.old_url.contains('\n') && !mapping.new_url.contains('\n'))
+        .map(|mapping| {
+            format!(
+                "s|{}|{}|g\n",
+                sed_escape_pattern(&mapping.old_url),
+                sed_escape_replacement(&mapping.new_url)
+            )
+        })
+        .collect()
+}
+
+/// 把迁移得到的链接映射导出为 CSV 或 sed 脚本
+#[tauri::command]
+pub async fn export_migration_mapping(
+    mappings: Vec<UrlMapping>,
+    format: MappingFormat,
+    path: String,
+) -> Result<usize, AppError> {
+    let content = match format {
+        MappingFormat::Csv => mappings_to_csv(&mappings),
+        MappingFormat::Sed => mappings_to_sed(&mappings),
+    };
+    atomic_file::write_async(&path, content)
+        .await
+        .map_err(|e| AppError::file_io(format!("写入映射文件失败: {}", e)))?;
+    log::info!(
+        "[Migrate] 已导出 {} 条映射: {}",
+        mappings.len(),
+        safe_path(&path)
+    );
+    Ok(mappings.len())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn mapping(old_url: &str, new_url: &str) -> UrlMapping {
+        UrlMapping {
+            history_id: "h1".to_string(),
+            old_url: old_url.to_string(),
+            new_url: new_url.to_string(),
+            reused: false,
+        }
+    }
+
+    #[test]
+    fn service_url_requires_success_status() {
+        let results: serde_json::Value = serde_json::from_str(
+            r#"[
+                {"serviceId": "weibo", "result": {"url": "https://wx1.sinaimg.cn/a.jpg"}, "status": "success"},
+                {"serviceId": "r2", "status": "failed", "error": "403"}
+            ]"#,
+        )
+        .unwrap();
+
+        assert_eq!(
+            service_url(&results, "weibo").as_deref(),
+            Some("https://wx1.sinaimg.cn/a.jpg")
+        );
+        assert_eq!(service_url(&results, "r2"), None);
+        assert_eq!(service_url(&serde_json::Value::Null, "weibo"), None);
+    }
+
+    #[test]
+    fn matches_filter_combines_conditions() {
+        let filter = MigrateRecordsFilter {
+            history_ids: None,
+            keyword: Some("Shot".to_string()),
+            since: Some(100),
+            until: Some(200),
+        };
+
+        assert!(matches_filter(&filter, "a", "screenshot.png", 150));
+        assert!(!matches_filter(&filter, "a", "photo.png", 150));
+        assert!(!matches_filter(&filter, "a", "screenshot.png", 250));
+
+        let by_id = MigrateRecordsFilter {
+            history_ids: Some(vec!["b".to_string()]),
+            ..Default::default()
+        };
+        assert!(!matches_filter(&by_id, "a", "x.png", 0));
+        assert!(matches_filter(&by_id, "b", "x.png", 0));
+    }
+
+    #[test]
+    fn csv_export_quotes_special_fields() {
+        let csv = mappings_to_csv(&[mapping("https://a/x.png?a=1,2", "https://b/\"y\".png")]);
+        assert_eq!(
+            csv,
+            "history_id,old_url,new_url\nh1,\"https://a/x.png?a=1,2\",\"https://b/\"\"y\"\".png\"\n"
+        );
+    }
+
+    #[test]
+    fn sed_export_escapes_regex_and_replacement() {
+        let sed = mappings_to_sed(&[mapping(
+            "https://wx1.sinaimg.cn/large/a.jpg",
+            "https://img.example.com/a.jpg?x=1&y=2",
+        )]);
+        assert_eq!(
+            sed,
+            "s|https://wx1\\.sinaimg\\.cn/large/a\\.jpg|https://img.example.com/a.jpg?x=1\\&y=2|g\n"
+        );
+    }
+
+    #[tokio::test]
+    async fn upload_pacer_spaces_consecutive_uploads() {
+        let pacer = UploadPacer::new(Duration::from_millis(30));
+        let start = Instant::now();
+        pacer.wait().await;
+        pacer.wait().await;
+        pacer.wait().await;
+        assert!(start.elapsed() >= Duration::from_millis(60));
+    }
+}
@@ -0,0 +1,170 @@
+// src-tauri/src/commands/http_client.rs
+// 全局 HTTP 客户端：连接池、HTTP/2、连接超时与 TCP keepalive 可在设置页调整
+//
+// 默认参数在网络不稳定时访问 sinaimg 等 CDN 表现较差（空闲连接被中间设备掐断、
+// HTTP/2 连接卡死），允许用户按需调整，保存后立即重建客户端。
+
+use std::sync::{Arc, LazyLock, RwLock};
+use std::time::Duration;
+
+use serde::Deserialize;
+
+use super::startup_profile;
+use crate::error::AppError;
+
+/// 整体请求超时（不随调优参数变化，各命令可按需单独覆盖）
+const REQUEST_TIMEOUT_SECS: u64 = 60;
+/// 连接池空闲超时
+const POOL_IDLE_TIMEOUT_SECS: u64 = 90;
+
+/// HTTP 客户端调优参数（由前端设置页传入，未启用时使用默认值）
+#[derive(Debug, Clone, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase", default)]
+pub struct HttpClientTuning {
+    /// 每个主机最多保持的空闲连接数（0 = 不复用连接）
+    pub pool_max_idle_per_host: usize,
+    /// 连接超时（秒）
+    pub connect_timeout_secs: u64,
+    /// 是否允许 HTTP/2；关闭后只使用 HTTP/1.1
+    pub http2: bool,
+    /// TCP keepalive 间隔（秒，0 = 关闭）
+    pub tcp_keepalive_secs: u64,
+}
+
+impl Default for HttpClientTuning {
+    fn default() -> Self {
+        Self {
+            pool_max_idle_per_host: 10,
+            connect_timeout_secs: 10,
+            http2: true,
+            tcp_keepalive_secs: 0,
+        }
+    }
+}
+
+impl HttpClientTuning {
+    fn validate(&self) -> Result<(), AppError> {
+        if self.pool_max_idle_per_host > 100 {
+            return Err(AppError::validation("每主机空闲连接数不能超过 100"));
+        }
+        if !(1..=120).contains(&self.connect_timeout_secs) {
+            return Err(AppError::validation("连接超时需在 1-120 秒之间"));
+        }
+        if self.tcp_keepalive_secs > 3600 {
+            return Err(AppError::validation("TCP keepalive 间隔不能超过 3600 秒"));
+        }
+        Ok(())
+    }
+}
+
+/// 按调优参数构建 reqwest 客户端
+pub fn build_http_client(tuning: &HttpClientTuning) -> Result<reqwest::Client, AppError> {
+    let mut builder = reqwest::Client::builder()
+        .timeout(Duration::from_secs(REQUEST_TIMEOUT_SECS))
+        .connect_timeout(Duration::from_secs(tuning.connect_timeout_secs))
+        .pool_idle_timeout(Duration::from_secs(POOL_IDLE_TIMEOUT_SECS))
+        .pool_max_idle_per_host(tuning.pool_max_idle_per_host)
+        .tcp_keepalive(
+            (tuning.tcp_keepalive_secs > 0).then(|| Duration::from_secs(tuning.tcp_keepalive_secs)),
+        );
+    if !tuning.http2 {
+        builder = builder.http1_only();
+    }
+    builder
+        .build()
+        .map_err(|e| AppError::network(format!("创建 HTTP 客户端失败: {}", e)))
+}
+
+/// 全局共享的 HTTP 客户端，调优参数变化时整体替换
+///
+/// 构建客户端要加载系统根证书，慢盘上可达数百毫秒；启动时只注册空槽位，首次请求时再构建
+#[derive(Default, Clone)]
+pub struct HttpClient(Arc<RwLock<Option<reqwest::Client>>>);
+
+/// 进程内唯一的客户端槽位，managed state 与拿不到 State 的入口共用
+static SHARED: LazyLock<HttpClient> = LazyLock::new(HttpClient::default);
+
+impl HttpClient {
+    /// 共享槽位的句柄：注册为 managed state，Server / CLI / 图床注册表等入口也从这里取客户端，
+    /// 设置页的调优参数对所有入口同时生效
+    pub fn shared() -> Self {
+        SHARED.clone()
+    }
+
+    /// 取当前客户端（reqwest::Client 内部是 Arc，克隆开销很小）
+    pub fn get(&self) -> reqwest::Client {
+        if let Some(client) = self.0.read().unwrap_or_else(|e| e.into_inner()).as_ref() {
+            return client.clone();
+        }
+
+        let mut slot = self.0.write().unwrap_or_else(|e| e.into_inner());
+        slot.get_or_insert_with(|| {
+            // 前端读取配置后会按调优参数重建
+            startup_profile::measure_deferred("http_client", || {
+                build_http_client(&HttpClientTuning::default()).unwrap_or_else(|e| {
+                    log::warn!("[HTTP Client] 创建失败: {:?}，使用默认配置", e);
+                    reqwest::Client::new()
+                })
+            })
+        })
+        .clone()
+    }
+
+    fn replace(&self, client: reqwest::Client) {
+        *self.0.write().unwrap_or_else(|e| e.into_inner()) = Some(client);
+    }
+}
+
+/// 应用 HTTP 客户端调优参数并重建全局客户端
+///
+/// 进行中的请求继续使用旧客户端直至完成，新请求立即使用新参数
+#[tauri::command]
+pub fn set_http_client_tuning(
+    tuning: Option<HttpClientTuning>,
+    http_client: tauri::State<'_, HttpClient>,
+) -> Result<(), AppError> {
+    let tuning = tuning.unwrap_or_default();
+    tuning.validate()?;
+    http_client.replace(build_http_client(&tuning)?);
+    log::info!("[HTTP Client] 已应用调优参数: {:?}", tuning);
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn missing_fields_fall_back_to_defaults() {
+        let tuning: HttpClientTuning =
+            serde_json::from_str(r#"{"http2": false, "tcpKeepaliveSecs": 30}"#).unwrap();
+        assert_eq!(
+            tuning,
+            HttpClientTuning {
+                http2: false,
+                tcp_keepalive_secs: 30,
+                ..HttpClientTuning::default()
+            }
+        );
+    }
+
+    #[test]
+    fn rejects_out_of_range_values() {
+        let zero_timeout = HttpClientTuning {
+            connect_timeout_secs: 0,
+            ..HttpClientTuning::default()
+        };
+        assert!(zero_timeout.validate().is_err());
+        assert!(HttpClientTuning::default().validate().is_ok());
+    }
+
+    #[test]
+    fn builds_client_with_http1_only() {
+        let tuning = HttpClientTuning {
+            http2: false,
+            pool_max_idle_per_host: 0,
+            ..HttpClientTuning::default()
+        };
+        assert!(build_http_client(&tuning).is_ok());
+    }
+}
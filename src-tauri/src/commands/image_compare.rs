@@ -0,0 +1,188 @@
+// src-tauri/src/commands/image_compare.rs
+// 图片比对：确认重新上传 / 迁移后的副本与原图一致
+//
+// 三个维度：
+// - 尺寸：读取文件头，不解码
+// - 哈希：文件 SHA-256，相同即逐字节一致
+// - 感知相似度：缩小到 9x8 灰度后计算差值哈希（dHash），并按平均亮度差加权；
+//   图床重新压缩（微博、知乎等会转码）后哈希必然不同，但相似度仍接近 1
+//
+// 迁移开启 verify 时用它校验新链接；前端在修复结果中调用 compare_images 展示比对结果。
+
+use std::path::{Path, PathBuf};
+
+use serde::Serialize;
+
+use super::image_compress::{check_pixel_limit, read_header_dimensions};
+use super::{cpu_pool, memory_budget, utils::sha256_file};
+use crate::error::AppError;
+use crate::path_utils;
+
+/// 尺寸相同且相似度不低于该值时视为同一张图（约等于 dHash 汉明距离 ≤ 6）
+const MATCH_THRESHOLD: f64 = 0.9;
+
+/// 差值哈希的采样尺寸：9 列比较出 8 个差值，8 行共 64 位
+const HASH_WIDTH: u32 = 9;
+const HASH_HEIGHT: u32 = 8;
+
+/// compare_images 的返回值
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ImageComparison {
+    pub width_a: u32,
+    pub height_a: u32,
+    pub width_b: u32,
+    pub height_b: u32,
+    pub same_dimensions: bool,
+    pub same_hash: bool,
+    /// 感知相似度（0-1）；任一图片无法解码（如 SVG、AVIF）时为 None
+    pub similarity: Option<f64>,
+    /// 综合判断：哈希相同，或尺寸相同且相似度达到阈值
+    pub matches: bool,
+}
+
+/// 感知指纹
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct Fingerprint {
+    dhash: u64,
+    /// 平均亮度（0-255），区分纯色图等 dHash 相同的情况
+    mean_luma: f64,
+}
+
+/// 由 9x8 灰度像素计算指纹（行优先）
+fn fingerprint_from_luma(pixels: &[u8]) -> Fingerprint {
+    let mut dhash = 0u64;
+    for row in 0..HASH_HEIGHT as usize {
+        for col in 0..(HASH_WIDTH - 1) as usize {
+            let left = pixels[row * HASH_WIDTH as usize + col];
+            let right = pixels[row * HASH_WIDTH as usize + col + 1];
+            dhash = (dhash << 1) | u64::from(left > right);
+        }
+    }
+    let mean_luma = pixels.iter().map(|&p| f64::from(p)).sum::<f64>() / pixels.len() as f64;
+    Fingerprint { dhash, mean_luma }
+}
+
+fn perceptual_similarity(a: &Fingerprint, b: &Fingerprint) -> f64 {
+    let structure = 1.0 - f64::from((a.dhash ^ b.dhash).count_ones()) / 64.0;
+    let brightness = 1.0 - (a.mean_luma - b.mean_luma).abs() / 255.0;
+    structure * brightness
+}
+
+fn is_match(same_hash: bool, same_dimensions: bool, similarity: Option<f64>) -> bool {
+    same_hash || (same_dimensions && similarity.is_some_and(|s| s >= MATCH_THRESHOLD))
+}
+
+/// 解码并计算指纹（在 CPU 线程池中执行）
+fn fingerprint_file(path: &Path) -> Result<Fingerprint, AppError> {
+    let (width, height) = read_header_dimensions(path)?;
+    check_pixel_limit(width, height)?;
+    let img = image::open(path).map_err(|e| AppError::file_io(format!("无法打开图片: {}", e)))?;
+    let luma = img.thumbnail_exact(HASH_WIDTH, HASH_HEIGHT).to_luma8();
+    Ok(fingerprint_from_luma(luma.as_raw()))
+}
+
+async fn fingerprint(path: PathBuf) -> Result<Fingerprint, AppError> {
+    let _reservation = memory_budget::reserve_for_image(&path, 1).await;
+    cpu_pool::spawn(move || fingerprint_file(&path)).await?
+}
+
+fn resolve(raw: &str) -> Result<PathBuf, AppError> {
+    let path = path_utils::canonicalize(path_utils::normalize_input(raw))
+        .map_err(|e| AppError::file_io(format!("无法解析文件路径 '{}': {}", raw, e)))?;
+    if !path.is_file() {
+        return Err(AppError::validation(format!("'{}' 不是有效的文件", raw)));
+    }
+    Ok(path)
+}
+
+/// 比对两个本地图片文件
+pub(crate) async fn compare_files(a: &Path, b: &Path) -> Result<ImageComparison, AppError> {
+    let (width_a, height_a) = read_header_dimensions(a)?;
+    let (width_b, height_b) = read_header_dimensions(b)?;
+    let (hash_a, hash_b) = tokio::try_join!(
+        sha256_file(&a.to_string_lossy()),
+        sha256_file(&b.to_string_lossy())
+    )?;
+    let same_dimensions = (width_a, height_a) == (width_b, height_b);
+    let same_hash = hash_a == hash_b;
+
+    let similarity = if same_hash {
+        Some(1.0)
+    } else {
+        match tokio::try_join!(fingerprint(a.to_path_buf()), fingerprint(b.to_path_buf())) {
+            Ok((fa, fb)) => Some(perceptual_similarity(&fa, &fb)),
+            Err(e) => {
+                log::debug!("[图片比对] 无法计算感知相似度: {}", e);
+                None
+            }
+        }
+    };
+
+    Ok(ImageComparison {
+        width_a,
+        height_a,
+        width_b,
+        height_b,
+        same_dimensions,
+        same_hash,
+        similarity,
+        matches: is_match(same_hash, same_dimensions, similarity),
+    })
+}
+
+/// 比对两张本地图片：尺寸、文件哈希与感知相似度
+#[tauri::command]
+pub async fn compare_images(path_a: String, path_b: String) -> Result<ImageComparison, AppError> {
+    let a = resolve(&path_a)?;
+    let b = resolve(&path_b)?;
+    compare_files(&a, &b).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// 水平渐变，`reverse` 时方向相反
+    fn gradient(reverse: bool, offset: u8) -> Vec<u8> {
+        (0..HASH_HEIGHT)
+            .flat_map(|_| {
+                (0..HASH_WIDTH).map(move |x| {
+                    let step = if reverse { HASH_WIDTH - 1 - x } else { x } as u8;
+                    offset + step * 20
+                })
+            })
+            .collect()
+    }
+
+    #[test]
+    fn fingerprint_tracks_structure_and_brightness() {
+        let base = fingerprint_from_luma(&gradient(false, 10));
+        assert_eq!(base.dhash, 0);
+        assert_eq!(perceptual_similarity(&base, &base), 1.0);
+
+        // 同样的结构，亮度轻微变化（重新压缩）：仍然相似
+        let recompressed = fingerprint_from_luma(&gradient(false, 14));
+        assert!(perceptual_similarity(&base, &recompressed) > MATCH_THRESHOLD);
+
+        // 方向相反：每一位都不同
+        let mirrored = fingerprint_from_luma(&gradient(true, 10));
+        assert_eq!(mirrored.dhash, u64::MAX);
+        assert_eq!(perceptual_similarity(&base, &mirrored), 0.0);
+
+        // 纯黑与纯白的 dHash 相同，但亮度差使相似度为 0
+        let black = fingerprint_from_luma(&[0; 72]);
+        let white = fingerprint_from_luma(&[255; 72]);
+        assert_eq!(black.dhash, white.dhash);
+        assert_eq!(perceptual_similarity(&black, &white), 0.0);
+    }
+
+    #[test]
+    fn match_requires_hash_or_same_size_and_similarity() {
+        assert!(is_match(true, false, None));
+        assert!(is_match(false, true, Some(0.95)));
+        assert!(!is_match(false, true, Some(0.5)));
+        assert!(!is_match(false, false, Some(1.0)));
+        assert!(!is_match(false, true, None));
+    }
+}
@@ -1,6 +1,7 @@
 use std::fs;
-use std::path::Path;
+use std::path::{Path, PathBuf};
 use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, LazyLock};
 
 use base64::{engine::general_purpose::STANDARD, Engine as _};
 use image::imageops::FilterType;
@@ -8,12 +9,28 @@ use image::GenericImageView;
 use serde::Serialize;
 use tauri::Manager;
 
+use super::cpu_pool;
+use super::disk_space;
+use super::file_cache::{FileStamp, FileStampCache};
+use super::memory_budget;
+use super::task_registry::TaskRegistry;
+use super::thumbnail_cache;
 use crate::error::AppError;
 use crate::log_utils::safe_path;
+use crate::path_utils;
 
 /// 全局原子计数器，为压缩临时文件生成唯一后缀，避免同名文件在同一毫秒并发压缩时覆盖。
 static TEMP_FILE_COUNTER: AtomicU64 = AtomicU64::new(0);
 
+/// 预览图缓存条目上限（单张预览约数百 KB）
+const PREVIEW_CACHE_CAPACITY: usize = 64;
+
+type PreviewBytes = (Arc<Vec<u8>>, &'static str);
+
+/// 按 (路径, 长边, 大小, mtime) 缓存的预览图，重复打开同一张图时不再解码
+static PREVIEW_CACHE: LazyLock<FileStampCache<(PathBuf, u32), PreviewBytes>> =
+    LazyLock::new(|| FileStampCache::new(PREVIEW_CACHE_CAPACITY));
+
 #[derive(Serialize)]
 pub struct CompressResult {
     /// 压缩后文件路径（临时目录）
@@ -57,15 +74,39 @@ pub async fn compress_image(
     max_long_side: u32,
     output_format: String,
     strip_exif: bool,
+    task_id: Option<String>,
+    tasks: tauri::State<'_, TaskRegistry>,
 ) -> Result<CompressResult, AppError> {
-    let path = Path::new(&file_path);
+    tasks
+        .run(
+            task_id.as_deref(),
+            compress_image_inner(
+                app,
+                file_path,
+                quality,
+                max_long_side,
+                output_format,
+                strip_exif,
+            ),
+        )
+        .await
+}
 
-    if !path.exists() {
+async fn compress_image_inner(
+    app: tauri::AppHandle,
+    file_path: String,
+    quality: u8,
+    max_long_side: u32,
+    output_format: String,
+    strip_exif: bool,
+) -> Result<CompressResult, AppError> {
+    let input_path = path_utils::normalize_input(&file_path);
+
+    if !input_path.exists() {
         return Err(AppError::file_io(format!("文件不存在: {}", file_path)));
     }
 
-    let canonical_path = path
-        .canonicalize()
+    let canonical_path = path_utils::canonicalize(&input_path)
         .map_err(|e| AppError::file_io(format!("无法解析文件路径: {}", e)))?;
     let path = canonical_path.as_path();
 
@@ -86,16 +127,15 @@ pub async fn compress_image(
 
     let quality = quality.clamp(1, 100);
 
-    // 在阻塞线程池中执行 CPU 密集的图片处理
+    // 解码缓冲 + 缩放 / 量化输出，按两份像素缓冲占用内存预算，超出时排队等待
+    let _reservation = memory_budget::reserve_for_image(path, 2).await;
+
+    // 在 CPU 线程池中执行 CPU 密集的图片处理
     let app_handle = app.clone();
     let file_path_owned = file_path.clone();
+    let canonical = canonical_path.clone();
 
-    tokio::task::spawn_blocking(move || {
-        let path = Path::new(&file_path_owned);
-        let canonical = path.canonicalize().map_err(|e| {
-            AppError::file_io(format!("无法解析文件路径: {}", e))
-        })?;
-
+    cpu_pool::spawn(move || {
         // 输入像素预检：image::open 用默认 limits 会把整张图加载到内存（u32::MAX 像素，
         // 数 GB 分配），针对 50000x50000 这种恶意/异常文件会先 OOM。先用 imagesize 只读
         // header 拿到原始尺寸，超过 check_pixel_limit 上限直接拒绝，避免落到 decoder。
@@ -141,9 +181,10 @@ pub async fn compress_image(
             AppError::file_io(format!("无法获取临时目录: {}", e))
         })?;
         let compress_dir = temp_dir.join("picnexus_compress");
-        fs::create_dir_all(&compress_dir).map_err(|e| {
-            AppError::file_io(format!("无法创建压缩临时目录: {}", e))
-        })?;
+        // 压缩结果通常不大于原图，按原图大小预检剩余空间
+        disk_space::preflight(&compress_dir, original_size)?;
+        fs::create_dir_all(&compress_dir)
+            .map_err(|e| AppError::write_failed("创建压缩临时目录", &e))?;
 
         let stem = canonical.file_stem()
             .and_then(|s| s.to_str())
@@ -190,9 +231,8 @@ pub async fn compress_image(
                     encoded_bytes
                 };
 
-                fs::write(&output_path, &final_bytes).map_err(|e| {
-                    AppError::file_io(format!("写入 JPEG 文件失败: {}", e))
-                })?;
+                fs::write(&output_path, &final_bytes)
+                    .map_err(|e| AppError::write_failed("写入 JPEG 文件", &e))?;
             }
             "webp" => {
                 let rgba = processed.to_rgba8();
@@ -200,15 +240,13 @@ pub async fn compress_image(
                 let encoded = encoder
                     .encode_simple(false, quality as f32)
                     .map_err(|e| AppError::file_io(format!("WebP 编码失败: {:?}", e)))?;
-                fs::write(&output_path, &*encoded).map_err(|e| {
-                    AppError::file_io(format!("写入 WebP 文件失败: {}", e))
-                })?;
+                fs::write(&output_path, &*encoded)
+                    .map_err(|e| AppError::write_failed("写入 WebP 文件", &e))?;
             }
             "png" => {
                 let png_bytes = encode_png_lossy(&processed, final_w, final_h, quality)?;
-                fs::write(&output_path, &png_bytes).map_err(|e| {
-                    AppError::file_io(format!("写入 PNG 文件失败: {}", e))
-                })?;
+                fs::write(&output_path, &png_bytes)
+                    .map_err(|e| AppError::write_failed("写入 PNG 文件", &e))?;
             }
             _ => {
                 processed.save(&output_path).map_err(|e| {
@@ -248,8 +286,7 @@ pub async fn compress_image(
             format: out_format_name.to_string(),
         })
     })
-    .await
-    .map_err(|e| AppError::external(format!("压缩任务执行失败: {}", e)))?
+    .await?
 }
 
 /// 清理压缩临时文件
@@ -262,13 +299,14 @@ pub async fn cleanup_compressed_files(
         .path()
         .temp_dir()
         .map_err(|e| AppError::file_io(format!("无法获取临时目录: {}", e)))?;
+    // 临时目录也要规范化后再比较前缀（macOS 的 /var 是符号链接）
     let compress_dir = temp_dir.join("picnexus_compress");
+    let compress_dir = path_utils::canonicalize(&compress_dir).unwrap_or(compress_dir);
 
     let mut cleaned = 0u32;
     for file_path in &file_paths {
-        let path = Path::new(file_path);
         // 安全检查：只删除压缩临时目录下的文件
-        if let Ok(canonical) = path.canonicalize() {
+        if let Ok(canonical) = path_utils::canonicalize(path_utils::normalize_input(file_path)) {
             if canonical.starts_with(&compress_dir) {
                 if fs::remove_file(&canonical).is_ok() {
                     cleaned += 1;
@@ -303,7 +341,7 @@ fn calculate_target_size(orig_w: u32, orig_h: u32, max_long_side: u32) -> (u32,
 }
 
 /// 提取 JPEG 中的 EXIF APP1 段（包含 marker + length + payload）
-fn extract_jpeg_exif_segment(jpeg: &[u8]) -> Option<Vec<u8>> {
+pub(super) fn extract_jpeg_exif_segment(jpeg: &[u8]) -> Option<Vec<u8>> {
     if jpeg.len() < 4 || jpeg[0] != 0xFF || jpeg[1] != 0xD8 {
         return None;
     }
@@ -366,7 +404,7 @@ fn inject_jpeg_exif_segment(jpeg: &[u8], exif_segment: &[u8]) -> Option<Vec<u8>>
 }
 
 /// 像素数预检：防止超大图片导致内存耗尽（上限 5000 万像素）
-fn check_pixel_limit(width: u32, height: u32) -> Result<(), AppError> {
+pub(super) fn check_pixel_limit(width: u32, height: u32) -> Result<(), AppError> {
     let pixel_count = (width as u64)
         .checked_mul(height as u64)
         .ok_or_else(|| AppError::file_io("图片尺寸溢出"))?;
@@ -379,7 +417,7 @@ fn check_pixel_limit(width: u32, height: u32) -> Result<(), AppError> {
     Ok(())
 }
 
-fn read_header_dimensions(path: &Path) -> Result<(u32, u32), AppError> {
+pub(super) fn read_header_dimensions(path: &Path) -> Result<(u32, u32), AppError> {
     let size = imagesize::size(path)
         .map_err(|e| AppError::validation(format!("无法解析图片头，已拒绝处理: {}", e)))?;
     let width =
@@ -523,14 +561,24 @@ fn encode_png_lossy(
 pub async fn strip_exif_only(
     app: tauri::AppHandle,
     file_path: String,
+    task_id: Option<String>,
+    tasks: tauri::State<'_, TaskRegistry>,
 ) -> Result<CompressResult, AppError> {
-    let path = Path::new(&file_path);
-    if !path.exists() {
+    tasks
+        .run(task_id.as_deref(), strip_exif_only_inner(app, file_path))
+        .await
+}
+
+async fn strip_exif_only_inner(
+    app: tauri::AppHandle,
+    file_path: String,
+) -> Result<CompressResult, AppError> {
+    let input_path = path_utils::normalize_input(&file_path);
+    if !input_path.exists() {
         return Err(AppError::file_io(format!("文件不存在: {}", file_path)));
     }
 
-    let canonical_path = path
-        .canonicalize()
+    let canonical_path = path_utils::canonicalize(&input_path)
         .map_err(|e| AppError::file_io(format!("无法解析文件路径: {}", e)))?;
 
     let original_size = fs::metadata(&canonical_path)
@@ -547,14 +595,13 @@ pub async fn strip_exif_only(
         return Err(AppError::validation("GIF 动图不支持 EXIF 剥离"));
     }
 
+    let _reservation = memory_budget::reserve_for_image(&canonical_path, 1).await;
+
     let app_handle = app.clone();
     let file_path_owned = file_path.clone();
+    let canonical = canonical_path.clone();
 
-    tokio::task::spawn_blocking(move || {
-        let canonical = Path::new(&file_path_owned)
-            .canonicalize()
-            .map_err(|e| AppError::file_io(format!("无法解析文件路径: {}", e)))?;
-
+    cpu_pool::spawn(move || {
         // 同 compress_image：image::open 前先用 imagesize header 校验原始像素数，
         // 避免超大图直接 OOM
         let (header_w, header_h) = read_header_dimensions(&canonical)?;
@@ -635,8 +682,106 @@ pub async fn strip_exif_only(
             format: out_ext.to_string(),
         })
     })
+    .await?
+}
+
+/// 清空内存中的预览图缓存（clear_thumbnail_cache 调用）
+pub(crate) fn clear_preview_cache() {
+    PREVIEW_CACHE.clear();
+}
+
+/// PNG/WebP 可能带透明通道，用 PNG 预览保留透明；其他格式用 JPEG（编码快、体积小）
+fn preview_mime(path: &Path) -> &'static str {
+    let ext = path
+        .extension()
+        .and_then(|e| e.to_str())
+        .map(|e| e.to_lowercase())
+        .unwrap_or_default();
+    if matches!(ext.as_str(), "png" | "webp") {
+        "image/png"
+    } else {
+        "image/jpeg"
+    }
+}
+
+/// 生成预览图：长边超过 max_side 时缩小，返回编码后的字节与 MIME 类型
+///
+/// 压缩预览与 picnexus-file 协议共用：文件未变化时依次查内存缓存与磁盘缩略图缓存，
+/// 都未命中时先占用内存预算，再在 CPU 线程池中解码，结果写回两级缓存。
+pub(crate) async fn render_preview(path: PathBuf, max_side: u32) -> Result<PreviewBytes, AppError> {
+    let Some(stamp) = FileStamp::of(&path) else {
+        return Err(AppError::file_io(format!(
+            "文件不存在: {}",
+            path.to_string_lossy()
+        )));
+    };
+    let key = (path.clone(), max_side);
+    if let Some(cached) = PREVIEW_CACHE.get(&key, stamp) {
+        return Ok(cached);
+    }
+
+    let mime = preview_mime(&path);
+    let disk_path = path.clone();
+    let from_disk = tokio::task::spawn_blocking(move || {
+        thumbnail_cache::load(&disk_path, max_side, stamp, mime)
+    })
     .await
-    .map_err(|e| AppError::external(format!("EXIF 剥离任务执行失败: {}", e)))?
+    .ok()
+    .flatten();
+    if let Some(bytes) = from_disk {
+        let preview = (Arc::new(bytes), mime);
+        PREVIEW_CACHE.insert(key, stamp, preview.clone());
+        return Ok(preview);
+    }
+
+    let _reservation = memory_budget::reserve_for_image(&path, 1).await;
+    let encode_path = path.clone();
+    let (bytes, mime) = cpu_pool::spawn(move || encode_preview(&encode_path, max_side)).await??;
+    let preview = (Arc::new(bytes), mime);
+    PREVIEW_CACHE.insert(key, stamp, preview.clone());
+
+    let bytes = Arc::clone(&preview.0);
+    tokio::task::spawn_blocking(move || {
+        thumbnail_cache::store(&path, max_side, stamp, mime, &bytes)
+    });
+    Ok(preview)
+}
+
+fn encode_preview(path: &Path, max_side: u32) -> Result<(Vec<u8>, &'static str), AppError> {
+    let mime = preview_mime(path);
+
+    // 预览路径同样要前置像素上限检查：避免给压缩面板预览传来一张 50000x50000 时
+    // 在 image::open 阶段就把进程拖崩
+    let (header_w, header_h) = read_header_dimensions(path)?;
+    check_pixel_limit(header_w, header_h)?;
+
+    let img = image::open(path).map_err(|e| AppError::file_io(format!("无法打开图片: {}", e)))?;
+
+    let (w, h) = img.dimensions();
+    let long_side = w.max(h);
+
+    let final_img = if long_side > max_side {
+        img.resize(max_side, max_side, FilterType::Triangle)
+    } else {
+        img
+    };
+
+    if mime == "image/png" {
+        let mut buf = Vec::new();
+        final_img
+            .write_to(&mut std::io::Cursor::new(&mut buf), image::ImageFormat::Png)
+            .map_err(|e| AppError::file_io(format!("预览 PNG 编码失败: {}", e)))?;
+        Ok((buf, "image/png"))
+    } else {
+        let rgb = final_img.to_rgb8();
+        let (fw, fh) = rgb.dimensions();
+        let mut buf = std::io::Cursor::new(Vec::new());
+        let mut encoder = image::codecs::jpeg::JpegEncoder::new_with_quality(&mut buf, 85);
+        encoder
+            .encode(rgb.as_raw(), fw, fh, image::ExtendedColorType::Rgb8)
+            .map_err(|e| AppError::file_io(format!("预览 JPEG 编码失败: {}", e)))?;
+        Ok((buf.into_inner(), "image/jpeg"))
+    }
 }
 
 /// 读取图片文件为 base64 data URI（用于压缩预览）
@@ -647,62 +792,18 @@ pub async fn read_image_as_base64(
     file_path: String,
     max_side: Option<u32>,
 ) -> Result<String, AppError> {
-    let path = Path::new(&file_path);
+    let path = path_utils::normalize_input(&file_path);
     if !path.exists() {
         return Err(AppError::file_io(format!("文件不存在: {}", file_path)));
     }
 
-    let ext = path
-        .extension()
-        .and_then(|e| e.to_str())
-        .map(|e| e.to_lowercase())
-        .unwrap_or_default();
-
-    // PNG/WebP 可能带透明通道，用 PNG 预览保留透明；其他格式用 JPEG（编码快、体积小）
-    let has_alpha = matches!(ext.as_str(), "png" | "webp");
-
     let max_side = max_side.unwrap_or(1200);
-    let file_path_clone = file_path.clone();
-
-    tokio::task::spawn_blocking(move || {
-        // 预览路径同样要前置像素上限检查：避免给压缩面板预览传来一张 50000x50000 时
-        // 在 image::open 阶段就把进程拖崩
-        let (header_w, header_h) = read_header_dimensions(Path::new(&file_path_clone))?;
-        check_pixel_limit(header_w, header_h)?;
-
-        let img = image::open(&file_path_clone)
-            .map_err(|e| AppError::file_io(format!("无法打开图片: {}", e)))?;
-
-        let (w, h) = img.dimensions();
-        let long_side = w.max(h);
-
-        let final_img = if long_side > max_side {
-            img.resize(max_side, max_side, FilterType::Triangle)
-        } else {
-            img
-        };
-
-        if has_alpha {
-            let mut buf = Vec::new();
-            final_img
-                .write_to(&mut std::io::Cursor::new(&mut buf), image::ImageFormat::Png)
-                .map_err(|e| AppError::file_io(format!("预览 PNG 编码失败: {}", e)))?;
-            let b64 = STANDARD.encode(&buf);
-            Ok(format!("data:image/png;base64,{}", b64))
-        } else {
-            let rgb = final_img.to_rgb8();
-            let (fw, fh) = rgb.dimensions();
-            let mut buf = std::io::Cursor::new(Vec::new());
-            let mut encoder = image::codecs::jpeg::JpegEncoder::new_with_quality(&mut buf, 85);
-            encoder
-                .encode(rgb.as_raw(), fw, fh, image::ExtendedColorType::Rgb8)
-                .map_err(|e| AppError::file_io(format!("预览 JPEG 编码失败: {}", e)))?;
-            let b64 = STANDARD.encode(buf.into_inner());
-            Ok(format!("data:image/jpeg;base64,{}", b64))
-        }
-    })
-    .await
-    .map_err(|e| AppError::external(format!("读取图片失败: {}", e)))?
+    let (bytes, mime) = render_preview(path, max_side).await?;
+    Ok(format!(
+        "data:{};base64,{}",
+        mime,
+        STANDARD.encode(bytes.as_slice())
+    ))
 }
 
 #[cfg(test)]
@@ -5,18 +5,56 @@
 use std::fs::{self, File};
 use std::io::Read;
 use std::path::Path;
+use std::sync::LazyLock;
 
 use serde::Serialize;
 
+use super::file_cache::{FileStamp, PathCache};
+use super::image_compress::extract_jpeg_exif_segment;
+use super::link_checker::detect_image_extension;
 use crate::error::AppError;
+use crate::path_utils;
 use crate::server::upload_handler::MAX_SERVER_UPLOAD_SIZE;
 
 const SVG_METADATA_READ_LIMIT: usize = 256 * 1024;
 
+/// 读取 EXIF 的字节上限：APP1 段最长 64KB，之前通常只有很短的 APP0
+const EXIF_READ_LIMIT: u64 = 128 * 1024;
+
+/// 识别格式时读取的文件头字节数（与链接检测的嗅探长度一致，足够识别 SVG）
+const FORMAT_SNIFF_LEN: u64 = 512;
+
+/// 按扩展名可直接认定的图片格式
+const KNOWN_EXTENSIONS: &[&str] = &[
+    "jpg", "jpeg", "png", "gif", "webp", "bmp", "tiff", "tif", "ico", "avif", "svg",
+];
+
+/// EXIF Orientation 标签
+const EXIF_TAG_ORIENTATION: u16 = 0x0112;
+
+/// IFD0 中的相机厂商、型号与修改时间标签
+const EXIF_TAG_MAKE: u16 = 0x010F;
+const EXIF_TAG_MODEL: u16 = 0x0110;
+const EXIF_TAG_DATE_TIME: u16 = 0x0132;
+
+/// 指向 Exif 子 IFD / GPS IFD 的指针标签
+const EXIF_TAG_EXIF_IFD: u16 = 0x8769;
+const EXIF_TAG_GPS_IFD: u16 = 0x8825;
+
+/// Exif 子 IFD 中的原始拍摄时间
+const EXIF_TAG_DATE_TIME_ORIGINAL: u16 = 0x9003;
+
+/// 元数据缓存条目上限（每条几十字节，足够覆盖一个大图库文件夹）
+const METADATA_CACHE_CAPACITY: usize = 8192;
+
+/// 按 (路径, 大小, mtime) 缓存的元数据，文件未变化时跳过头部解析
+static METADATA_CACHE: LazyLock<PathCache<ImageMetadata>> =
+    LazyLock::new(|| PathCache::new(METADATA_CACHE_CAPACITY));
+
 /// 图片元数据结构（简化版）
 /// 用于前端 Justified Layout 布局和历史记录存储
 /// 移除了 color_type 和 has_alpha 字段，因为实际使用中不需要
-#[derive(Serialize)]
+#[derive(Clone, Serialize)]
 pub struct ImageMetadata {
     /// 图片宽度（像素）
     pub width: u32,
@@ -28,6 +66,209 @@ pub struct ImageMetadata {
     pub file_size: u64,
     /// 图片格式（jpg, png, webp, gif, bmp 等）
     pub format: String,
+    /// EXIF 方向（1-8，1 为正常）；5-8 旋转了 90°/270°，width / height 已按显示方向交换
+    pub orientation: u16,
+}
+
+/// 图片 EXIF 中与隐私相关的字段
+/// 用于去除 EXIF 前向用户展示将被移除的内容，以及在历史记录中保存拍摄时间
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ImageExif {
+    /// 拍摄时间（ISO 8601 本地时间，不含时区，如 2024-05-01T08:30:15）
+    pub capture_date: Option<String>,
+    /// 相机厂商
+    pub camera_make: Option<String>,
+    /// 相机型号
+    pub camera_model: Option<String>,
+    /// 是否包含 GPS 位置信息
+    pub has_gps: bool,
+    /// EXIF 方向（1-8，缺失时为 1）
+    pub orientation: u16,
+}
+
+/// TIFF 结构（EXIF 负载）的只读访问器，按头部声明的字节序读取 IFD 条目
+struct TiffReader<'a> {
+    data: &'a [u8],
+    big_endian: bool,
+}
+
+impl<'a> TiffReader<'a> {
+    fn new(data: &'a [u8]) -> Option<Self> {
+        let big_endian = match data.get(0..2)? {
+            b"MM" => true,
+            b"II" => false,
+            _ => return None,
+        };
+        let reader = Self { data, big_endian };
+        (reader.u16_at(2)? == 42).then_some(reader)
+    }
+
+    fn u16_at(&self, offset: usize) -> Option<u16> {
+        let bytes: [u8; 2] = self.data.get(offset..offset + 2)?.try_into().ok()?;
+        Some(if self.big_endian {
+            u16::from_be_bytes(bytes)
+        } else {
+            u16::from_le_bytes(bytes)
+        })
+    }
+
+    fn u32_at(&self, offset: usize) -> Option<u32> {
+        let bytes: [u8; 4] = self.data.get(offset..offset + 4)?.try_into().ok()?;
+        Some(if self.big_endian {
+            u32::from_be_bytes(bytes)
+        } else {
+            u32::from_le_bytes(bytes)
+        })
+    }
+
+    /// IFD0 的偏移
+    fn ifd0(&self) -> Option<usize> {
+        Some(self.u32_at(4)? as usize)
+    }
+
+    /// 在 IFD 中查找标签，返回 12 字节条目的偏移
+    fn find_entry(&self, ifd: usize, tag: u16) -> Option<usize> {
+        let entries = self.u16_at(ifd)? as usize;
+        (0..entries)
+            .map(|i| ifd + 2 + i * 12)
+            .find(|&entry| self.u16_at(entry) == Some(tag))
+    }
+
+    fn short(&self, ifd: usize, tag: u16) -> Option<u16> {
+        self.u16_at(self.find_entry(ifd, tag)? + 8)
+    }
+
+    fn long(&self, ifd: usize, tag: u16) -> Option<u32> {
+        self.u32_at(self.find_entry(ifd, tag)? + 8)
+    }
+
+    /// ASCII 值：不超过 4 字节时内联在条目中，否则条目存放数据偏移；去掉末尾的 NUL 与空白
+    fn ascii(&self, ifd: usize, tag: u16) -> Option<String> {
+        let entry = self.find_entry(ifd, tag)?;
+        let count = self.u32_at(entry + 4)? as usize;
+        let start = if count <= 4 {
+            entry + 8
+        } else {
+            self.u32_at(entry + 8)? as usize
+        };
+        let bytes = self.data.get(start..start.checked_add(count)?)?;
+        let text = String::from_utf8_lossy(bytes);
+        let text = text.trim_end_matches('\0').trim();
+        (!text.is_empty()).then(|| text.to_string())
+    }
+}
+
+/// 从 TIFF 结构（EXIF 负载）的 IFD0 中读取 Orientation
+fn parse_exif_orientation(tiff: &[u8]) -> Option<u16> {
+    let reader = TiffReader::new(tiff)?;
+    reader
+        .short(reader.ifd0()?, EXIF_TAG_ORIENTATION)
+        .filter(|value| (1..=8).contains(value))
+}
+
+/// EXIF 日期 "YYYY:MM:DD HH:MM:SS" 转为 ISO 8601 本地时间 "YYYY-MM-DDTHH:MM:SS"；
+/// 全零等占位值返回 None
+fn normalize_exif_datetime(raw: &str) -> Option<String> {
+    let bytes = raw.as_bytes();
+    if bytes.len() < 19 || bytes[4] != b':' || bytes[7] != b':' || bytes[10] != b' ' {
+        return None;
+    }
+    let digits_ok = [0..4, 5..7, 8..10, 11..13, 14..16, 17..19]
+        .into_iter()
+        .all(|range| bytes[range].iter().all(u8::is_ascii_digit));
+    if !digits_ok || bytes[13] != b':' || bytes[16] != b':' || &raw[0..4] == "0000" {
+        return None;
+    }
+    Some(format!(
+        "{}-{}-{}T{}",
+        &raw[0..4],
+        &raw[5..7],
+        &raw[8..10],
+        &raw[11..19]
+    ))
+}
+
+/// 从 TIFF 结构中提取隐私相关字段：拍摄时间优先 DateTimeOriginal，缺失时回退 IFD0 的 DateTime
+fn parse_exif_fields(tiff: &[u8]) -> Option<ImageExif> {
+    let reader = TiffReader::new(tiff)?;
+    let ifd0 = reader.ifd0()?;
+    let original_date = reader
+        .long(ifd0, EXIF_TAG_EXIF_IFD)
+        .and_then(|exif_ifd| reader.ascii(exif_ifd as usize, EXIF_TAG_DATE_TIME_ORIGINAL));
+    let capture_date = original_date
+        .or_else(|| reader.ascii(ifd0, EXIF_TAG_DATE_TIME))
+        .and_then(|raw| normalize_exif_datetime(&raw));
+    let has_gps = reader
+        .long(ifd0, EXIF_TAG_GPS_IFD)
+        .and_then(|gps_ifd| reader.u16_at(gps_ifd as usize))
+        .is_some_and(|entries| entries > 0);
+
+    Some(ImageExif {
+        capture_date,
+        camera_make: reader.ascii(ifd0, EXIF_TAG_MAKE),
+        camera_model: reader.ascii(ifd0, EXIF_TAG_MODEL),
+        has_gps,
+        orientation: reader
+            .short(ifd0, EXIF_TAG_ORIENTATION)
+            .filter(|value| (1..=8).contains(value))
+            .unwrap_or(1),
+    })
+}
+
+/// 读取 JPEG 的 EXIF 负载（TIFF 数据）；没有 EXIF 时返回 None
+fn read_jpeg_exif_payload(path: &Path) -> Option<Vec<u8>> {
+    let mut buffer = Vec::new();
+    File::open(path)
+        .ok()?
+        .take(EXIF_READ_LIMIT)
+        .read_to_end(&mut buffer)
+        .ok()?;
+    let segment = extract_jpeg_exif_segment(&buffer)?;
+    // 段结构：FF E1 + 2 字节长度 + "Exif\0\0" + TIFF 数据
+    Some(segment.get(10..)?.to_vec())
+}
+
+/// 读取 JPEG 的 EXIF 方向；没有 EXIF 或解析失败时返回 None
+fn read_jpeg_orientation(path: &Path) -> Option<u16> {
+    parse_exif_orientation(&read_jpeg_exif_payload(path)?)
+}
+
+/// 读取文件头，按魔数识别格式
+fn sniff_image_format(path: &Path) -> Option<&'static str> {
+    let mut buffer = Vec::with_capacity(FORMAT_SNIFF_LEN as usize);
+    File::open(path)
+        .ok()?
+        .take(FORMAT_SNIFF_LEN)
+        .read_to_end(&mut buffer)
+        .ok()?;
+    detect_image_extension(&buffer)
+}
+
+/// 确定图片格式：优先用扩展名；没有扩展名或扩展名不是图片格式时
+/// （如剪贴板临时文件）按文件头识别，仍识别不出时沿用原扩展名或 "unknown"
+fn resolve_image_format(path: &Path) -> String {
+    let extension = path
+        .extension()
+        .and_then(|ext| ext.to_str())
+        .map(|ext| ext.to_lowercase());
+    if let Some(ext) = extension.as_deref() {
+        if KNOWN_EXTENSIONS.contains(&ext) {
+            return ext.to_string();
+        }
+    }
+    sniff_image_format(path)
+        .map(str::to_string)
+        .or(extension)
+        .unwrap_or_else(|| "unknown".to_string())
+}
+
+/// 按 EXIF 方向换算成显示尺寸：5-8 需要旋转 90°/270°，宽高互换
+fn oriented_size(width: u32, height: u32, orientation: u16) -> (u32, u32) {
+    if (5..=8).contains(&orientation) {
+        (height, width)
+    } else {
+        (width, height)
+    }
 }
 
 fn parse_svg_number(value: &str) -> Option<f64> {
@@ -118,6 +359,7 @@ fn read_svg_metadata_snippet(path: &Path) -> Result<String, AppError> {
 /// 性能优化：使用 imagesize crate 只读取图片头部（通常 16-64 字节），
 /// 而不是使用 image::open() 完整解码整个图片。
 /// 对于 50MB 的大图，性能从 2-5 秒提升到 <10ms。
+/// 结果按 (路径, 大小, mtime) 缓存，同一文件重复查询时只做一次 stat。
 ///
 /// # 参数
 /// - `file_path`: 图片文件的绝对路径
@@ -127,7 +369,7 @@ fn read_svg_metadata_snippet(path: &Path) -> Result<String, AppError> {
 /// - `Err(AppError)`: 文件读取或图片解析错误
 #[tauri::command]
 pub fn get_image_metadata(file_path: String) -> Result<ImageMetadata, AppError> {
-    let path = Path::new(&file_path);
+    let path = path_utils::normalize_input(&file_path);
 
     // 1. 检查文件是否存在
     if !path.exists() {
@@ -135,22 +377,27 @@ pub fn get_image_metadata(file_path: String) -> Result<ImageMetadata, AppError>
     }
 
     // 1.5 路径规范化，防止路径穿越攻击（如 ../../etc/passwd）
-    let canonical_path = path
-        .canonicalize()
+    let canonical_path = path_utils::canonicalize(&path)
         .map_err(|e| AppError::file_io(format!("无法解析文件路径: {}", e)))?;
     let path = canonical_path.as_path();
 
-    // 2. 获取文件大小（从文件系统元数据）
-    let file_size = fs::metadata(path)
-        .map_err(|e| AppError::file_io(format!("读取文件元数据失败: {}", e)))?
-        .len();
+    // 2. 获取文件大小（从文件系统元数据），文件未变化时直接返回缓存
+    let stamp = FileStamp::from_metadata(
+        &fs::metadata(path).map_err(|e| AppError::file_io(format!("读取文件元数据失败: {}", e)))?,
+    );
+    let cache_key = path.to_path_buf();
+    if let Some(cached) = METADATA_CACHE.get(&cache_key, stamp) {
+        return Ok(cached);
+    }
+
+    let metadata = read_image_metadata(path, stamp.size)?;
+    METADATA_CACHE.insert(cache_key, stamp, metadata.clone());
+    Ok(metadata)
+}
 
-    // 3. 从文件扩展名推断格式
-    let format = path
-        .extension()
-        .and_then(|ext| ext.to_str())
-        .map(|ext| ext.to_lowercase())
-        .unwrap_or_else(|| "unknown".to_string());
+fn read_image_metadata(path: &Path, file_size: u64) -> Result<ImageMetadata, AppError> {
+    // 3. 推断格式（扩展名优先，缺失时按文件头识别）
+    let format = resolve_image_format(path);
 
     if format == "svg" {
         if file_size > MAX_SERVER_UPLOAD_SIZE as u64 {
@@ -172,6 +419,7 @@ pub fn get_image_metadata(file_path: String) -> Result<ImageMetadata, AppError>
             aspect_ratio,
             file_size,
             format,
+            orientation: 1,
         });
     }
 
@@ -188,8 +436,13 @@ pub fn get_image_metadata(file_path: String) -> Result<ImageMetadata, AppError>
         }
     })?;
 
-    let width = size.width as u32;
-    let height = size.height as u32;
+    // 手机照片常以横向像素存储、靠 EXIF 方向竖着显示，布局要按显示方向的宽高比预留
+    let orientation = if format == "jpg" || format == "jpeg" {
+        read_jpeg_orientation(path).unwrap_or(1)
+    } else {
+        1
+    };
+    let (width, height) = oriented_size(size.width as u32, size.height as u32, orientation);
 
     // 5. 计算宽高比（避免除以零）
     let aspect_ratio = if height > 0 {
@@ -204,12 +457,44 @@ pub fn get_image_metadata(file_path: String) -> Result<ImageMetadata, AppError>
         aspect_ratio,
         file_size,
         format,
+        orientation,
     })
 }
 
+/// 读取图片 EXIF 中与隐私相关的字段（拍摄时间、相机、GPS、方向）
+///
+/// 仅解析 JPEG 的 APP1 段（只读取文件前 128KB）；其他格式或不含 EXIF 时返回 `None`。
+///
+/// # 参数
+/// - `file_path`: 图片文件的绝对路径
+///
+/// # 返回
+/// - `Ok(Some(ImageExif))`: 解析出的 EXIF 字段
+/// - `Ok(None)`: 不是 JPEG 或没有 EXIF
+/// - `Err(AppError)`: 文件不存在或路径无法解析
+#[tauri::command]
+pub fn get_image_exif(file_path: String) -> Result<Option<ImageExif>, AppError> {
+    let path = path_utils::normalize_input(&file_path);
+    if !path.exists() {
+        return Err(AppError::file_io(format!("文件不存在: {}", file_path)));
+    }
+    let canonical_path = path_utils::canonicalize(&path)
+        .map_err(|e| AppError::file_io(format!("无法解析文件路径: {}", e)))?;
+
+    let format = resolve_image_format(&canonical_path);
+    if format != "jpg" && format != "jpeg" {
+        return Ok(None);
+    }
+    Ok(read_jpeg_exif_payload(&canonical_path).and_then(|tiff| parse_exif_fields(&tiff)))
+}
+
 #[cfg(test)]
 mod tests {
-    use super::{get_image_metadata, parse_svg_dimensions};
+    use super::{
+        get_image_exif, get_image_metadata, normalize_exif_datetime, oriented_size,
+        parse_exif_fields, parse_exif_orientation, parse_svg_dimensions, resolve_image_format,
+        ImageExif,
+    };
     use crate::error::AppError;
     use crate::server::upload_handler::MAX_SERVER_UPLOAD_SIZE;
     use std::fs::{self, OpenOptions};
@@ -236,6 +521,147 @@ mod tests {
         assert_eq!(parse_svg_dimensions(svg), None);
     }
 
+    /// 只含一个 Orientation 条目（SHORT × 1）的 TIFF 结构
+    fn tiff_with_orientation(big_endian: bool, orientation: u16) -> Vec<u8> {
+        let u16_bytes = |v: u16| {
+            if big_endian {
+                v.to_be_bytes()
+            } else {
+                v.to_le_bytes()
+            }
+        };
+        let u32_bytes = |v: u32| {
+            if big_endian {
+                v.to_be_bytes()
+            } else {
+                v.to_le_bytes()
+            }
+        };
+        let mut tiff = Vec::new();
+        tiff.extend_from_slice(if big_endian { b"MM" } else { b"II" });
+        tiff.extend_from_slice(&u16_bytes(42));
+        tiff.extend_from_slice(&u32_bytes(8));
+        tiff.extend_from_slice(&u16_bytes(1));
+        tiff.extend_from_slice(&u16_bytes(0x0112));
+        tiff.extend_from_slice(&u16_bytes(3));
+        tiff.extend_from_slice(&u32_bytes(1));
+        tiff.extend_from_slice(&u16_bytes(orientation));
+        tiff.extend_from_slice(&[0; 6]);
+        tiff
+    }
+
+    #[test]
+    fn parse_exif_orientation_reads_both_byte_orders() {
+        assert_eq!(
+            parse_exif_orientation(&tiff_with_orientation(true, 6)),
+            Some(6)
+        );
+        assert_eq!(
+            parse_exif_orientation(&tiff_with_orientation(false, 8)),
+            Some(8)
+        );
+        assert_eq!(
+            parse_exif_orientation(&tiff_with_orientation(false, 0)),
+            None
+        );
+        assert_eq!(parse_exif_orientation(b"not a tiff"), None);
+    }
+
+    /// 小端 TIFF：IFD0 含 Make / Model / Orientation / Exif 指针 / GPS 指针，
+    /// Exif 子 IFD 含 DateTimeOriginal，GPS IFD 含 GPSVersionID
+    fn tiff_with_privacy_fields() -> Vec<u8> {
+        fn entry(tiff: &mut Vec<u8>, tag: u16, kind: u16, count: u32, value: u32) {
+            tiff.extend_from_slice(&tag.to_le_bytes());
+            tiff.extend_from_slice(&kind.to_le_bytes());
+            tiff.extend_from_slice(&count.to_le_bytes());
+            tiff.extend_from_slice(&value.to_le_bytes());
+        }
+        // 偏移：IFD0 8..74，Make 74..80，Model 80..87（补齐到 88），
+        // Exif IFD 88..106，日期 106..126，GPS IFD 126..144
+        let mut tiff = b"II".to_vec();
+        tiff.extend_from_slice(&42u16.to_le_bytes());
+        tiff.extend_from_slice(&8u32.to_le_bytes());
+        tiff.extend_from_slice(&5u16.to_le_bytes());
+        entry(&mut tiff, 0x010F, 2, 6, 74);
+        entry(&mut tiff, 0x0110, 2, 7, 80);
+        entry(&mut tiff, 0x0112, 3, 1, 6);
+        entry(&mut tiff, 0x8769, 4, 1, 88);
+        entry(&mut tiff, 0x8825, 4, 1, 126);
+        tiff.extend_from_slice(&[0; 4]);
+        tiff.extend_from_slice(b"Canon\0");
+        tiff.extend_from_slice(b"EOS R5\0\0");
+        tiff.extend_from_slice(&1u16.to_le_bytes());
+        entry(&mut tiff, 0x9003, 2, 20, 106);
+        tiff.extend_from_slice(&[0; 4]);
+        tiff.extend_from_slice(b"2024:05:01 08:30:15\0");
+        tiff.extend_from_slice(&1u16.to_le_bytes());
+        entry(&mut tiff, 0x0000, 1, 4, u32::from_le_bytes([2, 3, 0, 0]));
+        tiff.extend_from_slice(&[0; 4]);
+        tiff
+    }
+
+    #[test]
+    fn parse_exif_fields_reads_privacy_fields() {
+        assert_eq!(
+            parse_exif_fields(&tiff_with_privacy_fields()),
+            Some(ImageExif {
+                capture_date: Some("2024-05-01T08:30:15".to_string()),
+                camera_make: Some("Canon".to_string()),
+                camera_model: Some("EOS R5".to_string()),
+                has_gps: true,
+                orientation: 6,
+            })
+        );
+        assert_eq!(
+            parse_exif_fields(&tiff_with_orientation(true, 3)),
+            Some(ImageExif {
+                capture_date: None,
+                camera_make: None,
+                camera_model: None,
+                has_gps: false,
+                orientation: 3,
+            })
+        );
+        assert_eq!(parse_exif_fields(b"not a tiff"), None);
+    }
+
+    #[test]
+    fn normalize_exif_datetime_rejects_placeholders() {
+        assert_eq!(
+            normalize_exif_datetime("2024:05:01 08:30:15"),
+            Some("2024-05-01T08:30:15".to_string())
+        );
+        assert_eq!(normalize_exif_datetime("0000:00:00 00:00:00"), None);
+        assert_eq!(normalize_exif_datetime("    :  :     :  :  "), None);
+        assert_eq!(normalize_exif_datetime("2024-05-01"), None);
+    }
+
+    #[test]
+    fn get_image_exif_skips_non_jpeg_files() {
+        let path = std::env::temp_dir().join(format!(
+            "picnexus_exif_png_{}_{}.png",
+            std::process::id(),
+            SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .expect("system clock should be after unix epoch")
+                .as_nanos()
+        ));
+        fs::write(&path, b"\x89PNG\r\n\x1a\n\0\0\0\rIHDR").expect("test file should be written");
+
+        let result = get_image_exif(path.to_string_lossy().to_string());
+        let _ = fs::remove_file(&path);
+
+        assert!(matches!(result, Ok(None)));
+    }
+
+    #[test]
+    fn oriented_size_swaps_for_rotated_orientations() {
+        assert_eq!(oriented_size(4000, 3000, 1), (4000, 3000));
+        assert_eq!(oriented_size(4000, 3000, 3), (4000, 3000));
+        assert_eq!(oriented_size(4000, 3000, 6), (3000, 4000));
+        assert_eq!(oriented_size(4000, 3000, 8), (3000, 4000));
+    }
+
     #[test]
     fn get_image_metadata_rejects_oversized_svg_before_reading() {
         let unique = SystemTime::now()
@@ -260,7 +686,7 @@ mod tests {
         let _ = fs::remove_file(&path);
 
         match result {
-            Err(AppError::Validation { message }) => {
+            Err(AppError::Validation { message, .. }) => {
                 assert!(message.contains("SVG 文件过大"));
                 assert!(message.contains("50MB"));
             }
@@ -268,4 +694,31 @@ mod tests {
             Ok(_) => panic!("oversized SVG should be rejected"),
         }
     }
+
+    #[test]
+    fn resolve_image_format_sniffs_files_without_image_extension() {
+        let dir = std::env::temp_dir().join(format!(
+            "picnexus_format_sniff_{}_{}",
+            std::process::id(),
+            SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .expect("system clock should be after unix epoch")
+                .as_nanos()
+        ));
+        fs::create_dir_all(&dir).expect("test dir should be created");
+        let png = b"\x89PNG\r\n\x1a\n\0\0\0\rIHDR";
+        let bare = dir.join("clipboard");
+        let tmp = dir.join("clipboard.tmp");
+        let labeled = dir.join("photo.JPG");
+        let unknown = dir.join("notes");
+        fs::write(&bare, png).expect("test file should be written");
+        fs::write(&tmp, png).expect("test file should be written");
+        fs::write(&labeled, png).expect("test file should be written");
+        fs::write(&unknown, b"plain text").expect("test file should be written");
+
+        let formats = [&bare, &tmp, &labeled, &unknown].map(|p| resolve_image_format(p));
+        let _ = fs::remove_dir_all(&dir);
+
+        assert_eq!(formats, ["png", "png", "jpg", "unknown"]);
+    }
 }
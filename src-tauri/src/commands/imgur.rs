@@ -5,7 +5,10 @@ use reqwest::multipart;
 use serde::{Deserialize, Serialize};
 use tauri::{Emitter, Window};
 
-use super::utils::read_file_bytes;
+use super::host_capabilities;
+use super::net_limiter::NetworkLimiter;
+use super::task_registry::TaskRegistry;
+use super::utils::{read_file_bytes, remember_uploaded_source};
 use crate::error::{AppError, IntoAppError};
 use crate::log_utils::{safe_path, safe_url, summarize_text};
 
@@ -31,10 +34,6 @@ struct ImgurData {
     deletehash: String,
 }
 
-/// 文件大小限制：20MB（图片）或 200MB（GIF）
-const MAX_FILE_SIZE_IMAGE: u64 = 20 * 1024 * 1024;
-const MAX_FILE_SIZE_GIF: u64 = 200 * 1024 * 1024;
-
 /// 上传文件到 Imgur
 #[tauri::command]
 pub async fn upload_to_imgur(
@@ -43,7 +42,34 @@ pub async fn upload_to_imgur(
     file_path: String,
     imgur_client_id: String,
     imgur_client_secret: Option<String>,
+    limiter: tauri::State<'_, NetworkLimiter>,
+    tasks: tauri::State<'_, TaskRegistry>,
 ) -> Result<ImgurUploadResult, AppError> {
+    tasks
+        .run(
+            Some(&id),
+            upload_to_imgur_inner(
+                window,
+                id.clone(),
+                file_path.clone(),
+                imgur_client_id,
+                imgur_client_secret,
+                limiter,
+            ),
+        )
+        .await
+        .inspect(|_| remember_uploaded_source(&file_path))
+}
+
+async fn upload_to_imgur_inner(
+    window: Window,
+    id: String,
+    file_path: String,
+    imgur_client_id: String,
+    imgur_client_secret: Option<String>,
+    limiter: tauri::State<'_, NetworkLimiter>,
+) -> Result<ImgurUploadResult, AppError> {
+    let _permit = limiter.acquire("imgur").await;
     log::info!("[Imgur] 开始上传文件: {}", safe_path(&file_path));
 
     // 发送进度: 0% - 读取文件
@@ -71,28 +97,12 @@ pub async fn upload_to_imgur(
         .ok_or_else(|| AppError::validation("无法获取文件扩展名"))?
         .to_lowercase();
 
-    // 2. 验证文件类型并按类型限制读取大小。
-    let is_gif = ext == "gif";
-    let max_size = if is_gif {
-        MAX_FILE_SIZE_GIF
-    } else {
-        MAX_FILE_SIZE_IMAGE
-    };
+    // 2. 按类型限制读取大小（GIF 上限更高），再按图床能力表校验文件类型与大小。
+    let max_size = host_capabilities::capabilities_for("imgur")
+        .and_then(|caps| caps.max_size_for(&ext))
+        .unwrap_or(host_capabilities::DEFAULT_READ_LIMIT);
     let (buffer, file_size) = read_file_bytes(&file_path, max_size).await?;
-
-    if file_size > max_size {
-        return Err(AppError::validation(format!(
-            "文件大小 ({:.2}MB) 超过 Imgur 限制 ({:.0}MB)",
-            file_size as f64 / 1024.0 / 1024.0,
-            max_size as f64 / 1024.0 / 1024.0
-        )));
-    }
-
-    if !["jpg", "jpeg", "png", "gif", "apng", "tiff", "bmp", "webp"].contains(&ext.as_str()) {
-        return Err(AppError::validation(
-            "只支持 JPG、PNG、GIF、WebP、APNG、TIFF、BMP 格式的图片",
-        ));
-    }
+    host_capabilities::check_admission("imgur", &ext, file_size)?;
 
     // 发送进度: 33% - 准备上传
     let _ = window.emit(
@@ -6,7 +6,10 @@ use reqwest::multipart;
 use serde::{Deserialize, Serialize};
 use tauri::{Emitter, Window};
 
-use super::utils::read_file_bytes;
+use super::host_capabilities;
+use super::net_limiter::NetworkLimiter;
+use super::task_registry::TaskRegistry;
+use super::utils::{read_file_bytes, remember_uploaded_source};
 use crate::error::{AppError, IntoAppError};
 use crate::log_utils::{safe_path, safe_url, summarize_text};
 
@@ -31,9 +34,6 @@ struct JDUploadResponse {
     path: Option<String>,
 }
 
-/// 文件大小限制：15MB
-const MAX_FILE_SIZE: u64 = 15 * 1024 * 1024;
-
 /// 获取京东 aid 和 pin
 async fn get_aid_info() -> Result<AidInfo, AppError> {
     let url = "https://api.m.jd.com/client.action?functionId=getAidInfo&body=%7B%22aidClientType%22%3A%22comet%22%2C%22aidClientVersion%22%3A%22comet%20-v1.0.0%22%2C%22appId%22%3A%22im.customer%22%2C%22os%22%3A%22comet%22%2C%22entry%22%3A%22jd_web_EnterpriseZC%22%2C%22reqSrc%22%3A%22s_comet%22%2C%22siteId%22%3A-1%2C%22customerAppId%22%3A%22im.customer%22%7D&appid=wh5&client=wh5&clientVersion=1.0.0&loginType=3&callback=jsonp1";
@@ -109,7 +109,25 @@ pub async fn upload_to_jd(
     window: Window,
     id: String,
     file_path: String,
+    limiter: tauri::State<'_, NetworkLimiter>,
+    tasks: tauri::State<'_, TaskRegistry>,
+) -> Result<JDUploadResult, AppError> {
+    tasks
+        .run(
+            Some(&id),
+            upload_to_jd_inner(window, id.clone(), file_path.clone(), limiter),
+        )
+        .await
+        .inspect(|_| remember_uploaded_source(&file_path))
+}
+
+async fn upload_to_jd_inner(
+    window: Window,
+    id: String,
+    file_path: String,
+    limiter: tauri::State<'_, NetworkLimiter>,
 ) -> Result<JDUploadResult, AppError> {
+    let _permit = limiter.acquire("jd").await;
     log::info!("[JD] 开始上传文件: {}", safe_path(&file_path));
 
     // 发送进度: 0% - 读取文件
@@ -126,17 +144,10 @@ pub async fn upload_to_jd(
     );
 
     // 1. 读取文件
-    let (buffer, file_size) = read_file_bytes(&file_path, MAX_FILE_SIZE).await?;
-
-    // 2. 验证文件大小（限制 15MB）
-    if file_size > MAX_FILE_SIZE {
-        return Err(AppError::validation(format!(
-            "文件大小 ({:.2}MB) 超过限制 (15MB)",
-            file_size as f64 / 1024.0 / 1024.0
-        )));
-    }
+    let (buffer, file_size) =
+        read_file_bytes(&file_path, host_capabilities::read_limit("jd")).await?;
 
-    // 3. 验证文件类型（只允许图片）
+    // 2. 按图床能力表校验文件类型与大小
     let file_name = std::path::Path::new(&file_path)
         .file_name()
         .and_then(|n| n.to_str())
@@ -148,9 +159,7 @@ pub async fn upload_to_jd(
         .ok_or_else(|| AppError::validation("无法获取文件扩展名"))?
         .to_lowercase();
 
-    if !["jpg", "jpeg", "png", "gif"].contains(&ext.as_str()) {
-        return Err(AppError::validation("只支持 JPG、PNG、GIF 格式的图片"));
-    }
+    host_capabilities::check_admission("jd", &ext, file_size)?;
 
     // 发送进度: 25% - 获取凭证
     let _ = window.emit(
@@ -178,7 +187,7 @@ pub async fn upload_to_jd(
         file_name.to_string()
     };
 
-    let part = multipart::Part::bytes(buffer)
+    let part = multipart::Part::stream_with_length(buffer, file_size)
         .file_name(normalized_file_name)
         .mime_str("image/*")
         .into_validation_err_with("无法设置 MIME 类型")?;
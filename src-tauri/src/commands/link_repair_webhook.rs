@@ -0,0 +1,157 @@
+// src-tauri/src/commands/link_repair_webhook.rs
+// 链接修复 Webhook：检测发现失效链接后通知外部服务（如触发 CI 修补博客仓库）
+
+use hmac::{Hmac, Mac};
+use serde::Deserialize;
+use sha2::Sha256;
+use std::time::Duration;
+
+use crate::error::{AppError, IntoAppError};
+use crate::log_utils::{safe_url, summarize_text};
+
+type HmacSha256 = Hmac<Sha256>;
+
+/// Webhook 请求超时
+const WEBHOOK_TIMEOUT_SECS: u64 = 15;
+
+/// 事件类型请求头
+const EVENT_HEADER: &str = "X-PicNexus-Event";
+const EVENT_NAME: &str = "link-repair";
+
+/// 签名请求头，值为 `sha256=<hex>`（与 GitHub Webhook 的格式一致，便于复用校验代码）
+const SIGNATURE_HEADER: &str = "X-PicNexus-Signature-256";
+
+/// Webhook 配置（由前端设置页传入）
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LinkRepairWebhookConfig {
+    pub url: String,
+    /// 签名密钥；为空时不附带签名头
+    #[serde(default)]
+    pub secret: Option<String>,
+}
+
+impl LinkRepairWebhookConfig {
+    fn secret(&self) -> Option<&str> {
+        self.secret
+            .as_deref()
+            .map(str::trim)
+            .filter(|secret| !secret.is_empty())
+    }
+}
+
+/// 校验 Webhook 地址
+fn validate_url(url: &str) -> Result<&str, AppError> {
+    let url = url.trim();
+    if url.is_empty() {
+        return Err(AppError::config("Webhook 地址不能为空"));
+    }
+    if !url.starts_with("http://") && !url.starts_with("https://") {
+        return Err(AppError::config(
+            "Webhook 地址必须以 http:// 或 https:// 开头",
+        ));
+    }
+    Ok(url)
+}
+
+/// 计算请求体签名，返回 `sha256=<hex>`
+fn sign_body(secret: &str, body: &[u8]) -> Result<String, AppError> {
+    let mut mac =
+        HmacSha256::new_from_slice(secret.as_bytes()).into_external_err_with("HMAC 初始化失败")?;
+    mac.update(body);
+    Ok(format!(
+        "sha256={}",
+        hex::encode(mac.finalize().into_bytes())
+    ))
+}
+
+/// 发送链接修复 Webhook
+///
+/// `payload` 由前端组装（失效记录 ID、失效链接与建议镜像），此处负责签名与投递。
+/// 返回对方响应的 HTTP 状态码。
+#[tauri::command]
+pub async fn send_link_repair_webhook(
+    config: LinkRepairWebhookConfig,
+    payload: serde_json::Value,
+    http_client: tauri::State<'_, crate::HttpClient>,
+) -> Result<u16, AppError> {
+    let url = validate_url(&config.url)?;
+    let body = serde_json::to_vec(&payload)
+        .map_err(|e| AppError::validation(format!("Webhook 负载序列化失败: {}", e)))?;
+
+    let mut request = http_client
+        .get()
+        .post(url)
+        .header(reqwest::header::CONTENT_TYPE, "application/json")
+        .header(EVENT_HEADER, EVENT_NAME)
+        .timeout(Duration::from_secs(WEBHOOK_TIMEOUT_SECS));
+    if let Some(secret) = config.secret() {
+        request = request.header(SIGNATURE_HEADER, sign_body(secret, &body)?);
+    }
+
+    log::info!(
+        "[链接修复] 发送 Webhook: {} ({} 字节)",
+        safe_url(url),
+        body.len()
+    );
+    let response = request
+        .body(body)
+        .send()
+        .await
+        .into_network_err_with("Webhook 请求失败")?;
+
+    let status = response.status();
+    if status.is_success() {
+        log::info!("[链接修复] Webhook 已送达 (HTTP {})", status);
+        return Ok(status.as_u16());
+    }
+
+    let text = response.text().await.unwrap_or_default();
+    log::warn!(
+        "[链接修复] Webhook 返回错误 (HTTP {}): {}",
+        status,
+        summarize_text(&text)
+    );
+    if status == reqwest::StatusCode::UNAUTHORIZED || status == reqwest::StatusCode::FORBIDDEN {
+        return Err(AppError::auth("Webhook 鉴权失败，请检查签名密钥"));
+    }
+    Err(AppError::external(format!(
+        "Webhook 返回 HTTP {}: {}",
+        status,
+        summarize_text(&text)
+    )))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn validate_url_requires_http_scheme() {
+        assert_eq!(
+            validate_url(" https://ci.example.com/hook ").unwrap(),
+            "https://ci.example.com/hook"
+        );
+        assert!(validate_url("  ").is_err());
+        assert!(validate_url("ftp://ci.example.com/hook").is_err());
+    }
+
+    #[test]
+    fn sign_body_matches_known_hmac_sha256() {
+        // RFC 4231 测试用例 2
+        let signature = sign_body("Jefe", b"what do ya want for nothing?").unwrap();
+        assert_eq!(
+            signature,
+            "sha256=5bdcc146bf60754e6a042426089575c75a003f089d2739839dec58b964ec3843"
+        );
+    }
+
+    #[test]
+    fn blank_secret_is_treated_as_unsigned() {
+        let config = LinkRepairWebhookConfig {
+            url: "https://ci.example.com/hook".to_string(),
+            secret: Some("   ".to_string()),
+        };
+        assert_eq!(config.secret(), None);
+    }
+}
@@ -0,0 +1,412 @@
+// src-tauri/src/commands/link_templates.rs
+// 命名链接模板：用户自定义复制格式（Hugo 短代码、Obsidian 嵌入、论坛专用写法等）
+//
+// 模板保存在 {app_data_dir}/link-templates.json，格式：
+//   { "version": 1, "templates": [{ "id", "name", "template" }] }
+// 占位符写法为 {名称}，支持 url、alt、title、filename、width、height；
+// 名称以外的花括号原样保留，所以 `{{< figure src="{url}" >}}` 这类写法可以直接用。
+// 占位符替换为原始值，不做 HTML / Markdown 转义，引号等由模板自己处理。
+
+use std::path::{Path, PathBuf};
+use std::sync::LazyLock;
+
+use serde::{Deserialize, Serialize};
+use sqlx::sqlite::SqliteConnectOptions;
+use sqlx::{ConnectOptions, Row};
+use tokio::sync::Mutex;
+
+use super::upload_manifest::default_alt_text;
+use crate::atomic_file;
+use crate::error::AppError;
+
+const STORE_VERSION: u32 = 1;
+const STORE_FILE: &str = "link-templates.json";
+const MAX_TEMPLATES: usize = 100;
+const MAX_NAME_CHARS: usize = 50;
+const MAX_TEMPLATE_CHARS: usize = 2000;
+
+/// 读改写模板文件时串行化，避免两次保存互相覆盖
+static STORE_LOCK: LazyLock<Mutex<()>> = LazyLock::new(|| Mutex::new(()));
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LinkTemplate {
+    /// 新建时传空字符串，由后端生成
+    #[serde(default)]
+    pub id: String,
+    pub name: String,
+    pub template: String,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct TemplateStore {
+    version: u32,
+    #[serde(default)]
+    templates: Vec<LinkTemplate>,
+}
+
+/// 渲染模板所需的图片信息
+#[derive(Debug, Default)]
+struct LinkContext {
+    url: String,
+    alt: String,
+    title: String,
+    filename: String,
+    width: Option<i64>,
+    height: Option<i64>,
+}
+
+impl LinkContext {
+    /// 占位符对应的值；不认识的占位符返回 None
+    fn value(&self, name: &str) -> Option<String> {
+        // 尺寸未知时替换为空字符串
+        let dimension = |v: Option<i64>| v.filter(|v| *v > 0).map(|v| v.to_string());
+        match name {
+            "url" => Some(self.url.clone()),
+            "alt" => Some(self.alt.clone()),
+            "title" => Some(self.title.clone()),
+            "filename" => Some(self.filename.clone()),
+            "width" => Some(dimension(self.width).unwrap_or_default()),
+            "height" => Some(dimension(self.height).unwrap_or_default()),
+            _ => None,
+        }
+    }
+}
+
+/// 替换 {名称} 占位符，返回结果和不认识的占位符名
+///
+/// 名称只含小写字母和下划线；不认识的占位符原样保留
+fn substitute(template: &str, ctx: &LinkContext) -> (String, Vec<String>) {
+    let mut out = String::with_capacity(template.len());
+    let mut unknown = Vec::new();
+    let mut rest = template;
+    while let Some(start) = rest.find('{') {
+        out.push_str(&rest[..start]);
+        let after = &rest[start + 1..];
+        let name_len = after
+            .find(|c: char| !(c.is_ascii_lowercase() || c == '_'))
+            .unwrap_or(after.len());
+        if name_len == 0 || !after[name_len..].starts_with('}') {
+            out.push('{');
+            rest = after;
+            continue;
+        }
+        let name = &after[..name_len];
+        match ctx.value(name) {
+            Some(value) => out.push_str(&value),
+            None => {
+                out.push_str(&rest[start..start + name_len + 2]);
+                unknown.push(name.to_string());
+            }
+        }
+        rest = &after[name_len + 1..];
+    }
+    out.push_str(rest);
+    (out, unknown)
+}
+
+/// 校验并规范化模板（去掉首尾空白），不认识的占位符视为拼写错误
+fn validate(template: &LinkTemplate) -> Result<LinkTemplate, AppError> {
+    let name = template.name.trim();
+    if name.is_empty() {
+        return Err(AppError::validation("模板名称不能为空"));
+    }
+    if name.chars().count() > MAX_NAME_CHARS {
+        return Err(AppError::validation(format!(
+            "模板名称最多 {} 个字符",
+            MAX_NAME_CHARS
+        )));
+    }
+    let body = template.template.trim();
+    if body.is_empty() {
+        return Err(AppError::validation("模板内容不能为空"));
+    }
+    if body.chars().count() > MAX_TEMPLATE_CHARS {
+        return Err(AppError::validation(format!(
+            "模板内容最多 {} 个字符",
+            MAX_TEMPLATE_CHARS
+        )));
+    }
+    let (_, unknown) = substitute(body, &LinkContext::default());
+    if let Some(name) = unknown.first() {
+        return Err(AppError::validation(format!(
+            "未知占位符 {{{}}}，可用：{{url}} {{alt}} {{title}} {{filename}} {{width}} {{height}}",
+            name
+        )));
+    }
+    Ok(LinkTemplate {
+        id: template.id.trim().to_string(),
+        name: name.to_string(),
+        template: body.to_string(),
+    })
+}
+
+/// 新增或更新模板（按 id 匹配；id 为空时生成新 id），返回保存后的模板
+fn upsert(
+    templates: &mut Vec<LinkTemplate>,
+    template: &LinkTemplate,
+) -> Result<LinkTemplate, AppError> {
+    let mut template = validate(template)?;
+    if templates
+        .iter()
+        .any(|t| t.name == template.name && t.id != template.id)
+    {
+        return Err(AppError::validation(format!(
+            "已存在名为「{}」的模板",
+            template.name
+        )));
+    }
+    if let Some(existing) = templates.iter_mut().find(|t| t.id == template.id) {
+        *existing = template.clone();
+        return Ok(template);
+    }
+    if templates.len() >= MAX_TEMPLATES {
+        return Err(AppError::validation(format!(
+            "最多保存 {} 个链接模板",
+            MAX_TEMPLATES
+        )));
+    }
+    if template.id.is_empty() {
+        template.id = uuid::Uuid::new_v4().to_string();
+    }
+    templates.push(template.clone());
+    Ok(template)
+}
+
+fn store_path(app: &tauri::AppHandle) -> Result<PathBuf, AppError> {
+    Ok(crate::portable::user_data_dir(app)?.join(STORE_FILE))
+}
+
+async fn load(path: &Path) -> Result<Vec<LinkTemplate>, AppError> {
+    let content = match tokio::fs::read_to_string(path).await {
+        Ok(content) => content,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(e) => return Err(AppError::file_io(format!("读取链接模板失败: {}", e))),
+    };
+    let store: TemplateStore = serde_json::from_str(&content)
+        .map_err(|e| AppError::config(format!("链接模板文件格式无效: {}", e)))?;
+    if store.version > STORE_VERSION {
+        return Err(AppError::config(format!(
+            "不支持的链接模板版本: {}（当前支持 {}）",
+            store.version, STORE_VERSION
+        )));
+    }
+    Ok(store.templates)
+}
+
+async fn store(path: &Path, templates: Vec<LinkTemplate>) -> Result<(), AppError> {
+    if let Some(dir) = path.parent() {
+        tokio::fs::create_dir_all(dir)
+            .await
+            .map_err(|e| AppError::file_io(format!("无法创建配置目录: {}", e)))?;
+    }
+    let content = serde_json::to_string_pretty(&TemplateStore {
+        version: STORE_VERSION,
+        templates,
+    })
+    .map_err(|e| AppError::config(format!("链接模板序列化失败: {}", e)))?;
+    atomic_file::write_async(path, content)
+        .await
+        .map_err(|e| AppError::file_io(format!("写入链接模板失败: {}", e)))
+}
+
+/// 导入预设：按名称合并模板，同名的覆盖内容、其余新增，返回 (新增数, 更新数)
+///
+/// 先全部校验再写入，任一模板无效时不做任何修改
+pub(crate) async fn merge_templates(
+    app: &tauri::AppHandle,
+    incoming: &[LinkTemplate],
+) -> Result<(usize, usize), AppError> {
+    let path = store_path(app)?;
+    let _guard = STORE_LOCK.lock().await;
+    let mut templates = load(&path).await?;
+    let (mut added, mut updated) = (0, 0);
+    for template in incoming {
+        let name = template.name.trim();
+        let id = match templates.iter().find(|t| t.name == name) {
+            Some(existing) => {
+                updated += 1;
+                existing.id.clone()
+            }
+            None => {
+                added += 1;
+                String::new()
+            }
+        };
+        upsert(
+            &mut templates,
+            &LinkTemplate {
+                id,
+                ..template.clone()
+            },
+        )?;
+    }
+    if added + updated > 0 {
+        store(&path, templates).await?;
+    }
+    Ok((added, updated))
+}
+
+/// 只读查询一条历史记录，组装渲染上下文
+async fn load_record(db_path: &Path, record_id: &str) -> Result<LinkContext, AppError> {
+    if !db_path.exists() {
+        return Err(AppError::validation("历史记录不存在"));
+    }
+    let mut conn = SqliteConnectOptions::new()
+        .filename(db_path)
+        .read_only(true)
+        .connect()
+        .await
+        .map_err(|e| AppError::storage(format!("打开历史记录数据库失败: {}", e)))?;
+
+    let row = sqlx::query(
+        "SELECT local_file_name, generated_link, width, height, alt_text, title \
+         FROM history_items WHERE id = ?1",
+    )
+    .bind(record_id)
+    .fetch_optional(&mut conn)
+    .await
+    .map_err(|e| AppError::storage(format!("查询历史记录失败: {}", e)))?
+    .ok_or_else(|| AppError::validation("历史记录不存在"))?;
+
+    let url: String = row.try_get("generated_link").unwrap_or_default();
+    if url.is_empty() {
+        return Err(AppError::validation("该记录没有可用链接"));
+    }
+    let filename: String = row.try_get("local_file_name").unwrap_or_default();
+    // 上传时填写的替代文本与标题优先，未填写时由文件名推导
+    let stored = |column: &str| {
+        row.try_get::<Option<String>, _>(column)
+            .ok()
+            .flatten()
+            .filter(|v| !v.trim().is_empty())
+    };
+    let alt = stored("alt_text").unwrap_or_else(|| default_alt_text(&filename));
+    let title = stored("title").unwrap_or_else(|| {
+        Path::new(&filename)
+            .file_stem()
+            .map(|stem| stem.to_string_lossy().to_string())
+            .unwrap_or_default()
+    });
+    Ok(LinkContext {
+        url,
+        alt,
+        title,
+        filename,
+        width: row.try_get("width").ok(),
+        height: row.try_get("height").ok(),
+    })
+}
+
+/// 列出所有链接模板
+#[tauri::command]
+pub async fn list_link_templates(app: tauri::AppHandle) -> Result<Vec<LinkTemplate>, AppError> {
+    load(&store_path(&app)?).await
+}
+
+/// 新增或更新链接模板（id 为空时新建），返回保存后的模板
+#[tauri::command]
+pub async fn save_link_template(
+    app: tauri::AppHandle,
+    template: LinkTemplate,
+) -> Result<LinkTemplate, AppError> {
+    let path = store_path(&app)?;
+    let _guard = STORE_LOCK.lock().await;
+    let mut templates = load(&path).await?;
+    let saved = upsert(&mut templates, &template)?;
+    store(&path, templates).await?;
+    log::info!("[链接模板] 已保存: {}", saved.name);
+    Ok(saved)
+}
+
+/// 删除链接模板，返回是否存在
+#[tauri::command]
+pub async fn delete_link_template(app: tauri::AppHandle, id: String) -> Result<bool, AppError> {
+    let path = store_path(&app)?;
+    let _guard = STORE_LOCK.lock().await;
+    let mut templates = load(&path).await?;
+    let before = templates.len();
+    templates.retain(|t| t.id != id);
+    if templates.len() == before {
+        return Ok(false);
+    }
+    store(&path, templates).await?;
+    Ok(true)
+}
+
+/// 用指定模板渲染一条历史记录的链接
+#[tauri::command]
+pub async fn render_link(
+    app: tauri::AppHandle,
+    record_id: String,
+    template_id: String,
+) -> Result<String, AppError> {
+    let templates = load(&store_path(&app)?).await?;
+    let template = templates
+        .iter()
+        .find(|t| t.id == template_id)
+        .ok_or_else(|| AppError::validation("链接模板不存在"))?;
+    let db_path = crate::portable::history_db_path(&app)?;
+    let ctx = load_record(&db_path, &record_id).await?;
+    Ok(substitute(&template.template, &ctx).0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn template(id: &str, name: &str, body: &str) -> LinkTemplate {
+        LinkTemplate {
+            id: id.to_string(),
+            name: name.to_string(),
+            template: body.to_string(),
+        }
+    }
+
+    #[test]
+    fn substitute_fills_placeholders_and_keeps_other_braces() {
+        let ctx = LinkContext {
+            url: "https://img.example/a.png".to_string(),
+            alt: "sunset beach".to_string(),
+            title: "sunset_beach".to_string(),
+            filename: "sunset_beach.png".to_string(),
+            width: Some(800),
+            height: None,
+        };
+        let (hugo, unknown) = substitute(
+            r#"{{< figure src="{url}" alt="{alt}" width="{width}" height="{height}" >}}"#,
+            &ctx,
+        );
+        assert_eq!(
+            hugo,
+            r#"{{< figure src="https://img.example/a.png" alt="sunset beach" width="800" height="" >}}"#
+        );
+        assert!(unknown.is_empty());
+
+        let (text, unknown) = substitute("{title} {size} {", &ctx);
+        assert_eq!(text, "sunset_beach {size} {");
+        assert_eq!(unknown, vec!["size"]);
+    }
+
+    #[test]
+    fn validate_rejects_unknown_placeholders_and_empty_fields() {
+        assert!(validate(&template("", "Hugo", "{{< figure src=\"{url}\" >}}")).is_ok());
+        assert!(validate(&template("", "Typo", "![{alt}]({ulr})")).is_err());
+        assert!(validate(&template("", "  ", "{url}")).is_err());
+        assert!(validate(&template("", "Empty", "   ")).is_err());
+    }
+
+    #[test]
+    fn upsert_creates_updates_and_rejects_duplicate_names() {
+        let mut templates = Vec::new();
+        let created = upsert(&mut templates, &template("", " Hugo ", "{url}")).unwrap();
+        assert!(!created.id.is_empty());
+        assert_eq!(created.name, "Hugo");
+
+        let updated = upsert(&mut templates, &template(&created.id, "Hugo", "<{url}>")).unwrap();
+        assert_eq!(updated.id, created.id);
+        assert_eq!(templates.len(), 1);
+        assert_eq!(templates[0].template, "<{url}>");
+
+        assert!(upsert(&mut templates, &template("", "Hugo", "{alt}")).is_err());
+    }
+}
@@ -0,0 +1,217 @@
+// src-tauri/src/commands/log_level.rs
+// 运行时日志级别：按模块路径前缀设置，排查问题时无需重启即可打开某个模块的 debug 日志
+//
+// tauri-plugin-log 的 level / level_for 在构建时固定，这里改为插件放行全部级别、
+// 由 `enabled` 过滤：按日志 target（如 picnexus::commands::upload）匹配最长的模块前缀。
+
+use std::sync::{LazyLock, RwLock};
+
+use log::LevelFilter;
+use serde::Serialize;
+
+use crate::error::AppError;
+
+/// 未匹配任何模块时的默认级别
+const DEFAULT_LEVEL: LevelFilter = LevelFilter::Info;
+
+/// 启动时的模块级别：本应用 debug，依赖库中输出频繁的只保留警告
+const DEFAULT_MODULE_LEVELS: &[(&str, LevelFilter)] = &[
+    ("picnexus", LevelFilter::Debug),
+    ("hyper", LevelFilter::Warn),
+    ("hyper_util", LevelFilter::Warn),
+    ("reqwest", LevelFilter::Warn),
+    ("rustls", LevelFilter::Warn),
+    ("tungstenite", LevelFilter::Warn),
+    ("tokio_tungstenite", LevelFilter::Warn),
+    ("aws_sdk_s3", LevelFilter::Warn),
+    ("aws_config", LevelFilter::Warn),
+    ("aws_smithy_runtime", LevelFilter::Warn),
+    ("tracing", LevelFilter::Warn),
+];
+
+struct LogLevels {
+    default: LevelFilter,
+    /// (模块前缀, 级别)
+    modules: Vec<(String, LevelFilter)>,
+}
+
+impl LogLevels {
+    fn level_for(&self, target: &str) -> LevelFilter {
+        self.modules
+            .iter()
+            .filter(|(module, _)| module_matches(target, module))
+            .max_by_key(|(module, _)| module.len())
+            .map_or(self.default, |(_, level)| *level)
+    }
+
+    fn max_level(&self) -> LevelFilter {
+        self.modules
+            .iter()
+            .map(|(_, level)| *level)
+            .fold(self.default, Ord::max)
+    }
+}
+
+static LEVELS: LazyLock<RwLock<LogLevels>> = LazyLock::new(|| {
+    RwLock::new(LogLevels {
+        default: DEFAULT_LEVEL,
+        modules: DEFAULT_MODULE_LEVELS
+            .iter()
+            .map(|(module, level)| (module.to_string(), *level))
+            .collect(),
+    })
+});
+
+/// target 等于模块路径，或位于其子模块中
+fn module_matches(target: &str, module: &str) -> bool {
+    target
+        .strip_prefix(module)
+        .is_some_and(|rest| rest.is_empty() || rest.starts_with("::"))
+}
+
+/// 日志插件的过滤器
+pub fn enabled(metadata: &log::Metadata) -> bool {
+    let levels = LEVELS.read().unwrap_or_else(|e| e.into_inner());
+    metadata.level() <= levels.level_for(metadata.target())
+}
+
+/// 把 log 的全局最大级别同步为当前配置的最高级别（日志宏先比较该值，未开启的级别零开销）
+pub fn sync_max_level() {
+    let levels = LEVELS.read().unwrap_or_else(|e| e.into_inner());
+    log::set_max_level(levels.max_level());
+}
+
+fn parse_level(level: &str) -> Result<LevelFilter, AppError> {
+    level
+        .trim()
+        .parse::<LevelFilter>()
+        .map_err(|_| AppError::validation(format!("无效的日志级别: {}", level)))
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ModuleLogLevel {
+    pub module: String,
+    pub level: String,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LogLevelConfig {
+    pub default: String,
+    pub modules: Vec<ModuleLogLevel>,
+}
+
+fn snapshot(levels: &LogLevels) -> LogLevelConfig {
+    LogLevelConfig {
+        default: levels.default.as_str().to_ascii_lowercase(),
+        modules: levels
+            .modules
+            .iter()
+            .map(|(module, level)| ModuleLogLevel {
+                module: module.clone(),
+                level: level.as_str().to_ascii_lowercase(),
+            })
+            .collect(),
+    }
+}
+
+/// 设置日志级别（off / error / warn / info / debug / trace）
+///
+/// `module` 为模块路径前缀（如 `picnexus::commands::upload`、`reqwest`），省略时设置默认级别
+#[tauri::command]
+pub fn set_log_level(module: Option<String>, level: String) -> Result<LogLevelConfig, AppError> {
+    let level = parse_level(&level)?;
+    let module = module
+        .map(|m| m.trim().to_string())
+        .filter(|m| !m.is_empty());
+    if let Some(module) = &module {
+        if !module.split("::").all(|part| {
+            !part.is_empty() && part.chars().all(|c| c.is_ascii_alphanumeric() || c == '_')
+        }) {
+            return Err(AppError::validation(format!("无效的模块路径: {}", module)));
+        }
+    }
+
+    let config = {
+        let mut levels = LEVELS.write().unwrap_or_else(|e| e.into_inner());
+        match &module {
+            Some(module) => match levels.modules.iter_mut().find(|(m, _)| m == module) {
+                Some((_, existing)) => *existing = level,
+                None => levels.modules.push((module.clone(), level)),
+            },
+            None => levels.default = level,
+        }
+        snapshot(&levels)
+    };
+    sync_max_level();
+
+    log::info!(
+        "[日志] {} 级别已设为 {}",
+        module.as_deref().unwrap_or("默认"),
+        level
+    );
+    Ok(config)
+}
+
+/// 获取当前日志级别配置
+#[tauri::command]
+pub fn get_log_levels() -> LogLevelConfig {
+    snapshot(&LEVELS.read().unwrap_or_else(|e| e.into_inner()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn levels(modules: &[(&str, LevelFilter)]) -> LogLevels {
+        LogLevels {
+            default: LevelFilter::Info,
+            modules: modules
+                .iter()
+                .map(|(module, level)| (module.to_string(), *level))
+                .collect(),
+        }
+    }
+
+    #[test]
+    fn longest_module_prefix_wins() {
+        let levels = levels(&[
+            ("picnexus", LevelFilter::Debug),
+            ("picnexus::commands::upload", LevelFilter::Trace),
+        ]);
+        assert_eq!(
+            levels.level_for("picnexus::commands::upload"),
+            LevelFilter::Trace
+        );
+        assert_eq!(
+            levels.level_for("picnexus::commands::upload::stream"),
+            LevelFilter::Trace
+        );
+        assert_eq!(
+            levels.level_for("picnexus::commands::smms"),
+            LevelFilter::Debug
+        );
+        assert_eq!(levels.level_for("reqwest::connect"), LevelFilter::Info);
+    }
+
+    #[test]
+    fn module_prefix_matches_whole_path_segments() {
+        let levels = levels(&[("hyper", LevelFilter::Warn)]);
+        assert_eq!(levels.level_for("hyper::client"), LevelFilter::Warn);
+        assert_eq!(levels.level_for("hyper_util::client"), LevelFilter::Info);
+    }
+
+    #[test]
+    fn max_level_covers_every_module() {
+        let levels = levels(&[("picnexus", LevelFilter::Trace)]);
+        assert_eq!(levels.max_level(), LevelFilter::Trace);
+    }
+
+    #[test]
+    fn parse_level_is_case_insensitive() {
+        assert_eq!(parse_level("DEBUG").unwrap(), LevelFilter::Debug);
+        assert_eq!(parse_level(" off ").unwrap(), LevelFilter::Off);
+        assert!(parse_level("verbose").is_err());
+    }
+}
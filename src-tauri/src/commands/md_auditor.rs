@@ -0,0 +1,464 @@
+// Markdown 死链审计：检测文章中的远程图片并给出修复建议
+//
+// 工作流：
+//   audit_markdown(dir) -> 收集 MD / HTML 文件 -> 提取远程图片链接（跨文件去重）
+//   -> 复用 batch_check_links 的并发检测（进度事件、取消、暂停均一致）
+//   -> 按文件汇总失效图片，并从历史记录中查找可替换的备份链接 / 本地原图
+
+use crate::commands::link_checker::{
+    run_batch_check, BatchCheckCancelFlag, BatchCheckItem, BatchCheckPauseFlag, BatchCheckRequest,
+    CheckLinkResult,
+};
+use crate::commands::md_scanner::{
+    collect_files, extract_remote_image_links, is_markdown_or_html_file,
+};
+use crate::commands::net_limiter::NetworkLimiter;
+use crate::error::AppError;
+use crate::log_utils::safe_path;
+use crate::path_utils;
+use serde::{Deserialize, Serialize};
+use sqlx::sqlite::SqliteConnectOptions;
+use sqlx::{ConnectOptions, Row};
+use std::collections::{HashMap, HashSet};
+use std::path::Path;
+use std::sync::atomic::AtomicBool;
+
+/// 审计时的全局并发数（比 GUI 批量检测保守，避免对单篇文章的图床造成突发流量）
+const AUDIT_CHECK_CONCURRENCY: usize = 8;
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AuditMarkdownOptions {
+    #[serde(default = "default_include_subfolders")]
+    pub include_subfolders: bool,
+    /// 单链接超时秒数（默认 10）
+    #[serde(default)]
+    pub timeout_secs: Option<u64>,
+}
+
+fn default_include_subfolders() -> bool {
+    true
+}
+
+/// 修复建议
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(tag = "kind", rename_all = "camelCase")]
+pub enum AuditRepair {
+    /// 同一图片在其他图床上的有效备份
+    #[serde(rename_all = "camelCase")]
+    BackupLink { service_id: String, url: String },
+    /// 历史记录中的本地原图仍存在，可重新上传
+    #[serde(rename_all = "camelCase")]
+    ReuploadLocal { local_path: String },
+}
+
+/// 文件中的一处失效图片
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BrokenImage {
+    pub url: String,
+    pub line_number: usize,
+    /// "markdown" | "html"
+    pub syntax: String,
+    pub error_type: String,
+    pub status_code: Option<u16>,
+    pub error: Option<String>,
+    pub suggestion: Option<String>,
+    pub repairs: Vec<AuditRepair>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FileAuditReport {
+    pub file_path: String,
+    pub total_images: usize,
+    pub broken: Vec<BrokenImage>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AuditMarkdownResult {
+    pub batch_id: String,
+    pub total_files: usize,
+    pub total_links: usize,
+    pub unique_links: usize,
+    pub broken_links: usize,
+    /// 只包含存在失效图片的文件
+    pub files: Vec<FileAuditReport>,
+    pub skipped_dirs: Vec<String>,
+    pub cancelled: bool,
+}
+
+/// 单个文件中的远程图片：(URL, 行号, 语法)
+struct FileImageLinks {
+    file_path: String,
+    links: Vec<(String, usize, String)>,
+}
+
+fn collect_remote_links(paths: Vec<String>) -> Vec<FileImageLinks> {
+    paths
+        .into_iter()
+        .filter_map(|file_path| {
+            let content = match std::fs::read_to_string(&file_path) {
+                Ok(content) => content,
+                Err(e) => {
+                    log::warn!(
+                        "[MdAuditor] 读取文件失败: {} - {}",
+                        safe_path(&file_path),
+                        e
+                    );
+                    return None;
+                }
+            };
+            let name = Path::new(&file_path)
+                .file_name()
+                .map(|name| name.to_string_lossy().to_string())
+                .unwrap_or_default();
+            let links = extract_remote_image_links(&content, &name)
+                .into_iter()
+                .map(|link| (link.url, link.line_number, link.syntax))
+                .collect();
+            Some(FileImageLinks { file_path, links })
+        })
+        .collect()
+}
+
+/// 跨文件去重，保持首次出现的顺序
+fn unique_urls(files: &[FileImageLinks]) -> Vec<String> {
+    let mut seen = HashSet::new();
+    files
+        .iter()
+        .flat_map(|file| file.links.iter().map(|(url, _, _)| url))
+        .filter(|url| seen.insert(url.as_str()))
+        .cloned()
+        .collect()
+}
+
+/// 从历史记录的 results JSON 中提取除失效链接外的成功上传
+fn backup_links_from_results(results_json: &str, broken_url: &str) -> Vec<AuditRepair> {
+    let Ok(serde_json::Value::Array(results)) = serde_json::from_str(results_json) else {
+        return Vec::new();
+    };
+
+    results
+        .iter()
+        .filter(|entry| entry.get("status").and_then(|s| s.as_str()) == Some("success"))
+        .filter_map(|entry| {
+            let service_id = entry.get("serviceId")?.as_str()?;
+            let url = entry.get("result")?.get("url")?.as_str()?;
+            (!url.is_empty() && url != broken_url).then(|| AuditRepair::BackupLink {
+                service_id: service_id.to_string(),
+                url: url.to_string(),
+            })
+        })
+        .collect()
+}
+
+/// 在历史记录中查找失效链接对应的上传，给出备份链接和本地原图
+async fn find_history_repairs(
+    db_path: &Path,
+    broken_urls: &[String],
+) -> Result<HashMap<String, Vec<AuditRepair>>, String> {
+    let mut repairs = HashMap::new();
+    if broken_urls.is_empty() || !db_path.exists() {
+        return Ok(repairs);
+    }
+
+    // 只读连接：写入始终由前端 tauri-plugin-sql 负责
+    let mut conn = SqliteConnectOptions::new()
+        .filename(db_path)
+        .read_only(true)
+        .connect()
+        .await
+        .map_err(|e| format!("打开历史记录数据库失败: {}", e))?;
+
+    for url in broken_urls {
+        let rows = sqlx::query(
+            "SELECT file_path, results FROM history_items \
+             WHERE generated_link = ?1 OR instr(results, ?1) > 0 \
+             ORDER BY timestamp DESC LIMIT 5",
+        )
+        .bind(url)
+        .fetch_all(&mut conn)
+        .await
+        .map_err(|e| format!("查询历史记录失败: {}", e))?;
+
+        let mut found: Vec<AuditRepair> = Vec::new();
+        for row in rows {
+            let results: String = row.try_get("results").unwrap_or_default();
+            for repair in backup_links_from_results(&results, url) {
+                if !found.contains(&repair) {
+                    found.push(repair);
+                }
+            }
+
+            let local_path: Option<String> = row.try_get("file_path").ok().flatten();
+            if let Some(local_path) = local_path.filter(|p| Path::new(p).is_file()) {
+                let repair = AuditRepair::ReuploadLocal { local_path };
+                if !found.contains(&repair) {
+                    found.push(repair);
+                }
+            }
+        }
+
+        if !found.is_empty() {
+            repairs.insert(url.clone(), found);
+        }
+    }
+
+    Ok(repairs)
+}
+
+/// 按文件组装报告，只保留包含失效图片的文件
+fn build_file_reports(
+    files: Vec<FileImageLinks>,
+    checks: &HashMap<String, CheckLinkResult>,
+    repairs: &HashMap<String, Vec<AuditRepair>>,
+) -> Vec<FileAuditReport> {
+    files
+        .into_iter()
+        .filter_map(|file| {
+            let broken: Vec<BrokenImage> = file
+                .links
+                .iter()
+                .filter_map(|(url, line_number, syntax)| {
+                    let check = checks.get(url).filter(|check| !check.is_valid)?;
+                    Some(BrokenImage {
+                        url: url.clone(),
+                        line_number: *line_number,
+                        syntax: syntax.clone(),
+                        error_type: check.error_type.clone(),
+                        status_code: check.status_code,
+                        error: check.error.clone(),
+                        suggestion: check.suggestion.clone(),
+                        repairs: repairs.get(url).cloned().unwrap_or_default(),
+                    })
+                })
+                .collect();
+
+            (!broken.is_empty()).then_some(FileAuditReport {
+                file_path: file.file_path,
+                total_images: file.links.len(),
+                broken,
+            })
+        })
+        .collect()
+}
+
+/// 审计目录下 Markdown / HTML 中的远程图片，输出按文件分组的失效报告
+///
+/// 进度通过 batch-check-progress 事件上报（batch_id 以 md-audit- 开头），
+/// 可用 cancel_batch_check / pause_batch_check 控制。
+#[tauri::command]
+pub async fn audit_markdown(
+    app: tauri::AppHandle,
+    window: tauri::Window,
+    dir: String,
+    options: Option<AuditMarkdownOptions>,
+    cancel_flag: tauri::State<'_, BatchCheckCancelFlag>,
+    pause_flag: tauri::State<'_, BatchCheckPauseFlag>,
+    limiter: tauri::State<'_, NetworkLimiter>,
+) -> Result<AuditMarkdownResult, AppError> {
+    let options = options.unwrap_or(AuditMarkdownOptions {
+        include_subfolders: true,
+        timeout_secs: None,
+    });
+    let root = path_utils::canonicalize(path_utils::normalize_input(&dir))
+        .map_err(|e| AppError::file_io(format!("路径无效或不存在: {} ({})", dir, e)))?;
+    if !root.is_dir() {
+        return Err(AppError::validation(format!("不是目录: {}", dir)));
+    }
+
+    let include_subfolders = options.include_subfolders;
+    let (files, skipped_dirs) = tokio::task::spawn_blocking(move || {
+        let (paths, skipped_dirs) = collect_files(
+            &root,
+            include_subfolders,
+            is_markdown_or_html_file,
+            &AtomicBool::new(false),
+        );
+        (collect_remote_links(paths), skipped_dirs)
+    })
+    .await?;
+
+    let total_files = files.len();
+    let total_links = files.iter().map(|file| file.links.len()).sum();
+    let urls = unique_urls(&files);
+    let unique_links = urls.len();
+    let batch_id = format!("md-audit-{}", chrono::Utc::now().timestamp_millis());
+
+    log::info!(
+        "[MdAuditor] 开始审计: {} 个文件, {} 处图片, {} 个唯一链接",
+        total_files,
+        total_links,
+        unique_links
+    );
+
+    let request = BatchCheckRequest {
+        links: urls
+            .into_iter()
+            .map(|url| BatchCheckItem {
+                url,
+                history_id: None,
+                service_id: None,
+                fallback_url: None,
+            })
+            .collect(),
+        batch_id: Some(batch_id.clone()),
+        concurrency: Some(AUDIT_CHECK_CONCURRENCY),
+        per_host_limit: None,
+        timeout_secs: options.timeout_secs,
+    };
+    let batch = run_batch_check(&window, request, &cancel_flag, &pause_flag, &limiter).await?;
+
+    let checks: HashMap<String, CheckLinkResult> = batch
+        .results
+        .into_iter()
+        .map(|item| (item.check.link.clone(), item.check))
+        .collect();
+    let mut broken_urls: Vec<String> = checks
+        .values()
+        .filter(|check| !check.is_valid)
+        .map(|check| check.link.clone())
+        .collect();
+    broken_urls.sort();
+
+    // 历史记录不可用时仍返回检测结果，只是缺少修复建议
+    let repairs = match crate::portable::history_db_path(&app) {
+        Ok(db_path) => find_history_repairs(&db_path, &broken_urls)
+            .await
+            .unwrap_or_else(|e| {
+                log::warn!("[MdAuditor] 查询修复建议失败: {}", e);
+                HashMap::new()
+            }),
+        Err(e) => {
+            log::warn!("[MdAuditor] 历史记录数据库不可用: {}", e);
+            HashMap::new()
+        }
+    };
+
+    let reports = build_file_reports(files, &checks, &repairs);
+    log::info!(
+        "[MdAuditor] 完成: {} 个失效链接, 涉及 {} 个文件",
+        broken_urls.len(),
+        reports.len()
+    );
+
+    Ok(AuditMarkdownResult {
+        batch_id,
+        total_files,
+        total_links,
+        unique_links,
+        broken_links: broken_urls.len(),
+        files: reports,
+        skipped_dirs,
+        cancelled: batch.cancelled,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn check(url: &str, is_valid: bool) -> CheckLinkResult {
+        CheckLinkResult {
+            link: url.to_string(),
+            is_valid,
+            status_code: if is_valid { Some(200) } else { Some(404) },
+            error: None,
+            error_type: if is_valid { "success" } else { "http_4xx" }.to_string(),
+            suggestion: None,
+            response_time: None,
+            detected_service: None,
+            browser_might_work: false,
+            content_type: None,
+            content_length: None,
+        }
+    }
+
+    #[test]
+    fn backup_links_skip_failed_and_broken_entries() {
+        let results = r#"[
+            {"serviceId": "weibo", "result": {"url": "https://wx1.sinaimg.cn/a.jpg"}, "status": "success"},
+            {"serviceId": "r2", "result": {"url": "https://img.example.com/a.jpg"}, "status": "success"},
+            {"serviceId": "jd", "status": "failed", "error": "timeout"}
+        ]"#;
+
+        assert_eq!(
+            backup_links_from_results(results, "https://wx1.sinaimg.cn/a.jpg"),
+            vec![AuditRepair::BackupLink {
+                service_id: "r2".to_string(),
+                url: "https://img.example.com/a.jpg".to_string(),
+            }]
+        );
+        assert!(backup_links_from_results("not json", "https://x").is_empty());
+    }
+
+    #[test]
+    fn unique_urls_dedupes_across_files_in_order() {
+        let files = vec![
+            FileImageLinks {
+                file_path: "a.md".to_string(),
+                links: vec![
+                    ("https://x/1.png".to_string(), 1, "markdown".to_string()),
+                    ("https://x/2.png".to_string(), 2, "markdown".to_string()),
+                ],
+            },
+            FileImageLinks {
+                file_path: "b.html".to_string(),
+                links: vec![
+                    ("https://x/2.png".to_string(), 5, "html".to_string()),
+                    ("https://x/3.png".to_string(), 6, "html".to_string()),
+                ],
+            },
+        ];
+
+        assert_eq!(
+            unique_urls(&files),
+            vec!["https://x/1.png", "https://x/2.png", "https://x/3.png"]
+        );
+    }
+
+    #[test]
+    fn build_file_reports_keeps_only_files_with_broken_images() {
+        let files = vec![
+            FileImageLinks {
+                file_path: "ok.md".to_string(),
+                links: vec![("https://x/ok.png".to_string(), 1, "markdown".to_string())],
+            },
+            FileImageLinks {
+                file_path: "bad.md".to_string(),
+                links: vec![
+                    ("https://x/ok.png".to_string(), 3, "markdown".to_string()),
+                    ("https://x/gone.png".to_string(), 7, "markdown".to_string()),
+                ],
+            },
+        ];
+        let checks = HashMap::from([
+            (
+                "https://x/ok.png".to_string(),
+                check("https://x/ok.png", true),
+            ),
+            (
+                "https://x/gone.png".to_string(),
+                check("https://x/gone.png", false),
+            ),
+        ]);
+        let repairs = HashMap::from([(
+            "https://x/gone.png".to_string(),
+            vec![AuditRepair::ReuploadLocal {
+                local_path: "/pics/gone.png".to_string(),
+            }],
+        )]);
+
+        let reports = build_file_reports(files, &checks, &repairs);
+
+        assert_eq!(reports.len(), 1);
+        assert_eq!(reports[0].file_path, "bad.md");
+        assert_eq!(reports[0].total_images, 2);
+        assert_eq!(reports[0].broken.len(), 1);
+        assert_eq!(reports[0].broken[0].line_number, 7);
+        assert_eq!(reports[0].broken[0].error_type, "http_4xx");
+        assert_eq!(reports[0].broken[0].repairs.len(), 1);
+    }
+}
@@ -0,0 +1,454 @@
+// Markdown 发布：上传本地图片并改写为图床链接
+//
+// 工作流：
+//   process_markdown(path) -> 收集 MD 文件 -> 提取本地图片引用并解析为绝对路径
+//   -> dry_run：仅返回将要改写的行（新链接用占位符表示）
+//   -> 否则按 CLI 导出的图床配置并发上传（同一图片只传一次）-> 改写并原子写回文件
+//
+// 上传在 Rust 侧完成，不产生前端队列条目；每张上传成功的图片会发出 md-process://uploaded 事件，
+// 前端用与上传队列相同的 saveHistoryItem 写入历史记录，之后可照常检测链接、迁移和删除。
+// 每次上传都先向共享的 NetworkLimiter 排队取名额，与界面上传、迁移等共用同一并发上限。
+
+use crate::atomic_file;
+use crate::cli;
+use crate::commands::md_scanner::{
+    collect_files, extract_local_image_refs, is_markdown_file, rewrite_image_urls, MdLineChange,
+};
+use crate::commands::net_limiter::NetworkLimiter;
+use crate::commands::utils::remember_uploaded_source;
+use crate::error::AppError;
+use crate::log_utils::safe_path;
+use crate::path_utils;
+use crate::server::upload_handler::{upload_single_file, ServerUploadConfig};
+use futures::stream::{self, Stream, StreamExt};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeSet, HashMap};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::AtomicBool;
+use tauri::Emitter;
+
+/// 单张图片上传成功的事件（前端据此写入历史记录）
+pub const MD_UPLOADED_EVENT: &str = "md-process://uploaded";
+
+/// 同时上传的图片数
+const MARKDOWN_UPLOAD_CONCURRENCY: usize = 3;
+
+const MARKDOWN_IMAGE_EXTENSIONS: &[&str] = &[
+    "png", "jpg", "jpeg", "gif", "webp", "bmp", "avif", "svg", "ico", "tif", "tiff",
+];
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ProcessMarkdownOptions {
+    /// 只预览改写结果，不上传也不写文件
+    #[serde(default)]
+    pub dry_run: bool,
+    #[serde(default = "default_include_subfolders")]
+    pub include_subfolders: bool,
+    /// cli-config.json 中的图床 ID；只配置了一个图床时可省略
+    #[serde(default)]
+    pub service: Option<String>,
+}
+
+fn default_include_subfolders() -> bool {
+    true
+}
+
+/// 按有限并发上传一组本地图片，结果按完成顺序产出
+///
+/// 每张图片先向 limiter 按图床排队取名额；process_markdown 与 Git 钩子共用。
+pub(crate) fn upload_images<'a>(
+    paths: BTreeSet<PathBuf>,
+    config: &'a ServerUploadConfig,
+    limiter: &'a NetworkLimiter,
+    host: &'a str,
+) -> impl Stream<Item = (PathBuf, Result<String, String>)> + 'a {
+    stream::iter(paths)
+        .map(move |path| async move {
+            let _permit = limiter.acquire(host).await;
+            let result = upload_single_file(&path.to_string_lossy(), config).await;
+            (path, result)
+        })
+        .buffer_unordered(MARKDOWN_UPLOAD_CONCURRENCY)
+}
+
+/// 单张本地图片的上传结果
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MarkdownImageUpload {
+    pub local_path: String,
+    pub url: Option<String>,
+    pub error: Option<String>,
+}
+
+/// md-process://uploaded 事件载荷
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MarkdownImageUploaded {
+    pub local_path: String,
+    pub service_id: String,
+    pub url: String,
+}
+
+/// 无法解析为本地图片文件的引用
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UnresolvedImageRef {
+    pub file_path: String,
+    pub line_number: usize,
+    pub reference: String,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MarkdownFileChange {
+    pub file_path: String,
+    pub changes: Vec<MdLineChange>,
+    /// 是否已写回磁盘（dry_run 或无变更时为 false）
+    pub written: bool,
+    pub error: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ProcessMarkdownResult {
+    pub dry_run: bool,
+    pub service: Option<String>,
+    pub files: Vec<MarkdownFileChange>,
+    pub uploads: Vec<MarkdownImageUpload>,
+    pub unresolved: Vec<UnresolvedImageRef>,
+    pub skipped_dirs: Vec<String>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct ProcessProgress {
+    completed: usize,
+    total: usize,
+    current_file: String,
+}
+
+/// 单个 MD 文件中可解析的本地图片：原始引用 → 绝对路径
+struct MarkdownLocalRefs {
+    file_path: String,
+    content: String,
+    refs: Vec<(String, PathBuf)>,
+}
+
+pub(crate) fn is_markdown_image_path(path: &Path) -> bool {
+    path.extension()
+        .and_then(|ext| ext.to_str())
+        .is_some_and(|ext| {
+            MARKDOWN_IMAGE_EXTENSIONS
+                .iter()
+                .any(|allowed| ext.eq_ignore_ascii_case(allowed))
+        })
+}
+
+/// 把 Markdown 中的本地引用解析为绝对路径（相对路径以 MD 文件所在目录为基准）
+pub(crate) fn resolve_local_image(md_dir: &Path, reference: &str) -> Option<PathBuf> {
+    let candidates: Vec<PathBuf> = if reference.starts_with("file://") {
+        url::Url::parse(reference)
+            .ok()
+            .and_then(|url| url.to_file_path().ok())
+            .into_iter()
+            .collect()
+    } else {
+        // 先按原样查找，再尝试百分号解码（如 my%20image.png）
+        let mut candidates = vec![PathBuf::from(reference)];
+        if let Ok(decoded) = urlencoding::decode(reference) {
+            if decoded != reference {
+                candidates.push(PathBuf::from(decoded.into_owned()));
+            }
+        }
+        candidates
+    };
+
+    candidates
+        .into_iter()
+        .map(|path| {
+            if path.is_absolute() {
+                path
+            } else {
+                md_dir.join(path)
+            }
+        })
+        .find(|path| path.is_file() && is_markdown_image_path(path))
+        .and_then(|path| path_utils::canonicalize(path).ok())
+}
+
+fn collect_local_refs(md_paths: Vec<String>) -> (Vec<MarkdownLocalRefs>, Vec<UnresolvedImageRef>) {
+    let mut files = Vec::new();
+    let mut unresolved = Vec::new();
+
+    for file_path in md_paths {
+        let content = match std::fs::read_to_string(&file_path) {
+            Ok(content) => content,
+            Err(e) => {
+                log::warn!(
+                    "[MdProcessor] 读取文件失败: {} - {}",
+                    safe_path(&file_path),
+                    e
+                );
+                continue;
+            }
+        };
+        let md_dir = Path::new(&file_path)
+            .parent()
+            .map(Path::to_path_buf)
+            .unwrap_or_default();
+
+        let mut refs = Vec::new();
+        for link in extract_local_image_refs(&content) {
+            match resolve_local_image(&md_dir, &link.url) {
+                Some(path) => refs.push((link.url, path)),
+                None => unresolved.push(UnresolvedImageRef {
+                    file_path: file_path.clone(),
+                    line_number: link.line_number,
+                    reference: link.url,
+                }),
+            }
+        }
+
+        if !refs.is_empty() {
+            files.push(MarkdownLocalRefs {
+                file_path,
+                content,
+                refs,
+            });
+        }
+    }
+
+    (files, unresolved)
+}
+
+fn dry_run_placeholder(path: &Path) -> String {
+    let name = path
+        .file_name()
+        .map(|name| name.to_string_lossy().to_string())
+        .unwrap_or_default();
+    format!("<待上传:{}>", name)
+}
+
+/// 扫描 Markdown 文件（或目录），上传其中的本地图片并改写为新链接
+#[tauri::command]
+pub async fn process_markdown(
+    window: tauri::Window,
+    path: String,
+    options: ProcessMarkdownOptions,
+    limiter: tauri::State<'_, NetworkLimiter>,
+) -> Result<ProcessMarkdownResult, AppError> {
+    let canonical = path_utils::canonicalize(path_utils::normalize_input(&path))
+        .map_err(|e| AppError::file_io(format!("路径无效或不存在: {} ({})", path, e)))?;
+
+    let include_subfolders = options.include_subfolders;
+    let (files, unresolved, skipped_dirs) = tokio::task::spawn_blocking(move || {
+        let (md_paths, skipped_dirs) = if canonical.is_dir() {
+            collect_files(
+                &canonical,
+                include_subfolders,
+                is_markdown_file,
+                &AtomicBool::new(false),
+            )
+        } else {
+            let name = canonical
+                .file_name()
+                .map(|name| name.to_string_lossy().to_string())
+                .unwrap_or_default();
+            if !is_markdown_file(&name) {
+                return Err(AppError::validation(format!(
+                    "不是 Markdown 文件: {}",
+                    name
+                )));
+            }
+            (vec![canonical.to_string_lossy().to_string()], Vec::new())
+        };
+        let (files, unresolved) = collect_local_refs(md_paths);
+        Ok((files, unresolved, skipped_dirs))
+    })
+    .await??;
+
+    // 同一图片被多篇文章引用时只上传一次（BTreeSet 保证结果顺序稳定）
+    let unique_images: BTreeSet<PathBuf> = files
+        .iter()
+        .flat_map(|file| file.refs.iter().map(|(_, path)| path.clone()))
+        .collect();
+
+    let mut service = None;
+    let mut uploaded: HashMap<PathBuf, String> = HashMap::new();
+    let mut uploads = Vec::new();
+
+    if options.dry_run {
+        for path in &unique_images {
+            uploaded.insert(path.clone(), dry_run_placeholder(path));
+            uploads.push(MarkdownImageUpload {
+                local_path: path.to_string_lossy().to_string(),
+                url: None,
+                error: None,
+            });
+        }
+    } else if !unique_images.is_empty() {
+        let (service_id, config) =
+            cli::resolve_service_config(options.service.as_deref()).map_err(AppError::config)?;
+        let host: &str = service.insert(service_id);
+
+        let total = unique_images.len();
+        let mut results = upload_images(unique_images, &config, limiter.inner(), host);
+
+        while let Some((path, result)) = results.next().await {
+            let local_path = path.to_string_lossy().to_string();
+            let _ = window.emit(
+                "md-process://progress",
+                ProcessProgress {
+                    completed: uploads.len() + 1,
+                    total,
+                    current_file: local_path.clone(),
+                },
+            );
+            match result {
+                Ok(url) => {
+                    remember_uploaded_source(&local_path);
+                    let _ = window.emit(
+                        MD_UPLOADED_EVENT,
+                        MarkdownImageUploaded {
+                            local_path: local_path.clone(),
+                            service_id: host.to_string(),
+                            url: url.clone(),
+                        },
+                    );
+                    uploaded.insert(path, url.clone());
+                    uploads.push(MarkdownImageUpload {
+                        local_path,
+                        url: Some(url),
+                        error: None,
+                    });
+                }
+                Err(e) => {
+                    log::warn!("[MdProcessor] 上传失败: {} - {}", safe_path(&local_path), e);
+                    uploads.push(MarkdownImageUpload {
+                        local_path,
+                        url: None,
+                        error: Some(e),
+                    });
+                }
+            }
+        }
+    }
+
+    let dry_run = options.dry_run;
+    let file_changes = tokio::task::spawn_blocking(move || {
+        files
+            .into_iter()
+            .map(|file| {
+                // 上传失败的图片保留原引用
+                let replacements: HashMap<String, String> = file
+                    .refs
+                    .iter()
+                    .filter_map(|(reference, path)| {
+                        uploaded
+                            .get(path)
+                            .map(|url| (reference.clone(), url.clone()))
+                    })
+                    .collect();
+                let (rewritten, changes) = rewrite_image_urls(&file.content, &replacements);
+
+                let mut written = false;
+                let mut error = None;
+                if !dry_run && !changes.is_empty() {
+                    match atomic_file::write(&file.file_path, &rewritten) {
+                        Ok(()) => written = true,
+                        Err(e) => {
+                            log::warn!(
+                                "[MdProcessor] 写回失败: {} - {}",
+                                safe_path(&file.file_path),
+                                e
+                            );
+                            error = Some(format!("写回文件失败: {}", e));
+                        }
+                    }
+                }
+
+                MarkdownFileChange {
+                    file_path: file.file_path,
+                    changes,
+                    written,
+                    error,
+                }
+            })
+            .collect::<Vec<_>>()
+    })
+    .await?;
+
+    log::info!(
+        "[MdProcessor] 完成: {} 个文件, {} 张图片, dry_run={}",
+        file_changes.len(),
+        uploads.len(),
+        dry_run
+    );
+
+    Ok(ProcessMarkdownResult {
+        dry_run,
+        service,
+        files: file_changes,
+        uploads,
+        unresolved,
+        skipped_dirs,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn temp_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!(
+            "picnexus-md-processor-{}-{}",
+            std::process::id(),
+            name
+        ));
+        std::fs::create_dir_all(dir.join("assets")).unwrap();
+        dir
+    }
+
+    #[test]
+    fn resolve_local_image_handles_relative_and_encoded_paths() {
+        let dir = temp_dir("resolve");
+        std::fs::write(dir.join("assets").join("my shot.png"), b"fake").unwrap();
+        let expected = path_utils::canonicalize(dir.join("assets").join("my shot.png")).unwrap();
+
+        assert_eq!(
+            resolve_local_image(&dir, "assets/my shot.png"),
+            Some(expected.clone())
+        );
+        assert_eq!(
+            resolve_local_image(&dir, "./assets/my%20shot.png"),
+            Some(expected)
+        );
+        assert_eq!(resolve_local_image(&dir, "assets/missing.png"), None);
+
+        let _ = std::fs::remove_dir_all(dir);
+    }
+
+    #[test]
+    fn collect_local_refs_reports_unresolved_references() {
+        let dir = temp_dir("collect");
+        std::fs::write(dir.join("assets").join("a.png"), b"fake").unwrap();
+        let md = dir.join("post.md");
+        std::fs::write(
+            &md,
+            "![a](assets/a.png)\n![b](assets/b.png)\n![c](https://example.com/c.png)\n",
+        )
+        .unwrap();
+
+        let (files, unresolved) = collect_local_refs(vec![md.to_string_lossy().to_string()]);
+
+        assert_eq!(files.len(), 1);
+        assert_eq!(files[0].refs.len(), 1);
+        assert_eq!(files[0].refs[0].0, "assets/a.png");
+        assert_eq!(unresolved.len(), 1);
+        assert_eq!(unresolved[0].reference, "assets/b.png");
+        assert_eq!(unresolved[0].line_number, 2);
+
+        let _ = std::fs::remove_dir_all(dir);
+    }
+}
@@ -1,8 +1,10 @@
 // MD 文件批量扫描 + 图片链接提取
 // 单次 IPC 完成递归目录遍历 + 文件读取 + 正则提取，消除 JS 侧数百次 IPC 往返
 
+use crate::commands::progress_throttle::ProgressThrottle;
 use crate::error::AppError;
 use crate::log_utils::safe_path;
+use crate::path_utils;
 use fancy_regex::Regex as FancyRegex;
 use regex::Regex;
 use serde::Serialize;
@@ -91,19 +93,21 @@ pub struct MdScanCancelFlag(pub Arc<AtomicBool>);
 /// MD 文件扩展名
 const MD_EXTENSIONS: &[&str] = &[".md", ".markdown"];
 
-fn is_markdown_file(name: &str) -> bool {
+pub(crate) fn is_markdown_file(name: &str) -> bool {
     let lower = name.to_lowercase();
     MD_EXTENSIONS.iter().any(|ext| lower.ends_with(ext))
 }
 
-/// 递归扫描目录，收集所有 MD 文件路径
+/// 递归扫描目录，收集所有文件名满足 accept 的文件路径（默认为 MD 文件）
 ///
 /// 安全约束：
 /// - visited 记录已规范化的目录，避免符号链接循环导致栈溢出
 /// - depth 限制递归深度，兜底防止 visited 因规范化失败失守
+#[allow(clippy::too_many_arguments)]
 fn scan_md_files(
     dir: &Path,
     include_subfolders: bool,
+    accept: fn(&str) -> bool,
     cancel: &AtomicBool,
     results: &mut Vec<String>,
     skipped_dirs: &mut Vec<String>,
@@ -140,7 +144,7 @@ fn scan_md_files(
             sub_dirs.push(path);
         } else if path.is_file() {
             if let Some(name) = path.file_name().and_then(|n| n.to_str()) {
-                if is_markdown_file(name) {
+                if accept(name) {
                     results.push(path.to_string_lossy().into_owned());
                 }
             }
@@ -149,7 +153,7 @@ fn scan_md_files(
 
     for sub in sub_dirs {
         // 规范化后再查重：解析符号链接，避免 a/link → a 的循环
-        let canonical = match std::fs::canonicalize(&sub) {
+        let canonical = match path_utils::canonicalize(&sub) {
             Ok(c) => c,
             Err(e) => {
                 log::warn!("[MdScanner] 规范化子目录失败，跳过: {:?} - {}", sub, e);
@@ -164,6 +168,7 @@ fn scan_md_files(
         scan_md_files(
             &sub,
             include_subfolders,
+            accept,
             cancel,
             results,
             skipped_dirs,
@@ -241,6 +246,8 @@ fn strip_inline_code(line: &str) -> String {
 struct MarkdownImageMatch {
     start: usize,
     end: usize,
+    /// 链接目标起点（紧随 `(` 之后），改写时只在此之后查找 URL，避免命中同名 alt
+    dest_start: usize,
     alt: String,
     url: String,
 }
@@ -314,6 +321,7 @@ fn parse_markdown_image_at(line: &str, start: usize) -> Option<MarkdownImageMatc
         return Some(MarkdownImageMatch {
             start,
             end: close,
+            dest_start: url_start,
             alt,
             url: line[url_start + 1..url_end].trim().to_string(),
         });
@@ -337,6 +345,7 @@ fn parse_markdown_image_at(line: &str, start: usize) -> Option<MarkdownImageMatc
                 return Some(MarkdownImageMatch {
                     start,
                     end: i + 1,
+                    dest_start: url_start,
                     alt,
                     url: line[url_start..i].trim().to_string(),
                 });
@@ -346,6 +355,7 @@ fn parse_markdown_image_at(line: &str, start: usize) -> Option<MarkdownImageMatc
             return Some(MarkdownImageMatch {
                 start,
                 end: close,
+                dest_start: url_start,
                 alt,
                 url: line[url_start..i].trim().to_string(),
             });
@@ -376,41 +386,95 @@ fn parse_markdown_image_matches(line: &str) -> Vec<MarkdownImageMatch> {
     matches
 }
 
+/// 围栏代码块跟踪
+#[derive(Default)]
+struct FenceTracker {
+    // 当前打开的围栏：(开头字符, 反引号/波浪数)；None 表示未在围栏内
+    // 仅同字符且反引号数 ≥ open 时才关闭，避免 ``` 与 ~~~ 串扰和嵌套误判
+    open: Option<(char, usize)>,
+}
+
+impl FenceTracker {
+    /// 返回该行是否属于围栏（围栏标记行或围栏内的行）
+    fn skip_line(&mut self, line: &str) -> bool {
+        if let Some(caps) = FENCE_RE.captures(line) {
+            let m = caps.get(1).unwrap();
+            let seq = m.as_str();
+            let c = seq.chars().next().unwrap();
+            let len = seq.len();
+            // close fence 尾部必须只有空白（CommonMark 4.5），否则仍视为围栏内字面量
+            let tail_ws = line[m.end()..].chars().all(char::is_whitespace);
+            match self.open {
+                None => {
+                    self.open = Some((c, len));
+                    return true;
+                }
+                Some((oc, olen)) if oc == c && len >= olen && tail_ws => {
+                    self.open = None;
+                    return true;
+                }
+                _ => {} // 同字符但长度不足 / 不同字符 / 尾部带 info string：视为围栏内字面量
+            }
+        }
+        self.open.is_some()
+    }
+}
+
 /// 从 Markdown 内容中提取图片链接（与 JS 侧 extractImageLinks 逻辑一致）
 /// include_code_blocks=true 时不跳过围栏块和行内代码，与 JS 侧 options.includeCodeBlocks 语义相同
 fn extract_image_links(content: &str, include_code_blocks: bool) -> Vec<MdImageLink> {
+    extract_image_refs(content, include_code_blocks, is_valid_image_url)
+}
+
+/// 判断是否为本地图片引用（相对路径、绝对路径或 file:// URL）
+fn is_local_image_ref(url: &str) -> bool {
+    if url.is_empty() || url.starts_with('#') || url.starts_with("//") {
+        return false;
+    }
+    if url.starts_with("file://") {
+        return true;
+    }
+    // 带 scheme 的一律视为远程资源（http:、data:、mailto: 等）；
+    // Windows 盘符（C:\ / C:/）不算 scheme
+    match url.find(':') {
+        Some(1) => url.as_bytes()[0].is_ascii_alphabetic(),
+        Some(idx) => url[..idx].contains(['/', '\\']),
+        None => true,
+    }
+}
+
+/// HTML 文件扩展名（审计时与 MD 一并扫描）
+const HTML_EXTENSIONS: &[&str] = &[".html", ".htm"];
+
+pub(crate) fn is_markdown_or_html_file(name: &str) -> bool {
+    let lower = name.to_lowercase();
+    is_markdown_file(&lower) || HTML_EXTENSIONS.iter().any(|ext| lower.ends_with(ext))
+}
+
+/// 提取远程图片链接；HTML 文件没有围栏代码块语义，按原文提取
+pub(crate) fn extract_remote_image_links(content: &str, file_name: &str) -> Vec<MdImageLink> {
+    extract_image_links(content, !is_markdown_file(file_name))
+}
+
+/// 提取本地图片引用（始终跳过代码块，避免改写示例代码）
+pub(crate) fn extract_local_image_refs(content: &str) -> Vec<MdImageLink> {
+    extract_image_refs(content, false, is_local_image_ref)
+}
+
+fn extract_image_refs(
+    content: &str,
+    include_code_blocks: bool,
+    accept: fn(&str) -> bool,
+) -> Vec<MdImageLink> {
     let mut results = Vec::new();
     let mut seen_urls = std::collections::HashSet::new();
-    // 当前打开的围栏：(开头字符, 反引号/波浪数)；None 表示未在围栏内
-    // 仅同字符且反引号数 ≥ open 时才关闭，避免 ``` 与 ~~~ 串扰和嵌套误判
-    let mut fence_open: Option<(char, usize)> = None;
+    let mut fence = FenceTracker::default();
 
     for (i, line) in content.lines().enumerate() {
         let line_number = i + 1;
 
-        if !include_code_blocks {
-            if let Some(caps) = FENCE_RE.captures(line) {
-                let m = caps.get(1).unwrap();
-                let seq = m.as_str();
-                let c = seq.chars().next().unwrap();
-                let len = seq.len();
-                // close fence 尾部必须只有空白（CommonMark 4.5），否则仍视为围栏内字面量
-                let tail_ws = line[m.end()..].chars().all(char::is_whitespace);
-                match fence_open {
-                    None => {
-                        fence_open = Some((c, len));
-                        continue;
-                    }
-                    Some((oc, olen)) if oc == c && len >= olen && tail_ws => {
-                        fence_open = None;
-                        continue;
-                    }
-                    _ => {} // 同字符但长度不足 / 不同字符 / 尾部带 info string：视为围栏内字面量
-                }
-            }
-            if fence_open.is_some() {
-                continue;
-            }
+        if !include_code_blocks && fence.skip_line(line) {
+            continue;
         }
 
         // 代码块模式下直接用原始行，否则剥离行内代码
@@ -425,7 +489,7 @@ fn extract_image_links(content: &str, include_code_blocks: bool) -> Vec<MdImageL
         // Markdown 图片
         for md_match in parse_markdown_image_matches(&stripped) {
             let url = md_match.url.trim();
-            if is_valid_image_url(url) && seen_urls.insert(url.to_string()) {
+            if accept(url) && seen_urls.insert(url.to_string()) {
                 let original_text = if md_match.end <= line.len() {
                     line[md_match.start..md_match.end].to_string()
                 } else {
@@ -445,7 +509,7 @@ fn extract_image_links(content: &str, include_code_blocks: bool) -> Vec<MdImageL
         // HTML img 标签
         for cap in HTML_IMG_RE.captures_iter(&stripped) {
             let url = cap[1].trim();
-            if is_valid_image_url(url) && seen_urls.insert(url.to_string()) {
+            if accept(url) && seen_urls.insert(url.to_string()) {
                 let start = cap.get(0).unwrap().start();
                 let len = cap.get(0).unwrap().len();
                 let original_text = if start + len <= line.len() {
@@ -468,6 +532,113 @@ fn extract_image_links(content: &str, include_code_blocks: bool) -> Vec<MdImageL
     results
 }
 
+/// 改写产生的单行变更
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MdLineChange {
+    pub line_number: usize,
+    pub before: String,
+    pub after: String,
+}
+
+/// 按 旧 URL → 新 URL 映射改写图片链接（跳过代码块，保留原有换行符）
+///
+/// 与提取不同，这里会改写同一 URL 的每一处出现。
+pub(crate) fn rewrite_image_urls(
+    content: &str,
+    replacements: &std::collections::HashMap<String, String>,
+) -> (String, Vec<MdLineChange>) {
+    let mut output = String::with_capacity(content.len());
+    let mut changes = Vec::new();
+    let mut fence = FenceTracker::default();
+
+    for (i, raw_line) in content.split_inclusive('\n').enumerate() {
+        let body_len = raw_line.trim_end_matches(['\r', '\n']).len();
+        let (line, ending) = raw_line.split_at(body_len);
+
+        if fence.skip_line(line) {
+            output.push_str(raw_line);
+            continue;
+        }
+
+        // 在剥离行内代码后的副本上定位（等长替换，偏移与原行一致），再改写原行
+        let stripped = strip_inline_code(line);
+        let mut spans: Vec<(usize, usize, &str)> = Vec::new();
+        for md_match in parse_markdown_image_matches(&stripped) {
+            let url = md_match.url.trim();
+            if let Some(new_url) = replacements.get(url) {
+                if let Some(offset) = line[md_match.dest_start..md_match.end].find(url) {
+                    let start = md_match.dest_start + offset;
+                    spans.push((start, start + url.len(), new_url.as_str()));
+                }
+            }
+        }
+        for cap in HTML_IMG_RE.captures_iter(&stripped) {
+            let m = cap.get(1).unwrap();
+            let trimmed_start = m.start() + (m.as_str().len() - m.as_str().trim_start().len());
+            let url = m.as_str().trim();
+            if let Some(new_url) = replacements.get(url) {
+                spans.push((trimmed_start, trimmed_start + url.len(), new_url.as_str()));
+            }
+        }
+
+        if spans.is_empty() {
+            output.push_str(raw_line);
+            continue;
+        }
+
+        spans.sort_by_key(|(start, _, _)| *start);
+        let mut rewritten = String::with_capacity(line.len());
+        let mut cursor = 0usize;
+        for (start, end, new_url) in spans {
+            if start < cursor {
+                continue;
+            }
+            rewritten.push_str(&line[cursor..start]);
+            rewritten.push_str(new_url);
+            cursor = end;
+        }
+        rewritten.push_str(&line[cursor..]);
+
+        output.push_str(&rewritten);
+        output.push_str(ending);
+        if rewritten != line {
+            changes.push(MdLineChange {
+                line_number: i + 1,
+                before: line.to_string(),
+                after: rewritten,
+            });
+        }
+    }
+
+    (output, changes)
+}
+
+/// 收集目录下文件名满足 accept 的文件，返回 (文件列表, 跳过的目录)
+pub(crate) fn collect_files(
+    root: &Path,
+    include_subfolders: bool,
+    accept: fn(&str) -> bool,
+    cancel: &AtomicBool,
+) -> (Vec<String>, Vec<String>) {
+    let mut paths = Vec::new();
+    let mut skipped = Vec::new();
+    let mut visited: HashSet<PathBuf> = HashSet::new();
+    // 调用方传入的是已 canonicalize 的目录，先塞入 visited 防自环
+    visited.insert(root.to_path_buf());
+    scan_md_files(
+        root,
+        include_subfolders,
+        accept,
+        cancel,
+        &mut paths,
+        &mut skipped,
+        &mut visited,
+        0,
+    );
+    (paths, skipped)
+}
+
 /// 主命令：单次 IPC 完成目录扫描 + 文件读取 + 链接提取
 #[tauri::command]
 pub async fn scan_md_folder(
@@ -482,7 +653,7 @@ pub async fn scan_md_folder(
     cancel.store(false, Ordering::SeqCst);
 
     // 路径安全校验：规范化 + 确认是目录
-    let canonical = std::fs::canonicalize(&dir)
+    let canonical = path_utils::canonicalize(path_utils::normalize_input(&dir))
         .map_err(|e| AppError::file_io(format!("路径无效或不存在: {} ({})", dir, e)))?;
     if !canonical.is_dir() {
         return Err(AppError::file_io(format!("路径不是目录: {}", dir)));
@@ -493,19 +664,12 @@ pub async fn scan_md_folder(
     let cancel_clone = cancel.clone();
     let window_clone = window.clone();
     let scan_result = tokio::task::spawn_blocking(move || {
-        let mut paths = Vec::new();
-        let mut skipped = Vec::new();
-        let mut visited: HashSet<PathBuf> = HashSet::new();
-        // 入口目录已在外层 canonicalize 过，先塞入 visited 防自环
-        visited.insert(PathBuf::from(&dir_clone));
-        scan_md_files(
+        // 入口目录已在外层 canonicalize 过
+        let (paths, skipped) = collect_files(
             Path::new(&dir_clone),
             include_subfolders,
+            is_markdown_file,
             &cancel_clone,
-            &mut paths,
-            &mut skipped,
-            &mut visited,
-            0,
         );
 
         // 发送扫描完成进度
@@ -523,8 +687,7 @@ pub async fn scan_md_folder(
 
         (paths, skipped)
     })
-    .await
-    .map_err(|e| AppError::file_io(format!("扫描线程异常: {}", e)))?;
+    .await?;
 
     let (md_paths, skipped_dirs) = scan_result;
 
@@ -549,6 +712,7 @@ pub async fn scan_md_folder(
         let mut results: Vec<MdFileResult> = Vec::new();
         let mut total_links = 0usize;
         let mut read_failed_files = Vec::new();
+        let mut throttle = ProgressThrottle::default();
 
         for (idx, path_str) in md_paths.iter().enumerate() {
             if cancel_clone.load(Ordering::Relaxed) {
@@ -577,8 +741,8 @@ pub async fn scan_md_folder(
                 }
             }
 
-            // 每 5 个文件或最后一个文件发送进度
-            if (idx + 1) % 5 == 0 || idx + 1 == total_files {
+            // 节流发送进度，最后一个文件强制发送
+            if idx + 1 == total_files || throttle.ready() {
                 let _ = window_clone.emit(
                     "md-scan://progress",
                     ScanProgress {
@@ -595,8 +759,7 @@ pub async fn scan_md_folder(
 
         (results, total_links, read_failed_files)
     })
-    .await
-    .map_err(|e| AppError::file_io(format!("读取线程异常: {}", e)))?;
+    .await?;
 
     let (read_files, total_links, read_failed_files) = files;
     let cancelled = cancel.load(Ordering::SeqCst);
@@ -636,6 +799,7 @@ pub async fn cancel_md_scan(
 #[cfg(test)]
 mod tests {
     use super::*;
+    use std::collections::HashMap;
 
     // ---------- is_markdown_file ----------
 
@@ -1075,4 +1239,72 @@ line 4";
         let links = extract_image_links(content, false);
         assert_eq!(links[0].original_text, "![alt](https://example.com/x.jpg)");
     }
+
+    // ---------- 本地图片引用 / 改写 ----------
+
+    #[test]
+    fn local_image_ref_accepts_paths_and_rejects_remote() {
+        assert!(is_local_image_ref("assets/a.png"));
+        assert!(is_local_image_ref("./a b.png"));
+        assert!(is_local_image_ref("/home/me/a.png"));
+        assert!(is_local_image_ref("C:\\Users\\me\\a.png"));
+        assert!(is_local_image_ref("file:///tmp/a.png"));
+        assert!(!is_local_image_ref("https://example.com/a.png"));
+        assert!(!is_local_image_ref("//cdn.example.com/a.png"));
+        assert!(!is_local_image_ref("data:image/png;base64,xxx"));
+        assert!(!is_local_image_ref("#anchor"));
+    }
+
+    #[test]
+    fn extract_local_refs_skips_code_blocks() {
+        let content = "![a](a.png)\n```\n![b](b.png)\n```\n![c](https://x.com/c.png)";
+        let links = extract_local_image_refs(content);
+        assert_eq!(links.len(), 1);
+        assert_eq!(links[0].url, "a.png");
+    }
+
+    #[test]
+    fn rewrite_replaces_every_occurrence_and_keeps_line_endings() {
+        let content =
+            "![a](a.png) and ![again](a.png)\r\n<img src=\"a.png\" />\r\n`![code](a.png)`\r\n";
+        let replacements = HashMap::from([(
+            "a.png".to_string(),
+            "https://cdn.example.com/a.png".to_string(),
+        )]);
+
+        let (rewritten, changes) = rewrite_image_urls(content, &replacements);
+
+        assert_eq!(
+            rewritten,
+            "![a](https://cdn.example.com/a.png) and ![again](https://cdn.example.com/a.png)\r\n<img src=\"https://cdn.example.com/a.png\" />\r\n`![code](a.png)`\r\n"
+        );
+        assert_eq!(changes.len(), 2);
+        assert_eq!(changes[0].line_number, 1);
+        assert_eq!(changes[1].line_number, 2);
+        assert_eq!(changes[1].before, "<img src=\"a.png\" />");
+    }
+
+    #[test]
+    fn rewrite_skips_alt_text_equal_to_the_path() {
+        let content = "![a.png](a.png) ![a.png](<a.png> \"a.png\")\n";
+        let replacements = HashMap::from([("a.png".to_string(), "https://x/a.png".to_string())]);
+
+        let (rewritten, _) = rewrite_image_urls(content, &replacements);
+
+        assert_eq!(
+            rewritten,
+            "![a.png](https://x/a.png) ![a.png](<https://x/a.png> \"a.png\")\n"
+        );
+    }
+
+    #[test]
+    fn rewrite_leaves_fenced_code_untouched() {
+        let content = "```md\n![a](a.png)\n```\n";
+        let replacements = HashMap::from([("a.png".to_string(), "https://x/a.png".to_string())]);
+
+        let (rewritten, changes) = rewrite_image_urls(content, &replacements);
+
+        assert_eq!(rewritten, content);
+        assert!(changes.is_empty());
+    }
 }
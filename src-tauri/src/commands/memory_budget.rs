@@ -0,0 +1,241 @@
+// src-tauri/src/commands/memory_budget.rs
+// 图片解码内存预算：限制同时在内存中的解码缓冲总量
+//
+// 批量压缩 / 转换时前端会并发调用多个命令，每张大图解码后按 RGBA 计算可达数百 MB；
+// 预算用尽时新的解码任务排队等待，已占用的缓冲释放后再继续，避免进程膨胀到数 GB。
+// 可丢弃的内存缓存（如剪贴板 PNG 副本）也计入预算，但只占用当前空余额度，且不会让解码任务一直等待。
+
+use std::path::Path;
+use std::sync::{LazyLock, Mutex};
+
+use tokio::sync::Notify;
+
+use crate::error::AppError;
+
+/// 默认预算（MB），与前端 DEFAULT_CONFIG.memoryBudget 保持一致
+const DEFAULT_BUDGET_MB: u64 = 1024;
+/// 可配置的预算范围（MB）
+const MIN_BUDGET_MB: u64 = 256;
+const MAX_BUDGET_MB: u64 = 16 * 1024;
+
+static GLOBAL_BUDGET: LazyLock<MemoryBudget> =
+    LazyLock::new(|| MemoryBudget::new(Some(DEFAULT_BUDGET_MB * 1024 * 1024)));
+
+struct BudgetState {
+    /// None 表示不限制
+    limit: Option<u64>,
+    in_use: u64,
+    /// in_use 中可丢弃缓存占用的部分；只剩缓存时解码任务视预算为空闲
+    cached: u64,
+}
+
+pub struct MemoryBudget {
+    state: Mutex<BudgetState>,
+    released: Notify,
+}
+
+/// 预算占用凭证，drop 时归还
+pub struct MemoryReservation<'a> {
+    budget: &'a MemoryBudget,
+    bytes: u64,
+    cached: bool,
+}
+
+impl Drop for MemoryReservation<'_> {
+    fn drop(&mut self) {
+        let mut state = self.budget.lock();
+        state.in_use = state.in_use.saturating_sub(self.bytes);
+        if self.cached {
+            state.cached = state.cached.saturating_sub(self.bytes);
+        }
+        drop(state);
+        self.budget.released.notify_waiters();
+    }
+}
+
+impl MemoryBudget {
+    fn new(limit: Option<u64>) -> Self {
+        Self {
+            state: Mutex::new(BudgetState {
+                limit,
+                in_use: 0,
+                cached: 0,
+            }),
+            released: Notify::new(),
+        }
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, BudgetState> {
+        self.state.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    fn try_reserve(&self, bytes: u64) -> bool {
+        let mut state = self.lock();
+        // 预算空闲（或只剩缓存）时总是放行，单张超过预算的图片也能处理（只是独占）
+        let fits = match state.limit {
+            None => true,
+            Some(limit) => {
+                state.in_use == state.cached || state.in_use.saturating_add(bytes) <= limit
+            }
+        };
+        if fits {
+            state.in_use += bytes;
+        }
+        fits
+    }
+
+    /// 占用 `bytes` 字节预算，不足时等待其他任务释放
+    pub async fn reserve(&self, bytes: u64) -> MemoryReservation<'_> {
+        loop {
+            // 先登记等待再检查，避免检查与等待之间的释放通知丢失
+            let released = self.released.notified();
+            tokio::pin!(released);
+            released.as_mut().enable();
+
+            if self.try_reserve(bytes) {
+                return MemoryReservation {
+                    budget: self,
+                    bytes,
+                    cached: false,
+                };
+            }
+            released.await;
+        }
+    }
+
+    /// 为可丢弃的缓存占用 `bytes` 字节：只使用当前空余额度，不足时返回 None
+    fn try_reserve_cache(&self, bytes: u64) -> Option<MemoryReservation<'_>> {
+        let mut state = self.lock();
+        if let Some(limit) = state.limit {
+            if state.in_use.saturating_add(bytes) > limit {
+                return None;
+            }
+        }
+        state.in_use += bytes;
+        state.cached += bytes;
+        Some(MemoryReservation {
+            budget: self,
+            bytes,
+            cached: true,
+        })
+    }
+
+    fn set_limit(&self, limit: Option<u64>) {
+        self.lock().limit = limit;
+        // 预算调大后让排队的任务重新检查
+        self.released.notify_waiters();
+    }
+}
+
+/// 按图片头部估算解码后的内存占用（RGBA 每像素 4 字节）
+///
+/// 读不到头部时按文件大小估算，让格式异常的文件也计入预算
+pub fn estimate_decode_bytes(path: &Path) -> u64 {
+    match imagesize::size(path) {
+        Ok(size) => (size.width as u64)
+            .saturating_mul(size.height as u64)
+            .saturating_mul(4),
+        Err(_) => std::fs::metadata(path).map(|m| m.len()).unwrap_or(0),
+    }
+}
+
+/// 为即将解码的图片占用全局预算
+///
+/// `factor` 为同时存在的像素缓冲份数（如解码 + 缩放输出为 2）
+pub async fn reserve_for_image(path: &Path, factor: u64) -> MemoryReservation<'static> {
+    let path = path.to_path_buf();
+    let estimate = tokio::task::spawn_blocking(move || estimate_decode_bytes(&path))
+        .await
+        .unwrap_or(0);
+    GLOBAL_BUDGET
+        .reserve(estimate.saturating_mul(factor.max(1)))
+        .await
+}
+
+/// 为可丢弃的内存缓存占用全局预算；预算紧张时返回 None，调用方应放弃缓存
+pub fn reserve_for_cache(bytes: u64) -> Option<MemoryReservation<'static>> {
+    GLOBAL_BUDGET.try_reserve_cache(bytes)
+}
+
+/// 设置解码内存预算（MB），传 None 取消限制
+#[tauri::command]
+pub fn set_memory_budget(limit_mb: Option<u64>) -> Result<(), AppError> {
+    if let Some(mb) = limit_mb {
+        if !(MIN_BUDGET_MB..=MAX_BUDGET_MB).contains(&mb) {
+            return Err(AppError::validation(format!(
+                "内存预算需在 {}-{} MB 之间",
+                MIN_BUDGET_MB, MAX_BUDGET_MB
+            )));
+        }
+    }
+    GLOBAL_BUDGET.set_limit(limit_mb.map(|mb| mb * 1024 * 1024));
+    log::info!(
+        "[内存预算] 已设置为 {}",
+        limit_mb.map_or("不限制".to_string(), |mb| format!("{} MB", mb))
+    );
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    #[tokio::test]
+    async fn waits_until_budget_is_released() {
+        let budget = MemoryBudget::new(Some(100));
+        let first = budget.reserve(80).await;
+
+        // 超出预算的第二个任务被挂起
+        let blocked = tokio::time::timeout(Duration::from_millis(50), budget.reserve(40)).await;
+        assert!(blocked.is_err());
+
+        drop(first);
+        let second = tokio::time::timeout(Duration::from_millis(500), budget.reserve(40)).await;
+        assert!(second.is_ok());
+    }
+
+    #[tokio::test]
+    async fn oversized_request_runs_alone() {
+        let budget = MemoryBudget::new(Some(100));
+        let big = budget.reserve(500).await;
+        assert_eq!(budget.lock().in_use, 500);
+        drop(big);
+        assert_eq!(budget.lock().in_use, 0);
+    }
+
+    #[tokio::test]
+    async fn caches_use_only_spare_budget_and_never_block_decoding() {
+        let budget = MemoryBudget::new(Some(100));
+        let cache = budget.try_reserve_cache(80).unwrap();
+        assert!(budget.try_reserve_cache(40).is_none());
+
+        // 只剩缓存占用时解码任务照常放行
+        let decode = tokio::time::timeout(Duration::from_millis(50), budget.reserve(60)).await;
+        assert!(decode.is_ok());
+        drop(decode);
+
+        drop(cache);
+        let state = budget.lock();
+        assert_eq!((state.in_use, state.cached), (0, 0));
+    }
+
+    #[tokio::test]
+    async fn raising_limit_wakes_waiters() {
+        let budget = std::sync::Arc::new(MemoryBudget::new(Some(100)));
+        let _held = budget.reserve(100).await;
+
+        let waiter = {
+            let budget = budget.clone();
+            tokio::spawn(async move {
+                let _r = budget.reserve(50).await;
+            })
+        };
+        tokio::time::sleep(Duration::from_millis(20)).await;
+        budget.set_limit(None);
+
+        assert!(tokio::time::timeout(Duration::from_millis(500), waiter)
+            .await
+            .is_ok());
+    }
+}
@@ -2,23 +2,73 @@ pub mod r2;
 pub mod upload;
 pub mod user_files;
 
+pub mod alt_text;
 pub mod bilibili;
+pub mod cache_manager;
 pub mod chaoxing;
 pub mod cli_path;
 pub mod clipboard;
+pub mod clipboard_rehost;
+pub mod clock_skew;
+pub mod connection_probe;
+pub mod context_menu;
+pub mod cpu_pool;
+pub mod crash_report;
+pub mod deadline;
+pub mod diagnostics;
+pub mod disk_space;
+pub mod drag_out;
+pub mod file_cache;
+pub mod file_protocol;
 pub mod github;
+pub mod host_capabilities;
+pub mod host_health;
+pub mod host_migrator;
+pub mod http_client;
+pub mod image_compare;
 pub mod image_compress;
 pub mod image_meta;
 pub mod imgur;
 pub mod jd;
 pub mod link_checker;
+pub mod link_repair_webhook;
+pub mod link_templates;
+pub mod log_level;
+pub mod md_auditor;
+pub mod md_processor;
 pub mod md_scanner;
+pub mod memory_budget;
 pub mod nami;
 pub mod nami_token;
+pub mod net_limiter;
 pub mod nowcoder;
+pub mod object_name;
+pub mod palette;
+pub mod path_stat;
+pub mod plugin_uploader;
+pub mod post_upload_action;
+pub mod presets;
+pub mod progress_throttle;
 pub mod qiyu;
 pub mod qiyu_token;
+pub mod qr_code;
+pub mod recent_dirs;
+pub mod request_headers;
+pub mod retry_failed;
 pub mod s3_compatible;
+pub mod secret_vault;
+pub mod share_page;
+pub mod short_link;
 pub mod smms;
+pub mod startup_profile;
+pub mod task_registry;
+pub mod tempfiles;
+pub mod thumbnail_cache;
+pub mod thumbnail_prefetch;
+pub mod time_format;
+pub mod upload_manifest;
+pub mod uploader_registry;
+pub mod usage_stats;
 pub mod utils;
+pub mod window_capture;
 pub mod zhihu;
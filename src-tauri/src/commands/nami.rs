@@ -11,8 +11,11 @@ use sha1::{Digest as Sha1Digest, Sha1};
 use sha2::Sha256;
 use tauri::{Emitter, Manager, Window};
 
+use super::cpu_pool;
 use super::nami_token::fetch_nami_token_internal;
-use super::utils::read_file_bytes;
+use super::net_limiter::NetworkLimiter;
+use super::task_registry::TaskRegistry;
+use super::utils::{read_file_bytes, remember_uploaded_source};
 use crate::error::{AppError, IntoAppError};
 use crate::log_utils::{safe_path, safe_url, summarize_text};
 
@@ -535,7 +538,34 @@ pub async fn upload_to_nami(
     file_path: String,
     cookie: String,
     auth_token: String,
+    limiter: tauri::State<'_, NetworkLimiter>,
+    tasks: tauri::State<'_, TaskRegistry>,
 ) -> Result<NamiUploadResult, AppError> {
+    tasks
+        .run(
+            Some(&id),
+            upload_to_nami_inner(
+                window,
+                id.clone(),
+                file_path.clone(),
+                cookie,
+                auth_token,
+                limiter,
+            ),
+        )
+        .await
+        .inspect(|_| remember_uploaded_source(&file_path))
+}
+
+async fn upload_to_nami_inner(
+    window: Window,
+    id: String,
+    file_path: String,
+    cookie: String,
+    auth_token: String,
+    limiter: tauri::State<'_, NetworkLimiter>,
+) -> Result<NamiUploadResult, AppError> {
+    let _permit = limiter.acquire("nami").await;
     log::info!("[Nami] 开始上传文件: {}", safe_path(&file_path));
 
     // 1. 读取文件
@@ -553,8 +583,13 @@ pub async fn upload_to_nami(
         .ok_or_else(|| AppError::validation("无法获取文件扩展名"))?
         .to_lowercase();
 
-    // 3. 计算文件哈希
-    let hash = calculate_file_hash(&buffer);
+    // 3. 计算文件哈希（放到 CPU 线程池，避免阻塞异步运行时）
+    let (buffer, hash) = cpu_pool::spawn(move || {
+        let hash = calculate_file_hash(&buffer);
+        (buffer, hash)
+    })
+    .await
+    .map_err(|e| AppError::external(format!("计算文件哈希失败: {}", e)))?;
     let file_key = format!("web/{}.{}", hash, ext);
     log::debug!("[Nami] 文件 key: {}", file_key);
 
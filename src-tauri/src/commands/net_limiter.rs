@@ -0,0 +1,112 @@
+// src-tauri/src/commands/net_limiter.rs
+// 出站请求并发闸门：上传、链接检测、图片下载共用的全局 + 单主机信号量
+//
+// 各批量功能原本各自限流（批量检测自带并发上限、批量上传由前端控制），
+// 同时跑批量检测和批量上传时并发会叠加，一次打开上百个连接，容易触发系统或路由器的连接数限制。
+// 所有发请求的命令先从这里取许可，总连接数与单主机连接数都有上限。
+
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+
+use tokio::sync::{OwnedSemaphorePermit, Semaphore};
+
+/// 全局同时进行的出站请求上限
+const GLOBAL_LIMIT: usize = 32;
+/// 单个主机（图床 ID 或链接域名）同时进行的请求上限
+const PER_HOST_LIMIT: usize = 6;
+
+struct LimiterInner {
+    global: Arc<Semaphore>,
+    per_host_limit: usize,
+    hosts: Mutex<HashMap<String, Arc<Semaphore>>>,
+}
+
+/// 共享并发闸门（注册为 Tauri managed state，克隆后可带入 spawn 的任务）
+#[derive(Clone)]
+pub struct NetworkLimiter(Arc<LimiterInner>);
+
+/// 请求许可，drop 时同时归还全局与主机名额
+pub struct NetworkPermit {
+    _host: OwnedSemaphorePermit,
+    _global: OwnedSemaphorePermit,
+}
+
+impl Default for NetworkLimiter {
+    fn default() -> Self {
+        Self::new(GLOBAL_LIMIT, PER_HOST_LIMIT)
+    }
+}
+
+impl NetworkLimiter {
+    pub fn new(global_limit: usize, per_host_limit: usize) -> Self {
+        Self(Arc::new(LimiterInner {
+            global: Arc::new(Semaphore::new(global_limit.max(1))),
+            per_host_limit: per_host_limit.max(1),
+            hosts: Mutex::new(HashMap::new()),
+        }))
+    }
+
+    fn host_semaphore(&self, host: &str) -> Arc<Semaphore> {
+        let mut hosts = self.0.hosts.lock().unwrap_or_else(|e| e.into_inner());
+        hosts
+            .entry(host.to_string())
+            .or_insert_with(|| Arc::new(Semaphore::new(self.0.per_host_limit)))
+            .clone()
+    }
+
+    /// 取得访问 `host` 的许可，名额不足时等待
+    ///
+    /// 先取主机名额再取全局名额：排队等同一主机的任务不会占着全局名额不放
+    pub async fn acquire(&self, host: &str) -> NetworkPermit {
+        // 信号量从不 close，acquire_owned 不会失败
+        let host_permit = self
+            .host_semaphore(host)
+            .acquire_owned()
+            .await
+            .expect("主机信号量不会被关闭");
+        let global_permit = Arc::clone(&self.0.global)
+            .acquire_owned()
+            .await
+            .expect("全局信号量不会被关闭");
+        NetworkPermit {
+            _host: host_permit,
+            _global: global_permit,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    #[tokio::test]
+    async fn limits_requests_per_host() {
+        let limiter = NetworkLimiter::new(10, 1);
+        let held = limiter.acquire("a.example").await;
+
+        let same_host =
+            tokio::time::timeout(Duration::from_millis(50), limiter.acquire("a.example")).await;
+        assert!(same_host.is_err());
+
+        // 其他主机不受影响
+        let other_host =
+            tokio::time::timeout(Duration::from_millis(50), limiter.acquire("b.example")).await;
+        assert!(other_host.is_ok());
+
+        drop(held);
+        let retry =
+            tokio::time::timeout(Duration::from_millis(500), limiter.acquire("a.example")).await;
+        assert!(retry.is_ok());
+    }
+
+    #[tokio::test]
+    async fn limits_requests_globally() {
+        let limiter = NetworkLimiter::new(1, 5);
+        let _held = limiter.acquire("a.example").await;
+
+        let blocked =
+            tokio::time::timeout(Duration::from_millis(50), limiter.acquire("b.example")).await;
+        assert!(blocked.is_err());
+    }
+}
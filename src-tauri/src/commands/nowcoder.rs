@@ -7,7 +7,11 @@ use serde::{Deserialize, Serialize};
 use std::time::{SystemTime, UNIX_EPOCH};
 use tauri::Window;
 
-use super::utils::read_file_bytes;
+use super::host_capabilities;
+use super::net_limiter::NetworkLimiter;
+use super::request_headers::{resolve_request_headers, RequestHeaderTemplate, RequestHeadersExt};
+use super::task_registry::TaskRegistry;
+use super::utils::{read_file_bytes, remember_uploaded_source};
 use crate::error::{AppError, IntoAppError};
 use crate::log_utils::{safe_path, safe_url, summarize_text};
 
@@ -100,14 +104,34 @@ struct NowcoderApiResponse {
 #[tauri::command]
 pub async fn upload_to_nowcoder(
     _window: Window,
-    _id: String,
+    id: String,
     file_path: String,
     nowcoder_cookie: String,
+    request_headers: Option<RequestHeaderTemplate>,
+    limiter: tauri::State<'_, NetworkLimiter>,
+    tasks: tauri::State<'_, TaskRegistry>,
 ) -> Result<NowcoderUploadResult, AppError> {
+    tasks
+        .run(
+            Some(&id),
+            upload_to_nowcoder_inner(file_path.clone(), nowcoder_cookie, request_headers, limiter),
+        )
+        .await
+        .inspect(|_| remember_uploaded_source(&file_path))
+}
+
+async fn upload_to_nowcoder_inner(
+    file_path: String,
+    nowcoder_cookie: String,
+    request_headers: Option<RequestHeaderTemplate>,
+    limiter: tauri::State<'_, NetworkLimiter>,
+) -> Result<NowcoderUploadResult, AppError> {
+    let _permit = limiter.acquire("nowcoder").await;
     log::info!("[Nowcoder] 开始上传文件: {}", safe_path(&file_path));
 
     // 1. 读取文件
-    let (buffer, file_size) = read_file_bytes(&file_path, 50 * 1024 * 1024).await?;
+    let (buffer, file_size) =
+        read_file_bytes(&file_path, host_capabilities::read_limit("nowcoder")).await?;
 
     // 2. 验证文件类型（只允许图片）
     let file_name = std::path::Path::new(&file_path)
@@ -121,9 +145,7 @@ pub async fn upload_to_nowcoder(
         .ok_or_else(|| AppError::validation("无法获取文件扩展名"))?
         .to_lowercase();
 
-    if !["jpg", "jpeg", "png", "gif"].contains(&ext.as_str()) {
-        return Err(AppError::validation("只支持 JPG、PNG、GIF 格式的图片"));
-    }
+    host_capabilities::check_admission("nowcoder", &ext, file_size)?;
 
     // 3. 构建带时间戳的 URL
     let timestamp = SystemTime::now()
@@ -159,6 +181,7 @@ pub async fn upload_to_nowcoder(
         .header("Referer", "https://www.nowcoder.com/creation/write/article")
         .header("Origin", "https://www.nowcoder.com")
         .header("User-Agent", "Mozilla/5.0 (Windows NT 10.0; Win64; x64) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/142.0.0.0 Safari/537.36")
+        .with_request_headers(&resolve_request_headers(request_headers.as_ref()))
         .multipart(form)
         .send()
         .await
@@ -0,0 +1,340 @@
+// src-tauri/src/commands/object_name.rs
+// 对象名规范化：按图床配置的规则改写上传到对象存储的文件名
+//
+// 部分图床会把中文、空格、emoji 文件名转义成一长串 %XX，甚至直接拒绝；
+// 开启规则后上传前依次执行：去掉 emoji -> 中文转拼音 -> 空白替换为 - -> 转小写 -> 截断长度。
+// 扩展名保留（仅受小写规则影响），规则全部关闭时文件名原样使用。
+//
+// 规则按图床保存在 {app_data_dir}/object-name-rules.json，格式：
+//   { "version": 1, "hosts": { "r2": { "stripSpaces": true, "pinyin": true, ... } } }
+// GUI 上传（BaseS3Uploader）与编辑器 Server / CLI 上传共用同一份规则，
+// 只对保留原文件名的对象存储类图床生效（R2 / COS / OSS / 七牛 / 又拍云 / 自定义 S3）。
+
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::LazyLock;
+
+use pinyin::ToPinyin;
+use serde::{Deserialize, Serialize};
+use tokio::sync::Mutex;
+
+use crate::atomic_file;
+use crate::error::AppError;
+
+const STORE_VERSION: u32 = 1;
+const STORE_FILE: &str = "object-name-rules.json";
+
+/// 长度上限的取值范围（字符数，含扩展名）
+const MIN_MAX_LENGTH: usize = 8;
+const MAX_MAX_LENGTH: usize = 255;
+
+/// 规范化后文件名为空（如全是 emoji）时使用的主干名
+const FALLBACK_STEM: &str = "image";
+
+/// 读改写规则文件时串行化，避免两次保存互相覆盖
+static STORE_LOCK: LazyLock<Mutex<()>> = LazyLock::new(|| Mutex::new(()));
+
+/// 单个图床的文件名规则，缺省字段均为关闭
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct ObjectNameRules {
+    /// 空白替换为 -（连续空白合并为一个）
+    pub strip_spaces: bool,
+    /// 去掉 emoji
+    pub strip_emoji: bool,
+    /// 汉字转为不带声调的拼音，音节之间用 - 分隔
+    pub pinyin: bool,
+    /// 转为小写（含扩展名）
+    pub lowercase: bool,
+    /// 文件名最大字符数（含扩展名），超出时截断主干
+    pub max_length: Option<usize>,
+}
+
+impl ObjectNameRules {
+    fn is_noop(&self) -> bool {
+        *self == Self::default()
+    }
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct RulesStore {
+    version: u32,
+    #[serde(default)]
+    hosts: HashMap<String, ObjectNameRules>,
+}
+
+/// emoji 及其修饰符（变体选择符、零宽连接符、肤色、旗帜、键帽）
+fn is_emoji(c: char) -> bool {
+    matches!(
+        c as u32,
+        0x1F000..=0x1FAFF
+            | 0x2600..=0x27BF
+            | 0x2B00..=0x2BFF
+            | 0xFE00..=0xFE0F
+            | 0x200D
+            | 0x20E3
+            | 0xE0020..=0xE007F
+    )
+}
+
+/// 按规则改写主干名（不含扩展名）
+fn sanitize_stem(stem: &str, rules: &ObjectNameRules) -> String {
+    let mut out = String::with_capacity(stem.len());
+    for c in stem.chars() {
+        if rules.strip_emoji && is_emoji(c) {
+            continue;
+        }
+        if rules.pinyin {
+            if let Some(syllable) = c.to_pinyin() {
+                out.push('-');
+                out.push_str(syllable.plain());
+                out.push('-');
+                continue;
+            }
+        }
+        if rules.strip_spaces && c.is_whitespace() {
+            out.push('-');
+            continue;
+        }
+        out.push(c);
+    }
+    if rules.lowercase {
+        out = out.to_lowercase();
+    }
+
+    // 合并规则引入的连续分隔符，并去掉首尾的 -
+    if rules.pinyin || rules.strip_spaces {
+        let mut collapsed = String::with_capacity(out.len());
+        for c in out.chars() {
+            if c == '-' && collapsed.ends_with('-') {
+                continue;
+            }
+            collapsed.push(c);
+        }
+        out = collapsed.trim_matches('-').to_string();
+    }
+    out
+}
+
+/// 按规则改写文件名，扩展名保留
+pub(crate) fn sanitize(file_name: &str, rules: &ObjectNameRules) -> String {
+    if rules.is_noop() {
+        return file_name.to_string();
+    }
+    let (stem, ext) = match file_name.rsplit_once('.') {
+        Some((stem, ext)) if !stem.is_empty() && !ext.is_empty() => (stem, Some(ext)),
+        _ => (file_name, None),
+    };
+    let ext = ext.map(|e| {
+        if rules.lowercase {
+            e.to_lowercase()
+        } else {
+            e.to_string()
+        }
+    });
+
+    let mut stem = sanitize_stem(stem, rules);
+    if stem.is_empty() {
+        stem = FALLBACK_STEM.to_string();
+    }
+    if let Some(max) = rules.max_length {
+        let ext_len = ext.as_ref().map_or(0, |e| e.chars().count() + 1);
+        let keep = max.saturating_sub(ext_len).max(1);
+        if stem.chars().count() > keep {
+            stem = stem.chars().take(keep).collect::<String>();
+            let trimmed = stem.trim_end_matches(['-', '.', ' ']);
+            if !trimmed.is_empty() {
+                stem = trimmed.to_string();
+            }
+        }
+    }
+    match ext {
+        Some(ext) => format!("{}.{}", stem, ext),
+        None => stem,
+    }
+}
+
+fn validate(rules: &ObjectNameRules) -> Result<(), AppError> {
+    if let Some(max) = rules.max_length {
+        if !(MIN_MAX_LENGTH..=MAX_MAX_LENGTH).contains(&max) {
+            return Err(AppError::validation(format!(
+                "文件名长度上限需在 {}-{} 之间",
+                MIN_MAX_LENGTH, MAX_MAX_LENGTH
+            )));
+        }
+    }
+    Ok(())
+}
+
+fn store_path(app: &tauri::AppHandle) -> Result<PathBuf, AppError> {
+    Ok(crate::portable::user_data_dir(app)?.join(STORE_FILE))
+}
+
+async fn load(path: &Path) -> Result<HashMap<String, ObjectNameRules>, AppError> {
+    let content = match tokio::fs::read_to_string(path).await {
+        Ok(content) => content,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(HashMap::new()),
+        Err(e) => return Err(AppError::file_io(format!("读取文件名规则失败: {}", e))),
+    };
+    let store: RulesStore = serde_json::from_str(&content)
+        .map_err(|e| AppError::config(format!("文件名规则文件格式无效: {}", e)))?;
+    if store.version > STORE_VERSION {
+        return Err(AppError::config(format!(
+            "不支持的文件名规则版本: {}（当前支持 {}）",
+            store.version, STORE_VERSION
+        )));
+    }
+    Ok(store.hosts)
+}
+
+async fn store(path: &Path, hosts: HashMap<String, ObjectNameRules>) -> Result<(), AppError> {
+    if let Some(dir) = path.parent() {
+        tokio::fs::create_dir_all(dir)
+            .await
+            .map_err(|e| AppError::file_io(format!("无法创建配置目录: {}", e)))?;
+    }
+    let content = serde_json::to_string_pretty(&RulesStore {
+        version: STORE_VERSION,
+        hosts,
+    })
+    .map_err(|e| AppError::config(format!("文件名规则序列化失败: {}", e)))?;
+    atomic_file::write_async(path, content)
+        .await
+        .map_err(|e| AppError::file_io(format!("写入文件名规则失败: {}", e)))
+}
+
+async fn apply_from(path: &Path, service_id: &str, file_name: &str) -> String {
+    match load(path).await {
+        Ok(mut hosts) => sanitize(file_name, &hosts.remove(service_id).unwrap_or_default()),
+        Err(e) => {
+            // 规则文件损坏时不阻断上传，按原文件名上传
+            log::warn!("[文件名规则] {}", e);
+            file_name.to_string()
+        }
+    }
+}
+
+/// 编辑器 Server / CLI 上传使用：按图床规则改写对象名（无 AppHandle，路径与 cli-config.json 同目录）
+pub(crate) async fn apply_for_host(service_id: &str, file_name: &str) -> String {
+    match crate::cli::get_app_data_dir() {
+        Some(dir) => apply_from(&dir.join(STORE_FILE), service_id, file_name).await,
+        None => file_name.to_string(),
+    }
+}
+
+/// 所有图床的文件名规则（未配置的图床不出现）
+#[tauri::command]
+pub async fn get_object_name_rules(
+    app: tauri::AppHandle,
+) -> Result<HashMap<String, ObjectNameRules>, AppError> {
+    load(&store_path(&app)?).await
+}
+
+/// 设置某个图床的文件名规则；传 null 或全部关闭即删除该图床的规则
+#[tauri::command]
+pub async fn set_object_name_rules(
+    app: tauri::AppHandle,
+    service_id: String,
+    rules: Option<ObjectNameRules>,
+) -> Result<(), AppError> {
+    let service_id = service_id.trim().to_string();
+    if service_id.is_empty() {
+        return Err(AppError::validation("图床 ID 不能为空"));
+    }
+    let rules = rules.filter(|r| !r.is_noop());
+    if let Some(rules) = &rules {
+        validate(rules)?;
+    }
+
+    let path = store_path(&app)?;
+    let _guard = STORE_LOCK.lock().await;
+    let mut hosts = load(&path).await?;
+    match rules {
+        Some(rules) => {
+            hosts.insert(service_id.clone(), rules);
+        }
+        None => {
+            if hosts.remove(&service_id).is_none() {
+                return Ok(());
+            }
+        }
+    }
+    store(&path, hosts).await?;
+    log::info!("[文件名规则] 已更新: {}", service_id);
+    Ok(())
+}
+
+/// 按图床规则改写文件名（GUI 上传器构造对象 Key 前调用）
+#[tauri::command]
+pub async fn sanitize_object_name(
+    app: tauri::AppHandle,
+    service_id: String,
+    file_name: String,
+) -> Result<String, AppError> {
+    Ok(apply_from(&store_path(&app)?, &service_id, &file_name).await)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rules() -> ObjectNameRules {
+        ObjectNameRules {
+            strip_spaces: true,
+            strip_emoji: true,
+            pinyin: true,
+            lowercase: true,
+            max_length: None,
+        }
+    }
+
+    #[test]
+    fn default_rules_keep_name_unchanged() {
+        let name = "屏幕截图 2026 🎉.PNG";
+        assert_eq!(sanitize(name, &ObjectNameRules::default()), name);
+    }
+
+    #[test]
+    fn applies_all_rules_and_keeps_extension() {
+        assert_eq!(
+            sanitize("屏幕截图 2026-10-17 🎉.PNG", &rules()),
+            "ping-mu-jie-tu-2026-10-17.png"
+        );
+        assert_eq!(sanitize("My  Photo.jpg", &rules()), "my-photo.jpg");
+        assert_eq!(sanitize("🎉🎉.webp", &rules()), "image.webp");
+        assert_eq!(sanitize(".gitkeep", &rules()), ".gitkeep");
+
+        let only_spaces = ObjectNameRules {
+            strip_spaces: true,
+            ..Default::default()
+        };
+        assert_eq!(sanitize("截图 A.PNG", &only_spaces), "截图-A.PNG");
+    }
+
+    #[test]
+    fn truncates_stem_to_max_length() {
+        let limited = ObjectNameRules {
+            max_length: Some(12),
+            ..rules()
+        };
+        let name = sanitize("a very long screenshot name.png", &limited);
+        assert_eq!(name, "a-very-l.png");
+        assert!(name.chars().count() <= 12);
+
+        assert!(validate(&ObjectNameRules {
+            max_length: Some(4),
+            ..Default::default()
+        })
+        .is_err());
+    }
+
+    #[test]
+    fn store_round_trips_rules_per_host() {
+        let store: RulesStore =
+            serde_json::from_str(r#"{"version":1,"hosts":{"r2":{"pinyin":true,"maxLength":64}}}"#)
+                .unwrap();
+        let r2 = &store.hosts["r2"];
+        assert!(r2.pinyin && !r2.lowercase);
+        assert_eq!(r2.max_length, Some(64));
+    }
+}
@@ -0,0 +1,339 @@
+// src-tauri/src/commands/palette.rs
+// 快速面板后端：在历史记录（最近 + 收藏）与快捷操作中模糊搜索并排序
+//
+// 快速面板由全局快捷键唤起，每次按键都会查询一次，必须在 10ms 内返回。
+// 历史候选只在数据库文件（含 WAL）修改后才重新读取，平时直接在内存中打分；
+// 打分为子序列匹配：按顺序命中查询中的每个字符即算匹配，
+// 开头命中、词首命中、连续命中加分，命中之间的间隔扣分。
+
+use std::path::{Path, PathBuf};
+use std::sync::LazyLock;
+use std::time::{Instant, SystemTime};
+
+use serde::Serialize;
+use sqlx::sqlite::SqliteConnectOptions;
+use sqlx::{ConnectOptions, Row};
+use tokio::sync::Mutex;
+
+use crate::error::AppError;
+use crate::quick_actions::QuickAction;
+
+/// 参与搜索的最近记录条数（收藏记录不受此限制）
+const RECENT_LIMIT: i64 = 1000;
+
+/// 默认 / 最多返回的结果条数
+const DEFAULT_RESULT_LIMIT: usize = 20;
+const MAX_RESULT_LIMIT: usize = 100;
+
+// 打分权重
+const SCORE_MATCH: i32 = 16;
+const SCORE_CONSECUTIVE: i32 = 15;
+const SCORE_WORD_START: i32 = 10;
+const SCORE_PREFIX: i32 = 20;
+const SCORE_SUBSTRING: i32 = 40;
+const MAX_GAP_PENALTY: i32 = 5;
+const MAX_LEADING_PENALTY: i32 = 10;
+const BONUS_STARRED: i32 = 15;
+const BONUS_ACTION: i32 = 20;
+
+/// 结果类型
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum PaletteKind {
+    History,
+    Action,
+}
+
+/// palette_query 返回的单条结果
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PaletteResult {
+    pub kind: PaletteKind,
+    /// 历史记录 ID，或快捷操作 ID（与 tray-action 事件 payload 一致）
+    pub id: String,
+    pub title: String,
+    /// 历史记录为生成的链接，快捷操作为 None
+    pub subtitle: Option<String>,
+    pub score: i32,
+    pub starred: bool,
+}
+
+/// 内存中的历史候选
+#[derive(Debug, Clone)]
+struct HistoryEntry {
+    id: String,
+    file_name: String,
+    file_name_lower: String,
+    link: String,
+    link_lower: String,
+    starred: bool,
+}
+
+/// 数据库文件与 WAL 的修改时间，任一变化即重新加载
+type DbStamp = (Option<SystemTime>, Option<SystemTime>);
+
+struct PaletteIndex {
+    db_path: PathBuf,
+    stamp: DbStamp,
+    entries: Vec<HistoryEntry>,
+}
+
+static INDEX: LazyLock<Mutex<Option<PaletteIndex>>> = LazyLock::new(|| Mutex::new(None));
+
+fn db_stamp(db_path: &Path) -> DbStamp {
+    let modified = |path: &Path| std::fs::metadata(path).and_then(|m| m.modified()).ok();
+    let mut wal = db_path.as_os_str().to_owned();
+    wal.push("-wal");
+    (modified(db_path), modified(Path::new(&wal)))
+}
+
+/// 读取最近记录与全部收藏，按时间倒序
+async fn load_entries(db_path: &Path) -> Result<Vec<HistoryEntry>, AppError> {
+    let mut conn = SqliteConnectOptions::new()
+        .filename(db_path)
+        .read_only(true)
+        .connect()
+        .await
+        .map_err(|e| AppError::storage(format!("打开历史记录数据库失败: {}", e)))?;
+
+    let rows = sqlx::query(
+        "SELECT id, local_file_name, local_file_name_lower, generated_link, is_favorited \
+         FROM history_items \
+         WHERE is_favorited = 1 \
+            OR id IN (SELECT id FROM history_items ORDER BY timestamp DESC LIMIT ?1) \
+         ORDER BY timestamp DESC",
+    )
+    .bind(RECENT_LIMIT)
+    .fetch_all(&mut conn)
+    .await
+    .map_err(|e| AppError::storage(format!("查询历史记录失败: {}", e)))?;
+
+    Ok(rows
+        .iter()
+        .map(|row| {
+            let link: String = row.try_get("generated_link").unwrap_or_default();
+            HistoryEntry {
+                id: row.try_get("id").unwrap_or_default(),
+                file_name: row.try_get("local_file_name").unwrap_or_default(),
+                file_name_lower: row.try_get("local_file_name_lower").unwrap_or_default(),
+                link_lower: link.to_lowercase(),
+                link,
+                starred: row.try_get::<i64, _>("is_favorited").unwrap_or(0) != 0,
+            }
+        })
+        .collect())
+}
+
+/// 子序列模糊匹配打分；`query` 与 `text` 均需已转小写，未命中返回 None
+fn fuzzy_score(query: &str, text: &str) -> Option<i32> {
+    if query.is_empty() {
+        return Some(0);
+    }
+    let needle: Vec<char> = query.chars().collect();
+    let mut matched = 0;
+    let mut score = 0;
+    let mut first: Option<usize> = None;
+    let mut prev: Option<usize> = None;
+    let mut prev_char: Option<char> = None;
+
+    for (i, c) in text.chars().enumerate() {
+        if matched == needle.len() {
+            break;
+        }
+        if c == needle[matched] {
+            score += SCORE_MATCH;
+            match prev {
+                Some(p) if p + 1 == i => score += SCORE_CONSECUTIVE,
+                Some(p) => score -= ((i - p - 1) as i32).min(MAX_GAP_PENALTY),
+                None => first = Some(i),
+            }
+            if prev_char.is_none_or(|pc| !pc.is_alphanumeric()) {
+                score += SCORE_WORD_START;
+            }
+            prev = Some(i);
+            matched += 1;
+        }
+        prev_char = Some(c);
+    }
+    if matched < needle.len() {
+        return None;
+    }
+
+    let first = first.unwrap_or(0);
+    if first == 0 {
+        score += SCORE_PREFIX;
+    } else {
+        score -= (first as i32).min(MAX_LEADING_PENALTY);
+    }
+    if text.contains(query) {
+        score += SCORE_SUBSTRING;
+    }
+    Some(score)
+}
+
+/// 快捷操作的搜索文本：中文名称 + 英文关键词
+fn action_keywords(action: QuickAction) -> &'static str {
+    match action {
+        QuickAction::UploadClipboard => "upload clipboard paste",
+        QuickAction::OpenHistory => "open history gallery",
+        QuickAction::PauseQueue => "pause queue stop",
+    }
+}
+
+fn rank(entries: &[HistoryEntry], query: &str, limit: usize) -> Vec<PaletteResult> {
+    let query = query.trim().to_lowercase();
+    let mut results = Vec::new();
+
+    for action in QuickAction::ALL {
+        let score = fuzzy_score(&query, action.label())
+            .into_iter()
+            .chain(fuzzy_score(&query, action_keywords(action)))
+            .max();
+        if let Some(score) = score {
+            results.push(PaletteResult {
+                kind: PaletteKind::Action,
+                id: action.id().to_string(),
+                title: action.label().to_string(),
+                subtitle: None,
+                score: score + BONUS_ACTION,
+                starred: false,
+            });
+        }
+    }
+
+    for entry in entries {
+        // 文件名未命中时，链接中包含完整查询（如路径片段、域名）也算命中
+        let score = fuzzy_score(&query, &entry.file_name_lower).or_else(|| {
+            (query.chars().count() >= 3 && entry.link_lower.contains(&query)).then_some(SCORE_MATCH)
+        });
+        if let Some(score) = score {
+            results.push(PaletteResult {
+                kind: PaletteKind::History,
+                id: entry.id.clone(),
+                title: entry.file_name.clone(),
+                subtitle: Some(entry.link.clone()),
+                score: score + if entry.starred { BONUS_STARRED } else { 0 },
+                starred: entry.starred,
+            });
+        }
+    }
+
+    // 稳定排序：同分时保持快捷操作在前、历史记录按时间倒序
+    results.sort_by_key(|r| std::cmp::Reverse(r.score));
+    results.truncate(limit);
+    results
+}
+
+/// 快速面板查询：在最近 / 收藏的历史记录与快捷操作中模糊搜索，按相关度排序
+///
+/// 空查询返回全部快捷操作，其后是收藏与最近的记录
+#[tauri::command]
+pub async fn palette_query(
+    app: tauri::AppHandle,
+    query: String,
+    limit: Option<usize>,
+) -> Result<Vec<PaletteResult>, AppError> {
+    let started = Instant::now();
+    let limit = limit
+        .unwrap_or(DEFAULT_RESULT_LIMIT)
+        .clamp(1, MAX_RESULT_LIMIT);
+    let db_path = crate::portable::history_db_path(&app)?;
+
+    let mut index = INDEX.lock().await;
+    let stamp = db_stamp(&db_path);
+    let fresh = index
+        .as_ref()
+        .is_some_and(|idx| idx.db_path == db_path && idx.stamp == stamp);
+    if !fresh {
+        // 首次运行尚未创建数据库时只搜索快捷操作
+        let entries = if stamp.0.is_some() {
+            load_entries(&db_path).await?
+        } else {
+            Vec::new()
+        };
+        log::debug!("[快速面板] 已加载 {} 条历史候选", entries.len());
+        *index = Some(PaletteIndex {
+            db_path,
+            stamp,
+            entries,
+        });
+    }
+
+    let entries = index.as_ref().map_or(&[][..], |idx| &idx.entries);
+    let results = rank(entries, &query, limit);
+    log::debug!(
+        "[快速面板] 查询 {:?} 返回 {} 条，耗时 {:?}",
+        query,
+        results.len(),
+        started.elapsed()
+    );
+    Ok(results)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(id: &str, name: &str, starred: bool) -> HistoryEntry {
+        HistoryEntry {
+            id: id.to_string(),
+            file_name: name.to_string(),
+            file_name_lower: name.to_lowercase(),
+            link: format!("https://img.example.com/2026/{}", name),
+            link_lower: format!("https://img.example.com/2026/{}", name.to_lowercase()),
+            starred,
+        }
+    }
+
+    #[test]
+    fn fuzzy_score_prefers_prefix_word_start_and_contiguous_matches() {
+        let prefix = fuzzy_score("scr", "screenshot.png").unwrap();
+        let word_start = fuzzy_score("scr", "my-screenshot.png").unwrap();
+        let scattered = fuzzy_score("scr", "sunset_clear_rock.png").unwrap();
+        assert!(prefix > word_start);
+        assert!(word_start > scattered);
+        assert_eq!(fuzzy_score("xyz", "screenshot.png"), None);
+        assert!(fuzzy_score("截图", "微信截图_2026.png").is_some());
+    }
+
+    #[test]
+    fn rank_orders_by_score_and_boosts_starred() {
+        let entries = vec![
+            entry("1", "cat-photo.png", false),
+            entry("2", "concat.jpg", false),
+            entry("3", "cat-photo-2.png", true),
+        ];
+        let results = rank(&entries, "Cat", 10);
+        let ids: Vec<&str> = results
+            .iter()
+            .filter(|r| r.kind == PaletteKind::History)
+            .map(|r| r.id.as_str())
+            .collect();
+        assert_eq!(ids, ["3", "1", "2"]);
+        assert!(results[0].starred);
+        assert_eq!(
+            results[0].subtitle.as_deref(),
+            Some("https://img.example.com/2026/cat-photo-2.png")
+        );
+    }
+
+    #[test]
+    fn rank_includes_actions_and_respects_limit() {
+        let entries = vec![entry("1", "queue.png", false)];
+        let results = rank(&entries, "pause", 10);
+        assert_eq!(results[0].kind, PaletteKind::Action);
+        assert_eq!(results[0].id, "pause_queue");
+
+        let by_label = rank(&[], "剪贴板", 10);
+        assert_eq!(by_label[0].id, "upload_clipboard");
+
+        let empty = rank(&entries, "", 2);
+        assert_eq!(empty.len(), 2);
+        assert!(empty.iter().all(|r| r.kind == PaletteKind::Action));
+
+        // 链接中的完整片段也能命中
+        let by_link = rank(&entries, "img.example", 10);
+        assert_eq!(by_link.len(), 1);
+        assert_eq!(by_link[0].id, "1");
+    }
+}
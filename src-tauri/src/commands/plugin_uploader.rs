@@ -0,0 +1,279 @@
+// src-tauri/src/commands/plugin_uploader.rs
+// 插件图床：以子进程执行 PicGo 风格的上传脚本，保留 PicNexus 不会原生实现的小众图床
+//
+// 脚本约定：
+//   <command> <script> [args...] <file1> <file2> ...
+//   -> stdout 按输入顺序输出链接，支持以下任一格式：
+//      - 每行一个 URL
+//      - JSON 字符串数组：["https://...", ...]
+//      - PicGo output 数组：[{"imgUrl": "https://..."}, ...]（也接受 url 字段）
+//      - 包含上述数组的对象：{"output": [...]} / {"urls": [...]} / {"result": [...]}
+//   -> 退出码非 0 视为失败，stderr 作为错误信息
+
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+use std::process::Stdio;
+use std::time::Duration;
+
+use super::task_registry::TaskRegistry;
+use super::utils::remember_uploaded_source;
+use crate::error::AppError;
+use crate::log_utils::{safe_path, safe_url, summarize_text};
+
+#[cfg(windows)]
+const CREATE_NO_WINDOW: u32 = 0x08000000;
+
+/// 未指定解释器时使用 Node.js（PicGo 插件生态）
+const DEFAULT_PLUGIN_COMMAND: &str = "node";
+const DEFAULT_PLUGIN_TIMEOUT_SECS: u64 = 120;
+const MAX_PLUGIN_TIMEOUT_SECS: u64 = 600;
+
+/// 插件图床配置（由前端设置页传入）
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PluginUploaderConfig {
+    /// 解释器或可执行文件，留空为 node
+    #[serde(default)]
+    pub command: Option<String>,
+    /// 插件脚本路径
+    pub script: String,
+    /// 放在文件列表之前的额外参数
+    #[serde(default)]
+    pub args: Vec<String>,
+    #[serde(default)]
+    pub timeout_secs: Option<u64>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct PluginUploadResult {
+    pub url: String,
+}
+
+/// 从 JSON 值中提取链接数组
+fn urls_from_json(value: &serde_json::Value) -> Option<Vec<String>> {
+    match value {
+        serde_json::Value::Array(items) => items
+            .iter()
+            .map(|item| match item {
+                serde_json::Value::String(url) => Some(url.trim().to_string()),
+                serde_json::Value::Object(obj) => obj
+                    .get("imgUrl")
+                    .or_else(|| obj.get("url"))
+                    .and_then(|v| v.as_str())
+                    .map(|url| url.trim().to_string()),
+                _ => None,
+            })
+            .collect(),
+        serde_json::Value::Object(obj) => ["output", "urls", "result"]
+            .iter()
+            .find_map(|key| obj.get(*key))
+            .and_then(urls_from_json),
+        _ => None,
+    }
+}
+
+fn is_http_url(value: &str) -> bool {
+    value.starts_with("http://") || value.starts_with("https://")
+}
+
+/// 解析插件 stdout，链接数必须与输入文件数一致
+fn parse_plugin_output(stdout: &str, expected: usize) -> Result<Vec<String>, String> {
+    let trimmed = stdout.trim();
+    let urls = match serde_json::from_str::<serde_json::Value>(trimmed) {
+        Ok(value) if value.is_array() || value.is_object() => {
+            urls_from_json(&value).ok_or_else(|| "插件 JSON 输出中没有链接数组".to_string())?
+        }
+        // 纯文本模式：忽略插件打印的日志行（如 "[plugin] ..."），只保留 URL
+        _ => trimmed
+            .lines()
+            .map(str::trim)
+            .filter(|line| is_http_url(line))
+            .map(str::to_string)
+            .collect(),
+    };
+
+    if let Some(bad) = urls.iter().find(|url| !is_http_url(url)) {
+        return Err(format!("插件返回了无效链接: {}", summarize_text(bad)));
+    }
+    if urls.len() != expected {
+        return Err(format!(
+            "插件输出 {} 个链接，期望 {} 个",
+            urls.len(),
+            expected
+        ));
+    }
+    Ok(urls)
+}
+
+/// 执行插件脚本，按输入顺序返回链接
+pub async fn run_plugin_script(
+    plugin: &PluginUploaderConfig,
+    files: &[String],
+) -> Result<Vec<String>, AppError> {
+    if files.is_empty() {
+        return Err(AppError::validation("没有需要上传的文件"));
+    }
+    let script = Path::new(plugin.script.trim());
+    if !script.is_file() {
+        return Err(AppError::config(format!(
+            "插件脚本不存在: {}",
+            safe_path(&plugin.script)
+        )));
+    }
+    if let Some(missing) = files.iter().find(|file| !Path::new(file).is_file()) {
+        return Err(AppError::file_io(format!(
+            "文件不存在: {}",
+            safe_path(missing)
+        )));
+    }
+
+    let program = plugin
+        .command
+        .as_deref()
+        .map(str::trim)
+        .filter(|command| !command.is_empty())
+        .unwrap_or(DEFAULT_PLUGIN_COMMAND);
+    let timeout_secs = plugin
+        .timeout_secs
+        .unwrap_or(DEFAULT_PLUGIN_TIMEOUT_SECS)
+        .clamp(1, MAX_PLUGIN_TIMEOUT_SECS);
+
+    let mut command = tokio::process::Command::new(program);
+    command
+        .arg(script)
+        .args(&plugin.args)
+        .args(files)
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .kill_on_drop(true);
+    if let Some(dir) = script.parent().filter(|dir| !dir.as_os_str().is_empty()) {
+        command.current_dir(dir);
+    }
+
+    #[cfg(windows)]
+    command.creation_flags(CREATE_NO_WINDOW);
+
+    log::info!(
+        "[插件图床] 执行: {} {} ({} 个文件)",
+        program,
+        safe_path(&plugin.script),
+        files.len()
+    );
+    let child = command
+        .spawn()
+        .map_err(|e| AppError::external(format!("启动插件失败: {} ({})", program, e)))?;
+
+    // 超时后 future 被 drop，kill_on_drop 负责结束子进程
+    let output = tokio::time::timeout(Duration::from_secs(timeout_secs), child.wait_with_output())
+        .await
+        .map_err(|_| AppError::network(format!("插件执行超时（{} 秒）", timeout_secs)))?
+        .map_err(|e| AppError::external(format!("等待插件结束失败: {}", e)))?;
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    if !output.status.success() {
+        let reason = if stderr.trim().is_empty() {
+            stdout.trim()
+        } else {
+            stderr.trim()
+        };
+        return Err(AppError::external(format!(
+            "插件退出码 {}: {}",
+            output.status.code().unwrap_or(-1),
+            summarize_text(reason)
+        )));
+    }
+
+    parse_plugin_output(&stdout, files.len()).map_err(AppError::external)
+}
+
+/// 通过插件上传单个文件（与其他图床命令保持相同的 id / filePath 入参）
+#[tauri::command]
+pub async fn upload_via_plugin(
+    id: String,
+    file_path: String,
+    plugin: PluginUploaderConfig,
+    tasks: tauri::State<'_, TaskRegistry>,
+) -> Result<PluginUploadResult, AppError> {
+    tasks
+        .run(
+            Some(&id),
+            upload_via_plugin_inner(id.clone(), file_path.clone(), plugin),
+        )
+        .await
+        .inspect(|_| remember_uploaded_source(&file_path))
+}
+
+async fn upload_via_plugin_inner(
+    id: String,
+    file_path: String,
+    plugin: PluginUploaderConfig,
+) -> Result<PluginUploadResult, AppError> {
+    log::info!("[插件图床] 开始上传 (ID: {})", id);
+    let url = run_plugin_script(&plugin, std::slice::from_ref(&file_path))
+        .await?
+        .remove(0);
+    log::info!("[插件图床] 上传成功: {}", safe_url(&url));
+    Ok(PluginUploadResult { url })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_plugin_output_reads_plain_lines_and_skips_logs() {
+        let stdout =
+            "[plugin] uploading...\nhttps://a.example.com/1.png\n\nhttps://a.example.com/2.png\n";
+        assert_eq!(
+            parse_plugin_output(stdout, 2).unwrap(),
+            vec![
+                "https://a.example.com/1.png".to_string(),
+                "https://a.example.com/2.png".to_string()
+            ]
+        );
+    }
+
+    #[test]
+    fn parse_plugin_output_accepts_picgo_output_shapes() {
+        assert_eq!(
+            parse_plugin_output(r#"["https://a.example.com/1.png"]"#, 1).unwrap(),
+            vec!["https://a.example.com/1.png".to_string()]
+        );
+        assert_eq!(
+            parse_plugin_output(
+                r#"[{"fileName":"1.png","imgUrl":"https://a.example.com/1.png"}]"#,
+                1
+            )
+            .unwrap(),
+            vec!["https://a.example.com/1.png".to_string()]
+        );
+        assert_eq!(
+            parse_plugin_output(r#"{"output":[{"url":"https://a.example.com/1.png"}]}"#, 1)
+                .unwrap(),
+            vec!["https://a.example.com/1.png".to_string()]
+        );
+    }
+
+    #[test]
+    fn parse_plugin_output_rejects_count_mismatch_and_non_urls() {
+        assert!(parse_plugin_output("https://a.example.com/1.png", 2).is_err());
+        assert!(parse_plugin_output(r#"["ftp://a.example.com/1.png"]"#, 1).is_err());
+        assert!(parse_plugin_output(r#"{"ok":true}"#, 1).is_err());
+    }
+
+    #[tokio::test]
+    async fn run_plugin_script_rejects_missing_script() {
+        let plugin = PluginUploaderConfig {
+            command: None,
+            script: "/nonexistent/picnexus-plugin.js".to_string(),
+            args: Vec::new(),
+            timeout_secs: None,
+        };
+        let err = run_plugin_script(&plugin, &["a.png".to_string()])
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("插件脚本不存在"));
+    }
+}
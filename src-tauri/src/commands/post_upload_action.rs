@@ -0,0 +1,176 @@
+// src-tauri/src/commands/post_upload_action.rs
+// 上传完成后的默认操作：复制链接 / 显示通知 / 打开链接 / 不做任何操作
+//
+// 设置保存在 {app_data_dir}/post-upload-action.json（与 cli-config.json 同目录），格式：
+//   { "version": 1, "action": "copyLink" }
+// 主窗口上传队列与全局快捷键上传完成后由前端读取并执行，CLI 上传完成后在本进程内执行，
+// 几条上传路径共用同一份设置。从未设置过时为 null：前端沿用「上传后自动复制」开关，CLI 不做任何操作。
+
+use std::path::{Path, PathBuf};
+use std::sync::LazyLock;
+
+use serde::{Deserialize, Serialize};
+use tokio::sync::Mutex;
+
+use crate::atomic_file;
+use crate::error::AppError;
+use crate::log_utils::safe_url;
+
+const STORE_VERSION: u32 = 1;
+const STORE_FILE: &str = "post-upload-action.json";
+
+/// CLI 进程退出后 Linux 剪贴板内容随之消失，复制后保持剪贴板所有权的时长
+#[cfg(target_os = "linux")]
+const CLI_CLIPBOARD_HOLD_SECS: u64 = 2;
+
+/// 读改写设置文件时串行化
+static STORE_LOCK: LazyLock<Mutex<()>> = LazyLock::new(|| Mutex::new(()));
+
+/// 上传完成后的操作
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum PostUploadAction {
+    /// 复制链接（按默认复制格式）
+    CopyLink,
+    /// 显示系统通知
+    Notify,
+    /// 在浏览器中打开主力链接
+    OpenUrl,
+    /// 不做任何操作
+    #[serde(rename = "none")]
+    Nothing,
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct ActionStore {
+    version: u32,
+    #[serde(default)]
+    action: Option<PostUploadAction>,
+}
+
+fn store_path(app: &tauri::AppHandle) -> Result<PathBuf, AppError> {
+    Ok(crate::portable::user_data_dir(app)?.join(STORE_FILE))
+}
+
+async fn load(path: &Path) -> Result<Option<PostUploadAction>, AppError> {
+    let content = match tokio::fs::read_to_string(path).await {
+        Ok(content) => content,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(None),
+        Err(e) => return Err(AppError::file_io(format!("读取上传后操作设置失败: {}", e))),
+    };
+    let store: ActionStore = serde_json::from_str(&content)
+        .map_err(|e| AppError::config(format!("上传后操作设置格式无效: {}", e)))?;
+    if store.version > STORE_VERSION {
+        return Err(AppError::config(format!(
+            "不支持的上传后操作设置版本: {}（当前支持 {}）",
+            store.version, STORE_VERSION
+        )));
+    }
+    Ok(store.action)
+}
+
+async fn store(path: &Path, action: PostUploadAction) -> Result<(), AppError> {
+    if let Some(dir) = path.parent() {
+        tokio::fs::create_dir_all(dir)
+            .await
+            .map_err(|e| AppError::file_io(format!("无法创建配置目录: {}", e)))?;
+    }
+    let content = serde_json::to_string_pretty(&ActionStore {
+        version: STORE_VERSION,
+        action: Some(action),
+    })
+    .map_err(|e| AppError::config(format!("上传后操作设置序列化失败: {}", e)))?;
+    atomic_file::write_async(path, content)
+        .await
+        .map_err(|e| AppError::file_io(format!("写入上传后操作设置失败: {}", e)))
+}
+
+/// CLI 使用：读取设置（无 AppHandle，路径与 cli-config.json 同目录）；未设置或读取失败时不做任何操作
+async fn load_for_cli() -> PostUploadAction {
+    let Some(dir) = crate::cli::get_app_data_dir() else {
+        return PostUploadAction::Nothing;
+    };
+    match load(&dir.join(STORE_FILE)).await {
+        Ok(action) => action.unwrap_or(PostUploadAction::Nothing),
+        Err(e) => {
+            // 设置文件损坏时不影响上传结果
+            log::warn!("[上传后操作] {}", e);
+            PostUploadAction::Nothing
+        }
+    }
+}
+
+fn copy_text_for_cli(text: &str) -> Result<(), arboard::Error> {
+    let mut clipboard = arboard::Clipboard::new()?;
+    #[cfg(target_os = "linux")]
+    {
+        use arboard::SetExtLinux;
+        let deadline =
+            std::time::Instant::now() + std::time::Duration::from_secs(CLI_CLIPBOARD_HOLD_SECS);
+        clipboard.set().wait_until(deadline).text(text)
+    }
+    #[cfg(not(target_os = "linux"))]
+    clipboard.set_text(text)
+}
+
+/// CLI 上传完成后执行上传后操作，返回执行的操作
+///
+/// CLI 没有窗口，「显示通知」退化为不做任何操作（结果已输出到终端）
+pub(crate) async fn run_for_cli(urls: &[String]) -> Result<PostUploadAction, AppError> {
+    let Some(first) = urls.first() else {
+        return Ok(PostUploadAction::Nothing);
+    };
+    let action = load_for_cli().await;
+    match action {
+        PostUploadAction::CopyLink => copy_text_for_cli(&urls.join("\n"))
+            .map_err(|e| AppError::clipboard(format!("复制链接失败: {}", e)))?,
+        PostUploadAction::OpenUrl => {
+            log::info!("[上传后操作] 打开链接: {}", safe_url(first));
+            opener::open(first).map_err(|e| AppError::external(format!("无法打开链接: {}", e)))?
+        }
+        PostUploadAction::Notify | PostUploadAction::Nothing => {}
+    }
+    Ok(action)
+}
+
+/// 上传完成后的操作；从未设置过时返回 null
+#[tauri::command]
+pub async fn get_post_upload_action(
+    app: tauri::AppHandle,
+) -> Result<Option<PostUploadAction>, AppError> {
+    load(&store_path(&app)?).await
+}
+
+/// 设置上传完成后的操作
+#[tauri::command]
+pub async fn set_post_upload_action(
+    app: tauri::AppHandle,
+    action: PostUploadAction,
+) -> Result<(), AppError> {
+    let path = store_path(&app)?;
+    let _guard = STORE_LOCK.lock().await;
+    store(&path, action).await?;
+    log::info!("[上传后操作] 已更新: {:?}", action);
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn store_round_trips_action() {
+        let store: ActionStore =
+            serde_json::from_str(r#"{"version":1,"action":"openUrl"}"#).unwrap();
+        assert_eq!(store.action, Some(PostUploadAction::OpenUrl));
+
+        let store: ActionStore = serde_json::from_str(r#"{"version":1}"#).unwrap();
+        assert_eq!(store.action, None);
+
+        assert_eq!(
+            serde_json::to_string(&PostUploadAction::Nothing).unwrap(),
+            r#""none""#
+        );
+        assert!(serde_json::from_str::<PostUploadAction>(r#""launch""#).is_err());
+    }
+}
@@ -0,0 +1,627 @@
+// src-tauri/src/commands/presets.rs
+// 配置预设：把不含密钥的图床配置与链接模板打包成签名 JSON，分享给团队成员一键导入
+//
+// 文件格式：
+//   { "format": "picnexus-preset", "version": 1, "name", "description", "createdAt",
+//     "hosts": { "r2": { "bucketName": ..., "publicDomain": ... }, "custom_s3:team": { ... } },
+//     "linkTemplates": [{ "name", "template" }],
+//     "signature": "hmac-sha256:<hex>" | "sha256:<hex>" }
+//
+// 签名覆盖除 signature 外的全部字段（键排序后的紧凑 JSON）：
+// - 提供团队口令时为 HMAC-SHA256，导入方用同一口令校验，可确认预设出自团队且未被改动
+// - 未提供口令时只有 SHA-256 摘要，仅能发现传输损坏
+//
+// 导出与导入都会剔除密钥类字段（Secret / Token / Cookie / 密码等），预设永远不会带出或覆盖本机凭据。
+// 链接模板由这里直接合并；图床配置保存在前端加密存储中，导入后返回给前端合并（保留本机已填写的密钥）。
+//
+// 导入分两步：read_preset 只校验并返回内容，前端据此展示将被改动的图床字段（未用口令签名时需用户确认）；
+// import_preset 再次读取并核对签名与预览一致，才合并链接模板并返回图床配置，
+// 避免链接在预览与确认之间被换成指向其他主机的内容。
+
+use std::path::PathBuf;
+
+use hmac::{Hmac, Mac};
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+use sha2::{Digest, Sha256};
+
+use super::link_checker::prepare_download_target;
+use super::link_templates::{self, LinkTemplate};
+use crate::atomic_file;
+use crate::error::{AppError, IntoAppError};
+use crate::log_utils::safe_url;
+use crate::path_utils;
+use crate::uploaders::CUSTOM_S3_PREFIX;
+
+type HmacSha256 = Hmac<Sha256>;
+
+const PRESET_FORMAT: &str = "picnexus-preset";
+const PRESET_VERSION: u32 = 1;
+
+/// 预设文件大小上限（1MB）
+const MAX_PRESET_BYTES: usize = 1024 * 1024;
+
+/// 下载预设的超时
+const DOWNLOAD_TIMEOUT_SECS: u64 = 15;
+
+const HMAC_PREFIX: &str = "hmac-sha256:";
+const DIGEST_PREFIX: &str = "sha256:";
+
+/// 字段名（小写、去掉 _ 与 -）包含这些片段即视为密钥
+const SECRET_MARKERS: &[&str] = &[
+    "secret",
+    "password",
+    "passwd",
+    "token",
+    "cookie",
+    "accesskey",
+    "apikey",
+    "clientid",
+    "authorization",
+    "credential",
+    "privatekey",
+];
+
+/// 可出现在预设中的内置图床
+const PRESET_HOSTS: &[&str] = &[
+    "weibo", "r2", "jd", "nowcoder", "qiyu", "zhihu", "nami", "bilibili", "chaoxing", "smms",
+    "github", "imgur", "tencent", "aliyun", "qiniu", "upyun",
+];
+
+/// 预设中的链接模板（不含本机 ID）
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PresetTemplate {
+    pub name: String,
+    pub template: String,
+}
+
+/// 参与签名的预设内容
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct PresetBody {
+    format: String,
+    version: u32,
+    name: String,
+    #[serde(default)]
+    description: String,
+    #[serde(default)]
+    created_at: i64,
+    #[serde(default)]
+    hosts: Map<String, Value>,
+    #[serde(default)]
+    link_templates: Vec<PresetTemplate>,
+}
+
+/// export_preset 的输入（由前端设置页组装）
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PresetDraft {
+    pub name: String,
+    #[serde(default)]
+    pub description: String,
+    /// 图床 ID → 该图床的配置对象（密钥字段会被剔除）
+    #[serde(default)]
+    pub hosts: Map<String, Value>,
+    /// 要包含的链接模板 ID；None 表示全部
+    #[serde(default)]
+    pub template_ids: Option<Vec<String>>,
+}
+
+/// export_preset 的返回值
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ExportedPreset {
+    pub path: String,
+    pub hosts: usize,
+    pub templates: usize,
+    /// 已剔除的密钥字段（如 `r2.secretAccessKey`）
+    pub stripped_fields: Vec<String>,
+    /// 是否使用团队口令签名
+    pub signed: bool,
+}
+
+/// read_preset 的返回值：校验通过的预设内容，不做任何修改
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PresetPreview {
+    pub name: String,
+    pub description: String,
+    pub hosts: Map<String, Value>,
+    pub link_templates: Vec<PresetTemplate>,
+    pub stripped_fields: Vec<String>,
+    pub signed: bool,
+    /// 预设签名；确认导入时原样传给 import_preset
+    pub signature: String,
+}
+
+/// import_preset 的返回值
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ImportedPreset {
+    pub name: String,
+    pub description: String,
+    /// 待前端合并的图床配置（已剔除密钥字段）
+    pub hosts: Map<String, Value>,
+    pub templates_added: usize,
+    pub templates_updated: usize,
+    pub stripped_fields: Vec<String>,
+    /// 已用团队口令校验签名；false 表示只校验了摘要
+    pub signed: bool,
+}
+
+fn is_secret_key(key: &str) -> bool {
+    let normalized: String = key
+        .chars()
+        .filter(|c| *c != '_' && *c != '-')
+        .flat_map(char::to_lowercase)
+        .collect();
+    SECRET_MARKERS.iter().any(|m| normalized.contains(m))
+}
+
+/// 递归剔除密钥字段，记录被剔除字段的路径
+fn strip_secrets(value: &mut Value, path: &str, stripped: &mut Vec<String>) {
+    match value {
+        Value::Object(map) => {
+            map.retain(|key, _| {
+                let secret = is_secret_key(key);
+                if secret {
+                    stripped.push(format!("{}.{}", path, key));
+                }
+                !secret
+            });
+            for (key, child) in map.iter_mut() {
+                strip_secrets(child, &format!("{}.{}", path, key), stripped);
+            }
+        }
+        Value::Array(items) => {
+            for (index, child) in items.iter_mut().enumerate() {
+                strip_secrets(child, &format!("{}[{}]", path, index), stripped);
+            }
+        }
+        _ => {}
+    }
+}
+
+/// 内置图床或自定义 S3 实例；插件图床会执行本地脚本，不允许通过预设分发
+fn is_preset_host(id: &str) -> bool {
+    PRESET_HOSTS.contains(&id)
+        || id
+            .strip_prefix(CUSTOM_S3_PREFIX)
+            .is_some_and(|profile| !profile.is_empty())
+}
+
+/// 校验图床列表并剔除密钥，返回被剔除的字段
+fn sanitize_hosts(hosts: &mut Map<String, Value>) -> Result<Vec<String>, AppError> {
+    let mut stripped = Vec::new();
+    for (id, config) in hosts.iter_mut() {
+        if !is_preset_host(id) {
+            return Err(AppError::validation(format!("预设不支持图床: {}", id)));
+        }
+        if !config.is_object() {
+            return Err(AppError::validation(format!("图床 {} 的配置格式无效", id)));
+        }
+        strip_secrets(config, id, &mut stripped);
+    }
+    Ok(stripped)
+}
+
+/// 键排序后的紧凑 JSON，签名与校验都基于它，不受字段顺序与缩进影响
+fn canonical_json(value: &Value, out: &mut String) {
+    match value {
+        Value::Object(map) => {
+            let mut keys: Vec<&String> = map.keys().collect();
+            keys.sort();
+            out.push('{');
+            for (i, key) in keys.into_iter().enumerate() {
+                if i > 0 {
+                    out.push(',');
+                }
+                out.push_str(&Value::String(key.clone()).to_string());
+                out.push(':');
+                canonical_json(&map[key], out);
+            }
+            out.push('}');
+        }
+        Value::Array(items) => {
+            out.push('[');
+            for (i, item) in items.iter().enumerate() {
+                if i > 0 {
+                    out.push(',');
+                }
+                canonical_json(item, out);
+            }
+            out.push(']');
+        }
+        other => out.push_str(&other.to_string()),
+    }
+}
+
+fn new_mac(passphrase: &str) -> Result<HmacSha256, AppError> {
+    HmacSha256::new_from_slice(passphrase.as_bytes()).into_external_err_with("HMAC 初始化失败")
+}
+
+/// 计算签名：有口令时为 HMAC-SHA256，否则为 SHA-256 摘要
+fn sign(body: &Value, passphrase: Option<&str>) -> Result<String, AppError> {
+    let mut canonical = String::new();
+    canonical_json(body, &mut canonical);
+    Ok(match passphrase {
+        Some(passphrase) => {
+            let mut mac = new_mac(passphrase)?;
+            mac.update(canonical.as_bytes());
+            format!(
+                "{}{}",
+                HMAC_PREFIX,
+                hex::encode(mac.finalize().into_bytes())
+            )
+        }
+        None => format!(
+            "{}{}",
+            DIGEST_PREFIX,
+            hex::encode(Sha256::digest(canonical.as_bytes()))
+        ),
+    })
+}
+
+/// 校验签名，返回是否为口令签名
+fn verify(body: &Value, signature: &str, passphrase: Option<&str>) -> Result<bool, AppError> {
+    let mut canonical = String::new();
+    canonical_json(body, &mut canonical);
+    if let Some(hex_mac) = signature.strip_prefix(HMAC_PREFIX) {
+        let passphrase =
+            passphrase.ok_or_else(|| AppError::validation("该预设使用团队口令签名，请输入口令"))?;
+        let expected =
+            hex::decode(hex_mac).map_err(|_| AppError::validation("预设签名格式无效"))?;
+        let mut mac = new_mac(passphrase)?;
+        mac.update(canonical.as_bytes());
+        mac.verify_slice(&expected)
+            .map_err(|_| AppError::validation("预设签名校验失败：口令错误或内容已被修改"))?;
+        return Ok(true);
+    }
+    if let Some(hex_digest) = signature.strip_prefix(DIGEST_PREFIX) {
+        if hex::encode(Sha256::digest(canonical.as_bytes())) != hex_digest.to_ascii_lowercase() {
+            return Err(AppError::validation("预设内容与摘要不一致，文件可能已损坏"));
+        }
+        return Ok(false);
+    }
+    Err(AppError::validation("预设缺少有效签名"))
+}
+
+fn normalize_passphrase(passphrase: Option<String>) -> Option<String> {
+    passphrase.filter(|p| !p.trim().is_empty())
+}
+
+/// 解析并校验预设文件，返回预设内容、已剔除的字段、是否为口令签名与签名
+fn parse_preset(
+    content: &[u8],
+    passphrase: Option<&str>,
+) -> Result<(PresetBody, Vec<String>, bool, String), AppError> {
+    let mut value: Value = serde_json::from_slice(content)
+        .map_err(|e| AppError::validation(format!("预设文件不是有效的 JSON: {}", e)))?;
+    let signature = value
+        .as_object_mut()
+        .and_then(|map| map.remove("signature"))
+        .and_then(|s| s.as_str().map(str::to_string))
+        .ok_or_else(|| AppError::validation("预设缺少有效签名"))?;
+    let signed = verify(&value, &signature, passphrase)?;
+
+    let mut body: PresetBody = serde_json::from_value(value)
+        .map_err(|e| AppError::validation(format!("预设格式无效: {}", e)))?;
+    if body.format != PRESET_FORMAT {
+        return Err(AppError::validation("不是 PicNexus 配置预设文件"));
+    }
+    if body.version > PRESET_VERSION {
+        return Err(AppError::validation(format!(
+            "不支持的预设版本: {}（当前支持 {}），请升级应用",
+            body.version, PRESET_VERSION
+        )));
+    }
+    let stripped = sanitize_hosts(&mut body.hosts)?;
+    Ok((body, stripped, signed, signature))
+}
+
+/// 读取预设：https:// 开头的按公网地址下载，其余视为本地路径
+async fn read_source(source: &str) -> Result<Vec<u8>, AppError> {
+    let source = source.trim();
+    if source.is_empty() {
+        return Err(AppError::validation("请指定预设文件路径或链接"));
+    }
+    if !source.starts_with("https://") && !source.starts_with("http://") {
+        let path = path_utils::normalize_input(source);
+        let metadata = tokio::fs::metadata(&path)
+            .await
+            .map_err(|e| AppError::file_io(format!("无法读取预设文件: {}", e)))?;
+        if metadata.len() > MAX_PRESET_BYTES as u64 {
+            return Err(AppError::validation("预设文件过大"));
+        }
+        return tokio::fs::read(&path)
+            .await
+            .map_err(|e| AppError::file_io(format!("无法读取预设文件: {}", e)));
+    }
+
+    let (url, client) = prepare_download_target(source).await?;
+    let mut response = client
+        .get(url)
+        .timeout(std::time::Duration::from_secs(DOWNLOAD_TIMEOUT_SECS))
+        .send()
+        .await
+        .into_network_err_with("下载预设失败")?;
+    if !response.status().is_success() {
+        return Err(AppError::network(format!(
+            "下载预设失败: HTTP {}",
+            response.status()
+        )));
+    }
+    let mut content = Vec::new();
+    while let Some(chunk) = response
+        .chunk()
+        .await
+        .into_network_err_with("下载预设失败")?
+    {
+        if content.len() + chunk.len() > MAX_PRESET_BYTES {
+            return Err(AppError::validation("预设文件过大"));
+        }
+        content.extend_from_slice(&chunk);
+    }
+    log::debug!("[配置预设] 已下载 {}", safe_url(source));
+    Ok(content)
+}
+
+/// 核对签名与预览时一致（未提供预览签名时跳过）
+fn ensure_previewed(signature: &str, expected: Option<&str>) -> Result<(), AppError> {
+    match expected {
+        Some(expected) if !expected.eq_ignore_ascii_case(signature) => Err(AppError::validation(
+            "预设内容在预览后发生了变化，请重新导入",
+        )),
+        _ => Ok(()),
+    }
+}
+
+/// 把不含密钥的图床配置与链接模板导出为签名预设文件
+///
+/// 提供 `passphrase` 时用 HMAC-SHA256 签名，导入方需输入同一口令
+#[tauri::command]
+pub async fn export_preset(
+    app: tauri::AppHandle,
+    path: String,
+    preset: PresetDraft,
+    passphrase: Option<String>,
+) -> Result<ExportedPreset, AppError> {
+    let name = preset.name.trim().to_string();
+    if name.is_empty() {
+        return Err(AppError::validation("预设名称不能为空"));
+    }
+    let mut target = PathBuf::from(path.trim());
+    if target.as_os_str().is_empty() {
+        return Err(AppError::validation("导出路径不能为空"));
+    }
+    if !target
+        .extension()
+        .is_some_and(|ext| ext.eq_ignore_ascii_case("json"))
+    {
+        target.as_mut_os_string().push(".json");
+    }
+
+    let mut hosts = preset.hosts;
+    let stripped_fields = sanitize_hosts(&mut hosts)?;
+    let link_templates: Vec<PresetTemplate> = link_templates::list_link_templates(app)
+        .await?
+        .into_iter()
+        .filter(|t| {
+            preset
+                .template_ids
+                .as_ref()
+                .is_none_or(|ids| ids.contains(&t.id))
+        })
+        .map(|t| PresetTemplate {
+            name: t.name,
+            template: t.template,
+        })
+        .collect();
+    if hosts.is_empty() && link_templates.is_empty() {
+        return Err(AppError::validation("预设中没有任何图床配置或链接模板"));
+    }
+
+    let body = PresetBody {
+        format: PRESET_FORMAT.to_string(),
+        version: PRESET_VERSION,
+        name,
+        description: preset.description.trim().to_string(),
+        created_at: chrono::Utc::now().timestamp_millis(),
+        hosts,
+        link_templates,
+    };
+    let (host_count, template_count) = (body.hosts.len(), body.link_templates.len());
+    let passphrase = normalize_passphrase(passphrase);
+    let mut value = serde_json::to_value(&body)
+        .map_err(|e| AppError::config(format!("预设序列化失败: {}", e)))?;
+    let signature = sign(&value, passphrase.as_deref())?;
+    if let Some(map) = value.as_object_mut() {
+        map.insert("signature".to_string(), Value::String(signature));
+    }
+    let content = serde_json::to_string_pretty(&value)
+        .map_err(|e| AppError::config(format!("预设序列化失败: {}", e)))?;
+    atomic_file::write_async(target.clone(), content)
+        .await
+        .map_err(|e| AppError::file_io(format!("写入预设文件失败: {}", e)))?;
+
+    log::info!(
+        "[配置预设] 已导出 {} 个图床、{} 个链接模板，剔除 {} 个密钥字段",
+        host_count,
+        template_count,
+        stripped_fields.len()
+    );
+    Ok(ExportedPreset {
+        path: target.to_string_lossy().to_string(),
+        hosts: host_count,
+        templates: template_count,
+        stripped_fields,
+        signed: passphrase.is_some(),
+    })
+}
+
+/// 读取并校验预设（本地路径或 https 链接），不合并任何内容，供导入前预览
+#[tauri::command]
+pub async fn read_preset(
+    source: String,
+    passphrase: Option<String>,
+) -> Result<PresetPreview, AppError> {
+    let content = read_source(&source).await?;
+    let passphrase = normalize_passphrase(passphrase);
+    let (body, stripped_fields, signed, signature) = parse_preset(&content, passphrase.as_deref())?;
+    Ok(PresetPreview {
+        name: body.name,
+        description: body.description,
+        hosts: body.hosts,
+        link_templates: body.link_templates,
+        stripped_fields,
+        signed,
+        signature,
+    })
+}
+
+/// 导入预设（本地路径或 https 链接）：校验签名，合并链接模板，返回待前端合并的图床配置
+///
+/// `expected_signature` 为 read_preset 返回的签名；内容在预览后发生变化时拒绝导入
+#[tauri::command]
+pub async fn import_preset(
+    app: tauri::AppHandle,
+    source: String,
+    passphrase: Option<String>,
+    expected_signature: Option<String>,
+) -> Result<ImportedPreset, AppError> {
+    let content = read_source(&source).await?;
+    let passphrase = normalize_passphrase(passphrase);
+    let (body, stripped_fields, signed, signature) = parse_preset(&content, passphrase.as_deref())?;
+    ensure_previewed(&signature, expected_signature.as_deref())?;
+
+    let templates: Vec<LinkTemplate> = body
+        .link_templates
+        .into_iter()
+        .map(|t| LinkTemplate {
+            id: String::new(),
+            name: t.name,
+            template: t.template,
+        })
+        .collect();
+    let (templates_added, templates_updated) =
+        link_templates::merge_templates(&app, &templates).await?;
+
+    log::info!(
+        "[配置预设] 已导入「{}」：{} 个图床，链接模板新增 {} / 更新 {}",
+        body.name,
+        body.hosts.len(),
+        templates_added,
+        templates_updated
+    );
+    Ok(ImportedPreset {
+        name: body.name,
+        description: body.description,
+        hosts: body.hosts,
+        templates_added,
+        templates_updated,
+        stripped_fields,
+        signed,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn preset_json(passphrase: Option<&str>) -> Vec<u8> {
+        let mut value = serde_json::json!({
+            "format": PRESET_FORMAT,
+            "version": 1,
+            "name": "团队图床",
+            "hosts": {"r2": {"bucketName": "team-assets", "publicDomain": "https://img.example.com"}},
+            "linkTemplates": [{"name": "Hugo", "template": "{{< figure src=\"{url}\" >}}"}]
+        });
+        let signature = sign(&value, passphrase).unwrap();
+        value["signature"] = Value::String(signature);
+        serde_json::to_vec_pretty(&value).unwrap()
+    }
+
+    #[test]
+    fn strips_secret_fields_recursively() {
+        let mut hosts = serde_json::json!({
+            "r2": {
+                "accountId": "acc",
+                "accessKeyId": "AKID",
+                "secretAccessKey": "s3cr3t",
+                "bucketName": "team-assets",
+                "headers": [{"Authorization": "Bearer x", "X-Env": "prod"}]
+            },
+            "upyun": {"operator": "team", "password": "p"},
+            "custom_s3:team": {"endpoint": "https://s3.example.com", "api_key": "k"}
+        });
+        let stripped = sanitize_hosts(hosts.as_object_mut().unwrap()).unwrap();
+
+        assert_eq!(hosts["r2"]["accountId"], "acc");
+        assert_eq!(hosts["r2"]["bucketName"], "team-assets");
+        assert!(hosts["r2"].get("secretAccessKey").is_none());
+        assert!(hosts["r2"].get("accessKeyId").is_none());
+        assert_eq!(
+            hosts["r2"]["headers"][0],
+            serde_json::json!({"X-Env": "prod"})
+        );
+        assert_eq!(hosts["upyun"], serde_json::json!({"operator": "team"}));
+        assert!(stripped.contains(&"r2.headers[0].Authorization".to_string()));
+        assert!(stripped.contains(&"custom_s3:team.api_key".to_string()));
+        assert_eq!(stripped.len(), 5);
+    }
+
+    #[test]
+    fn rejects_plugin_and_unknown_hosts() {
+        for id in ["plugin:deploy", "custom_s3:", "ftp"] {
+            let mut hosts = Map::new();
+            hosts.insert(id.to_string(), serde_json::json!({}));
+            assert!(sanitize_hosts(&mut hosts).is_err(), "{}", id);
+        }
+    }
+
+    #[test]
+    fn canonical_json_ignores_key_order_and_whitespace() {
+        let a: Value =
+            serde_json::from_str(r#"{"b": 1, "a": {"y": [1, "二"], "x": null}}"#).unwrap();
+        let b: Value = serde_json::from_str(r#"{"a":{"x":null,"y":[1,"二"]},"b":1}"#).unwrap();
+        let (mut ca, mut cb) = (String::new(), String::new());
+        canonical_json(&a, &mut ca);
+        canonical_json(&b, &mut cb);
+        assert_eq!(ca, r#"{"a":{"x":null,"y":[1,"二"]},"b":1}"#);
+        assert_eq!(ca, cb);
+    }
+
+    #[test]
+    fn verifies_digest_and_passphrase_signatures() {
+        let (body, stripped, signed, _) = parse_preset(&preset_json(None), None).unwrap();
+        assert!(!signed && stripped.is_empty());
+        assert_eq!(body.name, "团队图床");
+        assert_eq!(body.link_templates.len(), 1);
+
+        let signed_preset = preset_json(Some("team-pass"));
+        assert!(parse_preset(&signed_preset, Some("team-pass")).unwrap().2);
+        assert!(parse_preset(&signed_preset, Some("wrong")).is_err());
+        assert!(parse_preset(&signed_preset, None).is_err());
+
+        // 改动任一字段都会导致校验失败
+        let tampered = String::from_utf8(signed_preset)
+            .unwrap()
+            .replace("team-assets", "evil-bucket");
+        assert!(parse_preset(tampered.as_bytes(), Some("team-pass")).is_err());
+    }
+
+    #[test]
+    fn import_rejects_content_changed_after_preview() {
+        let (.., previewed) = parse_preset(&preset_json(None), None).unwrap();
+        let changed = String::from_utf8(preset_json(None))
+            .unwrap()
+            .replace("team-assets", "other-bucket");
+        let mut body: Value = serde_json::from_str(&changed).unwrap();
+        body.as_object_mut().unwrap().remove("signature");
+        let resigned = sign(&body, None).unwrap();
+
+        assert!(ensure_previewed(&previewed, Some(&previewed)).is_ok());
+        assert!(ensure_previewed(&previewed, None).is_ok());
+        assert!(ensure_previewed(&resigned, Some(&previewed)).is_err());
+    }
+}
@@ -0,0 +1,132 @@
+// src-tauri/src/commands/progress_throttle.rs
+// 进度事件节流：把高频进度更新合并为每个任务最多约 10 次/秒
+//
+// 流式上传按数据块、批量任务按条目上报进度，频率可达每秒数百次，全部经 IPC 桥发给前端会拖慢 WebView；
+// 节流器只放行间隔足够的更新，间隔内的更新只保留最新一次，由下一次放行或任务结束时补发。
+
+use std::time::{Duration, Instant};
+
+use serde::Serialize;
+use tauri::{Emitter, Window};
+
+/// 默认最小发送间隔（约 10 次/秒）
+pub const DEFAULT_INTERVAL: Duration = Duration::from_millis(100);
+
+/// 单个任务的发送节拍
+pub struct ProgressThrottle {
+    interval: Duration,
+    last_emit: Option<Instant>,
+}
+
+impl Default for ProgressThrottle {
+    fn default() -> Self {
+        Self::with_interval(DEFAULT_INTERVAL)
+    }
+}
+
+impl ProgressThrottle {
+    pub fn with_interval(interval: Duration) -> Self {
+        Self {
+            interval,
+            last_emit: None,
+        }
+    }
+
+    /// 距上次放行已超过间隔时返回 true 并记录本次发送
+    pub fn ready(&mut self) -> bool {
+        self.ready_at(Instant::now())
+    }
+
+    fn ready_at(&mut self, now: Instant) -> bool {
+        let due = self
+            .last_emit
+            .is_none_or(|last| now.saturating_duration_since(last) >= self.interval);
+        if due {
+            self.last_emit = Some(now);
+        }
+        due
+    }
+}
+
+/// 带合并的进度事件发送器，每个任务（上传 id / 批次）一个实例
+pub struct ProgressEmitter<P> {
+    window: Window,
+    event: &'static str,
+    throttle: ProgressThrottle,
+    pending: Option<P>,
+}
+
+impl<P: Serialize + Clone> ProgressEmitter<P> {
+    pub fn new(window: Window, event: &'static str) -> Self {
+        Self {
+            window,
+            event,
+            throttle: ProgressThrottle::default(),
+            pending: None,
+        }
+    }
+
+    /// 上报进度；距上次发送不足间隔时暂存，只保留最新一次
+    pub fn update(&mut self, payload: P) {
+        if self.throttle.ready() {
+            self.pending = None;
+            self.send(payload);
+        } else {
+            self.pending = Some(payload);
+        }
+    }
+
+    /// 立即发送（步骤切换、最终状态），同时丢弃暂存的旧进度
+    pub fn force(&mut self, payload: P) {
+        self.pending = None;
+        self.throttle.last_emit = Some(Instant::now());
+        self.send(payload);
+    }
+
+    /// 补发暂存的最新进度（任务结束时调用，避免最后一次更新被吞掉）
+    pub fn flush(&mut self) {
+        if let Some(payload) = self.pending.take() {
+            self.throttle.last_emit = Some(Instant::now());
+            self.send(payload);
+        }
+    }
+
+    fn send(&self, payload: P) {
+        let _ = self.window.emit(self.event, payload);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn first_update_is_always_ready() {
+        let mut throttle = ProgressThrottle::default();
+        assert!(throttle.ready_at(Instant::now()));
+    }
+
+    #[test]
+    fn coalesces_updates_within_interval() {
+        let mut throttle = ProgressThrottle::with_interval(Duration::from_millis(100));
+        let start = Instant::now();
+        assert!(throttle.ready_at(start));
+        assert!(!throttle.ready_at(start + Duration::from_millis(30)));
+        assert!(!throttle.ready_at(start + Duration::from_millis(99)));
+        assert!(throttle.ready_at(start + Duration::from_millis(100)));
+        // 节拍从最近一次放行重新计算
+        assert!(!throttle.ready_at(start + Duration::from_millis(150)));
+        assert!(throttle.ready_at(start + Duration::from_millis(200)));
+    }
+
+    #[test]
+    fn caps_rate_at_ten_per_second() {
+        let mut throttle = ProgressThrottle::default();
+        let start = Instant::now();
+        // 模拟 1 秒内每毫秒一次的数据块回调
+        let emitted = (0..1000)
+            .filter(|ms| throttle.ready_at(start + Duration::from_millis(*ms)))
+            .count();
+        assert_eq!(emitted, 10);
+    }
+}
@@ -9,8 +9,11 @@ use serde::Serialize;
 use std::time::{Duration, SystemTime, UNIX_EPOCH};
 use tauri::{Emitter, Manager, Window};
 
+use super::host_capabilities;
+use super::net_limiter::NetworkLimiter;
 use super::qiyu_token::fetch_qiyu_token_internal;
-use super::utils::read_file_bytes;
+use super::task_registry::TaskRegistry;
+use super::utils::{read_file_bytes, remember_uploaded_source};
 use crate::error::{AppError, IntoAppError};
 use crate::log_utils::{safe_path, safe_url, summarize_text};
 
@@ -28,7 +31,25 @@ pub async fn upload_to_qiyu(
     window: Window,
     id: String,
     file_path: String,
+    limiter: tauri::State<'_, NetworkLimiter>,
+    tasks: tauri::State<'_, TaskRegistry>,
 ) -> Result<QiyuUploadResult, AppError> {
+    tasks
+        .run(
+            Some(&id),
+            upload_to_qiyu_inner(window, id.clone(), file_path.clone(), limiter),
+        )
+        .await
+        .inspect(|_| remember_uploaded_source(&file_path))
+}
+
+async fn upload_to_qiyu_inner(
+    window: Window,
+    id: String,
+    file_path: String,
+    limiter: tauri::State<'_, NetworkLimiter>,
+) -> Result<QiyuUploadResult, AppError> {
+    let _permit = limiter.acquire("qiyu").await;
     log::info!("[Qiyu] 开始上传文件: {}", safe_path(&file_path));
 
     // 发送步骤1进度：获取上传凭证 (0%)
@@ -50,7 +71,8 @@ pub async fn upload_to_qiyu(
     let object_path = &token_info.object_path;
 
     // 3. 读取文件
-    let (buffer, file_size) = read_file_bytes(&file_path, 50 * 1024 * 1024).await?;
+    let (buffer, file_size) =
+        read_file_bytes(&file_path, host_capabilities::read_limit("qiyu")).await?;
 
     // 4. 验证文件类型（只允许图片）
     let file_name = std::path::Path::new(&file_path)
@@ -64,6 +86,8 @@ pub async fn upload_to_qiyu(
         .ok_or_else(|| AppError::validation("无法获取文件扩展名"))?
         .to_lowercase();
 
+    host_capabilities::check_admission("qiyu", &ext, file_size)?;
+
     // 获取 Content-Type
     let content_type = match ext.as_str() {
         "jpg" | "jpeg" => "image/jpeg",
@@ -0,0 +1,179 @@
+// src-tauri/src/commands/qr_code.rs
+// 二维码生成：把上传链接渲染为 PNG，方便手机扫码打开
+
+use base64::{engine::general_purpose::STANDARD, Engine as _};
+use image::{ImageFormat, Luma};
+use qrcode::{EcLevel, QrCode};
+use serde::{Deserialize, Serialize};
+use std::io::Cursor;
+use std::sync::atomic::{AtomicU64, Ordering};
+
+use super::cpu_pool;
+use crate::error::AppError;
+use crate::log_utils::safe_path;
+
+/// 临时文件前缀（与剪贴板临时文件区分，便于清理）
+const QR_TEMP_PREFIX: &str = "picnexus_qr_";
+
+/// 默认 / 最小 / 最大边长（像素）
+const DEFAULT_QR_SIZE: u32 = 320;
+const MIN_QR_SIZE: u32 = 128;
+const MAX_QR_SIZE: u32 = 1024;
+
+/// 同毫秒生成多个文件时用于消除撞名
+static QR_TEMP_COUNTER: AtomicU64 = AtomicU64::new(0);
+
+/// 二维码输出方式
+#[derive(Debug, Clone, Copy, Default, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub enum QrOutput {
+    /// `data:image/png;base64,...`，可直接作为 `<img src>` 使用
+    #[default]
+    DataUrl,
+    /// 写入系统临时目录，返回文件路径
+    File,
+}
+
+/// 二维码生成结果
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct QrCodeImage {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub data_url: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub path: Option<String>,
+    /// 实际输出边长（像素）
+    pub size: u32,
+}
+
+/// 将文本渲染为 PNG 字节，返回 (PNG 数据, 实际边长)
+fn render_qr_png(text: &str, size: u32) -> Result<(Vec<u8>, u32), AppError> {
+    let code = QrCode::with_error_correction_level(text.as_bytes(), EcLevel::M)
+        .map_err(|e| AppError::validation(format!("链接过长，无法生成二维码: {}", e)))?;
+
+    let image = code
+        .render::<Luma<u8>>()
+        .quiet_zone(true)
+        .min_dimensions(size, size)
+        .build();
+    let actual_size = image.width();
+
+    let mut buffer = Cursor::new(Vec::new());
+    image
+        .write_to(&mut buffer, ImageFormat::Png)
+        .map_err(|e| AppError::external(format!("二维码 PNG 编码失败: {}", e)))?;
+
+    Ok((buffer.into_inner(), actual_size))
+}
+
+/// 为链接生成二维码 PNG
+///
+/// `output` 缺省为 data URL；`size` 为最小边长，超出范围时夹到 128-1024。
+/// 渲染与编码在 CPU 线程池中进行，不占用主线程。
+#[tauri::command]
+pub async fn generate_qr(
+    url: String,
+    output: Option<QrOutput>,
+    size: Option<u32>,
+) -> Result<QrCodeImage, AppError> {
+    let url = url.trim().to_string();
+    if url.is_empty() {
+        return Err(AppError::validation("链接为空，无法生成二维码"));
+    }
+
+    let size = size
+        .unwrap_or(DEFAULT_QR_SIZE)
+        .clamp(MIN_QR_SIZE, MAX_QR_SIZE);
+    let (png_bytes, actual_size) = cpu_pool::spawn(move || render_qr_png(&url, size)).await??;
+
+    match output.unwrap_or_default() {
+        QrOutput::DataUrl => Ok(QrCodeImage {
+            data_url: Some(format!(
+                "data:image/png;base64,{}",
+                STANDARD.encode(&png_bytes)
+            )),
+            path: None,
+            size: actual_size,
+        }),
+        QrOutput::File => {
+            let seq = QR_TEMP_COUNTER.fetch_add(1, Ordering::Relaxed);
+            let file_name = format!(
+                "{}{}_{}.png",
+                QR_TEMP_PREFIX,
+                chrono::Local::now().format("%Y%m%d_%H%M%S_%3f"),
+                seq
+            );
+            let temp_path = std::env::temp_dir().join(file_name);
+            tokio::fs::write(&temp_path, png_bytes)
+                .await
+                .map_err(|e| AppError::file_io(format!("写入二维码文件失败: {}", e)))?;
+
+            let path = temp_path.to_string_lossy().to_string();
+            log::info!("[二维码] 已保存到临时文件: {}", safe_path(&path));
+            Ok(QrCodeImage {
+                data_url: None,
+                path: Some(path),
+                size: actual_size,
+            })
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PNG_SIGNATURE: &[u8] = &[0x89, b'P', b'N', b'G', b'\r', b'\n', 0x1a, b'\n'];
+
+    #[test]
+    fn render_qr_png_produces_png_at_least_requested_size() {
+        let (bytes, size) = render_qr_png("https://img.example.com/a.png", 256).unwrap();
+        assert!(bytes.starts_with(PNG_SIGNATURE));
+        assert!(size >= 256);
+
+        let decoded = image::load_from_memory(&bytes).unwrap();
+        assert_eq!(decoded.width(), size);
+        assert_eq!(decoded.height(), size);
+    }
+
+    #[test]
+    fn render_qr_png_rejects_oversized_payload() {
+        let long = "a".repeat(5000);
+        assert!(render_qr_png(&long, DEFAULT_QR_SIZE).is_err());
+    }
+
+    #[tokio::test]
+    async fn generate_qr_defaults_to_data_url_and_clamps_size() {
+        let result = generate_qr("https://img.example.com/a.png".to_string(), None, Some(1))
+            .await
+            .unwrap();
+        assert!(result
+            .data_url
+            .as_deref()
+            .unwrap()
+            .starts_with("data:image/png;base64,"));
+        assert!(result.path.is_none());
+        assert!(result.size >= MIN_QR_SIZE);
+    }
+
+    #[tokio::test]
+    async fn generate_qr_writes_temp_file() {
+        let result = generate_qr(
+            "https://img.example.com/b.png".to_string(),
+            Some(QrOutput::File),
+            None,
+        )
+        .await
+        .unwrap();
+        let path = result.path.unwrap();
+        assert!(path.contains(QR_TEMP_PREFIX));
+        let bytes = std::fs::read(&path).unwrap();
+        assert!(bytes.starts_with(PNG_SIGNATURE));
+        let _ = std::fs::remove_file(path);
+    }
+
+    #[tokio::test]
+    async fn generate_qr_rejects_blank_url() {
+        assert!(generate_qr("  ".to_string(), None, None).await.is_err());
+    }
+}
@@ -5,14 +5,18 @@
 use aws_sdk_s3::config::{Credentials, Region};
 use aws_sdk_s3::{primitives::ByteStream, Client, Config};
 use serde::{Deserialize, Serialize};
-use std::path::Path;
-use tauri::{Emitter, Window};
+use tauri::{Emitter, Manager, Window};
 use tokio::fs::File;
 use tokio::io::AsyncReadExt;
 use tokio::time::{timeout, Duration};
 
+use super::clock_skew::{self, SkewAdjustedTime};
+use super::net_limiter::NetworkLimiter;
+use super::task_registry::TaskRegistry;
+use super::utils::remember_uploaded_source;
 use crate::error::{AppError, IntoAppError};
 use crate::log_utils::safe_path;
+use crate::path_utils;
 
 #[derive(Serialize, Deserialize)]
 pub struct R2UploadResult {
@@ -49,11 +53,45 @@ pub async fn upload_to_r2(
     secret_access_key: String,
     bucket_name: String,
     key: String,
+    limiter: tauri::State<'_, NetworkLimiter>,
+    tasks: tauri::State<'_, TaskRegistry>,
 ) -> Result<R2UploadResult, AppError> {
+    tasks
+        .run(
+            Some(&id),
+            upload_to_r2_inner(
+                window,
+                id.clone(),
+                file_path.clone(),
+                account_id,
+                access_key_id,
+                secret_access_key,
+                bucket_name,
+                key,
+                limiter,
+            ),
+        )
+        .await
+        .inspect(|_| remember_uploaded_source(&file_path))
+}
+
+#[allow(clippy::too_many_arguments)]
+async fn upload_to_r2_inner(
+    window: Window,
+    id: String,
+    file_path: String,
+    account_id: String,
+    access_key_id: String,
+    secret_access_key: String,
+    bucket_name: String,
+    key: String,
+    limiter: tauri::State<'_, NetworkLimiter>,
+) -> Result<R2UploadResult, AppError> {
+    let _permit = limiter.acquire("r2").await;
     log::info!("[R2] 开始上传: {} -> {}", safe_path(&file_path), key);
 
     // 1. 检查文件是否存在
-    let path = Path::new(&file_path);
+    let path = path_utils::normalize_input(&file_path);
     if !path.exists() {
         return Err(AppError::file_io(format!("文件不存在: {}", file_path)));
     }
@@ -79,12 +117,13 @@ pub async fn upload_to_r2(
         .endpoint_url(&endpoint)
         .credentials_provider(credentials)
         .region(Region::new("auto"))
+        .time_source(SkewAdjustedTime)
         .build();
 
     let client = Client::from_conf(config);
 
     // 5. 检测 MIME 类型
-    let content_type = mime_guess::from_path(path)
+    let content_type = mime_guess::from_path(&path)
         .first_or_octet_stream()
         .to_string();
 
@@ -103,24 +142,26 @@ pub async fn upload_to_r2(
     // 发送 50% 进度（文件已读取）
     emit_progress(&window, &id, file_size / 2, file_size);
 
-    // 7. 创建 ByteStream
-    let body = ByteStream::from(buffer);
+    // 7. 请求体（时钟偏差重试时复用，Bytes 克隆不复制数据）
+    let body = bytes::Bytes::from(buffer);
 
     // 8. 上传到 R2（设置 2 分钟超时）
     log::debug!("[R2] 开始上传到存储桶: {}", bucket_name);
 
     let upload_timeout = Duration::from_secs(120);
 
-    let result = timeout(upload_timeout, async {
-        client
-            .put_object()
-            .bucket(&bucket_name)
-            .key(&key)
-            .body(body)
-            .content_type(&content_type)
-            .send()
-            .await
-    })
+    let result = timeout(
+        upload_timeout,
+        clock_skew::send_with_skew_retry(Some(window.app_handle()), || {
+            client
+                .put_object()
+                .bucket(&bucket_name)
+                .key(&key)
+                .body(ByteStream::from(body.clone()))
+                .content_type(&content_type)
+                .send()
+        }),
+    )
     .await
     .map_err(|_| AppError::storage("R2 上传超时: 网络连接不稳定或文件过大，请稍后重试"))?
     .map_err(|e| {
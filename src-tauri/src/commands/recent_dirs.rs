@@ -0,0 +1,188 @@
+// src-tauri/src/commands/recent_dirs.rs
+// 最近使用的目录：文件选择、批量导入时记下所在目录，供打开对话框和监视文件夹设置作默认位置
+//
+// 列表保存在 {app_data_dir}/recent-dirs.json，格式：
+//   { "version": 1, "dirs": ["/Users/me/Pictures", ...] }
+// 首次读取后常驻内存，最近使用的在前；文件损坏时当作空列表，不影响选择文件本身。
+
+use std::path::{Path, PathBuf};
+use std::sync::LazyLock;
+
+use serde::{Deserialize, Serialize};
+use tokio::sync::Mutex;
+
+use crate::atomic_file;
+use crate::error::AppError;
+
+const STORE_FILE: &str = "recent-dirs.json";
+const STORE_VERSION: u32 = 1;
+
+/// 最多记住的目录数
+const MAX_RECENT_DIRS: usize = 10;
+
+/// 已加载的列表；None 表示尚未从磁盘读取
+static RECENT_DIRS: LazyLock<Mutex<Option<Vec<PathBuf>>>> = LazyLock::new(|| Mutex::new(None));
+
+#[derive(Debug, Serialize, Deserialize)]
+struct RecentDirsStore {
+    version: u32,
+    #[serde(default)]
+    dirs: Vec<PathBuf>,
+}
+
+fn store_path(app: &tauri::AppHandle) -> Result<PathBuf, AppError> {
+    Ok(crate::portable::user_data_dir(app)?.join(STORE_FILE))
+}
+
+async fn load(path: &Path) -> Vec<PathBuf> {
+    let content = match tokio::fs::read_to_string(path).await {
+        Ok(content) => content,
+        Err(e) => {
+            if e.kind() != std::io::ErrorKind::NotFound {
+                log::warn!("[最近目录] 读取失败: {}", e);
+            }
+            return Vec::new();
+        }
+    };
+    match serde_json::from_str::<RecentDirsStore>(&content) {
+        Ok(store) if store.version <= STORE_VERSION => store.dirs,
+        Ok(store) => {
+            log::warn!("[最近目录] 不支持的版本: {}", store.version);
+            Vec::new()
+        }
+        Err(e) => {
+            log::warn!("[最近目录] 文件格式无效: {}", e);
+            Vec::new()
+        }
+    }
+}
+
+async fn store(path: &Path, dirs: &[PathBuf]) -> Result<(), AppError> {
+    if let Some(dir) = path.parent() {
+        tokio::fs::create_dir_all(dir)
+            .await
+            .map_err(|e| AppError::file_io(format!("无法创建配置目录: {}", e)))?;
+    }
+    let content = serde_json::to_string_pretty(&RecentDirsStore {
+        version: STORE_VERSION,
+        dirs: dirs.to_vec(),
+    })
+    .map_err(|e| AppError::config(format!("最近目录序列化失败: {}", e)))?;
+    atomic_file::write_async(path, content)
+        .await
+        .map_err(|e| AppError::file_io(format!("写入最近目录失败: {}", e)))
+}
+
+/// 文件取所在目录，目录取自身；不存在的路径返回 None
+fn dir_of(path: &Path) -> Option<PathBuf> {
+    let metadata = std::fs::metadata(path).ok()?;
+    if metadata.is_dir() {
+        Some(path.to_path_buf())
+    } else {
+        path.parent().map(Path::to_path_buf)
+    }
+}
+
+/// Windows / macOS 默认文件系统不区分大小写，按不区分大小写去重
+fn same_dir(a: &Path, b: &Path) -> bool {
+    if cfg!(any(windows, target_os = "macos")) {
+        a.to_string_lossy()
+            .eq_ignore_ascii_case(&b.to_string_lossy())
+    } else {
+        a == b
+    }
+}
+
+/// 把目录移到列表最前，超出上限时丢弃最旧的
+fn push_front(dirs: &mut Vec<PathBuf>, dir: PathBuf) {
+    dirs.retain(|d| !same_dir(d, &dir));
+    dirs.insert(0, dir);
+    dirs.truncate(MAX_RECENT_DIRS);
+}
+
+async fn list(app: &tauri::AppHandle) -> Result<Vec<PathBuf>, AppError> {
+    let path = store_path(app)?;
+    let mut cached = RECENT_DIRS.lock().await;
+    if cached.is_none() {
+        *cached = Some(load(&path).await);
+    }
+    Ok(cached.clone().unwrap_or_default())
+}
+
+/// 记下一次使用的路径（文件记所在目录）
+pub(crate) async fn remember(app: &tauri::AppHandle, path: &Path) -> Result<(), AppError> {
+    let Some(dir) = dir_of(path) else {
+        return Ok(());
+    };
+    let store_file = store_path(app)?;
+    let mut cached = RECENT_DIRS.lock().await;
+    if cached.is_none() {
+        *cached = Some(load(&store_file).await);
+    }
+    let dirs = cached.get_or_insert_with(Vec::new);
+    if dirs.first().is_some_and(|d| same_dir(d, &dir)) {
+        return Ok(());
+    }
+    push_front(dirs, dir);
+    store(&store_file, dirs).await
+}
+
+/// 仍然存在的最近一个目录，用作对话框的默认位置
+pub(crate) async fn most_recent(app: &tauri::AppHandle) -> Option<PathBuf> {
+    list(app).await.ok()?.into_iter().find(|d| d.is_dir())
+}
+
+/// 最近使用的目录（最近的在前，已删除的目录不返回）
+#[tauri::command]
+pub async fn get_recent_dirs(app: tauri::AppHandle) -> Result<Vec<String>, AppError> {
+    Ok(list(&app)
+        .await?
+        .into_iter()
+        .filter(|d| d.is_dir())
+        .map(|d| d.to_string_lossy().to_string())
+        .collect())
+}
+
+/// 记录一次文件选择或批量导入的路径；传文件时记录其所在目录
+#[tauri::command]
+pub async fn record_recent_dir(app: tauri::AppHandle, path: String) -> Result<(), AppError> {
+    let path = crate::path_utils::normalize_input(&path);
+    if !path.is_absolute() {
+        return Err(AppError::validation("只能记录绝对路径"));
+    }
+    remember(&app, &path).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn push_front_dedupes_and_caps() {
+        let mut dirs: Vec<PathBuf> = (0..MAX_RECENT_DIRS)
+            .map(|i| PathBuf::from(format!("/dir{}", i)))
+            .collect();
+        push_front(&mut dirs, PathBuf::from("/dir3"));
+        assert_eq!(dirs[0], PathBuf::from("/dir3"));
+        assert_eq!(dirs.len(), MAX_RECENT_DIRS);
+        assert_eq!(dirs.iter().filter(|d| d.ends_with("dir3")).count(), 1);
+
+        push_front(&mut dirs, PathBuf::from("/new"));
+        assert_eq!(dirs[0], PathBuf::from("/new"));
+        assert_eq!(dirs.len(), MAX_RECENT_DIRS);
+        assert!(!dirs.contains(&PathBuf::from("/dir9")));
+    }
+
+    #[test]
+    fn dir_of_uses_parent_for_files() {
+        let dir = std::env::temp_dir().join(format!("picnexus_recent_dirs_{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let file = dir.join("a.png");
+        std::fs::write(&file, b"x").unwrap();
+
+        assert_eq!(dir_of(&file), Some(dir.clone()));
+        assert_eq!(dir_of(&dir), Some(dir.clone()));
+        assert_eq!(dir_of(&dir.join("missing.png")), None);
+        let _ = std::fs::remove_dir_all(dir);
+    }
+}
@@ -0,0 +1,174 @@
+// src-tauri/src/commands/request_headers.rs
+// 按图床自定义请求头：覆盖 User-Agent 或追加任意请求头
+// 部分 Cookie 类图床会校验 UA 指纹，用户可在设置中为每个图床单独配置，上传命令构建请求时套用
+
+use reqwest::header::{HeaderMap, HeaderName, HeaderValue, USER_AGENT};
+use reqwest::RequestBuilder;
+use serde::Deserialize;
+
+/// 由上传器维护、不允许被覆盖的请求头
+/// （Cookie 来自图床配置，Content-* 由请求体决定，Host 由 URL 决定）
+const PROTECTED_HEADERS: &[&str] = &["cookie", "host", "content-length", "content-type"];
+
+/// 单条自定义请求头
+#[derive(Debug, Clone, Deserialize)]
+pub struct HeaderEntry {
+    pub name: String,
+    pub value: String,
+}
+
+/// 图床请求头模板（前端 `config.requestHeaderOverrides[serviceId]`）
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RequestHeaderTemplate {
+    /// 覆盖默认 User-Agent（空字符串视为未设置）
+    #[serde(default)]
+    pub user_agent: Option<String>,
+    /// 追加的请求头，同名时覆盖上传器的默认值
+    #[serde(default)]
+    pub headers: Vec<HeaderEntry>,
+}
+
+impl RequestHeaderTemplate {
+    /// 转换为 HeaderMap；非法或受保护的条目跳过并记录警告
+    pub fn to_header_map(&self) -> HeaderMap {
+        let mut map = HeaderMap::new();
+
+        for entry in &self.headers {
+            let name = entry.name.trim();
+            if name.is_empty() {
+                continue;
+            }
+            if PROTECTED_HEADERS.contains(&name.to_ascii_lowercase().as_str()) {
+                log::warn!("[RequestHeaders] 忽略受保护的请求头: {}", name);
+                continue;
+            }
+            match (
+                HeaderName::from_bytes(name.as_bytes()),
+                HeaderValue::from_str(entry.value.trim()),
+            ) {
+                (Ok(name), Ok(value)) => {
+                    map.insert(name, value);
+                }
+                _ => log::warn!("[RequestHeaders] 忽略非法请求头: {}", name),
+            }
+        }
+
+        // 单独的 UA 字段优先于 headers 中的 User-Agent
+        if let Some(ua) = self
+            .user_agent
+            .as_deref()
+            .map(str::trim)
+            .filter(|ua| !ua.is_empty())
+        {
+            match HeaderValue::from_str(ua) {
+                Ok(value) => {
+                    map.insert(USER_AGENT, value);
+                }
+                Err(_) => log::warn!("[RequestHeaders] 忽略非法 User-Agent"),
+            }
+        }
+
+        map
+    }
+}
+
+/// 在请求构建链上套用图床的自定义请求头
+///
+/// 放在上传器默认请求头之后调用：同名请求头会被替换而不是追加
+pub trait RequestHeadersExt {
+    fn with_request_headers(self, headers: &HeaderMap) -> Self;
+}
+
+impl RequestHeadersExt for RequestBuilder {
+    fn with_request_headers(self, headers: &HeaderMap) -> Self {
+        if headers.is_empty() {
+            self
+        } else {
+            self.headers(headers.clone())
+        }
+    }
+}
+
+/// 上传命令入口处把可选模板解析为 HeaderMap
+pub fn resolve_request_headers(template: Option<&RequestHeaderTemplate>) -> HeaderMap {
+    template
+        .map(RequestHeaderTemplate::to_header_map)
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn template(user_agent: Option<&str>, headers: &[(&str, &str)]) -> RequestHeaderTemplate {
+        RequestHeaderTemplate {
+            user_agent: user_agent.map(str::to_string),
+            headers: headers
+                .iter()
+                .map(|(name, value)| HeaderEntry {
+                    name: name.to_string(),
+                    value: value.to_string(),
+                })
+                .collect(),
+        }
+    }
+
+    #[test]
+    fn user_agent_field_wins_over_header_entry() {
+        let map = template(
+            Some("PicNexus-Test/1.0"),
+            &[
+                ("User-Agent", "Other"),
+                ("X-Requested-With", "XMLHttpRequest"),
+            ],
+        )
+        .to_header_map();
+
+        assert_eq!(map.get(USER_AGENT).unwrap(), "PicNexus-Test/1.0");
+        assert_eq!(map.get("x-requested-with").unwrap(), "XMLHttpRequest");
+    }
+
+    #[test]
+    fn skips_protected_invalid_and_blank_entries() {
+        let map = template(
+            Some("   "),
+            &[
+                ("Cookie", "SUB=evil"),
+                ("Content-Type", "text/plain"),
+                ("Bad Header", "x"),
+                ("X-Ok", "line\nbreak"),
+                ("", "ignored"),
+            ],
+        )
+        .to_header_map();
+
+        assert!(map.is_empty());
+    }
+
+    #[test]
+    fn replaces_default_headers_on_the_request() {
+        let client = reqwest::Client::new();
+        let headers = template(Some("Custom/2.0"), &[]).to_header_map();
+        let request = client
+            .get("https://example.com")
+            .header(USER_AGENT, "Default/1.0")
+            .with_request_headers(&headers)
+            .build()
+            .unwrap();
+
+        let values: Vec<_> = request.headers().get_all(USER_AGENT).iter().collect();
+        assert_eq!(values, vec!["Custom/2.0"]);
+    }
+
+    #[test]
+    fn deserializes_camel_case_payload() {
+        let parsed: RequestHeaderTemplate = serde_json::from_str(
+            r#"{"userAgent":"UA","headers":[{"name":"Referer","value":"https://weibo.com/"}]}"#,
+        )
+        .unwrap();
+        assert_eq!(parsed.user_agent.as_deref(), Some("UA"));
+        assert_eq!(parsed.headers.len(), 1);
+        assert!(resolve_request_headers(None).is_empty());
+    }
+}
@@ -0,0 +1,334 @@
+// src-tauri/src/commands/retry_failed.rs
+// 批量重传失败任务：按图床 / 时间筛选历史记录中上传失败的图床，一次性重新加入上传队列
+//
+// 典型场景：某个图床 Cookie 过期后连续失败了几十张，更新 Cookie 后不必逐条点重试。
+//
+// 工作流：
+//   retry_failed(filter, staggerMs?) -> 只读查询历史记录，取出各图床最新状态为 failed 的条目
+//   -> 跳过原文件已不存在的记录，按时间顺序为每条记录分配错开的开始时间
+//   -> 暂存为 PendingRetries，发送 retry-failed 事件
+//   -> 前端上传页取走后加入队列，到开始时间再逐个重传失败的图床（成功后更新原记录）
+//
+// 错开开始时间是为了避免几十个请求同时打到刚恢复的图床上再次触发限流。
+
+use std::collections::HashSet;
+use std::path::Path;
+use std::sync::Mutex;
+
+use serde::{Deserialize, Serialize};
+use sqlx::sqlite::SqliteConnectOptions;
+use sqlx::{ConnectOptions, Row};
+use tauri::{Emitter, Manager};
+
+use crate::error::AppError;
+
+/// 相邻两条记录开始重传的默认间隔
+const DEFAULT_STAGGER_MS: u64 = 2000;
+
+/// 间隔上限（1 分钟）
+const MAX_STAGGER_MS: u64 = 60_000;
+
+/// 单次最多重新入队的记录数，超出部分留给下一次
+const MAX_RETRY_ITEMS: usize = 500;
+
+/// 筛选条件，缺省的字段不限制
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RetryFailedFilter {
+    /// 只重传这些图床的失败（为空表示全部图床）
+    #[serde(default)]
+    pub services: Vec<String>,
+    /// 上传时间下限（Unix 毫秒，含）
+    #[serde(default)]
+    pub since: Option<i64>,
+    /// 上传时间上限（Unix 毫秒，含）
+    #[serde(default)]
+    pub until: Option<i64>,
+}
+
+/// 一条待重传的记录
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RetryTask {
+    pub history_id: String,
+    pub file_path: String,
+    pub file_name: String,
+    /// 需要重传的图床（按 results 中出现的顺序）
+    pub services: Vec<String>,
+    /// 计划开始时间（Unix 毫秒）
+    pub start_at: i64,
+}
+
+/// retry_failed 的返回值
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RetryFailedSummary {
+    /// 重新入队的记录数
+    pub queued: usize,
+    /// 需要重传的图床次数（一条记录可能有多个图床失败）
+    pub services: usize,
+    /// 原文件已不存在而跳过的记录数
+    pub skipped_missing: usize,
+    /// 超出单次上限、留待下次的记录数
+    pub deferred: usize,
+    /// 最后一条记录的计划开始时间（无任务时为 None）
+    pub last_start_at: Option<i64>,
+}
+
+/// 待前端加入上传队列的重传任务
+#[derive(Default)]
+pub struct PendingRetries(pub Mutex<Vec<RetryTask>>);
+
+/// 一条有失败图床的历史记录
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct FailedRecord {
+    history_id: String,
+    file_path: String,
+    file_name: String,
+    timestamp: i64,
+    services: Vec<String>,
+}
+
+/// 从 results JSON 中取出最新状态为失败、且满足图床筛选的图床
+///
+/// 同一图床可能有多条结果（失败后又重试成功），以最后一条为准
+fn failed_services(results: &serde_json::Value, wanted: &[String]) -> Vec<String> {
+    let Some(entries) = results.as_array() else {
+        return Vec::new();
+    };
+    let mut failed: Vec<String> = Vec::new();
+    for entry in entries {
+        let (Some(service_id), Some(status)) = (
+            entry.get("serviceId").and_then(|v| v.as_str()),
+            entry.get("status").and_then(|v| v.as_str()),
+        ) else {
+            continue;
+        };
+        failed.retain(|s| s != service_id);
+        if status == "failed" && (wanted.is_empty() || wanted.iter().any(|w| w == service_id)) {
+            failed.push(service_id.to_string());
+        }
+    }
+    failed
+}
+
+fn in_range(filter: &RetryFailedFilter, timestamp: i64) -> bool {
+    filter.since.is_none_or(|since| timestamp >= since)
+        && filter.until.is_none_or(|until| timestamp <= until)
+}
+
+/// 只读查询满足筛选条件的失败记录（按上传时间升序）
+async fn load_failed(
+    db_path: &Path,
+    filter: &RetryFailedFilter,
+) -> Result<Vec<FailedRecord>, AppError> {
+    let mut conn = SqliteConnectOptions::new()
+        .filename(db_path)
+        .read_only(true)
+        .connect()
+        .await
+        .map_err(|e| AppError::storage(format!("打开历史记录数据库失败: {}", e)))?;
+
+    let rows = sqlx::query(
+        "SELECT id, timestamp, local_file_name, file_path, results \
+         FROM history_items WHERE results LIKE '%\"failed\"%' ORDER BY timestamp ASC",
+    )
+    .fetch_all(&mut conn)
+    .await
+    .map_err(|e| AppError::storage(format!("查询历史记录失败: {}", e)))?;
+
+    let mut records = Vec::new();
+    for row in rows {
+        let timestamp: i64 = row.try_get("timestamp").unwrap_or_default();
+        if !in_range(filter, timestamp) {
+            continue;
+        }
+        let results: String = row.try_get("results").unwrap_or_default();
+        let Ok(results) = serde_json::from_str::<serde_json::Value>(&results) else {
+            continue;
+        };
+        let services = failed_services(&results, &filter.services);
+        if services.is_empty() {
+            continue;
+        }
+        let Ok(history_id) = row.try_get::<String, _>("id") else {
+            continue;
+        };
+        records.push(FailedRecord {
+            history_id,
+            file_path: row
+                .try_get::<Option<String>, _>("file_path")
+                .ok()
+                .flatten()
+                .unwrap_or_default(),
+            file_name: row.try_get("local_file_name").unwrap_or_default(),
+            timestamp,
+            services,
+        });
+    }
+    Ok(records)
+}
+
+/// 按记录顺序分配错开的开始时间：第 i 条为 now + i × stagger
+fn schedule(records: Vec<FailedRecord>, now_ms: i64, stagger_ms: u64) -> Vec<RetryTask> {
+    records
+        .into_iter()
+        .enumerate()
+        .map(|(index, record)| RetryTask {
+            history_id: record.history_id,
+            file_path: record.file_path,
+            file_name: record.file_name,
+            services: record.services,
+            start_at: now_ms + (index as i64) * stagger_ms as i64,
+        })
+        .collect()
+}
+
+/// 把历史记录中上传失败的图床重新加入上传队列
+///
+/// 同一记录已在待处理队列中时不会重复加入
+#[tauri::command]
+pub async fn retry_failed(
+    app: tauri::AppHandle,
+    filter: Option<RetryFailedFilter>,
+    stagger_ms: Option<u64>,
+) -> Result<RetryFailedSummary, AppError> {
+    let filter = filter.unwrap_or_default();
+    if let (Some(since), Some(until)) = (filter.since, filter.until) {
+        if since > until {
+            return Err(AppError::validation("开始时间不能晚于结束时间"));
+        }
+    }
+    let stagger_ms = stagger_ms.unwrap_or(DEFAULT_STAGGER_MS);
+    if stagger_ms > MAX_STAGGER_MS {
+        return Err(AppError::validation(format!(
+            "重传间隔不能超过 {} 秒",
+            MAX_STAGGER_MS / 1000
+        )));
+    }
+
+    let db_path = crate::portable::history_db_path(&app)?;
+    let records = if db_path.exists() {
+        load_failed(&db_path, &filter).await?
+    } else {
+        Vec::new()
+    };
+
+    let pending_ids: HashSet<String> = app
+        .state::<PendingRetries>()
+        .0
+        .lock()
+        .map(|pending| pending.iter().map(|t| t.history_id.clone()).collect())
+        .unwrap_or_default();
+    let (mut records, missing): (Vec<_>, Vec<_>) = records
+        .into_iter()
+        .filter(|r| !pending_ids.contains(&r.history_id))
+        .partition(|r| !r.file_path.is_empty() && Path::new(&r.file_path).is_file());
+    let deferred = records.len().saturating_sub(MAX_RETRY_ITEMS);
+    records.truncate(MAX_RETRY_ITEMS);
+
+    let tasks = schedule(records, chrono::Utc::now().timestamp_millis(), stagger_ms);
+    let summary = RetryFailedSummary {
+        queued: tasks.len(),
+        services: tasks.iter().map(|t| t.services.len()).sum(),
+        skipped_missing: missing.len(),
+        deferred,
+        last_start_at: tasks.last().map(|t| t.start_at),
+    };
+    log::info!(
+        "[批量重传] 入队 {} 条记录（{} 个图床），跳过原文件缺失 {} 条，延后 {} 条",
+        summary.queued,
+        summary.services,
+        summary.skipped_missing,
+        summary.deferred
+    );
+    if tasks.is_empty() {
+        return Ok(summary);
+    }
+
+    if let Ok(mut pending) = app.state::<PendingRetries>().0.lock() {
+        pending.extend(tasks);
+    }
+    if let Some(window) = app.get_webview_window("main") {
+        let _ = window.emit("navigate-to", "upload");
+        let _ = window.emit("retry-failed", ());
+    }
+    Ok(summary)
+}
+
+/// 取走全部待重传任务
+#[tauri::command]
+pub fn take_pending_retries(pending: tauri::State<'_, PendingRetries>) -> Vec<RetryTask> {
+    pending
+        .0
+        .lock()
+        .map(|mut tasks| std::mem::take(&mut *tasks))
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn record(id: &str, services: &[&str]) -> FailedRecord {
+        FailedRecord {
+            history_id: id.to_string(),
+            file_path: format!("/tmp/{}.png", id),
+            file_name: format!("{}.png", id),
+            timestamp: 0,
+            services: services.iter().map(|s| s.to_string()).collect(),
+        }
+    }
+
+    #[test]
+    fn picks_latest_failed_status_per_service() {
+        let results = serde_json::json!([
+            {"serviceId": "weibo", "status": "failed", "error": "cookie expired"},
+            {"serviceId": "r2", "result": {"url": "https://img.example.com/a.png"}, "status": "success"},
+            {"serviceId": "jd", "status": "failed", "error": "timeout"},
+            {"serviceId": "jd", "result": {"url": "https://img.example.com/b.png"}, "status": "success"},
+            {"serviceId": "zhihu", "status": "failed", "error": "403"}
+        ]);
+        assert_eq!(failed_services(&results, &[]), vec!["weibo", "zhihu"]);
+        assert_eq!(
+            failed_services(&results, &["weibo".to_string(), "r2".to_string()]),
+            vec!["weibo"]
+        );
+        assert!(failed_services(&serde_json::json!({}), &[]).is_empty());
+    }
+
+    #[test]
+    fn filters_by_inclusive_time_range() {
+        let filter = RetryFailedFilter {
+            since: Some(100),
+            until: Some(200),
+            ..Default::default()
+        };
+        assert!(in_range(&filter, 100));
+        assert!(in_range(&filter, 200));
+        assert!(!in_range(&filter, 99));
+        assert!(!in_range(&filter, 201));
+        assert!(in_range(&RetryFailedFilter::default(), i64::MIN));
+    }
+
+    #[test]
+    fn staggers_start_times_in_record_order() {
+        let tasks = schedule(
+            vec![
+                record("a", &["weibo"]),
+                record("b", &["weibo", "zhihu"]),
+                record("c", &["jd"]),
+            ],
+            1_000,
+            2_500,
+        );
+        let starts: Vec<i64> = tasks.iter().map(|t| t.start_at).collect();
+        assert_eq!(starts, vec![1_000, 3_500, 6_000]);
+        assert_eq!(tasks[1].history_id, "b");
+        assert_eq!(tasks[1].services, vec!["weibo", "zhihu"]);
+
+        let value = serde_json::to_value(&tasks[0]).unwrap();
+        assert_eq!(value["historyId"], "a");
+        assert_eq!(value["startAt"], 1_000);
+    }
+}
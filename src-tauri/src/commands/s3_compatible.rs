@@ -4,12 +4,16 @@
 
 use aws_sdk_s3::config::{Credentials, Region};
 use aws_sdk_s3::primitives::ByteStream;
+use aws_sdk_s3::types::ObjectCannedAcl;
 use aws_sdk_s3::{Client, Config};
 use serde::{Deserialize, Serialize};
-use tauri::{Emitter, Window};
+use tauri::{AppHandle, Emitter, Manager, Window};
 use tokio::time::{timeout, Duration};
 
-use super::utils::read_file_bytes;
+use super::clock_skew::{self, SkewAdjustedTime};
+use super::net_limiter::NetworkLimiter;
+use super::task_registry::TaskRegistry;
+use super::utils::{read_file_bytes, remember_uploaded_source};
 use crate::error::AppError;
 use crate::log_utils::safe_path;
 
@@ -34,6 +38,7 @@ fn create_s3_client(endpoint: &str, access_key: &str, secret_key: &str, region:
         .region(Region::new(region.to_string()))
         .credentials_provider(credentials)
         .force_path_style(true)
+        .time_source(SkewAdjustedTime)
         .build();
 
     Client::from_conf(config)
@@ -78,10 +83,63 @@ pub async fn upload_to_s3_compatible(
     bucket: String,
     key: String,
     public_domain: String,
+    acl: Option<String>,
+    limiter: tauri::State<'_, NetworkLimiter>,
+    tasks: tauri::State<'_, TaskRegistry>,
 ) -> Result<S3UploadResult, AppError> {
+    tasks
+        .run(
+            Some(&id),
+            upload_to_s3_compatible_inner(
+                window,
+                id.clone(),
+                file_path.clone(),
+                endpoint,
+                access_key,
+                secret_key,
+                region,
+                bucket,
+                key,
+                public_domain,
+                acl,
+                limiter,
+            ),
+        )
+        .await
+        .inspect(|_| remember_uploaded_source(&file_path))
+}
+
+#[allow(clippy::too_many_arguments)]
+async fn upload_to_s3_compatible_inner(
+    window: Window,
+    id: String,
+    file_path: String,
+    endpoint: String,
+    access_key: String,
+    secret_key: String,
+    region: String,
+    bucket: String,
+    key: String,
+    public_domain: String,
+    acl: Option<String>,
+    limiter: tauri::State<'_, NetworkLimiter>,
+) -> Result<S3UploadResult, AppError> {
+    let _permit = limiter.acquire("s3").await;
     log::info!("[S3兼容] 开始上传文件: {}", safe_path(&file_path));
     validate_https_endpoint(&endpoint)?;
     validate_https_public_domain(&public_domain)?;
+    let acl = acl
+        .as_deref()
+        .map(str::trim)
+        .filter(|acl| !acl.is_empty())
+        .map(|acl| {
+            if ObjectCannedAcl::values().contains(&acl) {
+                Ok(ObjectCannedAcl::from(acl))
+            } else {
+                Err(AppError::config(format!("不支持的 ACL: {}", acl)))
+            }
+        })
+        .transpose()?;
 
     // 发送进度: 0% - 读取文件
     let _ = window.emit(
@@ -130,17 +188,20 @@ pub async fn upload_to_s3_compatible(
         }),
     );
 
-    // 3. 上传文件（带超时保护）
-    let body = ByteStream::from(buffer);
+    // 3. 上传文件（带超时保护，时钟偏差导致签名失败时校正后重试一次）
+    let body = bytes::Bytes::from(buffer);
 
     timeout(
         Duration::from_secs(S3_OPERATION_TIMEOUT_SECS * 2), // 上传操作给予更长超时
-        client
-            .put_object()
-            .bucket(&bucket)
-            .key(&key)
-            .body(body)
-            .send(),
+        clock_skew::send_with_skew_retry(Some(window.app_handle()), || {
+            client
+                .put_object()
+                .bucket(&bucket)
+                .key(&key)
+                .set_acl(acl.clone())
+                .body(ByteStream::from(body.clone()))
+                .send()
+        }),
     )
     .await
     .map_err(|_| {
@@ -167,7 +228,7 @@ pub async fn upload_to_s3_compatible(
 
 /// S3 兼容存储测试配置
 /// 支持多种字段名映射，兼容不同云服务商的配置格式
-#[derive(Debug, Clone, Deserialize)]
+#[derive(Debug, Clone, Default, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct S3TestConfig {
     // R2 专用
@@ -218,6 +279,7 @@ impl S3TestConfig {
 /// 包含重试机制（最多 1 次）以快速反馈配置问题
 #[tauri::command]
 pub async fn test_s3_connection(
+    app: AppHandle,
     service_id: String,
     config: S3TestConfig,
 ) -> Result<String, AppError> {
@@ -257,14 +319,12 @@ pub async fn test_s3_connection(
     let test_timeout = Duration::from_secs(10);
     let client = create_s3_client(&endpoint, &access_key, &secret_key, &region);
 
-    let result = timeout(test_timeout, async {
-        client
-            .list_objects_v2()
-            .bucket(&bucket)
-            .max_keys(1)
-            .send()
-            .await
-    })
+    let result = timeout(
+        test_timeout,
+        clock_skew::send_with_skew_retry(Some(&app), || {
+            client.list_objects_v2().bucket(&bucket).max_keys(1).send()
+        }),
+    )
     .await;
 
     match result {
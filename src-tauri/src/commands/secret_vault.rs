@@ -0,0 +1,149 @@
+// src-tauri/src/commands/secret_vault.rs
+// 系统钥匙串密钥存储：登录 Cookie、图床 API Token 等按类别分开保存，账户名为图床 ID
+//
+// 登录 Cookie 工作流：
+//   登录窗口（内嵌 webview）登录成功 -> save_cookie_from_login 校验字段
+//   -> store_cookie 写入钥匙串 -> 通知主窗口写入配置
+//   -> 配置中的 Cookie 丢失（恢复默认设置、同步覆盖）时，设置页从钥匙串恢复
+// API Token（如 SM.MS）由对应图床模块通过 TOKEN_VAULT 读写。
+//
+// 便携模式下不访问系统钥匙串，密钥只保存在便携目录的加密配置中。
+
+use keyring::Entry;
+
+use crate::error::AppError;
+use crate::portable;
+
+/// 一类密钥：各类使用与配置加密密钥分开的钥匙串服务名，账户名为图床 ID
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct SecretVault {
+    service: &'static str,
+    /// 日志与错误提示中的名称
+    label: &'static str,
+}
+
+/// 登录 Cookie
+pub(crate) const COOKIE_VAULT: SecretVault = SecretVault {
+    service: "us.picnex.app.cookies",
+    label: "Cookie",
+};
+
+/// 图床 API Token
+pub(crate) const TOKEN_VAULT: SecretVault = SecretVault {
+    service: "us.picnex.app.tokens",
+    label: "Token",
+};
+
+/// 只允许字母、数字、下划线和连字符，避免拼出任意钥匙串条目
+fn is_valid_vault_key(service_id: &str) -> bool {
+    !service_id.is_empty()
+        && service_id.len() <= 32
+        && service_id
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-')
+}
+
+impl SecretVault {
+    fn entry(&self, service_id: &str) -> Result<Entry, AppError> {
+        if !is_valid_vault_key(service_id) {
+            return Err(AppError::validation(format!(
+                "无效的服务 ID: {}",
+                service_id
+            )));
+        }
+        Entry::new(self.service, service_id)
+            .map_err(|e| AppError::external(format!("无法访问系统钥匙串: {}", e)))
+    }
+
+    /// 保存密钥，空值视为清除；便携模式下跳过并返回 false
+    pub(crate) fn store(&self, service_id: &str, secret: &str) -> Result<bool, AppError> {
+        if portable::is_portable() {
+            return Ok(false);
+        }
+        let secret = secret.trim();
+        if secret.is_empty() {
+            self.delete(service_id)?;
+            return Ok(true);
+        }
+        self.entry(service_id)?.set_password(secret).map_err(|e| {
+            AppError::external(format!("无法保存 {} 到系统钥匙串: {}", self.label, e))
+        })?;
+        log::debug!("[钥匙串] ✓ 已保存 {} {}", service_id, self.label);
+        Ok(true)
+    }
+
+    /// 读取密钥；不存在或便携模式下返回 None
+    pub(crate) fn load(&self, service_id: &str) -> Result<Option<String>, AppError> {
+        if portable::is_portable() {
+            return Ok(None);
+        }
+        match self.entry(service_id)?.get_password() {
+            Ok(secret) if !secret.trim().is_empty() => Ok(Some(secret.trim().to_string())),
+            Ok(_) | Err(keyring::Error::NoEntry) => Ok(None),
+            Err(e) => Err(AppError::external(format!(
+                "无法从系统钥匙串读取 {}: {}",
+                self.label, e
+            ))),
+        }
+    }
+
+    /// 删除密钥；条目不存在视为成功
+    pub(crate) fn delete(&self, service_id: &str) -> Result<(), AppError> {
+        if portable::is_portable() {
+            return Ok(());
+        }
+        match self.entry(service_id)?.delete_password() {
+            Ok(()) | Err(keyring::Error::NoEntry) => Ok(()),
+            Err(e) => Err(AppError::external(format!(
+                "无法从系统钥匙串删除 {}: {}",
+                self.label, e
+            ))),
+        }
+    }
+}
+
+/// 保存登录 Cookie；便携模式下跳过并返回 false
+pub(crate) fn store_cookie(service_id: &str, cookie: &str) -> Result<bool, AppError> {
+    COOKIE_VAULT.store(service_id, cookie)
+}
+
+/// 读取登录 Cookie；不存在或便携模式下返回 None
+pub(crate) fn load_cookie(service_id: &str) -> Result<Option<String>, AppError> {
+    COOKIE_VAULT.load(service_id)
+}
+
+/// 读取钥匙串中保存的登录 Cookie
+#[tauri::command]
+pub fn get_keychain_cookie(service_id: String) -> Result<Option<String>, AppError> {
+    load_cookie(&service_id)
+}
+
+/// 删除钥匙串中保存的登录 Cookie（用户清空 Cookie 时调用）
+#[tauri::command]
+pub fn delete_keychain_cookie(service_id: String) -> Result<(), AppError> {
+    COOKIE_VAULT.delete(&service_id)?;
+    log::info!("[钥匙串] 已删除 {} Cookie", service_id);
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn vault_key_rejects_unsafe_service_ids() {
+        assert!(is_valid_vault_key("weibo"));
+        assert!(is_valid_vault_key("custom_s3-1"));
+        assert!(!is_valid_vault_key(""));
+        assert!(!is_valid_vault_key("weibo/../other"));
+        assert!(!is_valid_vault_key(&"a".repeat(33)));
+    }
+
+    #[test]
+    fn invalid_service_id_is_rejected_before_touching_keychain() {
+        let err = load_cookie("we ibo").unwrap_err();
+        assert!(err.to_string().contains("无效的服务 ID"));
+        let err = TOKEN_VAULT.store("sm/ms", "token").unwrap_err();
+        assert!(err.to_string().contains("无效的服务 ID"));
+    }
+}
@@ -0,0 +1,303 @@
+// src-tauri/src/commands/share_page.rs
+// 分享页：把选中的多条历史记录生成一个带说明文字的 HTML 页面，一个链接分享一组截图
+//
+// 工作流：
+//   create_share_page(recordIds, title?, service?, localOnly?) -> 只读查询记录的链接与说明
+//   -> 生成静态 HTML（无脚本，图片直接引用图床链接）
+//   -> 对象存储类图床（R2 / COS / OSS / 七牛 / 自定义 S3）：上传到 share/{id}.html
+//   -> localOnly：写入 {app_data_dir}/share-pages/{id}.html，由本地 Server 的 /share/{id} 提供
+//
+// 未指定图床时使用 CLI 配置中唯一可用的图床，它不能托管网页时报错，不会悄悄改用本地链接：
+// 本地链接只在本机 Server 运行期间可访问（http://127.0.0.1），发给别人打不开，
+// 只有调用方明确传 localOnly（局域网或配合内网穿透使用）时才生成。
+
+use std::collections::HashMap;
+use std::path::Path;
+
+use serde::Serialize;
+use sqlx::sqlite::SqliteConnectOptions;
+use sqlx::{ConnectOptions, Row};
+
+use super::upload_manifest::default_alt_text;
+use crate::atomic_file;
+use crate::cli;
+use crate::error::AppError;
+use crate::log_utils::safe_url;
+use crate::server::upload_handler::{accepts_html, upload_html_page};
+
+/// 本地分享页目录（位于应用数据目录下）
+pub const SHARE_PAGE_DIR: &str = "share-pages";
+
+/// 上传到图床时的对象路径前缀（接在图床配置的上传路径之后）
+const REMOTE_SHARE_PREFIX: &str = "share";
+
+/// 一个分享页最多包含的图片数
+const MAX_SHARE_IMAGES: usize = 100;
+
+/// 分享页托管位置
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ShareTarget {
+    /// 已上传到图床
+    Host,
+    /// 由本地 Server 提供
+    Local,
+}
+
+/// create_share_page 的返回值
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SharePage {
+    pub id: String,
+    pub url: String,
+    pub target: ShareTarget,
+    /// 上传使用的图床 ID（本地分享页为 None）
+    pub service: Option<String>,
+    /// 页面中的图片数（没有链接的记录被跳过）
+    pub images: usize,
+}
+
+/// 页面中的一张图片
+#[derive(Debug, Clone, PartialEq)]
+struct ShareImage {
+    url: String,
+    alt: String,
+    caption: String,
+}
+
+/// 只读查询记录，按传入顺序返回有链接的记录（重复 ID 只保留第一次出现）
+async fn load_images(db_path: &Path, ids: &[String]) -> Result<Vec<ShareImage>, AppError> {
+    let mut conn = SqliteConnectOptions::new()
+        .filename(db_path)
+        .read_only(true)
+        .connect()
+        .await
+        .map_err(|e| AppError::storage(format!("打开历史记录数据库失败: {}", e)))?;
+
+    let placeholders = vec!["?"; ids.len()].join(",");
+    let sql = format!(
+        "SELECT id, local_file_name, generated_link, alt_text, title \
+         FROM history_items WHERE id IN ({})",
+        placeholders
+    );
+    let mut query = sqlx::query(&sql);
+    for id in ids {
+        query = query.bind(id);
+    }
+    let rows = query
+        .fetch_all(&mut conn)
+        .await
+        .map_err(|e| AppError::storage(format!("查询历史记录失败: {}", e)))?;
+
+    let mut found: HashMap<String, ShareImage> = rows
+        .iter()
+        .filter_map(|row| {
+            let id: String = row.try_get("id").ok()?;
+            let url: String = row.try_get("generated_link").ok()?;
+            if url.trim().is_empty() {
+                return None;
+            }
+            let filename: String = row.try_get("local_file_name").unwrap_or_default();
+            let stored = |column: &str| {
+                row.try_get::<Option<String>, _>(column)
+                    .ok()
+                    .flatten()
+                    .filter(|v| !v.trim().is_empty())
+            };
+            let alt = stored("alt_text").unwrap_or_else(|| default_alt_text(&filename));
+            // 说明文字：标题优先，其次替代文本，最后是文件名
+            let caption = stored("title")
+                .or_else(|| stored("alt_text"))
+                .unwrap_or_else(|| filename.clone());
+            Some((id, ShareImage { url, alt, caption }))
+        })
+        .collect();
+    Ok(ids.iter().filter_map(|id| found.remove(id)).collect())
+}
+
+/// HTML 文本与属性值转义
+fn escape_html(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&#39;"),
+            _ => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+/// 生成分享页 HTML（纯静态，不含脚本）
+fn render_page(title: &str, images: &[ShareImage]) -> String {
+    let title = escape_html(title);
+    let mut html = format!(
+        "<!DOCTYPE html>\n<html lang=\"zh-CN\">\n<head>\n<meta charset=\"utf-8\">\n\
+         <meta name=\"viewport\" content=\"width=device-width, initial-scale=1\">\n\
+         <meta name=\"referrer\" content=\"no-referrer\">\n<title>{title}</title>\n<style>\n\
+         body{{margin:0 auto;max-width:960px;padding:24px 16px;font-family:system-ui,sans-serif;\
+         background:#f5f5f5;color:#222}}\n\
+         h1{{font-size:22px;font-weight:600}}\n\
+         figure{{margin:0 0 28px;background:#fff;border-radius:8px;padding:12px;\
+         box-shadow:0 1px 3px rgba(0,0,0,.08)}}\n\
+         img{{display:block;max-width:100%;height:auto;margin:0 auto}}\n\
+         figcaption{{margin-top:8px;font-size:14px;color:#555;text-align:center}}\n\
+         </style>\n</head>\n<body>\n<h1>{title}</h1>\n"
+    );
+    for image in images {
+        html.push_str(&format!(
+            "<figure>\n<a href=\"{url}\"><img src=\"{url}\" alt=\"{alt}\" loading=\"lazy\"></a>\n\
+             <figcaption>{caption}</figcaption>\n</figure>\n",
+            url = escape_html(&image.url),
+            alt = escape_html(&image.alt),
+            caption = escape_html(&image.caption),
+        ));
+    }
+    html.push_str("</body>\n</html>\n");
+    html
+}
+
+/// 把选中的历史记录生成一个分享页，返回可分享的链接
+///
+/// 指定 service 时该图床必须能托管网页；未指定时使用默认图床；`local_only` 为 true 时只生成本地分享页
+#[tauri::command]
+pub async fn create_share_page(
+    app: tauri::AppHandle,
+    record_ids: Vec<String>,
+    title: Option<String>,
+    service: Option<String>,
+    local_only: Option<bool>,
+) -> Result<SharePage, AppError> {
+    let local_only = local_only.unwrap_or(false);
+    if local_only && service.is_some() {
+        return Err(AppError::validation("本地分享页不能同时指定图床"));
+    }
+    if record_ids.is_empty() {
+        return Err(AppError::validation("请至少选择一条记录"));
+    }
+    if record_ids.len() > MAX_SHARE_IMAGES {
+        return Err(AppError::validation(format!(
+            "一个分享页最多包含 {} 张图片",
+            MAX_SHARE_IMAGES
+        )));
+    }
+
+    let db_path = crate::portable::history_db_path(&app)?;
+    if !db_path.exists() {
+        return Err(AppError::validation("历史记录不存在"));
+    }
+    let images = load_images(&db_path, &record_ids).await?;
+    if images.is_empty() {
+        return Err(AppError::validation("所选记录没有可用链接"));
+    }
+
+    // 托管网页的图床必须能接受 HTML；本地分享页只在调用方明确要求时生成
+    let remote = if local_only {
+        None
+    } else {
+        let (service_id, config) =
+            cli::resolve_service_config(service.as_deref()).map_err(AppError::config)?;
+        if !accepts_html(&config) {
+            return Err(AppError::validation(format!(
+                "图床 {} 只接受图片，请选择对象存储类图床或使用本地分享",
+                service_id
+            )));
+        }
+        Some((service_id, config))
+    };
+
+    let id = uuid::Uuid::new_v4().simple().to_string();
+    let title = title
+        .map(|t| t.trim().to_string())
+        .filter(|t| !t.is_empty())
+        .unwrap_or_else(|| format!("{} 张图片", images.len()));
+    let html = render_page(&title, &images);
+
+    let (url, target, service) = match remote {
+        Some((service_id, config)) => {
+            let file_name = format!("{}/{}.html", REMOTE_SHARE_PREFIX, id);
+            let url = upload_html_page(&config, &file_name, html.into_bytes())
+                .await
+                .map_err(|e| AppError::upload(service_id.as_str(), e))?;
+            (url, ShareTarget::Host, Some(service_id))
+        }
+        None => {
+            let port = crate::server::running_port().ok_or_else(|| {
+                AppError::validation("本地分享页需要本地 Server，请先在设置中启用")
+            })?;
+            let dir = crate::portable::user_data_dir(&app)?.join(SHARE_PAGE_DIR);
+            tokio::fs::create_dir_all(&dir)
+                .await
+                .map_err(|e| AppError::file_io(format!("无法创建分享页目录: {}", e)))?;
+            atomic_file::write_async(dir.join(format!("{}.html", id)), html)
+                .await
+                .map_err(|e| AppError::file_io(format!("写入分享页失败: {}", e)))?;
+            let url = format!("http://127.0.0.1:{}/share/{}", port, id);
+            (url, ShareTarget::Local, None)
+        }
+    };
+    log::info!(
+        "[分享页] 已生成 {} 张图片的分享页: {}",
+        images.len(),
+        safe_url(&url)
+    );
+
+    Ok(SharePage {
+        id,
+        url,
+        target,
+        service,
+        images: images.len(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::server::share_handler::is_valid_share_id;
+
+    #[test]
+    fn escapes_html_special_characters() {
+        assert_eq!(
+            escape_html(r#"<a href="x">Tom & 'Jerry'</a>"#),
+            "&lt;a href=&quot;x&quot;&gt;Tom &amp; &#39;Jerry&#39;&lt;/a&gt;"
+        );
+        assert_eq!(escape_html("截图 01"), "截图 01");
+    }
+
+    #[test]
+    fn renders_static_page_with_escaped_captions() {
+        let images = vec![
+            ShareImage {
+                url: "https://img.example.com/a.png?x=1&y=2".to_string(),
+                alt: "登录页".to_string(),
+                caption: "<script>alert(1)</script>".to_string(),
+            },
+            ShareImage {
+                url: "https://img.example.com/b.png".to_string(),
+                alt: "b".to_string(),
+                caption: "第二步".to_string(),
+            },
+        ];
+        let html = render_page("Bug #42 复现", &images);
+
+        assert!(html.contains("<title>Bug #42 复现</title>"));
+        assert!(html.contains("src=\"https://img.example.com/a.png?x=1&amp;y=2\""));
+        assert!(html.contains("<figcaption>&lt;script&gt;alert(1)&lt;/script&gt;</figcaption>"));
+        assert!(!html.contains("<script"));
+        assert_eq!(html.matches("<figure>").count(), 2);
+        assert!(html.find("登录页").unwrap() < html.find("第二步").unwrap());
+    }
+
+    #[test]
+    fn share_ids_match_server_route() {
+        assert!(is_valid_share_id(
+            &uuid::Uuid::new_v4().simple().to_string()
+        ));
+        assert!(!is_valid_share_id("../../etc/passwd"));
+        assert!(!is_valid_share_id(""));
+    }
+}
@@ -0,0 +1,288 @@
+// src-tauri/src/commands/short_link.rs
+// 短链接服务：上传成功后将图床 URL 缩短（YOURLS / Sink / 自建服务）
+
+use serde::Deserialize;
+use std::time::Duration;
+
+use crate::error::{AppError, IntoAppError};
+use crate::log_utils::{safe_url, summarize_text};
+
+/// 短链接请求超时
+const SHORTEN_TIMEOUT_SECS: u64 = 15;
+
+/// 短链接服务类型
+#[derive(Debug, Clone, Copy, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum ShortLinkProvider {
+    /// YOURLS：`yourls-api.php?action=shorturl`
+    Yourls,
+    /// Sink（Cloudflare 短链）：`POST /api/link/create`
+    Sink,
+    /// 自建服务：`POST {endpoint}`，请求体 `{"url": "..."}`
+    Custom,
+}
+
+/// 短链接服务配置（由前端设置页传入）
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ShortLinkConfig {
+    pub provider: ShortLinkProvider,
+    /// 服务地址（YOURLS 为站点根地址或 yourls-api.php 完整地址）
+    pub endpoint: String,
+    /// YOURLS signature token / Sink 与自建服务的 Bearer token
+    #[serde(default)]
+    pub token: Option<String>,
+    /// 自建服务响应中短链接所在字段，支持 `data.url` 形式的点分路径；为空时按纯文本解析
+    #[serde(default)]
+    pub response_field: Option<String>,
+}
+
+impl ShortLinkConfig {
+    fn token(&self) -> Option<&str> {
+        self.token
+            .as_deref()
+            .map(str::trim)
+            .filter(|token| !token.is_empty())
+    }
+}
+
+/// 计算实际请求地址
+fn request_url(config: &ShortLinkConfig) -> Result<String, AppError> {
+    let endpoint = config.endpoint.trim().trim_end_matches('/');
+    if endpoint.is_empty() {
+        return Err(AppError::config("短链接服务地址不能为空"));
+    }
+    if !endpoint.starts_with("http://") && !endpoint.starts_with("https://") {
+        return Err(AppError::config(
+            "短链接服务地址必须以 http:// 或 https:// 开头",
+        ));
+    }
+
+    Ok(match config.provider {
+        ShortLinkProvider::Yourls if endpoint.ends_with(".php") => endpoint.to_string(),
+        ShortLinkProvider::Yourls => format!("{}/yourls-api.php", endpoint),
+        ShortLinkProvider::Sink if endpoint.ends_with("/api/link/create") => endpoint.to_string(),
+        ShortLinkProvider::Sink => format!("{}/api/link/create", endpoint),
+        ShortLinkProvider::Custom => endpoint.to_string(),
+    })
+}
+
+/// 按点分路径读取 JSON 字符串字段
+fn json_field<'a>(value: &'a serde_json::Value, path: &str) -> Option<&'a str> {
+    path.split('.')
+        .filter(|segment| !segment.is_empty())
+        .try_fold(value, |current, segment| current.get(segment))
+        .and_then(|v| v.as_str())
+}
+
+/// 从服务响应中解析短链接
+fn parse_short_url(provider: ShortLinkProvider, field: Option<&str>, body: &str) -> Option<String> {
+    let trimmed = body.trim();
+    let field = field.map(str::trim).filter(|f| !f.is_empty());
+
+    let parsed = match provider {
+        ShortLinkProvider::Yourls => {
+            let json: serde_json::Value = serde_json::from_str(trimmed).ok()?;
+            json_field(&json, "shorturl").map(str::to_string)
+        }
+        ShortLinkProvider::Sink => {
+            let json: serde_json::Value = serde_json::from_str(trimmed).ok()?;
+            json_field(&json, "shortLink").map(str::to_string)
+        }
+        ShortLinkProvider::Custom => match field {
+            Some(field) => {
+                let json: serde_json::Value = serde_json::from_str(trimmed).ok()?;
+                json_field(&json, field).map(str::to_string)
+            }
+            None => Some(trimmed.to_string()),
+        },
+    }?;
+
+    let parsed = parsed.trim();
+    (parsed.starts_with("http://") || parsed.starts_with("https://")).then(|| parsed.to_string())
+}
+
+/// 将图床 URL 转换为短链接
+#[tauri::command]
+pub async fn shorten_url(
+    url: String,
+    config: ShortLinkConfig,
+    http_client: tauri::State<'_, crate::HttpClient>,
+) -> Result<String, AppError> {
+    let url = url.trim().to_string();
+    if !url.starts_with("http://") && !url.starts_with("https://") {
+        return Err(AppError::validation("只能缩短 http/https 链接"));
+    }
+
+    let endpoint = request_url(&config)?;
+    log::info!(
+        "[短链接] 请求缩短: {} -> {}",
+        safe_url(&url),
+        safe_url(&endpoint)
+    );
+
+    let client = http_client.get();
+    let request = match config.provider {
+        ShortLinkProvider::Yourls => {
+            let signature = config
+                .token()
+                .ok_or_else(|| AppError::config("YOURLS 需要填写 signature token"))?;
+            client.post(&endpoint).form(&[
+                ("signature", signature),
+                ("action", "shorturl"),
+                ("format", "json"),
+                ("url", url.as_str()),
+            ])
+        }
+        ShortLinkProvider::Sink => {
+            let token = config
+                .token()
+                .ok_or_else(|| AppError::config("Sink 需要填写 Site Token"))?;
+            client
+                .post(&endpoint)
+                .bearer_auth(token)
+                .json(&serde_json::json!({ "url": url }))
+        }
+        ShortLinkProvider::Custom => {
+            let request = client
+                .post(&endpoint)
+                .json(&serde_json::json!({ "url": url }));
+            match config.token() {
+                Some(token) => request.bearer_auth(token),
+                None => request,
+            }
+        }
+    };
+
+    let response = request
+        .timeout(Duration::from_secs(SHORTEN_TIMEOUT_SECS))
+        .send()
+        .await
+        .into_network_err_with("短链接请求失败")?;
+
+    let status = response.status();
+    let body = response
+        .text()
+        .await
+        .into_network_err_with("无法读取短链接响应")?;
+
+    // YOURLS 对已存在的 URL 返回 400 + status:fail，但仍携带 shorturl
+    let short_url = parse_short_url(config.provider, config.response_field.as_deref(), &body);
+    match short_url {
+        Some(short_url) => {
+            log::info!("[短链接] 缩短成功: {}", safe_url(&short_url));
+            Ok(short_url)
+        }
+        None => {
+            log::warn!(
+                "[短链接] 响应无法解析 (HTTP {}): {}",
+                status,
+                summarize_text(&body)
+            );
+            if status == reqwest::StatusCode::UNAUTHORIZED
+                || status == reqwest::StatusCode::FORBIDDEN
+            {
+                return Err(AppError::auth("短链接服务鉴权失败，请检查 token"));
+            }
+            Err(AppError::external(format!(
+                "短链接服务未返回有效链接 (HTTP {}): {}",
+                status,
+                summarize_text(&body)
+            )))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn config(provider: ShortLinkProvider, endpoint: &str) -> ShortLinkConfig {
+        ShortLinkConfig {
+            provider,
+            endpoint: endpoint.to_string(),
+            token: None,
+            response_field: None,
+        }
+    }
+
+    #[test]
+    fn request_url_appends_provider_api_path() {
+        assert_eq!(
+            request_url(&config(ShortLinkProvider::Yourls, "https://s.example.com/")).unwrap(),
+            "https://s.example.com/yourls-api.php"
+        );
+        assert_eq!(
+            request_url(&config(
+                ShortLinkProvider::Yourls,
+                "https://s.example.com/yourls-api.php"
+            ))
+            .unwrap(),
+            "https://s.example.com/yourls-api.php"
+        );
+        assert_eq!(
+            request_url(&config(ShortLinkProvider::Sink, "https://sink.example.com")).unwrap(),
+            "https://sink.example.com/api/link/create"
+        );
+        assert_eq!(
+            request_url(&config(
+                ShortLinkProvider::Custom,
+                "https://x.example.com/new"
+            ))
+            .unwrap(),
+            "https://x.example.com/new"
+        );
+    }
+
+    #[test]
+    fn request_url_rejects_empty_or_non_http_endpoint() {
+        assert!(request_url(&config(ShortLinkProvider::Sink, "  ")).is_err());
+        assert!(request_url(&config(ShortLinkProvider::Custom, "ftp://x.example.com")).is_err());
+    }
+
+    #[test]
+    fn parse_short_url_reads_provider_fields() {
+        let yourls =
+            r#"{"status":"fail","code":"error:url","shorturl":"https://s.example.com/abc"}"#;
+        assert_eq!(
+            parse_short_url(ShortLinkProvider::Yourls, None, yourls).as_deref(),
+            Some("https://s.example.com/abc")
+        );
+
+        let sink = r#"{"link":{"slug":"abc"},"shortLink":"https://sink.example.com/abc"}"#;
+        assert_eq!(
+            parse_short_url(ShortLinkProvider::Sink, None, sink).as_deref(),
+            Some("https://sink.example.com/abc")
+        );
+    }
+
+    #[test]
+    fn parse_short_url_supports_custom_field_path_and_plain_text() {
+        let body = r#"{"data":{"url":"https://x.example.com/1"}}"#;
+        assert_eq!(
+            parse_short_url(ShortLinkProvider::Custom, Some("data.url"), body).as_deref(),
+            Some("https://x.example.com/1")
+        );
+        assert_eq!(
+            parse_short_url(
+                ShortLinkProvider::Custom,
+                None,
+                " https://x.example.com/2\n"
+            )
+            .as_deref(),
+            Some("https://x.example.com/2")
+        );
+    }
+
+    #[test]
+    fn parse_short_url_rejects_non_url_responses() {
+        assert_eq!(
+            parse_short_url(ShortLinkProvider::Custom, None, "<html>error</html>"),
+            None
+        );
+        assert_eq!(
+            parse_short_url(ShortLinkProvider::Yourls, None, r#"{"status":"fail"}"#),
+            None
+        );
+    }
+}
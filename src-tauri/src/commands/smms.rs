@@ -6,7 +6,10 @@ use serde::{Deserialize, Serialize};
 use tauri::{Emitter, Window};
 use url::Url;
 
-use super::utils::read_file_bytes;
+use super::host_capabilities;
+use super::net_limiter::NetworkLimiter;
+use super::task_registry::TaskRegistry;
+use super::utils::{read_file_bytes, remember_uploaded_source};
 use crate::error::{AppError, IntoAppError};
 use crate::log_utils::{safe_path, safe_url, summarize_text};
 
@@ -97,9 +100,6 @@ pub(crate) fn parse_smms_upload_response(
     })
 }
 
-/// 文件大小限制：5MB（SM.MS 免费用户限制）
-const MAX_FILE_SIZE: u64 = 5 * 1024 * 1024;
-
 /// 上传文件到 SM.MS
 #[tauri::command]
 pub async fn upload_to_smms(
@@ -107,7 +107,26 @@ pub async fn upload_to_smms(
     id: String,
     file_path: String,
     smms_token: String,
+    limiter: tauri::State<'_, NetworkLimiter>,
+    tasks: tauri::State<'_, TaskRegistry>,
+) -> Result<SmmsUploadResult, AppError> {
+    tasks
+        .run(
+            Some(&id),
+            upload_to_smms_inner(window, id.clone(), file_path.clone(), smms_token, limiter),
+        )
+        .await
+        .inspect(|_| remember_uploaded_source(&file_path))
+}
+
+async fn upload_to_smms_inner(
+    window: Window,
+    id: String,
+    file_path: String,
+    smms_token: String,
+    limiter: tauri::State<'_, NetworkLimiter>,
 ) -> Result<SmmsUploadResult, AppError> {
+    let _permit = limiter.acquire("smms").await;
     log::info!("[SM.MS] 开始上传文件: {}", safe_path(&file_path));
 
     // 发送进度: 0% - 读取文件
@@ -124,17 +143,10 @@ pub async fn upload_to_smms(
     );
 
     // 1. 读取文件
-    let (buffer, file_size) = read_file_bytes(&file_path, MAX_FILE_SIZE).await?;
+    let (buffer, file_size) =
+        read_file_bytes(&file_path, host_capabilities::read_limit("smms")).await?;
 
-    // 2. 验证文件大小（限制 5MB）
-    if file_size > MAX_FILE_SIZE {
-        return Err(AppError::validation(format!(
-            "文件大小 ({:.2}MB) 超过 SM.MS 限制 (5MB)",
-            file_size as f64 / 1024.0 / 1024.0
-        )));
-    }
-
-    // 3. 验证文件类型（只允许图片）
+    // 2. 按图床能力表校验文件类型与大小
     let file_name = std::path::Path::new(&file_path)
         .file_name()
         .and_then(|n| n.to_str())
@@ -146,11 +158,7 @@ pub async fn upload_to_smms(
         .ok_or_else(|| AppError::validation("无法获取文件扩展名"))?
         .to_lowercase();
 
-    if !["jpg", "jpeg", "png", "gif", "bmp", "webp"].contains(&ext.as_str()) {
-        return Err(AppError::validation(
-            "只支持 JPG、PNG、GIF、BMP、WebP 格式的图片",
-        ));
-    }
+    host_capabilities::check_admission("smms", &ext, file_size)?;
 
     // 发送进度: 33% - 准备上传
     let _ = window.emit(
@@ -166,7 +174,7 @@ pub async fn upload_to_smms(
     );
 
     // 4. 构建 multipart form
-    let part = multipart::Part::bytes(buffer)
+    let part = multipart::Part::stream_with_length(buffer, file_size)
         .file_name(file_name.to_string())
         .mime_str("image/*")
         .into_validation_err_with("无法设置 MIME 类型")?;
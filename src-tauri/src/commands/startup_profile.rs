@@ -0,0 +1,90 @@
+// src-tauri/src/commands/startup_profile.rs
+// 启动耗时记录：各子系统初始化的开始时刻与耗时，供设置页「关于」诊断慢启动
+//
+// 托盘与主窗口之外的子系统（HTTP 客户端、跳转列表、日志清理等）改为首次使用时或后台初始化，
+// 这里记录它们实际完成的时刻，用户反馈「托盘图标出现慢」时可以直接看到卡在哪一步。
+
+use std::sync::{Mutex, OnceLock};
+use std::time::{Duration, Instant};
+
+use serde::Serialize;
+
+static PROCESS_START: OnceLock<Instant> = OnceLock::new();
+static ENTRIES: Mutex<Vec<SubsystemTiming>> = Mutex::new(Vec::new());
+
+/// 单个子系统的初始化耗时
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SubsystemTiming {
+    pub name: &'static str,
+    /// 相对进程启动的开始时刻（毫秒）
+    pub started_at_ms: u64,
+    pub duration_ms: u64,
+    /// 是否延迟到首次使用 / 后台线程中初始化（不阻塞托盘出现）
+    pub deferred: bool,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StartupProfile {
+    pub entries: Vec<SubsystemTiming>,
+    /// 进程已运行时长（毫秒）
+    pub uptime_ms: u64,
+}
+
+/// 记录进程启动时刻（main 入口处调用）
+pub fn mark_process_start() {
+    let _ = PROCESS_START.set(Instant::now());
+}
+
+fn millis(duration: Duration) -> u64 {
+    u64::try_from(duration.as_millis()).unwrap_or(u64::MAX)
+}
+
+/// 记录从 `started` 到现在的耗时（异步初始化在完成处调用）
+pub fn record(name: &'static str, started: Instant, deferred: bool) {
+    let origin = *PROCESS_START.get_or_init(|| started);
+    let timing = SubsystemTiming {
+        name,
+        started_at_ms: millis(started.saturating_duration_since(origin)),
+        duration_ms: millis(started.elapsed()),
+        deferred,
+    };
+    log::debug!(
+        "[启动] {} 耗时 {} ms（+{} ms）",
+        name,
+        timing.duration_ms,
+        timing.started_at_ms
+    );
+    ENTRIES
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+        .push(timing);
+}
+
+/// 同步初始化，阻塞启动流程
+pub fn measure<T>(name: &'static str, init: impl FnOnce() -> T) -> T {
+    let started = Instant::now();
+    let value = init();
+    record(name, started, false);
+    value
+}
+
+/// 延迟初始化（首次使用 / 后台线程），不阻塞托盘出现
+pub fn measure_deferred<T>(name: &'static str, init: impl FnOnce() -> T) -> T {
+    let started = Instant::now();
+    let value = init();
+    record(name, started, true);
+    value
+}
+
+/// 获取各子系统的启动耗时
+#[tauri::command]
+pub fn get_startup_profile() -> StartupProfile {
+    let uptime_ms = PROCESS_START
+        .get()
+        .map_or(0, |start| millis(start.elapsed()));
+    let mut entries = ENTRIES.lock().unwrap_or_else(|e| e.into_inner()).clone();
+    entries.sort_by_key(|entry| entry.started_at_ms);
+    StartupProfile { entries, uptime_ms }
+}
@@ -0,0 +1,333 @@
+// src-tauri/src/commands/task_registry.rs
+// 长耗时任务注册表：上传 / 下载 / 链接检测 / 压缩按任务 ID 登记取消令牌，前端通过 cancel_task 统一取消
+//
+// 以前前端关闭窗口或按 Esc 只是丢弃 invoke 的结果，后台请求仍会跑完，继续占用网络、并发名额与临时文件。
+// 现在任务放在 `TaskRegistry::run` 中执行：令牌被取消时立即返回 E_CANCELLED，
+// 被丢弃的 future 随之中断网络请求，临时文件守卫在 drop 时清理。
+// 已提交到 CPU 线程池的计算无法中途打断，只是结果不再返回。
+//
+// 前端看门狗发现上传长时间没有进度时调用 cancel_stalled_task：任务同样被取消，
+// 但以可重试的 E_STALLED 结束，并把当时的任务快照写入日志，便于排查卡在哪一步。
+
+use std::collections::HashMap;
+use std::future::Future;
+use std::panic::AssertUnwindSafe;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex, OnceLock};
+use std::time::Instant;
+
+use futures::FutureExt;
+use tokio_util::sync::CancellationToken;
+
+use crate::error::AppError;
+
+/// 登记中的任务
+struct TaskEntry {
+    /// 登记序号，用于区分重复登记的同名任务
+    seq: u64,
+    token: CancellationToken,
+    started_at: Instant,
+    /// 被看门狗中止时记录的无进度秒数
+    stalled: Arc<OnceLock<u64>>,
+}
+
+/// 任务注册表（Tauri State 管理）
+#[derive(Default)]
+pub struct TaskRegistry {
+    tasks: Mutex<HashMap<String, TaskEntry>>,
+    next_seq: AtomicU64,
+}
+
+/// 已登记的任务，drop 时注销
+pub struct TaskGuard<'a> {
+    registry: &'a TaskRegistry,
+    task_id: String,
+    seq: u64,
+    token: CancellationToken,
+    stalled: Arc<OnceLock<u64>>,
+}
+
+/// 任务快照（看门狗诊断日志用）
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TaskSnapshot {
+    pub task_id: String,
+    /// 已运行秒数
+    pub age_secs: u64,
+}
+
+impl TaskGuard<'_> {
+    /// 取消令牌，供需要在循环中主动检查取消的任务使用
+    pub fn token(&self) -> &CancellationToken {
+        &self.token
+    }
+}
+
+impl Drop for TaskGuard<'_> {
+    fn drop(&mut self) {
+        let mut tasks = self
+            .registry
+            .tasks
+            .lock()
+            .unwrap_or_else(|e| e.into_inner());
+        // 同名任务已被新的登记覆盖时不能误删
+        if tasks
+            .get(&self.task_id)
+            .is_some_and(|entry| entry.seq == self.seq)
+        {
+            tasks.remove(&self.task_id);
+        }
+    }
+}
+
+impl TaskRegistry {
+    /// 登记任务；同名任务已存在时旧任务被取消并覆盖
+    pub fn register(&self, task_id: &str) -> TaskGuard<'_> {
+        let seq = self.next_seq.fetch_add(1, Ordering::Relaxed);
+        let token = CancellationToken::new();
+        let stalled = Arc::new(OnceLock::new());
+        let entry = TaskEntry {
+            seq,
+            token: token.clone(),
+            started_at: Instant::now(),
+            stalled: stalled.clone(),
+        };
+        let previous = self
+            .tasks
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .insert(task_id.to_string(), entry);
+        if let Some(previous) = previous {
+            log::warn!("[任务] 任务 ID 重复，取消旧任务: {}", task_id);
+            previous.token.cancel();
+        }
+        TaskGuard {
+            registry: self,
+            task_id: task_id.to_string(),
+            seq,
+            token,
+            stalled,
+        }
+    }
+
+    /// 以可取消的方式执行任务；`task_id` 为空时直接执行（前端未传任务 ID 的旧调用）
+    ///
+    /// 任务 panic 时转为 E_TASK_PANICKED 错误返回（panic hook 已写入崩溃报告），
+    /// 否则 invoke 永远不会返回，前端只能看到一个卡住的进度条
+    pub async fn run<T, F>(&self, task_id: Option<&str>, task: F) -> Result<T, AppError>
+    where
+        F: Future<Output = Result<T, AppError>>,
+    {
+        let task = async {
+            AssertUnwindSafe(task)
+                .catch_unwind()
+                .await
+                .unwrap_or_else(|_| {
+                    log::error!("[任务] 任务异常终止: {}", task_id.unwrap_or("<未登记>"));
+                    Err(AppError::task_panicked())
+                })
+        };
+        let Some(task_id) = task_id else {
+            return task.await;
+        };
+        let guard = self.register(task_id);
+        tokio::select! {
+            biased;
+            _ = guard.token().cancelled() => match guard.stalled.get() {
+                Some(&idle_secs) => Err(AppError::stalled(idle_secs)),
+                None => {
+                    log::info!("[任务] 已取消: {}", task_id);
+                    Err(AppError::cancelled())
+                }
+            },
+            result = task => result,
+        }
+    }
+
+    /// 取消指定任务；任务不存在（已结束或从未登记）时返回 false
+    pub fn cancel(&self, task_id: &str) -> bool {
+        let tasks = self.tasks.lock().unwrap_or_else(|e| e.into_inner());
+        match tasks.get(task_id) {
+            Some(entry) => {
+                entry.token.cancel();
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// 以 E_STALLED 中止长时间无进度的任务；任务不存在时返回 false
+    pub fn cancel_stalled(&self, task_id: &str, idle_secs: u64) -> bool {
+        let tasks = self.tasks.lock().unwrap_or_else(|e| e.into_inner());
+        match tasks.get(task_id) {
+            Some(entry) => {
+                // 先记录原因再取消，run 返回时据此区分用户取消与看门狗中止
+                let _ = entry.stalled.set(idle_secs);
+                entry.token.cancel();
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// 当前登记的任务（按已运行时长从长到短）
+    pub fn snapshot(&self) -> Vec<TaskSnapshot> {
+        let tasks = self.tasks.lock().unwrap_or_else(|e| e.into_inner());
+        let mut snapshot: Vec<TaskSnapshot> = tasks
+            .iter()
+            .map(|(task_id, entry)| TaskSnapshot {
+                task_id: task_id.clone(),
+                age_secs: entry.started_at.elapsed().as_secs(),
+            })
+            .collect();
+        snapshot.sort_by_key(|task| std::cmp::Reverse(task.age_secs));
+        snapshot
+    }
+
+    /// 取消全部任务，返回取消的数量（应用退出前调用）
+    pub fn cancel_all(&self) -> usize {
+        let tasks = self.tasks.lock().unwrap_or_else(|e| e.into_inner());
+        for entry in tasks.values() {
+            entry.token.cancel();
+        }
+        tasks.len()
+    }
+}
+
+/// 取消正在进行的任务（上传 ID、下载 / 检测 / 压缩时传入的 taskId）
+///
+/// 返回 false 表示任务已结束或不存在
+#[tauri::command]
+pub fn cancel_task(task_id: String, tasks: tauri::State<'_, TaskRegistry>) -> bool {
+    let cancelled = tasks.cancel(&task_id);
+    log::info!(
+        "[任务] 收到取消请求: {}{}",
+        task_id,
+        if cancelled {
+            ""
+        } else {
+            "（任务不存在）"
+        }
+    );
+    cancelled
+}
+
+/// 看门狗中止长时间无进度的任务（上传命令的 `id`）
+///
+/// `progress` / `step` 为前端最后一次收到的进度，与当前任务快照一起写入日志。
+/// 任务以可重试的 E_STALLED 错误结束；返回 false 表示任务已结束或不存在
+#[tauri::command]
+pub fn cancel_stalled_task(
+    task_id: String,
+    idle_secs: u64,
+    progress: Option<f64>,
+    step: Option<String>,
+    tasks: tauri::State<'_, TaskRegistry>,
+) -> bool {
+    let snapshot = tasks.snapshot();
+    let age_secs = snapshot
+        .iter()
+        .find(|task| task.task_id == task_id)
+        .map(|task| task.age_secs);
+    let Some(age_secs) = age_secs else {
+        log::info!("[看门狗] 任务已结束，无需中止: {}", task_id);
+        return false;
+    };
+    log::warn!(
+        "[看门狗] 任务 {} 已 {} 秒无进度，强制中止（已运行 {} 秒，最后进度 {}，步骤 {}）",
+        task_id,
+        idle_secs,
+        age_secs,
+        progress.map_or_else(|| "未知".to_string(), |p| format!("{:.0}%", p)),
+        step.as_deref().unwrap_or("无")
+    );
+    log::warn!(
+        "[看门狗] 当前共 {} 个任务: {}",
+        snapshot.len(),
+        snapshot
+            .iter()
+            .map(|task| format!("{}({}s)", task.task_id, task.age_secs))
+            .collect::<Vec<_>>()
+            .join(", ")
+    );
+    tasks.cancel_stalled(&task_id, idle_secs)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::error::ErrorCode;
+
+    #[tokio::test]
+    async fn run_returns_cancelled_error() {
+        let registry = TaskRegistry::default();
+        let task = registry.run(Some("t1"), async {
+            std::future::pending::<()>().await;
+            Ok(())
+        });
+        let cancel = async {
+            tokio::task::yield_now().await;
+            assert!(registry.cancel("t1"));
+        };
+        let (result, ()) = tokio::join!(task, cancel);
+        assert_eq!(result.unwrap_err().code(), ErrorCode::Cancelled);
+        assert!(!registry.cancel("t1"), "结束的任务应已注销");
+    }
+
+    #[tokio::test]
+    async fn stalled_task_ends_with_retriable_error() {
+        let registry = TaskRegistry::default();
+        let task = registry.run(Some("slow"), async {
+            std::future::pending::<()>().await;
+            Ok(())
+        });
+        let watchdog = async {
+            tokio::task::yield_now().await;
+            assert_eq!(registry.snapshot()[0].task_id, "slow");
+            assert!(registry.cancel_stalled("slow", 300));
+        };
+        let (result, ()) = tokio::join!(task, watchdog);
+        let err = result.unwrap_err();
+        assert_eq!(err.code(), ErrorCode::Stalled);
+        assert!(err.is_retriable());
+        assert!(registry.snapshot().is_empty());
+        assert!(!registry.cancel_stalled("slow", 300));
+    }
+
+    #[tokio::test]
+    async fn run_converts_panic_to_error() {
+        let registry = TaskRegistry::default();
+        let result: Result<(), AppError> =
+            registry.run(Some("panics"), async { panic!("boom") }).await;
+        assert_eq!(result.unwrap_err().code(), ErrorCode::TaskPanicked);
+        assert!(!registry.cancel("panics"));
+    }
+
+    #[tokio::test]
+    async fn run_without_task_id_is_passthrough() {
+        let registry = TaskRegistry::default();
+        assert_eq!(registry.run(None, async { Ok(7) }).await.unwrap(), 7);
+    }
+
+    #[test]
+    fn duplicate_registration_cancels_previous() {
+        let registry = TaskRegistry::default();
+        let first = registry.register("dup");
+        let second = registry.register("dup");
+        assert!(first.token().is_cancelled());
+        assert!(!second.token().is_cancelled());
+
+        // 旧守卫注销时不能移除新任务
+        drop(first);
+        assert!(registry.cancel("dup"));
+        assert!(second.token().is_cancelled());
+    }
+
+    #[test]
+    fn cancel_all_cancels_every_task() {
+        let registry = TaskRegistry::default();
+        let a = registry.register("a");
+        let b = registry.register("b");
+        assert_eq!(registry.cancel_all(), 2);
+        assert!(a.token().is_cancelled() && b.token().is_cancelled());
+    }
+}
@@ -0,0 +1,502 @@
+// src-tauri/src/commands/tempfiles.rs
+// 统一临时文件管理：应用专属临时目录、drop 即删除的守卫、总量配额
+//
+// 剪贴板图片、URL 下载、失效图片重新上传原本各自往系统临时目录写文件，
+// 再靠「文件名前缀 + 修改时间」零散清理，上传中途出错时常常残留。
+// 现在统一写到系统临时目录下的 picnexus 子目录：文件由 `TempFileGuard` 持有，
+// 出错返回或任务结束时自动删除；需要交给前端继续使用的文件调用 `keep()` 移交，
+// 由前端清理命令或过期清扫删除。目录总占用超过上限时拒绝新建。
+// 剪贴板图片例外：前端在上传全部成功前一直保留它（失败项可能隔天、重启后才重试），
+// 不参与过期清扫，只由前端清理命令或缓存管理的手动清理删除。
+//
+// 过期清扫和前端清理可能与正在进行的任务并发：一个任务刚下载完、还在排队等上传，
+// 另一个任务配额不足触发清扫，就会把它的文件删掉。所有守卫和正在读取的引用都登记在
+// 引用表中，清扫与清理跳过仍被引用的文件。
+
+use std::collections::HashMap;
+use std::ffi::{OsStr, OsString};
+use std::path::{Path, PathBuf};
+use std::sync::{LazyLock, Mutex};
+use std::time::{Duration, SystemTime};
+
+use super::clipboard::{self, CLIPBOARD_TEMP_PREFIX};
+use super::disk_space;
+use crate::error::{AppError, ErrorCode};
+use crate::log_utils::safe_path;
+use crate::path_utils;
+
+/// 应用临时目录名（位于系统临时目录下）
+const DIR_NAME: &str = "picnexus";
+
+/// 临时文件总占用上限（1GB）
+const MAX_TOTAL_BYTES: u64 = 1024 * 1024 * 1024;
+
+/// 移交给前端后未被清理的文件，超过该时长视为残留
+const MAX_AGE: Duration = Duration::from_secs(3600);
+
+/// 不参与过期清扫的文件名前缀（移交给前端后由前端负责回收）
+const SWEEP_EXEMPT_PREFIXES: &[&str] = &[CLIPBOARD_TEMP_PREFIX];
+
+fn is_sweep_exempt(name: &OsStr) -> bool {
+    let name = name.to_string_lossy();
+    SWEEP_EXEMPT_PREFIXES
+        .iter()
+        .any(|prefix| name.starts_with(prefix))
+}
+
+static ROOT: LazyLock<PathBuf> = LazyLock::new(|| std::env::temp_dir().join(DIR_NAME));
+
+/// 已占用字节数；None 表示尚未扫描过目录
+static USAGE: Mutex<Option<u64>> = Mutex::new(None);
+
+/// 仍被引用的文件（文件名 → 引用计数）；应用临时目录只有一层，文件名即可唯一定位
+static LIVE: LazyLock<Mutex<HashMap<OsString, usize>>> =
+    LazyLock::new(|| Mutex::new(HashMap::new()));
+
+fn track(name: &OsStr) {
+    let mut live = LIVE.lock().unwrap_or_else(|e| e.into_inner());
+    *live.entry(name.to_os_string()).or_insert(0) += 1;
+}
+
+fn untrack(name: &OsStr) {
+    let mut live = LIVE.lock().unwrap_or_else(|e| e.into_inner());
+    if let Some(count) = live.get_mut(name) {
+        *count -= 1;
+        if *count == 0 {
+            live.remove(name);
+        }
+    }
+}
+
+fn is_live(name: &OsStr) -> bool {
+    LIVE.lock()
+        .unwrap_or_else(|e| e.into_inner())
+        .contains_key(name)
+}
+
+/// 对应用临时目录中某个文件的引用，存活期间清扫与清理都会跳过该文件
+pub struct TempFileRef {
+    name: OsString,
+}
+
+impl TempFileRef {
+    fn hold(name: &OsStr) -> Self {
+        track(name);
+        Self {
+            name: name.to_os_string(),
+        }
+    }
+}
+
+impl Drop for TempFileRef {
+    fn drop(&mut self) {
+        untrack(&self.name);
+    }
+}
+
+/// 引用应用临时目录中的文件（读取已移交给前端的文件前调用）；不是托管文件时返回 None
+pub fn reference(path: &Path) -> Option<TempFileRef> {
+    if !is_managed(path) {
+        return None;
+    }
+    path.file_name().map(TempFileRef::hold)
+}
+
+/// 应用临时目录
+pub fn root() -> &'static Path {
+    ROOT.as_path()
+}
+
+fn ensure_root() -> Result<&'static Path, AppError> {
+    disk_space::check_writable(root())?;
+    std::fs::create_dir_all(root()).map_err(|e| AppError::write_failed("创建临时目录", &e))?;
+    Ok(root())
+}
+
+/// 删除临时文件，并丢弃剪贴板为它保留的内存副本
+fn remove(path: &Path) -> bool {
+    let removed = std::fs::remove_file(path).is_ok();
+    if removed {
+        clipboard::forget_handoff(&path.to_string_lossy());
+    }
+    removed
+}
+
+/// 删除目录中过期且未被引用的文件（剪贴板图片除外），返回剩余文件的总字节数
+fn sweep_dir(dir: &Path, now: SystemTime) -> u64 {
+    let mut remaining = 0;
+    let mut removed = 0;
+    for entry in std::fs::read_dir(dir).into_iter().flatten().flatten() {
+        let Ok(metadata) = entry.metadata() else {
+            continue;
+        };
+        if !metadata.is_file() {
+            continue;
+        }
+        let expired = metadata
+            .modified()
+            .ok()
+            .and_then(|modified| now.duration_since(modified).ok())
+            .is_some_and(|age| age > MAX_AGE)
+            && !is_live(&entry.file_name())
+            && !is_sweep_exempt(&entry.file_name());
+        if expired && remove(&entry.path()) {
+            removed += 1;
+        } else {
+            remaining += metadata.len();
+        }
+    }
+    if removed > 0 {
+        log::info!("[临时文件] 已清理 {} 个过期文件", removed);
+    }
+    remaining
+}
+
+/// 清理过期临时文件并校正占用统计（启动时在后台线程调用）
+pub fn sweep_expired() {
+    let remaining = sweep_dir(root(), SystemTime::now());
+    *USAGE.lock().unwrap_or_else(|e| e.into_inner()) = Some(remaining);
+}
+
+/// 删除目录中名称匹配、未被引用且在 `cutoff` 之前修改的文件，返回 (删除数, 释放字节)
+fn clear_dir(dir: &Path, matches: &dyn Fn(&OsStr) -> bool, cutoff: SystemTime) -> (usize, u64) {
+    let mut removed = 0;
+    let mut freed = 0;
+    for entry in std::fs::read_dir(dir).into_iter().flatten().flatten() {
+        let name = entry.file_name();
+        if !matches(&name) || is_live(&name) {
+            continue;
+        }
+        let Ok(metadata) = entry.metadata() else {
+            continue;
+        };
+        let settled = metadata.modified().is_ok_and(|modified| modified <= cutoff);
+        if metadata.is_file() && settled && remove(&entry.path()) {
+            removed += 1;
+            freed += metadata.len();
+        }
+    }
+    (removed, freed)
+}
+
+/// 手动清理（缓存管理）：删除名称匹配且未被引用的文件，返回 (删除数, 释放字节)
+///
+/// 已移交给前端、还在上传队列中的文件没有引用记录，`grace` 内修改过的文件一律保留
+pub fn clear(matches: impl Fn(&OsStr) -> bool, grace: Duration) -> (usize, u64) {
+    let cutoff = SystemTime::now() - grace;
+    let cleared = clear_dir(root(), &matches, cutoff);
+    // 下次占用配额时重新扫描，校正统计
+    *USAGE.lock().unwrap_or_else(|e| e.into_inner()) = None;
+    if cleared.0 > 0 {
+        log::info!(
+            "[临时文件] 手动清理 {} 个文件，释放 {} 字节",
+            cleared.0,
+            cleared.1
+        );
+    }
+    cleared
+}
+
+/// 占用 `bytes` 字节配额；超出上限时先清理过期文件，仍不足则拒绝
+fn reserve(bytes: u64) -> Result<(), AppError> {
+    let mut usage = USAGE.lock().unwrap_or_else(|e| e.into_inner());
+    let mut used = *usage.get_or_insert_with(|| sweep_dir(root(), SystemTime::now()));
+    if used.saturating_add(bytes) > MAX_TOTAL_BYTES {
+        // 重新扫描：清掉过期文件，同时校正被外部删除的文件造成的统计偏差
+        used = sweep_dir(root(), SystemTime::now());
+        *usage = Some(used);
+        if used.saturating_add(bytes) > MAX_TOTAL_BYTES {
+            return Err(AppError::storage(format!(
+                "临时文件占用已达上限（{} MB），请稍后重试",
+                MAX_TOTAL_BYTES / 1024 / 1024
+            ))
+            .with_code(ErrorCode::TempQuota));
+        }
+    }
+    *usage = Some(used + bytes);
+    Ok(())
+}
+
+fn unreserve(bytes: u64) {
+    let mut usage = USAGE.lock().unwrap_or_else(|e| e.into_inner());
+    if let Some(used) = usage.as_mut() {
+        *used = used.saturating_sub(bytes);
+    }
+}
+
+/// 随机 UUID 文件名：时间戳 + 序号只在单个进程内唯一，CLI、浏览器扩展宿主等多个进程
+/// 同时写入应用临时目录时仍会撞名，后写者覆盖前者，正在上传的进程读到错的字节
+fn unique_name(prefix: &str, ext: &str) -> String {
+    format!("{}{}.{}", prefix, uuid::Uuid::new_v4().simple(), ext)
+}
+
+/// 应用临时目录中的一个文件，drop 时删除并归还配额
+pub struct TempFileGuard {
+    path: PathBuf,
+    reserved: u64,
+    armed: bool,
+    live: Option<TempFileRef>,
+}
+
+impl TempFileGuard {
+    /// 分配一个新的临时文件路径（尚未创建文件）
+    pub fn new(prefix: &str, ext: &str) -> Result<Self, AppError> {
+        let dir = ensure_root()?;
+        let name = unique_name(prefix, ext);
+        Ok(Self {
+            path: dir.join(&name),
+            reserved: 0,
+            armed: true,
+            live: Some(TempFileRef::hold(OsStr::new(&name))),
+        })
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    pub fn path_string(&self) -> String {
+        self.path.to_string_lossy().to_string()
+    }
+
+    /// 把占用的配额调整为 `bytes`（写入前按预计大小、写完后按实际大小各调用一次）
+    ///
+    /// 增大时同时检查临时目录所在卷的剩余空间
+    pub fn resize(&mut self, bytes: u64) -> Result<(), AppError> {
+        if bytes > self.reserved {
+            disk_space::check_space(root(), bytes - self.reserved)?;
+            reserve(bytes - self.reserved)?;
+        } else {
+            unreserve(self.reserved - bytes);
+        }
+        self.reserved = bytes;
+        Ok(())
+    }
+
+    /// 更换扩展名（下载完成、确定真实格式后调用）
+    pub async fn set_extension(&mut self, ext: &str) -> Result<(), AppError> {
+        let target = self.path.with_extension(ext);
+        tokio::fs::rename(&self.path, &target)
+            .await
+            .map_err(|e| AppError::write_failed("写入文件", &e))?;
+        self.live = target.file_name().map(TempFileRef::hold);
+        self.path = target;
+        Ok(())
+    }
+
+    /// 移交文件所有权：不再随 drop 删除，由 `release` 或过期清扫回收
+    ///
+    /// 移交后不再登记引用，之后读取该文件需通过 `reference` 重新登记
+    pub fn keep(mut self) -> PathBuf {
+        self.armed = false;
+        self.live = None;
+        std::mem::take(&mut self.path)
+    }
+}
+
+impl Drop for TempFileGuard {
+    fn drop(&mut self) {
+        if !self.armed {
+            return;
+        }
+        match std::fs::remove_file(&self.path) {
+            Ok(()) => {}
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => {}
+            Err(e) => log::warn!(
+                "[临时文件] 删除失败 {}: {}",
+                safe_path(&self.path.to_string_lossy()),
+                e
+            ),
+        }
+        unreserve(self.reserved);
+    }
+}
+
+/// 把 `bytes` 写入新的临时文件
+pub async fn write(prefix: &str, ext: &str, bytes: &[u8]) -> Result<TempFileGuard, AppError> {
+    let mut guard = TempFileGuard::new(prefix, ext)?;
+    guard.resize(bytes.len() as u64)?;
+    tokio::fs::write(guard.path(), bytes)
+        .await
+        .map_err(|e| AppError::write_failed("写入临时文件", &e))?;
+    Ok(guard)
+}
+
+/// 路径是否位于应用临时目录中（只认直接子文件）
+pub fn is_managed(path: &Path) -> bool {
+    if path.file_name().is_none() {
+        return false;
+    }
+    let Some(parent) = path.parent() else {
+        return false;
+    };
+    let Ok(parent) = path_utils::canonicalize(parent) else {
+        return false;
+    };
+    let Ok(dir) = path_utils::canonicalize(root()) else {
+        return false;
+    };
+    parent == dir
+}
+
+/// 删除已移交给前端的临时文件；文件已不存在或仍被其他任务引用时返回 false
+///
+/// 只允许删除应用临时目录中的文件，避免前端传入任意路径造成误删
+pub fn release(path: &Path) -> Result<bool, AppError> {
+    if !is_managed(path) {
+        return Err(AppError::validation("只允许清理 PicNexus 创建的临时文件"));
+    }
+    if path.file_name().is_some_and(is_live) {
+        log::debug!(
+            "[临时文件] 文件仍被其他任务使用，跳过清理: {}",
+            safe_path(&path.to_string_lossy())
+        );
+        return Ok(false);
+    }
+    let Ok(metadata) = std::fs::metadata(path) else {
+        return Ok(false);
+    };
+    std::fs::remove_file(path)
+        .map_err(|e| AppError::file_io(format!("删除临时文件失败: {}", e)))?;
+    clipboard::forget_handoff(&path.to_string_lossy());
+    unreserve(metadata.len());
+    Ok(true)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn unique_names_keep_prefix_and_extension() {
+        let a = unique_name("picnexus_url_", "png");
+        let b = unique_name("picnexus_url_", "png");
+        assert_ne!(a, b);
+        assert!(a.starts_with("picnexus_url_"));
+        assert!(a.ends_with(".png"));
+    }
+
+    #[tokio::test]
+    async fn guard_removes_file_on_drop() {
+        let guard = write("tempfiles_test_drop_", "bin", b"temp").await.unwrap();
+        let path = guard.path().to_path_buf();
+        assert!(path.exists());
+        assert!(is_managed(&path));
+
+        drop(guard);
+        assert!(!path.exists());
+    }
+
+    #[tokio::test]
+    async fn kept_file_survives_until_released() {
+        let guard = write("tempfiles_test_keep_", "bin", b"temp").await.unwrap();
+        let path = guard.keep();
+        assert!(path.exists());
+
+        assert!(release(&path).unwrap());
+        assert!(!path.exists());
+        assert!(!release(&path).unwrap());
+    }
+
+    #[test]
+    fn release_rejects_paths_outside_temp_dir() {
+        let path = std::env::temp_dir().join("tempfiles_test_unmanaged.bin");
+        std::fs::write(&path, b"keep").unwrap();
+
+        assert!(release(&path).is_err());
+        assert!(path.exists());
+        let _ = std::fs::remove_file(path);
+    }
+
+    #[test]
+    fn rejects_reservations_beyond_quota() {
+        let mut guard = TempFileGuard::new("tempfiles_test_quota_", "bin").unwrap();
+        assert!(guard.resize(MAX_TOTAL_BYTES + 1).is_err());
+        assert_eq!(guard.reserved, 0);
+    }
+
+    #[test]
+    fn sweep_skips_referenced_files() {
+        let dir = std::env::temp_dir().join(format!("picnexus_live_test_{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        std::fs::write(dir.join("tempfiles_test_busy.bin"), b"123").unwrap();
+        std::fs::write(dir.join("tempfiles_test_idle.bin"), b"45").unwrap();
+
+        let busy = TempFileRef::hold(OsStr::new("tempfiles_test_busy.bin"));
+        let later = SystemTime::now() + Duration::from_secs(7200);
+        assert_eq!(sweep_dir(&dir, later), 3);
+        assert!(dir.join("tempfiles_test_busy.bin").exists());
+        assert!(!dir.join("tempfiles_test_idle.bin").exists());
+
+        drop(busy);
+        assert_eq!(sweep_dir(&dir, later), 0);
+        let _ = std::fs::remove_dir_all(dir);
+    }
+
+    #[test]
+    fn sweep_keeps_clipboard_images_handed_to_frontend() {
+        let dir = std::env::temp_dir().join(format!("picnexus_clip_test_{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let clipboard = format!("{}tempfiles_test.png", CLIPBOARD_TEMP_PREFIX);
+        std::fs::write(dir.join(&clipboard), b"123").unwrap();
+        std::fs::write(dir.join("picnexus_url_tempfiles_test.png"), b"45").unwrap();
+
+        let later = SystemTime::now() + Duration::from_secs(7 * 24 * 3600);
+        assert_eq!(sweep_dir(&dir, later), 3);
+        assert!(dir.join(&clipboard).exists());
+        assert!(!dir.join("picnexus_url_tempfiles_test.png").exists());
+        let _ = std::fs::remove_dir_all(dir);
+    }
+
+    #[tokio::test]
+    async fn release_skips_files_still_referenced() {
+        let guard = write("tempfiles_test_ref_", "bin", b"temp").await.unwrap();
+        let path = guard.keep();
+        let reading = reference(&path).expect("托管文件");
+
+        assert!(!release(&path).unwrap());
+        assert!(path.exists());
+
+        drop(reading);
+        assert!(release(&path).unwrap());
+    }
+
+    #[test]
+    fn clear_skips_referenced_unmatched_and_recent_files() {
+        let dir = std::env::temp_dir().join(format!("picnexus_clear_test_{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        for name in ["dl_a.bin", "dl_busy.bin", "other.bin"] {
+            std::fs::write(dir.join(name), b"123").unwrap();
+        }
+        let busy = TempFileRef::hold(OsStr::new("dl_busy.bin"));
+        let is_download = |name: &OsStr| name.to_string_lossy().starts_with("dl_");
+
+        // 截止时间早于文件修改时间：刚写入的文件全部保留
+        let earlier = SystemTime::now() - Duration::from_secs(3600);
+        assert_eq!(clear_dir(&dir, &is_download, earlier), (0, 0));
+
+        let later = SystemTime::now() + Duration::from_secs(60);
+        assert_eq!(clear_dir(&dir, &is_download, later), (1, 3));
+        assert!(!dir.join("dl_a.bin").exists());
+        assert!(dir.join("dl_busy.bin").exists());
+        assert!(dir.join("other.bin").exists());
+
+        drop(busy);
+        assert_eq!(clear_dir(&dir, &is_download, later), (1, 3));
+        let _ = std::fs::remove_dir_all(dir);
+    }
+
+    #[test]
+    fn sweep_keeps_fresh_files() {
+        let dir = std::env::temp_dir().join(format!("picnexus_sweep_test_{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        std::fs::write(dir.join("fresh.bin"), b"12345").unwrap();
+
+        assert_eq!(sweep_dir(&dir, SystemTime::now()), 5);
+        // 两小时后再扫描，文件已过期
+        let later = SystemTime::now() + Duration::from_secs(7200);
+        assert_eq!(sweep_dir(&dir, later), 0);
+        assert!(!dir.join("fresh.bin").exists());
+        let _ = std::fs::remove_dir_all(dir);
+    }
+}
@@ -0,0 +1,285 @@
+// src-tauri/src/commands/thumbnail_cache.rs
+// 磁盘缩略图缓存：预览图按 (路径, 长边, 大小, mtime) 落盘，重启后历史图库仍可直接命中
+//
+// 目录为应用缓存目录下的 thumbnails（便携版在 data/cache/thumbnails），一个缩略图一个文件；
+// 内存中维护 LRU 索引（文件名 → 字节数），总量超过上限时从最久未访问的条目开始删除。
+// 索引在首次访问时按文件 mtime 从磁盘重建，命中时刷新 mtime，让访问顺序跨进程保留。
+
+use std::collections::{BTreeMap, HashMap};
+use std::path::{Path, PathBuf};
+use std::sync::{LazyLock, Mutex, OnceLock};
+use std::time::SystemTime;
+
+use sha2::{Digest, Sha256};
+
+use super::file_cache::FileStamp;
+use crate::error::AppError;
+
+/// 缓存总量上限
+const MAX_CACHE_BYTES: u64 = 500 * 1024 * 1024;
+
+static CACHE_DIR: OnceLock<PathBuf> = OnceLock::new();
+
+static INDEX: LazyLock<Mutex<Option<LruIndex>>> = LazyLock::new(|| Mutex::new(None));
+
+/// 按字节数限额的 LRU 索引
+struct LruIndex {
+    max_bytes: u64,
+    total_bytes: u64,
+    next_tick: u64,
+    /// 文件名 → (字节数, 最近访问序号)
+    entries: HashMap<String, (u64, u64)>,
+    /// 访问序号 → 文件名，最小的即最久未访问
+    order: BTreeMap<u64, String>,
+}
+
+impl LruIndex {
+    fn new(max_bytes: u64) -> Self {
+        Self {
+            max_bytes,
+            total_bytes: 0,
+            next_tick: 0,
+            entries: HashMap::new(),
+            order: BTreeMap::new(),
+        }
+    }
+
+    fn bump(&mut self) -> u64 {
+        self.next_tick += 1;
+        self.next_tick
+    }
+
+    /// 标记为最近访问；不存在时返回 false
+    fn touch(&mut self, name: &str) -> bool {
+        let tick = self.bump();
+        let Some((_, last)) = self.entries.get_mut(name) else {
+            return false;
+        };
+        let previous = std::mem::replace(last, tick);
+        self.order.remove(&previous);
+        self.order.insert(tick, name.to_string());
+        true
+    }
+
+    /// 记录新条目，返回为腾出空间需要删除的文件名（最新插入的条目不会被淘汰）
+    fn insert(&mut self, name: String, size: u64) -> Vec<String> {
+        self.remove(&name);
+        let tick = self.bump();
+        self.entries.insert(name.clone(), (size, tick));
+        self.order.insert(tick, name.clone());
+        self.total_bytes += size;
+
+        let mut evicted = Vec::new();
+        while self.total_bytes > self.max_bytes && self.entries.len() > 1 {
+            let Some((_, oldest)) = self.order.pop_first() else {
+                break;
+            };
+            if let Some((size, _)) = self.entries.remove(&oldest) {
+                self.total_bytes = self.total_bytes.saturating_sub(size);
+            }
+            evicted.push(oldest);
+        }
+        evicted
+    }
+
+    fn remove(&mut self, name: &str) {
+        if let Some((size, tick)) = self.entries.remove(name) {
+            self.order.remove(&tick);
+            self.total_bytes = self.total_bytes.saturating_sub(size);
+        }
+    }
+}
+
+/// 设置缓存目录（应用启动时调用一次）
+pub fn init(dir: PathBuf) {
+    let _ = CACHE_DIR.set(dir);
+}
+
+/// 缩略图缓存目录；init 之前（或获取缓存目录失败时）为 None
+pub(crate) fn cache_dir() -> Option<&'static Path> {
+    CACHE_DIR.get().map(PathBuf::as_path)
+}
+
+/// 从磁盘重建索引：按 mtime 由旧到新排序
+fn scan_dir(dir: &Path) -> LruIndex {
+    let mut files: Vec<(SystemTime, String, u64)> = std::fs::read_dir(dir)
+        .into_iter()
+        .flatten()
+        .flatten()
+        .filter_map(|entry| {
+            let metadata = entry.metadata().ok().filter(|m| m.is_file())?;
+            // 写入中途退出残留的临时文件不计入索引
+            let name = entry
+                .file_name()
+                .into_string()
+                .ok()
+                .filter(|n| !n.ends_with(".tmp"))?;
+            let modified = metadata.modified().unwrap_or(SystemTime::UNIX_EPOCH);
+            Some((modified, name, metadata.len()))
+        })
+        .collect();
+    files.sort();
+
+    let mut index = LruIndex::new(MAX_CACHE_BYTES);
+    let mut stale = Vec::new();
+    for (_, name, size) in files {
+        stale.extend(index.insert(name, size));
+    }
+    for name in stale {
+        let _ = std::fs::remove_file(dir.join(name));
+    }
+    index
+}
+
+fn with_index<T>(dir: &Path, f: impl FnOnce(&mut LruIndex) -> T) -> T {
+    let mut guard = INDEX.lock().unwrap_or_else(|e| e.into_inner());
+    f(guard.get_or_insert_with(|| scan_dir(dir)))
+}
+
+/// 缓存文件名：源文件路径、长边与指纹的 SHA-256，扩展名与预览 MIME 对应
+fn entry_name(path: &Path, max_side: u32, stamp: FileStamp, mime: &str) -> String {
+    let modified = stamp
+        .modified
+        .and_then(|t| t.duration_since(SystemTime::UNIX_EPOCH).ok())
+        .map_or(0, |d| d.as_nanos());
+    let mut hasher = Sha256::new();
+    hasher.update(path.to_string_lossy().as_bytes());
+    hasher.update(format!("|{}|{}|{}", max_side, stamp.size, modified).as_bytes());
+    let ext = if mime == "image/png" { "png" } else { "jpg" };
+    format!("{}.{}", hex::encode(hasher.finalize()), ext)
+}
+
+/// 读取缓存的缩略图（阻塞 I/O，调用方放到 spawn_blocking 中）
+pub(crate) fn load(path: &Path, max_side: u32, stamp: FileStamp, mime: &str) -> Option<Vec<u8>> {
+    let dir = cache_dir()?;
+    let name = entry_name(path, max_side, stamp, mime);
+    if !with_index(dir, |index| index.touch(&name)) {
+        return None;
+    }
+
+    let file_path = dir.join(&name);
+    match std::fs::read(&file_path) {
+        Ok(bytes) => {
+            // 刷新 mtime，下次启动重建索引时保留访问顺序
+            if let Ok(file) = std::fs::File::options().write(true).open(&file_path) {
+                let _ = file.set_modified(SystemTime::now());
+            }
+            Some(bytes)
+        }
+        Err(_) => {
+            // 文件被外部删除，同步移出索引
+            with_index(dir, |index| index.remove(&name));
+            None
+        }
+    }
+}
+
+/// 写入缩略图并按上限淘汰旧条目（阻塞 I/O，调用方放到 spawn_blocking 中）
+pub(crate) fn store(path: &Path, max_side: u32, stamp: FileStamp, mime: &str, bytes: &[u8]) {
+    let Some(dir) = cache_dir() else {
+        return;
+    };
+    if let Err(e) = std::fs::create_dir_all(dir) {
+        log::warn!("[缩略图缓存] 创建目录失败: {}", e);
+        return;
+    }
+
+    let name = entry_name(path, max_side, stamp, mime);
+    // 先写临时文件再改名，避免并发读到半个文件
+    let tmp_path = dir.join(format!("{}.tmp", name));
+    let written =
+        std::fs::write(&tmp_path, bytes).and_then(|_| std::fs::rename(&tmp_path, dir.join(&name)));
+    if let Err(e) = written {
+        log::warn!("[缩略图缓存] 写入失败: {}", e);
+        let _ = std::fs::remove_file(&tmp_path);
+        return;
+    }
+
+    let evicted = with_index(dir, |index| index.insert(name, bytes.len() as u64));
+    for name in evicted {
+        let _ = std::fs::remove_file(dir.join(name));
+    }
+}
+
+/// 清空缩略图缓存（磁盘与内存中的预览），返回释放的字节数
+#[tauri::command]
+pub async fn clear_thumbnail_cache() -> Result<u64, AppError> {
+    clear().await
+}
+
+/// clear_thumbnail_cache 与缓存管理（clear_cache）共用
+pub(crate) async fn clear() -> Result<u64, AppError> {
+    super::image_compress::clear_preview_cache();
+    let Some(dir) = cache_dir() else {
+        return Ok(0);
+    };
+
+    let freed = tokio::task::spawn_blocking(move || {
+        let mut guard = INDEX.lock().unwrap_or_else(|e| e.into_inner());
+        let mut freed = 0;
+        for entry in std::fs::read_dir(dir).into_iter().flatten().flatten() {
+            let size = entry.metadata().map(|m| m.len()).unwrap_or(0);
+            if std::fs::remove_file(entry.path()).is_ok() {
+                freed += size;
+            }
+        }
+        *guard = Some(LruIndex::new(MAX_CACHE_BYTES));
+        freed
+    })
+    .await
+    .map_err(|e| AppError::file_io(format!("清理缩略图缓存失败: {}", e)))?;
+
+    log::info!("[缩略图缓存] 已清理 {} 字节", freed);
+    Ok(freed)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn evicts_least_recently_used_beyond_limit() {
+        let mut index = LruIndex::new(100);
+        assert!(index.insert("a".into(), 40).is_empty());
+        assert!(index.insert("b".into(), 40).is_empty());
+        // 访问 a 后，b 成为最久未访问
+        assert!(index.touch("a"));
+
+        let evicted = index.insert("c".into(), 40);
+        assert_eq!(evicted, vec!["b".to_string()]);
+        assert_eq!(index.total_bytes, 80);
+        assert!(!index.touch("b"));
+    }
+
+    #[test]
+    fn oversized_entry_is_kept_alone() {
+        let mut index = LruIndex::new(100);
+        index.insert("a".into(), 30);
+        let evicted = index.insert("big".into(), 500);
+        assert_eq!(evicted, vec!["a".to_string()]);
+        assert_eq!(index.entries.len(), 1);
+    }
+
+    #[test]
+    fn reinserting_replaces_size() {
+        let mut index = LruIndex::new(100);
+        index.insert("a".into(), 60);
+        index.insert("a".into(), 20);
+        assert_eq!(index.total_bytes, 20);
+        assert_eq!(index.order.len(), 1);
+    }
+
+    #[test]
+    fn entry_name_changes_with_stamp() {
+        let path = Path::new("/tmp/a.png");
+        let stamp = FileStamp {
+            size: 10,
+            modified: Some(SystemTime::UNIX_EPOCH),
+        };
+        let changed = FileStamp { size: 11, ..stamp };
+        let name = entry_name(path, 256, stamp, "image/png");
+        assert!(name.ends_with(".png"));
+        assert_ne!(name, entry_name(path, 256, changed, "image/png"));
+        assert_ne!(name, entry_name(path, 512, stamp, "image/png"));
+    }
+}
@@ -0,0 +1,220 @@
+// src-tauri/src/commands/thumbnail_prefetch.rs
+// 历史图库缩略图预取：按当前滚动窗口提前生成下一页的本地缩略图，快速滚动时不再逐张弹出
+//
+// 工作流：
+//   前端翻页 / 滚动停顿时 prefetch_thumbnails(ids, maxSide) -> 只读查询这些记录的本地原图路径
+//   -> 后台逐张 render_preview（写入内存与磁盘两级缩略图缓存）
+//   -> 每张完成后发送 thumbnail://ready { generation, id, path, maxSide, ok }
+//   -> 前端据此用 picnexus-file 协议加载，请求直接命中缓存
+//
+// 新的预取请求会取代尚未完成的旧请求（滚动很快时旧窗口已经看不到了），传空列表即取消。
+// 原图已删除或记录没有本地路径时跳过，不发事件；这类记录仍使用图床的远程缩略图。
+
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU64, Ordering};
+
+use futures::stream::{self, StreamExt};
+use serde::Serialize;
+use sqlx::sqlite::SqliteConnectOptions;
+use sqlx::{ConnectOptions, Row};
+use tauri::Emitter;
+
+use super::file_protocol::{MAX_PREVIEW_SIDE, MIN_PREVIEW_SIDE};
+use crate::error::AppError;
+
+pub const THUMBNAIL_READY_EVENT: &str = "thumbnail://ready";
+
+/// 单次预取的记录数上限（约两页瀑布流）
+const MAX_PREFETCH_IDS: usize = 200;
+/// 同时生成的缩略图数（解码占 CPU 与内存，另受内存预算限制）
+const PREFETCH_CONCURRENCY: usize = 2;
+/// 未指定长边时使用的尺寸（瀑布流卡片宽度的两倍，兼顾高分屏）
+const DEFAULT_MAX_SIDE: u32 = 400;
+
+/// 当前有效的预取批次；每次调用加一，旧批次在处理下一张前发现已过期即停止
+static GENERATION: AtomicU64 = AtomicU64::new(0);
+
+fn is_current(generation: u64) -> bool {
+    GENERATION.load(Ordering::SeqCst) == generation
+}
+
+/// 单张缩略图就绪事件
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ThumbnailReady {
+    pub generation: u64,
+    pub id: String,
+    pub path: String,
+    pub max_side: u32,
+    /// false 表示生成失败（图片损坏、超出像素上限等），前端应回退到远程缩略图
+    pub ok: bool,
+}
+
+/// prefetch_thumbnails 的返回值
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PrefetchTicket {
+    pub generation: u64,
+    /// 有本地原图、已加入后台队列的记录数
+    pub queued: usize,
+}
+
+/// 查询记录的本地原图路径，按传入 ID 的顺序返回（重复 ID 与无本地路径的记录被跳过）
+async fn load_paths(db_path: &Path, ids: &[String]) -> Result<Vec<(String, PathBuf)>, AppError> {
+    let mut conn = SqliteConnectOptions::new()
+        .filename(db_path)
+        .read_only(true)
+        .connect()
+        .await
+        .map_err(|e| AppError::storage(format!("打开历史记录数据库失败: {}", e)))?;
+
+    let placeholders = vec!["?"; ids.len()].join(",");
+    let sql = format!(
+        "SELECT id, file_path FROM history_items WHERE id IN ({})",
+        placeholders
+    );
+    let mut query = sqlx::query(&sql);
+    for id in ids {
+        query = query.bind(id);
+    }
+    let rows = query
+        .fetch_all(&mut conn)
+        .await
+        .map_err(|e| AppError::storage(format!("查询历史记录失败: {}", e)))?;
+
+    let found: HashMap<String, String> = rows
+        .iter()
+        .filter_map(|row| {
+            let id: String = row.try_get("id").ok()?;
+            let path: Option<String> = row.try_get("file_path").ok().flatten();
+            Some((id, path?))
+        })
+        .collect();
+    Ok(order_by_ids(ids, found))
+}
+
+fn order_by_ids(ids: &[String], mut found: HashMap<String, String>) -> Vec<(String, PathBuf)> {
+    ids.iter()
+        .filter_map(|id| {
+            let path = found.remove(id)?;
+            (!path.is_empty()).then(|| (id.clone(), PathBuf::from(path)))
+        })
+        .collect()
+}
+
+/// 为即将进入视口的历史记录预先生成本地缩略图，逐张发送 `thumbnail://ready` 事件
+///
+/// 立即返回本批次编号；新的调用会取代尚未完成的旧批次，传空列表即取消
+#[tauri::command]
+pub async fn prefetch_thumbnails(
+    app: tauri::AppHandle,
+    ids: Vec<String>,
+    max_side: Option<u32>,
+) -> Result<PrefetchTicket, AppError> {
+    let generation = GENERATION.fetch_add(1, Ordering::SeqCst) + 1;
+    if ids.is_empty() {
+        return Ok(PrefetchTicket {
+            generation,
+            queued: 0,
+        });
+    }
+    if ids.len() > MAX_PREFETCH_IDS {
+        return Err(AppError::validation(format!(
+            "一次最多预取 {} 条记录",
+            MAX_PREFETCH_IDS
+        )));
+    }
+    let max_side = max_side
+        .unwrap_or(DEFAULT_MAX_SIDE)
+        .clamp(MIN_PREVIEW_SIDE, MAX_PREVIEW_SIDE);
+
+    let db_path = crate::portable::history_db_path(&app)?;
+    if !db_path.exists() {
+        return Ok(PrefetchTicket {
+            generation,
+            queued: 0,
+        });
+    }
+    let entries = load_paths(&db_path, &ids).await?;
+    let queued = entries.len();
+    log::debug!(
+        "[缩略图预取] 批次 {}：{} 条记录中 {} 条有本地原图",
+        generation,
+        ids.len(),
+        queued
+    );
+
+    tauri::async_runtime::spawn(async move {
+        let app = &app;
+        stream::iter(entries)
+            .for_each_concurrent(PREFETCH_CONCURRENCY, |(id, path)| async move {
+                if !is_current(generation) {
+                    return;
+                }
+                if !tokio::fs::metadata(&path).await.is_ok_and(|m| m.is_file()) {
+                    return;
+                }
+                let ok = match super::image_compress::render_preview(path.clone(), max_side).await {
+                    Ok(_) => true,
+                    Err(e) => {
+                        log::debug!("[缩略图预取] 生成失败 {}: {}", id, e);
+                        false
+                    }
+                };
+                // 生成期间被新批次取代时不再通知，缓存照常保留
+                if is_current(generation) {
+                    let _ = app.emit(
+                        THUMBNAIL_READY_EVENT,
+                        ThumbnailReady {
+                            generation,
+                            id,
+                            path: path.to_string_lossy().to_string(),
+                            max_side,
+                            ok,
+                        },
+                    );
+                }
+            })
+            .await;
+    });
+
+    Ok(PrefetchTicket { generation, queued })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn order_by_ids_keeps_request_order_and_skips_missing() {
+        let ids: Vec<String> = ["c", "a", "missing", "b", "a", "empty"]
+            .iter()
+            .map(|s| s.to_string())
+            .collect();
+        let found: HashMap<String, String> = [
+            ("a", "/img/a.png"),
+            ("b", "/img/b.jpg"),
+            ("c", "/img/c.webp"),
+            ("empty", ""),
+        ]
+        .iter()
+        .map(|(id, path)| (id.to_string(), path.to_string()))
+        .collect();
+
+        let ordered: Vec<String> = order_by_ids(&ids, found)
+            .into_iter()
+            .map(|(id, _)| id)
+            .collect();
+        assert_eq!(ordered, ["c", "a", "b"]);
+    }
+
+    #[test]
+    fn newer_generation_supersedes_older() {
+        let first = GENERATION.fetch_add(1, Ordering::SeqCst) + 1;
+        assert!(is_current(first));
+        let second = GENERATION.fetch_add(1, Ordering::SeqCst) + 1;
+        assert!(!is_current(first));
+        assert!(is_current(second));
+    }
+}
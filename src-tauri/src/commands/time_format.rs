@@ -0,0 +1,280 @@
+// src-tauri/src/commands/time_format.rs
+// 时间显示：按用户设置的语言区域与时区格式化时间戳
+//
+// 历史记录、队列、同步日志一律保存 UTC 毫秒时间戳（与时区无关），只在显示时换算。
+// 以前部分导出内容直接写本地时间，换电脑或出差后与其他机器同步的记录对不上；
+// 现在统一由这里按设置的时区格式化。
+//
+// 时区支持：system（跟随系统）、UTC、固定偏移（+08:00 / UTC-5 / +0530）；
+// 不依赖时区数据库，因此不支持 Asia/Shanghai 这类 IANA 名称。
+
+use chrono::{DateTime, FixedOffset, Local, SecondsFormat, Utc};
+use serde::Deserialize;
+
+use crate::error::AppError;
+
+/// 一次最多格式化的时间戳数量（列表页按页调用）
+const MAX_BATCH: usize = 5000;
+
+/// 输出样式
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum TimeStyle {
+    /// 仅日期
+    Date,
+    /// 仅时间（时:分）
+    Time,
+    /// 日期 + 时:分
+    #[default]
+    DateTime,
+    /// 日期 + 时:分:秒 + 时区偏移
+    Full,
+    /// RFC 3339（带偏移），用于导出文件，不受语言区域影响
+    Iso,
+}
+
+/// 格式化选项，缺省字段跟随系统
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TimeFormatOptions {
+    /// 语言区域（如 zh-CN、en-US、en-GB），缺省为 zh-CN
+    pub locale: Option<String>,
+    /// 时区：system / UTC / ±HH:MM，缺省为 system
+    pub time_zone: Option<String>,
+    #[serde(default)]
+    pub style: TimeStyle,
+}
+
+/// 解析后的时区设置
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Zone {
+    System,
+    Fixed(FixedOffset),
+}
+
+/// 解析 "+08:00"、"-0530"、"+8" 形式的偏移（不含 UTC 前缀）
+fn parse_offset(value: &str) -> Option<FixedOffset> {
+    let (sign, rest) = match value.as_bytes().first()? {
+        b'+' => (1, &value[1..]),
+        b'-' => (-1, &value[1..]),
+        _ => return None,
+    };
+    let (hours, minutes) = match rest.split_once(':') {
+        Some((h, m)) => (h, m),
+        None if rest.len() == 4 => rest.split_at(2),
+        None => (rest, "0"),
+    };
+    if hours.is_empty() || hours.len() > 2 || minutes.len() > 2 {
+        return None;
+    }
+    let hours: i32 = hours.parse().ok()?;
+    let minutes: i32 = minutes.parse().ok()?;
+    if hours > 14 || minutes >= 60 {
+        return None;
+    }
+    FixedOffset::east_opt(sign * (hours * 3600 + minutes * 60))
+}
+
+fn parse_zone(value: Option<&str>) -> Result<Zone, AppError> {
+    let value = value.map(str::trim).unwrap_or_default();
+    if value.is_empty()
+        || value.eq_ignore_ascii_case("system")
+        || value.eq_ignore_ascii_case("local")
+    {
+        return Ok(Zone::System);
+    }
+    if value.eq_ignore_ascii_case("utc") || value.eq_ignore_ascii_case("gmt") || value == "Z" {
+        return Ok(Zone::Fixed(FixedOffset::east_opt(0).unwrap()));
+    }
+    let offset = value
+        .get(..3)
+        .filter(|p| p.eq_ignore_ascii_case("utc") || p.eq_ignore_ascii_case("gmt"))
+        .map_or(value, |_| &value[3..]);
+    parse_offset(offset)
+        .map(Zone::Fixed)
+        .ok_or_else(|| AppError::validation(format!("不支持的时区: {}", value)))
+}
+
+/// 各语言区域的日期 / 时间格式（strftime）
+struct LocalePattern {
+    date: &'static str,
+    time: &'static str,
+    time_seconds: &'static str,
+}
+
+const PATTERN_ZH: LocalePattern = LocalePattern {
+    date: "%Y/%m/%d",
+    time: "%H:%M",
+    time_seconds: "%H:%M:%S",
+};
+
+const PATTERN_EN_US: LocalePattern = LocalePattern {
+    date: "%m/%d/%Y",
+    time: "%I:%M %p",
+    time_seconds: "%I:%M:%S %p",
+};
+
+const PATTERN_EN_GB: LocalePattern = LocalePattern {
+    date: "%d/%m/%Y",
+    time: "%H:%M",
+    time_seconds: "%H:%M:%S",
+};
+
+/// 其他语言区域使用 ISO 风格，避免猜错日月顺序
+const PATTERN_ISO: LocalePattern = LocalePattern {
+    date: "%Y-%m-%d",
+    time: "%H:%M",
+    time_seconds: "%H:%M:%S",
+};
+
+fn pattern_for(locale: Option<&str>) -> &'static LocalePattern {
+    let locale = locale.map(str::trim).unwrap_or_default().replace('_', "-");
+    let mut parts = locale.split('-');
+    let language = parts.next().unwrap_or_default().to_ascii_lowercase();
+    let region = parts.next().unwrap_or_default().to_ascii_uppercase();
+    match (language.as_str(), region.as_str()) {
+        ("" | "zh", _) => &PATTERN_ZH,
+        ("en", "" | "US") => &PATTERN_EN_US,
+        ("en", _) => &PATTERN_EN_GB,
+        _ => &PATTERN_ISO,
+    }
+}
+
+/// 偏移显示为 UTC+08:00 / UTC
+fn offset_label(offset: &FixedOffset) -> String {
+    let secs = offset.local_minus_utc();
+    if secs == 0 {
+        return "UTC".to_string();
+    }
+    let sign = if secs < 0 { '-' } else { '+' };
+    let secs = secs.abs();
+    format!("UTC{}{:02}:{:02}", sign, secs / 3600, secs % 3600 / 60)
+}
+
+fn format_one(
+    timestamp_ms: i64,
+    zone: Zone,
+    pattern: &LocalePattern,
+    style: TimeStyle,
+) -> Result<String, AppError> {
+    let utc = DateTime::<Utc>::from_timestamp_millis(timestamp_ms)
+        .ok_or_else(|| AppError::validation(format!("无效的时间戳: {}", timestamp_ms)))?;
+    let dt = match zone {
+        Zone::System => utc.with_timezone(&Local).fixed_offset(),
+        Zone::Fixed(offset) => utc.with_timezone(&offset),
+    };
+    Ok(match style {
+        TimeStyle::Date => dt.format(pattern.date).to_string(),
+        TimeStyle::Time => dt.format(pattern.time).to_string(),
+        TimeStyle::DateTime => format!("{} {}", dt.format(pattern.date), dt.format(pattern.time)),
+        TimeStyle::Full => format!(
+            "{} {} ({})",
+            dt.format(pattern.date),
+            dt.format(pattern.time_seconds),
+            offset_label(dt.offset())
+        ),
+        TimeStyle::Iso => dt.to_rfc3339_opts(SecondsFormat::Secs, true),
+    })
+}
+
+/// 当前 UTC 时间（RFC 3339），写入导出文件 / 报告时使用，避免落盘本地时间
+pub(crate) fn now_utc_rfc3339() -> String {
+    Utc::now().to_rfc3339_opts(SecondsFormat::Secs, true)
+}
+
+/// 按语言区域与时区批量格式化 UTC 毫秒时间戳，返回与输入一一对应的字符串
+#[tauri::command]
+pub fn format_timestamps(
+    timestamps: Vec<i64>,
+    options: Option<TimeFormatOptions>,
+) -> Result<Vec<String>, AppError> {
+    if timestamps.len() > MAX_BATCH {
+        return Err(AppError::validation(format!(
+            "一次最多格式化 {} 个时间戳",
+            MAX_BATCH
+        )));
+    }
+    let options = options.unwrap_or_default();
+    let zone = parse_zone(options.time_zone.as_deref())?;
+    let pattern = pattern_for(options.locale.as_deref());
+    timestamps
+        .into_iter()
+        .map(|ts| format_one(ts, zone, pattern, options.style))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // 2026-03-05 01:02:03 UTC
+    const TS: i64 = 1_772_672_523_000;
+
+    fn format(ts: i64, locale: &str, zone: &str, style: TimeStyle) -> String {
+        format_timestamps(
+            vec![ts],
+            Some(TimeFormatOptions {
+                locale: Some(locale.to_string()),
+                time_zone: Some(zone.to_string()),
+                style,
+            }),
+        )
+        .unwrap()
+        .remove(0)
+    }
+
+    #[test]
+    fn parses_time_zone_settings() {
+        let east8 = Zone::Fixed(FixedOffset::east_opt(8 * 3600).unwrap());
+        assert_eq!(parse_zone(None).unwrap(), Zone::System);
+        assert_eq!(parse_zone(Some("system")).unwrap(), Zone::System);
+        assert_eq!(
+            parse_zone(Some("utc")).unwrap(),
+            Zone::Fixed(FixedOffset::east_opt(0).unwrap())
+        );
+        assert_eq!(parse_zone(Some("+08:00")).unwrap(), east8);
+        assert_eq!(parse_zone(Some("UTC+8")).unwrap(), east8);
+        assert_eq!(
+            parse_zone(Some("-0530")).unwrap(),
+            Zone::Fixed(FixedOffset::west_opt(5 * 3600 + 30 * 60).unwrap())
+        );
+        assert!(parse_zone(Some("Asia/Shanghai")).is_err());
+        assert!(parse_zone(Some("+25:00")).is_err());
+    }
+
+    #[test]
+    fn formats_by_locale_and_zone() {
+        assert_eq!(
+            format(TS, "zh-CN", "+08:00", TimeStyle::DateTime),
+            "2026/03/05 09:02"
+        );
+        assert_eq!(
+            format(TS, "en-US", "UTC-5", TimeStyle::DateTime),
+            "03/04/2026 08:02 PM"
+        );
+        assert_eq!(format(TS, "en_GB", "UTC", TimeStyle::Date), "05/03/2026");
+        assert_eq!(
+            format(TS, "de-DE", "UTC", TimeStyle::Full),
+            "2026-03-05 01:02:03 (UTC)"
+        );
+        assert_eq!(
+            format(TS, "zh-CN", "+05:30", TimeStyle::Full),
+            "2026/03/05 06:32:03 (UTC+05:30)"
+        );
+        assert_eq!(
+            format(TS, "en-US", "+08:00", TimeStyle::Iso),
+            "2026-03-05T09:02:03+08:00"
+        );
+        assert_eq!(
+            format(TS, "zh", "UTC", TimeStyle::Iso),
+            "2026-03-05T01:02:03Z"
+        );
+    }
+
+    #[test]
+    fn rejects_out_of_range_timestamps() {
+        assert!(format_timestamps(vec![i64::MAX], None).is_err());
+        assert!(format_timestamps(vec![0; MAX_BATCH + 1], None).is_err());
+        assert_eq!(format_timestamps(Vec::new(), None).unwrap().len(), 0);
+    }
+}
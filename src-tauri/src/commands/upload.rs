@@ -1,4 +1,13 @@
-use crate::error::AppError;
+use crate::commands::clipboard;
+use crate::commands::net_limiter::NetworkLimiter;
+use crate::commands::progress_throttle::ProgressEmitter;
+use crate::commands::request_headers::{
+    resolve_request_headers, RequestHeaderTemplate, RequestHeadersExt,
+};
+use crate::commands::task_registry::TaskRegistry;
+use crate::commands::utils::remember_uploaded_source;
+use crate::error::{AppError, ErrorCode};
+use crate::path_utils;
 use futures::StreamExt;
 use quick_xml::events::Event;
 use quick_xml::Reader;
@@ -8,6 +17,7 @@ use std::path::Path;
 use std::sync::{Arc, Mutex};
 use tauri::{Emitter, Window};
 use tokio::fs::File;
+use tokio_util::bytes::BytesMut;
 use tokio_util::codec::{BytesCodec, FramedRead};
 
 #[derive(Serialize)]
@@ -37,7 +47,9 @@ struct ProgressPayload {
 fn parse_weibo_response(xml: &str) -> Result<UploadResponse, AppError> {
     // 首先检查认证错误
     if xml.contains("<data>100006</data>") {
-        return Err(AppError::auth("Cookie expired (code 100006)"));
+        return Err(AppError::auth("Cookie expired (code 100006)")
+            .with_code(ErrorCode::UploadAuth)
+            .with_context("service", "weibo"));
     }
 
     let mut reader = Reader::from_str(xml);
@@ -160,17 +172,61 @@ fn find_xml_tag_content(xml: &str, tag: &str) -> Option<String> {
 // HttpClient 在 main.rs 中定义，这里直接使用
 use crate::HttpClient;
 
+/// 内存数据按块切分的大小，保证进度事件与文件流的粒度接近
+const MEMORY_CHUNK_SIZE: usize = 64 * 1024;
+
+/// 把内存中的字节按块包装成与 FramedRead 相同类型的流
+fn memory_chunk_stream(
+    bytes: Arc<Vec<u8>>,
+) -> impl futures::Stream<Item = Result<BytesMut, std::io::Error>> {
+    let len = bytes.len();
+    futures::stream::iter((0..len).step_by(MEMORY_CHUNK_SIZE).map(move |start| {
+        let end = (start + MEMORY_CHUNK_SIZE).min(len);
+        Ok(BytesMut::from(&bytes[start..end]))
+    }))
+}
+
 #[tauri::command]
 pub async fn upload_file_stream(
     window: Window,
     id: String,
     file_path: String,
     weibo_cookie: String,
+    request_headers: Option<RequestHeaderTemplate>,
+    http_client: tauri::State<'_, HttpClient>,
+    limiter: tauri::State<'_, NetworkLimiter>,
+    tasks: tauri::State<'_, TaskRegistry>,
+) -> Result<UploadResponse, AppError> {
+    tasks
+        .run(
+            Some(&id),
+            upload_file_stream_inner(
+                window,
+                id.clone(),
+                file_path.clone(),
+                weibo_cookie,
+                request_headers,
+                http_client,
+                limiter,
+            ),
+        )
+        .await
+        .inspect(|_| remember_uploaded_source(&file_path))
+}
+
+async fn upload_file_stream_inner(
+    window: Window,
+    id: String,
+    file_path: String,
+    weibo_cookie: String,
+    request_headers: Option<RequestHeaderTemplate>,
     http_client: tauri::State<'_, HttpClient>,
+    limiter: tauri::State<'_, NetworkLimiter>,
 ) -> Result<UploadResponse, AppError> {
+    let _permit = limiter.acquire("weibo").await;
     // 安全验证：防止路径遍历攻击
     // 使用 canonicalize 解析真实路径，防止通过 ../ 或符号链接访问未授权文件
-    let canonical_path = std::fs::canonicalize(&file_path)
+    let canonical_path = path_utils::canonicalize(path_utils::normalize_input(&file_path))
         .map_err(|e| AppError::file_io(format!("无法解析文件路径: {}", e)))?;
 
     // 验证是普通文件而不是目录或特殊文件
@@ -198,18 +254,26 @@ pub async fn upload_file_stream(
         .and_then(|n| n.to_str())
         .unwrap_or("image.jpg");
 
-    // 使用规范化后的路径打开文件
-    let file = File::open(&canonical_path).await?;
-    let metadata = file.metadata().await?;
-    let total_len = metadata.len();
-
-    // 使用 FramedRead 读取文件流
-    let stream = FramedRead::new(file, BytesCodec::new());
+    // 刚粘贴的剪贴板图片直接按块发送内存中的 PNG；其他文件用 FramedRead 读取文件流
+    let (stream, total_len) = match clipboard::handoff_bytes(&file_path) {
+        Some(bytes) => {
+            let total_len = bytes.len() as u64;
+            (memory_chunk_stream(bytes).boxed(), total_len)
+        }
+        None => {
+            // 使用规范化后的路径打开文件
+            let file = File::open(&canonical_path).await?;
+            let metadata = file.metadata().await?;
+            let total_len = metadata.len();
+            (FramedRead::new(file, BytesCodec::new()).boxed(), total_len)
+        }
+    };
 
     // 关键优化：通过 map 包装流，在此处注入进度监控
     let uploaded = Arc::new(Mutex::new(0u64));
     let uploaded_clone = Arc::clone(&uploaded);
-    let window_clone = window.clone();
+    // 每个数据块都会回调，经节流器合并后最多约 10 次/秒发往前端
+    let mut progress = ProgressEmitter::new(window.clone(), "upload://progress");
     let id_clone = id.clone();
     let total_len_clone = total_len;
 
@@ -244,18 +308,20 @@ pub async fn upload_file_stream(
                     current_progress
                 };
 
-                // 发送进度事件到前端(带步骤信息)
-                let _ = window_clone.emit(
-                    "upload://progress",
-                    ProgressPayload {
-                        id: id_clone.clone(),
-                        progress: safe_progress,
-                        total: total_len_clone,
-                        step: Some("正在上传...".to_string()),
-                        step_index: Some(2),
-                        total_steps: Some(3),
-                    },
-                );
+                // 发送进度事件到前端(带步骤信息)，最后一块强制发送
+                let payload = ProgressPayload {
+                    id: id_clone.clone(),
+                    progress: safe_progress,
+                    total: total_len_clone,
+                    step: Some("正在上传...".to_string()),
+                    step_index: Some(2),
+                    total_steps: Some(3),
+                };
+                if current_progress >= total_len_clone {
+                    progress.force(payload);
+                } else {
+                    progress.update(payload);
+                }
             }
             chunk
         },
@@ -266,17 +332,35 @@ pub async fn upload_file_stream(
     let url = "https://picupload.weibo.com/interface/pic_upload.php?s=xml&ori=1&data=1&rotate=0&wm=&app=miniblog&mime=image/jpeg";
 
     // 使用全局 HTTP 客户端（带连接池配置），而不是创建新客户端
-    let res = http_client.0.post(url)
+    let extra_headers = resolve_request_headers(request_headers.as_ref());
+    let res = http_client.get().post(url)
         .header(header::COOKIE, weibo_cookie)
         .header(header::CONTENT_LENGTH, total_len) // 必须显式设置长度，否则流式上传可能无法计算总长
         .header(header::CONTENT_TYPE, "application/octet-stream")
         .header(header::REFERER, "https://photo.weibo.com/")
         .header(header::ORIGIN, "https://photo.weibo.com")
         .header(header::USER_AGENT, "Mozilla/5.0 (Windows NT 10.0; Win64; x64) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/108.0.0.0 Safari/537.36")
+        .with_request_headers(&extra_headers)
         .body(body)
         .send()
         .await?;
 
+    // 401/403 通常意味着登录态失效，按 Cookie 过期处理，前端据此触发登录态探测
+    let status = res.status();
+    if status == reqwest::StatusCode::UNAUTHORIZED || status == reqwest::StatusCode::FORBIDDEN {
+        return Err(
+            AppError::auth(format!("Cookie expired (HTTP {})", status.as_u16()))
+                .with_code(ErrorCode::UploadAuth)
+                .with_context("service", "weibo"),
+        );
+    }
+
+    if status == reqwest::StatusCode::TOO_MANY_REQUESTS {
+        return Err(AppError::network("Rate limited (HTTP 429)")
+            .with_code(ErrorCode::UploadRateLimited)
+            .with_context("service", "weibo"));
+    }
+
     let text = res.text().await?;
 
     // 发送步骤3进度：处理响应 (95%)
@@ -330,7 +414,7 @@ pub async fn test_weibo_connection(
     let url = "https://picupload.weibo.com/interface/pic_upload.php?s=xml&ori=1&data=1&rotate=0&wm=&app=miniblog&mime=image/jpeg";
 
     // 发送测试上传请求
-    let response = http_client.0
+    let response = http_client.get()
         .post(url)
         .header(header::COOKIE, &weibo_cookie)
         .header(header::CONTENT_TYPE, "application/octet-stream")
@@ -343,9 +427,10 @@ pub async fn test_weibo_connection(
         .await
         .map_err(|e| {
             if e.is_timeout() {
-                AppError::network("请求超时，请检查网络连接")
+                AppError::network("请求超时，请检查网络连接").with_code(ErrorCode::NetworkTimeout)
             } else if e.is_connect() {
                 AppError::network("网络连接失败，请检查网络连接或防火墙设置")
+                    .with_code(ErrorCode::NetworkConnect)
             } else {
                 AppError::network(format!("请求失败: {}", e))
             }
@@ -374,3 +459,119 @@ pub async fn test_weibo_connection(
         Err(AppError::external("无法验证 Cookie 有效性（响应格式异常）"))
     }
 }
+
+/// 微博登录态探测结果
+#[derive(Debug, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct WeiboSessionStatus {
+    pub logged_in: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub uid: Option<String>,
+}
+
+/// 解析 m.weibo.cn/api/config 响应：{"data":{"login":true,"uid":"..."},"ok":1}
+fn parse_weibo_session(body: &str) -> Result<WeiboSessionStatus, AppError> {
+    let value: serde_json::Value = serde_json::from_str(body)
+        .map_err(|_| AppError::external("无法解析微博登录状态（响应格式异常）"))?;
+    let data = value
+        .get("data")
+        .ok_or_else(|| AppError::external("无法解析微博登录状态（缺少 data 字段）"))?;
+    let logged_in = data.get("login").and_then(|v| v.as_bool()).unwrap_or(false);
+    let uid = data.get("uid").and_then(|v| match v {
+        serde_json::Value::String(uid) if !uid.is_empty() => Some(uid.clone()),
+        serde_json::Value::Number(uid) => Some(uid.to_string()),
+        _ => None,
+    });
+    Ok(WeiboSessionStatus {
+        logged_in,
+        uid: uid.filter(|_| logged_in),
+    })
+}
+
+/// 探测微博 Cookie 登录态（只读请求，不上传测试图片，适合定时调用）
+/// 网络错误返回 Err，登录态失效返回 loggedIn: false
+#[tauri::command]
+pub async fn probe_weibo_session(
+    weibo_cookie: String,
+    http_client: tauri::State<'_, HttpClient>,
+) -> Result<WeiboSessionStatus, AppError> {
+    if weibo_cookie.trim().is_empty() {
+        return Err(AppError::validation("Cookie 不能为空"));
+    }
+
+    let response = http_client.get()
+        .get("https://m.weibo.cn/api/config")
+        .header(header::COOKIE, &weibo_cookie)
+        .header(header::REFERER, "https://m.weibo.cn/")
+        .header(header::USER_AGENT, "Mozilla/5.0 (Windows NT 10.0; Win64; x64) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/108.0.0.0 Safari/537.36")
+        .timeout(std::time::Duration::from_secs(15))
+        .send()
+        .await
+        .map_err(|e| {
+            if e.is_timeout() {
+                AppError::network("请求超时，请检查网络连接").with_code(ErrorCode::NetworkTimeout)
+            } else {
+                AppError::network(format!("请求失败: {}", e))
+            }
+        })?;
+
+    let status = response.status();
+    if status == reqwest::StatusCode::UNAUTHORIZED || status == reqwest::StatusCode::FORBIDDEN {
+        log::info!("[Weibo] 登录态探测: HTTP {}，视为已过期", status.as_u16());
+        return Ok(WeiboSessionStatus {
+            logged_in: false,
+            uid: None,
+        });
+    }
+    if !status.is_success() {
+        return Err(AppError::network(format!(
+            "探测登录状态失败: HTTP {}",
+            status.as_u16()
+        )));
+    }
+
+    let body = response
+        .text()
+        .await
+        .map_err(|e| AppError::network(format!("无法读取响应: {}", e)))?;
+    let session = parse_weibo_session(&body)?;
+    log::info!(
+        "[Weibo] 登录态探测: {}",
+        if session.logged_in {
+            "有效"
+        } else {
+            "已过期"
+        }
+    );
+    Ok(session)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_weibo_session_reads_login_flag_and_uid() {
+        let session =
+            parse_weibo_session(r#"{"data":{"login":true,"st":"abc","uid":"123456"},"ok":1}"#)
+                .unwrap();
+        assert_eq!(
+            session,
+            WeiboSessionStatus {
+                logged_in: true,
+                uid: Some("123456".to_string())
+            }
+        );
+    }
+
+    #[test]
+    fn parse_weibo_session_treats_missing_login_as_expired() {
+        let session = parse_weibo_session(r#"{"data":{"login":false},"ok":1}"#).unwrap();
+        assert!(!session.logged_in);
+        assert!(parse_weibo_session(r#"{"data":{"uid":1}}"#)
+            .unwrap()
+            .uid
+            .is_none());
+        assert!(parse_weibo_session("<html>").is_err());
+    }
+}
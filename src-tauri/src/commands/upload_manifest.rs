@@ -0,0 +1,373 @@
+// 团队共享的上传清单：导出供静态站点生成器读取的 JSON，并可重新检测其中的链接
+//
+// 工作流：
+//   export_upload_manifest(filter, path) -> 只读查询历史记录 -> 每张图片输出主链接、
+//   各图床镜像、SHA-256（本地原图仍存在时）与 alt 文本 -> 写入 JSON 文件
+//   verify_manifest(path) -> 读取清单 -> 并发检测其中每个链接 -> 返回失效明细
+//
+// 清单格式（version 1）：
+//   { "version": 1, "generator": "PicNexus x.y.z", "generatedAt": 毫秒时间戳,
+//     "images": [{ "id", "fileName", "alt", "url", "mirrors": [{ "service", "url" }],
+//                  "sha256", "width", "height", "format", "size", "uploadedAt" }] }
+
+use crate::atomic_file;
+use crate::commands::host_migrator::{matches_filter, MigrateRecordsFilter};
+use crate::commands::link_checker::{check_single_link, safe_no_redirect_client};
+use crate::commands::utils::sha256_file;
+use crate::error::AppError;
+use crate::log_utils::safe_path;
+use futures::stream::{self, StreamExt};
+use serde::{Deserialize, Serialize};
+use sqlx::sqlite::SqliteConnectOptions;
+use sqlx::{ConnectOptions, Row};
+use std::path::Path;
+
+pub const MANIFEST_VERSION: u32 = 1;
+/// 同时检测的链接数
+const VERIFY_CONCURRENCY: usize = 8;
+const VERIFY_TIMEOUT_SECS: u64 = 10;
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ManifestMirror {
+    pub service: String,
+    pub url: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ManifestImage {
+    pub id: String,
+    pub file_name: String,
+    pub alt: String,
+    /// 主链接（与历史记录中生成的链接一致）
+    pub url: String,
+    #[serde(default)]
+    pub mirrors: Vec<ManifestMirror>,
+    /// 本地原图的 SHA-256（原图已删除时为空）
+    #[serde(default)]
+    pub sha256: Option<String>,
+    #[serde(default)]
+    pub width: Option<u32>,
+    #[serde(default)]
+    pub height: Option<u32>,
+    #[serde(default)]
+    pub format: Option<String>,
+    #[serde(default)]
+    pub size: Option<u64>,
+    #[serde(default)]
+    pub uploaded_at: Option<i64>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UploadManifest {
+    pub version: u32,
+    #[serde(default)]
+    pub generator: String,
+    #[serde(default)]
+    pub generated_at: i64,
+    pub images: Vec<ManifestImage>,
+}
+
+/// 单个链接的检测结果
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ManifestLinkCheck {
+    pub id: String,
+    /// "primary" 或镜像所在图床 ID
+    pub service: String,
+    pub url: String,
+    pub is_valid: bool,
+    pub status_code: Option<u16>,
+    pub error_type: String,
+    pub error: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ManifestVerifyResult {
+    pub images: usize,
+    pub total: usize,
+    pub valid: usize,
+    pub invalid: usize,
+    /// 只包含失效链接，避免大清单把全部结果传回前端
+    pub failures: Vec<ManifestLinkCheck>,
+}
+
+/// 从 results JSON 中取出所有上传成功的 (图床, 链接)
+fn success_mirrors(results: &serde_json::Value) -> Vec<ManifestMirror> {
+    let Some(entries) = results.as_array() else {
+        return Vec::new();
+    };
+    entries
+        .iter()
+        .filter(|entry| entry.get("status").and_then(|v| v.as_str()) == Some("success"))
+        .filter_map(|entry| {
+            let service = entry.get("serviceId")?.as_str()?;
+            let url = entry.get("result")?.get("url")?.as_str()?;
+            (!url.is_empty()).then(|| ManifestMirror {
+                service: service.to_string(),
+                url: url.to_string(),
+            })
+        })
+        .collect()
+}
+
+/// 默认 alt 文本：去掉扩展名，把 - / _ 视为空格
+pub(crate) fn default_alt_text(file_name: &str) -> String {
+    let stem = Path::new(file_name)
+        .file_stem()
+        .map(|stem| stem.to_string_lossy().to_string())
+        .unwrap_or_default();
+    stem.replace(['-', '_'], " ").trim().to_string()
+}
+
+async fn sha256_of_file(path: &str) -> Option<String> {
+    sha256_file(path).await.ok()
+}
+
+/// 只读查询历史记录并组装清单条目（按上传时间升序）
+async fn load_manifest_images(
+    db_path: &Path,
+    filter: &MigrateRecordsFilter,
+) -> Result<Vec<ManifestImage>, String> {
+    if !db_path.exists() {
+        return Ok(Vec::new());
+    }
+
+    let mut conn = SqliteConnectOptions::new()
+        .filename(db_path)
+        .read_only(true)
+        .connect()
+        .await
+        .map_err(|e| format!("打开历史记录数据库失败: {}", e))?;
+
+    let rows = sqlx::query(
+        "SELECT id, timestamp, local_file_name, file_path, results, generated_link, \
+         width, height, format, file_size \
+         FROM history_items ORDER BY timestamp ASC",
+    )
+    .fetch_all(&mut conn)
+    .await
+    .map_err(|e| format!("查询历史记录失败: {}", e))?;
+
+    let mut images = Vec::new();
+    for row in rows {
+        let id: String = row.try_get("id").map_err(|e| e.to_string())?;
+        let timestamp: i64 = row.try_get("timestamp").unwrap_or_default();
+        let file_name: String = row.try_get("local_file_name").unwrap_or_default();
+        if !matches_filter(filter, &id, &file_name, timestamp) {
+            continue;
+        }
+
+        let results: String = row.try_get("results").unwrap_or_default();
+        let results: serde_json::Value =
+            serde_json::from_str(&results).unwrap_or(serde_json::Value::Null);
+        let mirrors = success_mirrors(&results);
+        let generated_link: String = row.try_get("generated_link").unwrap_or_default();
+        let url = if generated_link.is_empty() {
+            match mirrors.first() {
+                Some(mirror) => mirror.url.clone(),
+                None => continue,
+            }
+        } else {
+            generated_link
+        };
+
+        let file_path: Option<String> = row.try_get("file_path").ok().flatten();
+        let sha256 = match file_path.as_deref().filter(|p| !p.is_empty()) {
+            Some(path) => sha256_of_file(path).await,
+            None => None,
+        };
+
+        images.push(ManifestImage {
+            id,
+            alt: default_alt_text(&file_name),
+            file_name,
+            url,
+            mirrors,
+            sha256,
+            width: row.try_get::<i64, _>("width").ok().map(|v| v as u32),
+            height: row.try_get::<i64, _>("height").ok().map(|v| v as u32),
+            format: row
+                .try_get::<String, _>("format")
+                .ok()
+                .filter(|v| !v.is_empty()),
+            size: row.try_get::<i64, _>("file_size").ok().map(|v| v as u64),
+            uploaded_at: Some(timestamp),
+        });
+    }
+
+    Ok(images)
+}
+
+/// 清单中需要检测的链接：主链接 + 与主链接不同的镜像
+fn manifest_links(manifest: &UploadManifest) -> Vec<(String, String, String)> {
+    let mut links = Vec::new();
+    for image in &manifest.images {
+        links.push((image.id.clone(), "primary".to_string(), image.url.clone()));
+        for mirror in &image.mirrors {
+            if mirror.url != image.url {
+                links.push((image.id.clone(), mirror.service.clone(), mirror.url.clone()));
+            }
+        }
+    }
+    links
+}
+
+fn parse_manifest(content: &str) -> Result<UploadManifest, String> {
+    let manifest: UploadManifest =
+        serde_json::from_str(content).map_err(|e| format!("清单格式无效: {}", e))?;
+    if manifest.version > MANIFEST_VERSION {
+        return Err(format!(
+            "不支持的清单版本: {}（当前支持 {}）",
+            manifest.version, MANIFEST_VERSION
+        ));
+    }
+    Ok(manifest)
+}
+
+/// 把历史记录导出为上传清单 JSON，返回导出的图片数
+#[tauri::command]
+pub async fn export_upload_manifest(
+    app: tauri::AppHandle,
+    filter: Option<MigrateRecordsFilter>,
+    path: String,
+) -> Result<usize, AppError> {
+    let db_path = crate::portable::history_db_path(&app)?;
+    let images = load_manifest_images(&db_path, &filter.unwrap_or_default())
+        .await
+        .map_err(AppError::storage)?;
+
+    let manifest = UploadManifest {
+        version: MANIFEST_VERSION,
+        generator: format!("PicNexus {}", env!("CARGO_PKG_VERSION")),
+        generated_at: chrono::Utc::now().timestamp_millis(),
+        images,
+    };
+    let content = serde_json::to_string_pretty(&manifest)
+        .map_err(|e| AppError::file_io(format!("序列化清单失败: {}", e)))?;
+    atomic_file::write_async(&path, content)
+        .await
+        .map_err(|e| AppError::file_io(format!("写入清单失败: {}", e)))?;
+
+    log::info!(
+        "[Manifest] 已导出 {} 张图片: {}",
+        manifest.images.len(),
+        safe_path(&path)
+    );
+    Ok(manifest.images.len())
+}
+
+/// 重新检测清单中的每个链接（主链接与镜像）
+#[tauri::command]
+pub async fn verify_manifest(path: String) -> Result<ManifestVerifyResult, AppError> {
+    let content = tokio::fs::read_to_string(&path)
+        .await
+        .map_err(|e| AppError::file_io(format!("读取清单失败: {}", e)))?;
+    let manifest = parse_manifest(&content).map_err(AppError::validation)?;
+    let links = manifest_links(&manifest);
+    let total = links.len();
+
+    let client = safe_no_redirect_client()?;
+    let client = &client;
+    let checks: Vec<ManifestLinkCheck> = stream::iter(links)
+        .map(|(id, service, url)| async move {
+            let result = check_single_link(&url, client, VERIFY_TIMEOUT_SECS).await;
+            ManifestLinkCheck {
+                id,
+                service,
+                url,
+                is_valid: result.is_valid,
+                status_code: result.status_code,
+                error_type: result.error_type,
+                error: result.error,
+            }
+        })
+        .buffer_unordered(VERIFY_CONCURRENCY)
+        .collect()
+        .await;
+
+    let failures: Vec<ManifestLinkCheck> = checks.into_iter().filter(|c| !c.is_valid).collect();
+    log::info!(
+        "[Manifest] 检测完成: {} 个链接, {} 个失效 ({})",
+        total,
+        failures.len(),
+        safe_path(&path)
+    );
+
+    Ok(ManifestVerifyResult {
+        images: manifest.images.len(),
+        total,
+        valid: total - failures.len(),
+        invalid: failures.len(),
+        failures,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn success_mirrors_skips_failed_results() {
+        let results: serde_json::Value = serde_json::from_str(
+            r#"[
+                {"serviceId": "weibo", "result": {"url": "https://wx1.sinaimg.cn/a.jpg"}, "status": "success"},
+                {"serviceId": "r2", "status": "failed", "error": "403"},
+                {"serviceId": "github", "result": {"url": "https://cdn.example.com/a.jpg"}, "status": "success"}
+            ]"#,
+        )
+        .unwrap();
+
+        assert_eq!(
+            success_mirrors(&results),
+            vec![
+                ManifestMirror {
+                    service: "weibo".to_string(),
+                    url: "https://wx1.sinaimg.cn/a.jpg".to_string()
+                },
+                ManifestMirror {
+                    service: "github".to_string(),
+                    url: "https://cdn.example.com/a.jpg".to_string()
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn default_alt_text_uses_readable_stem() {
+        assert_eq!(default_alt_text("hello-world_2024.png"), "hello world 2024");
+        assert_eq!(default_alt_text("截图.jpg"), "截图");
+    }
+
+    #[test]
+    fn manifest_links_dedupes_primary_mirror() {
+        let manifest = parse_manifest(
+            r#"{
+                "version": 1,
+                "images": [{
+                    "id": "h1", "fileName": "a.png", "alt": "a",
+                    "url": "https://cdn.example.com/a.png",
+                    "mirrors": [
+                        {"service": "r2", "url": "https://cdn.example.com/a.png"},
+                        {"service": "weibo", "url": "https://wx1.sinaimg.cn/a.png"}
+                    ]
+                }]
+            }"#,
+        )
+        .unwrap();
+
+        let links = manifest_links(&manifest);
+
+        assert_eq!(links.len(), 2);
+        assert_eq!(links[0].1, "primary");
+        assert_eq!(links[1].1, "weibo");
+    }
+
+    #[test]
+    fn parse_manifest_rejects_future_versions() {
+        assert!(parse_manifest(r#"{"version": 2, "images": []}"#).is_err());
+        assert!(parse_manifest("not json").is_err());
+    }
+}
@@ -0,0 +1,356 @@
+// src-tauri/src/commands/uploader_registry.rs
+// 图床元数据：每个上传后端的配置字段（类型、是否必填、是否敏感）与能力
+//
+// 设置页原本为每个图床手写表单，新增字段时前后端要各改一遍。
+// 字段表经 Uploader trait 的默认实现（config_fields / category）挂到各上传后端上，
+// list_uploaders 直接遍历上传后端注册表（uploaders::registry）生成列表，与 list_upload_providers 同源；
+// 必填字段与前端 SERVICE_REQUIRED_FIELDS（src/constants/serviceRequiredFields.ts）保持一致。
+
+use serde::Serialize;
+
+use super::host_capabilities::{HostCapabilities, HOST_CAPABILITIES};
+use crate::uploaders::{self, Uploader};
+
+/// 配置字段类型（决定设置页使用的输入控件）
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum FieldKind {
+    /// 单行文本
+    Text,
+    /// 链接地址（保存前校验协议与域名）
+    Url,
+    /// 登录 Cookie（多行文本，可由登录窗口自动填入）
+    Cookie,
+    /// 开关
+    Boolean,
+}
+
+/// 单个配置字段
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ConfigField {
+    /// 配置对象中的字段名（与前端 *ServiceConfig 一致）
+    pub key: &'static str,
+    pub label: &'static str,
+    pub kind: FieldKind,
+    pub required: bool,
+    /// 敏感字段：界面遮挡显示，导出诊断信息时脱敏
+    pub secret: bool,
+}
+
+const fn field(key: &'static str, label: &'static str) -> ConfigField {
+    ConfigField {
+        key,
+        label,
+        kind: FieldKind::Text,
+        required: true,
+        secret: false,
+    }
+}
+
+const fn secret(key: &'static str, label: &'static str) -> ConfigField {
+    ConfigField {
+        secret: true,
+        ..field(key, label)
+    }
+}
+
+const fn optional(key: &'static str, label: &'static str) -> ConfigField {
+    ConfigField {
+        required: false,
+        ..field(key, label)
+    }
+}
+
+const COOKIE: ConfigField = ConfigField {
+    kind: FieldKind::Cookie,
+    ..secret("cookie", "Cookie")
+};
+
+const PUBLIC_DOMAIN: ConfigField = ConfigField {
+    kind: FieldKind::Url,
+    ..field("publicDomain", "公开访问域名")
+};
+
+const PATH_PREFIX: ConfigField = optional("path", "存储路径前缀");
+
+/// 图床分类：公共图床使用平台存储，私有存储使用用户自己的凭证
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum UploaderCategory {
+    Public,
+    Private,
+}
+
+/// list_uploaders 返回的单个图床描述
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UploaderInfo {
+    /// 服务 ID；多实例图床为复合 ID 的前缀（如 custom_s3，实际 ID 为 custom_s3:<profileId>）
+    pub id: String,
+    pub display_name: String,
+    pub category: UploaderCategory,
+    /// 是否允许配置多个实例
+    pub multi_instance: bool,
+    pub fields: &'static [ConfigField],
+    pub capabilities: HostCapabilities,
+}
+
+// 内置图床的配置字段（顺序即设置页展示顺序）
+
+const COOKIE_FIELDS: &[ConfigField] = &[COOKIE];
+
+const ZHIHU_FIELDS: &[ConfigField] = &[
+    COOKIE,
+    ConfigField {
+        kind: FieldKind::Boolean,
+        ..optional("sourceParamEnabled", "追加 source 参数")
+    },
+    optional("sourceParamValue", "source 参数值"),
+];
+
+const NAMI_FIELDS: &[ConfigField] = &[COOKIE, secret("authToken", "Auth-Token")];
+
+const R2_FIELDS: &[ConfigField] = &[
+    field("accountId", "账户 ID"),
+    field("accessKeyId", "Access Key ID"),
+    secret("secretAccessKey", "Secret Access Key"),
+    field("bucketName", "存储桶名称"),
+    PATH_PREFIX,
+    PUBLIC_DOMAIN,
+];
+
+const SMMS_FIELDS: &[ConfigField] = &[secret("token", "API Token")];
+
+const GITHUB_FIELDS: &[ConfigField] = &[
+    secret("token", "Personal Access Token"),
+    field("owner", "仓库所有者"),
+    field("repo", "仓库名称"),
+    optional("branch", "分支"),
+    PATH_PREFIX,
+];
+
+const IMGUR_FIELDS: &[ConfigField] = &[
+    field("clientId", "Client ID"),
+    ConfigField {
+        required: false,
+        ..secret("clientSecret", "Client Secret")
+    },
+];
+
+const TENCENT_FIELDS: &[ConfigField] = &[
+    field("secretId", "SecretId"),
+    secret("secretKey", "SecretKey"),
+    field("region", "地域"),
+    field("bucket", "存储桶名称"),
+    PATH_PREFIX,
+    ConfigField {
+        required: false,
+        ..PUBLIC_DOMAIN
+    },
+];
+
+const ALIYUN_FIELDS: &[ConfigField] = &[
+    field("accessKeyId", "AccessKey ID"),
+    secret("accessKeySecret", "AccessKey Secret"),
+    field("region", "地域"),
+    field("bucket", "存储桶名称"),
+    PATH_PREFIX,
+    PUBLIC_DOMAIN,
+    optional("styleSuffix", "图片样式后缀"),
+];
+
+const QINIU_FIELDS: &[ConfigField] = &[
+    field("accessKey", "AccessKey"),
+    secret("secretKey", "SecretKey"),
+    optional("region", "存储区域"),
+    field("bucket", "存储桶名称"),
+    PUBLIC_DOMAIN,
+    PATH_PREFIX,
+];
+
+const UPYUN_FIELDS: &[ConfigField] = &[
+    field("operator", "操作员"),
+    secret("password", "操作员密码"),
+    field("bucket", "服务名称"),
+    PUBLIC_DOMAIN,
+    PATH_PREFIX,
+];
+
+/// 按图床 ID 查字段表（Uploader::config_fields 的默认实现）
+pub(crate) fn fields_for(service: &str) -> &'static [ConfigField] {
+    match service {
+        "weibo" | "nowcoder" | "bilibili" | "chaoxing" => COOKIE_FIELDS,
+        "zhihu" => ZHIHU_FIELDS,
+        "nami" => NAMI_FIELDS,
+        "r2" => R2_FIELDS,
+        "smms" => SMMS_FIELDS,
+        "github" => GITHUB_FIELDS,
+        "imgur" => IMGUR_FIELDS,
+        "tencent" => TENCENT_FIELDS,
+        "aliyun" => ALIYUN_FIELDS,
+        "qiniu" => QINIU_FIELDS,
+        "upyun" => UPYUN_FIELDS,
+        // jd、qiyu 开箱即用
+        _ => &[],
+    }
+}
+
+/// 自定义 S3 单个 Profile 的配置字段
+pub(crate) const CUSTOM_S3_FIELDS: &[ConfigField] = &[
+    field("name", "显示名称"),
+    ConfigField {
+        kind: FieldKind::Url,
+        ..field("endpoint", "端点地址")
+    },
+    field("accessKeyId", "Access Key ID"),
+    secret("secretAccessKey", "Secret Access Key"),
+    field("region", "地域"),
+    field("bucket", "存储桶名称"),
+    PATH_PREFIX,
+    ConfigField {
+        required: false,
+        ..PUBLIC_DOMAIN
+    },
+];
+
+/// 按图床 ID 判断分类（Uploader::category 的默认实现）
+pub(crate) fn category_for(service: &str) -> UploaderCategory {
+    match service {
+        "r2" | "tencent" | "aliyun" | "qiniu" | "upyun" => UploaderCategory::Private,
+        _ => UploaderCategory::Public,
+    }
+}
+
+/// 自定义 S3 的能力（不在内置能力表中）
+pub(crate) static CUSTOM_S3_CAPABILITIES: HostCapabilities = HostCapabilities {
+    service: "custom_s3",
+    display_name: "自定义 S3",
+    max_file_size: None,
+    max_gif_size: None,
+    allowed_formats: &[],
+    needs_cookie: false,
+    supports_delete: true,
+    supports_multipart: false,
+};
+
+fn info_for(uploader: &dyn Uploader) -> Option<UploaderInfo> {
+    Some(UploaderInfo {
+        id: uploader.id().to_string(),
+        display_name: uploader.display_name().to_string(),
+        category: uploader.category(),
+        multi_instance: uploader.multi_instance(),
+        fields: uploader.config_fields(),
+        capabilities: uploader.capabilities()?.clone(),
+    })
+}
+
+/// 注册表中的上传后端（能力表未收录的不展示），按内置能力表顺序排列，多实例图床在最后
+fn uploaders() -> Vec<UploaderInfo> {
+    let mut list: Vec<UploaderInfo> = uploaders::registry()
+        .all()
+        .iter()
+        .filter_map(|uploader| info_for(uploader.as_ref()))
+        .collect();
+    list.sort_by_key(|info| {
+        HOST_CAPABILITIES
+            .iter()
+            .position(|caps| caps.service == info.id)
+            .unwrap_or(usize::MAX)
+    });
+    list
+}
+
+/// 列出所有上传后端的元数据（配置字段 + 能力），供设置页生成配置表单
+#[tauri::command]
+pub fn list_uploaders() -> Vec<UploaderInfo> {
+    uploaders()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn required_keys(id: &str) -> Vec<&'static str> {
+        let list = uploaders();
+        let info = list.iter().find(|u| u.id == id).unwrap();
+        info.fields
+            .iter()
+            .filter(|f| f.required)
+            .map(|f| f.key)
+            .collect()
+    }
+
+    #[test]
+    fn required_fields_match_frontend_table() {
+        assert!(required_keys("jd").is_empty());
+        assert_eq!(required_keys("weibo"), ["cookie"]);
+        assert_eq!(
+            required_keys("r2"),
+            [
+                "accountId",
+                "accessKeyId",
+                "secretAccessKey",
+                "bucketName",
+                "publicDomain"
+            ]
+        );
+        assert_eq!(
+            required_keys("tencent"),
+            ["secretId", "secretKey", "region", "bucket"]
+        );
+        assert_eq!(required_keys("github"), ["token", "owner", "repo"]);
+        assert_eq!(
+            required_keys("custom_s3"),
+            [
+                "name",
+                "endpoint",
+                "accessKeyId",
+                "secretAccessKey",
+                "region",
+                "bucket"
+            ]
+        );
+    }
+
+    #[test]
+    fn lists_every_registered_host_in_capability_order() {
+        let ids: Vec<String> = uploaders().into_iter().map(|u| u.id).collect();
+        let expected: Vec<&str> = HOST_CAPABILITIES
+            .iter()
+            .map(|caps| caps.service)
+            .chain(["custom_s3"])
+            .collect();
+        assert_eq!(ids, expected);
+    }
+
+    #[test]
+    fn cookie_hosts_declare_a_secret_cookie_field() {
+        for info in uploaders() {
+            let has_cookie = info
+                .fields
+                .iter()
+                .any(|f| f.kind == FieldKind::Cookie && f.secret && f.required);
+            assert_eq!(
+                has_cookie, info.capabilities.needs_cookie,
+                "{} 的 Cookie 字段与能力表不一致",
+                info.id
+            );
+        }
+    }
+
+    #[test]
+    fn serializes_with_camel_case_fields() {
+        let value = serde_json::to_value(uploaders()).unwrap();
+        let smms = value
+            .as_array()
+            .unwrap()
+            .iter()
+            .find(|u| u["id"] == "smms")
+            .unwrap();
+        assert_eq!(smms["displayName"], "SM.MS");
+        assert_eq!(smms["category"], "public");
+        assert_eq!(smms["fields"][0]["secret"], true);
+        assert_eq!(smms["capabilities"]["supportsDelete"], true);
+    }
+}
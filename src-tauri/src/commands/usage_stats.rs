@@ -0,0 +1,375 @@
+// src-tauri/src/commands/usage_stats.rs
+// 上传用量统计：按时间范围汇总上传次数、流量、平均大小、最忙的一天与各图床占比
+//
+// 统计页与年度「上传报告」导出共用。历史记录可能有十几万条，
+// 前端逐条读取再汇总会很慢，这里在 Rust 端只读查询需要的四列后一次遍历完成。
+
+use std::collections::{BTreeMap, HashMap};
+use std::path::Path;
+
+use chrono::{Local, NaiveDate, TimeZone};
+use serde::{Deserialize, Serialize};
+use sqlx::sqlite::SqliteConnectOptions;
+use sqlx::{ConnectOptions, Row};
+
+use crate::error::AppError;
+
+const DAY_MS: i64 = 24 * 60 * 60 * 1000;
+
+/// 统计的时间范围（按本地时区划分日期与年份）
+#[derive(Debug, Clone, Deserialize)]
+#[serde(
+    tag = "kind",
+    rename_all = "camelCase",
+    rename_all_fields = "camelCase"
+)]
+pub enum UsageRange {
+    /// 全部历史
+    All,
+    /// 最近 N 天（含今天之前的 N×24 小时）
+    LastDays { days: u32 },
+    /// 某个自然年
+    Year { year: i32 },
+    /// 自定义区间，左闭右开，缺省的一端不限制
+    Custom {
+        from_ms: Option<i64>,
+        to_ms: Option<i64>,
+    },
+}
+
+/// 单个图床的用量
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct HostUsage {
+    pub service: String,
+    pub uploads: u64,
+    pub bytes: u64,
+    /// 占所有图床上传次数的比例（0-1）；一张图片上传到多个图床时每个图床各计一次
+    pub share: f64,
+}
+
+/// 上传最多的一天
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BusiestDay {
+    /// 本地日期（YYYY-MM-DD）
+    pub date: String,
+    pub uploads: u64,
+    pub bytes: u64,
+}
+
+/// get_usage_stats 的返回值
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UsageStats {
+    pub from_ms: Option<i64>,
+    pub to_ms: Option<i64>,
+    /// 上传的图片数（历史记录条数）
+    pub uploads: u64,
+    /// 原图总字节数
+    pub bytes: u64,
+    pub average_bytes: u64,
+    /// 有上传的天数
+    pub active_days: u64,
+    pub busiest_day: Option<BusiestDay>,
+    pub first_upload_ms: Option<i64>,
+    pub last_upload_ms: Option<i64>,
+    /// 按上传次数降序
+    pub hosts: Vec<HostUsage>,
+}
+
+/// 参与统计的一条历史记录
+struct StatRow {
+    timestamp: i64,
+    file_size: u64,
+    primary_service: String,
+    /// successful_service_ids 列（JSON 数组）
+    service_ids: String,
+}
+
+/// 本地零点的 Unix 毫秒
+fn local_midnight_ms(year: i32, month: u32, day: u32) -> Option<i64> {
+    Local
+        .with_ymd_and_hms(year, month, day, 0, 0, 0)
+        .earliest()
+        .map(|t| t.timestamp_millis())
+}
+
+/// 把范围换算为 [from, to) 毫秒区间
+fn resolve_range(range: &UsageRange, now_ms: i64) -> Result<(Option<i64>, Option<i64>), AppError> {
+    match *range {
+        UsageRange::All => Ok((None, None)),
+        UsageRange::LastDays { days } => {
+            if days == 0 {
+                return Err(AppError::validation("天数必须大于 0"));
+            }
+            Ok((Some(now_ms - i64::from(days) * DAY_MS), None))
+        }
+        UsageRange::Year { year } => {
+            let from = local_midnight_ms(year, 1, 1);
+            let to = local_midnight_ms(year + 1, 1, 1);
+            match (from, to) {
+                (Some(from), Some(to)) => Ok((Some(from), Some(to))),
+                _ => Err(AppError::validation(format!("无效的年份: {}", year))),
+            }
+        }
+        UsageRange::Custom { from_ms, to_ms } => {
+            if let (Some(from), Some(to)) = (from_ms, to_ms) {
+                if from >= to {
+                    return Err(AppError::validation("开始时间必须早于结束时间"));
+                }
+            }
+            Ok((from_ms, to_ms))
+        }
+    }
+}
+
+/// 一次遍历完成汇总；`day_of` 把时间戳换算为本地日期（测试中注入固定时区）
+fn aggregate(
+    rows: impl IntoIterator<Item = StatRow>,
+    day_of: impl Fn(i64) -> Option<NaiveDate>,
+) -> UsageStats {
+    let mut uploads = 0u64;
+    let mut bytes = 0u64;
+    let mut first: Option<i64> = None;
+    let mut last: Option<i64> = None;
+    let mut days: BTreeMap<NaiveDate, (u64, u64)> = BTreeMap::new();
+    let mut hosts: HashMap<String, (u64, u64)> = HashMap::new();
+
+    for row in rows {
+        uploads += 1;
+        bytes += row.file_size;
+        first = Some(first.map_or(row.timestamp, |t| t.min(row.timestamp)));
+        last = Some(last.map_or(row.timestamp, |t| t.max(row.timestamp)));
+
+        if let Some(day) = day_of(row.timestamp) {
+            let entry = days.entry(day).or_default();
+            entry.0 += 1;
+            entry.1 += row.file_size;
+        }
+
+        // 旧记录没有成功图床列表时按主图床计
+        let mut services: Vec<String> = serde_json::from_str(&row.service_ids).unwrap_or_default();
+        if services.is_empty() && !row.primary_service.is_empty() {
+            services.push(row.primary_service);
+        }
+        services.sort_unstable();
+        services.dedup();
+        for service in services {
+            let entry = hosts.entry(service).or_default();
+            entry.0 += 1;
+            entry.1 += row.file_size;
+        }
+    }
+
+    // 同样多时取较早的一天
+    let busiest_day = days
+        .iter()
+        .fold(None::<(&NaiveDate, &(u64, u64))>, |best, day| match best {
+            Some(b) if b.1 .0 >= day.1 .0 => Some(b),
+            _ => Some(day),
+        })
+        .map(|(date, (uploads, bytes))| BusiestDay {
+            date: date.format("%Y-%m-%d").to_string(),
+            uploads: *uploads,
+            bytes: *bytes,
+        });
+
+    let host_total: u64 = hosts.values().map(|(count, _)| count).sum();
+    let mut hosts: Vec<HostUsage> = hosts
+        .into_iter()
+        .map(|(service, (count, size))| HostUsage {
+            service,
+            uploads: count,
+            bytes: size,
+            share: count as f64 / host_total as f64,
+        })
+        .collect();
+    hosts.sort_by(|a, b| {
+        b.uploads
+            .cmp(&a.uploads)
+            .then_with(|| a.service.cmp(&b.service))
+    });
+
+    UsageStats {
+        from_ms: None,
+        to_ms: None,
+        uploads,
+        bytes,
+        average_bytes: bytes.checked_div(uploads).unwrap_or(0),
+        active_days: days.len() as u64,
+        busiest_day,
+        first_upload_ms: first,
+        last_upload_ms: last,
+        hosts,
+    }
+}
+
+async fn load_rows(
+    db_path: &Path,
+    from: Option<i64>,
+    to: Option<i64>,
+) -> Result<Vec<StatRow>, AppError> {
+    let mut conn = SqliteConnectOptions::new()
+        .filename(db_path)
+        .read_only(true)
+        .connect()
+        .await
+        .map_err(|e| AppError::storage(format!("打开历史记录数据库失败: {}", e)))?;
+
+    let rows = sqlx::query(
+        "SELECT timestamp, file_size, primary_service, successful_service_ids \
+         FROM history_items \
+         WHERE (?1 IS NULL OR timestamp >= ?1) AND (?2 IS NULL OR timestamp < ?2)",
+    )
+    .bind(from)
+    .bind(to)
+    .fetch_all(&mut conn)
+    .await
+    .map_err(|e| AppError::storage(format!("查询历史记录失败: {}", e)))?;
+
+    Ok(rows
+        .iter()
+        .map(|row| StatRow {
+            timestamp: row.try_get("timestamp").unwrap_or_default(),
+            file_size: row.try_get::<i64, _>("file_size").unwrap_or(0).max(0) as u64,
+            primary_service: row.try_get("primary_service").unwrap_or_default(),
+            service_ids: row.try_get("successful_service_ids").unwrap_or_default(),
+        })
+        .collect())
+}
+
+/// 按时间范围汇总上传用量
+#[tauri::command]
+pub async fn get_usage_stats(
+    app: tauri::AppHandle,
+    range: UsageRange,
+) -> Result<UsageStats, AppError> {
+    let (from, to) = resolve_range(&range, chrono::Utc::now().timestamp_millis())?;
+    let db_path = crate::portable::history_db_path(&app)?;
+
+    // 尚未产生任何上传时数据库不存在，视为空历史
+    let rows = if db_path.exists() {
+        load_rows(&db_path, from, to).await?
+    } else {
+        Vec::new()
+    };
+    let stats = aggregate(rows, |ts| {
+        Local
+            .timestamp_millis_opt(ts)
+            .single()
+            .map(|t| t.date_naive())
+    });
+    log::debug!(
+        "[用量统计] {:?}: {} 次上传，{} 字节",
+        range,
+        stats.uploads,
+        stats.bytes
+    );
+    Ok(UsageStats {
+        from_ms: from,
+        to_ms: to,
+        ..stats
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn row(timestamp: i64, size: u64, primary: &str, ids: &str) -> StatRow {
+        StatRow {
+            timestamp,
+            file_size: size,
+            primary_service: primary.to_string(),
+            service_ids: ids.to_string(),
+        }
+    }
+
+    fn utc_day(ts: i64) -> Option<NaiveDate> {
+        chrono::DateTime::from_timestamp_millis(ts).map(|t| t.date_naive())
+    }
+
+    #[test]
+    fn aggregates_totals_busiest_day_and_host_shares() {
+        let rows = vec![
+            row(0, 100, "r2", r#"["r2","weibo"]"#),
+            row(DAY_MS + 1, 300, "r2", r#"["r2"]"#),
+            row(DAY_MS + 2, 200, "weibo", "[]"),
+            row(3 * DAY_MS, 400, "smms", "not json"),
+        ];
+        let stats = aggregate(rows, utc_day);
+
+        assert_eq!(stats.uploads, 4);
+        assert_eq!(stats.bytes, 1000);
+        assert_eq!(stats.average_bytes, 250);
+        assert_eq!(stats.active_days, 3);
+        assert_eq!(stats.first_upload_ms, Some(0));
+        assert_eq!(stats.last_upload_ms, Some(3 * DAY_MS));
+        assert_eq!(
+            stats.busiest_day,
+            Some(BusiestDay {
+                date: "1970-01-02".to_string(),
+                uploads: 2,
+                bytes: 500,
+            })
+        );
+
+        let hosts: Vec<(&str, u64, u64)> = stats
+            .hosts
+            .iter()
+            .map(|h| (h.service.as_str(), h.uploads, h.bytes))
+            .collect();
+        assert_eq!(hosts, [("r2", 2, 400), ("weibo", 2, 300), ("smms", 1, 400)]);
+        let share_sum: f64 = stats.hosts.iter().map(|h| h.share).sum();
+        assert!((share_sum - 1.0).abs() < 1e-9);
+        assert!((stats.hosts[0].share - 0.4).abs() < 1e-9);
+    }
+
+    #[test]
+    fn empty_history_yields_zeroes() {
+        let stats = aggregate(Vec::new(), utc_day);
+        assert_eq!(stats.uploads, 0);
+        assert_eq!(stats.average_bytes, 0);
+        assert!(stats.busiest_day.is_none());
+        assert!(stats.hosts.is_empty());
+    }
+
+    #[test]
+    fn resolves_ranges_and_rejects_invalid_ones() {
+        assert_eq!(resolve_range(&UsageRange::All, 0).unwrap(), (None, None));
+        assert_eq!(
+            resolve_range(&UsageRange::LastDays { days: 7 }, 10 * DAY_MS).unwrap(),
+            (Some(3 * DAY_MS), None)
+        );
+        let (from, to) = resolve_range(&UsageRange::Year { year: 2025 }, 0).unwrap();
+        let span = to.unwrap() - from.unwrap();
+        // 夏令时切换可能让一年差一小时
+        assert!((span - 365 * DAY_MS).abs() <= 60 * 60 * 1000);
+
+        assert!(resolve_range(&UsageRange::LastDays { days: 0 }, 0).is_err());
+        assert!(resolve_range(
+            &UsageRange::Custom {
+                from_ms: Some(5),
+                to_ms: Some(5)
+            },
+            0
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn deserializes_tagged_ranges() {
+        let range: UsageRange = serde_json::from_str(r#"{"kind":"lastDays","days":30}"#).unwrap();
+        assert!(matches!(range, UsageRange::LastDays { days: 30 }));
+        let range: UsageRange =
+            serde_json::from_str(r#"{"kind":"custom","fromMs":1,"toMs":null}"#).unwrap();
+        assert!(matches!(
+            range,
+            UsageRange::Custom {
+                from_ms: Some(1),
+                to_ms: None
+            }
+        ));
+    }
+}
@@ -1,3 +1,5 @@
+use super::{recent_dirs, tempfiles};
+use crate::atomic_file;
 use crate::error::AppError;
 use std::path::Path;
 use tauri::{AppHandle, Runtime};
@@ -50,6 +52,24 @@ fn selected_path(path: tauri_plugin_dialog::FilePath) -> Result<std::path::PathB
         .map_err(|e| AppError::file_io(format!("无法解析所选文件路径: {}", e)))
 }
 
+/// 对话框默认打开最近使用的目录
+fn with_directory<R: Runtime>(
+    builder: tauri_plugin_dialog::FileDialogBuilder<R>,
+    dir: Option<std::path::PathBuf>,
+) -> tauri_plugin_dialog::FileDialogBuilder<R> {
+    match dir {
+        Some(dir) => builder.set_directory(dir),
+        None => builder,
+    }
+}
+
+/// 记录所选文件的目录；失败只记日志，不影响本次读写
+async fn remember_dir(app: &AppHandle, path: &Path) {
+    if let Err(e) = recent_dirs::remember(app, path).await {
+        log::warn!("[最近目录] 记录失败: {}", e.message());
+    }
+}
+
 fn ensure_text_file_size(path: &Path) -> Result<(), AppError> {
     let metadata = std::fs::metadata(path)
         .map_err(|e| AppError::file_io(format!("无法访问所选文件: {}", e)))?;
@@ -74,15 +94,17 @@ pub async fn export_text_file(
         return Err(AppError::validation("默认文件名不合法"));
     }
 
-    let builder = app.dialog().file().set_file_name(default_path);
+    let recent_dir = recent_dirs::most_recent(&app).await;
+    let builder = with_directory(app.dialog().file().set_file_name(default_path), recent_dir);
     let builder = apply_filters(builder, filters)?;
     let Some(path) = builder.blocking_save_file() else {
         return Ok(None);
     };
     let path = selected_path(path)?;
 
-    std::fs::write(&path, content)
+    atomic_file::write(&path, content)
         .map_err(|e| AppError::file_io(format!("写入所选文件失败: {}", e)))?;
+    remember_dir(&app, &path).await;
 
     Ok(Some(path.to_string_lossy().to_string()))
 }
@@ -92,12 +114,14 @@ pub async fn import_text_file(
     app: AppHandle,
     filters: Vec<FileDialogFilter>,
 ) -> Result<Option<String>, AppError> {
-    let builder = apply_filters(app.dialog().file(), filters)?;
+    let recent_dir = recent_dirs::most_recent(&app).await;
+    let builder = apply_filters(with_directory(app.dialog().file(), recent_dir), filters)?;
     let Some(path) = builder.blocking_pick_file() else {
         return Ok(None);
     };
     let path = selected_path(path)?;
     ensure_text_file_size(&path)?;
+    remember_dir(&app, &path).await;
 
     std::fs::read_to_string(&path)
         .map(Some)
@@ -108,24 +132,10 @@ fn is_owned_temp_file(path: &Path) -> bool {
     let Some(file_name) = path.file_name().and_then(|name| name.to_str()) else {
         return false;
     };
-    if !OWNED_TEMP_PREFIXES
+    OWNED_TEMP_PREFIXES
         .iter()
         .any(|prefix| file_name.starts_with(prefix))
-    {
-        return false;
-    }
-
-    let Some(parent) = path.parent() else {
-        return false;
-    };
-    let Ok(parent) = parent.canonicalize() else {
-        return false;
-    };
-    let Ok(temp_dir) = std::env::temp_dir().canonicalize() else {
-        return false;
-    };
-
-    parent == temp_dir
+        && tempfiles::is_managed(path)
 }
 
 #[tauri::command]
@@ -134,13 +144,7 @@ pub fn cleanup_owned_temp_file(path: String) -> Result<bool, AppError> {
     if !is_owned_temp_file(path) {
         return Err(AppError::validation("只允许清理 PicNexus 创建的临时文件"));
     }
-    if !path.exists() {
-        return Ok(false);
-    }
-
-    std::fs::remove_file(path)
-        .map(|_| true)
-        .map_err(|e| AppError::file_io(format!("删除临时文件失败: {}", e)))
+    tempfiles::release(path)
 }
 
 #[cfg(test)]
@@ -149,7 +153,8 @@ mod tests {
 
     #[test]
     fn owned_temp_cleanup_rejects_unowned_path() {
-        let path = std::env::temp_dir().join("not_picnexus_url_temp.jpg");
+        // 前缀正确但不在应用临时目录中
+        let path = std::env::temp_dir().join("picnexus_url_unowned_temp.jpg");
         std::fs::write(&path, b"keep").expect("write temp marker");
 
         let result = cleanup_owned_temp_file(path.to_string_lossy().to_string());
@@ -159,11 +164,12 @@ mod tests {
         let _ = std::fs::remove_file(path);
     }
 
-    #[test]
-    fn owned_temp_cleanup_removes_picnexus_url_file() {
-        let path =
-            std::env::temp_dir().join(format!("picnexus_url_{}_test.jpg", std::process::id()));
-        std::fs::write(&path, b"temp").expect("write temp marker");
+    #[tokio::test]
+    async fn owned_temp_cleanup_removes_picnexus_url_file() {
+        let path = tempfiles::write("picnexus_url_", "jpg", b"temp")
+            .await
+            .expect("write temp marker")
+            .keep();
 
         let removed = cleanup_owned_temp_file(path.to_string_lossy().to_string())
             .expect("owned URL temp file should be removable");
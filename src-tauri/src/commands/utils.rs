@@ -1,11 +1,67 @@
 // src-tauri/src/commands/utils.rs
 // 通用工具函数
 
+use std::collections::VecDeque;
+use std::path::{Path, PathBuf};
+use std::sync::{LazyLock, Mutex};
+
+use bytes::Bytes;
+use sha2::{Digest, Sha256};
 use tokio::fs::File;
 use tokio::io::AsyncReadExt;
 
 use crate::error::AppError;
 
+/// 分块读取文件时的缓冲区大小
+const HASH_CHUNK_SIZE: usize = 64 * 1024;
+
+/// 边读边算的 SHA-256
+///
+/// 按块喂入，内存占用与文件大小无关
+#[derive(Default, Clone)]
+pub struct StreamingSha256 {
+    hasher: Sha256,
+}
+
+impl StreamingSha256 {
+    pub fn update(&mut self, chunk: &[u8]) {
+        self.hasher.update(chunk);
+    }
+
+    /// 返回小写十六进制摘要
+    pub fn finish(self) -> String {
+        hex::encode(self.hasher.finalize())
+    }
+}
+
+/// 分块计算文件 SHA-256，内存占用固定为一个缓冲区
+pub async fn sha256_file(path: &str) -> Result<String, AppError> {
+    let mut file = File::open(path)
+        .await
+        .map_err(|e| AppError::file_io(format!("无法打开文件: {}", e)))?;
+    let mut digest = StreamingSha256::default();
+    let mut buffer = vec![0u8; HASH_CHUNK_SIZE];
+    loop {
+        let n = file
+            .read(&mut buffer)
+            .await
+            .map_err(|e| AppError::file_io(format!("无法读取文件: {}", e)))?;
+        if n == 0 {
+            break;
+        }
+        digest.update(&buffer[..n]);
+    }
+    Ok(digest.finish())
+}
+
+fn file_too_large_error(size: u64, max_bytes: u64) -> AppError {
+    AppError::validation(format!(
+        "文件大小 ({:.2}MB) 超过读取上限 ({:.2}MB)",
+        size as f64 / 1024.0 / 1024.0,
+        max_bytes as f64 / 1024.0 / 1024.0,
+    ))
+}
+
 /// 读取文件到字节数组
 ///
 /// # 参数
@@ -13,9 +69,21 @@ use crate::error::AppError;
 /// - `max_bytes`: 允许读取的最大文件大小
 ///
 /// # 返回
-/// - `Ok((Vec<u8>, u64))`: 文件内容和文件大小
+/// - `Ok((Bytes, u64))`: 文件内容和文件大小（剪贴板副本与副本表共享内存，不复制）
 /// - `Err(AppError)`: 文件 IO 错误
-pub async fn read_file_bytes(path: &str, max_bytes: u64) -> Result<(Vec<u8>, u64), AppError> {
+pub async fn read_file_bytes(path: &str, max_bytes: u64) -> Result<(Bytes, u64), AppError> {
+    // 刚粘贴的剪贴板图片直接使用内存中的 PNG，不再重新读盘
+    if let Some(bytes) = super::clipboard::handoff_bytes(path) {
+        let size = bytes.len() as u64;
+        if size > max_bytes {
+            return Err(file_too_large_error(size, max_bytes));
+        }
+        return Ok((bytes, size));
+    }
+
+    // 读取期间登记引用，避免应用临时目录中的文件被并发的过期清扫删除
+    let _live = super::tempfiles::reference(Path::new(path));
+
     let file = File::open(path)
         .await
         .map_err(|e| AppError::file_io(format!("无法打开文件: {}", e)))?;
@@ -27,11 +95,7 @@ pub async fn read_file_bytes(path: &str, max_bytes: u64) -> Result<(Vec<u8>, u64
         .len();
 
     if file_size > max_bytes {
-        return Err(AppError::validation(format!(
-            "文件大小 ({:.2}MB) 超过读取上限 ({:.2}MB)",
-            file_size as f64 / 1024.0 / 1024.0,
-            max_bytes as f64 / 1024.0 / 1024.0,
-        )));
+        return Err(file_too_large_error(file_size, max_bytes));
     }
 
     let capacity = usize::try_from(file_size)
@@ -47,7 +111,106 @@ pub async fn read_file_bytes(path: &str, max_bytes: u64) -> Result<(Vec<u8>, u64
         return Err(AppError::validation("文件在读取期间增长并超过大小上限"));
     }
 
-    Ok((buffer, actual_size))
+    Ok((Bytes::from(buffer), actual_size))
+}
+
+/// 在系统文件管理器（访达 / 资源管理器）中定位文件，用于从历史记录跳转到本地归档副本
+#[tauri::command]
+pub fn reveal_in_file_manager(path: String) -> Result<(), AppError> {
+    let path = crate::validate_open_file_path(&path)?;
+    opener::reveal(&path)
+        .map_err(|e| AppError::file_io(format!("无法在文件管理器中显示 {}: {}", path.display(), e)))
+}
+
+/// 用系统默认应用打开本地文件（拒绝可执行文件、应用包与快捷方式）
+#[tauri::command]
+pub fn open_with_default_app(path: String) -> Result<(), AppError> {
+    let path = crate::validate_open_file_path(&path)?;
+    opener::open(&path)
+        .map_err(|e| AppError::file_io(format!("无法打开 {}: {}", path.display(), e)))
+}
+
+/// 最多记住的已上传源文件数（按登记先后淘汰）
+const UPLOADED_SOURCES_MAX: usize = 1024;
+
+/// 上传成功的本地源文件，「上传后删除原图」只能把这些文件移到回收站
+static UPLOADED_SOURCES: LazyLock<Mutex<VecDeque<PathBuf>>> =
+    LazyLock::new(|| Mutex::new(VecDeque::new()));
+
+/// 登记上传成功的源文件（各上传命令成功返回时调用）
+pub fn remember_uploaded_source(file_path: &str) {
+    let path = crate::path_utils::normalize_input(file_path);
+    let mut sources = UPLOADED_SOURCES.lock().unwrap_or_else(|e| e.into_inner());
+    if sources.contains(&path) {
+        return;
+    }
+    if sources.len() >= UPLOADED_SOURCES_MAX {
+        sources.pop_front();
+    }
+    sources.push_back(path);
+}
+
+fn is_uploaded_source(path: &Path) -> bool {
+    UPLOADED_SOURCES
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+        .iter()
+        .any(|source| source == path)
+}
+
+/// 校验要移到回收站的路径，返回规范化后的文件路径（阻塞调用）
+///
+/// 只接受已登记的上传源文件，或位于 `roots`（应用数据 / 缓存目录，本地归档副本所在处）内的文件；
+/// 路径先规范化再比较，`..` 与符号链接无法借道跳出允许范围
+fn resolve_trash_target(input: &str, roots: &[PathBuf]) -> Result<PathBuf, AppError> {
+    let path = crate::path_utils::normalize_input(input);
+    if !path.is_absolute() {
+        return Err(AppError::validation("只能删除绝对路径"));
+    }
+    let metadata = std::fs::symlink_metadata(&path)
+        .map_err(|e| AppError::file_io(format!("无法访问路径: {}", e)))?;
+    if metadata.is_dir() {
+        return Err(AppError::validation("只能把文件移到回收站，不支持文件夹"));
+    }
+
+    let canonical = crate::path_utils::canonicalize(&path)
+        .map_err(|e| AppError::file_io(format!("无法解析路径: {}", e)))?;
+    if !canonical.is_file() {
+        return Err(AppError::validation("只能把文件移到回收站，不支持文件夹"));
+    }
+    let in_roots = roots.iter().any(|root| {
+        crate::path_utils::canonicalize(root).is_ok_and(|root| canonical.starts_with(root))
+    });
+    if !in_roots && !is_uploaded_source(&path) && !is_uploaded_source(&canonical) {
+        return Err(AppError::validation(
+            "只能删除已上传的原图或应用目录内的本地归档副本",
+        ));
+    }
+    Ok(canonical)
+}
+
+/// 把本地文件移到系统回收站（废纸篓），用户仍可找回
+///
+/// 用于上传后删除原图、清理本地归档副本；只接受文件，不处理文件夹
+#[tauri::command]
+pub async fn trash_file(app: tauri::AppHandle, path: String) -> Result<(), AppError> {
+    let roots: Vec<PathBuf> = [
+        crate::portable::user_data_dir(&app),
+        crate::portable::cache_dir(&app),
+    ]
+    .into_iter()
+    .filter_map(Result::ok)
+    .collect();
+
+    tokio::task::spawn_blocking(move || {
+        let path = resolve_trash_target(&path, &roots)?;
+        trash::delete(&path)
+            .map_err(|e| AppError::file_io(format!("无法移到回收站 {}: {}", path.display(), e)))?;
+        log::info!("[回收站] 已移到回收站: {}", path.display());
+        Ok(())
+    })
+    .await
+    .map_err(|e| AppError::file_io(format!("移到回收站失败: {}", e)))?
 }
 
 #[cfg(test)]
@@ -96,7 +259,7 @@ mod tests {
             .await
             .expect("应能读取存在的文件");
 
-        assert_eq!(bytes, content);
+        assert_eq!(bytes, &content[..]);
         assert_eq!(size, content.len() as u64);
     }
 
@@ -135,7 +298,7 @@ mod tests {
         let result = read_file_bytes(bogus.to_str().unwrap(), 1024).await;
 
         match result {
-            Err(AppError::FileIo { message }) => {
+            Err(AppError::FileIo { message, .. }) => {
                 assert!(
                     message.contains("无法打开文件"),
                     "错误消息应指明打开失败: {}",
@@ -147,6 +310,29 @@ mod tests {
         }
     }
 
+    #[tokio::test]
+    async fn sha256_file_matches_one_shot_digest() {
+        // 跨越多个缓冲区，验证分块累加与一次性计算一致
+        let content: Vec<u8> = (0..HASH_CHUNK_SIZE * 2 + 17).map(|i| i as u8).collect();
+        let tmp = TempFile::new(&content);
+
+        let streamed = sha256_file(tmp.path.to_str().unwrap()).await.unwrap();
+
+        assert_eq!(streamed, hex::encode(Sha256::digest(&content)));
+    }
+
+    #[test]
+    fn streaming_sha256_matches_one_shot_digest() {
+        let mut digest = StreamingSha256::default();
+        digest.update(b"hello ");
+        digest.update(b"picnexus");
+
+        assert_eq!(
+            digest.finish(),
+            hex::encode(Sha256::digest(b"hello picnexus"))
+        );
+    }
+
     #[tokio::test]
     async fn rejects_sparse_file_from_metadata_before_reading() {
         let path = std::env::temp_dir().join(format!(
@@ -163,4 +349,33 @@ mod tests {
 
         assert!(matches!(result, Err(AppError::Validation { .. })));
     }
+
+    #[test]
+    fn trash_target_rejects_relative_paths_and_directories() {
+        let relative = resolve_trash_target("photo.png", &[]);
+        assert!(matches!(relative, Err(AppError::Validation { .. })));
+
+        let dir = std::env::temp_dir();
+        let folder = resolve_trash_target(&dir.to_string_lossy(), std::slice::from_ref(&dir));
+        assert!(matches!(folder, Err(AppError::Validation { .. })));
+    }
+
+    #[test]
+    fn trash_target_must_be_inside_roots_or_uploaded() {
+        let file = TempFile::new(b"original");
+        let path = file.path.to_string_lossy().to_string();
+        let elsewhere = std::env::temp_dir().join("picnexus_trash_root_missing");
+
+        let outside = resolve_trash_target(&path, std::slice::from_ref(&elsewhere));
+        assert!(matches!(outside, Err(AppError::Validation { .. })));
+
+        let in_root = resolve_trash_target(&path, &[std::env::temp_dir()]).unwrap();
+        assert_eq!(
+            in_root,
+            crate::path_utils::canonicalize(&file.path).unwrap()
+        );
+
+        remember_uploaded_source(&path);
+        assert!(resolve_trash_target(&path, &[elsewhere]).is_ok());
+    }
 }
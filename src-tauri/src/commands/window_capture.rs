@@ -0,0 +1,244 @@
+// src-tauri/src/commands/window_capture.rs
+// 窗口截图：截取单个应用窗口并送入上传队列，写文档时不必再手动裁剪全屏截图
+//
+// 工作流：
+//   list_capturable_windows() -> 前端列出可截取的窗口供选择
+//   capture_window(window, upload?) -> 按 ID 或标题匹配窗口 -> 截取窗口内容并编码为 PNG
+//   -> 写入应用临时目录 -> 默认按「打开方式」的路径加入上传队列（见 open_files）
+//
+// 只截取目标窗口自身的内容，PicNexus 自己的窗口（主窗口、托盘菜单等）不参与匹配。
+// 最小化的窗口没有可截取的内容，同样跳过。
+
+use std::io::Cursor;
+
+use image::ImageFormat;
+use serde::Serialize;
+
+use super::{cpu_pool, tempfiles};
+use crate::error::AppError;
+use crate::log_utils::safe_path;
+
+const CAPTURE_TEMP_PREFIX: &str = "window_capture_";
+const CAPTURE_TEMP_EXTENSION: &str = "png";
+
+/// 可截取的窗口
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CapturableWindow {
+    pub id: u32,
+    pub title: String,
+    pub app_name: String,
+    #[serde(skip)]
+    pid: u32,
+    #[serde(skip)]
+    minimized: bool,
+}
+
+/// capture_window 的返回值
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CapturedWindow {
+    pub id: u32,
+    pub title: String,
+    pub app_name: String,
+    pub width: u32,
+    pub height: u32,
+    /// 截图临时文件路径
+    pub file_path: String,
+    /// 是否已加入上传队列
+    pub queued: bool,
+}
+
+fn capture_error(e: impl std::fmt::Display) -> AppError {
+    AppError::external(format!("截取窗口失败: {}", e))
+}
+
+/// 系统中的所有窗口（按前后顺序，最前面的在前）
+fn enumerate_windows() -> Result<Vec<(CapturableWindow, xcap::Window)>, AppError> {
+    let windows = xcap::Window::all().map_err(capture_error)?;
+    Ok(windows
+        .into_iter()
+        .filter_map(|window| {
+            let info = CapturableWindow {
+                id: window.id().ok()?,
+                title: window.title().unwrap_or_default(),
+                app_name: window.app_name().unwrap_or_default(),
+                pid: window.pid().unwrap_or_default(),
+                minimized: window.is_minimized().unwrap_or(false),
+            };
+            Some((info, window))
+        })
+        .collect())
+}
+
+/// 能否截取：排除本进程窗口、最小化窗口与没有标题的辅助窗口
+fn is_capturable(window: &CapturableWindow, own_pid: u32) -> bool {
+    window.pid != own_pid && !window.minimized && !window.title.trim().is_empty()
+}
+
+/// 按窗口 ID 或标题选出目标窗口的下标
+///
+/// 纯数字先按 ID 匹配；标题优先完全一致（不区分大小写），
+/// 其次是标题或应用名包含关键字的最前面一个窗口
+fn select_window(
+    windows: &[CapturableWindow],
+    query: &str,
+    own_pid: u32,
+) -> Result<usize, AppError> {
+    let query = query.trim();
+    if query.is_empty() {
+        return Err(AppError::validation("请指定窗口标题或 ID"));
+    }
+    let candidates: Vec<usize> = (0..windows.len())
+        .filter(|&i| is_capturable(&windows[i], own_pid))
+        .collect();
+
+    if let Ok(id) = query.parse::<u32>() {
+        if let Some(&i) = candidates.iter().find(|&&i| windows[i].id == id) {
+            return Ok(i);
+        }
+    }
+    let needle = query.to_lowercase();
+    candidates
+        .iter()
+        .find(|&&i| windows[i].title.to_lowercase() == needle)
+        .or_else(|| {
+            candidates.iter().find(|&&i| {
+                windows[i].title.to_lowercase().contains(&needle)
+                    || windows[i].app_name.to_lowercase().contains(&needle)
+            })
+        })
+        .copied()
+        .ok_or_else(|| AppError::validation(format!("未找到可截取的窗口: {}", query)))
+}
+
+/// 截取窗口并编码为 PNG（阻塞，在 CPU 线程池中执行）
+fn capture_png(query: String) -> Result<(CapturableWindow, u32, u32, Vec<u8>), AppError> {
+    let (infos, mut windows): (Vec<_>, Vec<_>) = enumerate_windows()?.into_iter().unzip();
+    let index = select_window(&infos, &query, std::process::id())?;
+    let window = windows.swap_remove(index);
+    let image = window.capture_image().map_err(capture_error)?;
+    let (width, height) = image.dimensions();
+    if width == 0 || height == 0 {
+        return Err(capture_error("窗口内容为空"));
+    }
+
+    let mut png = Cursor::new(Vec::new());
+    image
+        .write_to(&mut png, ImageFormat::Png)
+        .map_err(|e| AppError::file_io(format!("PNG 编码失败: {}", e)))?;
+    Ok((infos[index].clone(), width, height, png.into_inner()))
+}
+
+/// 列出可截取的窗口（不含 PicNexus 自身与最小化的窗口）
+#[tauri::command]
+pub async fn list_capturable_windows() -> Result<Vec<CapturableWindow>, AppError> {
+    // 平台窗口句柄不一定能跨线程传递，只把窗口信息带回来
+    cpu_pool::spawn(|| {
+        let own_pid = std::process::id();
+        enumerate_windows().map(|windows| {
+            windows
+                .into_iter()
+                .map(|(info, _)| info)
+                .filter(|info| is_capturable(info, own_pid))
+                .collect()
+        })
+    })
+    .await?
+}
+
+/// 截取单个窗口（`window` 为窗口 ID 或标题关键字），默认加入上传队列
+#[tauri::command]
+pub async fn capture_window(
+    app: tauri::AppHandle,
+    window: String,
+    upload: Option<bool>,
+) -> Result<CapturedWindow, AppError> {
+    let (info, width, height, png) = cpu_pool::spawn(move || capture_png(window)).await??;
+
+    let temp_file =
+        tempfiles::write(CAPTURE_TEMP_PREFIX, CAPTURE_TEMP_EXTENSION, png.as_slice()).await?;
+    let file_path = temp_file.keep().to_string_lossy().to_string();
+    log::info!(
+        "[窗口截图] 已截取 {}（{}x{}）: {}",
+        info.title,
+        width,
+        height,
+        safe_path(&file_path)
+    );
+
+    let queued = upload.unwrap_or(true);
+    if queued {
+        crate::open_files::handle_open_files(&app, vec![file_path.clone()]);
+    }
+
+    Ok(CapturedWindow {
+        id: info.id,
+        title: info.title,
+        app_name: info.app_name,
+        width,
+        height,
+        file_path,
+        queued,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const OWN_PID: u32 = 100;
+
+    fn window(id: u32, title: &str, app_name: &str, pid: u32) -> CapturableWindow {
+        CapturableWindow {
+            id,
+            title: title.to_string(),
+            app_name: app_name.to_string(),
+            pid,
+            minimized: false,
+        }
+    }
+
+    fn windows() -> Vec<CapturableWindow> {
+        let mut minimized = window(4, "Terminal", "Terminal", 40);
+        minimized.minimized = true;
+        vec![
+            window(1, "PicNexus", "picnexus", OWN_PID),
+            window(2, "README.md - Visual Studio Code", "Code", 20),
+            window(3, "Code Settings", "Code", 20),
+            minimized,
+            window(5, "", "Dock", 50),
+            window(6, "Settings", "System Settings", 60),
+        ]
+    }
+
+    #[test]
+    fn selects_by_id_then_exact_title_then_keyword() {
+        let windows = windows();
+        assert_eq!(select_window(&windows, "3", OWN_PID).unwrap(), 2);
+        // 完全一致（不区分大小写）的标题优先于更靠前的包含匹配
+        assert_eq!(select_window(&windows, "SETTINGS", OWN_PID).unwrap(), 5);
+        assert_eq!(
+            select_window(&windows, "visual studio", OWN_PID).unwrap(),
+            1
+        );
+        assert_eq!(select_window(&windows, "code", OWN_PID).unwrap(), 1);
+    }
+
+    #[test]
+    fn skips_own_minimized_and_untitled_windows() {
+        let windows = windows();
+        assert!(select_window(&windows, "1", OWN_PID).is_err());
+        assert!(select_window(&windows, "PicNexus", OWN_PID).is_err());
+        assert!(select_window(&windows, "Terminal", OWN_PID).is_err());
+        assert!(select_window(&windows, "Dock", OWN_PID).is_err());
+        assert!(select_window(&windows, "  ", OWN_PID).is_err());
+
+        let listed: Vec<u32> = windows
+            .iter()
+            .filter(|w| is_capturable(w, OWN_PID))
+            .map(|w| w.id)
+            .collect();
+        assert_eq!(listed, vec![2, 3, 6]);
+    }
+}
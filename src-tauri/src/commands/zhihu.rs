@@ -14,13 +14,19 @@ use base64::{engine::general_purpose::STANDARD, Engine};
 use hmac::{Hmac, Mac};
 use md5::{Digest, Md5};
 use regex::Regex;
+use reqwest::header::HeaderMap;
 use reqwest::Client;
 use serde::{Deserialize, Serialize};
 use sha1::Sha1;
 use std::time::Duration;
 use tauri::Window;
 
-use super::utils::read_file_bytes;
+use super::cpu_pool;
+use super::host_capabilities;
+use super::net_limiter::NetworkLimiter;
+use super::request_headers::{resolve_request_headers, RequestHeaderTemplate, RequestHeadersExt};
+use super::task_registry::TaskRegistry;
+use super::utils::{read_file_bytes, remember_uploaded_source};
 use crate::error::{AppError, IntoAppError};
 use crate::log_utils::{safe_path, safe_url, summarize_text};
 
@@ -132,10 +138,30 @@ fn get_mime_type(ext: &str) -> &'static str {
 #[tauri::command]
 pub async fn upload_to_zhihu(
     _window: Window,
-    _id: String,
+    id: String,
     file_path: String,
     zhihu_cookie: String,
+    request_headers: Option<RequestHeaderTemplate>,
+    limiter: tauri::State<'_, NetworkLimiter>,
+    tasks: tauri::State<'_, TaskRegistry>,
 ) -> Result<ZhihuUploadResult, AppError> {
+    tasks
+        .run(
+            Some(&id),
+            upload_to_zhihu_with_retry(file_path.clone(), zhihu_cookie, request_headers, limiter),
+        )
+        .await
+        .inspect(|_| remember_uploaded_source(&file_path))
+}
+
+async fn upload_to_zhihu_with_retry(
+    file_path: String,
+    zhihu_cookie: String,
+    request_headers: Option<RequestHeaderTemplate>,
+    limiter: tauri::State<'_, NetworkLimiter>,
+) -> Result<ZhihuUploadResult, AppError> {
+    let _permit = limiter.acquire("zhihu").await;
+    let extra_headers = resolve_request_headers(request_headers.as_ref());
     let mut last_error: Option<AppError> = None;
 
     for attempt in 0..=MAX_UPLOAD_RETRIES {
@@ -145,7 +171,7 @@ pub async fn upload_to_zhihu(
             tokio::time::sleep(Duration::from_secs(delay as u64)).await;
         }
 
-        match upload_to_zhihu_inner(&file_path, &zhihu_cookie).await {
+        match upload_to_zhihu_inner(&file_path, &zhihu_cookie, &extra_headers).await {
             Ok(result) => return Ok(result),
             Err(e) => {
                 // 只对"图片处理超时"错误进行重试
@@ -172,11 +198,13 @@ pub async fn upload_to_zhihu(
 async fn upload_to_zhihu_inner(
     file_path: &str,
     zhihu_cookie: &str,
+    extra_headers: &HeaderMap,
 ) -> Result<ZhihuUploadResult, AppError> {
     log::info!("[Zhihu] 开始上传文件: {}", safe_path(file_path));
 
     // 1. 读取文件
-    let (buffer, file_size) = read_file_bytes(file_path, 50 * 1024 * 1024).await?;
+    let (buffer, file_size) =
+        read_file_bytes(file_path, host_capabilities::read_limit("zhihu")).await?;
 
     // 2. 验证文件类型（只允许图片）
     let file_name = std::path::Path::new(&file_path)
@@ -190,16 +218,17 @@ async fn upload_to_zhihu_inner(
         .ok_or_else(|| AppError::validation("无法获取文件扩展名"))?
         .to_lowercase();
 
-    if !["jpg", "jpeg", "png", "gif", "webp"].contains(&ext.as_str()) {
-        return Err(AppError::validation(
-            "只支持 JPG、PNG、GIF、WebP 格式的图片",
-        ));
-    }
+    host_capabilities::check_admission("zhihu", &ext, file_size)?;
 
     let content_type = get_mime_type(&ext);
 
-    // 3. 计算图片 MD5
-    let image_hash = calculate_md5(&buffer);
+    // 3. 计算图片 MD5（大图哈希放到 CPU 线程池，避免阻塞异步运行时）
+    let (buffer, image_hash) = cpu_pool::spawn(move || {
+        let hash = calculate_md5(&buffer);
+        (buffer, hash)
+    })
+    .await
+    .map_err(|e| AppError::external(format!("计算图片 MD5 失败: {}", e)))?;
     log::debug!("[Zhihu] 图片 MD5: {}", image_hash);
 
     // 4. 获取上传凭证
@@ -215,6 +244,7 @@ async fn upload_to_zhihu_inner(
         .header("User-Agent", "Mozilla/5.0 (Windows NT 10.0; Win64; x64) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/120.0.0.0 Safari/537.36")
         .header("Referer", "https://www.zhihu.com/")
         .header("Origin", "https://www.zhihu.com")
+        .with_request_headers(extra_headers)
         .json(&serde_json::json!({
             "image_hash": image_hash,
             "source": "pin"
@@ -260,7 +290,7 @@ async fn upload_to_zhihu_inner(
     let final_url = if credentials.upload_file.state == 1 {
         // 图片已存在，直接查询状态获取 URL
         log::info!("[Zhihu] 图片已存在，跳过上传");
-        poll_image_status(&client, zhihu_cookie, &image_id, 30).await?
+        poll_image_status(&client, zhihu_cookie, extra_headers, &image_id, 30).await?
     } else {
         // 需要上传到 OSS
         let upload_token = credentials
@@ -317,6 +347,7 @@ async fn upload_to_zhihu_inner(
             .header("User-Agent", "Mozilla/5.0 (Windows NT 10.0; Win64; x64) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/120.0.0.0 Safari/537.36")
             .header("Referer", "https://www.zhihu.com/")
             .header("Origin", "https://www.zhihu.com")
+            .with_request_headers(extra_headers)
             .json(&serde_json::json!({
                 "upload_result": "success"
             }))
@@ -333,7 +364,7 @@ async fn upload_to_zhihu_inner(
         log::debug!("[Zhihu] 开始轮询图片状态");
 
         // 5.3 轮询图片状态
-        poll_image_status(&client, zhihu_cookie, &image_id, 30).await?
+        poll_image_status(&client, zhihu_cookie, extra_headers, &image_id, 30).await?
     };
 
     // 6. 标准化 URL
@@ -353,6 +384,7 @@ async fn upload_to_zhihu_inner(
 async fn poll_image_status(
     client: &Client,
     cookie: &str,
+    extra_headers: &HeaderMap,
     image_id: &str,
     max_attempts: u32,
 ) -> Result<String, AppError> {
@@ -364,6 +396,7 @@ async fn poll_image_status(
             .header("Cookie", cookie)
             .header("User-Agent", "Mozilla/5.0 (Windows NT 10.0; Win64; x64) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/120.0.0.0 Safari/537.36")
             .header("Referer", "https://www.zhihu.com/")
+            .with_request_headers(extra_headers)
             .send()
             .await
             .into_network_err_with("查询图片状态失败")?;
@@ -0,0 +1,142 @@
+// src-tauri/src/dbus_service.rs
+// Linux DBus 服务：让平铺式窗口管理器用户通过脚本绑定快捷键
+//
+// 工作流：
+//   主实例启动 -> 在 session bus 上注册 com.picnexus.PicNexus
+//   -> busctl / gdbus / dbus-send 调用方法 -> 复用「打开方式」与快捷操作的分发逻辑
+//
+// 示例（sway / i3 配置中绑定）：
+//   busctl --user call com.picnexus.PicNexus /com/picnexus/PicNexus \
+//       com.picnexus.PicNexus1 UploadFiles as 1 /home/me/shot.png
+//   busctl --user call com.picnexus.PicNexus /com/picnexus/PicNexus \
+//       com.picnexus.PicNexus1 UploadClipboard
+//   busctl --user call com.picnexus.PicNexus /com/picnexus/PicNexus \
+//       com.picnexus.PicNexus1 GetRecent u 5
+
+use std::path::{Path, PathBuf};
+
+use tauri::Manager;
+
+use crate::open_files::{handle_open_files, resolve_open_files};
+use crate::quick_actions::{dispatch_quick_action, QuickAction};
+use crate::server::api_handler::{query_history, HistoryQuery};
+
+pub const DBUS_BUS_NAME: &str = "com.picnexus.PicNexus";
+pub const DBUS_OBJECT_PATH: &str = "/com/picnexus/PicNexus";
+
+/// GetRecent 单次最多返回的条数
+const MAX_RECENT_ITEMS: u32 = 50;
+
+/// 持有 session bus 连接；连接被 drop 后服务名会自动释放
+pub struct DbusConnection(#[allow(dead_code)] pub zbus::Connection);
+
+struct PicNexusInterface {
+    app: tauri::AppHandle,
+    history_db_path: Option<PathBuf>,
+}
+
+/// GetRecent 返回的单条记录：(id, 时间戳毫秒, 文件名, 链接)
+type RecentItem = (String, i64, String, String);
+
+/// DBus 调用方没有工作目录语义，只接受绝对路径
+fn absolute_paths(paths: &[String]) -> Result<Vec<String>, String> {
+    if let Some(relative) = paths.iter().find(|path| !Path::new(path).is_absolute()) {
+        return Err(format!("只接受绝对路径: {}", relative));
+    }
+    Ok(resolve_open_files(paths, Path::new("/")))
+}
+
+#[zbus::interface(name = "com.picnexus.PicNexus1")]
+impl PicNexusInterface {
+    /// 把本地图片加入上传队列，返回实际入队的文件数
+    async fn upload_files(&self, paths: Vec<String>) -> zbus::fdo::Result<u32> {
+        let files = absolute_paths(&paths).map_err(zbus::fdo::Error::InvalidArgs)?;
+        if files.is_empty() {
+            return Err(zbus::fdo::Error::InvalidArgs(
+                "没有可上传的图片文件".to_string(),
+            ));
+        }
+
+        let count = files.len() as u32;
+        log::info!("[DBus] UploadFiles: {} 个文件", count);
+        handle_open_files(&self.app, files);
+        Ok(count)
+    }
+
+    /// 上传剪贴板图片（与托盘菜单「上传剪贴板」一致）
+    async fn upload_clipboard(&self) {
+        log::info!("[DBus] UploadClipboard");
+        dispatch_quick_action(&self.app, QuickAction::UploadClipboard);
+    }
+
+    /// 返回最近上传的记录，按时间倒序
+    async fn get_recent(&self, limit: u32) -> zbus::fdo::Result<Vec<RecentItem>> {
+        let Some(db_path) = self.history_db_path.as_deref() else {
+            return Err(zbus::fdo::Error::Failed("历史记录数据库不可用".to_string()));
+        };
+
+        let query = HistoryQuery {
+            limit: Some(limit.clamp(1, MAX_RECENT_ITEMS)),
+            ..Default::default()
+        };
+        let response = query_history(db_path, &query)
+            .await
+            .map_err(zbus::fdo::Error::Failed)?;
+
+        Ok(response
+            .items
+            .into_iter()
+            .map(|item| (item.id, item.timestamp, item.file_name, item.link))
+            .collect())
+    }
+}
+
+/// 在 session bus 上注册服务，连接交给 Tauri 托管以保持存活
+pub async fn start_dbus_service(app: tauri::AppHandle) -> zbus::Result<()> {
+    let history_db_path = crate::portable::history_db_path(&app)
+        .map_err(|e| log::warn!("[DBus] 历史记录数据库路径不可用，GetRecent 将被禁用: {}", e))
+        .ok();
+    let interface = PicNexusInterface {
+        app: app.clone(),
+        history_db_path,
+    };
+
+    let connection = zbus::connection::Builder::session()?
+        .name(DBUS_BUS_NAME)?
+        .serve_at(DBUS_OBJECT_PATH, interface)?
+        .build()
+        .await?;
+
+    log::info!("[DBus] 服务已注册: {}", DBUS_BUS_NAME);
+    app.manage(DbusConnection(connection));
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn absolute_paths_rejects_relative_paths() {
+        let err = absolute_paths(&["/tmp/a.png".to_string(), "b.png".to_string()]).unwrap_err();
+        assert!(err.contains("b.png"));
+    }
+
+    #[test]
+    fn absolute_paths_filters_missing_and_non_image_files() {
+        let dir = std::env::temp_dir().join(format!("picnexus-dbus-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        std::fs::write(dir.join("a.png"), b"fake").unwrap();
+        std::fs::write(dir.join("notes.txt"), b"text").unwrap();
+
+        let files = absolute_paths(&[
+            dir.join("a.png").to_string_lossy().to_string(),
+            dir.join("notes.txt").to_string_lossy().to_string(),
+            dir.join("missing.jpg").to_string_lossy().to_string(),
+        ])
+        .unwrap();
+
+        assert_eq!(files, vec![dir.join("a.png").to_string_lossy().to_string()]);
+        let _ = std::fs::remove_dir_all(dir);
+    }
+}
@@ -0,0 +1,285 @@
+// src-tauri/src/deep_link.rs
+// 自定义 URL Scheme：picnexus://
+//
+// 支持的动作：
+//   picnexus://upload?path=<本地图片路径>[&path=...]  把本地图片加入上传队列
+//   picnexus://rehost?url=<图片 URL>                  下载网络图片后重新上传
+//
+// 工作流：
+//   浏览器书签 / 其他应用打开链接 -> tauri-plugin-deep-link 收到 URL
+//   -> 已有实例：single-instance（deep-link feature）转发给主实例 on_open_url
+//   -> 冷启动：setup 中通过 get_current 读取
+//   -> 解析后放入 PendingDeepLinks 并发送 deep-link 事件，前端上传页取走执行
+//
+// 链接可能来自任意网页，upload 动作只接受已存在的本地图片文件，
+// 且前端在加入队列前会弹窗确认。
+
+use std::path::Path;
+use std::sync::Mutex;
+
+use serde::Serialize;
+use tauri::{Emitter, Manager};
+use url::Url;
+
+use crate::path_utils;
+
+pub const DEEP_LINK_SCHEME: &str = "picnexus";
+
+/// 单个链接最多携带的文件数
+const MAX_DEEP_LINK_PATHS: usize = 50;
+
+pub(crate) const DEEP_LINK_IMAGE_EXTENSIONS: &[&str] = &[
+    "png", "jpg", "jpeg", "gif", "webp", "bmp", "avif", "svg", "ico", "tif", "tiff",
+];
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(tag = "action", rename_all = "camelCase")]
+pub enum DeepLinkAction {
+    Upload { paths: Vec<String> },
+    Rehost { url: String },
+}
+
+/// 待前端处理的深度链接（冷启动时前端监听器尚未就绪，统一先入队）
+#[derive(Default)]
+pub struct PendingDeepLinks(pub Mutex<Vec<DeepLinkAction>>);
+
+/// 判断命令行参数是否为 picnexus:// 链接（Windows / Linux 通过 argv 传入）
+pub fn is_deep_link_arg(arg: &str) -> bool {
+    let prefix = format!("{}://", DEEP_LINK_SCHEME);
+    arg.get(..prefix.len())
+        .is_some_and(|head| head.eq_ignore_ascii_case(&prefix))
+}
+
+pub(crate) fn is_supported_image_path(path: &Path) -> bool {
+    path.extension()
+        .and_then(|ext| ext.to_str())
+        .is_some_and(|ext| {
+            DEEP_LINK_IMAGE_EXTENSIONS
+                .iter()
+                .any(|allowed| ext.eq_ignore_ascii_case(allowed))
+        })
+}
+
+fn parse_upload_paths(url: &Url) -> Result<Vec<String>, String> {
+    let paths: Vec<String> = url
+        .query_pairs()
+        .filter(|(key, _)| key == "path")
+        .map(|(_, value)| {
+            path_utils::normalize_input(&value)
+                .to_string_lossy()
+                .into_owned()
+        })
+        .filter(|value| !value.is_empty())
+        .collect();
+
+    if paths.is_empty() {
+        return Err("upload 链接缺少 path 参数".to_string());
+    }
+    if paths.len() > MAX_DEEP_LINK_PATHS {
+        return Err(format!("单个链接最多上传 {} 个文件", MAX_DEEP_LINK_PATHS));
+    }
+
+    for raw in &paths {
+        let path = Path::new(raw);
+        if !path.is_absolute() {
+            return Err(format!("文件路径必须是绝对路径: {}", raw));
+        }
+        if !is_supported_image_path(path) {
+            return Err(format!("不支持的图片格式: {}", raw));
+        }
+        if !path.is_file() {
+            return Err(format!("文件不存在: {}", raw));
+        }
+    }
+
+    Ok(paths)
+}
+
+fn parse_rehost_url(url: &Url) -> Result<String, String> {
+    let target = url
+        .query_pairs()
+        .find(|(key, _)| key == "url")
+        .map(|(_, value)| value.trim().to_string())
+        .filter(|value| !value.is_empty())
+        .ok_or_else(|| "rehost 链接缺少 url 参数".to_string())?;
+
+    let parsed = Url::parse(&target).map_err(|e| format!("无效的图片 URL: {}", e))?;
+    if !matches!(parsed.scheme(), "http" | "https") {
+        return Err(format!("仅支持 http/https 图片 URL: {}", target));
+    }
+
+    Ok(target)
+}
+
+/// 解析 picnexus:// 链接
+pub fn parse_deep_link(raw: &str) -> Result<DeepLinkAction, String> {
+    let url = Url::parse(raw.trim()).map_err(|e| format!("无效的链接: {}", e))?;
+    if url.scheme() != DEEP_LINK_SCHEME {
+        return Err(format!("不支持的协议: {}", url.scheme()));
+    }
+
+    // picnexus://upload?... 中动作名解析为 host；兼容 picnexus:upload?... 写法
+    let action = url
+        .host_str()
+        .filter(|host| !host.is_empty())
+        .unwrap_or_else(|| url.path().trim_matches('/'))
+        .to_ascii_lowercase();
+
+    match action.as_str() {
+        "upload" => parse_upload_paths(&url).map(|paths| DeepLinkAction::Upload { paths }),
+        "rehost" => parse_rehost_url(&url).map(|url| DeepLinkAction::Rehost { url }),
+        other => Err(format!("未知的链接动作: {}", other)),
+    }
+}
+
+/// 解析并排队一组链接；返回是否有新的有效动作
+fn enqueue_deep_links<I, S>(app: &tauri::AppHandle, urls: I) -> bool
+where
+    I: IntoIterator<Item = S>,
+    S: AsRef<str>,
+{
+    let actions: Vec<DeepLinkAction> = urls
+        .into_iter()
+        .filter_map(|raw| match parse_deep_link(raw.as_ref()) {
+            Ok(action) => Some(action),
+            Err(e) => {
+                log::warn!("[DeepLink] 忽略无效链接 {}: {}", raw.as_ref(), e);
+                None
+            }
+        })
+        .collect();
+
+    if actions.is_empty() {
+        return false;
+    }
+
+    let pending = app.state::<PendingDeepLinks>();
+    let queued = match pending.0.lock() {
+        Ok(mut queue) => {
+            queue.extend(actions);
+            true
+        }
+        Err(e) => {
+            log::error!("[DeepLink] 待处理队列锁异常: {}", e);
+            false
+        }
+    };
+    queued
+}
+
+/// 处理收到的链接：入队后显示主窗口并切换到上传页，由前端取走执行
+pub fn handle_deep_links<I, S>(app: &tauri::AppHandle, urls: I)
+where
+    I: IntoIterator<Item = S>,
+    S: AsRef<str>,
+{
+    if !enqueue_deep_links(app, urls) {
+        return;
+    }
+
+    let Some(window) = app.get_webview_window("main") else {
+        log::warn!("[DeepLink] 主窗口不存在，链接将在前端就绪后处理");
+        return;
+    };
+    let _ = window.unminimize();
+    let _ = window.show();
+    let _ = window.set_focus();
+    let _ = window.emit("navigate-to", "upload");
+    let _ = window.emit("deep-link", ());
+}
+
+/// 取走全部待处理的深度链接
+#[tauri::command]
+pub fn take_pending_deep_links(pending: tauri::State<'_, PendingDeepLinks>) -> Vec<DeepLinkAction> {
+    pending
+        .0
+        .lock()
+        .map(|mut queue| std::mem::take(&mut *queue))
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn temp_image(name: &str) -> std::path::PathBuf {
+        let path = std::env::temp_dir().join(format!(
+            "picnexus-deep-link-{}-{}",
+            std::process::id(),
+            name
+        ));
+        std::fs::write(&path, b"fake").unwrap();
+        path
+    }
+
+    #[test]
+    fn is_deep_link_arg_matches_scheme_case_insensitively() {
+        assert!(is_deep_link_arg("picnexus://upload?path=a"));
+        assert!(is_deep_link_arg("PicNexus://rehost?url=b"));
+        assert!(!is_deep_link_arg("picnexus:"));
+        assert!(!is_deep_link_arg("./picnexus.png"));
+    }
+
+    #[test]
+    fn parse_upload_link_collects_paths() {
+        let first = temp_image("a.png");
+        let second = temp_image("b.JPG");
+        let link = format!(
+            "picnexus://upload?path={}&path={}",
+            urlencoding::encode(&first.to_string_lossy()),
+            urlencoding::encode(&second.to_string_lossy())
+        );
+
+        assert_eq!(
+            parse_deep_link(&link),
+            Ok(DeepLinkAction::Upload {
+                paths: vec![
+                    first.to_string_lossy().to_string(),
+                    second.to_string_lossy().to_string()
+                ]
+            })
+        );
+
+        let _ = std::fs::remove_file(first);
+        let _ = std::fs::remove_file(second);
+    }
+
+    #[test]
+    fn parse_upload_link_rejects_unsafe_paths() {
+        assert!(parse_deep_link("picnexus://upload").is_err());
+        assert!(parse_deep_link("picnexus://upload?path=relative.png").is_err());
+
+        let document = temp_image("secret.txt");
+        let link = format!(
+            "picnexus://upload?path={}",
+            urlencoding::encode(&document.to_string_lossy())
+        );
+        assert!(parse_deep_link(&link).is_err());
+        let _ = std::fs::remove_file(document);
+
+        let missing = std::env::temp_dir().join("picnexus-deep-link-missing.png");
+        let link = format!(
+            "picnexus://upload?path={}",
+            urlencoding::encode(&missing.to_string_lossy())
+        );
+        assert!(parse_deep_link(&link).is_err());
+    }
+
+    #[test]
+    fn parse_rehost_link_requires_http_url() {
+        assert_eq!(
+            parse_deep_link("picnexus://rehost?url=https%3A%2F%2Fexample.com%2Fa.png"),
+            Ok(DeepLinkAction::Rehost {
+                url: "https://example.com/a.png".to_string()
+            })
+        );
+        assert!(parse_deep_link("picnexus://rehost").is_err());
+        assert!(parse_deep_link("picnexus://rehost?url=file%3A%2F%2F%2Fetc%2Fpasswd").is_err());
+    }
+
+    #[test]
+    fn parse_deep_link_rejects_unknown_action_or_scheme() {
+        assert!(parse_deep_link("picnexus://delete?id=1").is_err());
+        assert!(parse_deep_link("https://upload?path=a.png").is_err());
+    }
+}
@@ -0,0 +1,379 @@
+// src-tauri/src/editor_protocol.rs
+// 编辑器扩展 stdio 协议（供 VS Code 等编辑器扩展以子进程方式调用）
+//
+// 工作流：
+//   扩展启动 picnexus --stdio -> 每行一条 JSON 请求（JSON Lines）
+//   -> 按顺序处理 -> stdout 输出 progress / result / error 帧（同样每行一条）
+//   -> stdin 关闭或收到 shutdown 后退出
+//
+// 协议版本见 EDITOR_PROTOCOL_VERSION，完整说明见 docs/reference/api/editor-protocol.md。
+// stdout 只写协议帧，日志一律写 stderr。
+
+use std::io::{BufRead, Write};
+use std::path::Path;
+
+use serde::{Deserialize, Serialize};
+
+use crate::cli;
+use crate::server::upload_handler::upload_single_file;
+
+pub const EDITOR_STDIO_ARG: &str = "--stdio";
+/// 不兼容变更时递增；新增方法或可选字段不改变版本号
+pub const EDITOR_PROTOCOL_VERSION: u32 = 1;
+
+const EDITOR_METHODS: &[&str] = &["hello", "listServices", "upload", "shutdown"];
+/// 单次 upload 请求最多的文件数
+const MAX_UPLOAD_FILES: usize = 50;
+
+/// 稳定的错误码，扩展按 code 分支处理，message 仅用于展示
+pub mod error_code {
+    pub const PARSE_ERROR: &str = "PARSE_ERROR";
+    pub const INVALID_REQUEST: &str = "INVALID_REQUEST";
+    pub const METHOD_NOT_FOUND: &str = "METHOD_NOT_FOUND";
+    pub const INVALID_PARAMS: &str = "INVALID_PARAMS";
+    pub const CONFIG: &str = "CONFIG";
+    pub const UPLOAD_FAILED: &str = "UPLOAD_FAILED";
+}
+
+#[derive(Debug, Deserialize)]
+struct EditorRequest {
+    #[serde(default)]
+    id: Option<serde_json::Value>,
+    method: String,
+    #[serde(default)]
+    params: serde_json::Value,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct UploadParams {
+    files: Vec<String>,
+    #[serde(default)]
+    service: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct EditorError {
+    pub code: &'static str,
+    pub message: String,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UploadItem {
+    pub file: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub url: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub markdown: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UploadProgress {
+    pub index: usize,
+    pub total: usize,
+    pub file: String,
+    /// "uploading" | "done" | "failed"
+    pub stage: &'static str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub url: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+/// 输出帧：每个请求以一个 result 或 error 帧结束，之前可有任意个 progress 帧
+#[derive(Debug, Serialize)]
+#[serde(tag = "type", rename_all = "camelCase")]
+pub enum EditorFrame {
+    Progress {
+        id: Option<serde_json::Value>,
+        progress: UploadProgress,
+    },
+    Result {
+        id: Option<serde_json::Value>,
+        result: serde_json::Value,
+    },
+    Error {
+        id: Option<serde_json::Value>,
+        error: EditorError,
+    },
+}
+
+impl EditorFrame {
+    fn error(
+        id: Option<serde_json::Value>,
+        code: &'static str,
+        message: impl Into<String>,
+    ) -> Self {
+        Self::Error {
+            id,
+            error: EditorError {
+                code,
+                message: message.into(),
+            },
+        }
+    }
+}
+
+fn write_frame<W: Write>(writer: &mut W, frame: &EditorFrame) -> std::io::Result<()> {
+    let line = serde_json::to_string(frame).map_err(std::io::Error::other)?;
+    writer.write_all(line.as_bytes())?;
+    writer.write_all(b"\n")?;
+    writer.flush()
+}
+
+fn hello_result() -> serde_json::Value {
+    serde_json::json!({
+        "protocol": EDITOR_PROTOCOL_VERSION,
+        "version": env!("CARGO_PKG_VERSION"),
+        "methods": EDITOR_METHODS,
+        "services": cli::configured_services().unwrap_or_default(),
+    })
+}
+
+fn file_name_of(path: &str) -> String {
+    Path::new(path)
+        .file_name()
+        .map(|name| name.to_string_lossy().to_string())
+        .unwrap_or_else(|| path.to_string())
+}
+
+/// 逐个上传文件，每个文件开始和结束时各发一个 progress 帧
+async fn handle_upload<F>(
+    id: &Option<serde_json::Value>,
+    params: serde_json::Value,
+    mut emit: F,
+) -> EditorFrame
+where
+    F: FnMut(EditorFrame),
+{
+    let params: UploadParams = match serde_json::from_value(params) {
+        Ok(params) => params,
+        Err(e) => return EditorFrame::error(id.clone(), error_code::INVALID_PARAMS, e.to_string()),
+    };
+    if params.files.is_empty() || params.files.len() > MAX_UPLOAD_FILES {
+        return EditorFrame::error(
+            id.clone(),
+            error_code::INVALID_PARAMS,
+            format!("files 需要 1-{} 个文件路径", MAX_UPLOAD_FILES),
+        );
+    }
+    if let Some(relative) = params.files.iter().find(|f| !Path::new(f).is_absolute()) {
+        return EditorFrame::error(
+            id.clone(),
+            error_code::INVALID_PARAMS,
+            format!("只接受绝对路径: {}", relative),
+        );
+    }
+
+    let (service, config) = match cli::resolve_service_config(params.service.as_deref()) {
+        Ok(resolved) => resolved,
+        Err(e) => return EditorFrame::error(id.clone(), error_code::CONFIG, e),
+    };
+
+    let total = params.files.len();
+    let mut items = Vec::with_capacity(total);
+    for (index, file) in params.files.into_iter().enumerate() {
+        let file_name = file_name_of(&file);
+        emit(EditorFrame::Progress {
+            id: id.clone(),
+            progress: UploadProgress {
+                index,
+                total,
+                file: file.clone(),
+                stage: "uploading",
+                url: None,
+                error: None,
+            },
+        });
+
+        let result = upload_single_file(&file, &config).await;
+        let (stage, url, error) = match result {
+            Ok(url) => ("done", Some(url), None),
+            Err(e) => ("failed", None, Some(e)),
+        };
+        emit(EditorFrame::Progress {
+            id: id.clone(),
+            progress: UploadProgress {
+                index,
+                total,
+                file: file.clone(),
+                stage,
+                url: url.clone(),
+                error: error.clone(),
+            },
+        });
+        items.push(UploadItem {
+            markdown: url
+                .as_deref()
+                .map(|url| cli::format_markdown_link(&file_name, url)),
+            file,
+            url,
+            error,
+        });
+    }
+
+    if items.iter().all(|item| item.error.is_some()) {
+        let message = items
+            .iter()
+            .find_map(|item| item.error.as_deref())
+            .unwrap_or_default()
+            .to_string();
+        return EditorFrame::error(id.clone(), error_code::UPLOAD_FAILED, message);
+    }
+
+    EditorFrame::Result {
+        id: id.clone(),
+        result: serde_json::json!({ "service": service, "items": items }),
+    }
+}
+
+/// 处理一行请求；返回 false 表示应当退出
+async fn handle_line<F>(line: &str, mut emit: F) -> bool
+where
+    F: FnMut(EditorFrame),
+{
+    let request: EditorRequest = match serde_json::from_str(line) {
+        Ok(request) => request,
+        Err(e) => {
+            let code = if serde_json::from_str::<serde_json::Value>(line).is_ok() {
+                error_code::INVALID_REQUEST
+            } else {
+                error_code::PARSE_ERROR
+            };
+            emit(EditorFrame::error(None, code, e.to_string()));
+            return true;
+        }
+    };
+
+    let id = request.id;
+    match request.method.as_str() {
+        "hello" => emit(EditorFrame::Result {
+            id,
+            result: hello_result(),
+        }),
+        "listServices" => match cli::configured_services() {
+            Ok(services) => emit(EditorFrame::Result {
+                id,
+                result: serde_json::json!({ "services": services }),
+            }),
+            Err(e) => emit(EditorFrame::error(id, error_code::CONFIG, e)),
+        },
+        "upload" => {
+            let frame = handle_upload(&id, request.params, &mut emit).await;
+            emit(frame);
+        }
+        "shutdown" => {
+            emit(EditorFrame::Result {
+                id,
+                result: serde_json::Value::Null,
+            });
+            return false;
+        }
+        other => emit(EditorFrame::error(
+            id,
+            error_code::METHOD_NOT_FOUND,
+            format!("未知方法: {}", other),
+        )),
+    }
+    true
+}
+
+/// 运行 stdio 协议循环，直到 stdin 关闭或收到 shutdown
+pub fn run_editor_stdio() {
+    let runtime = match tokio::runtime::Builder::new_current_thread()
+        .enable_all()
+        .build()
+    {
+        Ok(rt) => rt,
+        Err(e) => {
+            eprintln!("[PicNexus] 初始化失败: {}", e);
+            std::process::exit(1);
+        }
+    };
+
+    let stdin = std::io::stdin().lock();
+    let mut stdout = std::io::stdout().lock();
+    let mut write_failed = false;
+
+    for line in stdin.lines() {
+        let line = match line {
+            Ok(line) => line,
+            Err(e) => {
+                eprintln!("[PicNexus] 读取请求失败: {}", e);
+                std::process::exit(1);
+            }
+        };
+        if line.trim().is_empty() {
+            continue;
+        }
+
+        let keep_running = runtime.block_on(handle_line(&line, |frame| {
+            if let Err(e) = write_frame(&mut stdout, &frame) {
+                eprintln!("[PicNexus] 写入响应失败: {}", e);
+                write_failed = true;
+            }
+        }));
+        if !keep_running || write_failed {
+            break;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn run(line: &str) -> (bool, Vec<serde_json::Value>) {
+        let runtime = tokio::runtime::Builder::new_current_thread()
+            .enable_all()
+            .build()
+            .unwrap();
+        let mut frames = Vec::new();
+        let keep_running = runtime.block_on(handle_line(line, |frame| {
+            frames.push(serde_json::to_value(&frame).unwrap());
+        }));
+        (keep_running, frames)
+    }
+
+    #[test]
+    fn malformed_json_returns_parse_error_without_id() {
+        let (keep_running, frames) = run("{not json");
+
+        assert!(keep_running);
+        assert_eq!(frames.len(), 1);
+        assert_eq!(frames[0]["type"], "error");
+        assert_eq!(frames[0]["id"], serde_json::Value::Null);
+        assert_eq!(frames[0]["error"]["code"], error_code::PARSE_ERROR);
+    }
+
+    #[test]
+    fn unknown_method_echoes_request_id() {
+        let (_, frames) = run(r#"{"id": 7, "method": "format"}"#);
+
+        assert_eq!(frames[0]["id"], 7);
+        assert_eq!(frames[0]["error"]["code"], error_code::METHOD_NOT_FOUND);
+    }
+
+    #[test]
+    fn upload_validates_params_before_loading_config() {
+        let (_, frames) = run(r#"{"id": "a", "method": "upload", "params": {"files": []}}"#);
+        assert_eq!(frames[0]["error"]["code"], error_code::INVALID_PARAMS);
+
+        let (_, frames) =
+            run(r#"{"id": "b", "method": "upload", "params": {"files": ["rel/a.png"]}}"#);
+        assert_eq!(frames[0]["id"], "b");
+        assert_eq!(frames[0]["error"]["code"], error_code::INVALID_PARAMS);
+    }
+
+    #[test]
+    fn shutdown_replies_then_stops() {
+        let (keep_running, frames) = run(r#"{"id": 1, "method": "shutdown"}"#);
+
+        assert!(!keep_running);
+        assert_eq!(frames[0]["type"], "result");
+    }
+}
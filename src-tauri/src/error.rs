@@ -2,58 +2,303 @@
 // 统一应用错误类型
 // v2.10: 扩展错误类型覆盖所有服务
 
-use serde::Serialize;
+use std::collections::BTreeMap;
+
+use serde::ser::SerializeMap;
+use serde::{Serialize, Serializer};
+
+/// 稳定的机器可读错误码
+///
+/// 前端按错误码分支处理与做国际化，不再匹配中文错误消息；已发布的错误码不要改名
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub enum ErrorCode {
+    #[serde(rename = "E_NETWORK")]
+    Network,
+    #[serde(rename = "E_NETWORK_TIMEOUT")]
+    NetworkTimeout,
+    #[serde(rename = "E_NETWORK_CONNECT")]
+    NetworkConnect,
+    #[serde(rename = "E_AUTH")]
+    Auth,
+    #[serde(rename = "E_FILE_IO")]
+    FileIo,
+    #[serde(rename = "E_FILE_NOT_FOUND")]
+    FileNotFound,
+    #[serde(rename = "E_UPLOAD")]
+    Upload,
+    #[serde(rename = "E_UPLOAD_AUTH")]
+    UploadAuth,
+    #[serde(rename = "E_UPLOAD_RATE_LIMITED")]
+    UploadRateLimited,
+    #[serde(rename = "E_UPLOAD_SERVER")]
+    UploadServer,
+    #[serde(rename = "E_CONFIG")]
+    Config,
+    #[serde(rename = "E_CLIPBOARD")]
+    Clipboard,
+    #[serde(rename = "E_EXTERNAL")]
+    External,
+    #[serde(rename = "E_VALIDATION")]
+    Validation,
+    #[serde(rename = "E_WEBDAV")]
+    WebDav,
+    #[serde(rename = "E_STORAGE")]
+    Storage,
+    #[serde(rename = "E_TEMP_QUOTA")]
+    TempQuota,
+    #[serde(rename = "E_LINK_TIMEOUT")]
+    LinkTimeout,
+    #[serde(rename = "E_LINK_FORBIDDEN_TARGET")]
+    LinkForbiddenTarget,
+    #[serde(rename = "E_CANCELLED")]
+    Cancelled,
+    #[serde(rename = "E_TASK_PANICKED")]
+    TaskPanicked,
+    #[serde(rename = "E_DISK_FULL")]
+    DiskFull,
+    #[serde(rename = "E_READONLY")]
+    ReadOnly,
+    #[serde(rename = "E_NOT_IMAGE")]
+    NotImage,
+    #[serde(rename = "E_DEADLINE")]
+    Deadline,
+    #[serde(rename = "E_STALLED")]
+    Stalled,
+}
+
+impl ErrorCode {
+    /// 该类错误默认是否值得重试
+    pub fn default_retriable(self) -> bool {
+        matches!(
+            self,
+            Self::Network
+                | Self::NetworkTimeout
+                | Self::NetworkConnect
+                | Self::UploadRateLimited
+                | Self::UploadServer
+                | Self::LinkTimeout
+                | Self::Deadline
+                | Self::Stalled
+        )
+    }
+}
+
+/// 错误的附加信息：显式指定的错误码 / 可重试标记，以及供前端展示或排查的上下文
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ErrorMeta {
+    code: Option<ErrorCode>,
+    retriable: Option<bool>,
+    context: BTreeMap<String, String>,
+}
 
 /// 应用统一错误类型
 ///
-/// 前端通过 `type` 字段识别错误类型，进行差异化处理
-/// 使用 `#[serde(tag = "type", content = "data")]` 实现结构化序列化
-#[derive(Debug, Serialize)]
-#[serde(tag = "type", content = "data")]
+/// 序列化为 `{ type, data, code, retriable, context? }`：
+/// 前端通过 `type` 字段识别错误类别，通过 `code` 区分具体错误，`retriable` 决定是否提供重试
+#[derive(Debug)]
 pub enum AppError {
     /// 网络错误：连接失败、超时等
-    #[serde(rename = "NETWORK")]
-    Network { message: String },
+    Network { message: String, meta: ErrorMeta },
 
     /// 认证错误：Cookie 过期、Token 无效等
-    #[serde(rename = "AUTH")]
-    Auth { message: String },
+    Auth { message: String, meta: ErrorMeta },
 
     /// 文件 IO 错误：读写文件失败等
-    #[serde(rename = "FILE_IO")]
-    FileIo { message: String },
+    FileIo { message: String, meta: ErrorMeta },
 
     /// 上传错误：图床返回错误
-    #[serde(rename = "UPLOAD")]
     Upload {
         service: String,
         code: Option<i32>,
         message: String,
+        meta: ErrorMeta,
     },
 
     /// 配置错误：配置缺失或无效
-    #[serde(rename = "CONFIG")]
-    Config { message: String },
+    Config { message: String, meta: ErrorMeta },
 
     /// 剪贴板错误
-    #[serde(rename = "CLIPBOARD")]
-    Clipboard { message: String },
+    Clipboard { message: String, meta: ErrorMeta },
 
     /// 外部服务错误：sidecar 进程、浏览器检测等
-    #[serde(rename = "EXTERNAL")]
-    External { message: String },
+    External { message: String, meta: ErrorMeta },
 
     /// 验证错误：参数验证失败
-    #[serde(rename = "VALIDATION")]
-    Validation { message: String },
+    Validation { message: String, meta: ErrorMeta },
 
     /// WebDAV 错误
-    #[serde(rename = "WEBDAV")]
-    WebDAV { message: String },
+    WebDAV { message: String, meta: ErrorMeta },
 
     /// R2/S3 存储错误
-    #[serde(rename = "STORAGE")]
-    Storage { message: String },
+    Storage { message: String, meta: ErrorMeta },
+}
+
+// ==================== 错误码与序列化 ====================
+
+impl AppError {
+    /// 前端识别错误类别的 `type` 字段
+    pub fn kind(&self) -> &'static str {
+        match self {
+            Self::Network { .. } => "NETWORK",
+            Self::Auth { .. } => "AUTH",
+            Self::FileIo { .. } => "FILE_IO",
+            Self::Upload { .. } => "UPLOAD",
+            Self::Config { .. } => "CONFIG",
+            Self::Clipboard { .. } => "CLIPBOARD",
+            Self::External { .. } => "EXTERNAL",
+            Self::Validation { .. } => "VALIDATION",
+            Self::WebDAV { .. } => "WEBDAV",
+            Self::Storage { .. } => "STORAGE",
+        }
+    }
+
+    /// 错误消息（不含类别前缀）
+    pub fn message(&self) -> &str {
+        match self {
+            Self::Network { message, .. }
+            | Self::Auth { message, .. }
+            | Self::FileIo { message, .. }
+            | Self::Upload { message, .. }
+            | Self::Config { message, .. }
+            | Self::Clipboard { message, .. }
+            | Self::External { message, .. }
+            | Self::Validation { message, .. }
+            | Self::WebDAV { message, .. }
+            | Self::Storage { message, .. } => message,
+        }
+    }
+
+    fn meta(&self) -> &ErrorMeta {
+        match self {
+            Self::Network { meta, .. }
+            | Self::Auth { meta, .. }
+            | Self::FileIo { meta, .. }
+            | Self::Upload { meta, .. }
+            | Self::Config { meta, .. }
+            | Self::Clipboard { meta, .. }
+            | Self::External { meta, .. }
+            | Self::Validation { meta, .. }
+            | Self::WebDAV { meta, .. }
+            | Self::Storage { meta, .. } => meta,
+        }
+    }
+
+    fn meta_mut(&mut self) -> &mut ErrorMeta {
+        match self {
+            Self::Network { meta, .. }
+            | Self::Auth { meta, .. }
+            | Self::FileIo { meta, .. }
+            | Self::Upload { meta, .. }
+            | Self::Config { meta, .. }
+            | Self::Clipboard { meta, .. }
+            | Self::External { meta, .. }
+            | Self::Validation { meta, .. }
+            | Self::WebDAV { meta, .. }
+            | Self::Storage { meta, .. } => meta,
+        }
+    }
+
+    /// 错误码：显式指定的优先，否则按变体（上传错误再看 HTTP 状态码）推断
+    pub fn code(&self) -> ErrorCode {
+        if let Some(code) = self.meta().code {
+            return code;
+        }
+        match self {
+            Self::Network { .. } => ErrorCode::Network,
+            Self::Auth { .. } => ErrorCode::Auth,
+            Self::FileIo { .. } => ErrorCode::FileIo,
+            Self::Upload { code, .. } => match code {
+                Some(401 | 403) => ErrorCode::UploadAuth,
+                Some(429) => ErrorCode::UploadRateLimited,
+                Some(500..=599) => ErrorCode::UploadServer,
+                _ => ErrorCode::Upload,
+            },
+            Self::Config { .. } => ErrorCode::Config,
+            Self::Clipboard { .. } => ErrorCode::Clipboard,
+            Self::External { .. } => ErrorCode::External,
+            Self::Validation { .. } => ErrorCode::Validation,
+            Self::WebDAV { .. } => ErrorCode::WebDav,
+            Self::Storage { .. } => ErrorCode::Storage,
+        }
+    }
+
+    /// 是否值得重试：显式指定的优先，否则取错误码的默认值
+    pub fn is_retriable(&self) -> bool {
+        self.meta()
+            .retriable
+            .unwrap_or_else(|| self.code().default_retriable())
+    }
+
+    /// 附加上下文（如图床名、主机名、HTTP 状态码）
+    pub fn context(&self) -> &BTreeMap<String, String> {
+        &self.meta().context
+    }
+
+    /// 指定错误码
+    pub fn with_code(mut self, code: ErrorCode) -> Self {
+        self.meta_mut().code = Some(code);
+        self
+    }
+
+    /// 覆盖默认的可重试标记
+    pub fn with_retriable(mut self, retriable: bool) -> Self {
+        self.meta_mut().retriable = Some(retriable);
+        self
+    }
+
+    /// 附加一条上下文
+    pub fn with_context(mut self, key: impl Into<String>, value: impl ToString) -> Self {
+        self.meta_mut()
+            .context
+            .insert(key.into(), value.to_string());
+        self
+    }
+}
+
+#[derive(Serialize)]
+struct MessageData<'a> {
+    message: &'a str,
+}
+
+#[derive(Serialize)]
+struct UploadData<'a> {
+    service: &'a str,
+    code: Option<i32>,
+    message: &'a str,
+}
+
+impl Serialize for AppError {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut map = serializer.serialize_map(None)?;
+        map.serialize_entry("type", self.kind())?;
+        match self {
+            Self::Upload {
+                service,
+                code,
+                message,
+                ..
+            } => map.serialize_entry(
+                "data",
+                &UploadData {
+                    service,
+                    code: *code,
+                    message,
+                },
+            )?,
+            _ => map.serialize_entry(
+                "data",
+                &MessageData {
+                    message: self.message(),
+                },
+            )?,
+        }
+        map.serialize_entry("code", &self.code())?;
+        map.serialize_entry("retriable", &self.is_retriable())?;
+        if !self.context().is_empty() {
+            map.serialize_entry("context", self.context())?;
+        }
+        map.end()
+    }
 }
 
 // ==================== From trait 实现 ====================
@@ -61,48 +306,56 @@ pub enum AppError {
 impl From<reqwest::Error> for AppError {
     fn from(err: reqwest::Error) -> Self {
         if err.is_timeout() {
-            AppError::Network {
-                message: "请求超时".to_string(),
-            }
+            AppError::network("请求超时").with_code(ErrorCode::NetworkTimeout)
         } else if err.is_connect() {
-            AppError::Network {
-                message: "连接失败".to_string(),
-            }
+            AppError::network("连接失败").with_code(ErrorCode::NetworkConnect)
         } else {
-            AppError::Network {
-                message: err.to_string(),
-            }
+            AppError::network(err.to_string())
         }
     }
 }
 
 impl From<std::io::Error> for AppError {
     fn from(err: std::io::Error) -> Self {
-        AppError::FileIo {
-            message: err.to_string(),
+        let code = match err.kind() {
+            std::io::ErrorKind::NotFound => Some(ErrorCode::FileNotFound),
+            std::io::ErrorKind::StorageFull => Some(ErrorCode::DiskFull),
+            std::io::ErrorKind::ReadOnlyFilesystem => Some(ErrorCode::ReadOnly),
+            _ => None,
+        };
+        let app_err = AppError::file_io(err.to_string());
+        match code {
+            Some(code) => app_err.with_code(code),
+            None => app_err,
         }
     }
 }
 
 impl From<serde_json::Error> for AppError {
     fn from(err: serde_json::Error) -> Self {
-        AppError::Validation {
-            message: format!("JSON 解析失败: {}", err),
+        AppError::validation(format!("JSON 解析失败: {}", err))
+    }
+}
+
+impl From<tokio::task::JoinError> for AppError {
+    fn from(err: tokio::task::JoinError) -> Self {
+        if err.is_cancelled() {
+            AppError::cancelled()
+        } else {
+            AppError::task_panicked()
         }
     }
 }
 
 impl From<String> for AppError {
     fn from(message: String) -> Self {
-        AppError::Network { message }
+        AppError::network(message)
     }
 }
 
 impl From<&str> for AppError {
     fn from(message: &str) -> Self {
-        AppError::Network {
-            message: message.to_string(),
-        }
+        AppError::network(message)
     }
 }
 
@@ -111,18 +364,18 @@ impl From<&str> for AppError {
 impl std::fmt::Display for AppError {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
-            Self::Network { message } => write!(f, "网络错误: {}", message),
-            Self::Auth { message } => write!(f, "认证错误: {}", message),
-            Self::FileIo { message } => write!(f, "文件错误: {}", message),
+            Self::Network { message, .. } => write!(f, "网络错误: {}", message),
+            Self::Auth { message, .. } => write!(f, "认证错误: {}", message),
+            Self::FileIo { message, .. } => write!(f, "文件错误: {}", message),
             Self::Upload {
                 service, message, ..
             } => write!(f, "{} 上传错误: {}", service, message),
-            Self::Config { message } => write!(f, "配置错误: {}", message),
-            Self::Clipboard { message } => write!(f, "剪贴板错误: {}", message),
-            Self::External { message } => write!(f, "外部服务错误: {}", message),
-            Self::Validation { message } => write!(f, "验证错误: {}", message),
-            Self::WebDAV { message } => write!(f, "WebDAV 错误: {}", message),
-            Self::Storage { message } => write!(f, "存储错误: {}", message),
+            Self::Config { message, .. } => write!(f, "配置错误: {}", message),
+            Self::Clipboard { message, .. } => write!(f, "剪贴板错误: {}", message),
+            Self::External { message, .. } => write!(f, "外部服务错误: {}", message),
+            Self::Validation { message, .. } => write!(f, "验证错误: {}", message),
+            Self::WebDAV { message, .. } => write!(f, "WebDAV 错误: {}", message),
+            Self::Storage { message, .. } => write!(f, "存储错误: {}", message),
         }
     }
 }
@@ -136,6 +389,7 @@ impl AppError {
     pub fn network(message: impl Into<String>) -> Self {
         AppError::Network {
             message: message.into(),
+            meta: ErrorMeta::default(),
         }
     }
 
@@ -143,6 +397,7 @@ impl AppError {
     pub fn auth(message: impl Into<String>) -> Self {
         AppError::Auth {
             message: message.into(),
+            meta: ErrorMeta::default(),
         }
     }
 
@@ -150,6 +405,7 @@ impl AppError {
     pub fn file_io(message: impl Into<String>) -> Self {
         AppError::FileIo {
             message: message.into(),
+            meta: ErrorMeta::default(),
         }
     }
 
@@ -159,6 +415,7 @@ impl AppError {
             service: service.into(),
             code: None,
             message: message.into(),
+            meta: ErrorMeta::default(),
         }
     }
 
@@ -172,6 +429,7 @@ impl AppError {
             service: service.into(),
             code: Some(code),
             message: message.into(),
+            meta: ErrorMeta::default(),
         }
     }
 
@@ -179,6 +437,7 @@ impl AppError {
     pub fn config(message: impl Into<String>) -> Self {
         AppError::Config {
             message: message.into(),
+            meta: ErrorMeta::default(),
         }
     }
 
@@ -186,6 +445,7 @@ impl AppError {
     pub fn clipboard(message: impl Into<String>) -> Self {
         AppError::Clipboard {
             message: message.into(),
+            meta: ErrorMeta::default(),
         }
     }
 
@@ -193,6 +453,7 @@ impl AppError {
     pub fn external(message: impl Into<String>) -> Self {
         AppError::External {
             message: message.into(),
+            meta: ErrorMeta::default(),
         }
     }
 
@@ -200,6 +461,7 @@ impl AppError {
     pub fn validation(message: impl Into<String>) -> Self {
         AppError::Validation {
             message: message.into(),
+            meta: ErrorMeta::default(),
         }
     }
 
@@ -207,6 +469,7 @@ impl AppError {
     pub fn webdav(message: impl Into<String>) -> Self {
         AppError::WebDAV {
             message: message.into(),
+            meta: ErrorMeta::default(),
         }
     }
 
@@ -214,8 +477,45 @@ impl AppError {
     pub fn storage(message: impl Into<String>) -> Self {
         AppError::Storage {
             message: message.into(),
+            meta: ErrorMeta::default(),
         }
     }
+
+    /// 创建写入失败的错误：磁盘已满、只读或无写权限时使用专门的错误码
+    ///
+    /// `action` 为失败的操作（如「写入临时文件」），拼在消息开头
+    pub fn write_failed(action: &str, err: &std::io::Error) -> Self {
+        match err.kind() {
+            std::io::ErrorKind::StorageFull => {
+                AppError::file_io(format!("{}失败: 磁盘空间不足（{}）", action, err))
+                    .with_code(ErrorCode::DiskFull)
+            }
+            std::io::ErrorKind::ReadOnlyFilesystem | std::io::ErrorKind::PermissionDenied => {
+                AppError::file_io(format!(
+                    "{}失败: 目标位置只读或没有写入权限（{}）",
+                    action, err
+                ))
+                .with_code(ErrorCode::ReadOnly)
+            }
+            _ => AppError::file_io(format!("{}失败: {}", action, err)),
+        }
+    }
+
+    /// 创建任务被取消的错误（用户主动取消，前端不应提示失败）
+    pub fn cancelled() -> Self {
+        AppError::external("任务已取消").with_code(ErrorCode::Cancelled)
+    }
+
+    /// 创建后台任务 panic 的错误（详细信息见崩溃报告）
+    pub fn task_panicked() -> Self {
+        AppError::external("后台任务异常终止，已生成崩溃报告").with_code(ErrorCode::TaskPanicked)
+    }
+
+    /// 创建任务长时间无进度、被看门狗强制中止的错误（可重试）
+    pub fn stalled(idle_secs: u64) -> Self {
+        AppError::network(format!("任务 {} 秒无进度，已强制中止", idle_secs))
+            .with_code(ErrorCode::Stalled)
+    }
 }
 
 // ==================== Result 扩展 trait ====================
@@ -315,7 +615,7 @@ mod tests {
     fn network_constructor_sets_message() {
         let err = AppError::network("连接超时");
         match err {
-            AppError::Network { message } => assert_eq!(message, "连接超时"),
+            AppError::Network { message, .. } => assert_eq!(message, "连接超时"),
             _ => panic!("应为 Network 变体"),
         }
     }
@@ -323,7 +623,7 @@ mod tests {
     #[test]
     fn auth_constructor_sets_message() {
         match AppError::auth("Cookie 过期") {
-            AppError::Auth { message } => assert_eq!(message, "Cookie 过期"),
+            AppError::Auth { message, .. } => assert_eq!(message, "Cookie 过期"),
             _ => panic!("应为 Auth 变体"),
         }
     }
@@ -331,7 +631,7 @@ mod tests {
     #[test]
     fn file_io_constructor_sets_message() {
         match AppError::file_io("权限不足") {
-            AppError::FileIo { message } => assert_eq!(message, "权限不足"),
+            AppError::FileIo { message, .. } => assert_eq!(message, "权限不足"),
             _ => panic!("应为 FileIo 变体"),
         }
     }
@@ -343,6 +643,7 @@ mod tests {
                 service,
                 code,
                 message,
+                ..
             } => {
                 assert_eq!(service, "weibo");
                 assert!(code.is_none());
@@ -359,6 +660,7 @@ mod tests {
                 service,
                 code,
                 message,
+                ..
             } => {
                 assert_eq!(service, "jd");
                 assert_eq!(code, Some(403));
@@ -371,7 +673,7 @@ mod tests {
     #[test]
     fn config_constructor_sets_message() {
         match AppError::config("缺失 token") {
-            AppError::Config { message } => assert_eq!(message, "缺失 token"),
+            AppError::Config { message, .. } => assert_eq!(message, "缺失 token"),
             _ => panic!("应为 Config 变体"),
         }
     }
@@ -379,7 +681,7 @@ mod tests {
     #[test]
     fn clipboard_constructor_sets_message() {
         match AppError::clipboard("无法访问剪贴板") {
-            AppError::Clipboard { message } => assert_eq!(message, "无法访问剪贴板"),
+            AppError::Clipboard { message, .. } => assert_eq!(message, "无法访问剪贴板"),
             _ => panic!("应为 Clipboard 变体"),
         }
     }
@@ -387,7 +689,7 @@ mod tests {
     #[test]
     fn external_constructor_sets_message() {
         match AppError::external("sidecar 未启动") {
-            AppError::External { message } => assert_eq!(message, "sidecar 未启动"),
+            AppError::External { message, .. } => assert_eq!(message, "sidecar 未启动"),
             _ => panic!("应为 External 变体"),
         }
     }
@@ -395,7 +697,7 @@ mod tests {
     #[test]
     fn validation_constructor_sets_message() {
         match AppError::validation("参数为空") {
-            AppError::Validation { message } => assert_eq!(message, "参数为空"),
+            AppError::Validation { message, .. } => assert_eq!(message, "参数为空"),
             _ => panic!("应为 Validation 变体"),
         }
     }
@@ -403,7 +705,7 @@ mod tests {
     #[test]
     fn webdav_constructor_sets_message() {
         match AppError::webdav("PROPFIND 失败") {
-            AppError::WebDAV { message } => assert_eq!(message, "PROPFIND 失败"),
+            AppError::WebDAV { message, .. } => assert_eq!(message, "PROPFIND 失败"),
             _ => panic!("应为 WebDAV 变体"),
         }
     }
@@ -411,7 +713,7 @@ mod tests {
     #[test]
     fn storage_constructor_sets_message() {
         match AppError::storage("桶不存在") {
-            AppError::Storage { message } => assert_eq!(message, "桶不存在"),
+            AppError::Storage { message, .. } => assert_eq!(message, "桶不存在"),
             _ => panic!("应为 Storage 变体"),
         }
     }
@@ -435,7 +737,7 @@ mod tests {
         let io_err = std::io::Error::new(std::io::ErrorKind::NotFound, "file missing");
         let app_err: AppError = io_err.into();
         match app_err {
-            AppError::FileIo { message } => assert!(message.contains("file missing")),
+            AppError::FileIo { message, .. } => assert!(message.contains("file missing")),
             _ => panic!("io::Error 应映射到 FileIo"),
         }
     }
@@ -446,7 +748,7 @@ mod tests {
         let serde_err = bad.unwrap_err();
         let app_err: AppError = serde_err.into();
         match app_err {
-            AppError::Validation { message } => {
+            AppError::Validation { message, .. } => {
                 assert!(message.contains("JSON 解析失败"));
             }
             _ => panic!("serde_json::Error 应映射到 Validation"),
@@ -457,7 +759,7 @@ mod tests {
     fn from_string_maps_to_network() {
         let app_err: AppError = String::from("断网了").into();
         match app_err {
-            AppError::Network { message } => assert_eq!(message, "断网了"),
+            AppError::Network { message, .. } => assert_eq!(message, "断网了"),
             _ => panic!("String 应映射到 Network"),
         }
     }
@@ -466,7 +768,7 @@ mod tests {
     fn from_str_maps_to_network() {
         let app_err: AppError = "静态字面量".into();
         match app_err {
-            AppError::Network { message } => assert_eq!(message, "静态字面量"),
+            AppError::Network { message, .. } => assert_eq!(message, "静态字面量"),
             _ => panic!("&str 应映射到 Network"),
         }
     }
@@ -553,6 +855,99 @@ mod tests {
         assert!(json["data"]["code"].is_null());
     }
 
+    // ---------- 错误码 / 可重试 / 上下文 ----------
+
+    #[test]
+    fn code_defaults_follow_variant() {
+        assert_eq!(AppError::network("x").code(), ErrorCode::Network);
+        assert_eq!(AppError::validation("x").code(), ErrorCode::Validation);
+        assert_eq!(AppError::webdav("x").code(), ErrorCode::WebDav);
+        assert!(AppError::network("x").is_retriable());
+        assert!(!AppError::validation("x").is_retriable());
+    }
+
+    #[test]
+    fn upload_code_derives_from_http_status() {
+        let cases = [
+            (Some(401), ErrorCode::UploadAuth, false),
+            (Some(403), ErrorCode::UploadAuth, false),
+            (Some(429), ErrorCode::UploadRateLimited, true),
+            (Some(502), ErrorCode::UploadServer, true),
+            (Some(400), ErrorCode::Upload, false),
+            (None, ErrorCode::Upload, false),
+        ];
+        for (status, code, retriable) in cases {
+            let err = match status {
+                Some(status) => AppError::upload_with_code("jd", status, "m"),
+                None => AppError::upload("jd", "m"),
+            };
+            assert_eq!(err.code(), code, "status {:?}", status);
+            assert_eq!(err.is_retriable(), retriable, "status {:?}", status);
+        }
+    }
+
+    #[test]
+    fn explicit_code_and_retriable_override_defaults() {
+        let err = AppError::network("超时").with_code(ErrorCode::LinkTimeout);
+        assert_eq!(err.code(), ErrorCode::LinkTimeout);
+        assert!(err.is_retriable());
+
+        let err = AppError::network("HTTP 404").with_retriable(false);
+        assert_eq!(err.code(), ErrorCode::Network);
+        assert!(!err.is_retriable());
+    }
+
+    #[test]
+    fn from_io_not_found_uses_dedicated_code() {
+        let io_err = std::io::Error::new(std::io::ErrorKind::NotFound, "missing");
+        assert_eq!(AppError::from(io_err).code(), ErrorCode::FileNotFound);
+        let io_err = std::io::Error::new(std::io::ErrorKind::PermissionDenied, "denied");
+        assert_eq!(AppError::from(io_err).code(), ErrorCode::FileIo);
+        let io_err = std::io::Error::new(std::io::ErrorKind::StorageFull, "full");
+        assert_eq!(AppError::from(io_err).code(), ErrorCode::DiskFull);
+    }
+
+    #[test]
+    fn write_failed_classifies_disk_full_and_readonly() {
+        let err = std::io::Error::new(std::io::ErrorKind::StorageFull, "no space");
+        let app_err = AppError::write_failed("写入临时文件", &err);
+        assert_eq!(app_err.code(), ErrorCode::DiskFull);
+        assert!(app_err.message().starts_with("写入临时文件失败"));
+
+        let err = std::io::Error::new(std::io::ErrorKind::PermissionDenied, "denied");
+        assert_eq!(
+            AppError::write_failed("写入", &err).code(),
+            ErrorCode::ReadOnly
+        );
+
+        let err = std::io::Error::other("boom");
+        assert_eq!(
+            AppError::write_failed("写入", &err).code(),
+            ErrorCode::FileIo
+        );
+    }
+
+    #[test]
+    fn serde_emits_code_retriable_and_context() {
+        let err = AppError::auth("Cookie expired")
+            .with_code(ErrorCode::UploadAuth)
+            .with_context("service", "weibo");
+        let json = serde_json::to_value(&err).unwrap();
+        assert_eq!(json["type"], "AUTH");
+        assert_eq!(json["data"]["message"], "Cookie expired");
+        assert_eq!(json["code"], "E_UPLOAD_AUTH");
+        assert_eq!(json["retriable"], false);
+        assert_eq!(json["context"]["service"], "weibo");
+    }
+
+    #[test]
+    fn serde_omits_empty_context() {
+        let json = serde_json::to_value(AppError::config("x")).unwrap();
+        assert_eq!(json["code"], "E_CONFIG");
+        assert!(json.get("context").is_none());
+        assert!(json["data"].get("meta").is_none());
+    }
+
     // ---------- IntoAppError 扩展 trait ----------
 
     fn err<T>() -> Result<T, &'static str> {
@@ -563,7 +958,7 @@ mod tests {
     fn into_network_err_wraps_display() {
         let r: Result<(), AppError> = err().into_network_err();
         match r.unwrap_err() {
-            AppError::Network { message } => assert_eq!(message, "底层错误"),
+            AppError::Network { message, .. } => assert_eq!(message, "底层错误"),
             _ => panic!("应为 Network"),
         }
     }
@@ -602,7 +997,7 @@ mod tests {
     fn into_file_io_err_with_prepends_prefix() {
         let r: Result<(), AppError> = err().into_file_io_err_with("读取配置");
         match r.unwrap_err() {
-            AppError::FileIo { message } => {
+            AppError::FileIo { message, .. } => {
                 assert!(message.starts_with("读取配置: "));
                 assert!(message.ends_with("底层错误"));
             }
@@ -614,7 +1009,7 @@ mod tests {
     fn into_network_err_with_prepends_prefix() {
         let r: Result<(), AppError> = err().into_network_err_with("调用 API");
         match r.unwrap_err() {
-            AppError::Network { message } => assert!(message.starts_with("调用 API: ")),
+            AppError::Network { message, .. } => assert!(message.starts_with("调用 API: ")),
             _ => panic!("应为 Network"),
         }
     }
@@ -623,7 +1018,7 @@ mod tests {
     fn into_external_err_with_prepends_prefix() {
         let r: Result<(), AppError> = err().into_external_err_with("启动 sidecar");
         match r.unwrap_err() {
-            AppError::External { message } => assert!(message.starts_with("启动 sidecar: ")),
+            AppError::External { message, .. } => assert!(message.starts_with("启动 sidecar: ")),
             _ => panic!("应为 External"),
         }
     }
@@ -632,7 +1027,7 @@ mod tests {
     fn into_storage_err_with_prepends_prefix() {
         let r: Result<(), AppError> = err().into_storage_err_with("上传到 R2");
         match r.unwrap_err() {
-            AppError::Storage { message } => assert!(message.starts_with("上传到 R2: ")),
+            AppError::Storage { message, .. } => assert!(message.starts_with("上传到 R2: ")),
             _ => panic!("应为 Storage"),
         }
     }
@@ -641,7 +1036,7 @@ mod tests {
     fn into_validation_err_with_prepends_prefix() {
         let r: Result<(), AppError> = err().into_validation_err_with("参数校验");
         match r.unwrap_err() {
-            AppError::Validation { message } => assert!(message.starts_with("参数校验: ")),
+            AppError::Validation { message, .. } => assert!(message.starts_with("参数校验: ")),
             _ => panic!("应为 Validation"),
         }
     }
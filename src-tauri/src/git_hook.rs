@@ -0,0 +1,406 @@
+// src-tauri/src/git_hook.rs
+// Git pre-commit 钩子：上传暂存区中新增的本地图片并改写 Markdown 引用
+//
+// 工作流：
+//   picnexus hook --staged [--service r2] [--dry-run]
+//   -> git diff --cached 找出新增图片与已暂存的 Markdown
+//   -> 只处理引用了新增图片的 Markdown -> 有限并发上传（同一图片只传一次）
+//   -> 改写并原子写回 -> git add 重新暂存 Markdown
+//   -> git rm --cached 取消暂存已上传的图片（工作区文件保留），避免图片本身也被提交
+//
+// 任一图片上传失败时不改写任何文件，并以退出码 1 中止提交。
+// 工作区与暂存区内容不一致的 Markdown 会被跳过，避免把未暂存的修改一并提交。
+
+use crate::atomic_file;
+use crate::cli;
+use crate::commands::md_processor::{is_markdown_image_path, resolve_local_image, upload_images};
+use crate::commands::md_scanner::{extract_local_image_refs, is_markdown_file, rewrite_image_urls};
+use crate::commands::net_limiter::NetworkLimiter;
+use crate::path_utils;
+use futures::StreamExt;
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+/// 暂存区中的一项变更
+#[derive(Debug, PartialEq, Eq)]
+struct StagedEntry {
+    status: char,
+    path: String,
+}
+
+/// 需要改写的 Markdown：仓库内路径、原文、本地引用 → 新增图片绝对路径
+struct HookMarkdown {
+    path: String,
+    content: String,
+    refs: Vec<(String, PathBuf)>,
+}
+
+fn run_git(repo: &Path, args: &[&str]) -> Result<String, String> {
+    let output = Command::new("git")
+        .args(args)
+        .current_dir(repo)
+        .output()
+        .map_err(|e| format!("无法执行 git: {}", e))?;
+    if !output.status.success() {
+        return Err(format!(
+            "git {} 失败: {}",
+            args.join(" "),
+            String::from_utf8_lossy(&output.stderr).trim()
+        ));
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+}
+
+/// 解析 `git diff --name-status -z` 输出（状态与路径以 NUL 交替分隔）
+fn parse_name_status_z(output: &str) -> Vec<StagedEntry> {
+    let mut entries = Vec::new();
+    let mut parts = output.split('\0').filter(|part| !part.is_empty());
+    while let (Some(status), Some(path)) = (parts.next(), parts.next()) {
+        if let Some(status) = status.chars().next() {
+            entries.push(StagedEntry {
+                status,
+                path: path.to_string(),
+            });
+        }
+    }
+    entries
+}
+
+/// 从暂存区变更中拆出新增图片与 Markdown 文件
+fn split_staged_entries(entries: &[StagedEntry]) -> (Vec<&str>, Vec<&str>) {
+    let images = entries
+        .iter()
+        .filter(|entry| entry.status == 'A' && is_markdown_image_path(Path::new(&entry.path)))
+        .map(|entry| entry.path.as_str())
+        .collect();
+    let markdown = entries
+        .iter()
+        .filter(|entry| matches!(entry.status, 'A' | 'M') && is_markdown_file(&entry.path))
+        .map(|entry| entry.path.as_str())
+        .collect();
+    (images, markdown)
+}
+
+/// 只保留指向新增图片的本地引用（已提交过的图片不重复上传）
+fn collect_hook_refs(
+    md_path: &Path,
+    content: &str,
+    new_images: &HashMap<PathBuf, &str>,
+) -> Vec<(String, PathBuf)> {
+    let md_dir = md_path.parent().unwrap_or(Path::new(""));
+    extract_local_image_refs(content)
+        .into_iter()
+        .filter_map(|link| {
+            resolve_local_image(md_dir, &link.url)
+                .filter(|path| new_images.contains_key(path))
+                .map(|path| (link.url, path))
+        })
+        .collect()
+}
+
+/// 把 Markdown 中已上传图片的引用改写为新链接，返回改写后的内容与改写行数
+fn rewrite_hook_markdown(
+    file: &HookMarkdown,
+    uploaded: &HashMap<PathBuf, String>,
+) -> (String, usize) {
+    let replacements: HashMap<String, String> = file
+        .refs
+        .iter()
+        .filter_map(|(reference, path)| {
+            uploaded
+                .get(path)
+                .map(|url| (reference.clone(), url.clone()))
+        })
+        .collect();
+    let (content, changes) = rewrite_image_urls(&file.content, &replacements);
+    (content, changes.len())
+}
+
+/// Markdown 引用的新增图片在暂存区中的仓库路径（改写后据此取消暂存）
+fn staged_image_paths<'a>(
+    file: &HookMarkdown,
+    new_images: &HashMap<PathBuf, &'a str>,
+) -> Vec<&'a str> {
+    file.refs
+        .iter()
+        .filter_map(|(_, path)| new_images.get(path).copied())
+        .collect()
+}
+
+fn exit_hook_error(message: &str) -> ! {
+    eprintln!("[PicNexus] {}", message);
+    std::process::exit(1);
+}
+
+/// `picnexus hook --staged` 主入口
+pub fn run_git_hook(service_id: Option<String>, dry_run: bool) {
+    let cwd = std::env::current_dir().unwrap_or_default();
+    let repo = match run_git(&cwd, &["rev-parse", "--show-toplevel"]) {
+        // 规范化后才能与 resolve_local_image 返回的路径比较前缀
+        Ok(root) => {
+            path_utils::canonicalize(root.trim()).unwrap_or_else(|_| PathBuf::from(root.trim()))
+        }
+        Err(e) => exit_hook_error(&format!("当前目录不是 Git 仓库: {}", e)),
+    };
+
+    let staged = match run_git(
+        &repo,
+        &[
+            "diff",
+            "--cached",
+            "--name-status",
+            "-z",
+            "--diff-filter=AM",
+        ],
+    ) {
+        Ok(output) => parse_name_status_z(&output),
+        Err(e) => exit_hook_error(&e),
+    };
+    let (image_paths, markdown_paths) = split_staged_entries(&staged);
+    if image_paths.is_empty() || markdown_paths.is_empty() {
+        return;
+    }
+
+    // 规范化路径 → 暂存区中的仓库路径（取消暂存时使用）
+    let new_images: HashMap<PathBuf, &str> = image_paths
+        .iter()
+        .filter_map(|path| {
+            path_utils::canonicalize(repo.join(path))
+                .ok()
+                .map(|canonical| (canonical, *path))
+        })
+        .collect();
+    let unstaged: HashSet<String> = run_git(&repo, &["diff", "--name-only", "-z"])
+        .map(|output| {
+            output
+                .split('\0')
+                .filter(|part| !part.is_empty())
+                .map(str::to_string)
+                .collect()
+        })
+        .unwrap_or_default();
+
+    let mut files = Vec::new();
+    for md_path in markdown_paths {
+        let full_path = repo.join(md_path);
+        let content = match std::fs::read_to_string(&full_path) {
+            Ok(content) => content,
+            Err(e) => {
+                eprintln!("[PicNexus] 跳过 {}: 读取失败 ({})", md_path, e);
+                continue;
+            }
+        };
+        let refs = collect_hook_refs(&full_path, &content, &new_images);
+        if refs.is_empty() {
+            continue;
+        }
+        if unstaged.contains(md_path) {
+            eprintln!(
+                "[PicNexus] 跳过 {}: 存在未暂存的修改，请先 git add 后再提交",
+                md_path
+            );
+            continue;
+        }
+        files.push(HookMarkdown {
+            path: md_path.to_string(),
+            content,
+            refs,
+        });
+    }
+
+    let unique_images: BTreeSet<PathBuf> = files
+        .iter()
+        .flat_map(|file| file.refs.iter().map(|(_, path)| path.clone()))
+        .collect();
+    if unique_images.is_empty() {
+        return;
+    }
+
+    if dry_run {
+        for file in &files {
+            for (reference, _) in &file.refs {
+                println!("{}: {}", file.path, reference);
+            }
+        }
+        eprintln!(
+            "[PicNexus] dry-run: {} 张新增图片，{} 个 Markdown 文件将被改写",
+            unique_images.len(),
+            files.len()
+        );
+        return;
+    }
+
+    let (service, config) = match cli::resolve_service_config(service_id.as_deref()) {
+        Ok(resolved) => resolved,
+        Err(e) => exit_hook_error(&e),
+    };
+
+    let runtime = match tokio::runtime::Builder::new_current_thread()
+        .enable_all()
+        .build()
+    {
+        Ok(rt) => rt,
+        Err(e) => exit_hook_error(&format!("初始化失败: {}", e)),
+    };
+
+    let total = unique_images.len();
+    let mut uploaded: HashMap<PathBuf, String> = HashMap::new();
+    let mut failed = Vec::new();
+    eprintln!("[PicNexus] 正在上传 {} 张图片 -> {} ...", total, service);
+    // 钩子运行在独立的 CLI 进程中，按与界面相同的默认上限限流
+    let limiter = NetworkLimiter::default();
+    runtime.block_on(async {
+        let mut results = upload_images(unique_images, &config, &limiter, &service);
+        let mut completed = 0;
+        while let Some((path, result)) = results.next().await {
+            completed += 1;
+            let display = path
+                .strip_prefix(&repo)
+                .unwrap_or(&path)
+                .to_string_lossy()
+                .to_string();
+            match result {
+                Ok(url) => {
+                    eprintln!("[PicNexus] 已上传 ({}/{}): {}", completed, total, display);
+                    uploaded.insert(path, url);
+                }
+                Err(e) => failed.push(format!("{} - {}", display, e)),
+            }
+        }
+    });
+
+    if !failed.is_empty() {
+        for line in &failed {
+            eprintln!("[PicNexus] ✗ 上传失败: {}", line);
+        }
+        exit_hook_error("存在上传失败的图片，未改写任何文件，已中止提交");
+    }
+
+    // 文件名按仓库路径排序，保证输出稳定
+    let mut rewritten: BTreeMap<String, usize> = BTreeMap::new();
+    let mut replaced_images: BTreeSet<&str> = BTreeSet::new();
+    for file in files {
+        let (content, changed) = rewrite_hook_markdown(&file, &uploaded);
+        if changed == 0 {
+            continue;
+        }
+        if let Err(e) = atomic_file::write(repo.join(&file.path), &content) {
+            exit_hook_error(&format!("写回 {} 失败: {}", file.path, e));
+        }
+        replaced_images.extend(staged_image_paths(&file, &new_images));
+        rewritten.insert(file.path, changed);
+    }
+
+    if rewritten.is_empty() {
+        return;
+    }
+    let mut add_args = vec!["add", "--"];
+    add_args.extend(rewritten.keys().map(String::as_str));
+    if let Err(e) = run_git(&repo, &add_args) {
+        exit_hook_error(&format!("重新暂存 Markdown 失败: {}", e));
+    }
+    // 图片已换成图床链接，只取消暂存，工作区中的文件保留
+    let mut rm_args = vec!["rm", "--cached", "--quiet", "--"];
+    rm_args.extend(replaced_images.iter().copied());
+    if let Err(e) = run_git(&repo, &rm_args) {
+        exit_hook_error(&format!("取消暂存已上传的图片失败: {}", e));
+    }
+
+    for (path, count) in &rewritten {
+        eprintln!("[PicNexus] ✓ 已改写 {} 处引用: {}", count, path);
+    }
+    eprintln!(
+        "[PicNexus] ✓ 已取消暂存 {} 张已上传的图片（文件仍保留在工作区）",
+        replaced_images.len()
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_name_status_z_reads_status_path_pairs() {
+        let entries = parse_name_status_z("A\0posts/a b.md\0M\0assets/x.png\0");
+
+        assert_eq!(
+            entries,
+            vec![
+                StagedEntry {
+                    status: 'A',
+                    path: "posts/a b.md".to_string()
+                },
+                StagedEntry {
+                    status: 'M',
+                    path: "assets/x.png".to_string()
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn split_staged_entries_only_counts_added_images() {
+        let entries =
+            parse_name_status_z("A\0img/new.png\0M\0img/old.jpg\0M\0post.md\0A\0notes.txt\0");
+
+        let (images, markdown) = split_staged_entries(&entries);
+
+        assert_eq!(images, vec!["img/new.png"]);
+        assert_eq!(markdown, vec!["post.md"]);
+    }
+
+    #[test]
+    fn collect_hook_refs_skips_images_already_committed() {
+        let dir = std::env::temp_dir().join(format!("picnexus-git-hook-{}", std::process::id()));
+        std::fs::create_dir_all(dir.join("img")).unwrap();
+        std::fs::write(dir.join("img").join("new.png"), b"fake").unwrap();
+        std::fs::write(dir.join("img").join("old.png"), b"fake").unwrap();
+        let md = dir.join("post.md");
+        let content = "![new](img/new.png)\n![old](img/old.png)\n";
+        let new_images = HashMap::from([(
+            path_utils::canonicalize(dir.join("img").join("new.png")).unwrap(),
+            "img/new.png",
+        )]);
+
+        let refs = collect_hook_refs(&md, content, &new_images);
+
+        assert_eq!(refs.len(), 1);
+        assert_eq!(refs[0].0, "img/new.png");
+        let _ = std::fs::remove_dir_all(dir);
+    }
+
+    #[test]
+    fn rewrite_hook_markdown_keeps_alt_text_equal_to_the_path() {
+        let image = PathBuf::from("/repo/img/new.png");
+        let file = HookMarkdown {
+            path: "post.md".to_string(),
+            content: "![img/new.png](img/new.png)\n".to_string(),
+            refs: vec![("img/new.png".to_string(), image.clone())],
+        };
+        let uploaded = HashMap::from([(image, "https://cdn.example.com/new.png".to_string())]);
+
+        let (content, changed) = rewrite_hook_markdown(&file, &uploaded);
+
+        assert_eq!(content, "![img/new.png](https://cdn.example.com/new.png)\n");
+        assert_eq!(changed, 1);
+    }
+
+    #[test]
+    fn staged_image_paths_maps_refs_back_to_repo_paths() {
+        let image = PathBuf::from("/repo/posts/img/图 1.png");
+        let file = HookMarkdown {
+            path: "posts/post.md".to_string(),
+            content: String::new(),
+            refs: vec![
+                ("img/图 1.png".to_string(), image.clone()),
+                ("./img/图 1.png".to_string(), image.clone()),
+            ],
+        };
+        let new_images = HashMap::from([(image, "posts/img/图 1.png")]);
+
+        let paths: BTreeSet<&str> = staged_image_paths(&file, &new_images).into_iter().collect();
+
+        assert_eq!(paths, BTreeSet::from(["posts/img/图 1.png"]));
+    }
+}
@@ -1,7 +1,10 @@
 use regex::Regex;
+use std::borrow::Cow;
+use std::sync::LazyLock;
 use url::Url;
 
 const MAX_PREVIEW_CHARS: usize = 160;
+const REDACTED: &str = "[REDACTED]";
 
 pub fn safe_path(path: &str) -> String {
     let basename = basename_from_any_platform_path(path).unwrap_or("path");
@@ -28,39 +31,46 @@ pub fn summarize_text(text: &str) -> String {
     )
 }
 
-pub fn sanitize_text(text: &str) -> String {
-    let with_redacted_auth_headers = authorization_header_regex()
-        .replace_all(text, |captures: &regex::Captures<'_>| {
-            format!("{}=[REDACTED]", &captures[1])
-        })
-        .to_string();
-    let with_redacted_headers = cookie_header_regex()
-        .replace_all(
-            &with_redacted_auth_headers,
-            |captures: &regex::Captures<'_>| format!("{}=[REDACTED]", &captures[1]),
-        )
-        .to_string();
+/// 脱敏 Cookie、令牌与签名参数，其余内容原样保留
+///
+/// 日志插件对每一条日志（含前端转发、第三方库输出）都会调用，没有命中时不分配新字符串
+pub fn redact_secrets(text: &str) -> Cow<'_, str> {
+    let mut output = Cow::Borrowed(text);
+    for regex in [
+        &*SIGNED_QUERY_RE,
+        &*AUTHORIZATION_HEADER_RE,
+        &*COOKIE_HEADER_RE,
+        &*SESSION_COOKIE_RE,
+        &*SENSITIVE_ASSIGNMENT_RE,
+    ] {
+        if let Cow::Owned(replaced) = regex.replace_all(&output, mask_value) {
+            output = Cow::Owned(replaced);
+        }
+    }
+    output
+}
+
+/// 把 `键=值` 中的值替换为 [REDACTED]（已脱敏的值不再重复处理）
+fn mask_value(captures: &regex::Captures<'_>) -> String {
+    if captures[2].starts_with("[REDACTED") {
+        return captures[0].to_string();
+    }
+    format!("{}={}", &captures[1], REDACTED)
+}
 
-    let with_redacted_assignments = sensitive_assignment_regex()
-        .replace_all(&with_redacted_headers, |captures: &regex::Captures<'_>| {
-            format!("{}=[REDACTED]", &captures[1])
-        })
-        .to_string();
+pub fn sanitize_text(text: &str) -> String {
+    let redacted = redact_secrets(text);
 
-    let with_safe_urls = url_regex()
-        .replace_all(
-            &with_redacted_assignments,
-            |captures: &regex::Captures<'_>| safe_url(&captures[0]),
-        )
-        .to_string();
+    let with_safe_urls = URL_RE.replace_all(&redacted, |captures: &regex::Captures<'_>| {
+        safe_url(&captures[0])
+    });
 
-    let with_safe_windows_paths = windows_path_regex()
+    let with_safe_windows_paths = WINDOWS_PATH_RE
         .replace_all(&with_safe_urls, |captures: &regex::Captures<'_>| {
             safe_path(&captures[0])
-        })
-        .to_string();
+        });
 
-    unix_path_regex()
+    UNIX_PATH_RE
         .replace_all(
             &with_safe_windows_paths,
             |captures: &regex::Captures<'_>| format!("{}{}", &captures[1], safe_path(&captures[2])),
@@ -89,37 +99,52 @@ fn basename_from_any_platform_path(path: &str) -> Option<&str> {
     path.split(['/', '\\']).rev().find(|part| !part.is_empty())
 }
 
-fn sensitive_assignment_regex() -> Regex {
+static SENSITIVE_ASSIGNMENT_RE: LazyLock<Regex> = LazyLock::new(|| {
     Regex::new(
         r#"(?i)["']?\b(cookie|token|auth|password|secret|credential|session|authorization|apiKey|accessKey|secretKey|privateKey)\b["']?\s*[:=]\s*("[^"]*"|'[^']*'|[^;,\s}\]]+)"#,
     )
     .expect("valid sensitive assignment regex")
-}
+});
 
-fn authorization_header_regex() -> Regex {
+static AUTHORIZATION_HEADER_RE: LazyLock<Regex> = LazyLock::new(|| {
     Regex::new(
         r#"(?i)\b(authorization)\b\s*[:=]\s*("[^"]*"|'[^']*'|(?:Bearer|Basic|token|Client-ID)\s+[A-Za-z0-9._~+/=-]+|[^;,\s}\]]+)"#,
     )
     .expect("valid authorization header regex")
-}
+});
 
-fn cookie_header_regex() -> Regex {
+static COOKIE_HEADER_RE: LazyLock<Regex> = LazyLock::new(|| {
     Regex::new(r#"(?i)\b(cookie)\b\s*[:=]\s*("[^"]*"|'[^']*'|[^,\r\n}\]]+)"#)
         .expect("valid cookie header regex")
-}
+});
 
-fn url_regex() -> Regex {
-    Regex::new(r#"https?://[^\s"'<>]+"#).expect("valid URL regex")
-}
+/// 不带 `Cookie:` 前缀、单独出现的登录态 Cookie（微博、知乎、百度、京东等），名称区分大小写
+static SESSION_COOKIE_RE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(
+        r#"\b(SUB|SUBP|SCF|ALF|SSOLoginState|_T_WM|XSRF-TOKEN|z_c0|d_c0|BDUSS|STOKEN|pt_key|pt_pin|thor|PHPSESSID|JSESSIONID|sessionid|csrftoken)=([^;\s,"'&]+)"#,
+    )
+    .expect("valid session cookie regex")
+});
+
+/// 预签名 URL / 图床接口中的签名与令牌参数（S3、OSS、COS、七牛等）
+static SIGNED_QUERY_RE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(
+        r#"(?i)([?&](?:x-amz-signature|x-amz-credential|x-amz-security-token|signature|sign|sig|q-signature|q-ak|ossaccesskeyid|security-token|access_token|auth_key|upload_token|token))=([^&\s"'#<>]+)"#,
+    )
+    .expect("valid signed query regex")
+});
+
+static URL_RE: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r#"https?://[^\s"'<>]+"#).expect("valid URL regex"));
 
-fn windows_path_regex() -> Regex {
+static WINDOWS_PATH_RE: LazyLock<Regex> = LazyLock::new(|| {
     Regex::new(r#"\b[A-Za-z]:[\\/][^\s"'<>|]+"#).expect("valid Windows path regex")
-}
+});
 
-fn unix_path_regex() -> Regex {
+static UNIX_PATH_RE: LazyLock<Regex> = LazyLock::new(|| {
     Regex::new(r#"(^|[\s"'`=({,])(/(?:Users|home|tmp|var|private|Volumes)[^\s"'<>)]*)"#)
         .expect("valid Unix path regex")
-}
+});
 
 #[cfg(test)]
 mod tests {
@@ -182,4 +207,25 @@ mod tests {
         assert!(!result.contains("secret-token"));
         assert!(!result.contains("SUB=abc"));
     }
+
+    #[test]
+    fn redact_secrets_masks_bare_session_cookies_and_signed_queries() {
+        let raw = "[微博] 提取到 SUB=_2A25Lx; SUBP=0033Wr, 上传地址 https://bucket.s3.amazonaws.com/a.png?X-Amz-Credential=AKIA%2F&X-Amz-Signature=deadbeef&size=1";
+        let result = redact_secrets(raw);
+
+        assert!(result.contains("SUB=[REDACTED]; SUBP=[REDACTED]"));
+        assert!(result.contains("?X-Amz-Credential=[REDACTED]&X-Amz-Signature=[REDACTED]&size=1"));
+        assert!(!result.contains("_2A25Lx"));
+        assert!(!result.contains("deadbeef"));
+    }
+
+    #[test]
+    fn redact_secrets_keeps_plain_messages_and_is_idempotent() {
+        let plain = "[下载图片] 下载成功，大小: 1024 bytes";
+        assert!(matches!(redact_secrets(plain), Cow::Borrowed(_)));
+
+        let once = redact_secrets("https://example.com/a.png?token=abc&x=1").into_owned();
+        assert_eq!(once, "https://example.com/a.png?token=[REDACTED]&x=1");
+        assert_eq!(redact_secrets(&once), once);
+    }
 }
@@ -4,12 +4,25 @@
     windows_subsystem = "windows"
 )]
 
+mod atomic_file;
 mod cli;
 mod commands;
+#[cfg(target_os = "linux")]
+mod dbus_service;
+mod deep_link;
+mod editor_protocol;
 mod error;
+mod git_hook;
 mod log_utils;
+mod native_messaging;
+mod open_files;
+mod path_utils;
 mod portable;
+mod power_events;
+mod quick_actions;
 mod server;
+mod uploaders;
+mod url_utils;
 
 use error::AppError;
 use log::LevelFilter;
@@ -328,11 +341,14 @@ fn validate_webdav_url(raw_url: &str) -> Result<Url, AppError> {
     validate_external_url(raw_url).map_err(|err| AppError::webdav(err.to_string()))
 }
 
-pub struct HttpClient(pub reqwest::Client);
+pub use commands::http_client::HttpClient;
 
 fn main() {
+    commands::startup_profile::mark_process_start();
+
     // CLI 模式检测
-    match cli::parse_cli_args() {
+    let mut startup_open_files = Vec::new();
+    let startup_quick_action = match cli::parse_cli_args() {
         cli::CliAction::Help => {
             cli::print_help();
             return;
@@ -343,11 +359,22 @@ fn main() {
         }
         cli::CliAction::Upload {
             files,
-            json_output,
+            output,
             service_id,
             profile,
         } => {
-            cli::run_cli_upload(files, json_output, service_id, profile);
+            cli::run_cli_upload(files, output, service_id, profile);
+            return;
+        }
+        cli::CliAction::LatestLink(output) => {
+            cli::run_cli_latest_link(output);
+            return;
+        }
+        cli::CliAction::GitHook {
+            service_id,
+            dry_run,
+        } => {
+            git_hook::run_git_hook(service_id, dry_run);
             return;
         }
         cli::CliAction::Error(message) => {
@@ -355,22 +382,41 @@ fn main() {
             eprintln!("[PicNexus] 使用 --help 查看命令行用法");
             std::process::exit(1);
         }
-        cli::CliAction::None => {}
-    }
-
-    // 创建全局 HTTP 客户端（带连接池配置）
-    let http_client = reqwest::Client::builder()
-        .timeout(std::time::Duration::from_secs(60)) // 60秒超时
-        .connect_timeout(std::time::Duration::from_secs(10)) // 10秒连接超时
-        .pool_idle_timeout(std::time::Duration::from_secs(90)) // 连接池空闲超时
-        .pool_max_idle_per_host(10) // 每个主机最多保持10个空闲连接
-        .build()
-        .unwrap_or_else(|e| {
-            log::warn!("[HTTP Client] 创建失败: {:?}，使用默认配置", e);
-            reqwest::Client::new()
-        });
+        cli::CliAction::QuickAction(action) => Some(action),
+        cli::CliAction::NativeMessaging => {
+            native_messaging::run_native_host();
+            return;
+        }
+        cli::CliAction::EditorStdio => {
+            editor_protocol::run_editor_stdio();
+            return;
+        }
+        cli::CliAction::InstallNativeHost(extension_id) => {
+            match native_messaging::install_native_host(&extension_id) {
+                Ok(registered) => {
+                    for location in registered {
+                        eprintln!("[PicNexus] 已注册: {}", location);
+                    }
+                }
+                Err(e) => {
+                    eprintln!("[PicNexus] {}", e);
+                    std::process::exit(1);
+                }
+            }
+            return;
+        }
+        cli::CliAction::OpenFiles(files) => {
+            let cwd = std::env::current_dir().unwrap_or_default();
+            startup_open_files = open_files::resolve_open_files(&files, &cwd);
+            None
+        }
+        cli::CliAction::DeepLink | cli::CliAction::None => None,
+    };
 
-    let mut log_targets = vec![Target::new(TargetKind::Stdout)];
+    // 日志文件按 10MB 轮转、保留 5 个；控制台输出只在开发构建中开启
+    let mut log_targets = Vec::new();
+    #[cfg(debug_assertions)]
+    log_targets.push(Target::new(TargetKind::Stdout));
     if let Some(log_dir) = portable::portable_data_dir().map(|dir| dir.join("logs")) {
         log_targets.push(Target::new(TargetKind::Folder {
             path: log_dir,
@@ -383,13 +429,24 @@ fn main() {
     log_targets.push(Target::new(TargetKind::Webview));
 
     tauri::Builder::default()
-        .plugin(tauri_plugin_single_instance::init(|app, _args, _cwd| {
-            if let Some(window) = app.get_webview_window("main") {
-                let _ = window.unminimize();
-                let _ = window.show();
-                let _ = window.set_focus();
+        .plugin(tauri_plugin_single_instance::init(|app, args, cwd| {
+            // 跳转列表等入口会以 --quick-action 启动第二个进程，参数在此转发给主实例
+            if let Some(action) = quick_actions::find_quick_action(&args) {
+                quick_actions::dispatch_quick_action(app, action);
+                return;
             }
+
+            // 「打开方式」/ 拖到 exe 上：相对路径按第二个进程的工作目录解析
+            let files = open_files::find_open_files(&args, Path::new(&cwd));
+            if !files.is_empty() {
+                open_files::handle_open_files(app, files);
+                return;
+            }
+
+            // picnexus:// 链接由 deep-link feature 转发到 on_open_url，这里只负责唤起窗口
+            let _ = reveal_main_window(app);
         }))
+        .plugin(tauri_plugin_deep_link::init())
         // 注册 Tauri 2.0 插件
         .plugin(tauri_plugin_positioner::init())
         .plugin(tauri_plugin_autostart::init(
@@ -409,38 +466,79 @@ fn main() {
         .plugin(
             tauri_plugin_log::Builder::new()
                 .targets(log_targets)
-                .level(LevelFilter::Info)
-                .level_for("picnexus", LevelFilter::Debug)
-                .level_for("hyper", LevelFilter::Warn)
-                .level_for("hyper_util", LevelFilter::Warn)
-                .level_for("reqwest", LevelFilter::Warn)
-                .level_for("rustls", LevelFilter::Warn)
-                .level_for("tungstenite", LevelFilter::Warn)
-                .level_for("tokio_tungstenite", LevelFilter::Warn)
-                .level_for("aws_sdk_s3", LevelFilter::Warn)
-                .level_for("aws_config", LevelFilter::Warn)
-                .level_for("aws_smithy_runtime", LevelFilter::Warn)
-                .level_for("tracing", LevelFilter::Warn)
+                // 插件放行全部级别，按模块的级别由 log_level 在运行时过滤（set_log_level 可调整）
+                .level(LevelFilter::Trace)
+                .filter(commands::log_level::enabled)
+                // 写出前统一脱敏 Cookie、令牌与签名参数（含前端转发和第三方库的日志）；
+                // 格式与插件默认一致：UTC 时间 + 目标 + 级别
+                .format(|out, message, record| {
+                    let message = message.to_string();
+                    out.finish(format_args!(
+                        "{}[{}][{}] {}",
+                        chrono::Utc::now().format("[%Y-%m-%d][%H:%M:%S]"),
+                        record.target(),
+                        record.level(),
+                        log_utils::redact_secrets(&message)
+                    ))
+                })
                 .max_file_size(10_000_000)
                 .rotation_strategy(tauri_plugin_log::RotationStrategy::KeepSome(5))
                 .build(),
         )
-        .manage(HttpClient(http_client)) // 注册全局 HTTP 客户端
+        // picnexus-file:// 协议：前端直接显示本地图片（带令牌校验，可按需缩放）
+        .register_asynchronous_uri_scheme_protocol(
+            commands::file_protocol::FILE_PROTOCOL_SCHEME,
+            |_ctx, request, responder| {
+                tauri::async_runtime::spawn(async move {
+                    responder.respond(commands::file_protocol::handle_file_request(request).await);
+                });
+            },
+        )
+        .manage(HttpClient::shared()) // 注册全局 HTTP 客户端（首次请求时构建）
         .manage(CloseToTrayState(AtomicBool::new(true)))
+        .manage(commands::clipboard::SharedClipboard::default())
+        .manage(commands::net_limiter::NetworkLimiter::default()) // 上传 / 检测 / 下载共享的并发上限
         .manage(commands::link_checker::BatchCheckCancelFlag::new())
+        .manage(commands::host_health::HostHealthBoard::default())
+        .manage(commands::task_registry::TaskRegistry::default()) // 可取消的长耗时任务
         .manage(commands::link_checker::BatchCheckPauseFlag(Arc::new(
             AtomicBool::new(false),
         )))
         .manage(commands::md_scanner::MdScanCancelFlag(Arc::new(
             AtomicBool::new(false),
         )))
+        .manage(commands::host_migrator::MigrationCancelFlag(Arc::new(
+            AtomicBool::new(false),
+        )))
         .manage(ServerState {
             upload_config: Arc::new(TokioMutex::new(None)),
             auth_token: Arc::new(TokioMutex::new(None)),
             abort_handle: std::sync::Mutex::new(None),
         })
+        .manage(quick_actions::PendingQuickAction(std::sync::Mutex::new(
+            startup_quick_action,
+        )))
+        .manage(deep_link::PendingDeepLinks::default())
+        .manage(open_files::PendingOpenFiles(std::sync::Mutex::new(
+            startup_open_files,
+        )))
+        .manage(commands::retry_failed::PendingRetries::default())
         .invoke_handler(tauri::generate_handler![
             set_close_to_tray,
+            commands::http_client::set_http_client_tuning,
+            commands::memory_budget::set_memory_budget,
+            commands::thumbnail_cache::clear_thumbnail_cache,
+            commands::thumbnail_prefetch::prefetch_thumbnails,
+            commands::cache_manager::get_cache_usage,
+            commands::cache_manager::clear_cache,
+            commands::startup_profile::get_startup_profile,
+            commands::log_level::set_log_level,
+            commands::log_level::get_log_levels,
+            commands::diagnostics::export_diagnostics,
+            commands::task_registry::cancel_task,
+            commands::task_registry::cancel_stalled_task,
+            commands::crash_report::get_pending_crash_reports,
+            commands::crash_report::dismiss_crash_reports,
             is_portable_mode,
             get_user_data_dir,
             get_history_db_path,
@@ -454,6 +552,7 @@ fn main() {
             test_webdav_connection,
             commands::upload::upload_file_stream,
             commands::upload::test_weibo_connection,
+            commands::upload::probe_weibo_session,
             commands::r2::upload_to_r2,
             commands::jd::upload_to_jd,
             commands::jd::check_jd_available,
@@ -475,11 +574,27 @@ fn main() {
             commands::cli_path::get_cli_path_status,
             commands::cli_path::add_cli_to_path,
             commands::cli_path::remove_cli_from_path,
+            commands::context_menu::get_context_menu_status,
+            commands::context_menu::install_context_menu,
+            commands::context_menu::uninstall_context_menu,
+            commands::secret_vault::get_keychain_cookie,
+            commands::secret_vault::delete_keychain_cookie,
             commands::smms::upload_to_smms,
             commands::github::upload_to_github,
             commands::imgur::upload_to_imgur,
             commands::s3_compatible::upload_to_s3_compatible,
+            uploaders::oss::upload_to_aliyun_oss,
             commands::s3_compatible::test_s3_connection,
+            commands::connection_probe::test_host_connection,
+            commands::host_capabilities::get_host_capabilities,
+            commands::uploader_registry::list_uploaders,
+            uploaders::list_upload_providers,
+            uploaders::upload_image,
+            uploaders::smms::save_smms_token,
+            uploaders::smms::delete_smms_image,
+            uploaders::smms::get_smms_quota,
+            commands::host_health::record_host_outcome,
+            commands::host_health::get_host_health,
             commands::link_checker::check_image_link,
             commands::link_checker::download_image_from_url,
             commands::link_checker::download_url_image,
@@ -490,16 +605,59 @@ fn main() {
             commands::clipboard::clipboard_has_image,
             commands::clipboard::read_clipboard_image,
             commands::clipboard::cleanup_clipboard_temp_file,
+            commands::clipboard_rehost::rehost_clipboard_url,
             commands::user_files::export_text_file,
             commands::user_files::import_text_file,
             commands::user_files::cleanup_owned_temp_file,
+            commands::recent_dirs::get_recent_dirs,
+            commands::recent_dirs::record_recent_dir,
             commands::image_meta::get_image_metadata,
+            commands::image_meta::get_image_exif,
+            commands::image_compare::compare_images,
+            commands::path_stat::stat_path,
+            commands::utils::reveal_in_file_manager,
+            commands::utils::open_with_default_app,
+            commands::utils::trash_file,
+            commands::drag_out::start_drag_out,
             commands::image_compress::compress_image,
             commands::image_compress::cleanup_compressed_files,
             commands::image_compress::strip_exif_only,
             commands::image_compress::read_image_as_base64,
+            commands::file_protocol::get_file_protocol_token,
             commands::md_scanner::scan_md_folder,
             commands::md_scanner::cancel_md_scan,
+            commands::md_processor::process_markdown,
+            commands::md_auditor::audit_markdown,
+            commands::host_migrator::migrate_images,
+            commands::host_migrator::cancel_migration,
+            commands::host_migrator::export_migration_mapping,
+            commands::share_page::create_share_page,
+            commands::short_link::shorten_url,
+            commands::qr_code::generate_qr,
+            commands::window_capture::list_capturable_windows,
+            commands::window_capture::capture_window,
+            commands::alt_text::suggest_alt_text,
+            commands::link_repair_webhook::send_link_repair_webhook,
+            commands::link_templates::list_link_templates,
+            commands::link_templates::save_link_template,
+            commands::link_templates::delete_link_template,
+            commands::link_templates::render_link,
+            commands::presets::export_preset,
+            commands::presets::import_preset,
+            commands::presets::read_preset,
+            commands::object_name::get_object_name_rules,
+            commands::object_name::set_object_name_rules,
+            commands::object_name::sanitize_object_name,
+            commands::post_upload_action::get_post_upload_action,
+            commands::post_upload_action::set_post_upload_action,
+            commands::palette::palette_query,
+            commands::usage_stats::get_usage_stats,
+            commands::time_format::format_timestamps,
+            commands::plugin_uploader::upload_via_plugin,
+            commands::upload_manifest::export_upload_manifest,
+            commands::upload_manifest::verify_manifest,
+            commands::retry_failed::retry_failed,
+            commands::retry_failed::take_pending_retries,
             get_or_create_secure_key,
             set_secure_key,
             open_log_dir,
@@ -508,9 +666,18 @@ fn main() {
             check_port_free,
             update_server_config,
             save_cli_config,
-            get_executable_path
+            get_executable_path,
+            quick_actions::take_pending_quick_action,
+            deep_link::take_pending_deep_links,
+            open_files::take_pending_open_files
         ])
         .setup(|app| {
+            let setup_started = std::time::Instant::now();
+            // 日志插件初始化时把全局级别设为 Trace，这里收窄到实际配置的最高级别
+            commands::log_level::sync_max_level();
+            // 尽早安装 panic hook，启动阶段的 panic 也能留下崩溃报告
+            commands::crash_report::install_panic_hook(app.handle());
+
             // 1. 创建原生菜单栏 (仅 macOS)
             // 在 Windows 上不设置原生菜单栏，避免启动时菜单栏闪烁
             #[cfg(target_os = "macos")]
@@ -534,7 +701,26 @@ fn main() {
 
                 let window_menu = Submenu::with_items(app, "窗口", true, &[&history])?;
 
-                let menu = Menu::with_items(app, &[&file_menu, &window_menu])?;
+                // 快捷操作：macOS 不创建托盘，Tauri 也未开放 Dock 菜单接口，放在菜单栏中
+                let quick_action_items = quick_actions::QuickAction::ALL
+                    .into_iter()
+                    .map(|action| {
+                        MenuItem::with_id(
+                            app,
+                            format!("{}{}", quick_actions::MENU_ID_PREFIX, action.id()),
+                            action.label(),
+                            true,
+                            None::<&str>,
+                        )
+                    })
+                    .collect::<Result<Vec<_>, _>>()?;
+                let quick_action_refs = quick_action_items
+                    .iter()
+                    .map(|item| item as &dyn tauri::menu::IsMenuItem<_>)
+                    .collect::<Vec<_>>();
+                let quick_menu = Submenu::with_items(app, "快捷操作", true, &quick_action_refs)?;
+
+                let menu = Menu::with_items(app, &[&file_menu, &quick_menu, &window_menu])?;
                 app.set_menu(menu)?;
 
                 // 处理菜单事件 (macOS)
@@ -562,7 +748,15 @@ fn main() {
                             }
                         }
                         _ => {
-                            log::debug!("未知菜单项: {}", menu_id);
+                            match menu_id
+                                .strip_prefix(quick_actions::MENU_ID_PREFIX)
+                                .and_then(quick_actions::QuickAction::from_id)
+                            {
+                                Some(action) => {
+                                    quick_actions::dispatch_quick_action(app_handle, action)
+                                }
+                                None => log::debug!("未知菜单项: {}", menu_id),
+                            }
                         }
                     }
                 });
@@ -572,6 +766,7 @@ fn main() {
             // macOS 使用上面的应用级菜单栏作为入口，不创建系统托盘
             #[cfg(not(target_os = "macos"))]
             {
+                let tray_started = std::time::Instant::now();
                 let tray_menu_window = WebviewWindowBuilder::new(
                     app,
                     TRAY_MENU_WINDOW_LABEL,
@@ -636,8 +831,63 @@ fn main() {
                         }
                     })
                     .build(app)?;
+                commands::startup_profile::record("tray", tray_started, false);
             } // #[cfg(not(target_os = "macos"))] 块结束
 
+            // 任务栏跳转列表：与托盘菜单共用快捷操作分发（COM 调用较慢，放到后台线程）
+            #[cfg(windows)]
+            std::thread::spawn(|| {
+                let result = commands::startup_profile::measure_deferred(
+                    "jump_list",
+                    quick_actions::register_jump_list,
+                );
+                if let Err(e) = result {
+                    log::warn!("[QuickAction] {}", e);
+                }
+            });
+
+            // Linux DBus 服务：供平铺式窗口管理器脚本调用
+            #[cfg(target_os = "linux")]
+            {
+                let app_handle = app.handle().clone();
+                tauri::async_runtime::spawn(async move {
+                    let started = std::time::Instant::now();
+                    let result = dbus_service::start_dbus_service(app_handle).await;
+                    commands::startup_profile::record("dbus", started, true);
+                    if let Err(e) = result {
+                        log::warn!("[DBus] 注册服务失败: {}", e);
+                    }
+                });
+            }
+
+            // picnexus:// 深度链接
+            commands::startup_profile::measure("deep_link", || {
+                use tauri_plugin_deep_link::DeepLinkExt;
+
+                // 安装包会注册协议；便携版 / 开发模式需要运行时写入注册表或 .desktop
+                #[cfg(any(windows, target_os = "linux"))]
+                if portable::is_portable() || cfg!(debug_assertions) {
+                    if let Err(e) = app.deep_link().register_all() {
+                        log::warn!(
+                            "[DeepLink] 注册 {}:// 协议失败: {}",
+                            deep_link::DEEP_LINK_SCHEME,
+                            e
+                        );
+                    }
+                }
+
+                let app_handle = app.handle().clone();
+                app.deep_link().on_open_url(move |event| {
+                    deep_link::handle_deep_links(&app_handle, event.urls());
+                });
+
+                match app.deep_link().get_current() {
+                    Ok(Some(urls)) => deep_link::handle_deep_links(app.handle(), urls),
+                    Ok(None) => {}
+                    Err(e) => log::warn!("[DeepLink] 读取启动链接失败: {}", e),
+                }
+            });
+
             // 5. 窗口初始化
             let window = match app.get_webview_window("main") {
                 Some(w) => w,
@@ -720,6 +970,15 @@ fn main() {
                             if close_to_tray {
                                 api.prevent_close();
                                 let _ = window_for_close.hide();
+                            } else {
+                                // 真正退出：中止仍在进行的上传 / 下载，避免请求在进程退出前半途写入
+                                let cancelled = window_for_close
+                                    .app_handle()
+                                    .state::<commands::task_registry::TaskRegistry>()
+                                    .cancel_all();
+                                if cancelled > 0 {
+                                    log::info!("[任务] 窗口关闭，已取消 {} 个任务", cancelled);
+                                }
                             }
                         }
                         #[cfg(target_os = "windows")]
@@ -758,41 +1017,67 @@ fn main() {
                 });
             }
 
-            // 启动时清理过期日志（保留最近 7 天）
+            match portable::cache_dir(app.handle()) {
+                Ok(dir) => commands::thumbnail_cache::init(dir.join("thumbnails")),
+                Err(e) => log::warn!("[缩略图缓存] 缓存目录不可用，仅使用内存缓存: {}", e),
+            }
+
+            // 启动时清理过期日志（保留最近 7 天），慢盘上遍历目录较慢，放到后台线程
             if let Ok(log_dir) = portable::log_dir(app.handle()) {
-                let max_age = std::time::Duration::from_secs(7 * 24 * 3600);
-                let now = std::time::SystemTime::now();
-                for entry in std::fs::read_dir(&log_dir).into_iter().flatten().flatten() {
-                    let path = entry.path();
-                    let is_log = path.extension().and_then(|e| e.to_str()) == Some("log")
-                        || path.to_string_lossy().contains(".log.");
-                    if !is_log {
-                        continue;
-                    }
+                std::thread::spawn(move || {
+                    commands::startup_profile::measure_deferred("log_cleanup", || {
+                        cleanup_expired_logs(&log_dir)
+                    })
+                });
+            }
 
-                    let expired = entry
-                        .metadata()
-                        .ok()
-                        .and_then(|m| m.modified().or_else(|_| m.created()).ok())
-                        .and_then(|t| now.duration_since(t).ok())
-                        .is_some_and(|age| age > max_age);
+            // 休眠唤醒检测：唤醒后通知前端错开执行定时检测
+            power_events::start(app.handle().clone());
 
-                    if expired {
-                        let _ = std::fs::remove_file(&path);
-                        log::debug!(
-                            "[日志清理] 已删除过期日志: {}",
-                            log_utils::safe_path(&path.to_string_lossy())
-                        );
-                    }
-                }
-            }
+            // 清理上次运行残留的过期临时文件，并统计应用临时目录的当前占用
+            std::thread::spawn(|| {
+                commands::startup_profile::measure_deferred(
+                    "temp_sweep",
+                    commands::tempfiles::sweep_expired,
+                )
+            });
 
+            commands::startup_profile::record("setup", setup_started, false);
             Ok(())
         })
         .run(tauri::generate_context!())
         .expect("error while running tauri application");
 }
 
+/// 删除日志目录下超过 7 天的日志文件
+fn cleanup_expired_logs(log_dir: &Path) {
+    let max_age = std::time::Duration::from_secs(7 * 24 * 3600);
+    let now = std::time::SystemTime::now();
+    for entry in std::fs::read_dir(log_dir).into_iter().flatten().flatten() {
+        let path = entry.path();
+        let is_log = path.extension().and_then(|e| e.to_str()) == Some("log")
+            || path.to_string_lossy().contains(".log.");
+        if !is_log {
+            continue;
+        }
+
+        let expired = entry
+            .metadata()
+            .ok()
+            .and_then(|m| m.modified().or_else(|_| m.created()).ok())
+            .and_then(|t| now.duration_since(t).ok())
+            .is_some_and(|age| age > max_age);
+
+        if expired {
+            let _ = std::fs::remove_file(&path);
+            log::debug!(
+                "[日志清理] 已删除过期日志: {}",
+                log_utils::safe_path(&path.to_string_lossy())
+            );
+        }
+    }
+}
+
 /// Cookie 更新事件的 payload 结构
 #[derive(Clone, serde::Serialize)]
 struct CookieUpdatedPayload {
@@ -966,6 +1251,11 @@ async fn save_cookie_from_login(
         )));
     }
 
+    // 钥匙串保存失败不阻断登录，Cookie 仍会写入加密配置
+    if let Err(e) = commands::secret_vault::store_cookie(&service, &cookie) {
+        log::warn!("[保存Cookie] 写入钥匙串失败: {}", e);
+    }
+
     if let Some(main_window) = app.get_webview_window("main") {
         let payload = CookieUpdatedPayload {
             service_id: service.clone(),
@@ -2197,7 +2487,7 @@ async fn test_r2_connection(
     );
 
     match http_client
-        .0
+        .get()
         .head(&endpoint_url)
         .header("Host", host)
         .header("x-amz-date", datetime_str)
@@ -2257,7 +2547,7 @@ async fn test_webdav_connection(
     );
 
     let response = http_client
-        .0
+        .get()
         .request(
             reqwest::Method::from_bytes(b"PROPFIND").unwrap(),
             &config.url,
@@ -2311,7 +2601,7 @@ async fn webdav_request(
     }
 
     let timeout = Duration::from_millis(request.timeout_ms.unwrap_or(30_000).clamp(1_000, 60_000));
-    let mut builder = http_client.0.request(method, url).timeout(timeout);
+    let mut builder = http_client.get().request(method, url).timeout(timeout);
 
     if let Some(headers) = request.headers {
         for (name, value) in headers {
@@ -2377,7 +2667,7 @@ fn get_or_create_secure_key() -> Result<String, AppError> {
         let mut key_bytes = [0u8; 32];
         rand::thread_rng().fill(&mut key_bytes);
         let new_key = STANDARD.encode(key_bytes);
-        std::fs::write(&key_path, &new_key)
+        atomic_file::write(&key_path, &new_key)
             .map_err(|e| AppError::file_io(format!("无法保存便携密钥: {}", e)))?;
         return Ok(new_key);
     }
@@ -2424,7 +2714,7 @@ fn set_secure_key(key: String) -> Result<(), AppError> {
             std::fs::create_dir_all(parent)
                 .map_err(|e| AppError::file_io(format!("无法创建便携数据目录: {}", e)))?;
         }
-        std::fs::write(&key_path, key)
+        atomic_file::write(&key_path, key)
             .map_err(|e| AppError::file_io(format!("无法更新便携密钥: {}", e)))?;
         log::debug!("[密钥管理] ✓ 密钥已更新到便携数据目录");
         return Ok(());
@@ -2509,12 +2799,15 @@ fn validate_open_url(input: &str) -> Result<Option<OpenTarget>, AppError> {
     Ok(Some(OpenTarget::Url(validated.to_string())))
 }
 
-fn validate_open_file_path(input: &str) -> Result<OpenTarget, AppError> {
-    if has_forbidden_windows_device_prefix(input) {
+/// 校验要交给系统打开的本地路径：必须是存在的绝对路径，拒绝设备路径与可执行文件
+fn validate_open_file_path(input: &str) -> Result<PathBuf, AppError> {
+    // 先去掉能安全去掉的 \\?\ 前缀（应用自己返回过的规范化路径），剩下的才是设备路径
+    let normalized = path_utils::normalize_input(input);
+    if has_forbidden_windows_device_prefix(&normalized.to_string_lossy()) {
         return Err(AppError::validation("不支持的系统设备路径"));
     }
 
-    let path = Path::new(input);
+    let path = normalized.as_path();
     if !path.is_absolute() {
         return Err(AppError::validation("只能打开绝对路径"));
     }
@@ -2525,7 +2818,7 @@ fn validate_open_file_path(input: &str) -> Result<OpenTarget, AppError> {
         return Err(AppError::validation("只能打开文件或文件夹"));
     }
 
-    let canonical = std::fs::canonicalize(path)
+    let canonical = path_utils::canonicalize(path)
         .map_err(|e| AppError::file_io(format!("无法解析路径: {}", e)))?;
 
     if let Some(ext) = canonical
@@ -2538,7 +2831,7 @@ fn validate_open_file_path(input: &str) -> Result<OpenTarget, AppError> {
         }
     }
 
-    Ok(OpenTarget::Path(canonical))
+    Ok(canonical)
 }
 
 fn validate_open_target(input: &str) -> Result<OpenTarget, AppError> {
@@ -2554,7 +2847,7 @@ fn validate_open_target(input: &str) -> Result<OpenTarget, AppError> {
         return Ok(target);
     }
 
-    validate_open_file_path(trimmed)
+    validate_open_file_path(trimmed).map(OpenTarget::Path)
 }
 
 #[cfg(test)]
@@ -2690,7 +2983,7 @@ async fn save_cli_config(
     let json = serde_json::to_string_pretty(&payload)
         .map_err(|e| AppError::config(format!("CLI 配置序列化失败: {}", e)))?;
 
-    std::fs::write(&config_path, &json)
+    atomic_file::write(&config_path, &json)
         .map_err(|e| AppError::file_io(format!("写入 cli-config.json 失败: {}", e)))?;
 
     log::info!(
@@ -2715,7 +3008,10 @@ async fn check_port_free(port: u16) -> bool {
 ///   传 null 时清空配置（Server 收到请求会提示未配置图床）
 #[tauri::command]
 async fn update_server_config(
+    app: tauri::AppHandle,
     state: tauri::State<'_, ServerState>,
+    clipboard: tauri::State<'_, commands::clipboard::SharedClipboard>,
+    limiter: tauri::State<'_, commands::net_limiter::NetworkLimiter>,
     enabled: bool,
     port: u16,
     service_config_json: Option<String>,
@@ -2771,8 +3067,31 @@ async fn update_server_config(
 
         let config_arc = Arc::clone(&state.upload_config);
         let auth_token_arc = Arc::clone(&state.auth_token);
+        let clipboard = clipboard.inner().clone();
+        let limiter = limiter.inner().clone();
+        let history_db_path = portable::history_db_path(&app)
+            .map_err(|e| {
+                log::warn!(
+                    "[Server] 历史记录数据库路径不可用，/history 将被禁用: {}",
+                    e
+                )
+            })
+            .ok();
+        let share_dir = portable::user_data_dir(&app)
+            .map(|dir| dir.join(commands::share_page::SHARE_PAGE_DIR))
+            .ok();
         let task = tokio::task::spawn(async move {
-            if let Err(e) = server::run_server(listener, config_arc, auth_token_arc).await {
+            if let Err(e) = server::run_server(
+                listener,
+                config_arc,
+                auth_token_arc,
+                history_db_path,
+                clipboard,
+                share_dir,
+                limiter,
+            )
+            .await
+            {
                 log::error!("[Server] 运行失败: {}", e);
             }
         });
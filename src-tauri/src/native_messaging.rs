@@ -0,0 +1,404 @@
+// src-tauri/src/native_messaging.rs
+// 浏览器扩展 Native Messaging Host
+//
+// 工作流：
+//   扩展右键「用 PicNexus 转存这张图片」-> chrome.runtime.sendNativeMessage("com.picnexus.host", ...)
+//   -> 浏览器启动 picnexus chrome-extension://<id>/ -> 本模块按 stdio 协议读取消息
+//   -> 下载图片 -> 按 cli-config.json 中的图床上传 -> 返回新链接
+//
+// 协议：每条消息为 4 字节本机字节序长度 + UTF-8 JSON，stdout 只能写协议消息。
+//   {"action": "ping"}                                  -> {"success": true, "version", "services"}
+//   {"action": "rehost", "url": "...", "service": "r2"} -> {"success": true, "url": "..."}
+//   请求中的 id 字段会原样带回，便于扩展对应 connectNative 的响应。
+//
+// 注册：picnexus --install-native-host <扩展 ID> 生成 manifest 并注册到 Chrome / Edge。
+
+use std::io::{Read, Write};
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+
+use crate::cli;
+use crate::commands::deadline::{self, REHOST_DEADLINE};
+use crate::commands::link_checker::download_url_image_to_temp;
+use crate::server::upload_handler::upload_single_file;
+use crate::url_utils;
+
+pub const NATIVE_HOST_NAME: &str = "com.picnexus.host";
+pub const INSTALL_NATIVE_HOST_ARG: &str = "--install-native-host";
+
+/// 扩展发给 host 的单条消息上限（Chrome 允许更大，但转存请求只含 URL）
+const MAX_INCOMING_MESSAGE_SIZE: usize = 64 * 1024;
+/// host 发给浏览器的单条消息上限（Chrome 限制为 1 MB）
+const MAX_OUTGOING_MESSAGE_SIZE: usize = 1024 * 1024;
+
+#[derive(Debug, Deserialize)]
+#[serde(tag = "action", rename_all = "camelCase")]
+enum NativeRequestKind {
+    Ping,
+    Rehost {
+        url: String,
+        #[serde(default)]
+        service: Option<String>,
+    },
+}
+
+#[derive(Debug, Deserialize)]
+struct NativeRequest {
+    #[serde(default)]
+    id: Option<serde_json::Value>,
+    #[serde(flatten)]
+    kind: NativeRequestKind,
+}
+
+#[derive(Debug, Default, Serialize)]
+struct NativeResponse {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    id: Option<serde_json::Value>,
+    success: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    url: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    service: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    version: Option<&'static str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    services: Option<Vec<String>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    error: Option<String>,
+}
+
+impl NativeResponse {
+    fn failure(id: Option<serde_json::Value>, error: impl Into<String>) -> Self {
+        Self {
+            id,
+            error: Some(error.into()),
+            ..Default::default()
+        }
+    }
+}
+
+/// 判断当前进程是否由浏览器作为 native messaging host 启动
+///
+/// - Chrome / Edge：第一个参数是调用方 origin（chrome-extension://<id>/），
+///   Windows 上还会追加 --parent-window=<hwnd>
+/// - Firefox：参数为 manifest 路径和扩展 ID
+pub fn is_native_messaging_launch(args: &[String]) -> bool {
+    match args {
+        [origin, ..] if origin.starts_with("chrome-extension://") => true,
+        [manifest, _extension_id] => manifest.ends_with(&format!("{}.json", NATIVE_HOST_NAME)),
+        _ => false,
+    }
+}
+
+/// Chrome 扩展 ID：32 个 a-p 字母
+pub fn is_valid_extension_id(id: &str) -> bool {
+    id.len() == 32 && id.bytes().all(|b| (b'a'..=b'p').contains(&b))
+}
+
+fn read_message<R: Read>(reader: &mut R) -> Result<Option<Vec<u8>>, String> {
+    let mut len_buf = [0u8; 4];
+    match reader.read_exact(&mut len_buf) {
+        Ok(()) => {}
+        // 浏览器关闭管道即正常退出
+        Err(e) if e.kind() == std::io::ErrorKind::UnexpectedEof => return Ok(None),
+        Err(e) => return Err(format!("读取消息长度失败: {}", e)),
+    }
+
+    let len = u32::from_ne_bytes(len_buf) as usize;
+    if len > MAX_INCOMING_MESSAGE_SIZE {
+        return Err(format!("消息过大: {} 字节", len));
+    }
+
+    let mut body = vec![0u8; len];
+    reader
+        .read_exact(&mut body)
+        .map_err(|e| format!("读取消息内容失败: {}", e))?;
+    Ok(Some(body))
+}
+
+fn write_message<W: Write>(writer: &mut W, response: &NativeResponse) -> Result<(), String> {
+    let mut body = serde_json::to_vec(response).map_err(|e| format!("序列化响应失败: {}", e))?;
+    if body.len() > MAX_OUTGOING_MESSAGE_SIZE {
+        body = serde_json::to_vec(&NativeResponse::failure(
+            response.id.clone(),
+            "响应超过 1 MB 限制",
+        ))
+        .map_err(|e| format!("序列化响应失败: {}", e))?;
+    }
+
+    writer
+        .write_all(&(body.len() as u32).to_ne_bytes())
+        .and_then(|_| writer.write_all(&body))
+        .and_then(|_| writer.flush())
+        .map_err(|e| format!("写入响应失败: {}", e))
+}
+
+async fn rehost(url: &str, service: Option<&str>) -> Result<(String, String), String> {
+    // 先校验链接，无效链接不必再读取图床配置
+    let url = url_utils::parse_and_normalize(url).map_err(|e| e.to_string())?;
+    let (service_id, config) = cli::resolve_service_config(service)?;
+
+    // 下载 + 上传共用一个总时限，某一步反复卡在自己的超时边缘时不会无限拖下去
+    let new_url = deadline::run("重新上传", REHOST_DEADLINE, async {
+        let downloaded = download_url_image_to_temp(url.as_str())
+            .await
+            .map_err(|e| e.to_string())?;
+
+        // 临时文件随 downloaded 一起 drop 删除
+        upload_single_file(&downloaded.file.path_string(), &config).await
+    })
+    .await
+    .map_err(|e| e.to_string())??;
+    Ok((service_id, new_url))
+}
+
+async fn handle_request(raw: &[u8]) -> NativeResponse {
+    let request: NativeRequest = match serde_json::from_slice(raw) {
+        Ok(request) => request,
+        Err(e) => return NativeResponse::failure(None, format!("无效的请求: {}", e)),
+    };
+
+    match request.kind {
+        NativeRequestKind::Ping => match cli::configured_services() {
+            Ok(services) => NativeResponse {
+                id: request.id,
+                success: true,
+                version: Some(env!("CARGO_PKG_VERSION")),
+                services: Some(services),
+                ..Default::default()
+            },
+            Err(e) => NativeResponse::failure(request.id, e),
+        },
+        NativeRequestKind::Rehost { url, service } => {
+            match rehost(&url, service.as_deref()).await {
+                Ok((service_id, new_url)) => NativeResponse {
+                    id: request.id,
+                    success: true,
+                    url: Some(new_url),
+                    service: Some(service_id),
+                    ..Default::default()
+                },
+                Err(e) => NativeResponse::failure(request.id, e),
+            }
+        }
+    }
+}
+
+/// 运行 native messaging host，直到浏览器关闭 stdin
+pub fn run_native_host() {
+    let runtime = match tokio::runtime::Builder::new_current_thread()
+        .enable_all()
+        .build()
+    {
+        Ok(rt) => rt,
+        Err(e) => {
+            eprintln!("[PicNexus] 初始化失败: {}", e);
+            std::process::exit(1);
+        }
+    };
+
+    let mut stdin = std::io::stdin().lock();
+    let mut stdout = std::io::stdout().lock();
+
+    loop {
+        let raw = match read_message(&mut stdin) {
+            Ok(Some(raw)) => raw,
+            Ok(None) => break,
+            Err(e) => {
+                eprintln!("[PicNexus] {}", e);
+                std::process::exit(1);
+            }
+        };
+
+        let response = runtime.block_on(handle_request(&raw));
+        if let Err(e) = write_message(&mut stdout, &response) {
+            eprintln!("[PicNexus] {}", e);
+            std::process::exit(1);
+        }
+    }
+}
+
+// ==================== 注册 ====================
+
+fn host_manifest(exe: &Path, extension_id: &str) -> serde_json::Value {
+    serde_json::json!({
+        "name": NATIVE_HOST_NAME,
+        "description": "PicNexus 图片转存",
+        "path": exe.to_string_lossy(),
+        "type": "stdio",
+        "allowed_origins": [format!("chrome-extension://{}/", extension_id)],
+    })
+}
+
+/// Chrome / Edge 读取 manifest 的目录（macOS / Linux 按文件位置发现）
+#[cfg(not(windows))]
+fn browser_manifest_dirs() -> Vec<PathBuf> {
+    let Some(home) = std::env::var_os("HOME").map(PathBuf::from) else {
+        return Vec::new();
+    };
+
+    #[cfg(target_os = "macos")]
+    let browsers = [
+        "Library/Application Support/Google/Chrome",
+        "Library/Application Support/Chromium",
+        "Library/Application Support/Microsoft Edge",
+    ];
+    #[cfg(not(target_os = "macos"))]
+    let browsers = [
+        ".config/google-chrome",
+        ".config/chromium",
+        ".config/microsoft-edge",
+    ];
+
+    browsers
+        .iter()
+        .map(|dir| home.join(dir))
+        // 只为已安装的浏览器注册
+        .filter(|dir| dir.is_dir())
+        .map(|dir| dir.join("NativeMessagingHosts"))
+        .collect()
+}
+
+/// 生成 host manifest 并注册到已安装的 Chromium 系浏览器
+pub fn install_native_host(extension_id: &str) -> Result<Vec<String>, String> {
+    if !is_valid_extension_id(extension_id) {
+        return Err(format!("无效的扩展 ID: {}", extension_id));
+    }
+
+    let exe = std::env::current_exe().map_err(|e| format!("无法获取可执行文件路径: {}", e))?;
+    let manifest = serde_json::to_string_pretty(&host_manifest(&exe, extension_id))
+        .map_err(|e| format!("生成 manifest 失败: {}", e))?;
+    let file_name = format!("{}.json", NATIVE_HOST_NAME);
+
+    #[cfg(windows)]
+    {
+        // Windows 通过注册表指向 manifest 文件，manifest 放在应用数据目录
+        let dir = crate::portable::portable_data_dir()
+            .or_else(|| std::env::var_os("APPDATA").map(|p| PathBuf::from(p).join("us.picnex.app")))
+            .ok_or_else(|| "无法确定应用数据目录".to_string())?;
+        std::fs::create_dir_all(&dir).map_err(|e| format!("创建目录失败: {}", e))?;
+        let manifest_path = dir.join(&file_name);
+        std::fs::write(&manifest_path, manifest)
+            .map_err(|e| format!("写入 manifest 失败: {}", e))?;
+
+        let mut registered = Vec::new();
+        for browser in ["Google\\Chrome", "Microsoft\\Edge"] {
+            let key_path = format!(
+                "Software\\{}\\NativeMessagingHosts\\{}",
+                browser, NATIVE_HOST_NAME
+            );
+            windows_registry::CURRENT_USER
+                .create(&key_path)
+                .and_then(|key| key.set_string("", manifest_path.to_string_lossy().as_ref()))
+                .map_err(|e| format!("写入注册表失败 ({}): {}", key_path, e))?;
+            registered.push(format!("HKCU\\{}", key_path));
+        }
+        Ok(registered)
+    }
+
+    #[cfg(not(windows))]
+    {
+        let dirs = browser_manifest_dirs();
+        if dirs.is_empty() {
+            return Err("未找到 Chrome / Chromium / Edge 的配置目录".to_string());
+        }
+
+        let mut registered = Vec::new();
+        for dir in dirs {
+            std::fs::create_dir_all(&dir).map_err(|e| format!("创建目录失败: {}", e))?;
+            let manifest_path = dir.join(&file_name);
+            std::fs::write(&manifest_path, &manifest)
+                .map_err(|e| format!("写入 manifest 失败: {}", e))?;
+            registered.push(manifest_path.to_string_lossy().to_string());
+        }
+        Ok(registered)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Cursor;
+
+    fn args(values: &[&str]) -> Vec<String> {
+        values.iter().map(|value| value.to_string()).collect()
+    }
+
+    fn frame(body: &[u8]) -> Vec<u8> {
+        let mut framed = (body.len() as u32).to_ne_bytes().to_vec();
+        framed.extend_from_slice(body);
+        framed
+    }
+
+    #[test]
+    fn detects_chrome_and_firefox_launch_arguments() {
+        assert!(is_native_messaging_launch(&args(&[
+            "chrome-extension://abcdefghijklmnopabcdefghijklmnop/",
+            "--parent-window=0",
+        ])));
+        assert!(is_native_messaging_launch(&args(&[
+            "/usr/lib/mozilla/native-messaging-hosts/com.picnexus.host.json",
+            "picnexus@example.com",
+        ])));
+        assert!(!is_native_messaging_launch(&args(&["a.png", "b.png"])));
+        assert!(!is_native_messaging_launch(&args(&[])));
+    }
+
+    #[test]
+    fn validates_extension_id() {
+        assert!(is_valid_extension_id("abcdefghijklmnopabcdefghijklmnop"));
+        assert!(!is_valid_extension_id("abcdefghijklmnopabcdefghijklmnoz"));
+        assert!(!is_valid_extension_id("short"));
+    }
+
+    #[test]
+    fn read_message_handles_frames_and_eof() {
+        let mut input = Cursor::new(frame(br#"{"action":"ping"}"#));
+        assert_eq!(
+            read_message(&mut input).unwrap(),
+            Some(br#"{"action":"ping"}"#.to_vec())
+        );
+        assert_eq!(read_message(&mut input).unwrap(), None);
+    }
+
+    #[test]
+    fn read_message_rejects_oversized_frame() {
+        let len = (MAX_INCOMING_MESSAGE_SIZE as u32 + 1).to_ne_bytes();
+        assert!(read_message(&mut Cursor::new(len.to_vec())).is_err());
+    }
+
+    #[test]
+    fn write_message_prefixes_length_and_echoes_id() {
+        let mut output = Vec::new();
+        let response = NativeResponse::failure(Some(serde_json::json!(7)), "boom");
+        write_message(&mut output, &response).unwrap();
+
+        let body = read_message(&mut Cursor::new(output)).unwrap().unwrap();
+        let value: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        assert_eq!(value["id"], 7);
+        assert_eq!(value["success"], false);
+        assert_eq!(value["error"], "boom");
+    }
+
+    #[tokio::test]
+    async fn handle_request_rejects_unknown_action() {
+        let response = handle_request(br#"{"id":"a","action":"delete"}"#).await;
+        assert!(!response.success);
+        assert!(response.error.is_some());
+    }
+
+    #[test]
+    fn host_manifest_restricts_origin_to_extension() {
+        let manifest = host_manifest(
+            Path::new("/opt/picnexus/picnexus"),
+            "abcdefghijklmnopabcdefghijklmnop",
+        );
+        assert_eq!(manifest["name"], NATIVE_HOST_NAME);
+        assert_eq!(manifest["type"], "stdio");
+        assert_eq!(
+            manifest["allowed_origins"][0],
+            "chrome-extension://abcdefghijklmnopabcdefghijklmnop/"
+        );
+    }
+}
@@ -0,0 +1,125 @@
+// src-tauri/src/open_files.rs
+// 「打开方式」/ 拖到 exe 上启动时传入的文件路径
+//
+// 工作流：
+//   picnexus.exe a.png b.jpg（无其他 CLI 选项）
+//   -> 已有实例：single-instance 回调收到参数和第二个进程的 cwd -> handle_open_files
+//   -> 冷启动：main 中解析为 CliAction::OpenFiles，暂存为 PendingOpenFiles
+//   -> 前端上传页收到 open-files 事件后取走，加入上传队列
+
+use std::path::Path;
+use std::sync::Mutex;
+
+use tauri::{Emitter, Manager};
+
+use crate::cli::{parse_cli_args_from, CliAction};
+use crate::deep_link::is_supported_image_path;
+use crate::path_utils;
+
+/// 待加入上传队列的本地文件（绝对路径）
+#[derive(Default)]
+pub struct PendingOpenFiles(pub Mutex<Vec<String>>);
+
+/// 把相对路径解析为绝对路径，并过滤掉不存在或非图片的文件
+pub fn resolve_open_files(files: &[String], cwd: &Path) -> Vec<String> {
+    files
+        .iter()
+        .filter_map(|raw| {
+            let path = path_utils::normalize_input(raw);
+            let path = if path.is_absolute() {
+                path
+            } else {
+                cwd.join(path)
+            };
+
+            if !path.is_file() || !is_supported_image_path(&path) {
+                log::warn!("[OpenFiles] 忽略非图片或不存在的文件: {}", path.display());
+                return None;
+            }
+            Some(path.to_string_lossy().to_string())
+        })
+        .collect()
+}
+
+/// 从 single-instance 回调的完整参数（含可执行文件路径）中提取待上传文件
+pub fn find_open_files(args: &[String], cwd: &Path) -> Vec<String> {
+    match parse_cli_args_from(args.iter().skip(1).cloned()) {
+        CliAction::OpenFiles(files) => resolve_open_files(&files, cwd),
+        _ => Vec::new(),
+    }
+}
+
+/// 入队后显示主窗口并切换到上传页，由前端取走加入上传队列
+pub fn handle_open_files(app: &tauri::AppHandle, files: Vec<String>) {
+    if files.is_empty() {
+        return;
+    }
+    log::info!("[OpenFiles] 收到 {} 个待上传文件", files.len());
+
+    if let Ok(mut pending) = app.state::<PendingOpenFiles>().0.lock() {
+        pending.extend(files);
+    }
+
+    let Some(window) = app.get_webview_window("main") else {
+        log::warn!("[OpenFiles] 主窗口不存在，文件将在前端就绪后加入队列");
+        return;
+    };
+    let _ = window.unminimize();
+    let _ = window.show();
+    let _ = window.set_focus();
+    let _ = window.emit("navigate-to", "upload");
+    let _ = window.emit("open-files", ());
+}
+
+/// 取走全部待上传文件
+#[tauri::command]
+pub fn take_pending_open_files(pending: tauri::State<'_, PendingOpenFiles>) -> Vec<String> {
+    pending
+        .0
+        .lock()
+        .map(|mut files| std::mem::take(&mut *files))
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn resolve_open_files_joins_cwd_and_filters_non_images() {
+        let dir = std::env::temp_dir().join(format!("picnexus-open-files-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        std::fs::write(dir.join("a.png"), b"fake").unwrap();
+        std::fs::write(dir.join("notes.txt"), b"text").unwrap();
+
+        let resolved = resolve_open_files(
+            &[
+                "a.png".to_string(),
+                "notes.txt".to_string(),
+                "missing.jpg".to_string(),
+            ],
+            &dir,
+        );
+
+        assert_eq!(
+            resolved,
+            vec![dir.join("a.png").to_string_lossy().to_string()]
+        );
+        let _ = std::fs::remove_dir_all(dir);
+    }
+
+    #[test]
+    fn find_open_files_ignores_cli_upload_and_quick_actions() {
+        let cwd = std::env::temp_dir();
+        let args = |values: &[&str]| -> Vec<String> {
+            values.iter().map(|value| value.to_string()).collect()
+        };
+
+        assert!(find_open_files(&args(&["picnexus"]), &cwd).is_empty());
+        assert!(find_open_files(&args(&["picnexus", "--service", "r2", "a.png"]), &cwd).is_empty());
+        assert!(
+            find_open_files(&args(&["picnexus", "--quick-action", "open_history"]), &cwd)
+                .is_empty()
+        );
+    }
+}
@@ -0,0 +1,186 @@
+// src-tauri/src/path_utils.rs
+// 本地文件路径的规范化：前端传入的路径、canonicalize 的结果统一成同一种写法
+//
+// Windows 上 std::fs::canonicalize 返回 `\\?\C:\...` 形式的 verbatim 路径。这种路径不做任何
+// 解析：`/` 不会被当成分隔符，前端拼接出的 `\\?\C:\图片/a.png` 直接「文件不存在」；
+// 和用户输入的 `C:\...` 比较也永远不相等，插件 fs 的作用域校验同样不认。
+// 长路径（超过 260 字符，OneDrive 深层目录很常见）不需要手动加前缀：
+// 标准库的文件 API 会按需自动补上 `\\?\`，所以这里统一去掉前缀，只保留普通写法。
+//
+// 路径始终按 Path / OsStr 传递，中文、emoji 文件名不做任何字节级截断或编码转换。
+
+use std::io;
+use std::path::{Path, PathBuf};
+
+/// Win32 保留设备名：去掉 verbatim 前缀后会被解析成设备，带这些名字的路径保留前缀
+const RESERVED_NAMES: &[&str] = &[
+    "CON", "PRN", "AUX", "NUL", "COM1", "COM2", "COM3", "COM4", "COM5", "COM6", "COM7", "COM8",
+    "COM9", "LPT1", "LPT2", "LPT3", "LPT4", "LPT5", "LPT6", "LPT7", "LPT8", "LPT9",
+];
+
+/// 去掉 verbatim 前缀后 Win32 解析结果不变的路径段
+///
+/// 以 `.` / 空格结尾的段会被 Win32 静默截掉，`.` / `..` 会被折叠，保留设备名会变成设备
+fn is_plain_component(component: &str) -> bool {
+    if component.is_empty() || component.ends_with('.') || component.ends_with(' ') {
+        return false;
+    }
+    let stem = component.split('.').next().unwrap_or(component);
+    !RESERVED_NAMES
+        .iter()
+        .any(|name| stem.eq_ignore_ascii_case(name))
+}
+
+/// 把 `\\?\C:\...` / `\\?\UNC\server\share\...` 还原为普通写法
+///
+/// `\\?\Volume{...}` 之类没有普通写法的路径、以及去掉前缀会改变含义的路径返回 None
+fn strip_verbatim_prefix(path: &str) -> Option<String> {
+    let rest = path.strip_prefix(r"\\?\")?;
+    let (plain, body) = if let Some(unc) = rest.strip_prefix(r"UNC\") {
+        (format!(r"\\{}", unc), unc)
+    } else if is_drive_root(rest) {
+        (rest.to_string(), &rest[3..])
+    } else {
+        return None;
+    };
+    (body.is_empty() || body.split('\\').all(is_plain_component)).then_some(plain)
+}
+
+/// 是否以 `C:\` 形式的盘符开头
+fn is_drive_root(path: &str) -> bool {
+    let bytes = path.as_bytes();
+    bytes.len() >= 3 && bytes[0].is_ascii_alphabetic() && bytes[1] == b':' && bytes[2] == b'\\'
+}
+
+/// Windows 路径写法规范化（纯字符串处理，便于在所有平台上测试）
+///
+/// - `/` 统一为 `\`（文件名里不可能出现 `/`，verbatim 路径中的 `/` 只会是拼接错误）
+/// - 能安全去掉的 `\\?\` 前缀去掉
+fn normalize_windows_str(path: &str) -> String {
+    let unified = if path.starts_with(r"\\?\") || path.starts_with(r"\\.\") {
+        // 设备前缀本身不能改写，只改写其后的部分
+        let (head, tail) = path.split_at(4);
+        format!("{}{}", head, tail.replace('/', "\\"))
+    } else {
+        path.replace('/', "\\")
+    };
+    strip_verbatim_prefix(&unified).unwrap_or(unified)
+}
+
+/// 解析前端 / 命令行传入的本地路径
+///
+/// - 去掉首尾空白与成对引号（资源管理器「复制文件地址」会带双引号）
+/// - Windows 上统一分隔符并去掉多余的 `\\?\` 前缀
+pub fn normalize_input(raw: &str) -> PathBuf {
+    let mut text = raw.trim();
+    for quote in ['"', '\''] {
+        if let Some(inner) = text
+            .strip_prefix(quote)
+            .and_then(|rest| rest.strip_suffix(quote))
+        {
+            text = inner.trim();
+        }
+    }
+    if cfg!(windows) {
+        PathBuf::from(normalize_windows_str(text))
+    } else {
+        PathBuf::from(text)
+    }
+}
+
+/// 去掉 canonicalize 结果中的 verbatim 前缀（非 Windows 平台原样返回）
+pub fn simplify(path: PathBuf) -> PathBuf {
+    if !cfg!(windows) {
+        return path;
+    }
+    match path.to_str().and_then(strip_verbatim_prefix) {
+        Some(plain) => PathBuf::from(plain),
+        None => path,
+    }
+}
+
+/// canonicalize 并还原为普通写法，结果可以直接返回前端或与用户输入比较
+pub fn canonicalize(path: impl AsRef<Path>) -> io::Result<PathBuf> {
+    std::fs::canonicalize(path).map(simplify)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn strips_verbatim_drive_and_unc_prefixes() {
+        assert_eq!(
+            strip_verbatim_prefix(r"\\?\C:\Users\张三\OneDrive\图片\😀.png").as_deref(),
+            Some(r"C:\Users\张三\OneDrive\图片\😀.png")
+        );
+        assert_eq!(
+            strip_verbatim_prefix(r"\\?\UNC\nas\share\相册\a.jpg").as_deref(),
+            Some(r"\\nas\share\相册\a.jpg")
+        );
+        assert_eq!(strip_verbatim_prefix(r"\\?\D:\").as_deref(), Some(r"D:\"));
+    }
+
+    #[test]
+    fn keeps_prefix_when_stripping_changes_meaning() {
+        assert_eq!(strip_verbatim_prefix(r"\\?\C:\dir\con.png"), None);
+        assert_eq!(strip_verbatim_prefix(r"\\?\C:\dir\trailing. "), None);
+        assert_eq!(strip_verbatim_prefix(r"\\?\C:\a\..\b.png"), None);
+        assert_eq!(
+            strip_verbatim_prefix(r"\\?\Volume{01234567-89ab}\a.png"),
+            None
+        );
+        assert_eq!(strip_verbatim_prefix(r"C:\plain\a.png"), None);
+    }
+
+    #[test]
+    fn normalizes_windows_separators_and_long_paths() {
+        let deep = format!(
+            r"C:\Users\李四\OneDrive\{}照片.jpg",
+            "很深的目录\\".repeat(40)
+        );
+        assert!(deep.len() > 260);
+        assert_eq!(
+            normalize_windows_str(&format!(r"\\?\{}", deep.replace('\\', "/"))),
+            deep
+        );
+        assert_eq!(
+            normalize_windows_str("C:/图片/截图 (1).png"),
+            r"C:\图片\截图 (1).png"
+        );
+        assert_eq!(
+            normalize_windows_str(r"\\.\PhysicalDrive0"),
+            r"\\.\PhysicalDrive0"
+        );
+    }
+
+    #[test]
+    fn normalize_input_trims_quotes_and_whitespace() {
+        let expected = if cfg!(windows) {
+            PathBuf::from(r"C:\相册\a.png")
+        } else {
+            PathBuf::from("/相册/a.png")
+        };
+        let raw = if cfg!(windows) {
+            r#"  "C:\相册\a.png"  "#
+        } else {
+            r#"  "/相册/a.png"  "#
+        };
+        assert_eq!(normalize_input(raw), expected);
+    }
+
+    #[test]
+    fn canonicalize_handles_non_ascii_names() {
+        let dir = std::env::temp_dir().join(format!("picnexus_路径_{}", std::process::id()));
+        let file = dir.join("截图 😀.png");
+        std::fs::create_dir_all(&dir).unwrap();
+        std::fs::write(&file, b"x").unwrap();
+
+        let canonical = canonicalize(&file);
+        let _ = std::fs::remove_dir_all(&dir);
+
+        let canonical = canonical.unwrap();
+        assert!(!canonical.to_string_lossy().starts_with(r"\\?\"));
+        assert_eq!(canonical.file_name().unwrap(), "截图 😀.png");
+    }
+}
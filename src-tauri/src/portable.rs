@@ -51,6 +51,30 @@ pub fn log_dir(app: &tauri::AppHandle) -> Result<PathBuf, AppError> {
         .map_err(|e| AppError::file_io(format!("无法获取日志目录: {}", e)))
 }
 
+/// 可随时删除的缓存目录（缩略图等）
+pub fn cache_dir(app: &tauri::AppHandle) -> Result<PathBuf, AppError> {
+    if let Some(dir) = portable_data_dir() {
+        return Ok(dir.join("cache"));
+    }
+
+    app.path()
+        .app_cache_dir()
+        .map_err(|e| AppError::file_io(format!("无法获取缓存目录: {}", e)))
+}
+
+/// 历史记录数据库的实际文件路径
+/// 与 tauri-plugin-sql 对 history_db_url 的解析一致：相对路径挂在 app_config_dir 下
+pub fn history_db_path(app: &tauri::AppHandle) -> Result<PathBuf, AppError> {
+    if let Some(dir) = portable_data_dir() {
+        return Ok(dir.join("history.db"));
+    }
+
+    app.path()
+        .app_config_dir()
+        .map(|dir| dir.join("history.db"))
+        .map_err(|e| AppError::file_io(format!("无法获取应用配置目录: {}", e)))
+}
+
 pub fn history_db_url() -> String {
     match portable_data_dir() {
         Some(dir) => format!("sqlite:{}", dir.join("history.db").display()),
@@ -0,0 +1,85 @@
+// src-tauri/src/power_events.rs
+// 系统休眠 / 唤醒检测：唤醒后通知前端，错开定时检测与重试
+//
+// 各平台的电源通知接口差异很大（Windows 要挂窗口消息 WM_POWERBROADCAST，Linux 要订阅
+// logind 的 PrepareForSleep，macOS 要走 IOKit），而且 WebView 内的定时器在休眠期间是否计时
+// 也因平台而异。这里统一用「墙上时间跳变」判断：后台线程按固定间隔醒来，
+// 实际流逝的系统时间远大于睡眠间隔，说明中间整机挂起过。
+//
+// 检测到唤醒后向前端发出 system-resumed 事件（载荷为休眠时长），由前端调度器错开执行过期任务。
+
+use std::time::{Duration, SystemTime};
+
+use serde::Serialize;
+use tauri::{AppHandle, Emitter};
+
+/// 唤醒事件名
+pub const SYSTEM_RESUMED_EVENT: &str = "system-resumed";
+
+/// 检测线程的醒来间隔
+const TICK: Duration = Duration::from_secs(10);
+
+/// 超出醒来间隔多少才算休眠（线程调度延迟、高负载时的短暂卡顿不算）
+const SLEEP_THRESHOLD: Duration = Duration::from_secs(30);
+
+/// system-resumed 事件载荷
+#[derive(Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct SystemResumed {
+    /// 估算的休眠时长（毫秒）
+    slept_ms: u64,
+}
+
+/// 根据一次睡眠间隔内实际流逝的墙上时间判断是否休眠过，返回估算的休眠时长
+///
+/// 系统时间被往回调时 `elapsed` 为 None，不算休眠
+fn detect_sleep(expected: Duration, elapsed: Option<Duration>) -> Option<Duration> {
+    let elapsed = elapsed?;
+    if elapsed < expected + SLEEP_THRESHOLD {
+        return None;
+    }
+    Some(elapsed - expected)
+}
+
+/// 启动休眠检测后台线程
+pub fn start(app: AppHandle) {
+    std::thread::spawn(move || loop {
+        let before = SystemTime::now();
+        std::thread::sleep(TICK);
+        let elapsed = SystemTime::now().duration_since(before).ok();
+
+        if let Some(slept) = detect_sleep(TICK, elapsed) {
+            log::info!("[电源] 检测到系统唤醒，休眠约 {} 秒", slept.as_secs());
+            let payload = SystemResumed {
+                slept_ms: slept.as_millis() as u64,
+            };
+            if let Err(e) = app.emit(SYSTEM_RESUMED_EVENT, payload) {
+                log::warn!("[电源] 发送唤醒事件失败: {}", e);
+            }
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn detect_sleep_ignores_scheduling_jitter_and_clock_rollback() {
+        assert_eq!(detect_sleep(TICK, Some(TICK)), None);
+        assert_eq!(
+            detect_sleep(TICK, Some(TICK + Duration::from_secs(29))),
+            None
+        );
+        assert_eq!(detect_sleep(TICK, None), None);
+    }
+
+    #[test]
+    fn detect_sleep_reports_time_spent_asleep() {
+        let elapsed = TICK + Duration::from_secs(3600);
+        assert_eq!(
+            detect_sleep(TICK, Some(elapsed)),
+            Some(Duration::from_secs(3600))
+        );
+    }
+}
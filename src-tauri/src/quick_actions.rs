@@ -0,0 +1,229 @@
+// src-tauri/src/quick_actions.rs
+// 平台快捷操作：Windows 任务栏跳转列表 / macOS 菜单栏
+//
+// 工作流：
+//   跳转列表项 -> picnexus.exe --quick-action upload_clipboard
+//   -> 已有实例：single-instance 回调收到参数 -> dispatch_quick_action
+//   -> 冷启动：暂存为 PendingQuickAction，前端挂载后通过 take_pending_quick_action 取走
+//
+// 分发逻辑与托盘菜单（src/services/trayMenu.ts）保持一致：
+// 显示主窗口后发送 navigate-to / tray-action 事件，由前端同一套处理器执行。
+
+use std::sync::Mutex;
+
+use tauri::{Emitter, Manager};
+
+/// 命令行参数名（跳转列表项通过该参数启动或转发给已运行实例）
+pub const QUICK_ACTION_ARG: &str = "--quick-action";
+
+/// macOS 菜单项 id 前缀，用于在 on_menu_event 中区分快捷操作
+#[cfg(target_os = "macos")]
+pub const MENU_ID_PREFIX: &str = "quick_action:";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum QuickAction {
+    UploadClipboard,
+    OpenHistory,
+    PauseQueue,
+}
+
+impl QuickAction {
+    pub const ALL: [QuickAction; 3] = [
+        QuickAction::UploadClipboard,
+        QuickAction::OpenHistory,
+        QuickAction::PauseQueue,
+    ];
+
+    /// 与前端 tray-action 事件 payload 一致的标识
+    pub fn id(self) -> &'static str {
+        match self {
+            QuickAction::UploadClipboard => "upload_clipboard",
+            QuickAction::OpenHistory => "open_history",
+            QuickAction::PauseQueue => "pause_queue",
+        }
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            QuickAction::UploadClipboard => "上传剪贴板",
+            QuickAction::OpenHistory => "打开历史",
+            QuickAction::PauseQueue => "暂停队列",
+        }
+    }
+
+    pub fn from_id(id: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|action| action.id() == id)
+    }
+}
+
+/// 冷启动时携带的快捷操作（前端监听器就绪前无法接收事件，先暂存）
+pub struct PendingQuickAction(pub Mutex<Option<QuickAction>>);
+
+/// 从完整参数列表中查找 `--quick-action <id>`（single-instance 回调的 args 含可执行文件路径）
+pub fn find_quick_action(args: &[String]) -> Option<QuickAction> {
+    args.iter()
+        .position(|arg| arg == QUICK_ACTION_ARG)
+        .and_then(|idx| args.get(idx + 1))
+        .and_then(|id| QuickAction::from_id(id))
+}
+
+/// 执行快捷操作：与托盘菜单动作保持相同的事件语义
+pub fn dispatch_quick_action(app: &tauri::AppHandle, action: QuickAction) {
+    log::info!("[QuickAction] 执行快捷操作: {}", action.id());
+
+    let Some(window) = app.get_webview_window("main") else {
+        log::warn!("[QuickAction] 主窗口不存在，忽略快捷操作: {}", action.id());
+        return;
+    };
+    let _ = window.unminimize();
+    let _ = window.show();
+    let _ = window.set_focus();
+
+    match action {
+        QuickAction::UploadClipboard => {
+            let _ = window.emit("navigate-to", "upload");
+            let _ = window.emit("tray-action", action.id());
+        }
+        QuickAction::OpenHistory => {
+            let _ = window.emit("navigate-to", "history");
+        }
+        QuickAction::PauseQueue => {
+            // 上传队列在前端调度，由前端暂停，保证暂停状态与队列面板同步
+            let _ = window.emit("tray-action", action.id());
+        }
+    }
+}
+
+/// 取走冷启动时暂存的快捷操作（只返回一次）
+#[tauri::command]
+pub fn take_pending_quick_action(
+    pending: tauri::State<'_, PendingQuickAction>,
+) -> Option<&'static str> {
+    pending
+        .0
+        .lock()
+        .ok()
+        .and_then(|mut guard| guard.take())
+        .map(QuickAction::id)
+}
+
+/// 注册 Windows 任务栏跳转列表的「任务」分组
+///
+/// 每一项都是指向当前可执行文件的 ShellLink，参数为 `--quick-action <id>`；
+/// 应用已运行时由 single-instance 插件把参数转发给主实例。
+#[cfg(windows)]
+pub fn register_jump_list() -> Result<(), String> {
+    let exe = std::env::current_exe().map_err(|e| format!("无法获取可执行文件路径: {}", e))?;
+    unsafe { windows_jump_list::commit(&exe) }.map_err(|e| format!("注册跳转列表失败: {}", e))
+}
+
+#[cfg(windows)]
+mod windows_jump_list {
+    use super::{QuickAction, QUICK_ACTION_ARG};
+    use windows::core::{Interface, HSTRING, PWSTR};
+    use windows::Win32::Foundation::E_OUTOFMEMORY;
+    use windows::Win32::Storage::EnhancedStorage::PKEY_Title;
+    use windows::Win32::System::Com::StructuredStorage::PROPVARIANT;
+    use windows::Win32::System::Com::{
+        CoCreateInstance, CoInitializeEx, CoTaskMemAlloc, CLSCTX_INPROC_SERVER,
+        COINIT_APARTMENTTHREADED,
+    };
+    use windows::Win32::System::Variant::VT_LPWSTR;
+    use windows::Win32::UI::Shell::Common::{IObjectArray, IObjectCollection};
+    use windows::Win32::UI::Shell::PropertiesSystem::IPropertyStore;
+    use windows::Win32::UI::Shell::{
+        DestinationList, EnumerableObjectCollection, ICustomDestinationList, IShellLinkW, ShellLink,
+    };
+
+    /// 跳转列表标题必须是 VT_LPWSTR，PROPVARIANT::from(&str) 生成的是 VT_BSTR
+    unsafe fn lpwstr_variant(text: &str) -> windows::core::Result<PROPVARIANT> {
+        let wide: Vec<u16> = text.encode_utf16().chain(std::iter::once(0)).collect();
+        let buf = CoTaskMemAlloc(wide.len() * std::mem::size_of::<u16>()) as *mut u16;
+        if buf.is_null() {
+            return Err(E_OUTOFMEMORY.into());
+        }
+        std::ptr::copy_nonoverlapping(wide.as_ptr(), buf, wide.len());
+
+        // 内存由 PROPVARIANT 的 Drop（PropVariantClear）通过 CoTaskMemFree 释放
+        let mut value = PROPVARIANT::default();
+        let inner = &mut *value.Anonymous.Anonymous;
+        inner.vt = VT_LPWSTR;
+        inner.Anonymous.pwszVal = PWSTR(buf);
+        Ok(value)
+    }
+
+    pub(super) unsafe fn commit(exe: &std::path::Path) -> windows::core::Result<()> {
+        // setup 运行在主线程，WebView2 已初始化 STA；重复初始化返回 S_FALSE，可忽略
+        let _ = CoInitializeEx(None, COINIT_APARTMENTTHREADED);
+
+        let exe = HSTRING::from(exe.as_os_str());
+        let list: ICustomDestinationList =
+            CoCreateInstance(&DestinationList, None, CLSCTX_INPROC_SERVER)?;
+        let mut max_slots = 0u32;
+        let _removed: IObjectArray = list.BeginList(&mut max_slots)?;
+
+        let tasks: IObjectCollection =
+            CoCreateInstance(&EnumerableObjectCollection, None, CLSCTX_INPROC_SERVER)?;
+        for action in QuickAction::ALL {
+            let link: IShellLinkW = CoCreateInstance(&ShellLink, None, CLSCTX_INPROC_SERVER)?;
+            link.SetPath(&exe)?;
+            link.SetArguments(&HSTRING::from(format!(
+                "{} {}",
+                QUICK_ACTION_ARG,
+                action.id()
+            )))?;
+            link.SetIconLocation(&exe, 0)?;
+            link.SetDescription(&HSTRING::from(action.label()))?;
+
+            let store: IPropertyStore = link.cast()?;
+            store.SetValue(&PKEY_Title, &lpwstr_variant(action.label())?)?;
+            store.Commit()?;
+
+            tasks.AddObject(&link)?;
+        }
+
+        let tasks: IObjectArray = tasks.cast()?;
+        list.AddUserTasks(&tasks)?;
+        list.CommitList()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(values: &[&str]) -> Vec<String> {
+        values.iter().map(|value| value.to_string()).collect()
+    }
+
+    #[test]
+    fn action_ids_round_trip() {
+        for action in QuickAction::ALL {
+            assert_eq!(QuickAction::from_id(action.id()), Some(action));
+        }
+        assert_eq!(QuickAction::from_id("quit"), None);
+    }
+
+    #[test]
+    fn find_quick_action_skips_executable_path() {
+        let parsed = find_quick_action(&args(&[
+            "C:\\Program Files\\PicNexus\\picnexus.exe",
+            "--quick-action",
+            "pause_queue",
+        ]));
+        assert_eq!(parsed, Some(QuickAction::PauseQueue));
+    }
+
+    #[test]
+    fn find_quick_action_ignores_missing_or_unknown_value() {
+        assert_eq!(
+            find_quick_action(&args(&["picnexus", "--quick-action"])),
+            None
+        );
+        assert_eq!(
+            find_quick_action(&args(&["picnexus", "--quick-action", "rm"])),
+            None
+        );
+        assert_eq!(find_quick_action(&args(&["picnexus", "a.png"])), None);
+    }
+}
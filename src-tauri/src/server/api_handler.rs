@@ -0,0 +1,308 @@
+// src-tauri/src/server/api_handler.rs
+// 本地 HTTP API：历史记录查询与链接检测
+//
+// 与 /upload 不同，这两个端点会读取本地数据或代为发起外部请求，
+// 因此无论请求是否来自浏览器都必须携带 token（X-PicNexus-Token / Bearer / ?token=）。
+
+use super::upload_handler::request_has_valid_server_token;
+use super::ServerRuntimeState;
+use crate::commands::link_checker::{check_single_link, safe_no_redirect_client, CheckLinkResult};
+use crate::url_utils;
+use axum::body::Body;
+use axum::extract::{Query, State};
+use axum::http::{Request, StatusCode};
+use axum::middleware::Next;
+use axum::response::{IntoResponse, Response};
+use axum::Json;
+use futures::stream::{self, StreamExt};
+use serde::{Deserialize, Serialize};
+use sqlx::sqlite::SqliteConnectOptions;
+use sqlx::{ConnectOptions, Row};
+
+const DEFAULT_HISTORY_PAGE_SIZE: u32 = 50;
+const MAX_HISTORY_PAGE_SIZE: u32 = 200;
+
+/// 单次 /check 最多检测的链接数（批量检测请走 GUI 的 batch_check_links）
+const MAX_API_CHECK_LINKS: usize = 100;
+const API_CHECK_CONCURRENCY: usize = 8;
+const API_CHECK_TIMEOUT_SECS: u64 = 10;
+
+#[derive(Serialize)]
+struct ApiErrorResponse {
+    success: bool,
+    message: String,
+}
+
+fn api_error(status: StatusCode, message: impl Into<String>) -> Response {
+    (
+        status,
+        Json(ApiErrorResponse {
+            success: false,
+            message: message.into(),
+        }),
+    )
+        .into_response()
+}
+
+/// 强制 token 校验；未配置 token 时一律拒绝
+pub async fn require_api_token(
+    State(state): State<ServerRuntimeState>,
+    request: Request<Body>,
+    next: Next,
+) -> Response {
+    let authorized = {
+        let token_guard = state.auth_token.lock().await;
+        token_guard.as_deref().is_some_and(|expected| {
+            request_has_valid_server_token(request.headers(), request.uri().query(), expected)
+        })
+    };
+
+    if !authorized {
+        log::warn!(
+            "[Server] 拒绝未携带有效 token 的 API 请求: {}",
+            request.uri().path()
+        );
+        return api_error(
+            StatusCode::UNAUTHORIZED,
+            "Missing or invalid PicNexus server token",
+        );
+    }
+
+    next.run(request).await
+}
+
+// ==================== GET /history ====================
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct HistoryQuery {
+    pub limit: Option<u32>,
+    pub offset: Option<u32>,
+    /// 按主图床过滤（如 r2、weibo）
+    pub service: Option<String>,
+    /// 按文件名模糊搜索（不区分大小写）
+    pub keyword: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct HistoryItem {
+    pub id: String,
+    pub timestamp: i64,
+    pub file_name: String,
+    pub primary_service: String,
+    pub link: String,
+    pub width: i64,
+    pub height: i64,
+    pub file_size: i64,
+    pub format: String,
+    pub is_favorited: bool,
+}
+
+#[derive(Debug, Serialize)]
+pub struct HistoryResponse {
+    pub success: bool,
+    pub total: i64,
+    pub items: Vec<HistoryItem>,
+}
+
+/// LIKE 模式转义：用户输入中的 % _ \ 按字面量匹配
+fn escape_like(keyword: &str) -> String {
+    let mut escaped = String::with_capacity(keyword.len() + 2);
+    escaped.push('%');
+    for c in keyword.to_lowercase().chars() {
+        if matches!(c, '%' | '_' | '\\') {
+            escaped.push('\\');
+        }
+        escaped.push(c);
+    }
+    escaped.push('%');
+    escaped
+}
+
+pub(crate) async fn query_history(
+    db_path: &std::path::Path,
+    query: &HistoryQuery,
+) -> Result<HistoryResponse, String> {
+    // 尚未产生任何上传时数据库不存在，视为空历史
+    if !db_path.exists() {
+        return Ok(HistoryResponse {
+            success: true,
+            total: 0,
+            items: Vec::new(),
+        });
+    }
+
+    // 只读连接：写入始终由前端 tauri-plugin-sql 负责
+    let mut conn = SqliteConnectOptions::new()
+        .filename(db_path)
+        .read_only(true)
+        .connect()
+        .await
+        .map_err(|e| format!("打开历史记录数据库失败: {}", e))?;
+
+    let limit = query
+        .limit
+        .unwrap_or(DEFAULT_HISTORY_PAGE_SIZE)
+        .clamp(1, MAX_HISTORY_PAGE_SIZE);
+    let offset = query.offset.unwrap_or(0);
+    let service = query
+        .service
+        .as_deref()
+        .map(str::trim)
+        .filter(|s| !s.is_empty());
+    let keyword = query
+        .keyword
+        .as_deref()
+        .map(str::trim)
+        .filter(|s| !s.is_empty())
+        .map(escape_like);
+
+    const FILTER: &str = "WHERE (?1 IS NULL OR primary_service = ?1) \
+         AND (?2 IS NULL OR local_file_name_lower LIKE ?2 ESCAPE '\\')";
+
+    let total: i64 = sqlx::query_scalar(&format!("SELECT COUNT(*) FROM history_items {}", FILTER))
+        .bind(service)
+        .bind(keyword.as_deref())
+        .fetch_one(&mut conn)
+        .await
+        .map_err(|e| format!("查询历史记录失败: {}", e))?;
+
+    let rows = sqlx::query(&format!(
+        "SELECT id, timestamp, local_file_name, primary_service, generated_link, \
+         width, height, file_size, format, is_favorited \
+         FROM history_items {} ORDER BY timestamp DESC LIMIT ?3 OFFSET ?4",
+        FILTER
+    ))
+    .bind(service)
+    .bind(keyword.as_deref())
+    .bind(limit)
+    .bind(offset)
+    .fetch_all(&mut conn)
+    .await
+    .map_err(|e| format!("查询历史记录失败: {}", e))?;
+
+    let items = rows
+        .iter()
+        .map(|row| {
+            Ok(HistoryItem {
+                id: row.try_get("id")?,
+                timestamp: row.try_get("timestamp")?,
+                file_name: row.try_get("local_file_name")?,
+                primary_service: row.try_get("primary_service")?,
+                link: row.try_get("generated_link")?,
+                width: row.try_get("width")?,
+                height: row.try_get("height")?,
+                file_size: row.try_get("file_size")?,
+                format: row.try_get("format")?,
+                is_favorited: row.try_get::<i64, _>("is_favorited")? != 0,
+            })
+        })
+        .collect::<Result<Vec<_>, sqlx::Error>>()
+        .map_err(|e| format!("解析历史记录失败: {}", e))?;
+
+    Ok(HistoryResponse {
+        success: true,
+        total,
+        items,
+    })
+}
+
+/// GET /history?limit=&offset=&service=&keyword=
+pub async fn handle_history(
+    State(state): State<ServerRuntimeState>,
+    Query(query): Query<HistoryQuery>,
+) -> Response {
+    let Some(db_path) = state.history_db_path.as_deref() else {
+        return api_error(StatusCode::SERVICE_UNAVAILABLE, "历史记录数据库不可用");
+    };
+
+    match query_history(db_path, &query).await {
+        Ok(response) => Json(response).into_response(),
+        Err(e) => {
+            log::warn!("[Server] /history 查询失败: {}", e);
+            api_error(StatusCode::INTERNAL_SERVER_ERROR, e)
+        }
+    }
+}
+
+// ==================== POST /check ====================
+
+#[derive(Debug, Deserialize)]
+pub struct CheckRequest {
+    pub urls: Vec<String>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct CheckResponse {
+    pub success: bool,
+    pub results: Vec<CheckLinkResult>,
+}
+
+/// POST /check {"urls": ["https://..."]}
+/// 结果顺序与请求一致；单条失败不影响整体 success
+pub async fn handle_check(
+    State(state): State<ServerRuntimeState>,
+    Json(req): Json<CheckRequest>,
+) -> Response {
+    if req.urls.is_empty() {
+        return api_error(StatusCode::BAD_REQUEST, "urls 不能为空");
+    }
+    if req.urls.len() > MAX_API_CHECK_LINKS {
+        return api_error(
+            StatusCode::BAD_REQUEST,
+            format!("单次最多检测 {} 条链接", MAX_API_CHECK_LINKS),
+        );
+    }
+
+    let client = match safe_no_redirect_client() {
+        Ok(client) => client,
+        Err(e) => return api_error(StatusCode::INTERNAL_SERVER_ERROR, e.to_string()),
+    };
+
+    let limiter = &state.limiter;
+    let results = stream::iter(req.urls)
+        .map(|url| {
+            let client = client.clone();
+            async move {
+                // 与 GUI 的检测 / 上传共用单主机与全局并发上限
+                let _permit = limiter.acquire(&url_utils::host_key(&url)).await;
+                check_single_link(&url, &client, API_CHECK_TIMEOUT_SECS).await
+            }
+        })
+        .buffered(API_CHECK_CONCURRENCY)
+        .collect::<Vec<_>>()
+        .await;
+
+    Json(CheckResponse {
+        success: true,
+        results,
+    })
+    .into_response()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn escape_like_wraps_and_escapes_wildcards() {
+        assert_eq!(escape_like("Shot"), "%shot%");
+        assert_eq!(escape_like("100%_a\\b"), "%100\\%\\_a\\\\b%");
+    }
+
+    #[tokio::test]
+    async fn query_history_returns_empty_when_database_missing() {
+        let missing = std::env::temp_dir().join(format!(
+            "picnexus-missing-history-{}.db",
+            std::process::id()
+        ));
+        let response = query_history(&missing, &HistoryQuery::default())
+            .await
+            .expect("missing database should not be an error");
+
+        assert!(response.success);
+        assert_eq!(response.total, 0);
+        assert!(response.items.is_empty());
+    }
+}
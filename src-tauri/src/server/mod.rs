@@ -1,8 +1,13 @@
 // src-tauri/src/server/mod.rs
 // PicGo 兼容 HTTP Server
 // 监听 127.0.0.1:{port}，提供 POST /upload 接口
-// 兼容 Typora、Obsidian 等编辑器的图片上传
+// 兼容 Typora、Obsidian 等编辑器的图片上传（请求/响应格式与 picgo-server 一致，
+// 包括空 body 上传剪贴板与 POST /heartbeat）
+// 另提供需 token 的本地 API：GET /history、POST /check
+// 以及公开的分享页 GET /share/:id（见 share_handler）
 
+pub mod api_handler;
+pub mod share_handler;
 pub mod upload_handler;
 
 use axum::{
@@ -13,17 +18,51 @@ use axum::{
     Router,
 };
 use std::net::SocketAddr;
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicU16, Ordering};
 use std::sync::Arc;
 use tokio::net::TcpListener;
 use tokio::sync::Mutex;
 use tower_http::cors::{Any, CorsLayer};
 
+use crate::commands::clipboard::SharedClipboard;
+use crate::commands::net_limiter::NetworkLimiter;
+
 pub use upload_handler::ServerUploadConfig;
 
 #[derive(Clone)]
 pub struct ServerRuntimeState {
     pub upload_config: Arc<Mutex<Option<ServerUploadConfig>>>,
     pub auth_token: Arc<Mutex<Option<String>>>,
+    /// 历史记录数据库路径（/history 只读查询）
+    pub history_db_path: Option<PathBuf>,
+    /// 与前端命令共用的剪贴板句柄（空 body 上传剪贴板）
+    pub clipboard: SharedClipboard,
+    /// 本地分享页目录（/share/:id）；None 时分享页不可用
+    pub share_dir: Option<PathBuf>,
+    /// 与前端命令共用的出站并发闸门（/upload 与 /check 的请求同样计入上限）
+    pub limiter: NetworkLimiter,
+}
+
+/// 正在运行的 Server 端口；0 表示未运行
+static RUNNING_PORT: AtomicU16 = AtomicU16::new(0);
+
+/// Server 正在监听的端口（未启动时为 None），用于生成本地分享链接
+pub fn running_port() -> Option<u16> {
+    match RUNNING_PORT.load(Ordering::SeqCst) {
+        0 => None,
+        port => Some(port),
+    }
+}
+
+/// Server 任务结束（包括被 abort）时清除端口记录
+struct RunningPortGuard(u16);
+
+impl Drop for RunningPortGuard {
+    fn drop(&mut self) {
+        // 新 Server 可能已经在其他端口启动，只清除自己的记录
+        let _ = RUNNING_PORT.compare_exchange(self.0, 0, Ordering::SeqCst, Ordering::SeqCst);
+    }
 }
 
 fn build_router(state: ServerRuntimeState) -> Router {
@@ -38,7 +77,12 @@ fn build_router(state: ServerRuntimeState) -> Router {
         ]);
 
     let protected_routes = Router::new()
-        .route("/upload", post(upload_handler::handle_upload))
+        .route(
+            "/upload",
+            post(upload_handler::handle_upload).layer(DefaultBodyLimit::max(
+                upload_handler::MAX_SERVER_UPLOAD_SIZE,
+            )),
+        )
         .route(
             "/upload/file",
             post(upload_handler::handle_file_upload).layer(DefaultBodyLimit::max(
@@ -49,11 +93,23 @@ fn build_router(state: ServerRuntimeState) -> Router {
             state.clone(),
             upload_handler::require_upload_auth,
         ))
+        .layer(cors.clone());
+
+    let api_routes = Router::new()
+        .route("/history", get(api_handler::handle_history))
+        .route("/check", post(api_handler::handle_check))
+        .route_layer(middleware::from_fn_with_state(
+            state.clone(),
+            api_handler::require_api_token,
+        ))
         .layer(cors);
 
     Router::new()
         .route("/status", get(upload_handler::handle_status))
+        .route("/heartbeat", post(upload_handler::handle_heartbeat))
+        .route("/share/:id", get(share_handler::handle_share_page))
         .merge(protected_routes)
+        .merge(api_routes)
         .with_state(state)
 }
 
@@ -71,13 +127,25 @@ pub async fn run_server(
     listener: TcpListener,
     upload_config: Arc<Mutex<Option<ServerUploadConfig>>>,
     auth_token: Arc<Mutex<Option<String>>>,
+    history_db_path: Option<PathBuf>,
+    clipboard: SharedClipboard,
+    share_dir: Option<PathBuf>,
+    limiter: NetworkLimiter,
 ) -> Result<(), String> {
     let state = ServerRuntimeState {
         upload_config,
         auth_token,
+        history_db_path,
+        clipboard,
+        share_dir,
+        limiter,
     };
     let app = build_router(state);
 
+    let port = listener.local_addr().map(|addr| addr.port()).unwrap_or(0);
+    RUNNING_PORT.store(port, Ordering::SeqCst);
+    let _port_guard = RunningPortGuard(port);
+
     log::info!("[Server] ✓ 编辑器兼容 Server 已启动");
 
     axum::serve(listener, app)
@@ -106,6 +174,10 @@ mod tests {
         ServerRuntimeState {
             upload_config: Arc::new(Mutex::new(None)),
             auth_token: Arc::new(Mutex::new(Some("test-token".to_string()))),
+            history_db_path: None,
+            clipboard: SharedClipboard::default(),
+            share_dir: None,
+            limiter: NetworkLimiter::default(),
         }
     }
 
@@ -150,4 +222,107 @@ mod tests {
             .get(ACCESS_CONTROL_ALLOW_ORIGIN)
             .is_some());
     }
+
+    #[tokio::test]
+    async fn api_history_requires_token_even_without_origin() {
+        let response = build_router(test_state())
+            .oneshot(
+                Request::builder()
+                    .uri("/history")
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+    }
+
+    #[tokio::test]
+    async fn api_history_with_token_reports_missing_database() {
+        let response = build_router(test_state())
+            .oneshot(
+                Request::builder()
+                    .uri("/history?token=test-token")
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::SERVICE_UNAVAILABLE);
+    }
+
+    #[tokio::test]
+    async fn api_check_rejects_empty_url_list() {
+        let response = build_router(test_state())
+            .oneshot(
+                Request::builder()
+                    .method(Method::POST)
+                    .uri("/check")
+                    .header(header::AUTHORIZATION, "Bearer test-token")
+                    .header(header::CONTENT_TYPE, "application/json")
+                    .body(Body::from(r#"{"urls":[]}"#))
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+    }
+
+    #[tokio::test]
+    async fn picgo_heartbeat_is_public() {
+        let response = build_router(test_state())
+            .oneshot(
+                Request::builder()
+                    .method(Method::POST)
+                    .uri("/heartbeat")
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::OK);
+    }
+
+    #[tokio::test]
+    async fn share_page_is_public_and_rejects_bad_ids() {
+        let dir = std::env::temp_dir().join(format!("picnexus_share_{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let id = "0123456789abcdef0123456789abcdef";
+        std::fs::write(dir.join(format!("{}.html", id)), "<p>hi</p>").unwrap();
+        let state = ServerRuntimeState {
+            share_dir: Some(dir.clone()),
+            ..test_state()
+        };
+
+        let response = build_router(state.clone())
+            .oneshot(
+                Request::builder()
+                    .uri(format!("/share/{}", id))
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        assert!(response
+            .headers()
+            .get(header::CONTENT_SECURITY_POLICY)
+            .is_some());
+
+        let response = build_router(state)
+            .oneshot(
+                Request::builder()
+                    .uri("/share/..%2Fsecret")
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+        let _ = std::fs::remove_dir_all(dir);
+    }
 }
@@ -0,0 +1,51 @@
+// src-tauri/src/server/share_handler.rs
+// 分享页：GET /share/:id 返回 create_share_page 生成的本地 HTML 页面
+//
+// 分享链接会发给别人在浏览器中打开，因此不要求 token；
+// 只接受 32 位十六进制 ID（页面文件名），内容只来自应用数据目录下的 share-pages/，
+// 并附带禁止脚本的 CSP，即使页面文件被改动也无法执行代码。
+
+use super::ServerRuntimeState;
+use axum::extract::{Path, State};
+use axum::http::{header, StatusCode};
+use axum::response::{IntoResponse, Response};
+
+/// 分享页只需要展示图片与内联样式
+const SHARE_PAGE_CSP: &str =
+    "default-src 'none'; img-src * data:; style-src 'unsafe-inline'; base-uri 'none'; form-action 'none'";
+
+/// 分享页 ID：uuid 去掉连字符后的 32 位十六进制
+pub(crate) fn is_valid_share_id(id: &str) -> bool {
+    id.len() == 32 && id.bytes().all(|b| b.is_ascii_hexdigit())
+}
+
+pub async fn handle_share_page(
+    State(state): State<ServerRuntimeState>,
+    Path(id): Path<String>,
+) -> Response {
+    if !is_valid_share_id(&id) {
+        return StatusCode::NOT_FOUND.into_response();
+    }
+    let Some(dir) = state.share_dir.as_ref() else {
+        return StatusCode::NOT_FOUND.into_response();
+    };
+
+    match tokio::fs::read(dir.join(format!("{}.html", id))).await {
+        Ok(html) => (
+            [
+                (header::CONTENT_TYPE, "text/html; charset=utf-8"),
+                (header::CONTENT_SECURITY_POLICY, SHARE_PAGE_CSP),
+                (header::X_CONTENT_TYPE_OPTIONS, "nosniff"),
+                (header::REFERRER_POLICY, "no-referrer"),
+            ],
+            html,
+        )
+            .into_response(),
+        Err(e) => {
+            if e.kind() != std::io::ErrorKind::NotFound {
+                log::warn!("[Server] 读取分享页失败 {}: {}", id, e);
+            }
+            StatusCode::NOT_FOUND.into_response()
+        }
+    }
+}
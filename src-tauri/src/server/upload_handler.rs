@@ -4,6 +4,7 @@
 
 use super::ServerRuntimeState;
 use axum::body::{Body, Bytes};
+use axum::extract::{FromRequest, Multipart};
 use axum::http::{HeaderMap, Request, StatusCode};
 use axum::middleware::Next;
 use axum::response::{IntoResponse, Response};
@@ -24,7 +25,15 @@ use hmac::{Hmac, Mac};
 use md5::{Digest, Md5};
 use sha1::Sha1;
 
+use crate::cli;
+use crate::commands::clipboard::SharedClipboard;
+use crate::commands::clock_skew::{self, SkewAdjustedTime};
+use crate::commands::http_client::HttpClient;
+use crate::commands::net_limiter::NetworkLimiter;
+use crate::commands::object_name;
 use crate::log_utils::{safe_path, safe_url, summarize_text};
+use crate::path_utils;
+use crate::uploaders::{oss, s3};
 
 type HmacSha1 = Hmac<Sha1>;
 
@@ -326,7 +335,7 @@ fn auth_value_matches(value: &str, expected: &str) -> bool {
     !expected.is_empty() && value.trim() == expected
 }
 
-fn request_has_valid_server_token(
+pub(super) fn request_has_valid_server_token(
     headers: &HeaderMap,
     query: Option<&str>,
     expected: &str,
@@ -354,7 +363,8 @@ fn request_has_valid_server_token(
 
     query.is_some_and(|query| {
         url::form_urlencoded::parse(query.as_bytes()).any(|(key, value)| {
-            matches!(key.as_ref(), "token" | "authToken" | "auth_token")
+            // key：picgo-server 的 ?key= 鉴权参数
+            matches!(key.as_ref(), "token" | "authToken" | "auth_token" | "key")
                 && auth_value_matches(value.as_ref(), expected)
         })
     })
@@ -505,6 +515,9 @@ pub enum ServerUploadConfig {
         bucket: String,
         path: String,
         public_domain: String,
+        /// 图片处理样式后缀（如 !webp），追加到返回链接末尾
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        style_suffix: Option<String>,
     },
     /// 七牛云
     Qiniu {
@@ -531,6 +544,9 @@ pub enum ServerUploadConfig {
         bucket: String,
         path: String,
         public_domain: String,
+        /// 预设 ACL（如 public-read），留空沿用存储桶策略
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        acl: Option<String>,
     },
 
     // ── 需要 Puppeteer（仅 GUI 模式） ─────────────
@@ -561,31 +577,59 @@ pub struct UploadResponse {
 
 // ==================== 主处理器 ====================
 
+fn upload_failure(message: impl Into<String>) -> Json<UploadResponse> {
+    Json(UploadResponse {
+        success: false,
+        result: None,
+        message: Some(message.into()),
+    })
+}
+
+fn is_multipart_request(headers: &HeaderMap) -> bool {
+    headers
+        .get("content-type")
+        .and_then(|v| v.to_str().ok())
+        .is_some_and(|v| {
+            v.trim()
+                .to_ascii_lowercase()
+                .starts_with("multipart/form-data")
+        })
+}
+
 /// POST /upload 处理器（PicGo 协议兼容）
+/// - application/json：{"list": ["/path/to/img.jpg"]}，上传本地路径
+/// - multipart/form-data：每个文件字段作为一张图片上传，结果顺序与字段顺序一致
 pub async fn handle_upload(
     State(state): State<ServerRuntimeState>,
-    Json(req): Json<UploadRequest>,
+    request: Request<Body>,
 ) -> Json<UploadResponse> {
     let config_guard = state.upload_config.lock().await;
     let Some(config) = config_guard.as_ref() else {
-        return Json(UploadResponse {
-            success: false,
-            result: None,
-            message: Some(
-                "Server 未配置图床，请在「常规设置 → 编辑器兼容 Server」中选择默认图床".to_string(),
-            ),
-        });
+        return upload_failure(
+            "Server 未配置图床，请在「常规设置 → 编辑器兼容 Server」中选择默认图床",
+        );
     };
 
-    let file_paths = req.list.unwrap_or_default();
-    if file_paths.is_empty() {
-        return Json(UploadResponse {
-            success: false,
-            result: None,
-            message: Some("请求中 list 字段为空，请提供文件路径".to_string()),
-        });
+    if is_multipart_request(request.headers()) {
+        return match Multipart::from_request(request, &state).await {
+            Ok(multipart) => handle_multipart_upload(multipart, config, &state.limiter).await,
+            Err(e) => upload_failure(format!("multipart 请求解析失败: {}", e)),
+        };
     }
 
+    // 不依赖 Content-Type：部分 PicGo 客户端发送 JSON 时不带该头，或直接发送空 body
+    let body = match Bytes::from_request(request, &state).await {
+        Ok(body) => body,
+        Err(e) => return upload_failure(format!("请求体读取失败: {}", e)),
+    };
+    let file_paths = match parse_picgo_upload_body(&body) {
+        Ok(PicGoUploadSource::Paths(paths)) => paths,
+        Ok(PicGoUploadSource::Clipboard) => {
+            return handle_clipboard_upload(&state.clipboard, config, &state.limiter).await
+        }
+        Err(e) => return upload_failure(e),
+    };
+
     let mut urls = Vec::new();
     for path in &file_paths {
         if let Err(e) = validate_image_file(std::path::Path::new(path)) {
@@ -596,7 +640,7 @@ pub async fn handle_upload(
             });
         }
 
-        match upload_single_file(path, config).await {
+        match upload_with_permit(&state.limiter, path, config).await {
             Ok(url) => {
                 log::info!("[Server] ✓ 上传成功: {}", safe_url(&url));
                 urls.push(url);
@@ -619,6 +663,111 @@ pub async fn handle_upload(
     })
 }
 
+/// picgo-server 语义：body 为空或 list 为空时上传剪贴板图片
+#[derive(Debug, PartialEq, Eq)]
+enum PicGoUploadSource {
+    Paths(Vec<String>),
+    Clipboard,
+}
+
+fn parse_picgo_upload_body(body: &[u8]) -> Result<PicGoUploadSource, String> {
+    if body.iter().all(u8::is_ascii_whitespace) {
+        return Ok(PicGoUploadSource::Clipboard);
+    }
+
+    let req: UploadRequest =
+        serde_json::from_slice(body).map_err(|e| format!("请求体解析失败: {}", e))?;
+    match req.list {
+        Some(list) if !list.is_empty() => Ok(PicGoUploadSource::Paths(list)),
+        _ => Ok(PicGoUploadSource::Clipboard),
+    }
+}
+
+async fn handle_clipboard_upload(
+    clipboard: &SharedClipboard,
+    config: &ServerUploadConfig,
+    limiter: &NetworkLimiter,
+) -> Json<UploadResponse> {
+    let clipboard = clipboard.clone();
+    let png = match crate::commands::cpu_pool::spawn(move || {
+        crate::commands::clipboard::read_clipboard_png(&clipboard)
+    })
+    .await
+    {
+        Ok(Ok(png)) => png,
+        Ok(Err(e)) => return upload_failure(e.to_string()),
+        Err(e) => return upload_failure(format!("读取剪贴板线程异常: {}", e)),
+    };
+
+    match upload_image_bytes(&png, Some("image/png"), None, config, limiter).await {
+        Ok(url) => {
+            log::info!("[Server] ✓ 剪贴板图片上传成功: {}", safe_url(&url));
+            Json(UploadResponse {
+                success: true,
+                result: Some(vec![url]),
+                message: None,
+            })
+        }
+        Err(e) => {
+            log::warn!("[Server] ✗ 剪贴板图片上传失败: {}", e);
+            upload_failure(e)
+        }
+    }
+}
+
+async fn handle_multipart_upload(
+    mut multipart: Multipart,
+    config: &ServerUploadConfig,
+    limiter: &NetworkLimiter,
+) -> Json<UploadResponse> {
+    let mut urls = Vec::new();
+    loop {
+        let field = match multipart.next_field().await {
+            Ok(Some(field)) => field,
+            Ok(None) => break,
+            Err(e) => return upload_failure(format!("multipart 字段读取失败: {}", e)),
+        };
+        // 只处理文件字段，忽略普通表单字段
+        let Some(filename) = field.file_name().map(str::to_owned) else {
+            continue;
+        };
+        let content_type = field.content_type().map(str::to_owned);
+        let body = match field.bytes().await {
+            Ok(body) => body,
+            Err(e) => return upload_failure(format!("读取文件 '{}' 失败: {}", filename, e)),
+        };
+
+        match upload_image_bytes(
+            &body,
+            content_type.as_deref(),
+            Some(&filename),
+            config,
+            limiter,
+        )
+        .await
+        {
+            Ok(url) => {
+                log::info!("[Server] ✓ 上传成功: {}", safe_url(&url));
+                urls.push(url);
+            }
+            Err(e) => {
+                log::warn!("[Server] ✗ 上传失败 ({}): {}", safe_path(&filename), e);
+                return upload_failure(e);
+            }
+        }
+    }
+
+    if urls.is_empty() {
+        return upload_failure("multipart 请求中没有文件字段");
+    }
+
+    Json(UploadResponse {
+        success: true,
+        result: Some(urls),
+        message: None,
+    })
+}
+
 // ==================== 状态端点 ====================
 
 /// 服务状态响应
@@ -652,6 +801,21 @@ pub async fn handle_status(State(state): State<ServerRuntimeState>) -> Json<Stat
     })
 }
 
+/// picgo-server 心跳响应
+#[derive(Serialize)]
+pub struct HeartbeatResponse {
+    pub success: bool,
+    pub result: &'static str,
+}
+
+/// POST /heartbeat 处理器（picgo-server 兼容，客户端用于探测服务是否存活）
+pub async fn handle_heartbeat() -> Json<HeartbeatResponse> {
+    Json(HeartbeatResponse {
+        success: true,
+        result: "alive",
+    })
+}
+
 fn get_service_info(config: &ServerUploadConfig) -> (&str, &str) {
     match config {
         ServerUploadConfig::Jd => ("jd", "京东图床"),
@@ -697,41 +861,63 @@ pub async fn handle_file_upload(
     };
 
     let content_type = headers.get("Content-Type").and_then(|v| v.to_str().ok());
-    let detected_kind = match validate_image_bytes(&body, content_type) {
-        Ok(kind) => kind,
-        Err(message) => {
-            return (
+    let filename = headers
+        .get("X-Filename")
+        .and_then(|v| v.to_str().ok())
+        .and_then(|s| urlencoding::decode(s).ok())
+        .map(|s| s.into_owned());
+
+    match upload_image_bytes(
+        &body,
+        content_type,
+        filename.as_deref(),
+        cfg,
+        &state.limiter,
+    )
+    .await
+    {
+        Ok(url) => {
+            log::info!("[Server] ✓ 文件上传成功: {}", safe_url(&url));
+            (
+                StatusCode::OK,
+                Json(UploadResponse {
+                    success: true,
+                    result: Some(vec![url]),
+                    message: None,
+                }),
+            )
+        }
+        Err(e) => {
+            log::warn!("[Server] ✗ 文件上传失败: {}", e);
+            (
                 StatusCode::OK,
                 Json(UploadResponse {
                     success: false,
                     result: None,
-                    message: Some(message),
+                    message: Some(e),
                 }),
-            );
+            )
         }
-    };
+    }
+}
 
-    let filename = headers
-        .get("X-Filename")
-        .and_then(|v| v.to_str().ok())
-        .and_then(|s| urlencoding::decode(s).ok())
-        .map(|s| s.into_owned())
-        .unwrap_or_else(|| {
-            let ext = detected_kind.preferred_extension();
-            format!("upload_{}.{}", chrono::Utc::now().timestamp_millis(), ext)
-        });
+/// 将请求体中的图片内容写入独立临时目录后上传（/upload/file 与 multipart /upload 共用）
+async fn upload_image_bytes(
+    body: &[u8],
+    content_type: Option<&str>,
+    filename: Option<&str>,
+    cfg: &ServerUploadConfig,
+    limiter: &NetworkLimiter,
+) -> Result<String, String> {
+    let detected_kind = validate_image_bytes(body, content_type)?;
+
+    let filename = filename.map(str::to_owned).unwrap_or_else(|| {
+        let ext = detected_kind.preferred_extension();
+        format!("upload_{}.{}", chrono::Utc::now().timestamp_millis(), ext)
+    });
 
     let temp_dir = std::env::temp_dir().join("picnexus_uploads");
-    if let Err(e) = std::fs::create_dir_all(&temp_dir) {
-        return (
-            StatusCode::OK,
-            Json(UploadResponse {
-                success: false,
-                result: None,
-                message: Some(format!("无法创建临时目录: {}", e)),
-            }),
-        );
-    }
+    std::fs::create_dir_all(&temp_dir).map_err(|e| format!("无法创建临时目录: {}", e))?;
 
     // 安全：只取纯文件名，防止路径穿越攻击（如 ../../etc/passwd）
     let safe_path = std::path::Path::new(&filename);
@@ -748,68 +934,40 @@ pub async fn handle_file_upload(
     };
     let safe_filename = format!("{}.{}", safe_stem, safe_ext);
     let request_temp_dir = unique_upload_temp_dir(&temp_dir);
-    if let Err(e) = std::fs::create_dir_all(&request_temp_dir) {
-        return (
-            StatusCode::OK,
-            Json(UploadResponse {
-                success: false,
-                result: None,
-                message: Some(format!("无法创建请求临时目录: {}", e)),
-            }),
-        );
-    }
+    std::fs::create_dir_all(&request_temp_dir)
+        .map_err(|e| format!("无法创建请求临时目录: {}", e))?;
 
     let temp_path = request_temp_dir.join(&safe_filename);
-    if let Err(e) = std::fs::write(&temp_path, &body) {
+    if let Err(e) = std::fs::write(&temp_path, body) {
         let _ = std::fs::remove_dir_all(&request_temp_dir);
-        return (
-            StatusCode::OK,
-            Json(UploadResponse {
-                success: false,
-                result: None,
-                message: Some(format!("无法写入临时文件: {}", e)),
-            }),
-        );
+        return Err(format!("无法写入临时文件: {}", e));
     }
 
-    let result = upload_single_file(temp_path.to_str().unwrap_or(""), cfg).await;
+    let result = upload_with_permit(limiter, temp_path.to_str().unwrap_or(""), cfg).await;
     let _ = std::fs::remove_file(&temp_path);
     let _ = std::fs::remove_dir(&request_temp_dir);
 
-    match result {
-        Ok(url) => {
-            log::info!("[Server] ✓ 文件上传成功: {}", safe_url(&url));
-            (
-                StatusCode::OK,
-                Json(UploadResponse {
-                    success: true,
-                    result: Some(vec![url]),
-                    message: None,
-                }),
-            )
-        }
-        Err(e) => {
-            log::warn!("[Server] ✗ 文件上传失败: {}", e);
-            (
-                StatusCode::OK,
-                Json(UploadResponse {
-                    success: false,
-                    result: None,
-                    message: Some(format!("上传失败: {}", e)),
-                }),
-            )
-        }
-    }
+    result.map_err(|e| format!("上传失败: {}", e))
 }
 
 // ==================== 分发器 ====================
 
+/// 先取得图床的并发许可再上传，Server 上传与前端上传共用同一组上限
+async fn upload_with_permit(
+    limiter: &NetworkLimiter,
+    file_path: &str,
+    config: &ServerUploadConfig,
+) -> Result<String, String> {
+    let _permit = limiter.acquire(cli::service_id_for_config(config)).await;
+    upload_single_file(file_path, config).await
+}
+
 /// 统一上传入口（Server 和 CLI 模式共用）
 pub async fn upload_single_file(
     file_path: &str,
     config: &ServerUploadConfig,
 ) -> Result<String, String> {
-    let canonical = std::fs::canonicalize(file_path)
+    let canonical = path_utils::canonicalize(path_utils::normalize_input(file_path))
         .map_err(|e| format!("无法解析文件路径 '{}': {}", file_path, e))?;
 
     if !canonical.is_file() {
@@ -837,8 +995,12 @@ pub async fn upload_single_file(
         ServerUploadConfig::Tencent { secret_id, secret_key, region, bucket, path, public_domain } => {
             server_upload_tencent(&canonical, secret_id, secret_key, region, bucket, path, public_domain).await
         }
-        ServerUploadConfig::Aliyun { access_key_id, access_key_secret, region, bucket, path, public_domain } => {
-            server_upload_aliyun(&canonical, access_key_id, access_key_secret, region, bucket, path, public_domain).await
+        ServerUploadConfig::Aliyun { .. } => {
+            // 与 upload_image 共用原生 OSS 实现（V4 签名、自定义域名与图片样式后缀）
+            let settings = oss::OssSettings::from_config(config).map_err(|e| e.to_string())?;
+            oss::upload_file(&HttpClient::shared().get(), &settings, &canonical)
+                .await
+                .map_err(|e| e.to_string())
         }
         ServerUploadConfig::Qiniu { access_key, secret_key, region, bucket, custom_domain, path } => {
             server_upload_qiniu(&canonical, access_key, secret_key, region, bucket, custom_domain, path).await
@@ -846,8 +1008,12 @@ pub async fn upload_single_file(
         ServerUploadConfig::Upyun { operator, password, bucket, public_domain } => {
             server_upload_upyun(&canonical, operator, password, bucket, public_domain).await
         }
-        ServerUploadConfig::CustomS3 { endpoint, access_key_id, secret_access_key, region, bucket, path, public_domain } => {
-            server_upload_custom_s3(&canonical, endpoint, access_key_id, secret_access_key, region, bucket, path, public_domain).await
+        ServerUploadConfig::CustomS3 { .. } => {
+            // 与 upload_image 共用 S3 实现（含 ACL 与大文件分片上传）
+            let settings = s3::S3Settings::from_config(config).map_err(|e| e.to_string())?;
+            s3::upload_file(&settings, "custom_s3", &canonical)
+                .await
+                .map_err(|e| e.to_string())
         }
         ServerUploadConfig::Nami { .. } => {
             Err("Nami 图床不支持外部编辑器模式（需要浏览器自动化获取凭证）。请在 PicNexus 设置中切换为京东、SM.MS 等支持该模式的图床".to_string())
@@ -895,6 +1061,7 @@ fn create_s3_client(endpoint: &str, access_key: &str, secret_key: &str, region:
         .region(S3Region::new(region.to_string()))
         .credentials_provider(creds)
         .force_path_style(true)
+        .time_source(SkewAdjustedTime)
         .build();
     S3Client::from_conf(config)
 }
@@ -906,15 +1073,29 @@ async fn s3_put_object(
     key: &str,
     buffer: Vec<u8>,
 ) -> Result<(), String> {
-    let body = ByteStream::from(buffer);
+    s3_put_object_with_type(client, bucket, key, buffer, None).await
+}
+
+/// S3 上传，可指定 Content-Type（缺省时由服务端推断，网页需显式声明才能在浏览器中直接打开）
+async fn s3_put_object_with_type(
+    client: &S3Client,
+    bucket: &str,
+    key: &str,
+    buffer: Vec<u8>,
+    content_type: Option<&str>,
+) -> Result<(), String> {
+    let body = Bytes::from(buffer);
     tokio::time::timeout(
         std::time::Duration::from_secs(120),
-        client
-            .put_object()
-            .bucket(bucket)
-            .key(key)
-            .body(body)
-            .send(),
+        clock_skew::send_with_skew_retry(None, || {
+            client
+                .put_object()
+                .bucket(bucket)
+                .key(key)
+                .set_content_type(content_type.map(str::to_string))
+                .body(ByteStream::from(body.clone()))
+                .send()
+        }),
     )
     .await
     .map_err(|_| "上传超时（120秒）".to_string())?
@@ -922,7 +1103,11 @@ async fn s3_put_object(
     Ok(())
 }
 
-fn validate_https_url(value: &str, label: &str, allow_empty: bool) -> Result<(), String> {
+pub(crate) fn validate_https_url(
+    value: &str,
+    label: &str,
+    allow_empty: bool,
+) -> Result<(), String> {
     let value = value.trim();
     if value.is_empty() {
         return if allow_empty {
@@ -997,7 +1182,7 @@ async fn server_upload_jd(path: &std::path::Path) -> Result<String, String> {
         .text("clientType", "comet")
         .text("pin", aid_info.1);
 
-    let client = reqwest::Client::new();
+    let client = HttpClient::shared().get();
     let resp = client
         .post("https://file-dd.jd.com/file/uploadImg.action")
         .header("Accept", "application/json, text/plain, */*")
@@ -1029,7 +1214,7 @@ async fn server_upload_jd(path: &std::path::Path) -> Result<String, String> {
 async fn jd_get_aid() -> Result<(String, String), String> {
     let url = "https://api.m.jd.com/client.action?functionId=getAidInfo&body=%7B%22aidClientType%22%3A%22comet%22%2C%22aidClientVersion%22%3A%22comet%20-v1.0.0%22%2C%22appId%22%3A%22im.customer%22%2C%22os%22%3A%22comet%22%2C%22entry%22%3A%22jd_web_EnterpriseZC%22%2C%22reqSrc%22%3A%22s_comet%22%2C%22siteId%22%3A-1%2C%22customerAppId%22%3A%22im.customer%22%7D&appid=wh5&client=wh5&clientVersion=1.0.0&loginType=3&callback=jsonp1";
 
-    let text = reqwest::Client::new()
+    let text = HttpClient::shared().get()
         .get(url)
         .header("Accept", "*/*")
         .header("Accept-Language", "zh-CN,zh;q=0.9,en;q=0.8")
@@ -1101,7 +1286,8 @@ async fn server_upload_github(
         "branch": branch,
     });
 
-    let resp = reqwest::Client::new()
+    let resp = HttpClient::shared()
+        .get()
         .put(&url)
         .header("Authorization", format!("token {}", token))
         .header("User-Agent", "PicNexus")
@@ -1174,7 +1360,8 @@ async fn server_upload_smms(path: &std::path::Path, token: &str) -> Result<Strin
         .mime_str("image/*")
         .map_err(|e| format!("MIME 设置失败: {}", e))?;
 
-    let resp = reqwest::Client::new()
+    let resp = HttpClient::shared()
+        .get()
         .post("https://sm.ms/api/v2/upload")
         .header("Authorization", token)
         .multipart(multipart::Form::new().part("smfile", part))
@@ -1212,7 +1399,8 @@ async fn server_upload_imgur(path: &std::path::Path, client_id: &str) -> Result<
 
     let encoded = STANDARD.encode(&buffer);
 
-    let resp = reqwest::Client::new()
+    let resp = HttpClient::shared()
+        .get()
         .post("https://api.imgur.com/3/image")
         .header("Authorization", format!("Client-ID {}", client_id))
         .form(&[("image", encoded.as_str()), ("type", "base64")])
@@ -1256,6 +1444,16 @@ async fn server_upload_imgur(path: &std::path::Path, client_id: &str) -> Result<
 // ── 微博图床 ──────────────────────────────────────────
 
 async fn server_upload_weibo(path: &std::path::Path, cookie: &str) -> Result<String, String> {
+    // 导出配置中没有 Cookie 时回退到登录窗口保存在钥匙串中的 Cookie
+    let keychain_cookie;
+    let cookie = if cookie.trim().is_empty() {
+        keychain_cookie = crate::commands::secret_vault::load_cookie("weibo")
+            .map_err(|e| e.to_string())?
+            .ok_or_else(|| "未配置微博 Cookie，请先在设置中登录微博".to_string())?;
+        keychain_cookie.as_str()
+    } else {
+        cookie
+    };
     let buffer = tokio::fs::read(path)
         .await
         .map_err(|e| format!("读取文件失败: {}", e))?;
@@ -1263,7 +1461,7 @@ async fn server_upload_weibo(path: &std::path::Path, cookie: &str) -> Result<Str
     let total_len = buffer.len() as u64;
     let url = "https://picupload.weibo.com/interface/pic_upload.php?s=xml&ori=1&data=1&rotate=0&wm=&app=miniblog&mime=image/jpeg";
 
-    let resp = reqwest::Client::new()
+    let resp = HttpClient::shared().get()
         .post(url)
         .header("Cookie", cookie)
         .header("Content-Length", total_len)
@@ -1341,7 +1539,7 @@ async fn server_upload_bilibili(path: &std::path::Path, cookie: &str) -> Result<
 
     let form = multipart::Form::new().part("file", part).text("csrf", csrf);
 
-    let resp = reqwest::Client::new()
+    let resp = HttpClient::shared().get()
         .post("https://mall.bilibili.com/mall-up-c/common/image")
         .header("Cookie", format!("SESSDATA={}", sessdata))
         .header("Referer", "https://mall.bilibili.com/")
@@ -1407,7 +1605,7 @@ async fn server_upload_nowcoder(path: &std::path::Path, cookie: &str) -> Result<
         .mime_str("image/*")
         .map_err(|e| format!("MIME 设置失败: {}", e))?;
 
-    let resp = reqwest::Client::new()
+    let resp = HttpClient::shared().get()
         .post(&url)
         .header("Cookie", cookie)
         .header("Referer", "https://www.nowcoder.com/creation/write/article")
@@ -1486,7 +1684,7 @@ async fn server_upload_chaoxing(path: &std::path::Path, cookie: &str) -> Result<
         .mime_str(mime_type)
         .map_err(|e| format!("MIME 设置失败: {}", e))?;
 
-    let resp = reqwest::Client::new()
+    let resp = HttpClient::shared().get()
         .post("https://notice.chaoxing.com/pc/files/uploadNoticeFile")
         .header("Cookie", cookie)
         .header("Referer", "https://notice.chaoxing.com/")
@@ -1553,10 +1751,15 @@ async fn server_upload_zhihu(
         _ => "image/jpeg",
     };
 
-    // 计算图片 MD5
-    let mut hasher = Md5::new();
-    hasher.update(&buffer);
-    let image_hash = hex::encode(hasher.finalize());
+    // 计算图片 MD5（放到 CPU 线程池，避免阻塞异步运行时）
+    let (buffer, image_hash) = crate::commands::cpu_pool::spawn(move || {
+        let mut hasher = Md5::new();
+        hasher.update(&buffer);
+        let hash = hex::encode(hasher.finalize());
+        (buffer, hash)
+    })
+    .await
+    .map_err(|e| format!("计算图片 MD5 失败: {}", e))?;
 
     let client = reqwest::Client::builder()
         .timeout(std::time::Duration::from_secs(30))
@@ -1716,41 +1919,6 @@ async fn server_upload_zhihu(
     Err("知乎图片处理超时（30秒）".to_string())
 }
 
-// ── 自定义 S3 ─────────────────────────────────────────
-
-async fn server_upload_custom_s3(
-    path: &std::path::Path,
-    endpoint: &str,
-    access_key_id: &str,
-    secret_access_key: &str,
-    region: &str,
-    bucket: &str,
-    upload_path: &str,
-    public_domain: &str,
-) -> Result<String, String> {
-    validate_https_url(endpoint, "自定义 S3 Endpoint", false)?;
-    validate_https_url(public_domain, "自定义 S3 公开域名", true)?;
-
-    let file_name = path
-        .file_name()
-        .and_then(|n| n.to_str())
-        .ok_or("无法获取文件名")?;
-    let key = build_upload_key(upload_path, file_name);
-    let buffer = tokio::fs::read(path)
-        .await
-        .map_err(|e| format!("读取文件失败: {}", e))?;
-
-    let client = create_s3_client(endpoint, access_key_id, secret_access_key, region);
-    s3_put_object(&client, bucket, &key, buffer).await?;
-
-    let url = if public_domain.trim().is_empty() {
-        format!("{}/{}/{}", endpoint.trim_end_matches('/'), bucket, key)
-    } else {
-        format!("{}/{}", public_domain.trim_end_matches('/'), key)
-    };
-    Ok(url)
-}
-
 // ── Cloudflare R2 ─────────────────────────────────────
 
 async fn server_upload_r2(
@@ -1766,7 +1934,8 @@ async fn server_upload_r2(
         .file_name()
         .and_then(|n| n.to_str())
         .ok_or("无法获取文件名")?;
-    let key = build_upload_key(upload_path, file_name);
+    let object_name = object_name::apply_for_host("r2", file_name).await;
+    let key = build_upload_key(upload_path, &object_name);
     let buffer = tokio::fs::read(path)
         .await
         .map_err(|e| format!("读取文件失败: {}", e))?;
@@ -1798,7 +1967,8 @@ async fn server_upload_tencent(
         .file_name()
         .and_then(|n| n.to_str())
         .ok_or("无法获取文件名")?;
-    let key = build_upload_key(upload_path, file_name);
+    let object_name = object_name::apply_for_host("tencent", file_name).await;
+    let key = build_upload_key(upload_path, &object_name);
     let buffer = tokio::fs::read(path)
         .await
         .map_err(|e| format!("读取文件失败: {}", e))?;
@@ -1815,38 +1985,6 @@ async fn server_upload_tencent(
     Ok(url)
 }
 
-// ── 阿里云 OSS ────────────────────────────────────────
-
-async fn server_upload_aliyun(
-    path: &std::path::Path,
-    access_key_id: &str,
-    access_key_secret: &str,
-    region: &str,
-    bucket: &str,
-    upload_path: &str,
-    public_domain: &str,
-) -> Result<String, String> {
-    let file_name = path
-        .file_name()
-        .and_then(|n| n.to_str())
-        .ok_or("无法获取文件名")?;
-    let key = build_upload_key(upload_path, file_name);
-    let buffer = tokio::fs::read(path)
-        .await
-        .map_err(|e| format!("读取文件失败: {}", e))?;
-
-    let endpoint = format!("https://oss-{}.aliyuncs.com", region);
-    let client = create_s3_client(&endpoint, access_key_id, access_key_secret, region);
-    s3_put_object(&client, bucket, &key, buffer).await?;
-
-    let url = if public_domain.is_empty() {
-        format!("https://{}.oss-{}.aliyuncs.com/{}", bucket, region, key)
-    } else {
-        format!("{}/{}", public_domain.trim_end_matches('/'), key)
-    };
-    Ok(url)
-}
-
 // ── 七牛云 ────────────────────────────────────────────
 
 async fn server_upload_qiniu(
@@ -1862,7 +2000,8 @@ async fn server_upload_qiniu(
         .file_name()
         .and_then(|n| n.to_str())
         .ok_or("无法获取文件名")?;
-    let key = build_upload_key(upload_path, file_name);
+    let object_name = object_name::apply_for_host("qiniu", file_name).await;
+    let key = build_upload_key(upload_path, &object_name);
     let buffer = tokio::fs::read(path)
         .await
         .map_err(|e| format!("读取文件失败: {}", e))?;
@@ -1877,6 +2016,172 @@ async fn server_upload_qiniu(
     Ok(format!("{}/{}", custom_domain.trim_end_matches('/'), key))
 }
 
+// ── HTML 页面（分享页） ───────────────────────────────
+
+/// 对象存储上传目标；公开链接规则与上面各图床的图片上传一致
+struct ObjectStoreTarget<'a> {
+    endpoint: String,
+    access_key: &'a str,
+    secret_key: &'a str,
+    region: &'a str,
+    bucket: &'a str,
+    upload_path: &'a str,
+    /// 公开访问前缀（不含 key）
+    public_base: String,
+}
+
+fn object_store_target(config: &ServerUploadConfig) -> Result<ObjectStoreTarget<'_>, String> {
+    let public_or = |domain: &str, fallback: String| {
+        if domain.trim().is_empty() {
+            fallback
+        } else {
+            domain.trim().trim_end_matches('/').to_string()
+        }
+    };
+    match config {
+        ServerUploadConfig::R2 {
+            account_id,
+            access_key_id,
+            secret_access_key,
+            bucket_name,
+            path,
+            public_domain,
+        } => {
+            let endpoint = format!("https://{}.r2.cloudflarestorage.com", account_id);
+            Ok(ObjectStoreTarget {
+                public_base: public_or(public_domain, format!("{}/{}", endpoint, bucket_name)),
+                endpoint,
+                access_key: access_key_id,
+                secret_key: secret_access_key,
+                region: "auto",
+                bucket: bucket_name,
+                upload_path: path,
+            })
+        }
+        ServerUploadConfig::Tencent {
+            secret_id,
+            secret_key,
+            region,
+            bucket,
+            path,
+            public_domain,
+        } => Ok(ObjectStoreTarget {
+            endpoint: format!("https://cos.{}.myqcloud.com", region),
+            access_key: secret_id,
+            secret_key,
+            region,
+            bucket,
+            upload_path: path,
+            public_base: public_or(
+                public_domain,
+                format!("https://{}.cos.{}.myqcloud.com", bucket, region),
+            ),
+        }),
+        ServerUploadConfig::Aliyun {
+            access_key_id,
+            access_key_secret,
+            region,
+            bucket,
+            path,
+            public_domain,
+            ..
+        } => Ok(ObjectStoreTarget {
+            endpoint: format!("https://oss-{}.aliyuncs.com", region),
+            access_key: access_key_id,
+            secret_key: access_key_secret,
+            region,
+            bucket,
+            upload_path: path,
+            public_base: public_or(
+                public_domain,
+                format!("https://{}.oss-{}.aliyuncs.com", bucket, region),
+            ),
+        }),
+        ServerUploadConfig::Qiniu {
+            access_key,
+            secret_key,
+            region,
+            bucket,
+            custom_domain,
+            path,
+        } => {
+            if custom_domain.trim().is_empty() {
+                return Err("七牛云需要配置自定义域名才能获取访问 URL".to_string());
+            }
+            Ok(ObjectStoreTarget {
+                endpoint: format!("https://s3-{}.qiniucs.com", region),
+                access_key,
+                secret_key,
+                region,
+                bucket,
+                upload_path: path,
+                public_base: public_or(custom_domain, String::new()),
+            })
+        }
+        ServerUploadConfig::CustomS3 {
+            endpoint,
+            access_key_id,
+            secret_access_key,
+            region,
+            bucket,
+            path,
+            public_domain,
+            ..
+        } => {
+            validate_https_url(endpoint, "自定义 S3 Endpoint", false)?;
+            validate_https_url(public_domain, "自定义 S3 公开域名", true)?;
+            let endpoint = endpoint.trim().trim_end_matches('/').to_string();
+            Ok(ObjectStoreTarget {
+                public_base: public_or(public_domain, format!("{}/{}", endpoint, bucket)),
+                endpoint,
+                access_key: access_key_id,
+                secret_key: secret_access_key,
+                region,
+                bucket,
+                upload_path: path,
+            })
+        }
+        _ => Err("该图床只接受图片，无法托管网页".to_string()),
+    }
+}
+
+/// 图床是否可以托管 HTML 页面（对象存储类图床）
+pub(crate) fn accepts_html(config: &ServerUploadConfig) -> bool {
+    matches!(
+        config,
+        ServerUploadConfig::R2 { .. }
+            | ServerUploadConfig::Tencent { .. }
+            | ServerUploadConfig::Aliyun { .. }
+            | ServerUploadConfig::Qiniu { .. }
+            | ServerUploadConfig::CustomS3 { .. }
+    )
+}
+
+/// 上传 HTML 页面到对象存储类图床，返回公开访问链接
+pub(crate) async fn upload_html_page(
+    config: &ServerUploadConfig,
+    file_name: &str,
+    html: Vec<u8>,
+) -> Result<String, String> {
+    let target = object_store_target(config)?;
+    let key = build_upload_key(target.upload_path, file_name);
+    let client = create_s3_client(
+        &target.endpoint,
+        target.access_key,
+        target.secret_key,
+        target.region,
+    );
+    s3_put_object_with_type(
+        &client,
+        target.bucket,
+        &key,
+        html,
+        Some("text/html; charset=utf-8"),
+    )
+    .await?;
+    Ok(format!("{}/{}", target.public_base, key))
+}
+
 // ── 又拍云 ────────────────────────────────────────────
 
 async fn server_upload_upyun(
@@ -1895,10 +2200,12 @@ async fn server_upload_upyun(
         .map_err(|e| format!("读取文件失败: {}", e))?;
 
     let auth = STANDARD.encode(format!("{}:{}", operator, password));
-    let remote_path = format!("/{}/{}", bucket, file_name);
+    let object_name = object_name::apply_for_host("upyun", file_name).await;
+    let remote_path = format!("/{}/{}", bucket, object_name);
     let upload_url = format!("https://v0.api.upyun.com{}", remote_path);
 
-    let resp = reqwest::Client::new()
+    let resp = HttpClient::shared()
+        .get()
         .put(&upload_url)
         .header("Authorization", format!("Basic {}", auth))
         .header("Content-Length", buffer.len())
@@ -1933,9 +2240,9 @@ async fn server_upload_upyun(
 #[cfg(test)]
 mod tests {
     use super::{
-        request_has_browser_origin, request_has_valid_server_token, unique_upload_temp_dir,
-        validate_https_url, validate_image_bytes, DetectedImageKind, ServerUploadConfig,
-        MAX_SERVER_UPLOAD_SIZE, SERVER_AUTH_TOKEN_HEADER,
+        parse_picgo_upload_body, request_has_browser_origin, request_has_valid_server_token,
+        unique_upload_temp_dir, validate_https_url, validate_image_bytes, DetectedImageKind,
+        PicGoUploadSource, ServerUploadConfig, MAX_SERVER_UPLOAD_SIZE, SERVER_AUTH_TOKEN_HEADER,
     };
     use axum::http::HeaderMap;
     use std::io::Cursor;
@@ -1987,6 +2294,39 @@ mod tests {
             Some("token=secret"),
             "secret",
         ));
+        assert!(request_has_valid_server_token(
+            &headers,
+            Some("picbed=smms&key=secret"),
+            "secret",
+        ));
+    }
+
+    #[test]
+    fn parse_picgo_upload_body_falls_back_to_clipboard() {
+        assert_eq!(
+            parse_picgo_upload_body(b""),
+            Ok(PicGoUploadSource::Clipboard)
+        );
+        assert_eq!(
+            parse_picgo_upload_body(b"{}"),
+            Ok(PicGoUploadSource::Clipboard)
+        );
+        assert_eq!(
+            parse_picgo_upload_body(br#"{"list":[]}"#),
+            Ok(PicGoUploadSource::Clipboard)
+        );
+    }
+
+    #[test]
+    fn parse_picgo_upload_body_reads_path_list() {
+        assert_eq!(
+            parse_picgo_upload_body(br#"{"list":["/tmp/a.png","/tmp/b.jpg"]}"#),
+            Ok(PicGoUploadSource::Paths(vec![
+                "/tmp/a.png".to_string(),
+                "/tmp/b.jpg".to_string()
+            ]))
+        );
+        assert!(parse_picgo_upload_body(b"not json").is_err());
     }
 
     #[test]
@@ -2097,4 +2437,41 @@ mod tests {
                 .expect_err("credentialed URL should be rejected");
         assert!(credentialed.contains("用户名或密码"));
     }
+
+    #[test]
+    fn object_store_target_matches_image_link_rules() {
+        let tencent = ServerUploadConfig::Tencent {
+            secret_id: "id".to_string(),
+            secret_key: "key".to_string(),
+            region: "ap-shanghai".to_string(),
+            bucket: "pics-123".to_string(),
+            path: "share".to_string(),
+            public_domain: String::new(),
+        };
+        assert!(accepts_html(&tencent));
+        let target = object_store_target(&tencent).unwrap();
+        assert_eq!(target.endpoint, "https://cos.ap-shanghai.myqcloud.com");
+        assert_eq!(
+            target.public_base,
+            "https://pics-123.cos.ap-shanghai.myqcloud.com"
+        );
+
+        let custom = ServerUploadConfig::CustomS3 {
+            endpoint: "https://s3.example.com/".to_string(),
+            access_key_id: "ak".to_string(),
+            secret_access_key: "sk".to_string(),
+            region: "auto".to_string(),
+            bucket: "bucket".to_string(),
+            path: String::new(),
+            public_domain: "https://cdn.example.com/".to_string(),
+            acl: None,
+        };
+        assert_eq!(
+            object_store_target(&custom).unwrap().public_base,
+            "https://cdn.example.com"
+        );
+
+        assert!(!accepts_html(&ServerUploadConfig::Jd));
+        assert!(object_store_target(&ServerUploadConfig::Jd).is_err());
+    }
 }
@@ -0,0 +1,58 @@
+// src-tauri/src/uploaders/builtin.rs
+// 内置图床：读取 GUI 导出的 cli-config.json 中对应图床的配置，交给 upload_single_file 上传
+
+use std::sync::Arc;
+
+use futures::future::BoxFuture;
+
+use super::{Uploader, UploaderRegistry};
+use crate::cli;
+use crate::commands::host_capabilities::HOST_CAPABILITIES;
+use crate::error::AppError;
+use crate::server::upload_handler::upload_single_file;
+
+/// 由 cli-config.json 驱动的图床
+pub struct ConfiguredUploader {
+    id: String,
+    display_name: String,
+}
+
+impl ConfiguredUploader {
+    pub fn new(id: impl Into<String>, display_name: impl Into<String>) -> Self {
+        Self {
+            id: id.into(),
+            display_name: display_name.into(),
+        }
+    }
+}
+
+impl Uploader for ConfiguredUploader {
+    fn id(&self) -> &str {
+        &self.id
+    }
+
+    fn display_name(&self) -> &str {
+        &self.display_name
+    }
+
+    fn upload<'a>(&'a self, file_path: &'a str) -> BoxFuture<'a, Result<String, AppError>> {
+        Box::pin(async move {
+            // 每次上传重新读取配置，设置页保存后立即生效
+            let (_, config) =
+                cli::resolve_service_config(Some(&self.id)).map_err(AppError::config)?;
+            upload_single_file(file_path, &config)
+                .await
+                .map_err(|e| AppError::upload(self.id.as_str(), e))
+        })
+    }
+}
+
+/// 注册能力表中的全部内置图床
+pub(super) fn register_all(registry: &UploaderRegistry) {
+    for host in HOST_CAPABILITIES {
+        registry.register(Arc::new(ConfiguredUploader::new(
+            host.service,
+            host.display_name,
+        )));
+    }
+}
@@ -0,0 +1,263 @@
+// src-tauri/src/uploaders/mod.rs
+// 上传后端注册表：按图床 ID 查找 Uploader 实现
+//
+// 命令代码只面向 Uploader trait 与注册表，新增图床时实现 trait 并在 registry() 初始化处注册即可，
+// 不必再改动上传命令。内置图床默认由 ConfiguredUploader 实现（读取 cli-config.json 后走
+// upload_single_file）；R2 与自定义 S3 由 S3Uploader 实现（支持 ACL 与大文件分片上传），
+// 自定义 S3 为多实例：注册表中的 `custom_s3` 只是模板（提供设置页元数据，不能直接上传），
+// `custom_s3:<id>` 在查找时按前缀动态生成；阿里云由 OssUploader 实现
+// （原生 V4 签名，支持自定义域名与图片样式后缀）；SM.MS 由 SmmsUploader 实现
+// （Token 可保存在系统钥匙串，另提供删除与容量查询命令）。
+//
+// 设置页元数据（配置字段、分类、能力）同样挂在 trait 上，默认取 uploader_registry 的字段表与
+// host_capabilities 的能力表；list_uploaders 与 list_upload_providers 都从这里的注册表生成。
+
+mod builtin;
+pub mod oss;
+pub mod s3;
+pub mod smms;
+
+use std::collections::{BTreeMap, HashSet};
+use std::sync::{Arc, LazyLock, RwLock};
+
+use futures::future::BoxFuture;
+use serde::Serialize;
+use tauri::State;
+
+use crate::cli;
+use crate::commands::host_capabilities::{capabilities_for, HostCapabilities};
+use crate::commands::net_limiter::NetworkLimiter;
+use crate::commands::uploader_registry::{self, ConfigField, UploaderCategory};
+use crate::error::AppError;
+use crate::log_utils::safe_url;
+
+pub use builtin::ConfiguredUploader;
+pub use oss::OssUploader;
+pub use s3::S3Uploader;
+pub use smms::SmmsUploader;
+
+/// 自定义 S3 的复合 ID 前缀
+pub const CUSTOM_S3_PREFIX: &str = "custom_s3:";
+
+/// 一个上传后端
+pub trait Uploader: Send + Sync {
+    /// 图床 ID（与前端 ServiceType、cli-config.json 中的键一致）
+    fn id(&self) -> &str;
+
+    /// 显示名称
+    fn display_name(&self) -> &str;
+
+    /// 图床分类（公共图床 / 私有存储）
+    fn category(&self) -> UploaderCategory {
+        uploader_registry::category_for(self.id())
+    }
+
+    /// 是否为多实例图床的模板（实际上传使用 `<id>:<profileId>`）
+    fn multi_instance(&self) -> bool {
+        false
+    }
+
+    /// 设置页配置字段（顺序即展示顺序）
+    fn config_fields(&self) -> &'static [ConfigField] {
+        uploader_registry::fields_for(self.id())
+    }
+
+    /// 能力描述；能力表未收录时为 None
+    fn capabilities(&self) -> Option<&'static HostCapabilities> {
+        capabilities_for(self.id())
+    }
+
+    /// 是否已配置可直接上传；`exported` 为 cli-config.json 中已导出的图床 ID
+    fn is_configured(&self, exported: &HashSet<String>) -> bool {
+        exported.contains(self.id())
+    }
+
+    /// 上传本地图片，返回公开链接
+    fn upload<'a>(&'a self, file_path: &'a str) -> BoxFuture<'a, Result<String, AppError>>;
+}
+
+/// 图床 ID → Uploader 实现
+#[derive(Default)]
+pub struct UploaderRegistry {
+    providers: RwLock<BTreeMap<String, Arc<dyn Uploader>>>,
+}
+
+impl UploaderRegistry {
+    /// 注册（或替换）一个上传后端
+    pub fn register(&self, uploader: Arc<dyn Uploader>) {
+        let mut providers = self.providers.write().unwrap_or_else(|e| e.into_inner());
+        providers.insert(uploader.id().to_string(), uploader);
+    }
+
+    /// 按 ID 查找；`custom_s3:<id>` 未单独注册时按前缀生成
+    pub fn get(&self, id: &str) -> Option<Arc<dyn Uploader>> {
+        let providers = self.providers.read().unwrap_or_else(|e| e.into_inner());
+        if let Some(uploader) = providers.get(id) {
+            return Some(uploader.clone());
+        }
+        let profile = id.strip_prefix(CUSTOM_S3_PREFIX)?;
+        (!profile.is_empty()).then(|| {
+            Arc::new(S3Uploader::new(id, format!("自定义 S3（{}）", profile))) as Arc<dyn Uploader>
+        })
+    }
+
+    /// 已注册的上传后端，按 ID 排序
+    pub fn all(&self) -> Vec<Arc<dyn Uploader>> {
+        let providers = self.providers.read().unwrap_or_else(|e| e.into_inner());
+        providers.values().cloned().collect()
+    }
+}
+
+static REGISTRY: LazyLock<UploaderRegistry> = LazyLock::new(|| {
+    let registry = UploaderRegistry::default();
+    builtin::register_all(&registry);
+    oss::register_all(&registry);
+    s3::register_all(&registry);
+    smms::register_all(&registry);
+    registry
+});
+
+/// 全局注册表（首次访问时注册内置图床）
+pub fn registry() -> &'static UploaderRegistry {
+    &REGISTRY
+}
+
+/// list_upload_providers 的返回项
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UploadProvider {
+    pub id: String,
+    pub display_name: String,
+    pub configured: bool,
+}
+
+/// 注册表中可直接上传的后端（不含多实例模板），加上已导出但未单独注册的自定义 S3 实例
+fn list_providers(registry: &UploaderRegistry, exported: &HashSet<String>) -> Vec<UploadProvider> {
+    let mut uploaders = registry.all();
+    uploaders.retain(|uploader| !uploader.multi_instance());
+    let mut profiles: Vec<&String> = exported
+        .iter()
+        .filter(|id| id.starts_with(CUSTOM_S3_PREFIX))
+        .collect();
+    profiles.sort();
+    uploaders.extend(profiles.into_iter().filter_map(|id| registry.get(id)));
+
+    let mut seen = HashSet::new();
+    uploaders
+        .into_iter()
+        .filter(|uploader| seen.insert(uploader.id().to_string()))
+        .map(|uploader| UploadProvider {
+            id: uploader.id().to_string(),
+            display_name: uploader.display_name().to_string(),
+            configured: uploader.is_configured(exported),
+        })
+        .collect()
+}
+
+/// 列出所有上传后端及是否已配置
+#[tauri::command]
+pub fn list_upload_providers() -> Vec<UploadProvider> {
+    // 尚未导出 CLI 配置时视为全部未配置
+    let exported: HashSet<String> = cli::configured_services()
+        .unwrap_or_default()
+        .into_iter()
+        .collect();
+    list_providers(registry(), &exported)
+}
+
+/// upload_image 的返回值
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UploadedImage {
+    pub provider_id: String,
+    pub url: String,
+}
+
+/// 通过注册表中的后端上传一张本地图片
+#[tauri::command]
+pub async fn upload_image(
+    provider_id: String,
+    path: String,
+    limiter: State<'_, NetworkLimiter>,
+) -> Result<UploadedImage, AppError> {
+    let uploader = registry()
+        .get(&provider_id)
+        .ok_or_else(|| AppError::validation(format!("未知图床: {}", provider_id)))?;
+    if uploader.multi_instance() {
+        return Err(AppError::validation(format!(
+            "请指定 {} 的具体实例: {}:<profileId>",
+            uploader.display_name(),
+            uploader.id()
+        )));
+    }
+
+    let _permit = limiter.acquire(uploader.id()).await;
+    let url = uploader.upload(&path).await?;
+    log::info!("[上传后端] {} 上传完成: {}", uploader.id(), safe_url(&url));
+
+    Ok(UploadedImage { provider_id, url })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct EchoUploader;
+
+    impl Uploader for EchoUploader {
+        fn id(&self) -> &str {
+            "echo"
+        }
+
+        fn display_name(&self) -> &str {
+            "Echo"
+        }
+
+        fn is_configured(&self, _exported: &HashSet<String>) -> bool {
+            true
+        }
+
+        fn upload<'a>(&'a self, file_path: &'a str) -> BoxFuture<'a, Result<String, AppError>> {
+            Box::pin(async move { Ok(format!("https://echo.example.com/{}", file_path)) })
+        }
+    }
+
+    #[test]
+    fn registry_resolves_registered_and_custom_s3_providers() {
+        let registry = UploaderRegistry::default();
+        registry.register(Arc::new(EchoUploader));
+
+        assert_eq!(registry.get("echo").unwrap().display_name(), "Echo");
+        assert_eq!(
+            registry.get("custom_s3:team").unwrap().id(),
+            "custom_s3:team"
+        );
+        assert!(registry.get("custom_s3:").is_none());
+        assert!(registry.get("ftp").is_none());
+
+        let url = futures::executor::block_on(registry.get("echo").unwrap().upload("a.png"));
+        assert_eq!(url.unwrap(), "https://echo.example.com/a.png");
+    }
+
+    #[test]
+    fn lists_builtin_and_exported_custom_s3_providers() {
+        let registry = UploaderRegistry::default();
+        builtin::register_all(&registry);
+        s3::register_all(&registry);
+        registry.register(Arc::new(EchoUploader));
+        let exported: HashSet<String> = ["r2", "custom_s3:team"]
+            .into_iter()
+            .map(String::from)
+            .collect();
+
+        let providers = list_providers(&registry, &exported);
+        let find = |id: &str| providers.iter().find(|p| p.id == id).cloned();
+        assert!(find("r2").unwrap().configured);
+        assert!(!find("weibo").unwrap().configured);
+        assert!(find("echo").unwrap().configured);
+        assert!(find("custom_s3:team").unwrap().configured);
+        assert!(find("custom_s3").is_none());
+        assert!(registry.get("custom_s3").unwrap().multi_instance());
+        assert_eq!(providers.last().unwrap().id, "custom_s3:team");
+    }
+}
@@ -0,0 +1,543 @@
+// src-tauri/src/uploaders/oss.rs
+// 阿里云 OSS：原生 PUT 上传，使用 V4 签名（OSS4-HMAC-SHA256）
+//
+// 配置来自 cli-config.json 中的 aliyun 条目（地域、存储桶、路径前缀、自定义域名、图片样式后缀），
+// 主窗口上传走 upload_to_aliyun_oss 命令，由前端传入配置。
+// 返回链接优先使用自定义域名（CNAME），否则为 `https://<bucket>.oss-<region>.aliyuncs.com/<key>`；
+// 配置了图片处理样式（如 `!webp`）时追加到链接末尾。
+
+use std::path::Path;
+use std::sync::Arc;
+
+use aws_smithy_async::time::TimeSource;
+use bytes::Bytes;
+use chrono::{DateTime, Utc};
+use futures::future::BoxFuture;
+use hmac::{Hmac, Mac};
+use serde::Serialize;
+use sha2::{Digest, Sha256};
+use tauri::{AppHandle, Emitter, Manager, Window};
+
+use super::{Uploader, UploaderRegistry};
+use crate::cli;
+use crate::commands::clock_skew::{self, SkewAdjustedTime};
+use crate::commands::host_capabilities;
+use crate::commands::http_client::HttpClient;
+use crate::commands::net_limiter::NetworkLimiter;
+use crate::commands::object_name;
+use crate::commands::task_registry::TaskRegistry;
+use crate::commands::utils::{read_file_bytes, remember_uploaded_source};
+use crate::error::{AppError, IntoAppError};
+use crate::log_utils::{safe_path, safe_url, summarize_text};
+use crate::server::upload_handler::{validate_https_url, ServerUploadConfig};
+
+type HmacSha256 = Hmac<Sha256>;
+
+const SIGN_ALGORITHM: &str = "OSS4-HMAC-SHA256";
+
+/// 不对请求体做摘要（HTTPS 已保证完整性），避免大文件额外计算一遍 SHA-256
+const UNSIGNED_PAYLOAD: &str = "UNSIGNED-PAYLOAD";
+
+/// 单次 PUT 请求超时
+const REQUEST_TIMEOUT_SECS: u64 = 120;
+
+/// 阿里云 OSS 的上传配置
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OssSettings {
+    /// 地域 ID（如 cn-hangzhou，不含 oss- 前缀）
+    pub region: String,
+    pub bucket: String,
+    pub access_key_id: String,
+    pub access_key_secret: String,
+    /// 对象 Key 前缀（如 blog/images）
+    pub path_prefix: String,
+    /// 返回链接使用的自定义域名（CNAME）；为空时使用存储桶默认域名
+    pub custom_domain: String,
+    /// 图片处理样式后缀（如 !webp）；为空时不追加
+    pub style_suffix: String,
+}
+
+impl OssSettings {
+    /// 从 CLI 导出的图床配置构建；非阿里云图床返回配置错误
+    pub fn from_config(config: &ServerUploadConfig) -> Result<Self, AppError> {
+        match config {
+            ServerUploadConfig::Aliyun {
+                access_key_id,
+                access_key_secret,
+                region,
+                bucket,
+                path,
+                public_domain,
+                style_suffix,
+            } => Self {
+                region: region.clone(),
+                bucket: bucket.clone(),
+                access_key_id: access_key_id.clone(),
+                access_key_secret: access_key_secret.clone(),
+                path_prefix: path.clone(),
+                custom_domain: public_domain.clone(),
+                style_suffix: style_suffix.clone().unwrap_or_default(),
+            }
+            .normalized(),
+            _ => Err(AppError::config("不是阿里云 OSS 配置")),
+        }
+    }
+
+    fn normalized(mut self) -> Result<Self, AppError> {
+        // 设置页的地域允许填写 oss-cn-hangzhou 或 cn-hangzhou
+        let region = self.region.trim();
+        self.region = region.strip_prefix("oss-").unwrap_or(region).to_string();
+        self.bucket = self.bucket.trim().to_string();
+        self.custom_domain = self.custom_domain.trim().trim_end_matches('/').to_string();
+        self.style_suffix = normalize_style_suffix(&self.style_suffix)?;
+
+        if self.region.is_empty() {
+            return Err(AppError::config("阿里云 OSS 地域不能为空"));
+        }
+        if self.bucket.is_empty() {
+            return Err(AppError::config("存储桶不能为空"));
+        }
+        validate_https_url(&self.custom_domain, "自定义域名", true).map_err(AppError::config)?;
+        Ok(self)
+    }
+
+    /// 存储桶的默认访问域名
+    fn bucket_host(&self) -> String {
+        format!("{}.oss-{}.aliyuncs.com", self.bucket, self.region)
+    }
+
+    /// 对象 Key：路径前缀 + 文件名
+    fn object_key(&self, object_name: &str) -> String {
+        match self.path_prefix.trim().trim_matches('/') {
+            "" => object_name.to_string(),
+            prefix => format!("{}/{}", prefix, object_name),
+        }
+    }
+
+    /// 对象的公开访问链接（含图片样式后缀）
+    fn object_url(&self, key: &str) -> String {
+        let base = if self.custom_domain.is_empty() {
+            format!("https://{}", self.bucket_host())
+        } else {
+            self.custom_domain.clone()
+        };
+        format!("{}/{}{}", base, encode_key(key), self.style_suffix)
+    }
+}
+
+/// 图片样式后缀：`!webp`、`-thumb`、`?x-oss-process=style/webp` 原样保留，
+/// 只填样式名（如 webp）时按默认分隔符 `!` 补全
+fn normalize_style_suffix(suffix: &str) -> Result<String, AppError> {
+    let suffix = suffix.trim();
+    if suffix.is_empty() {
+        return Ok(String::new());
+    }
+    if suffix.chars().any(|c| c.is_whitespace() || c == '#') {
+        return Err(AppError::config(format!("无效的图片样式后缀: {}", suffix)));
+    }
+    Ok(match suffix.chars().next() {
+        Some('!' | '-' | '_' | '/' | '?') => suffix.to_string(),
+        _ => format!("!{}", suffix),
+    })
+}
+
+/// 按 RFC 3986 编码对象 Key，保留路径分隔符
+pub(crate) fn encode_key(key: &str) -> String {
+    key.split('/')
+        .map(|segment| urlencoding::encode(segment).into_owned())
+        .collect::<Vec<_>>()
+        .join("/")
+}
+
+fn hmac_sha256(key: &[u8], data: &str) -> Result<Vec<u8>, AppError> {
+    let mut mac = HmacSha256::new_from_slice(key).into_external_err_with("HMAC 初始化失败")?;
+    mac.update(data.as_bytes());
+    Ok(mac.finalize().into_bytes().to_vec())
+}
+
+/// V4 签名的规范请求（只签 Content-Type 与 x-oss-* 头，不需要 AdditionalHeaders）
+fn canonical_request(bucket: &str, key: &str, content_type: &str, oss_date: &str) -> String {
+    format!(
+        "PUT\n/{}/{}\n\ncontent-type:{}\nx-oss-content-sha256:{}\nx-oss-date:{}\n\n\n{}",
+        bucket,
+        encode_key(key),
+        content_type,
+        UNSIGNED_PAYLOAD,
+        oss_date,
+        UNSIGNED_PAYLOAD
+    )
+}
+
+/// 计算 PUT 请求的签名头，返回 (x-oss-date, Authorization)
+fn sign_put(
+    settings: &OssSettings,
+    key: &str,
+    content_type: &str,
+    now: DateTime<Utc>,
+) -> Result<(String, String), AppError> {
+    let oss_date = now.format("%Y%m%dT%H%M%SZ").to_string();
+    let date = now.format("%Y%m%d").to_string();
+    let scope = format!("{}/{}/oss/aliyun_v4_request", date, settings.region);
+
+    let canonical = canonical_request(&settings.bucket, key, content_type, &oss_date);
+    let string_to_sign = format!(
+        "{}\n{}\n{}\n{}",
+        SIGN_ALGORITHM,
+        oss_date,
+        scope,
+        hex::encode(Sha256::digest(canonical.as_bytes()))
+    );
+
+    let mut signing_key = hmac_sha256(
+        format!("aliyun_v4{}", settings.access_key_secret).as_bytes(),
+        &date,
+    )?;
+    for part in [settings.region.as_str(), "oss", "aliyun_v4_request"] {
+        signing_key = hmac_sha256(&signing_key, part)?;
+    }
+    let signature = hex::encode(hmac_sha256(&signing_key, &string_to_sign)?);
+
+    let authorization = format!(
+        "{} Credential={}/{},Signature={}",
+        SIGN_ALGORITHM, settings.access_key_id, scope, signature
+    );
+    Ok((oss_date, authorization))
+}
+
+/// 从 OSS 的 XML 错误响应中取出指定元素
+fn xml_element<'a>(body: &'a str, name: &str) -> Option<&'a str> {
+    let open = format!("<{}>", name);
+    let start = body.find(&open)? + open.len();
+    let end = body[start..].find(&format!("</{}>", name))? + start;
+    Some(body[start..end].trim())
+}
+
+/// 签名并 PUT 上传一个对象
+///
+/// 签名因时钟偏差失败时按服务器时间重新签名并重试一次（请求体为 `Bytes`，重试不复制数据）；
+/// 传入 `app` 时向前端发出时钟偏差提示
+async fn put_object(
+    client: &reqwest::Client,
+    app: Option<&AppHandle>,
+    settings: &OssSettings,
+    key: &str,
+    content_type: &str,
+    body: Bytes,
+) -> Result<(), AppError> {
+    let url = format!("https://{}/{}", settings.bucket_host(), encode_key(key));
+    let mut skew_retried = false;
+
+    loop {
+        let now = DateTime::<Utc>::from(SkewAdjustedTime.now());
+        let (oss_date, authorization) = sign_put(settings, key, content_type, now)?;
+
+        let response = client
+            .put(&url)
+            .header("Content-Type", content_type)
+            .header("x-oss-content-sha256", UNSIGNED_PAYLOAD)
+            .header("x-oss-date", oss_date)
+            .header("Authorization", authorization)
+            .body(body.clone())
+            .timeout(std::time::Duration::from_secs(REQUEST_TIMEOUT_SECS))
+            .send()
+            .await
+            .into_network_err_with("上传请求失败")?;
+
+        let status = response.status();
+        if status.is_success() {
+            return Ok(());
+        }
+        let date_header = response
+            .headers()
+            .get("date")
+            .and_then(|value| value.to_str().ok())
+            .map(str::to_string);
+        let text = response.text().await.unwrap_or_default();
+        let code = xml_element(&text, "Code");
+
+        if !skew_retried {
+            if let Some(skew_seconds) =
+                clock_skew::detect_from_response(code, date_header.as_deref())
+            {
+                clock_skew::notify(app, skew_seconds);
+                skew_retried = true;
+                continue;
+            }
+        }
+
+        log::error!("[OSS] API 错误响应: {}", summarize_text(&text));
+        let code = code.unwrap_or("Unknown");
+        let message = xml_element(&text, "Message").unwrap_or_default();
+        return Err(match code {
+            "InvalidAccessKeyId" | "SignatureDoesNotMatch" => {
+                AppError::auth(format!("阿里云 AccessKey 无效或签名错误: {}", message))
+            }
+            "RequestTimeTooSkewed" => {
+                AppError::upload("阿里云 OSS", "本机时间与服务器相差过大，请校准系统时间")
+            }
+            _ => AppError::upload(
+                "阿里云 OSS",
+                format!("上传失败 (HTTP {}): {} {}", status, code, message),
+            ),
+        });
+    }
+}
+
+/// 按图床能力表校验文件类型与大小
+fn check_admission(path: &Path, file_size: u64) -> Result<(), AppError> {
+    let ext = path
+        .extension()
+        .and_then(|ext| ext.to_str())
+        .ok_or_else(|| AppError::validation("无法获取文件扩展名"))?;
+    host_capabilities::check_admission("aliyun", ext, file_size)
+}
+
+/// 上传本地文件，返回公开访问链接
+pub async fn upload_file(
+    client: &reqwest::Client,
+    settings: &OssSettings,
+    path: &Path,
+) -> Result<String, AppError> {
+    let file_name = path
+        .file_name()
+        .and_then(|name| name.to_str())
+        .ok_or_else(|| AppError::validation("无法获取文件名"))?;
+    let (buffer, file_size) = read_file_bytes(
+        &path.to_string_lossy(),
+        host_capabilities::read_limit("aliyun"),
+    )
+    .await?;
+    check_admission(path, file_size)?;
+    let object_name = object_name::apply_for_host("aliyun", file_name).await;
+    let key = settings.object_key(&object_name);
+    let content_type = mime_guess::from_path(path)
+        .first_or_octet_stream()
+        .to_string();
+
+    put_object(client, None, settings, &key, &content_type, buffer).await?;
+    let url = settings.object_url(&key);
+    log::info!("[OSS] 上传成功: {}", safe_url(&url));
+    Ok(url)
+}
+
+/// 由 cli-config.json 中的 aliyun 配置驱动的图床
+pub struct OssUploader;
+
+impl Uploader for OssUploader {
+    fn id(&self) -> &str {
+        "aliyun"
+    }
+
+    fn display_name(&self) -> &str {
+        "阿里云 OSS"
+    }
+
+    fn upload<'a>(&'a self, file_path: &'a str) -> BoxFuture<'a, Result<String, AppError>> {
+        Box::pin(async move {
+            let (_, config) =
+                cli::resolve_service_config(Some("aliyun")).map_err(AppError::config)?;
+            let settings = OssSettings::from_config(&config)?;
+            upload_file(&HttpClient::shared().get(), &settings, Path::new(file_path)).await
+        })
+    }
+}
+
+/// 用原生 OSS 实现替换阿里云的默认注册
+pub(super) fn register_all(registry: &UploaderRegistry) {
+    registry.register(Arc::new(OssUploader));
+}
+
+/// upload_to_aliyun_oss 的返回值
+#[derive(Debug, Serialize)]
+pub struct OssUploadResult {
+    pub url: String,
+    pub key: String,
+}
+
+/// 上传文件到阿里云 OSS（主窗口上传，配置由前端传入，key 已按对象名规则改写）
+#[tauri::command]
+#[allow(clippy::too_many_arguments)] // Tauri IPC 参数与 S3 兼容上传命令保持一致，前端共用同一套参数。
+pub async fn upload_to_aliyun_oss(
+    window: Window,
+    id: String,
+    file_path: String,
+    access_key: String,
+    secret_key: String,
+    region: String,
+    bucket: String,
+    key: String,
+    public_domain: String,
+    style_suffix: Option<String>,
+    limiter: tauri::State<'_, NetworkLimiter>,
+    tasks: tauri::State<'_, TaskRegistry>,
+    http_client: tauri::State<'_, HttpClient>,
+) -> Result<OssUploadResult, AppError> {
+    tasks
+        .run(
+            Some(&id),
+            upload_to_aliyun_oss_inner(
+                window,
+                id.clone(),
+                file_path.clone(),
+                access_key,
+                secret_key,
+                region,
+                bucket,
+                key,
+                public_domain,
+                style_suffix,
+                limiter,
+                http_client,
+            ),
+        )
+        .await
+        .inspect(|_| remember_uploaded_source(&file_path))
+}
+
+#[allow(clippy::too_many_arguments)]
+async fn upload_to_aliyun_oss_inner(
+    window: Window,
+    id: String,
+    file_path: String,
+    access_key: String,
+    secret_key: String,
+    region: String,
+    bucket: String,
+    key: String,
+    public_domain: String,
+    style_suffix: Option<String>,
+    limiter: tauri::State<'_, NetworkLimiter>,
+    http_client: tauri::State<'_, HttpClient>,
+) -> Result<OssUploadResult, AppError> {
+    let _permit = limiter.acquire("aliyun").await;
+    log::info!("[OSS] 开始上传文件: {}", safe_path(&file_path));
+    let settings = OssSettings {
+        region,
+        bucket,
+        access_key_id: access_key,
+        access_key_secret: secret_key,
+        path_prefix: String::new(),
+        custom_domain: public_domain,
+        style_suffix: style_suffix.unwrap_or_default(),
+    }
+    .normalized()?;
+
+    let _ = window.emit(
+        "upload://progress",
+        serde_json::json!({
+            "id": id,
+            "progress": 0,
+            "total": 100,
+            "step": "读取文件...",
+            "step_index": 1,
+            "total_steps": 2
+        }),
+    );
+    let (buffer, file_size) =
+        read_file_bytes(&file_path, host_capabilities::read_limit("aliyun")).await?;
+    check_admission(Path::new(&file_path), file_size)?;
+    let content_type = mime_guess::from_path(&file_path)
+        .first_or_octet_stream()
+        .to_string();
+
+    let _ = window.emit(
+        "upload://progress",
+        serde_json::json!({
+            "id": id,
+            "progress": 50,
+            "total": 100,
+            "step": "正在上传...",
+            "step_index": 2,
+            "total_steps": 2
+        }),
+    );
+    put_object(
+        &http_client.get(),
+        Some(window.app_handle()),
+        &settings,
+        &key,
+        &content_type,
+        buffer,
+    )
+    .await?;
+
+    let url = settings.object_url(&key);
+    log::info!("[OSS] 上传成功: {}", safe_url(&url));
+    Ok(OssUploadResult { url, key })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn settings(public_domain: &str, style_suffix: &str) -> OssSettings {
+        OssSettings::from_config(&ServerUploadConfig::Aliyun {
+            access_key_id: "LTAIexample".to_string(),
+            access_key_secret: "secret".to_string(),
+            region: " oss-cn-hangzhou ".to_string(),
+            bucket: "pics".to_string(),
+            path: "/blog/images/".to_string(),
+            public_domain: public_domain.to_string(),
+            style_suffix: Some(style_suffix.to_string()),
+        })
+        .expect("aliyun config should normalize")
+    }
+
+    #[test]
+    fn builds_urls_with_cname_and_style_suffix() {
+        let default = settings("", "");
+        assert_eq!(default.region, "cn-hangzhou");
+        let key = default.object_key("猫 1.png");
+        assert_eq!(key, "blog/images/猫 1.png");
+        assert_eq!(
+            default.object_url(&key),
+            "https://pics.oss-cn-hangzhou.aliyuncs.com/blog/images/%E7%8C%AB%201.png"
+        );
+
+        let cname = settings("https://img.example.com/", "webp");
+        assert_eq!(
+            cname.object_url("a.png"),
+            "https://img.example.com/a.png!webp"
+        );
+        assert_eq!(
+            settings("", "?x-oss-process=style/thumb").object_url("a.png"),
+            "https://pics.oss-cn-hangzhou.aliyuncs.com/a.png?x-oss-process=style/thumb"
+        );
+
+        assert!(normalize_style_suffix("!we bp").is_err());
+        assert!(OssSettings::from_config(&ServerUploadConfig::Jd).is_err());
+    }
+
+    #[test]
+    fn signs_put_with_v4_scope_and_canonical_request() {
+        assert_eq!(
+            canonical_request("pics", "a b.png", "image/png", "20240102T030405Z"),
+            "PUT\n/pics/a%20b.png\n\ncontent-type:image/png\nx-oss-content-sha256:UNSIGNED-PAYLOAD\nx-oss-date:20240102T030405Z\n\n\nUNSIGNED-PAYLOAD"
+        );
+
+        let now = DateTime::parse_from_rfc3339("2024-01-02T03:04:05Z")
+            .unwrap()
+            .with_timezone(&Utc);
+        let settings = settings("", "");
+        let (oss_date, authorization) = sign_put(&settings, "a.png", "image/png", now).unwrap();
+        assert_eq!(oss_date, "20240102T030405Z");
+        let prefix = "OSS4-HMAC-SHA256 Credential=LTAIexample/20240102/cn-hangzhou/oss/aliyun_v4_request,Signature=";
+        assert!(authorization.starts_with(prefix));
+        let signature = &authorization[prefix.len()..];
+        assert_eq!(signature.len(), 64);
+        assert!(signature.chars().all(|c| c.is_ascii_hexdigit()));
+        // 同一时刻、同一请求的签名稳定
+        assert_eq!(
+            sign_put(&settings, "a.png", "image/png", now).unwrap().1,
+            authorization
+        );
+    }
+
+    #[test]
+    fn extracts_error_code_from_xml() {
+        let body = "<?xml version=\"1.0\"?><Error><Code>AccessDenied</Code><Message>denied</Message></Error>";
+        assert_eq!(xml_element(body, "Code"), Some("AccessDenied"));
+        assert_eq!(xml_element(body, "Message"), Some("denied"));
+        assert_eq!(xml_element("not xml", "Code"), None);
+    }
+}
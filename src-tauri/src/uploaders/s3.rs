@@ -0,0 +1,546 @@
+// src-tauri/src/uploaders/s3.rs
+// S3 兼容对象存储：Amazon S3、MinIO、Cloudflare R2 与自定义 S3 Profile 共用的上传实现
+//
+// 配置来自 cli-config.json 中的 r2 / customS3 条目（端点、存储桶、地域、路径前缀、ACL、自定义域名）。
+// 不超过 5MB 的文件一次 PutObject；更大的文件按 8MB 分片流式读取并走分片上传，
+// 任一分片失败时中止分片上传，避免存储桶中残留未完成的分片。
+
+use std::path::Path;
+use std::sync::Arc;
+
+use aws_sdk_s3::config::{Credentials, Region};
+use aws_sdk_s3::primitives::ByteStream;
+use aws_sdk_s3::types::{CompletedMultipartUpload, CompletedPart, ObjectCannedAcl};
+use aws_sdk_s3::{Client, Config};
+use futures::future::BoxFuture;
+use tokio::io::AsyncReadExt;
+use tokio::time::{timeout, Duration};
+
+use super::oss::encode_key;
+use super::{Uploader, UploaderRegistry};
+use crate::cli;
+use crate::commands::clock_skew::{self, SkewAdjustedTime};
+use crate::commands::host_capabilities::{capabilities_for, HostCapabilities};
+use crate::commands::object_name;
+use crate::commands::uploader_registry::{
+    self, ConfigField, UploaderCategory, CUSTOM_S3_CAPABILITIES, CUSTOM_S3_FIELDS,
+};
+use crate::error::{AppError, IntoAppError};
+use crate::log_utils::safe_path;
+use crate::server::upload_handler::{validate_https_url, ServerUploadConfig};
+
+/// 超过该大小改用分片上传（S3 要求除最后一片外每片至少 5MB）
+const MULTIPART_THRESHOLD: u64 = 5 * 1024 * 1024;
+
+/// 分片大小
+const PART_SIZE: u64 = 8 * 1024 * 1024;
+
+/// 单次请求（PutObject / 单个分片）超时
+const REQUEST_TIMEOUT_SECS: u64 = 120;
+
+/// 自定义 S3 模板在注册表中的 ID（各 Profile 为 custom_s3:<profileId>）
+const CUSTOM_S3_TEMPLATE_ID: &str = "custom_s3";
+
+/// S3 兼容存储的上传配置
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct S3Settings {
+    /// 端点地址（不含末尾斜杠）
+    pub endpoint: String,
+    pub region: String,
+    pub bucket: String,
+    pub access_key_id: String,
+    pub secret_access_key: String,
+    /// 对象 Key 前缀（如 blog/images）
+    pub path_prefix: String,
+    /// 预设 ACL（如 public-read）；为空时沿用存储桶策略
+    pub acl: Option<String>,
+    /// 返回链接使用的自定义域名；为空时按 `端点/存储桶/Key` 拼接
+    pub custom_domain: String,
+}
+
+impl S3Settings {
+    /// 从 CLI 导出的图床配置构建；非 S3 兼容图床返回配置错误
+    pub fn from_config(config: &ServerUploadConfig) -> Result<Self, AppError> {
+        let settings = match config {
+            ServerUploadConfig::R2 {
+                account_id,
+                access_key_id,
+                secret_access_key,
+                bucket_name,
+                path,
+                public_domain,
+            } => Self {
+                endpoint: format!("https://{}.r2.cloudflarestorage.com", account_id.trim()),
+                region: "auto".to_string(),
+                bucket: bucket_name.clone(),
+                access_key_id: access_key_id.clone(),
+                secret_access_key: secret_access_key.clone(),
+                path_prefix: path.clone(),
+                // R2 不支持对象 ACL，公开访问由存储桶设置决定
+                acl: None,
+                custom_domain: public_domain.clone(),
+            },
+            ServerUploadConfig::CustomS3 {
+                endpoint,
+                access_key_id,
+                secret_access_key,
+                region,
+                bucket,
+                path,
+                public_domain,
+                acl,
+            } => Self {
+                endpoint: endpoint.clone(),
+                region: region.clone(),
+                bucket: bucket.clone(),
+                access_key_id: access_key_id.clone(),
+                secret_access_key: secret_access_key.clone(),
+                path_prefix: path.clone(),
+                acl: acl.clone(),
+                custom_domain: public_domain.clone(),
+            },
+            _ => return Err(AppError::config("该图床不是 S3 兼容存储")),
+        };
+        settings.normalized()
+    }
+
+    /// 去掉首尾空白与末尾斜杠，并校验端点、自定义域名与 ACL
+    fn normalized(mut self) -> Result<Self, AppError> {
+        self.endpoint = self.endpoint.trim().trim_end_matches('/').to_string();
+        self.custom_domain = self.custom_domain.trim().trim_end_matches('/').to_string();
+        self.bucket = self.bucket.trim().to_string();
+        self.region = match self.region.trim() {
+            "" => "us-east-1".to_string(),
+            region => region.to_string(),
+        };
+        self.acl = self
+            .acl
+            .as_deref()
+            .map(str::trim)
+            .filter(|acl| !acl.is_empty())
+            .map(str::to_string);
+
+        validate_https_url(&self.endpoint, "S3 Endpoint", false).map_err(AppError::config)?;
+        validate_https_url(&self.custom_domain, "自定义域名", true).map_err(AppError::config)?;
+        if self.bucket.is_empty() {
+            return Err(AppError::config("存储桶不能为空"));
+        }
+        if let Some(acl) = &self.acl {
+            if !ObjectCannedAcl::values().contains(&acl.as_str()) {
+                return Err(AppError::config(format!(
+                    "不支持的 ACL: {}（可选：{}）",
+                    acl,
+                    ObjectCannedAcl::values().join(" / ")
+                )));
+            }
+        }
+        Ok(self)
+    }
+
+    /// 对象 Key：路径前缀 + 文件名
+    fn object_key(&self, object_name: &str) -> String {
+        match self.path_prefix.trim().trim_matches('/') {
+            "" => object_name.to_string(),
+            prefix => format!("{}/{}", prefix, object_name),
+        }
+    }
+
+    /// 对象的公开访问链接（Key 按路径段编码，中文、空格等字符不会破坏链接）
+    fn object_url(&self, key: &str) -> String {
+        let key = encode_key(key);
+        if self.custom_domain.is_empty() {
+            format!("{}/{}/{}", self.endpoint, self.bucket, key)
+        } else {
+            format!("{}/{}", self.custom_domain, key)
+        }
+    }
+
+    fn canned_acl(&self) -> Option<ObjectCannedAcl> {
+        self.acl.as_deref().map(ObjectCannedAcl::from)
+    }
+
+    fn client(&self) -> Client {
+        let credentials = Credentials::new(
+            &self.access_key_id,
+            &self.secret_access_key,
+            None,
+            None,
+            "PicNexus",
+        );
+        // 路径风格访问兼容 MinIO 等自建服务与未配置虚拟主机域名的端点
+        let config = Config::builder()
+            .endpoint_url(&self.endpoint)
+            .region(Region::new(self.region.clone()))
+            .credentials_provider(credentials)
+            .force_path_style(true)
+            .time_source(SkewAdjustedTime)
+            .build();
+        Client::from_conf(config)
+    }
+}
+
+/// 按分片大小切分文件：返回每片的字节数
+fn part_sizes(file_size: u64) -> Vec<u64> {
+    let mut sizes = Vec::new();
+    let mut remaining = file_size;
+    while remaining > 0 {
+        let size = remaining.min(PART_SIZE);
+        sizes.push(size);
+        remaining -= size;
+    }
+    sizes
+}
+
+/// 上传本地文件，返回公开访问链接
+///
+/// `host` 为对象名规则所属的图床（r2 / custom_s3）
+pub async fn upload_file(
+    settings: &S3Settings,
+    host: &str,
+    path: &Path,
+) -> Result<String, AppError> {
+    let file_name = path
+        .file_name()
+        .and_then(|name| name.to_str())
+        .ok_or_else(|| AppError::validation("无法获取文件名"))?;
+    let file_size = tokio::fs::metadata(path)
+        .await
+        .into_file_io_err_with("读取文件信息失败")?
+        .len();
+    let object_name = object_name::apply_for_host(host, file_name).await;
+    let key = settings.object_key(&object_name);
+    let content_type = mime_guess::from_path(path)
+        .first_or_octet_stream()
+        .to_string();
+    let client = settings.client();
+
+    if file_size > MULTIPART_THRESHOLD {
+        log::info!(
+            "[S3] 分片上传 {}（{} 字节，{} 片）",
+            safe_path(&path.to_string_lossy()),
+            file_size,
+            part_sizes(file_size).len()
+        );
+        multipart_upload(&client, settings, host, &key, &content_type, path).await?;
+    } else {
+        let buffer = tokio::fs::read(path)
+            .await
+            .into_file_io_err_with("读取文件失败")?;
+        let body = bytes::Bytes::from(buffer);
+        timeout(
+            Duration::from_secs(REQUEST_TIMEOUT_SECS),
+            clock_skew::send_with_skew_retry(None, || {
+                client
+                    .put_object()
+                    .bucket(&settings.bucket)
+                    .key(&key)
+                    .content_type(&content_type)
+                    .set_acl(settings.canned_acl())
+                    .body(ByteStream::from(body.clone()))
+                    .send()
+            }),
+        )
+        .await
+        .map_err(|_| AppError::upload(host, format!("上传超时（{}秒）", REQUEST_TIMEOUT_SECS)))?
+        .map_err(|e| AppError::upload(host, format!("上传失败: {}", e)))?;
+    }
+
+    log::info!("[S3] 上传成功 - Key: {}", key);
+    Ok(settings.object_url(&key))
+}
+
+/// 分片上传；失败时中止，清理已上传的分片
+async fn multipart_upload(
+    client: &Client,
+    settings: &S3Settings,
+    host: &str,
+    key: &str,
+    content_type: &str,
+    path: &Path,
+) -> Result<(), AppError> {
+    let created = clock_skew::send_with_skew_retry(None, || {
+        client
+            .create_multipart_upload()
+            .bucket(&settings.bucket)
+            .key(key)
+            .content_type(content_type)
+            .set_acl(settings.canned_acl())
+            .send()
+    })
+    .await
+    .map_err(|e| AppError::upload(host, format!("创建分片上传失败: {}", e)))?;
+    let upload_id = created
+        .upload_id()
+        .ok_or_else(|| AppError::upload(host, "创建分片上传失败: 响应缺少 UploadId"))?
+        .to_string();
+
+    let parts = match upload_parts(client, settings, host, key, &upload_id, path).await {
+        Ok(parts) => parts,
+        Err(e) => {
+            if let Err(abort_err) = client
+                .abort_multipart_upload()
+                .bucket(&settings.bucket)
+                .key(key)
+                .upload_id(&upload_id)
+                .send()
+                .await
+            {
+                log::warn!("[S3] 中止分片上传失败: {}", abort_err);
+            }
+            return Err(e);
+        }
+    };
+
+    clock_skew::send_with_skew_retry(None, || {
+        client
+            .complete_multipart_upload()
+            .bucket(&settings.bucket)
+            .key(key)
+            .upload_id(&upload_id)
+            .multipart_upload(
+                CompletedMultipartUpload::builder()
+                    .set_parts(Some(parts.clone()))
+                    .build(),
+            )
+            .send()
+    })
+    .await
+    .map_err(|e| AppError::upload(host, format!("完成分片上传失败: {}", e)))?;
+    Ok(())
+}
+
+/// 逐片读取文件并上传，内存中同时只保留一个分片
+async fn upload_parts(
+    client: &Client,
+    settings: &S3Settings,
+    host: &str,
+    key: &str,
+    upload_id: &str,
+    path: &Path,
+) -> Result<Vec<CompletedPart>, AppError> {
+    let mut file = tokio::fs::File::open(path)
+        .await
+        .into_file_io_err_with("打开文件失败")?;
+    let file_size = file
+        .metadata()
+        .await
+        .into_file_io_err_with("读取文件信息失败")?
+        .len();
+
+    let mut parts = Vec::new();
+    for (index, size) in part_sizes(file_size).into_iter().enumerate() {
+        let part_number = index as i32 + 1;
+        let mut buffer = Vec::with_capacity(size as usize);
+        (&mut file)
+            .take(size)
+            .read_to_end(&mut buffer)
+            .await
+            .into_file_io_err_with("读取文件失败")?;
+        if buffer.len() as u64 != size {
+            return Err(AppError::file_io("上传过程中文件被修改"));
+        }
+        let body = bytes::Bytes::from(buffer);
+
+        let output = timeout(
+            Duration::from_secs(REQUEST_TIMEOUT_SECS),
+            clock_skew::send_with_skew_retry(None, || {
+                client
+                    .upload_part()
+                    .bucket(&settings.bucket)
+                    .key(key)
+                    .upload_id(upload_id)
+                    .part_number(part_number)
+                    .body(ByteStream::from(body.clone()))
+                    .send()
+            }),
+        )
+        .await
+        .map_err(|_| {
+            AppError::upload(
+                host,
+                format!(
+                    "第 {} 片上传超时（{}秒）",
+                    part_number, REQUEST_TIMEOUT_SECS
+                ),
+            )
+        })?
+        .map_err(|e| AppError::upload(host, format!("第 {} 片上传失败: {}", part_number, e)))?;
+
+        parts.push(
+            CompletedPart::builder()
+                .set_e_tag(output.e_tag().map(str::to_string))
+                .part_number(part_number)
+                .build(),
+        );
+    }
+    Ok(parts)
+}
+
+/// 由 cli-config.json 中的 S3 兼容配置驱动的图床
+pub struct S3Uploader {
+    id: String,
+    display_name: String,
+}
+
+impl S3Uploader {
+    pub fn new(id: impl Into<String>, display_name: impl Into<String>) -> Self {
+        Self {
+            id: id.into(),
+            display_name: display_name.into(),
+        }
+    }
+
+    /// 是否为自定义 S3（模板或某个 Profile）
+    fn is_custom(&self) -> bool {
+        self.id == CUSTOM_S3_TEMPLATE_ID || self.id.starts_with(super::CUSTOM_S3_PREFIX)
+    }
+
+    /// 对象名规则所属的图床（自定义 S3 各 Profile 共用 custom_s3 规则）
+    fn rule_host(&self) -> &str {
+        if self.is_custom() {
+            CUSTOM_S3_TEMPLATE_ID
+        } else {
+            &self.id
+        }
+    }
+}
+
+impl Uploader for S3Uploader {
+    fn id(&self) -> &str {
+        &self.id
+    }
+
+    fn display_name(&self) -> &str {
+        &self.display_name
+    }
+
+    fn category(&self) -> UploaderCategory {
+        UploaderCategory::Private
+    }
+
+    fn multi_instance(&self) -> bool {
+        self.id == CUSTOM_S3_TEMPLATE_ID
+    }
+
+    fn config_fields(&self) -> &'static [ConfigField] {
+        if self.is_custom() {
+            CUSTOM_S3_FIELDS
+        } else {
+            uploader_registry::fields_for(&self.id)
+        }
+    }
+
+    fn capabilities(&self) -> Option<&'static HostCapabilities> {
+        if self.is_custom() {
+            Some(&CUSTOM_S3_CAPABILITIES)
+        } else {
+            capabilities_for(&self.id)
+        }
+    }
+
+    fn upload<'a>(&'a self, file_path: &'a str) -> BoxFuture<'a, Result<String, AppError>> {
+        Box::pin(async move {
+            let (_, config) =
+                cli::resolve_service_config(Some(&self.id)).map_err(AppError::config)?;
+            let settings = S3Settings::from_config(&config)?;
+            upload_file(&settings, self.rule_host(), Path::new(file_path)).await
+        })
+    }
+}
+
+/// 用 S3 实现替换 R2 的默认注册（获得分片上传），并注册自定义 S3 模板
+pub(super) fn register_all(registry: &UploaderRegistry) {
+    registry.register(Arc::new(S3Uploader::new("r2", "Cloudflare R2")));
+    registry.register(Arc::new(S3Uploader::new(
+        CUSTOM_S3_TEMPLATE_ID,
+        "自定义 S3",
+    )));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn custom_s3(acl: Option<&str>, public_domain: &str) -> ServerUploadConfig {
+        ServerUploadConfig::CustomS3 {
+            endpoint: " https://minio.example.com:9000/ ".to_string(),
+            access_key_id: "ak".to_string(),
+            secret_access_key: "sk".to_string(),
+            region: String::new(),
+            bucket: "pics".to_string(),
+            path: "/blog/images/".to_string(),
+            public_domain: public_domain.to_string(),
+            acl: acl.map(str::to_string),
+        }
+    }
+
+    #[test]
+    fn builds_settings_and_urls_from_cli_config() {
+        let settings = S3Settings::from_config(&custom_s3(Some("public-read"), "")).unwrap();
+        assert_eq!(settings.endpoint, "https://minio.example.com:9000");
+        assert_eq!(settings.region, "us-east-1");
+        assert_eq!(settings.acl.as_deref(), Some("public-read"));
+        let key = settings.object_key("a.png");
+        assert_eq!(key, "blog/images/a.png");
+        assert_eq!(
+            settings.object_url(&key),
+            "https://minio.example.com:9000/pics/blog/images/a.png"
+        );
+
+        let settings =
+            S3Settings::from_config(&custom_s3(Some(" "), "https://cdn.example.com/")).unwrap();
+        assert_eq!(settings.acl, None);
+        assert_eq!(
+            settings.object_url("a.png"),
+            "https://cdn.example.com/a.png"
+        );
+
+        let r2 = S3Settings::from_config(&ServerUploadConfig::R2 {
+            account_id: "abc123".to_string(),
+            access_key_id: "ak".to_string(),
+            secret_access_key: "sk".to_string(),
+            bucket_name: "pics".to_string(),
+            path: String::new(),
+            public_domain: "https://img.example.com".to_string(),
+        })
+        .unwrap();
+        assert_eq!(r2.endpoint, "https://abc123.r2.cloudflarestorage.com");
+        assert_eq!(r2.region, "auto");
+        assert_eq!(r2.object_key("a.png"), "a.png");
+    }
+
+    #[test]
+    fn encodes_each_key_segment_in_urls() {
+        let settings = S3Settings::from_config(&custom_s3(None, "")).unwrap();
+        let key = settings.object_key("我的 截图#1.png");
+        assert_eq!(key, "blog/images/我的 截图#1.png");
+        assert_eq!(
+            settings.object_url(&key),
+            "https://minio.example.com:9000/pics/blog/images/%E6%88%91%E7%9A%84%20%E6%88%AA%E5%9B%BE%231.png"
+        );
+
+        let settings =
+            S3Settings::from_config(&custom_s3(None, "https://cdn.example.com")).unwrap();
+        assert_eq!(
+            settings.object_url("a b/c.png"),
+            "https://cdn.example.com/a%20b/c.png"
+        );
+    }
+
+    #[test]
+    fn rejects_invalid_settings() {
+        assert!(S3Settings::from_config(&custom_s3(Some("world-writable"), "")).is_err());
+        assert!(S3Settings::from_config(&custom_s3(None, "http://cdn.example.com")).is_err());
+        assert!(S3Settings::from_config(&ServerUploadConfig::Jd).is_err());
+    }
+
+    #[test]
+    fn splits_large_files_into_parts() {
+        assert!(part_sizes(0).is_empty());
+        assert_eq!(part_sizes(PART_SIZE), vec![PART_SIZE]);
+        assert_eq!(
+            part_sizes(2 * PART_SIZE + 10),
+            vec![PART_SIZE, PART_SIZE, 10]
+        );
+    }
+}
@@ -0,0 +1,322 @@
+// src-tauri/src/uploaders/smms.rs
+// SM.MS 图床：API Token 存储、上传、按 hash 删除与容量查询
+//
+// Token 来源优先级：命令参数 > 系统钥匙串（设置页保存 Token 时写入）> cli-config.json 中导出的 smms 配置，
+// 因此 CLI 与注册表上传无需前端传入 Token。便携模式下不访问系统钥匙串，只使用导出的配置。
+
+use std::collections::HashSet;
+use std::sync::Arc;
+
+use futures::future::BoxFuture;
+use serde::{Deserialize, Deserializer, Serialize};
+
+use super::{Uploader, UploaderRegistry};
+use crate::cli;
+use crate::commands::http_client::HttpClient;
+use crate::commands::secret_vault::TOKEN_VAULT;
+use crate::error::{AppError, IntoAppError};
+use crate::log_utils::summarize_text;
+use crate::server::upload_handler::{upload_single_file, ServerUploadConfig};
+
+const API_BASE: &str = "https://sm.ms/api/v2";
+
+/// 钥匙串中 Token 条目的账户名
+const TOKEN_VAULT_ACCOUNT: &str = "smms";
+
+/// 删除 / 容量查询请求超时
+const REQUEST_TIMEOUT_SECS: u64 = 30;
+
+/// 保存 Token 到钥匙串，空 Token 视为清除；便携模式下跳过并返回 false
+pub(crate) fn store_token(token: &str) -> Result<bool, AppError> {
+    TOKEN_VAULT.store(TOKEN_VAULT_ACCOUNT, token)
+}
+
+/// 读取钥匙串中的 Token；不存在或便携模式下返回 None
+fn load_stored_token() -> Result<Option<String>, AppError> {
+    TOKEN_VAULT.load(TOKEN_VAULT_ACCOUNT)
+}
+
+/// cli-config.json 中导出的 Token
+fn exported_token() -> Option<String> {
+    match cli::resolve_service_config(Some("smms")) {
+        Ok((_, ServerUploadConfig::Smms { token })) if !token.trim().is_empty() => {
+            Some(token.trim().to_string())
+        }
+        _ => None,
+    }
+}
+
+/// 按优先级取 Token：参数 > 钥匙串 > 导出的 CLI 配置
+pub(crate) fn resolve_token(explicit: Option<String>) -> Result<String, AppError> {
+    if let Some(token) = explicit.filter(|t| !t.trim().is_empty()) {
+        return Ok(token.trim().to_string());
+    }
+    let stored = load_stored_token().unwrap_or_else(|e| {
+        // 钥匙串不可用时继续尝试导出的配置
+        log::warn!("[SM.MS] {}", e);
+        None
+    });
+    stored
+        .or_else(exported_token)
+        .ok_or_else(|| AppError::config("未配置 SM.MS API Token"))
+}
+
+/// hash 只允许字母与数字，避免拼出任意 API 路径
+fn is_valid_hash(hash: &str) -> bool {
+    !hash.is_empty() && hash.len() <= 64 && hash.chars().all(|c| c.is_ascii_alphanumeric())
+}
+
+/// SM.MS API 通用响应
+#[derive(Debug, Deserialize)]
+struct ApiResponse<T> {
+    success: bool,
+    #[serde(default)]
+    code: String,
+    #[serde(default)]
+    message: String,
+    data: Option<T>,
+}
+
+impl<T> ApiResponse<T> {
+    fn into_result(self) -> Result<Option<T>, AppError> {
+        if self.success {
+            Ok(self.data)
+        } else {
+            Err(AppError::upload(
+                "SM.MS",
+                format!("{}: {}", self.code, self.message),
+            ))
+        }
+    }
+}
+
+/// 字节数字段兼容数字与数字字符串，缺失或为 null 时为 0
+fn de_bytes<'de, D: Deserializer<'de>>(deserializer: D) -> Result<u64, D::Error> {
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum Raw {
+        Int(u64),
+        Float(f64),
+        Text(String),
+    }
+    Ok(match Option::<Raw>::deserialize(deserializer)? {
+        Some(Raw::Int(n)) => n,
+        Some(Raw::Float(f)) => f.max(0.0) as u64,
+        Some(Raw::Text(s)) => s.trim().parse::<f64>().map_or(0, |f| f.max(0.0) as u64),
+        None => 0,
+    })
+}
+
+/// /profile 返回的账户信息
+#[derive(Debug, Deserialize)]
+struct ProfileData {
+    #[serde(default)]
+    username: String,
+    #[serde(default)]
+    role: String,
+    #[serde(default)]
+    disk_usage: String,
+    #[serde(default)]
+    disk_limit: String,
+    #[serde(default, deserialize_with = "de_bytes")]
+    disk_usage_raw: u64,
+    #[serde(default, deserialize_with = "de_bytes")]
+    disk_limit_raw: u64,
+}
+
+/// SM.MS 账户容量
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SmmsQuota {
+    pub username: String,
+    pub role: String,
+    /// SM.MS 格式化后的已用容量（如 "12.34 MB"）
+    pub disk_usage: String,
+    /// SM.MS 格式化后的总容量（如 "5.00 GB"）
+    pub disk_limit: String,
+    pub used_bytes: u64,
+    pub limit_bytes: u64,
+    pub remaining_bytes: u64,
+}
+
+fn parse_profile_response(text: &str) -> Result<SmmsQuota, AppError> {
+    let response: ApiResponse<ProfileData> = serde_json::from_str(text)
+        .map_err(|e| AppError::upload("SM.MS", format!("JSON 解析失败: {}", e)))?;
+    let data = response
+        .into_result()?
+        .ok_or_else(|| AppError::upload("SM.MS", "API 未返回账户信息"))?;
+    Ok(SmmsQuota {
+        remaining_bytes: data.disk_limit_raw.saturating_sub(data.disk_usage_raw),
+        username: data.username,
+        role: data.role,
+        disk_usage: data.disk_usage,
+        disk_limit: data.disk_limit,
+        used_bytes: data.disk_usage_raw,
+        limit_bytes: data.disk_limit_raw,
+    })
+}
+
+fn parse_delete_response(text: &str) -> Result<(), AppError> {
+    let response: ApiResponse<serde_json::Value> = serde_json::from_str(text)
+        .map_err(|e| AppError::upload("SM.MS", format!("JSON 解析失败: {}", e)))?;
+    response.into_result().map(|_| ())
+}
+
+/// 发送带 Token 的 API 请求，返回响应正文
+async fn send_api_request(
+    request: reqwest::RequestBuilder,
+    token: &str,
+) -> Result<String, AppError> {
+    let response = request
+        .header("Authorization", token)
+        .timeout(std::time::Duration::from_secs(REQUEST_TIMEOUT_SECS))
+        .send()
+        .await
+        .into_network_err_with("SM.MS 请求失败")?;
+
+    let status = response.status();
+    let text = response
+        .text()
+        .await
+        .into_network_err_with("无法读取响应")?;
+    log::debug!("[SM.MS] API 响应: {}", summarize_text(&text));
+
+    match status {
+        s if s.is_success() => Ok(text),
+        reqwest::StatusCode::UNAUTHORIZED => Err(AppError::auth("SM.MS Token 无效或已过期")),
+        reqwest::StatusCode::TOO_MANY_REQUESTS => {
+            Err(AppError::upload("SM.MS", "API 调用频率超限，请稍后重试"))
+        }
+        _ => Err(AppError::upload(
+            "SM.MS",
+            format!("请求失败 (HTTP {}): {}", status, summarize_text(&text)),
+        )),
+    }
+}
+
+/// SM.MS 上传后端：Token 按 resolve_token 的优先级解析
+pub struct SmmsUploader;
+
+impl Uploader for SmmsUploader {
+    fn id(&self) -> &str {
+        "smms"
+    }
+
+    fn display_name(&self) -> &str {
+        "SM.MS"
+    }
+
+    fn is_configured(&self, exported: &HashSet<String>) -> bool {
+        exported.contains(self.id()) || matches!(load_stored_token(), Ok(Some(_)))
+    }
+
+    fn upload<'a>(&'a self, file_path: &'a str) -> BoxFuture<'a, Result<String, AppError>> {
+        Box::pin(async move {
+            let config = ServerUploadConfig::Smms {
+                token: resolve_token(None)?,
+            };
+            upload_single_file(file_path, &config)
+                .await
+                .map_err(|e| AppError::upload("SM.MS", e))
+        })
+    }
+}
+
+/// 注册 SM.MS（替换内置图床中由 cli-config.json 驱动的同名实现）
+pub(super) fn register_all(registry: &UploaderRegistry) {
+    registry.register(Arc::new(SmmsUploader));
+}
+
+/// 保存 SM.MS API Token 到系统钥匙串（空字符串表示清除）；便携模式下跳过并返回 false
+#[tauri::command]
+pub fn save_smms_token(token: String) -> Result<bool, AppError> {
+    store_token(&token)
+}
+
+/// 按 hash 删除 SM.MS 上的图片；未传 token 时使用已保存的 Token
+#[tauri::command]
+pub async fn delete_smms_image(
+    hash: String,
+    token: Option<String>,
+    http_client: tauri::State<'_, HttpClient>,
+) -> Result<(), AppError> {
+    let hash = hash.trim();
+    if !is_valid_hash(hash) {
+        return Err(AppError::validation(format!(
+            "无效的 SM.MS 图片 hash: {}",
+            hash
+        )));
+    }
+    let token = resolve_token(token)?;
+    let text = send_api_request(
+        http_client
+            .get()
+            .get(format!("{}/delete/{}", API_BASE, hash)),
+        &token,
+    )
+    .await?;
+    parse_delete_response(&text)?;
+    log::info!("[SM.MS] 已删除图片: {}", hash);
+    Ok(())
+}
+
+/// 查询 SM.MS 账户容量；未传 token 时使用已保存的 Token
+#[tauri::command]
+pub async fn get_smms_quota(
+    token: Option<String>,
+    http_client: tauri::State<'_, HttpClient>,
+) -> Result<SmmsQuota, AppError> {
+    let token = resolve_token(token)?;
+    let text = send_api_request(
+        http_client.get().post(format!("{}/profile", API_BASE)),
+        &token,
+    )
+    .await?;
+    parse_profile_response(&text)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_profile_quota_with_numeric_or_text_sizes() {
+        let quota = parse_profile_response(
+            r#"{"success":true,"code":"success","message":"ok","data":{"username":"alice","role":"user","disk_usage":"1.00 MB","disk_limit":"5.00 GB","disk_usage_raw":1048576,"disk_limit_raw":"5368709120"}}"#,
+        )
+        .expect("profile should parse");
+        assert_eq!(quota.username, "alice");
+        assert_eq!(quota.used_bytes, 1_048_576);
+        assert_eq!(quota.limit_bytes, 5_368_709_120);
+        assert_eq!(quota.remaining_bytes, 5_368_709_120 - 1_048_576);
+
+        let quota = parse_profile_response(
+            r#"{"success":true,"data":{"username":"bob","disk_usage_raw":10,"disk_limit_raw":null}}"#,
+        )
+        .expect("missing limit should parse");
+        assert_eq!(quota.remaining_bytes, 0);
+    }
+
+    #[test]
+    fn surfaces_api_failures() {
+        let err = parse_profile_response(
+            r#"{"success":false,"code":"unauthorized","message":"Token invalid","data":null}"#,
+        )
+        .unwrap_err();
+        assert!(err.to_string().contains("unauthorized"));
+
+        assert!(parse_delete_response(r#"{"success":true,"code":"success","data":[]}"#).is_ok());
+        assert!(
+            parse_delete_response(r#"{"success":false,"code":"delete_failed","message":"x"}"#)
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn hash_rejects_path_characters() {
+        assert!(is_valid_hash("aB3xYz9"));
+        assert!(!is_valid_hash(""));
+        assert!(!is_valid_hash("../upload"));
+        assert!(!is_valid_hash("abc?format=xml"));
+    }
+}
@@ -0,0 +1,181 @@
+// src-tauri/src/url_utils.rs
+// 图片链接的解析与规范化：链接检测、URL 下载、浏览器扩展转存共用
+//
+// 用户粘贴的链接常带 Markdown 尖括号 / 引号、协议相对写法（//cdn...）、中文域名和
+// utm_* 之类的追踪参数。这里统一解析为标准 URL：域名转为 punycode、去掉追踪参数与片段，
+// 只接受 http / https，后续的内网地址校验与请求都基于规范化后的结果。
+
+use url::Url;
+
+use crate::error::AppError;
+
+/// 允许的协议
+pub const ALLOWED_SCHEMES: &[&str] = &["https", "http"];
+
+/// 链接最大长度，超出视为无效输入
+const MAX_URL_LEN: usize = 8 * 1024;
+
+/// 常见的追踪参数，去掉后不影响图片内容
+const TRACKING_PARAMS: &[&str] = &[
+    "fbclid", "gclid", "msclkid", "yclid", "igshid", "mc_cid", "mc_eid", "_hsenc", "_hsmi", "spm",
+];
+
+fn is_tracking_param(key: &str) -> bool {
+    let key = key.to_ascii_lowercase();
+    key.starts_with("utm_") || TRACKING_PARAMS.contains(&key.as_str())
+}
+
+/// 解析用户输入的链接
+///
+/// - 去掉首尾空白、Markdown 尖括号与成对引号
+/// - `//host/path` 按 https 补全
+/// - 国际化域名转为 punycode（由 url 库按 IDNA 规则处理）
+/// - 只接受 `ALLOWED_SCHEMES` 中的协议，且必须带主机名
+pub fn parse_link(raw: &str) -> Result<Url, AppError> {
+    let mut text = raw.trim();
+    for (open, close) in [('<', '>'), ('"', '"'), ('\'', '\'')] {
+        if let Some(inner) = text
+            .strip_prefix(open)
+            .and_then(|rest| rest.strip_suffix(close))
+        {
+            text = inner.trim();
+        }
+    }
+
+    if text.is_empty() {
+        return Err(AppError::validation("链接为空"));
+    }
+    if text.len() > MAX_URL_LEN {
+        return Err(AppError::validation("链接过长"));
+    }
+    if text.chars().any(|c| c.is_whitespace() || c.is_control()) {
+        return Err(AppError::validation("链接中不能包含空白或控制字符"));
+    }
+
+    let owned;
+    let text = if text.starts_with("//") {
+        owned = format!("https:{}", text);
+        owned.as_str()
+    } else {
+        text
+    };
+
+    let parsed =
+        Url::parse(text).map_err(|e| AppError::validation(format!("链接格式不正确: {}", e)))?;
+    if !ALLOWED_SCHEMES.contains(&parsed.scheme()) {
+        return Err(AppError::validation(format!(
+            "不支持的链接协议: {}（仅支持 http / https）",
+            parsed.scheme()
+        )));
+    }
+    if parsed.host_str().is_none_or(str::is_empty) {
+        return Err(AppError::validation("链接缺少主机名"));
+    }
+    Ok(parsed)
+}
+
+/// 规范化：去掉追踪参数与片段（片段不会发给服务器）
+///
+/// 其余参数按原始文本保留，不重新编码，避免破坏签名链接
+pub fn normalize(mut url: Url) -> Url {
+    url.set_fragment(None);
+    let Some(query) = url.query() else {
+        return url;
+    };
+    let pairs: Vec<&str> = query.split('&').collect();
+    let kept: Vec<&str> = pairs
+        .iter()
+        .copied()
+        .filter(|pair| !is_tracking_param(pair.split('=').next().unwrap_or("")))
+        .collect();
+    if kept.len() != pairs.len() {
+        let kept = kept.join("&");
+        url.set_query((!kept.is_empty()).then_some(kept.as_str()));
+    }
+    url
+}
+
+/// 解析并规范化
+pub fn parse_and_normalize(raw: &str) -> Result<Url, AppError> {
+    parse_link(raw).map(normalize)
+}
+
+/// 链接的主机键（主机名 + 非默认端口），用于按主机限流；无法解析时返回 "unknown"
+pub fn host_key(raw: &str) -> String {
+    let Ok(parsed) = parse_link(raw) else {
+        return "unknown".to_string();
+    };
+    let host = parsed.host_str().unwrap_or("unknown");
+    match parsed.port() {
+        Some(port) => format!("{}:{}", host, port),
+        None => host.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_link_strips_markdown_wrappers() {
+        let url = parse_link("  <https://example.com/a.png>  ").unwrap();
+        assert_eq!(url.as_str(), "https://example.com/a.png");
+        let url = parse_link("\"https://example.com/b.png\"").unwrap();
+        assert_eq!(url.as_str(), "https://example.com/b.png");
+    }
+
+    #[test]
+    fn parse_link_completes_protocol_relative_urls() {
+        let url = parse_link("//cdn.example.com/a.png").unwrap();
+        assert_eq!(url.scheme(), "https");
+        assert_eq!(url.host_str(), Some("cdn.example.com"));
+    }
+
+    #[test]
+    fn parse_link_converts_idn_to_punycode() {
+        let url = parse_link("https://例子.测试/图.png").unwrap();
+        assert_eq!(url.host_str(), Some("xn--fsqu00a.xn--0zwm56d"));
+    }
+
+    #[test]
+    fn parse_link_rejects_bad_input() {
+        for raw in [
+            "",
+            "   ",
+            "not a url",
+            "ftp://example.com/a.png",
+            "file:///etc/passwd",
+            "javascript:alert(1)",
+            "https://exa mple.com/a.png",
+            "data:image/png;base64,AAAA",
+        ] {
+            assert!(parse_link(raw).is_err(), "{:?} should be rejected", raw);
+        }
+    }
+
+    #[test]
+    fn normalize_drops_tracking_params_and_fragment() {
+        let url = parse_and_normalize(
+            "https://example.com/a.png?w=100&utm_source=x&UTM_Medium=y&spm=1#top",
+        )
+        .unwrap();
+        assert_eq!(url.as_str(), "https://example.com/a.png?w=100");
+
+        let url = parse_and_normalize("https://example.com/a.png?fbclid=abc").unwrap();
+        assert_eq!(url.as_str(), "https://example.com/a.png");
+    }
+
+    #[test]
+    fn normalize_keeps_untracked_query_verbatim() {
+        // 签名参数的编码形式必须原样保留
+        let raw = "https://example.com/a.png?x-oss-process=image%2Fresize&sign=a%2Bb";
+        assert_eq!(parse_and_normalize(raw).unwrap().as_str(), raw);
+    }
+
+    #[test]
+    fn host_key_includes_non_default_port() {
+        assert_eq!(host_key("https://Example.com/a.png"), "example.com");
+        assert_eq!(host_key("http://localhost:1420/a.png"), "localhost:1420");
+        assert_eq!(host_key("garbage"), "unknown");
+    }
+}
@@ -243,23 +243,26 @@ export async function migrateOneItem(
       status.error = formatMigrateFailureSummary(failedDetails);
     }
     try {
-      const updatedResults = [
-        ...item.results,
-        ...newResults.map(r => ({ serviceId: r.serviceId, result: r.result, status: r.status, error: r.error })),
-      ];
-
-      // 同步 linkCheckSummary：新追加的成功图床尚未检测，计入 total 和 unchecked
+      const appended = newResults.map(r => ({ serviceId: r.serviceId, result: r.result, status: r.status, error: r.error }));
       const appendedSuccess = newResults.filter(r => r.status === 'success').length;
-      const updates: Partial<HistoryItem> = { results: updatedResults };
-      if (appendedSuccess > 0 && item.linkCheckSummary) {
-        updates.linkCheckSummary = {
-          ...item.linkCheckSummary,
-          totalLinks: item.linkCheckSummary.totalLinks + appendedSuccess,
-          uncheckedLinks: item.linkCheckSummary.uncheckedLinks + appendedSuccess,
-        };
-      }
 
-      await historyDB.update(item.id, updates);
+      // 下载 + 上传耗时较长，期间记录可能被其他流程改过：在写锁内基于最新记录追加，
+      // 而不是用迁移开始时的 item 快照整字段覆盖
+      const updated = await historyDB.modify(item.id, (latest) => {
+        const updates: Partial<HistoryItem> = { results: [...latest.results, ...appended] };
+        // 同步 linkCheckSummary：新追加的成功图床尚未检测，计入 total 和 unchecked
+        if (appendedSuccess > 0 && latest.linkCheckSummary) {
+          updates.linkCheckSummary = {
+            ...latest.linkCheckSummary,
+            totalLinks: latest.linkCheckSummary.totalLinks + appendedSuccess,
+            uncheckedLinks: latest.linkCheckSummary.uncheckedLinks + appendedSuccess,
+          };
+        }
+        return updates;
+      });
+      if (!updated) {
+        throw new Error(`记录不存在: ${item.id}`);
+      }
       status.status = 'success';
     } catch (e) {
       log.error(`更新历史记录失败: ${item.id}`, e);
@@ -73,6 +73,45 @@ function stripServiceFromItem(
   return { nextItem, matched: true };
 }
 
+/**
+ * 在写锁内基于最新记录摘除图床结果
+ * 确认弹窗期间记录可能已被上传 / 检测流程改过，不能用弹窗前读到的快照整字段写回
+ * @returns 'updated' 已写回；'deleted' 结果摘空后已整条删除；false 记录或图床已不存在
+ */
+async function stripServicesFromRecord(
+  historyId: string,
+  serviceIds: string[],
+): Promise<ResultDeleteOutcome> {
+  const outcome: { value: ResultDeleteOutcome } = { value: false };
+  await historyDB.modify(historyId, (latest) => {
+    let current: HistoryItem | null = latest;
+    let anyMatched = false;
+    for (const serviceId of serviceIds) {
+      if (!current) break;
+      const { nextItem, matched } = stripServiceFromItem(current, serviceId);
+      if (matched) anyMatched = true;
+      current = nextItem;
+    }
+    if (!anyMatched) return null;
+    if (current === null) {
+      outcome.value = 'deleted';
+      return null;
+    }
+    outcome.value = 'updated';
+    return {
+      results: current.results,
+      linkCheckStatus: current.linkCheckStatus,
+      linkCheckSummary: current.linkCheckSummary,
+      primaryService: current.primaryService,
+      generatedLink: current.generatedLink,
+    };
+  });
+  if (outcome.value === 'deleted') {
+    await historyDB.delete(historyId);
+  }
+  return outcome.value;
+}
+
 export function createResultOps(ctx: ResultOpsContext) {
   const toast = useToast();
   const { confirm } = useConfirm();
@@ -134,26 +173,18 @@ export function createResultOps(ctx: ResultOpsContext) {
       });
       if (!confirmed) return false;
 
-      const { nextItem, matched } = stripServiceFromItem(existing, serviceId);
-      if (!matched) {
+      const outcome = await stripServicesFromRecord(historyId, [serviceId]);
+      if (!outcome) {
         log.warn('[历史记录] 目标图床不存在，跳过删除', { historyId, serviceId });
         return false;
       }
 
-      if (nextItem === null) {
-        await historyDB.delete(historyId);
+      if (outcome === 'deleted') {
         await applyChanges([historyId], []);
         toast.showConfig('success', TOAST_MESSAGES.common.deleteSuccess(1));
         return 'deleted';
       }
 
-      await historyDB.update(historyId, {
-        results: nextItem.results,
-        linkCheckStatus: nextItem.linkCheckStatus,
-        linkCheckSummary: nextItem.linkCheckSummary,
-        primaryService: nextItem.primaryService,
-        generatedLink: nextItem.generatedLink,
-      });
       await applyChanges([], [historyId]);
       toast.showConfig('success', TOAST_MESSAGES.common.deleteSuccess(1));
       return 'updated';
@@ -197,30 +228,10 @@ export function createResultOps(ctx: ResultOpsContext) {
       const updatedItemIds: string[] = [];
 
       for (const [historyId, serviceSet] of grouped) {
-        const existing = await historyDB.getById(historyId);
-        if (!existing) continue;
-
-        let current: HistoryItem | null = existing;
-        let anyMatched = false;
-        for (const serviceId of serviceSet) {
-          if (!current) break;
-          const { nextItem, matched } = stripServiceFromItem(current, serviceId);
-          if (matched) anyMatched = true;
-          current = nextItem;
-        }
-        if (!anyMatched) continue;
-
-        if (current === null) {
-          await historyDB.delete(historyId);
+        const outcome = await stripServicesFromRecord(historyId, [...serviceSet]);
+        if (outcome === 'deleted') {
           deletedItemIds.push(historyId);
-        } else {
-          await historyDB.update(historyId, {
-            results: current.results,
-            linkCheckStatus: current.linkCheckStatus,
-            linkCheckSummary: current.linkCheckSummary,
-            primaryService: current.primaryService,
-            generatedLink: current.generatedLink,
-          });
+        } else if (outcome === 'updated') {
           updatedItemIds.push(historyId);
         }
       }
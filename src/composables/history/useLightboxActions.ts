@@ -4,6 +4,7 @@ import { useToast } from '../useToast';
 import { useCopyLink } from '../useCopyLink';
 import { createLogger } from '../../utils/logger';
 import { openUserExternalUrl } from '../../security/shellOpen';
+import { getWeiboSizeVariants, type WeiboSize } from '../../uploaders/weibo/weiboSizes';
 
 const logger = createLogger('LightboxActions');
 const COPY_FEEDBACK_DURATION = 2000;
@@ -18,6 +19,8 @@ export function useLightboxActions({ item, resetZoom, onDelete }: LightboxAction
   const toast = useToast();
   const { copyLink: copyLinkAction, applyConfiguredUrl } = useCopyLink();
   const copySuccess = ref(false);
+  const qrVisible = ref(false);
+  const qrUrl = ref('');
   let copyTimer: ReturnType<typeof setTimeout> | null = null;
 
   // 切换图片时重置反馈状态
@@ -56,6 +59,10 @@ export function useLightboxActions({ item, resetZoom, onDelete }: LightboxAction
       serviceId: ctx.record.primaryService as ServiceType,
       width: ctx.record.width,
       height: ctx.record.height,
+      shortUrl: ctx.record.shortUrl,
+      altText: ctx.record.altText,
+      altSuggestion: ctx.record.altSuggestion,
+      title: ctx.record.title,
     }, { showSuccessToast: false });
     if (result.ok) showCopyFeedback();
   }
@@ -75,10 +82,46 @@ export function useLightboxActions({ item, resetZoom, onDelete }: LightboxAction
       serviceId: serviceId as ServiceType,
       width: item.value.width,
       height: item.value.height,
+      altText: item.value.altText,
+      altSuggestion: item.value.altSuggestion,
+      title: item.value.title,
     }, { showSuccessToast: false });
     if (result.ok) showCopyFeedback();
   }
 
+  /** 复制微博指定尺寸的链接（前缀、格式与普通复制一致） */
+  async function handleCopyWeiboSize(size: WeiboSize) {
+    if (!item.value) return;
+    const weiboResult = item.value.results.find(
+      r => r.serviceId === 'weibo' && r.status === 'success',
+    );
+    const variants = getWeiboSizeVariants(weiboResult?.result);
+    if (!variants) {
+      toast.warn('无可用链接', '该记录没有微博链接');
+      return;
+    }
+    const result = await copyLinkAction({
+      url: variants[size],
+      fileName: item.value.localFileName,
+      serviceId: 'weibo',
+      width: item.value.width,
+      height: item.value.height,
+      altText: item.value.altText,
+      altSuggestion: item.value.altSuggestion,
+      title: item.value.title,
+    }, { showSuccessToast: false });
+    if (result.ok) showCopyFeedback();
+  }
+
+  /** 打开二维码：与复制一致，优先使用短链接 */
+  function handleShowQr() {
+    const ctx = requireLink();
+    if (!ctx) return;
+    qrUrl.value = ctx.record.shortUrl
+      || applyConfiguredUrl(ctx.link, ctx.record.primaryService as ServiceType);
+    qrVisible.value = true;
+  }
+
   async function openInBrowser() {
     const ctx = requireLink();
     if (!ctx) return;
@@ -102,5 +145,15 @@ export function useLightboxActions({ item, resetZoom, onDelete }: LightboxAction
     if (copyTimer) { clearTimeout(copyTimer); copyTimer = null; }
   });
 
-  return { handleCopyLink, handleCopyServiceLink, copySuccess, openInBrowser, handleDelete };
+  return {
+    handleCopyLink,
+    handleCopyServiceLink,
+    handleCopyWeiboSize,
+    copySuccess,
+    handleShowQr,
+    qrVisible,
+    qrUrl,
+    openInBrowser,
+    handleDelete,
+  };
 }
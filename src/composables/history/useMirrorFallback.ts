@@ -104,14 +104,6 @@ export function useMirrorFallback(item: Ref<HistoryItem | null>) {
     { deep: true },
   );
 
-  /**
-   * checkMirror 的 DB 写入串行队列。
-   * 不同 serviceId 的 invoke('check_image_link') 仍然并行（网络无序），但落库前
-   * 通过这条链排队。原因：historyDB.update 内部是 read-modify-write + 浅合并，
-   * 两个并发回写都拿同一份 baseline 后写回，会让先写的那条结果被后写的整体覆盖。
-   */
-  let dbWriteQueue: Promise<unknown> = Promise.resolve();
-
   /**
    * 全部成功上传的图床备份 + 各自 linkCheck 状态。
    * 顺序严格按 results 原序——切换主图床时行不动位，仅圆点跳到目标行。
@@ -360,18 +352,11 @@ export function useMirrorFallback(item: Ref<HistoryItem | null>) {
         },
       });
 
-      // 串行化 DB 写入：等上一笔回写完成再读 → 合并 → 写。
-      // 失败也要继续放行队列，避免一次抛错导致所有后续写永远卡住。
-      const writeTask = dbWriteQueue.then(async () => {
-        const latest = await historyDB.getById(cur.id);
-        if (!latest) return false;
-        const linkCheckStatus = { ...(latest.linkCheckStatus ?? {}) };
-        linkCheckStatus[serviceId] = entry;
-        await historyDB.update(cur.id, { linkCheckStatus });
-        return true;
-      });
-      dbWriteQueue = writeTask.catch(() => undefined);
-      const written = await writeTask;
+      // 不同 serviceId 的检测并行返回，落库时在写锁内基于最新记录合并，
+      // 避免两个回写拿同一份 baseline，先写的结果被后写的整字段覆盖
+      const written = await historyDB.modify(cur.id, latest => ({
+        linkCheckStatus: { ...(latest.linkCheckStatus ?? {}), [serviceId]: entry },
+      }));
       if (!written) return;
 
       await notifyUpdated(cur.id);
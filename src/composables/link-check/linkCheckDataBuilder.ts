@@ -1,4 +1,5 @@
 import type { HistoryItem, ServiceType, UserConfig } from '../../config/types';
+import { getActivePrefix } from '../../config/types';
 import type { LinkCheckLiteRow } from '../../services/HistoryDatabase';
 import type {
   BatchCheckRequestItem,
@@ -7,6 +8,7 @@ import type {
   LinkCheckRow,
 } from '../../types/linkCheck';
 import { applyConfiguredUrlWithConfig } from '../useCopyLink';
+import { rewriteWeiboCdnHost, weiboCdnProbeHost } from '../../uploaders/weibo/weiboCdn';
 
 export function linkCheckRowKey(row: Pick<LinkCheckRow, 'historyId' | 'serviceId'>): string {
   return `${row.historyId}::${row.serviceId}`;
@@ -45,6 +47,9 @@ export function buildCheckItemsSync(
 } {
   const requestItems: BatchCheckRequestItem[] = [];
   const rows: LinkCheckRow[] = [];
+  // 微博 CDN 自动模式：直连检测时轮流使用各节点，为节点测速积累样本（使用前缀时测到的是代理延迟，不参与）
+  const probeWeiboCdn = config.services?.weibo?.cdnHost === 'auto' && !getActivePrefix(config);
+  let weiboProbeIndex = 0;
 
   for (const item of items) {
     if (!item.results) continue;
@@ -53,7 +58,9 @@ export function buildCheckItemsSync(
       if (result.status !== 'success' || !result.result?.url) continue;
 
       const rawUrl = result.result.url;
-      const finalUrl = applyConfiguredUrlWithConfig(rawUrl, result.serviceId, config);
+      const finalUrl = probeWeiboCdn && result.serviceId === 'weibo'
+        ? rewriteWeiboCdnHost(rawUrl, weiboCdnProbeHost(weiboProbeIndex++))
+        : applyConfiguredUrlWithConfig(rawUrl, result.serviceId, config);
       const rawGithubUrl = result.serviceId === 'github'
         ? ((result.result.metadata as Record<string, unknown>)?.rawUrl as string | undefined)
         : undefined;
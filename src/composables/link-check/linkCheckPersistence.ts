@@ -3,6 +3,7 @@
 import type { HistoryItem } from '../../config/types';
 import { historyDB } from '../../services/HistoryDatabase';
 import { createLogger } from '../../utils/logger';
+import { recordWeiboCdnLatency } from '../../uploaders/weibo/weiboCdn';
 import type {
   BatchCheckResult,
   LinkCheckRow,
@@ -56,6 +57,13 @@ function countSuccessServices(rawResults: string | null): number {
 export async function updateHistoryCheckStatus(
   result: BatchCheckResult,
 ): Promise<void> {
+  // 微博链接的响应时间同时作为 CDN 节点测速样本（内部已捕获错误，不阻塞状态写入）
+  void recordWeiboCdnLatency(
+    result.results
+      .filter((r) => r.service_id === 'weibo')
+      .map((r) => ({ url: r.link, responseTime: r.response_time, ok: r.is_valid || r.browser_might_work })),
+  );
+
   // 按 historyId 分组检测结果
   const grouped = new Map<string, typeof result.results>();
   for (const r of result.results) {
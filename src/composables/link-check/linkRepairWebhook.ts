@@ -0,0 +1,132 @@
+// 链接修复 Webhook —— 全量检测发现失效链接后通知外部服务（如触发 CI 修补博客仓库）
+
+import { invoke } from '@tauri-apps/api/core';
+import type { LinkRepairWebhookConfig, UserConfig } from '../../config/types';
+import type {
+  BatchCheckItemResult,
+  BatchCheckResult,
+  LinkCheckRow,
+} from '../../types/linkCheck';
+import { createLogger } from '../../utils/logger';
+
+const log = createLogger('LinkCheck');
+
+export interface LinkRepairBrokenLink {
+  serviceId: string;
+  url: string;
+  errorType: BatchCheckItemResult['error_type'];
+  statusCode?: number;
+}
+
+export interface LinkRepairMirror {
+  serviceId: string;
+  url: string;
+}
+
+export interface LinkRepairRecord {
+  historyId: string;
+  fileName: string;
+  broken: LinkRepairBrokenLink[];
+  /** 同一条历史中检测有效的其他图床链接，可直接替换失效链接 */
+  suggestedMirrors: LinkRepairMirror[];
+}
+
+export interface LinkRepairPayload {
+  event: 'link-repair';
+  generatedAt: string;
+  total: number;
+  invalid: number;
+  records: LinkRepairRecord[];
+}
+
+/** 与 Rust 侧 invalid 统计口径一致：排除超时、可疑和浏览器可能可访问的结果 */
+function isBroken(result: BatchCheckItemResult): boolean {
+  return !result.is_valid
+    && result.error_type !== 'timeout'
+    && result.error_type !== 'suspicious'
+    && !result.browser_might_work;
+}
+
+/**
+ * 返回可用的 Webhook 配置；未启用或未填写地址时返回 null
+ */
+export function getActiveLinkRepairWebhook(config: UserConfig): LinkRepairWebhookConfig | null {
+  const webhook = config.linkRepairWebhook;
+  if (!webhook?.enabled || !webhook.url.trim()) return null;
+  return webhook;
+}
+
+/**
+ * 由批量检测结果组装 Webhook 负载
+ *
+ * rows 需已应用本次结果（applyResultsToRows），用于查找文件名与同条历史的有效镜像。
+ * 没有失效链接时返回 null。
+ */
+export function buildLinkRepairPayload(
+  rows: LinkCheckRow[],
+  result: BatchCheckResult,
+): LinkRepairPayload | null {
+  const records = new Map<string, LinkRepairRecord>();
+
+  for (const item of result.results) {
+    if (!item.history_id || !item.service_id || !isBroken(item)) continue;
+    let record = records.get(item.history_id);
+    if (!record) {
+      record = { historyId: item.history_id, fileName: '', broken: [], suggestedMirrors: [] };
+      records.set(item.history_id, record);
+    }
+    record.broken.push({
+      serviceId: item.service_id,
+      url: item.link,
+      errorType: item.error_type,
+      statusCode: item.status_code,
+    });
+  }
+
+  if (records.size === 0) return null;
+
+  for (const row of rows) {
+    const record = records.get(row.historyId);
+    if (!record) continue;
+    if (!record.fileName) record.fileName = row.fileName;
+    if (row.checkResult?.is_valid && !record.broken.some(b => b.serviceId === row.serviceId)) {
+      record.suggestedMirrors.push({ serviceId: row.serviceId, url: row.url });
+    }
+  }
+
+  return {
+    event: 'link-repair',
+    generatedAt: new Date().toISOString(),
+    total: result.total,
+    invalid: result.invalid,
+    records: [...records.values()],
+  };
+}
+
+/**
+ * 检测完成后发送链接修复 Webhook
+ *
+ * @returns 发送的失效记录数；未启用或无失效链接时返回 0
+ * @throws Webhook 请求失败时抛出，由调用方决定是否提示
+ */
+export async function notifyLinkRepairWebhook(
+  config: UserConfig,
+  rows: LinkCheckRow[],
+  result: BatchCheckResult,
+): Promise<number> {
+  const webhook = getActiveLinkRepairWebhook(config);
+  if (!webhook) return 0;
+
+  const payload = buildLinkRepairPayload(rows, result);
+  if (!payload) return 0;
+
+  await invoke<number>('send_link_repair_webhook', {
+    config: {
+      url: webhook.url.trim(),
+      secret: webhook.secret?.trim() || null,
+    },
+    payload,
+  });
+  log.info(`[LinkCheck] 链接修复 Webhook 已发送: ${payload.records.length} 条失效记录`);
+  return payload.records.length;
+}
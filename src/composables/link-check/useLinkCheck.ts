@@ -33,6 +33,7 @@ import {
   updateHistoryCheckStatus,
   exportCsv,
 } from './linkCheckPersistence';
+import { notifyLinkRepairWebhook } from './linkRepairWebhook';
 import { onCacheEvent } from '../../events/cacheEvents';
 
 const log = createLogger('LinkCheck');
@@ -410,6 +411,15 @@ export function useLinkCheckManager() {
           '检测完成',
           `共 ${result.total} 条：有效 ${result.valid} / 失效 ${result.invalid} / 超时 ${result.timeout} / 可疑 ${result.suspicious}`,
         );
+        // 仅完整跑完的全量检测才通知外部，避免中途取消时上报不完整的失效列表
+        if (result.invalid > 0) {
+          void loadConfig()
+            .then(config => notifyLinkRepairWebhook(config, rows, result))
+            .catch((err) => {
+              log.warn('[LinkCheck] 链接修复 Webhook 发送失败', err);
+              toast.warn('Webhook 发送失败', errorMessage(err));
+            });
+        }
       }
 
       return result;
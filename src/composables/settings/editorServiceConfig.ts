@@ -133,7 +133,16 @@ export function buildServiceConfig(service: ServerServiceType | null, fd: Settin
     case 'tencent':
       return { type: 'tencent', secret_id: fd.tencent.secretId, secret_key: fd.tencent.secretKey, region: fd.tencent.region, bucket: fd.tencent.bucket, path: fd.tencent.path, public_domain: fd.tencent.publicDomain };
     case 'aliyun':
-      return { type: 'aliyun', access_key_id: fd.aliyun.accessKeyId, access_key_secret: fd.aliyun.accessKeySecret, region: fd.aliyun.region, bucket: fd.aliyun.bucket, path: fd.aliyun.path, public_domain: fd.aliyun.publicDomain };
+      return {
+        type: 'aliyun',
+        access_key_id: fd.aliyun.accessKeyId,
+        access_key_secret: fd.aliyun.accessKeySecret,
+        region: fd.aliyun.region,
+        bucket: fd.aliyun.bucket,
+        path: fd.aliyun.path,
+        public_domain: fd.aliyun.publicDomain,
+        ...(fd.aliyun.styleSuffix?.trim() ? { style_suffix: fd.aliyun.styleSuffix.trim() } : {}),
+      };
     case 'qiniu':
       return { type: 'qiniu', access_key: fd.qiniu.accessKey, secret_key: fd.qiniu.secretKey, region: fd.qiniu.region, bucket: fd.qiniu.bucket, custom_domain: fd.qiniu.publicDomain, path: fd.qiniu.path };
     case 'upyun':
@@ -150,6 +159,7 @@ export function buildServiceConfig(service: ServerServiceType | null, fd: Settin
         bucket: profile.bucket,
         path: profile.path,
         public_domain: profile.publicDomain,
+        ...(profile.acl?.trim() ? { acl: profile.acl.trim() } : {}),
       };
     }
   }
@@ -14,7 +14,7 @@ export interface SettingsFormShape {
   weiboCookie: string;
   r2: { accountId: string; accessKeyId: string; secretAccessKey: string; bucketName: string; path: string; publicDomain: string };
   tencent: { secretId: string; secretKey: string; region: string; bucket: string; path: string; publicDomain: string };
-  aliyun: { accessKeyId: string; accessKeySecret: string; region: string; bucket: string; path: string; publicDomain: string };
+  aliyun: { accessKeyId: string; accessKeySecret: string; region: string; bucket: string; path: string; publicDomain: string; styleSuffix?: string };
   qiniu: { accessKey: string; secretKey: string; region: string; bucket: string; publicDomain: string; path: string };
   upyun: { operator: string; password: string; bucket: string; publicDomain: string; path: string };
   custom_s3_profiles: CustomS3Profile[];
@@ -14,6 +14,7 @@ import { useConfirm } from '../useConfirm';
 import { createLogger } from '../../utils/logger';
 import { extractNamiAuthToken } from '../../utils/namiAuthToken';
 import { extractErrorMessage } from '../../utils/serviceHealthMessage';
+import { saveSmmsToken } from '../../services/smms';
 import type {
   UserConfig,
   ServiceType,
@@ -24,7 +25,7 @@ import type {
   CustomS3Profile,
   LinkPrefixItem,
 } from '../../config/types';
-import { DEFAULT_CONFIG, cloneDefaultPrefixes, isCustomS3Id, makeCustomS3Id } from '../../config/types';
+import { DEFAULT_CONFIG, cloneDefaultPrefixes, isCustomS3Id, isPluginUploaderId, makeCustomS3Id } from '../../config/types';
 import { applyConfigToForm } from './settingsFormSnapshot';
 import type { SettingsFormData } from './settingsFormTypes';
 import { validateS3Config } from './s3ConfigValidation';
@@ -55,7 +56,7 @@ export function useSettingsForm() {
     weiboCookie: '',
     r2: { accountId: '', accessKeyId: '', secretAccessKey: '', bucketName: '', path: '', publicDomain: '' },
     tencent: { secretId: '', secretKey: '', region: '', bucket: '', path: '', publicDomain: '' },
-    aliyun: { accessKeyId: '', accessKeySecret: '', region: '', bucket: '', path: '', publicDomain: '' },
+    aliyun: { accessKeyId: '', accessKeySecret: '', region: '', bucket: '', path: '', publicDomain: '', styleSuffix: '' },
     qiniu: { accessKey: '', secretKey: '', region: '', bucket: '', publicDomain: '', path: '' },
     upyun: { operator: '', password: '', bucket: '', publicDomain: '', path: '' },
     custom_s3_profiles: [] as CustomS3Profile[],
@@ -220,13 +221,23 @@ export function useSettingsForm() {
     return Date.now().toString(36) + Math.random().toString(36).substring(2, 11);
   }
 
+  /** 配置中没有 Cookie 时，从登录窗口写入系统钥匙串的副本恢复 */
+  async function restoreKeychainCookie(serviceId: string): Promise<string> {
+    try {
+      return (await invoke<string | null>('get_keychain_cookie', { serviceId })) ?? '';
+    } catch (e) {
+      log.warn('读取钥匙串 Cookie 失败', e);
+      return '';
+    }
+  }
+
   // ---- 加载设置 ----
 
   async function loadSettings() {
     try {
       const config = await configStore.get<UserConfig>('config') || DEFAULT_CONFIG;
 
-      formData.value.weiboCookie = config.services?.weibo?.cookie || '';
+      formData.value.weiboCookie = config.services?.weibo?.cookie || await restoreKeychainCookie('weibo');
       formData.value.r2 = { ...formData.value.r2, ...(config.services?.r2 || {}) };
       formData.value.tencent = { ...formData.value.tencent, ...(config.services?.tencent || {}) };
       formData.value.aliyun = { ...formData.value.aliyun, ...(config.services?.aliyun || {}) };
@@ -354,10 +365,17 @@ export function useSettingsForm() {
 
     try {
       const config = await configStore.get<UserConfig>('config') || { ...DEFAULT_CONFIG };
+      const weiboCookieCleared = !!config.services?.weibo?.cookie?.trim() && !formData.value.weiboCookie.trim();
+      const smmsTokenChanged = (config.services?.smms?.token ?? '') !== (formData.value.smms.token ?? '');
+      // Cookie 变化（重新登录或手动修改）后旧的过期标记不再成立，等待下次探测
+      if ((config.services?.weibo?.cookie ?? '') !== formData.value.weiboCookie) {
+        delete config.weiboSession;
+      }
 
       config.services = {
         ...config.services,
-        weibo: { enabled: true, cookie: formData.value.weiboCookie },
+        // 轮换账号由微博卡片中的账号列表单独维护，以已保存的配置为准
+        weibo: { ...config.services?.weibo, enabled: true, cookie: formData.value.weiboCookie },
         r2: { ...formData.value.r2, enabled: true },
         tencent: { ...formData.value.tencent, enabled: true },
         aliyun: { ...formData.value.aliyun, enabled: true },
@@ -417,9 +435,14 @@ export function useSettingsForm() {
       const customS3ServiceIds = new Set(
         formData.value.custom_s3_profiles.map((profile: CustomS3Profile) => makeCustomS3Id(profile.id)),
       );
-      const syncedAvailableServices = availableServices.value.filter(
-        serviceId => !isCustomS3Id(serviceId) || customS3ServiceIds.has(serviceId),
-      );
+      // 插件图床由高级设置中的卡片单独维护，以已保存的配置为准
+      const pluginServiceIds = (config.availableServices ?? []).filter(isPluginUploaderId);
+      const syncedAvailableServices = [
+        ...availableServices.value.filter(
+          serviceId => !isPluginUploaderId(serviceId) && (!isCustomS3Id(serviceId) || customS3ServiceIds.has(serviceId)),
+        ),
+        ...pluginServiceIds,
+      ];
       config.availableServices = syncedAvailableServices;
       availableServices.value = [...syncedAvailableServices];
       config.enabledServices = (config.enabledServices ?? []).filter(
@@ -430,6 +453,20 @@ export function useSettingsForm() {
       syncCustomS3Uploaders(formData.value.custom_s3_profiles);
       serviceHealth.evaluateConfig(config);
 
+      // SM.MS Token 同步到钥匙串，注册表与 CLI 上传使用
+      if (smmsTokenChanged) {
+        await saveSmmsToken(formData.value.smms.token ?? '');
+      }
+
+      // 用户清空 Cookie 视为退出登录，同时删除钥匙串副本，避免下次加载时被恢复
+      if (weiboCookieCleared) {
+        try {
+          await invoke('delete_keychain_cookie', { serviceId: 'weibo' });
+        } catch (e) {
+          log.warn('删除钥匙串 Cookie 失败', e);
+        }
+      }
+
       if (trackAdvancedStatus) setAdvancedSaveState('saved', '已保存');
       return true;
     } catch (e) {
@@ -30,9 +30,15 @@ export const MAX_FILES_PER_UPLOAD = 200;
 
 const IMAGE_VALIDATION_CONCURRENCY = 5;
 
-interface ImageHeaderMetadata {
-  width: number;
-  height: number;
+/** stat_path 返回的路径状态 */
+interface PathStat {
+  exists: boolean;
+  kind: 'file' | 'dir' | 'symlink' | null;
+  size: number;
+  modifiedMs: number | null;
+  readable: boolean;
+  looksLikeImage: boolean;
+  format: string | null;
 }
 
 export interface FileValidationResult {
@@ -46,10 +52,11 @@ function hasValidImageExtension(filePath: string): boolean {
   return !!ext && (VALID_IMAGE_EXTENSIONS as readonly string[]).includes(ext);
 }
 
+/** 一次 IPC 确认文件存在、可读且文件头是图片（符号链接按目标判断） */
 async function canReadImageHeader(filePath: string): Promise<boolean> {
   try {
-    const metadata = await invoke<ImageHeaderMetadata>('get_image_metadata', { filePath });
-    return Number(metadata?.width) > 0 && Number(metadata?.height) > 0;
+    const stat = await invoke<PathStat>('stat_path', { path: filePath });
+    return !!stat?.exists && stat.kind !== 'dir' && stat.readable && stat.looksLikeImage;
   } catch (error) {
     log.warn('图片文件头校验失败:', filePath, error);
     return false;
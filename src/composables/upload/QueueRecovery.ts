@@ -0,0 +1,234 @@
+// 上传队列崩溃恢复：持久化未完成的队列项，下次启动时与实际状态对账
+//
+// 队列本身只在内存中，应用崩溃 / 被强制退出后进行中的上传会凭空消失：
+// 有的源文件还在、可以重新上传；有的已经传到图床但历史记录没来得及写入（或写入失败）。
+// 这里把 pending / uploading 的队列项写入 syncStatusStore 的 queueJournal，启动时逐项对账：
+// 1. 已拿到链接的图床先做上传后校验（check_image_link），有效的补写进历史记录；
+// 2. 一个图床都没传成功的，源文件仍存在则可重新上传，否则记为源文件丢失。
+
+import { watch, type WatchStopHandle } from 'vue';
+import { invoke } from '@tauri-apps/api/core';
+import { exists } from '@tauri-apps/plugin-fs';
+import type { HistoryItem } from '../../config/types';
+import type { MultiUploadResult, SingleServiceResult } from '../../core/MultiServiceUploader';
+import type { QueueItem } from '../../core/UploadQueue';
+import { historyDB } from '../../services/HistoryDatabase';
+import { syncStatusStore } from '../../store/instances';
+import type { CheckLinkResult } from '../../types/linkCheck';
+import { debounce } from '../../utils/debounce';
+import { createLogger } from '../../utils/logger';
+import { useQueueState } from '../useQueueState';
+import { useHistorySaver } from '../useHistorySaver';
+
+const log = createLogger('QueueRecovery');
+
+const JOURNAL_KEY = 'queueJournal';
+
+/** 队列变化后延迟写入日志的时间（进度更新很频繁，合并写入） */
+const JOURNAL_WRITE_DELAY_MS = 1000;
+
+/** 单个图床已拿到的上传结果 */
+export interface JournalServiceResult {
+  url: string;
+  fileKey?: string;
+}
+
+/** 日志中的未完成队列项 */
+export interface QueueJournalEntry {
+  id: string;
+  filePath: string;
+  fileName: string;
+  /** 上传开始时分配的历史记录 ID；尚未开始上传时为空 */
+  historyId?: string;
+  enabledServices: string[];
+  /** 已拿到链接的图床 */
+  completed: Record<string, JournalServiceResult>;
+}
+
+export interface QueueRecoveryReport {
+  /** 已上传但未记录、校验后补写进历史的项 */
+  recorded: QueueJournalEntry[];
+  /** 未上传成功、源文件仍存在，可重新上传的项 */
+  resumable: QueueJournalEntry[];
+  /** 未上传成功且源文件已不存在的项 */
+  missing: QueueJournalEntry[];
+}
+
+/** 对账依赖（测试时替换） */
+export interface QueueRecoveryDeps {
+  fileExists(path: string): Promise<boolean>;
+  getHistory(historyId: string): Promise<HistoryItem | null>;
+  /** 上传后校验：链接是否真实可访问 */
+  verifyLink(url: string): Promise<boolean>;
+  /** 把校验通过的结果写入历史记录（existing 为空表示记录不存在） */
+  recordResults(
+    entry: QueueJournalEntry,
+    results: SingleServiceResult[],
+    existing: HistoryItem | null
+  ): Promise<void>;
+}
+
+function isUnfinished(item: QueueItem): boolean {
+  return item.status === 'pending' || item.status === 'uploading';
+}
+
+/** 把队列项转成日志条目（只保留对账需要的字段） */
+export function toJournalEntry(item: QueueItem): QueueJournalEntry {
+  const completed: Record<string, JournalServiceResult> = {};
+  for (const serviceId of item.enabledServices) {
+    const progress = item.serviceProgress[serviceId];
+    if (!progress?.link) continue;
+    const fileKey = progress.metadata?.fileKey;
+    completed[serviceId] = {
+      url: progress.link,
+      ...(typeof fileKey === 'string' ? { fileKey } : {}),
+    };
+  }
+  return {
+    id: item.id,
+    filePath: item.filePath,
+    fileName: item.fileName,
+    ...(item.historyId ? { historyId: item.historyId } : {}),
+    enabledServices: [...item.enabledServices],
+    completed,
+  };
+}
+
+function toServiceResult(serviceId: string, result: JournalServiceResult): SingleServiceResult {
+  return {
+    serviceId,
+    status: 'success',
+    result: { serviceId, fileKey: result.fileKey ?? '', url: result.url },
+  };
+}
+
+/**
+ * 按上传后校验的结果对账日志条目
+ * 历史记录中已有的图床结果视为已记录，不重复校验
+ */
+export async function reconcileQueueJournal(
+  entries: QueueJournalEntry[],
+  deps: QueueRecoveryDeps
+): Promise<QueueRecoveryReport> {
+  const report: QueueRecoveryReport = { recorded: [], resumable: [], missing: [] };
+
+  for (const entry of entries) {
+    const existing = entry.historyId ? await deps.getHistory(entry.historyId) : null;
+    const recordedServices = new Set(
+      (existing?.results ?? [])
+        .filter(r => r.status === 'success' && r.result?.url)
+        .map(r => r.serviceId)
+    );
+
+    const verified: SingleServiceResult[] = [];
+    for (const [serviceId, result] of Object.entries(entry.completed)) {
+      if (recordedServices.has(serviceId)) continue;
+      if (await deps.verifyLink(result.url)) {
+        verified.push(toServiceResult(serviceId, result));
+      } else {
+        log.warn(`${entry.fileName} 的 ${serviceId} 链接校验未通过，视为未上传`);
+      }
+    }
+
+    if (verified.length > 0) {
+      try {
+        await deps.recordResults(entry, verified, existing);
+        report.recorded.push(entry);
+      } catch (error) {
+        log.error(`${entry.fileName} 补写历史记录失败:`, error);
+      }
+      continue;
+    }
+    // 已有记录说明至少一个图床成功过，剩余图床可在历史记录中单独重试
+    if (recordedServices.size > 0) continue;
+
+    if (await deps.fileExists(entry.filePath)) {
+      report.resumable.push(entry);
+    } else {
+      report.missing.push(entry);
+    }
+  }
+
+  return report;
+}
+
+function createDefaultDeps(): QueueRecoveryDeps {
+  const { saveHistoryItem, addResultToHistoryItem } = useHistorySaver();
+  return {
+    fileExists: (path) => exists(path).catch(() => false),
+    getHistory: (historyId) => historyDB.getById(historyId),
+    verifyLink: async (url) => {
+      try {
+        const result = await invoke<CheckLinkResult>('check_image_link', { link: url, fallbackUrl: null });
+        // 防盗链图床直接请求会 403，浏览器仍能访问即视为上传成功
+        return result.is_valid || result.browser_might_work;
+      } catch (error) {
+        log.warn('上传后校验失败:', error);
+        return false;
+      }
+    },
+    recordResults: async (entry, results, existing) => {
+      if (existing) {
+        for (const result of results) {
+          await addResultToHistoryItem(existing.id, result);
+        }
+        return;
+      }
+      // 主力图床按启用顺序选第一个校验通过的
+      const primary = entry.enabledServices
+        .map(serviceId => results.find(r => r.serviceId === serviceId))
+        .find(r => r !== undefined) ?? results[0];
+      const uploadResult: MultiUploadResult = {
+        primaryService: primary.serviceId,
+        results,
+        primaryUrl: primary.result?.url ?? '',
+      };
+      await saveHistoryItem(entry.filePath, uploadResult, entry.historyId);
+    },
+  };
+}
+
+/**
+ * 读取上次运行遗留的日志并对账，对账后清空日志
+ * 日志为空时返回 null
+ */
+export async function recoverInterruptedQueue(
+  deps: QueueRecoveryDeps = createDefaultDeps()
+): Promise<QueueRecoveryReport | null> {
+  const entries = await syncStatusStore.get<QueueJournalEntry[]>(JOURNAL_KEY);
+  if (!Array.isArray(entries) || entries.length === 0) return null;
+
+  log.info(`发现 ${entries.length} 个上次未完成的上传，开始对账`);
+  const report = await reconcileQueueJournal(entries, deps);
+  await syncStatusStore.set(JOURNAL_KEY, []);
+  log.info(
+    `对账完成: 补写 ${report.recorded.length}，可重新上传 ${report.resumable.length}，源文件丢失 ${report.missing.length}`
+  );
+  return report;
+}
+
+/**
+ * 开始把未完成的队列项写入日志（应在 recoverInterruptedQueue 之后调用，否则会覆盖遗留日志）
+ * @returns 停止监听的函数
+ */
+export function startQueueJournal(): WatchStopHandle {
+  const { queueItems } = useQueueState();
+  let lastWritten = '';
+
+  const write = debounce(() => {
+    const entries = queueItems.value.filter(isUnfinished).map(toJournalEntry);
+    const serialized = JSON.stringify(entries);
+    if (serialized === lastWritten) return;
+    lastWritten = serialized;
+    syncStatusStore.set(JOURNAL_KEY, entries).catch((error) => {
+      lastWritten = '';
+      log.warn('写入上传队列日志失败:', error);
+    });
+  }, JOURNAL_WRITE_DELAY_MS);
+
+  const stop = watch(queueItems, () => write(), { deep: true });
+  return () => {
+    stop();
+    write.cancel();
+  };
+}
@@ -1,12 +1,13 @@
 // src/composables/upload/UploadExecutor.ts
 // 上传执行器：并发调度 + 单文件多图床上传 + 历史记录联动
 
-import type { UserConfig, ServiceType } from '../../config/types';
+import type { UserConfig, ServiceType, ImageTextMeta } from '../../config/types';
 import { MultiServiceUploader, SingleServiceResult, MultiUploadResult } from '../../core/MultiServiceUploader';
 import { UploadQueueManager, type ServiceProgress } from '../../core/UploadQueue';
 import type { CopyLinkItem } from '../useCopyLink';
 import type { UploadSessionSummary } from '../../utils/uploadSummary';
 import { useServiceHealth } from '../useServiceHealth';
+import { reportWeiboAuthFailure } from '../useWeiboSession';
 import { useServiceAvailability } from '../useServiceAvailability';
 import type { useToast } from '../useToast';
 import { TOAST_MESSAGES } from '../../constants';
@@ -18,6 +19,8 @@ import {
 } from '../../constants/serviceFormats';
 import { AUTH_CONFIG_ERROR_CODES } from '../../types/serviceHealth';
 import { createLogger } from '../../utils/logger';
+import { shortenHistoryLink } from '../../services/shortLink';
+import { suggestHistoryAltText } from '../../services/altTextSuggestion';
 
 const log = createLogger('UploadExecutor');
 
@@ -29,7 +32,8 @@ export interface UploadExecutorContext {
   saveHistoryItemImmediate: (
     filePath: string,
     firstResult: SingleServiceResult,
-    historyId: string
+    historyId: string,
+    textMeta?: ImageTextMeta
   ) => Promise<void>;
   addResultToHistoryItem: (
     historyId: string,
@@ -45,7 +49,8 @@ export interface UploadExecutorContext {
     filePath: string,
     uploadResult: MultiUploadResult,
     customId?: string,
-    liveResults?: SingleServiceResult[]
+    liveResults?: SingleServiceResult[],
+    textMeta?: ImageTextMeta
   ) => Promise<string | undefined>;
   /** toast 实例（由门面在 setup 阶段注入，避免在异步函数中调用 useToast） */
   toast: ReturnType<typeof useToast>;
@@ -69,6 +74,8 @@ export async function processUploadQueue(
     /** 实际喂给 uploader 的路径，可能为压缩后的临时文件，会话结束被清理 */
     uploadFilePath: string;
     fileName: string;
+    /** 上传时填写的替代文本与标题，写入历史记录 */
+    textMeta?: ImageTextMeta;
   }>,
   config: UserConfig,
   enabledServices: string[],
@@ -96,7 +103,7 @@ export async function processUploadQueue(
   const orderedCollectedLinks: Array<CopyLinkItem | undefined> = [];
 
   // 为每个队列项创建上传任务
-  const uploadTasks = queueItems.map(({ itemId, filePath, uploadFilePath, fileName }, queueIndex) => {
+  const uploadTasks = queueItems.map(({ itemId, filePath, uploadFilePath, fileName, textMeta }, queueIndex) => {
     // itemId 在创建时已经过重复检查
     if (!itemId) {
       log.debug(`跳过无效队列项: ${fileName}`);
@@ -128,7 +135,7 @@ export async function processUploadQueue(
             historyCreating = true;
             try {
               // 立即创建历史记录（只包含当前这个成功结果）
-              await saveHistoryItemImmediate(filePath, serviceResult, historyId);
+              await saveHistoryItemImmediate(filePath, serviceResult, historyId, textMeta);
               historyCreated = true;
               historyCreating = false;
 
@@ -203,6 +210,7 @@ export async function processUploadQueue(
               const code = serviceResult.structuredError.code;
               if ((AUTH_CONFIG_ERROR_CODES as readonly string[]).includes(code)) {
                 useServiceHealth().markUploadError(serviceId, serviceResult.structuredError);
+                if (serviceId === 'weibo') reportWeiboAuthFailure();
               }
             }
           }
@@ -258,7 +266,9 @@ export async function processUploadQueue(
             );
           },
           // 单项完成回调 - 实现实时 UI 响应
-          (singleResult) => handleServiceResult(singleResult)
+          (singleResult) => handleServiceResult(singleResult),
+          // 幂等范围：该条目之后的重试不会在已成功的图床上重复上传
+          itemId
         );
 
         log.info(`${fileName} 全部完成，主力图床: ${result.primaryService}`);
@@ -269,7 +279,7 @@ export async function processUploadQueue(
         if (!historyCreated && result.primaryUrl) {
           log.warn(`[历史记录] ${fileName} 立即保存全程失败，触发兜底完整保存`);
           try {
-            await saveHistoryItem(filePath, result, historyId);
+            await saveHistoryItem(filePath, result, historyId, undefined, textMeta);
             historyCreated = true;
           } catch (fallbackError) {
             log.error(`[历史记录] ${fileName} 兜底保存仍失败，本次上传无历史记录:`, fallbackError);
@@ -308,12 +318,26 @@ export async function processUploadQueue(
           );
         }
 
+        // 短链接：记录已落库时才生成，失败不影响上传结果
+        const shortUrl = historyCreated && result.primaryUrl
+          ? await shortenHistoryLink(historyId, result.primaryUrl, result.primaryService, config)
+          : undefined;
+
+        // 替代文本建议：仅开启且未填写 alt 时生成，失败不影响上传结果
+        const altSuggestion = historyCreated && result.primaryUrl
+          ? await suggestHistoryAltText(historyId, filePath, textMeta?.altText, config)
+          : undefined;
+
         // 收集主力图床链接（用于自动复制，serviceId 用于统一前缀处理）
         if (result.primaryUrl) {
           orderedCollectedLinks[queueIndex] = {
             url: result.primaryUrl,
             fileName,
             serviceId: result.primaryService as ServiceType,
+            shortUrl,
+            altText: textMeta?.altText,
+            altSuggestion,
+            title: textMeta?.title,
           };
         }
 
@@ -5,6 +5,7 @@ import { ref, type Ref } from 'vue';
 import { invoke } from '@tauri-apps/api/core';
 import { open as dialogOpen } from '@tauri-apps/plugin-dialog';
 import type { CompressionPreset } from '../config/types';
+import { getLocalImageUrl } from '../utils/localImageUrl';
 
 /** Tauri 返回的压缩结果 */
 export interface CompressResult {
@@ -35,8 +36,10 @@ export function useCompressionTask(
   const errorMsg = ref('');
 
   // 用户在压缩中点"换一张"会发起第二次 selectAndCompress；旧 invoke 还在飞，回来时不能覆盖新结果。
-  // 自增序号充当 cancel token——回调里对不上就当作过期任务丢弃。
+  // 自增序号充当 cancel token——回调里对不上就当作过期任务丢弃；同时通知后端取消旧的压缩任务。
   let activeSeq = 0;
+  const instanceId = Math.random().toString(36).slice(2, 10);
+  const taskIdFor = (seq: number) => `compress-preview-${instanceId}-${seq}`;
 
   /** 节省百分比 */
   function getSaved(): number {
@@ -67,10 +70,13 @@ export function useCompressionTask(
   }
 
   /**
-   * 弹出文件选择框 → 执行压缩 → 加载预览 base64
+   * 弹出文件选择框 → 执行压缩 → 加载预览
    * @returns 用户是否选择了文件（false = 用户取消了选择）
    */
   async function selectAndCompress(): Promise<boolean> {
+    if (activeSeq > 0) {
+      invoke('cancel_task', { taskId: taskIdFor(activeSeq) }).catch(() => {});
+    }
     const mySeq = ++activeSeq;
     resetState();
 
@@ -100,15 +106,17 @@ export function useCompressionTask(
         );
       }
 
-      const [compressResult, origB64] = await Promise.all([
+      const [compressResult, origUrl] = await Promise.all([
         invoke<CompressResult>('compress_image', {
+          taskId: taskIdFor(mySeq),
           filePath,
           quality: p.quality,
           maxLongSide,
           outputFormat: p.outputFormat,
           stripExif: p.stripExif,
         }),
-        invoke<string>('read_image_as_base64', { filePath, maxSide: 1200 }),
+        // 原图是用户文件，直接走 picnexus-file 协议；压缩产物随后会被清理，仍读成 base64
+        getLocalImageUrl(filePath, { maxSide: 1200 }),
       ]);
 
       if (mySeq !== activeSeq) {
@@ -118,7 +126,7 @@ export function useCompressionTask(
       }
 
       result.value = compressResult;
-      originalSrc.value = origB64;
+      originalSrc.value = origUrl;
 
       const compB64 = await invoke<string>('read_image_as_base64', {
         filePath: compressResult.outputPath,
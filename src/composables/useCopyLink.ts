@@ -8,6 +8,8 @@ import { getActivePrefix } from '../config/types';
 import type { UserConfig } from '../config/types';
 import { applyPrefixTemplate } from '../utils/linkPrefixTemplate';
 import { applyZhihuSourceFromConfig } from '../utils/zhihuSource';
+import { applyWeiboCdnFromConfig } from '../uploaders/weibo/weiboCdn';
+import { applyImageTransformFromConfig } from '../utils/imageTransform';
 import { formatLink, FORMAT_NAMES, type LinkFormat } from '../utils/linkFormatter';
 import { createLogger } from '../utils/logger';
 
@@ -19,6 +21,14 @@ export interface CopyLinkItem {
   serviceId?: string;
   width?: number;
   height?: number;
+  /** 短链接（已是最终链接，存在时替代 url 且不再应用前缀变换） */
+  shortUrl?: string;
+  /** 上传时填写的替代文本（存在时替代文件名作为 alt） */
+  altText?: string;
+  /** 自动生成的替代文本建议（未填写 altText 时作为 alt） */
+  altSuggestion?: string;
+  /** 上传时填写的标题 */
+  title?: string;
 }
 
 interface CopyLinkOptions {
@@ -52,11 +62,13 @@ export function applyLinkPrefix(url: string, serviceId: string | undefined, conf
 }
 
 /**
- * 对原始 URL 应用所有配置化的 URL 变换（知乎 source、微博前缀等）。
+ * 对原始 URL 应用所有配置化的 URL 变换（知乎 source、微博 CDN 节点、微博前缀等）。
+ * CDN 节点须在前缀之前改写，前缀模板会把整条链接编码进代理地址
  */
 export function applyConfiguredUrlWithConfig(url: string, serviceId: string | undefined, config: UserConfig): string {
   const withZhihuSource = applyZhihuSourceFromConfig(url, config);
-  return applyLinkPrefix(withZhihuSource, serviceId, config);
+  const withCdn = serviceId === 'weibo' ? applyWeiboCdnFromConfig(withZhihuSource, config) : withZhihuSource;
+  return applyLinkPrefix(withCdn, serviceId, config);
 }
 
 /**
@@ -81,10 +93,14 @@ export function formatLinkWithConfig(
 ): string {
   const { format: defaultFormat, customTemplate } = getLinkFormatConfig(config);
   const finalFormat = format || defaultFormat;
-  const finalUrl = applyConfiguredUrlWithConfig(item.url, item.serviceId, config);
+  // 变换参数按复制格式追加在代理地址上；短链接已是最终链接，不再改写
+  const finalUrl = item.shortUrl
+    || applyImageTransformFromConfig(applyConfiguredUrlWithConfig(item.url, item.serviceId, config), finalFormat, config);
   return formatLink(finalUrl, item.fileName, finalFormat, customTemplate, {
     width: item.width,
     height: item.height,
+    altText: item.altText?.trim() || item.altSuggestion,
+    title: item.title,
   });
 }
 
@@ -4,7 +4,6 @@ import { ref } from 'vue';
 import { invoke } from '@tauri-apps/api/core';
 import { register, unregisterAll, isRegistered, unregister } from '@tauri-apps/plugin-global-shortcut';
 import { open as dialogOpen } from '@tauri-apps/plugin-dialog';
-import { writeText } from '@tauri-apps/plugin-clipboard-manager';
 import {
   isPermissionGranted,
   requestPermission,
@@ -22,7 +21,9 @@ import {
 import type { ServiceType } from '../config/types';
 import { MultiServiceUploader, SingleServiceResult } from '../core/MultiServiceUploader';
 import { useHistorySaver } from './useHistorySaver';
-import { formatLinkWithConfig, getLinkFormatConfig } from './useCopyLink';
+import type { CopyLinkItem } from './useCopyLink';
+import { shortenHistoryLink } from '../services/shortLink';
+import { runPostUploadAction } from '../services/postUploadAction';
 import { filterValidFiles, MAX_FILES_PER_UPLOAD, VALID_IMAGE_EXTENSIONS } from './upload/FileValidator';
 import { buildUploadSummaryToast, type UploadCopySummary } from '../utils/uploadSummary';
 import { createLogger } from '../utils/logger';
@@ -87,6 +88,8 @@ async function getFileName(filePath: string): Promise<string> {
 interface UploadResult {
   primaryUrl: string;
   primaryService: ServiceType;
+  /** 短链接（启用短链接服务且生成成功时存在） */
+  shortUrl?: string;
 }
 
 async function uploadFileInBackground(filePath: string, config: UserConfig): Promise<UploadResult | null> {
@@ -139,9 +142,13 @@ async function uploadFileInBackground(filePath: string, config: UserConfig): Pro
   );
 
   if (!result.primaryUrl) return null;
+  const shortUrl = firstSaved
+    ? await shortenHistoryLink(historyId, result.primaryUrl, result.primaryService, config)
+    : undefined;
   return {
     primaryUrl: result.primaryUrl,
     primaryService: result.primaryService as ServiceType,
+    shortUrl,
   };
 }
 
@@ -162,23 +169,23 @@ async function withUploadGuard(label: string, fn: () => Promise<void>) {
   }
 }
 
-async function formatLinkForShortcut(
-  primaryUrl: string,
-  filePath: string,
-  config: UserConfig,
-  serviceId?: string
-): Promise<string> {
-  const fileName = await getFileName(filePath);
-  return formatLinkWithConfig({ url: primaryUrl, fileName, serviceId }, config);
+async function toCopyLinkItem(uploadResult: UploadResult, filePath: string): Promise<CopyLinkItem> {
+  return {
+    url: uploadResult.primaryUrl,
+    fileName: await getFileName(filePath),
+    serviceId: uploadResult.primaryService,
+    shortUrl: uploadResult.shortUrl,
+  };
 }
 
-function createShortcutCopySummary(config: UserConfig, autoCopyEnabled: boolean): UploadCopySummary {
-  const { format } = getLinkFormatConfig(config);
+/** 执行上传后操作；结果通知由调用方发送，notify 操作不再重复 */
+async function runShortcutPostUploadAction(items: CopyLinkItem[], config: UserConfig): Promise<UploadCopySummary> {
+  const outcome = await runPostUploadAction(items, config, { notified: true });
   return {
-    autoCopyEnabled,
-    copiedCount: 0,
-    format,
-    copyFailed: false,
+    autoCopyEnabled: outcome.action === 'copyLink',
+    copiedCount: outcome.copiedCount,
+    format: outcome.format,
+    copyFailed: outcome.copyFailed,
   };
 }
 
@@ -223,19 +230,8 @@ const handleClipboardUpload = () => withUploadGuard('剪贴板上传', async ()
     const uploadResult = await uploadFileInBackground(tempFilePath, config);
     if (!uploadResult) return;
 
-    const linkOutput = config.linkOutput || DEFAULT_CONFIG.linkOutput!;
-    const autoCopyEnabled = linkOutput.autoCopy !== false;
-    const copySummary = createShortcutCopySummary(config, autoCopyEnabled);
-    const formatted = await formatLinkForShortcut(uploadResult.primaryUrl, tempFilePath, config, uploadResult.primaryService);
-    if (autoCopyEnabled) {
-      try {
-        await writeText(formatted);
-        copySummary.copiedCount = 1;
-      } catch (err) {
-        copySummary.copyFailed = true;
-        log.error('自动复制失败:', err);
-      }
-    }
+    const item = await toCopyLinkItem(uploadResult, tempFilePath);
+    const copySummary = await runShortcutPostUploadAction([item], config);
     await notifyUploadSummary(1, 1, copySummary);
   } finally {
     await cleanupClipboardTempFile(tempFilePath);
@@ -255,40 +251,25 @@ const handleFileSelectUpload = () => withUploadGuard('文件选择上传', async
   if (filePaths.length === 0) return;
 
   const config = await loadConfig();
-  const linkOutput = config.linkOutput || DEFAULT_CONFIG.linkOutput!;
-  const autoCopyEnabled = linkOutput.autoCopy !== false;
-  const copySummary = createShortcutCopySummary(config, autoCopyEnabled);
-  const allLinks: string[] = [];
+  const allLinks: CopyLinkItem[] = [];
 
   for (const filePath of filePaths) {
     try {
       const uploadResult = await uploadFileInBackground(filePath, config);
       if (uploadResult) {
-        const formatted = await formatLinkForShortcut(
-          uploadResult.primaryUrl, filePath, config, uploadResult.primaryService
-        );
-        allLinks.push(formatted);
+        allLinks.push(await toCopyLinkItem(uploadResult, filePath));
       }
     } catch (err) {
       log.error(`文件上传失败: ${filePath}`, err);
     }
   }
 
-  if (allLinks.length > 0 && autoCopyEnabled) {
-    try {
-      await writeText(allLinks.join('\n'));
-      copySummary.copiedCount = allLinks.length;
-    } catch (err) {
-      copySummary.copyFailed = true;
-      log.error('自动复制失败:', err);
-    }
-  }
-
   if (allLinks.length === 0) {
     await notify('上传失败', '所有文件上传均失败');
     return;
   }
 
+  const copySummary = await runShortcutPostUploadAction(allLinks, config);
   await notifyUploadSummary(filePaths.length, allLinks.length, copySummary);
 });
 
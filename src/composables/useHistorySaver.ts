@@ -1,12 +1,12 @@
 // 历史记录保存模块 - 管理上传历史的保存和更新
 
 import { basename } from '@tauri-apps/api/path';
-import type { HistoryItem } from '../config/types';
+import type { HistoryItem, ImageTextMeta } from '../config/types';
 import type { SingleServiceResult, MultiUploadResult } from '../core/MultiServiceUploader';
 import { historyDB } from '../services/HistoryDatabase';
 import { invalidateCache } from './useHistory';
 import { emitHistoryUpdated } from '../events/cacheEvents';
-import { getImageMetadata, clearImageMetadataCache } from './useImageMetadata';
+import { getImageMetadata, getImageExif, clearImageMetadataCache } from './useImageMetadata';
 import { createLogger } from '../utils/logger';
 
 const log = createLogger('HistorySaver');
@@ -18,13 +18,15 @@ export interface UseHistorySaverReturn {
     filePath: string,
     uploadResult: MultiUploadResult,
     customId?: string,
-    liveResults?: SingleServiceResult[]
+    liveResults?: SingleServiceResult[],
+    textMeta?: ImageTextMeta
   ): Promise<string | undefined>;
 
   saveHistoryItemImmediate(
     filePath: string,
     firstResult: SingleServiceResult,
-    historyId: string
+    historyId: string,
+    textMeta?: ImageTextMeta
   ): Promise<void>;
 
   addResultToHistoryItem(
@@ -56,6 +58,16 @@ async function getFileName(filePath: string): Promise<string> {
   return filePath.split(/[/\\]/).pop() || '未知文件';
 }
 
+/**
+ * 上传时填写的替代文本与标题：去掉首尾空白，空字符串视为未填写
+ */
+function normalizeTextMeta(textMeta?: ImageTextMeta): ImageTextMeta {
+  return {
+    altText: textMeta?.altText?.trim() || undefined,
+    title: textMeta?.title?.trim() || undefined,
+  };
+}
+
 /**
  * 同一条历史记录的追加更新队列
  * 解决并发 addResultToHistoryItem 的读改写覆盖问题
@@ -117,7 +129,8 @@ export function useHistorySaver(): UseHistorySaverReturn {
     filePath: string,
     uploadResult: MultiUploadResult,
     customId?: string,
-    liveResults?: SingleServiceResult[]
+    liveResults?: SingleServiceResult[],
+    textMeta?: ImageTextMeta
   ): Promise<string | undefined> {
     const targetId = customId || crypto.randomUUID();
 
@@ -139,7 +152,7 @@ export function useHistorySaver(): UseHistorySaverReturn {
           });
           log.info('[历史记录] 已更新:', existing.localFileName, '(retry 复用 historyId)');
         } else {
-          const metadata = await getImageMetadata(filePath);
+          const [metadata, exif] = await Promise.all([getImageMetadata(filePath), getImageExif(filePath)]);
 
           const newItem: HistoryItem = {
             id: targetId,
@@ -153,7 +166,9 @@ export function useHistorySaver(): UseHistorySaverReturn {
             height: metadata.height,
             aspectRatio: metadata.aspect_ratio,
             fileSize: metadata.file_size,
-            format: metadata.format
+            format: metadata.format,
+            captureDate: exif?.capture_date ?? undefined,
+            ...normalizeTextMeta(textMeta),
           };
 
           await historyDB.insertOrIgnore(newItem);
@@ -184,11 +199,12 @@ export function useHistorySaver(): UseHistorySaverReturn {
   async function saveHistoryItemImmediate(
     filePath: string,
     firstResult: SingleServiceResult,
-    historyId: string
+    historyId: string,
+    textMeta?: ImageTextMeta
   ): Promise<void> {
     return withHistoryUpdateQueue(historyId, async () => {
       const fileName = await getFileName(filePath);
-      const metadata = await getImageMetadata(filePath);
+      const [metadata, exif] = await Promise.all([getImageMetadata(filePath), getImageExif(filePath)]);
 
       const newItem: HistoryItem = {
         id: historyId,
@@ -202,7 +218,9 @@ export function useHistorySaver(): UseHistorySaverReturn {
         height: metadata.height,
         aspectRatio: metadata.aspect_ratio,
         fileSize: metadata.file_size,
-        format: metadata.format
+        format: metadata.format,
+        captureDate: exif?.capture_date ?? undefined,
+        ...normalizeTextMeta(textMeta),
       };
 
       await historyDB.insertOrIgnore(newItem);
@@ -89,6 +89,9 @@ export function useHistoryViewState() {
           url: result.result.url,
           fileName: detail.localFileName,
           serviceId,
+          altText: detail.altText,
+          altSuggestion: detail.altSuggestion,
+          title: detail.title,
         });
       }
 
@@ -117,6 +120,10 @@ export function useHistoryViewState() {
           url: meta.primaryUrl,
           fileName: meta.localFileName,
           serviceId: meta.primaryService,
+          shortUrl: meta.shortUrl,
+          altText: meta.altText,
+          altSuggestion: meta.altSuggestion,
+          title: meta.title,
         }));
 
       if (items.length === 0) {
@@ -0,0 +1,64 @@
+// 图床能力表：从 Rust 端读取一次后全局缓存，供设置页等 UI 展示限制说明
+
+import { ref, computed, type MaybeRefOrGetter, toValue } from 'vue';
+import { invoke } from '@tauri-apps/api/core';
+import type { HostCapabilities } from '../types/hostCapabilities';
+import { createLogger } from '../utils/logger';
+
+const log = createLogger('HostCapabilities');
+
+const capabilities = ref<Record<string, HostCapabilities>>({});
+let loadPromise: Promise<void> | null = null;
+
+function ensureLoaded(): Promise<void> {
+  if (!loadPromise) {
+    loadPromise = (async () => {
+      try {
+        const list = await invoke<HostCapabilities[]>('get_host_capabilities');
+        capabilities.value = Object.fromEntries((Array.isArray(list) ? list : []).map(c => [c.service, c]));
+      } catch (error) {
+        log.warn('读取图床能力表失败:', error);
+        // 允许下次调用重试
+        loadPromise = null;
+      }
+    })();
+  }
+  return loadPromise;
+}
+
+function formatSize(bytes: number): string {
+  return `${Math.round(bytes / 1024 / 1024)} MB`;
+}
+
+/**
+ * 生成一行能力说明，如 "单张 ≤ 15 MB · JPG/PNG/GIF"
+ */
+export function describeHostCapabilities(caps: HostCapabilities): string {
+  const parts: string[] = [];
+  if (caps.maxFileSize !== null) {
+    parts.push(caps.maxGifSize !== null
+      ? `单张 ≤ ${formatSize(caps.maxFileSize)}（GIF ≤ ${formatSize(caps.maxGifSize)}）`
+      : `单张 ≤ ${formatSize(caps.maxFileSize)}`);
+  }
+  const formats = caps.allowedFormats.filter(f => f !== 'jpeg').map(f => f.toUpperCase());
+  parts.push(formats.length > 0 ? formats.join('/') : '格式不限');
+  if (caps.supportsDelete) parts.push('支持删除');
+  if (caps.supportsMultipart) parts.push('分片上传');
+  return parts.join(' · ');
+}
+
+/**
+ * 读取指定图床的能力描述（未登记的图床返回 null）
+ */
+export function useHostCapabilities(serviceId: MaybeRefOrGetter<string>) {
+  void ensureLoaded();
+  const caps = computed(() => capabilities.value[toValue(serviceId)] ?? null);
+  const summary = computed(() => (caps.value ? describeHostCapabilities(caps.value) : ''));
+  return { capabilities: caps, summary };
+}
+
+/** 仅供测试：清空缓存 */
+export function __resetHostCapabilitiesForTests(): void {
+  capabilities.value = {};
+  loadPromise = null;
+}
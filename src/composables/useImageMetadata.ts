@@ -1,7 +1,7 @@
-// 图片元信息处理模块 - 获取和缓存图片的宽高、大小等元信息
+// 图片元信息处理模块 - 获取和缓存图片的宽高、大小等元信息，读取 EXIF 隐私字段
 
 import { invoke } from '@tauri-apps/api/core';
-import type { ImageMetadata } from '../config/types';
+import type { ImageExif, ImageMetadata } from '../config/types';
 import { Semaphore } from '../utils/semaphore';
 import { createLogger } from '../utils/logger';
 
@@ -49,7 +49,8 @@ export async function getImageMetadata(filePath: string): Promise<ImageMetadata>
       height: 0,
       aspect_ratio: 1,
       file_size: 0,
-      format: 'unknown'
+      format: 'unknown',
+      orientation: 1
     };
   }
 }
@@ -66,6 +67,60 @@ export function clearImageMetadataCache(filePath?: string): void {
   }
 }
 
+/**
+ * 读取图片 EXIF 中与隐私相关的字段（拍摄时间、相机、GPS）
+ * 非 JPEG、没有 EXIF 或读取失败时返回 null，不影响上传流程
+ * @param filePath 图片文件路径
+ */
+export async function getImageExif(filePath: string): Promise<ImageExif | null> {
+  try {
+    return await invoke<ImageExif | null>('get_image_exif', { filePath });
+  } catch (error) {
+    log.warn('读取 EXIF 失败:', filePath, error);
+    return null;
+  }
+}
+
+/**
+ * 列出去除 EXIF 时会被移除的隐私信息，如 ["拍摄时间 2024-05-01 08:30:15", "相机 Canon EOS R5", "GPS 位置"]
+ * 型号已包含厂商名时（如 "Canon EOS R5"）不再重复拼接厂商
+ */
+export function describeExifForPrivacy(exif: ImageExif | null): string[] {
+  if (!exif) return [];
+  const items: string[] = [];
+  if (exif.capture_date) {
+    items.push(`拍摄时间 ${exif.capture_date.replace('T', ' ')}`);
+  }
+  const make = exif.camera_make?.trim() ?? '';
+  const model = exif.camera_model?.trim() ?? '';
+  const camera = make && !model.toLowerCase().startsWith(make.toLowerCase())
+    ? `${make} ${model}`.trim()
+    : model || make;
+  if (camera) {
+    items.push(`相机 ${camera}`);
+  }
+  if (exif.has_gps) {
+    items.push('GPS 位置');
+  }
+  return items;
+}
+
+/**
+ * 汇总一批图片将被去除的 EXIF 隐私信息，用于去 EXIF 时的提示
+ * - 单张图片列出具体内容；多张时给出数量，并单独点出含 GPS 位置的张数
+ * - 没有任何隐私信息时返回 null
+ */
+export function summarizeExifForPrivacy(exifs: Array<ImageExif | null>): string | null {
+  const described = exifs.map(describeExifForPrivacy).filter(items => items.length > 0);
+  if (described.length === 0) return null;
+  if (described.length === 1) {
+    return `已移除：${described[0].join('、')}`;
+  }
+  const gpsCount = exifs.filter(exif => exif?.has_gps).length;
+  const gpsHint = gpsCount > 0 ? `，其中 ${gpsCount} 张含 GPS 位置` : '';
+  return `${described.length} 张图片的 EXIF 隐私信息已移除${gpsHint}`;
+}
+
 /**
  * 批量获取图片元数据（带并发控制和错误隔离）
  * 性能优化：分批获取避免同时发起大量请求
@@ -97,7 +152,8 @@ export async function fetchMetadataBatch(
           height: 0,
           aspect_ratio: 1,
           file_size: 0,
-          format: 'unknown'
+          format: 'unknown',
+          orientation: 1
         } as ImageMetadata,
         success: false as const
       };
@@ -8,6 +8,8 @@ import type { SyncStatus } from '../config/types';
 import { syncStatusStore } from '../store/instances';
 import { useServiceHealth } from './useServiceHealth';
 import { isUploading } from './uploadState';
+import { isOnline } from '../utils/networkMonitor';
+import { scheduleInterval } from '../utils/wakeScheduler';
 import { buildServiceCheckSummarySnapshot, useServiceCheckRunner } from './useServiceCheckRunner';
 import type { ServiceCheckMode } from '../types/serviceCheck';
 import type { ServiceHealthStatus } from '../types/serviceHealth';
@@ -36,7 +38,7 @@ export interface UseServiceAvailabilityReturn {
   checkJdAvailable: (forceCheck?: boolean) => Promise<void>;
   checkAllAvailabilityWithCooldown: (syncStatus?: SyncStatus) => Promise<void>;
   markServiceAvailable: (serviceId: BuiltinServiceId) => Promise<void>;
-  startPeriodicCheck: () => { intervalId: ReturnType<typeof setInterval>; stopWatch: () => void };
+  startPeriodicCheck: () => () => void;
 }
 
 // ==================== 常量 ====================
@@ -279,30 +281,33 @@ async function markServiceAvailable(serviceId: BuiltinServiceId): Promise<void>
 }
 
 /**
- * 启动周期性检测（每 12 小时）
- * 若上传期间定时触发，则推迟到上传完成后执行
+ * 启动周期性检测（每 12 小时，按墙上时间计时，系统唤醒后错开执行）
+ * 若上传期间或断网时定时触发，则推迟到上传完成、网络恢复后执行
  *
- * @returns intervalId 供 onUnmounted 清理
+ * @returns 停止检测的函数，供 onUnmounted 清理
  */
-function startPeriodicCheck(): { intervalId: ReturnType<typeof setInterval>; stopWatch: () => void } {
+function startPeriodicCheck(): () => void {
   let pendingCheck = false;
 
-  const intervalId = setInterval(async () => {
-    if (isUploading.value) {
+  const stopSchedule = scheduleInterval(() => {
+    if (isUploading.value || !isOnline.value) {
       pendingCheck = true;
       return;
     }
-    await checkAllAvailabilityWithCooldown();
+    void checkAllAvailabilityWithCooldown();
   }, CHECK_SUCCESS_COOLDOWN);
 
-  const stopWatch = watch(isUploading, async (uploading) => {
-    if (!uploading && pendingCheck) {
+  const stopWatch = watch([isUploading, isOnline], async ([uploading, online]) => {
+    if (!uploading && online && pendingCheck) {
       pendingCheck = false;
       await checkAllAvailabilityWithCooldown();
     }
   });
 
-  return { intervalId, stopWatch };
+  return () => {
+    stopSchedule();
+    stopWatch();
+  };
 }
 
 // ==================== 主 Composable ====================
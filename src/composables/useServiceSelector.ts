@@ -4,7 +4,7 @@ import { ref, computed, type Ref, type ComputedRef } from 'vue';
 import { listen, type UnlistenFn } from '@tauri-apps/api/event';
 import { configStore } from '../store/instances';
 import type { UserConfig } from '../config/types';
-import { DEFAULT_CONFIG, isCustomS3Id, makeCustomS3Id } from '../config/types';
+import { DEFAULT_CONFIG, isCustomS3Id, makeCustomS3Id, isPluginUploaderId, makePluginUploaderId } from '../config/types';
 import { useToast } from './useToast';
 import { TOAST_MESSAGES } from '../constants';
 import { CUSTOM_S3_REQUIRED_FIELDS, getRequiredFields, NO_CONFIG_SERVICES } from '../constants/serviceRequiredFields';
@@ -140,7 +140,7 @@ export function useServiceSelector(): UseServiceSelectorReturn {
     }
 
     for (const serviceId of Object.keys(serviceConfigStatus.value)) {
-      if (isCustomS3Id(serviceId)) continue;
+      if (isCustomS3Id(serviceId) || isPluginUploaderId(serviceId)) continue;
       serviceConfigStatus.value[serviceId] = (NO_CONFIG_SERVICES as readonly string[]).includes(serviceId)
         || hasAllRequiredFields(
           config.services[serviceId as keyof UserConfig['services']] as Record<string, unknown> | undefined,
@@ -149,7 +149,7 @@ export function useServiceSelector(): UseServiceSelectorReturn {
     }
 
     for (const serviceId of Object.keys(serviceConfigStatus.value)) {
-      if (isCustomS3Id(serviceId)) delete serviceConfigStatus.value[serviceId];
+      if (isCustomS3Id(serviceId) || isPluginUploaderId(serviceId)) delete serviceConfigStatus.value[serviceId];
     }
 
     // 自定义 S3 profiles
@@ -160,6 +160,11 @@ export function useServiceSelector(): UseServiceSelectorReturn {
         CUSTOM_S3_REQUIRED_FIELDS
       );
     }
+
+    // 插件图床 profiles
+    for (const profile of config.pluginUploaders ?? []) {
+      serviceConfigStatus.value[makePluginUploaderId(profile.id)] = !!profile.script?.trim();
+    }
   }
 
   /**
@@ -10,16 +10,18 @@ import {
   DEFAULT_CONFIG,
   DEFAULT_COMPRESSION_PRESET,
   isPublicRiskService,
+  type ImageTextMeta,
 } from '../config/types';
 import { UploadQueueManager } from '../core/UploadQueue';
 import { useToast } from './useToast';
-import { useCopyLink, type CopyLinkItem, type CopyLinkResult } from './useCopyLink';
+import { useCopyLink, type CopyLinkItem } from './useCopyLink';
 import { TOAST_MESSAGES } from '../constants';
-import { checkNetworkConnectivity } from '../utils/network';
+import { runPostUploadAction } from '../services/postUploadAction';
+import { checkOnline } from '../utils/networkMonitor';
 import { chunkArray } from '../utils/semaphore';
 import { useServiceSelector } from './useServiceSelector';
 import { useHistorySaver } from './useHistorySaver';
-import { fetchMetadataBatch, getImageMetadata } from './useImageMetadata';
+import { fetchMetadataBatch, getImageExif, getImageMetadata, summarizeExifForPrivacy } from './useImageMetadata';
 import { useImageCompress } from './useImageCompress';
 import { createLogger } from '../utils/logger';
 import { buildUploadSummaryToast, type UploadSessionSummary, type UploadCopySummary } from '../utils/uploadSummary';
@@ -77,8 +79,9 @@ export function useUploadManager(queueManager?: UploadQueueManager) {
   /**
    * 处理文件上传
    * @param filePaths 文件路径列表
+   * @param textMeta 替代文本与标题（可选，应用到本次上传的每个文件，写入历史记录）
    */
-  async function handleFilesUpload(filePaths: string[]): Promise<void> {
+  async function handleFilesUpload(filePaths: string[], textMeta?: ImageTextMeta): Promise<void> {
     // 防止重入：上传期间不接受新上传
     if (isUploading.value) {
       toast.showConfig('warn', { summary: '请稍候', detail: '当前有上传任务进行中，请等待完成后再试' });
@@ -208,15 +211,10 @@ export function useUploadManager(queueManager?: UploadQueueManager) {
         return;
       }
 
-      // ⭐ 异步检测网络（在处理之前）
-      const isNetworkAvailable = await checkNetworkConnectivity();
+      // ⭐ 异步检测网络（在处理之前）：断网时照常入队，上传在网络恢复前暂停
+      const isNetworkAvailable = await checkOnline();
       if (!isNetworkAvailable) {
-        toast.error(
-          '网络请求失败',
-          `${valid.length} 个文件请求超时或中断，请检查网络\n建议：检查图床配置 / 切换图床`,
-          6000
-        );
-        return;
+        toast.showConfig('warn', TOAST_MESSAGES.network.uploadPaused(valid.length));
       }
 
       // ⭐ 流水线处理：分批获取元数据 + 上传
@@ -269,6 +267,14 @@ export function useUploadManager(queueManager?: UploadQueueManager) {
             if (pathMap.size > 0) {
               actualFiles = batchFiles.map(fp => pathMap.get(fp) ?? fp);
               log.info(`批次 ${batchIndex + 1}: ${pathMap.size} 张图片已压缩`);
+              if (activePreset.stripExif) {
+                // 从原图读取，告知用户这一步具体移除了哪些隐私信息
+                const exifs = await Promise.all([...pathMap.keys()].map(getImageExif));
+                const detail = summarizeExifForPrivacy(exifs);
+                if (detail) {
+                  toast.showConfig('info', { summary: '已去除图片隐私信息', detail });
+                }
+              }
             }
           } catch (compressError) {
             log.warn(`批次 ${batchIndex + 1} 压缩失败，使用原图:`, compressError);
@@ -283,7 +289,7 @@ export function useUploadManager(queueManager?: UploadQueueManager) {
           const originalPath = batchFiles[index];
           const fileName = originalPath.split(/[/\\]/).pop() || originalPath;
           const itemId = queueManager!.addFile(originalPath, fileName, [...enabledServices]);
-          return { itemId, filePath: originalPath, uploadFilePath, fileName };
+          return { itemId, filePath: originalPath, uploadFilePath, fileName, textMeta };
         }).filter(item => item.itemId);
 
         // 同批次内被 isFileInQueue 拦截的重复路径计数（addFile 返回 null 时被 filter 过滤）
@@ -357,21 +363,16 @@ export function useUploadManager(queueManager?: UploadQueueManager) {
         )
       );
 
-      // 自动复制链接到剪贴板（使用统一 useCopyLink）
-      const autoCopyEnabled = config.linkOutput?.autoCopy !== false;
-      let copyResult: CopyLinkResult | null = null;
-      if (autoCopyEnabled && collectedLinks.length > 0) {
-        copyResult = await copyLinks(collectedLinks, {
-          showSuccessToast: false,
-          showErrorToast: false,
-        });
-      }
+      // 按设置执行上传后操作（复制链接 / 通知 / 打开链接）
+      const outcome = await runPostUploadAction(collectedLinks, config, {
+        copy: links => copyLinks(links, { showSuccessToast: false, showErrorToast: false }),
+      });
 
       showUploadSessionSummary(uploadSummary, {
-        autoCopyEnabled,
-        copiedCount: copyResult?.copiedCount ?? 0,
-        format: copyResult?.format || config.linkOutput?.defaultFormat || 'url',
-        copyFailed: autoCopyEnabled && !!copyResult && !copyResult.ok,
+        autoCopyEnabled: outcome.action === 'copyLink',
+        copiedCount: outcome.copiedCount,
+        format: outcome.format,
+        copyFailed: outcome.copyFailed,
       });
     } catch (error) {
       log.error('文件处理失败:', error);
@@ -0,0 +1,145 @@
+// 微博登录态监测 Composable
+// 定时探测微博 Cookie 是否仍有效，上传返回认证错误时立即复查；
+// 失效时写入 config.weiboSession、把健康状态标红，并提醒用户重新登录
+// 模块级单例：App.vue 启动监测，UploadExecutor 在认证失败时上报
+
+import { invoke } from '@tauri-apps/api/core';
+import { emit as tauriEmit } from '@tauri-apps/api/event';
+import { isPermissionGranted, requestPermission, sendNotification } from '@tauri-apps/plugin-notification';
+import { configStore } from '../store/instances';
+import type { UserConfig, WeiboSessionState } from '../config/types';
+import { TOAST_MESSAGES } from '../constants';
+import { useServiceHealth } from './useServiceHealth';
+import { useToast } from './useToast';
+import { createLogger } from '../utils/logger';
+import { isOnline } from '../utils/networkMonitor';
+import { scheduleInterval } from '../utils/wakeScheduler';
+
+const log = createLogger('WeiboSession');
+
+/** 定时探测间隔（6 小时） */
+const PROBE_INTERVAL_MS = 6 * 60 * 60 * 1000;
+/** 启动后首次探测延迟，避开启动时的配置同步与可用性检测 */
+const INITIAL_PROBE_DELAY_MS = 10_000;
+/** 上传失败触发探测的冷却时间，批量上传连续失败时只探测一次 */
+const FAILURE_PROBE_COOLDOWN_MS = 60_000;
+
+export const WEIBO_SESSION_EXPIRED_MESSAGE = '微博 Cookie 已过期，请重新登录';
+
+interface WeiboSessionProbe {
+  loggedIn: boolean;
+  uid?: string;
+}
+
+// ==================== 模块级共享状态（单例） ====================
+
+let notifyExpired: (() => void) | null = null;
+let probing: Promise<boolean | null> | null = null;
+let lastFailureProbeAt = 0;
+/** 每次启动只提醒一次，避免定时探测反复打扰 */
+let notifiedThisSession = false;
+
+async function saveSessionState(config: UserConfig, state: WeiboSessionState): Promise<void> {
+  const configToSave = JSON.parse(JSON.stringify(config)) as UserConfig;
+  configToSave.weiboSession = state;
+  await configStore.set('config', configToSave);
+  await configStore.save();
+  await tauriEmit('config-updated', { timestamp: Date.now(), source: 'weibo-session' });
+}
+
+async function probe(): Promise<boolean | null> {
+  const config = await configStore.get<UserConfig>('config');
+  const cookie = config?.services?.weibo?.cookie?.trim();
+  if (!config || !cookie) return null;
+
+  const result = await invoke<WeiboSessionProbe>('probe_weibo_session', { weiboCookie: cookie });
+  const previous = config.weiboSession;
+  const now = Date.now();
+
+  if (result.loggedIn) {
+    notifiedThisSession = false;
+    if (!previous || previous.expired) {
+      await saveSessionState(config, { expired: false, checkedAt: now, expiredAt: null });
+    }
+    return true;
+  }
+
+  if (!previous?.expired) {
+    await saveSessionState(config, { expired: true, checkedAt: now, expiredAt: now });
+  }
+  useServiceHealth().markTestFailed('weibo', WEIBO_SESSION_EXPIRED_MESSAGE);
+  if (!notifiedThisSession) {
+    notifiedThisSession = true;
+    notifyExpired?.();
+  }
+  return false;
+}
+
+/**
+ * 探测微博登录态
+ * @returns true 有效 / false 已过期 / null 未配置 Cookie 或探测失败（网络问题不判定为过期）
+ */
+export function checkWeiboSession(): Promise<boolean | null> {
+  if (probing) return probing;
+  probing = probe()
+    .catch((e) => {
+      log.warn('探测微博登录态失败:', e);
+      return null;
+    })
+    .finally(() => {
+      probing = null;
+    });
+  return probing;
+}
+
+/**
+ * 上传返回认证类错误时调用：复查登录态，确认过期后再提醒
+ */
+export function reportWeiboAuthFailure(): void {
+  const now = Date.now();
+  if (now - lastFailureProbeAt < FAILURE_PROBE_COOLDOWN_MS) return;
+  lastFailureProbeAt = now;
+  void checkWeiboSession();
+}
+
+async function sendSystemNotification(title: string, body: string): Promise<void> {
+  try {
+    let granted = await isPermissionGranted();
+    if (!granted) {
+      granted = (await requestPermission()) === 'granted';
+    }
+    if (granted) sendNotification({ title, body });
+  } catch (err) {
+    log.warn('系统通知发送失败:', err);
+  }
+}
+
+/**
+ * 在组件 setup 中调用（需要 Toast 上下文）
+ */
+export function useWeiboSession() {
+  const toast = useToast();
+
+  /** 启动定时探测，返回停止函数 */
+  function startMonitor(): () => void {
+    notifyExpired = () => {
+      const message = TOAST_MESSAGES.auth.sessionExpired('微博');
+      toast.showConfig('warn', message);
+      // 窗口隐藏在托盘时 Toast 不可见，补发系统通知
+      if (document.hidden) void sendSystemNotification(message.summary, message.detail ?? '');
+    };
+
+    // 断网时跳过定时探测，避免白白失败
+    const probeIfOnline = () => {
+      if (isOnline.value) void checkWeiboSession();
+    };
+    const stopSchedule = scheduleInterval(probeIfOnline, PROBE_INTERVAL_MS, INITIAL_PROBE_DELAY_MS);
+
+    return () => {
+      stopSchedule();
+      notifyExpired = null;
+    };
+  }
+
+  return { startMonitor, checkWeiboSession };
+}
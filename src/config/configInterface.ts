@@ -18,6 +18,7 @@ import type {
   QiniuServiceConfig,
   UpyunServiceConfig,
   CustomS3Profile,
+  PluginUploaderProfile,
   WebDAVConfig,
 } from './serviceTypes';
 import type { ImageCompressionConfig } from './compressionTypes';
@@ -85,7 +86,7 @@ export interface LinkPrefixConfig {
 
 /**
  * 默认前缀列表
- * 顺序：搜狗 → cdnjson → Jetpack → IPFS Scan（搜狗为默认选中）
+ * 顺序：搜狗 → cdnjson → Jetpack → IPFS Scan → weserv（搜狗为默认选中）
  */
 export const DEFAULT_LINK_PREFIXES: LinkPrefixItem[] = [
   {
@@ -104,6 +105,26 @@ export const DEFAULT_LINK_PREFIXES: LinkPrefixItem[] = [
     name: 'IPFS Scan',
     template: 'https://cdn.ipfsscan.io/weibo/{path}',
   },
+  {
+    name: 'weserv',
+    template: 'https://images.weserv.nl/?url={url_encoded}',
+  },
+];
+
+/**
+ * 代理前缀预设（设置页「从预设添加」）
+ * 自建 Worker 的域名因人而异，添加后需要用户改成自己的地址
+ */
+export const LINK_PREFIX_PRESETS: Array<LinkPrefixItem & { needsEdit?: boolean }> = [
+  {
+    name: 'weserv',
+    template: 'https://images.weserv.nl/?url={url_encoded}',
+  },
+  {
+    name: 'Cloudflare Worker',
+    template: 'https://img-proxy.example.workers.dev/?url={url_encoded}',
+    needsEdit: true,
+  },
 ];
 
 /** 返回默认前缀列表的浅拷贝（避免 Vue 响应式代理污染常量） */
@@ -127,6 +148,17 @@ export interface AppBehaviorConfig {
   closeToTray: boolean;
 }
 
+/**
+ * 时间显示配置
+ * 历史记录与队列只保存 UTC 毫秒时间戳，按这里的设置显示（Rust 命令 format_timestamps）
+ */
+export interface TimeDisplayConfig {
+  /** 语言区域（如 'zh-CN'、'en-US'），未设置时为 zh-CN */
+  locale?: string;
+  /** 时区：'system'（跟随系统）、'UTC' 或固定偏移 '+08:00'，未设置时跟随系统 */
+  timeZone?: string;
+}
+
 /**
  * 全局快捷键配置
  * 控制在任何应用中通过快捷键触发上传
@@ -151,6 +183,116 @@ export interface LinkOutputConfig {
   customTemplate: string;
   /** 上传完成后是否自动复制到剪贴板 */
   autoCopy: boolean;
+  /** 上传完成后为未填写替代文本的图片生成建议（文件名 / OCR），复制 Markdown / HTML 时作为 alt */
+  suggestAltText?: boolean;
+}
+
+/** 短链接服务类型（与 Rust 侧 ShortLinkProvider 保持一致） */
+export type ShortLinkProvider = 'yourls' | 'sink' | 'custom';
+
+/**
+ * 短链接配置
+ * 上传成功后调用短链接服务缩短主链接，复制时优先使用短链接
+ */
+export interface ShortLinkConfig {
+  /** 是否启用 */
+  enabled: boolean;
+  /** 服务类型 */
+  provider: ShortLinkProvider;
+  /** 服务地址（YOURLS 站点根地址 / Sink 站点地址 / 自建服务完整接口地址） */
+  endpoint: string;
+  /** YOURLS signature token 或 Bearer token */
+  token: string;
+  /** 自建服务响应中短链接字段（点分路径，如 data.url）；留空表示响应体即短链接 */
+  responseField?: string;
+}
+
+/** 图片代理输出格式 */
+export type ImageTransformOutput = 'webp' | 'avif' | 'jpg' | 'png';
+
+/** 图片变换参数（weserv/wsrv 的 w / q / output），未设置的项不追加 */
+export interface ImageTransformParams {
+  /** 输出宽度（像素） */
+  width?: number;
+  /** 输出质量（1-100） */
+  quality?: number;
+  /** 输出格式 */
+  format?: ImageTransformOutput;
+}
+
+/**
+ * 链接变换参数配置
+ * 按复制格式分别配置，生成指向 weserv/wsrv 代理的链接时追加变换参数
+ */
+export interface LinkTransformConfig {
+  /** 是否启用 */
+  enabled: boolean;
+  /** 各复制格式的变换参数 */
+  templates: Partial<Record<import('../utils/linkFormatter').LinkFormat, ImageTransformParams>>;
+}
+
+/**
+ * HTTP 客户端调优配置
+ * 与 Rust 侧 HttpClientTuning 保持一致；未启用时 Rust 使用内置默认值
+ */
+export interface HttpClientTuningConfig {
+  /** 是否启用自定义参数 */
+  enabled: boolean;
+  /** 每个主机最多保持的空闲连接数（0 = 不复用连接） */
+  poolMaxIdlePerHost: number;
+  /** 连接超时（秒） */
+  connectTimeoutSecs: number;
+  /** 是否允许 HTTP/2 */
+  http2: boolean;
+  /** TCP keepalive 间隔（秒，0 = 关闭） */
+  tcpKeepaliveSecs: number;
+}
+
+/**
+ * 图片解码内存预算
+ * 批量压缩 / 预览时同时在内存中的解码缓冲上限，超出后新任务排队等待
+ */
+export interface MemoryBudgetConfig {
+  /** 是否启用（关闭后不限制） */
+  enabled: boolean;
+  /** 预算上限（MB，256-16384） */
+  limitMb: number;
+}
+
+/**
+ * 链接修复 Webhook 配置
+ * 全量链接检测发现失效链接后，向该地址 POST 失效记录与建议镜像，便于外部 CI 自动修补文章
+ */
+export interface LinkRepairWebhookConfig {
+  /** 是否启用 */
+  enabled: boolean;
+  /** 接收地址 */
+  url: string;
+  /** 签名密钥（HMAC-SHA256，留空不签名） */
+  secret: string;
+}
+
+/**
+ * 微博登录态（由定时探测与上传认证失败写入）
+ */
+export interface WeiboSessionState {
+  /** Cookie 是否已失效 */
+  expired: boolean;
+  /** 最近一次探测时间 */
+  checkedAt: number | null;
+  /** 首次发现失效的时间 */
+  expiredAt: number | null;
+}
+
+/**
+ * 图床自定义请求头（覆盖 User-Agent / 追加请求头）
+ * 与 Rust 侧 RequestHeaderTemplate 保持一致；Cookie、Host、Content-* 由上传器维护，配置了也会被忽略
+ */
+export interface RequestHeaderTemplate {
+  /** 覆盖默认 User-Agent（留空使用上传器内置值） */
+  userAgent?: string;
+  /** 追加的请求头，同名时覆盖上传器默认值 */
+  headers: { name: string; value: string }[];
 }
 
 /**
@@ -215,12 +357,33 @@ export interface UserConfig {
   /** 自定义 S3 存储 profiles（多实例） */
   custom_s3_profiles?: CustomS3Profile[];
 
+  /** 插件图床 profiles（PicGo 风格脚本，复合 ID 为 plugin:xxx） */
+  pluginUploaders?: PluginUploaderProfile[];
+
   /** 微博代理模式 */
   weiboProxyMode: WeiboProxyMode;
 
+  /** 微博登录态（Cookie 失效时提示重新登录，重新登录后清除） */
+  weiboSession?: WeiboSessionState;
+
   /** 链接输出配置 */
   linkOutput?: LinkOutputConfig;
 
+  /** 短链接配置 */
+  shortLink?: ShortLinkConfig;
+
+  /** 链接变换参数配置（weserv/wsrv 代理） */
+  linkTransform?: LinkTransformConfig;
+
+  /** HTTP 客户端调优配置（连接池、HTTP/2、超时） */
+  httpClientTuning?: HttpClientTuningConfig;
+
+  /** 图片解码内存预算 */
+  memoryBudget?: MemoryBudgetConfig;
+
+  /** 链接修复 Webhook 配置 */
+  linkRepairWebhook?: LinkRepairWebhookConfig;
+
   /** 链接前缀配置（用于微博图床代理） */
   linkPrefixConfig?: LinkPrefixConfig;
 
@@ -246,6 +409,9 @@ export interface UserConfig {
   /** 全局快捷键配置 */
   globalShortcut?: GlobalShortcutConfig;
 
+  /** 时间显示配置（语言区域与时区） */
+  timeDisplay?: TimeDisplayConfig;
+
   /** 自动更新配置 */
   autoUpdate?: AutoUpdateConfig;
 
@@ -263,4 +429,10 @@ export interface UserConfig {
 
   /** MD 文档救援图床优先级（serviceId 列表，空数组 = 不限） */
   mdRescueHostPreference?: string[];
+
+  /** 各图床每分钟上传次数上限（serviceId → 次数，0 = 不限速；未设置时使用内置默认值） */
+  uploadRateLimits?: Record<string, number>;
+
+  /** 各图床的自定义请求头（serviceId → 模板，仅 Cookie 类图床生效） */
+  requestHeaderOverrides?: Record<string, RequestHeaderTemplate>;
 }
@@ -115,11 +115,40 @@ export const DEFAULT_CONFIG: UserConfig = {
     }
   },
   custom_s3_profiles: [],
+  pluginUploaders: [],
   weiboProxyMode: 'baidu-proxy',
   linkOutput: {
     defaultFormat: 'url',
     customTemplate: '{url}',
     autoCopy: true,
+    suggestAltText: false,
+  },
+  shortLink: {
+    enabled: false,
+    provider: 'yourls',
+    endpoint: '',
+    token: '',
+    responseField: '',
+  },
+  linkTransform: {
+    enabled: false,
+    templates: {},
+  },
+  httpClientTuning: {
+    enabled: false,
+    poolMaxIdlePerHost: 10,
+    connectTimeoutSecs: 10,
+    http2: true,
+    tcpKeepaliveSecs: 0,
+  },
+  memoryBudget: {
+    enabled: true,
+    limitMb: 1024,
+  },
+  linkRepairWebhook: {
+    enabled: false,
+    url: '',
+    secret: '',
   },
   linkPrefixConfig: {
     enabled: true,
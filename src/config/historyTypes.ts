@@ -104,6 +104,33 @@ export interface HistoryItem {
    * - 用户在失败项手动点「跳过」写入；可在设置页一键清空
    */
   migrationSkip?: boolean;
+
+  /**
+   * 短链接（上传完成后由短链接服务生成）
+   * - 存在时复制链接优先使用短链接，generatedLink 仍保留原始长链接
+   */
+  shortUrl?: string;
+
+  /** 替代文本（上传时填写，渲染 Markdown / HTML 链接时替代文件名） */
+  altText?: string;
+
+  /** 替代文本建议（未填写 altText 时上传后自动生成，复制 Markdown / HTML 链接时作为 alt） */
+  altSuggestion?: string;
+
+  /** 图片标题（上传时填写，渲染为 Markdown 链接标题 / HTML title 属性） */
+  title?: string;
+
+  /** 拍摄时间（取自 JPEG EXIF，ISO 8601 本地时间；无 EXIF 时为空） */
+  captureDate?: string;
+}
+
+/**
+ * 上传时填写的替代文本与标题
+ * 写入历史记录，之后复制 Markdown / HTML 链接时自动带上，不必在编辑器里重新输入
+ */
+export interface ImageTextMeta {
+  altText?: string;
+  title?: string;
 }
 
 /**
@@ -122,6 +149,25 @@ export interface ImageMetadata {
   file_size: number;
   /** 图片格式（jpg, png, webp, gif, bmp 等） */
   format: string;
+  /** EXIF 方向（1-8，1 为正常）；5-8 为旋转 90°/270°，width / height 已按显示方向交换 */
+  orientation: number;
+}
+
+/**
+ * 图片 EXIF 中与隐私相关的字段
+ * 由 Rust 后端 get_image_exif 命令返回（非 JPEG 或无 EXIF 时为 null）
+ */
+export interface ImageExif {
+  /** 拍摄时间（ISO 8601 本地时间，不含时区，如 2024-05-01T08:30:15） */
+  capture_date: string | null;
+  /** 相机厂商 */
+  camera_make: string | null;
+  /** 相机型号 */
+  camera_model: string | null;
+  /** 是否包含 GPS 位置信息 */
+  has_gps: boolean;
+  /** EXIF 方向（1-8，缺失时为 1） */
+  orientation: number;
 }
 
 /**
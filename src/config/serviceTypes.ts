@@ -25,6 +25,20 @@ export function makeCustomS3Id(profileId: string): string {
   return `custom_s3:${profileId}`;
 }
 
+// ==================== 插件图床复合 ID 工具函数 ====================
+
+export function isPluginUploaderId(id: string): boolean {
+  return id.startsWith('plugin:');
+}
+
+export function getPluginUploaderProfileId(compositeId: string): string {
+  return compositeId.slice('plugin:'.length);
+}
+
+export function makePluginUploaderId(profileId: string): string {
+  return `plugin:${profileId}`;
+}
+
 /**
  * 公共图床服务列表
  * 使用公共平台的存储服务
@@ -55,10 +69,44 @@ export interface BaseServiceConfig {
  * 微博服务配置
  */
 export interface WeiboServiceConfig extends BaseServiceConfig {
-  /** 微博 Cookie */
+  /** 微博 Cookie（主账号） */
+  cookie: string;
+  /** 额外的轮换账号（主账号之外） */
+  accounts?: WeiboAccount[];
+  /** 多账号轮换策略（默认 round-robin） */
+  rotation?: WeiboRotationStrategy;
+  /** 输出链接使用的 sinaimg CDN 节点（默认 tvax1，'auto' 按链接检测测得的延迟选择） */
+  cdnHost?: WeiboCdnSetting;
+}
+
+/**
+ * 微博轮换账号
+ */
+export interface WeiboAccount {
+  /** 唯一标识符 */
+  id: string;
+  /** 显示名称 */
+  name: string;
+  /** 账号 Cookie */
   cookie: string;
+  /** 是否参与轮换 */
+  enabled: boolean;
 }
 
+/**
+ * 微博多账号轮换策略
+ * - round-robin: 依次轮流使用
+ * - lru: 优先使用最久未用的账号
+ */
+export type WeiboRotationStrategy = 'round-robin' | 'lru';
+
+/**
+ * 微博图片 CDN 节点（xxx.sinaimg.cn 的子域名）
+ */
+export type WeiboCdnHost = 'tvax1' | 'wx1' | 'wx2' | 'wx3' | 'wx4' | 'ww1';
+
+export type WeiboCdnSetting = WeiboCdnHost | 'auto';
+
 /**
  * Cloudflare R2 服务配置
  */
@@ -263,8 +311,10 @@ export interface AliyunServiceConfig extends BaseServiceConfig {
   bucket: string;
   /** 存储路径前缀（默认 images/） */
   path: string;
-  /** 公开访问域名 */
+  /** 公开访问域名（绑定到存储桶的 CNAME） */
   publicDomain: string;
+  /** 图片处理样式后缀（如 !webp），追加到返回链接末尾 */
+  styleSuffix?: string;
 }
 
 /**
@@ -326,6 +376,25 @@ export interface CustomS3Profile {
   path: string;
   /** 公开访问域名（可选，留空则使用 endpoint 构建链接） */
   publicDomain: string;
+  /** 预设 ACL（可选，如 public-read；留空沿用存储桶策略） */
+  acl?: string;
+}
+
+/**
+ * 插件图床 Profile
+ * 以子进程执行 PicGo 风格的上传脚本：脚本接收文件路径列表，stdout 输出链接
+ */
+export interface PluginUploaderProfile {
+  /** 唯一标识符，用于构建复合 ID（plugin:xxx） */
+  id: string;
+  /** 显示名称 */
+  name: string;
+  /** 解释器或可执行文件（留空为 node） */
+  command: string;
+  /** 插件脚本路径 */
+  script: string;
+  /** 放在文件列表之前的额外参数 */
+  args: string[];
 }
 
 /**
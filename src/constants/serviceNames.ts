@@ -3,7 +3,7 @@
  */
 
 import type { ServiceType, UserConfig } from '../config/types';
-import { isCustomS3Id, getCustomS3ProfileId } from '../config/types';
+import { isCustomS3Id, getCustomS3ProfileId, isPluginUploaderId, getPluginUploaderProfileId } from '../config/types';
 
 export const SERVICE_DISPLAY_NAMES: Record<ServiceType, string> = {
   weibo: '微博',
@@ -40,7 +40,7 @@ export const SERVICE_NAME_ALIASES: Partial<Record<ServiceType, string[]>> = {
 
 /**
  * 获取服务显示名称
- * 支持内置服务和 custom_s3:profileId 复合 ID
+ * 支持内置服务、custom_s3:profileId 和 plugin:profileId 复合 ID
  */
 export function getServiceDisplayName(serviceId: string, config?: UserConfig): string {
   if (isCustomS3Id(serviceId)) {
@@ -48,6 +48,11 @@ export function getServiceDisplayName(serviceId: string, config?: UserConfig): s
     const profile = config?.custom_s3_profiles?.find(p => p.id === profileId);
     return profile?.name || `自定义 S3 (${profileId})`;
   }
+  if (isPluginUploaderId(serviceId)) {
+    const profileId = getPluginUploaderProfileId(serviceId);
+    const profile = config?.pluginUploaders?.find(p => p.id === profileId);
+    return profile?.name || `插件图床 (${profileId})`;
+  }
   return SERVICE_DISPLAY_NAMES[serviceId as ServiceType] || serviceId;
 }
 
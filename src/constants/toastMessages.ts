@@ -218,12 +218,25 @@ export const TOAST_MESSAGES = {
   network: {
     disconnected: {
       summary: '网络已断开',
-      detail: '请检查网络连接'
+      detail: '上传和定时检测已暂停，网络恢复后自动继续'
     } as StaticMessage,
     restored: {
       summary: '网络已恢复',
-      detail: '可以继续上传'
-    } as StaticMessage
+      detail: '已继续暂停的上传'
+    } as StaticMessage,
+    uploadPaused: (count: number): ToastMessageConfig => ({
+      summary: '网络已断开',
+      detail: `${count} 个文件已加入队列，网络恢复后自动上传`
+    }),
+    clockSkew: (skewSeconds: number): ToastMessageConfig => {
+      const seconds = Math.abs(skewSeconds);
+      const amount = seconds >= 120 ? `${Math.round(seconds / 60)} 分钟` : `${seconds} 秒`;
+      return {
+        summary: '系统时间偏差',
+        detail: `本机时间比服务器${skewSeconds > 0 ? '慢' : '快'}约 ${amount}，已自动校正上传签名，建议开启系统自动对时`,
+        life: 10000
+      };
+    }
   },
 
   // === 认证相关 ===
@@ -264,6 +277,11 @@ export const TOAST_MESSAGES = {
       summary: 'Cookie 已更新',
       detail: `${serviceName} Cookie 已自动填充并保存！`
     }),
+    sessionExpired: (serviceName: string): ToastMessageConfig => ({
+      summary: '登录已过期',
+      detail: `${serviceName} Cookie 已失效，请在设置中重新登录，否则上传会失败`,
+      life: 10000
+    }),
     cookieInvalid: {
       summary: 'Cookie 无效',
       detail: '接收到的 Cookie 为空'
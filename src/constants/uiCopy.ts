@@ -87,7 +87,32 @@ export const UI_COPY = {
         icon: 'pi pi-exclamation-triangle',
         acceptClass: 'p-button-danger',
       } satisfies ConfirmCopy,
+      deepLinkUpload: (paths: string[]) => ({
+        header: '外部上传请求',
+        message: `其他应用请求上传以下 ${paths.length} 个文件：\n${paths.join('\n')}`,
+        acceptLabel: '上传',
+        rejectLabel: '忽略',
+        icon: 'pi pi-link',
+      }) satisfies ConfirmCopy,
+      queueRecovery: (resumable: number, recorded: number, missing: number) => ({
+        header: '恢复未完成的上传',
+        message: [
+          `上次退出时有 ${resumable} 个文件尚未上传完成，是否重新上传？`,
+          recorded > 0 ? `另有 ${recorded} 个文件已上传成功，已补写到历史记录。` : '',
+          missing > 0 ? `${missing} 个文件的源文件已不存在，无法恢复。` : '',
+        ].filter(Boolean).join('\n'),
+        acceptLabel: '重新上传',
+        rejectLabel: '忽略',
+        icon: 'pi pi-history',
+      }) satisfies ConfirmCopy,
     },
+    crashReports: (count: number, latestMessage: string) => ({
+      header: '检测到异常退出',
+      message: `上次运行时发生了 ${count} 次内部错误，已生成崩溃报告。反馈问题时附上报告可帮助定位原因。\n\n最近一次：${latestMessage || '未知错误'}`,
+      acceptLabel: '打开报告目录',
+      rejectLabel: '忽略',
+      icon: 'pi pi-exclamation-circle',
+    }) satisfies ConfirmCopy,
   },
 
   toast: {
@@ -105,6 +130,13 @@ export const UI_COPY = {
         summary: '上传失败',
         detail: error,
       }),
+      queueRecovered: (recorded: number, missing: number) => ({
+        summary: '已恢复未完成的上传',
+        detail: [
+          recorded > 0 ? `${recorded} 个文件已上传成功，已补写到历史记录` : '',
+          missing > 0 ? `${missing} 个文件的源文件已不存在，无法恢复` : '',
+        ].filter(Boolean).join('；'),
+      }),
     },
   },
 
@@ -131,6 +163,13 @@ export const UI_COPY = {
   },
 
   dialogs: {
+    qrCode: {
+      title: '扫码打开',
+      generating: '正在生成二维码...',
+      note: '使用手机相机或微信扫一扫，即可在手机上打开图片',
+      copyLink: '复制链接',
+      failed: '二维码生成失败',
+    },
     urlDownload: {
       title: '从 URL 下载图片',
       inputLabel: '图片 URL（每行一个）',
@@ -2,7 +2,7 @@
 
 import { UploaderFactory } from '../uploaders/base/UploaderFactory';
 import { UploadResult } from '../uploaders/base/types';
-import { UserConfig, ServiceType, isCustomS3Id, getCustomS3ProfileId } from '../config/types';
+import { UserConfig, ServiceType, isCustomS3Id, getCustomS3ProfileId, isPluginUploaderId, getPluginUploaderProfileId } from '../config/types';
 import { StructuredError, UploadErrorCode, createStructuredError } from '../uploaders/base/ErrorTypes';
 import { convertToStructuredWeiboError } from '../uploaders/weibo/WeiboError';
 import { convertToStructuredR2Error } from '../uploaders/r2/R2Error';
@@ -10,6 +10,11 @@ import { convertToStructuredR2Error } from '../uploaders/r2/R2Error';
 import { convertToJDError } from '../uploaders/jd/JDError';
 import { convertToNamiError } from '../uploaders/nami/NamiError';
 import { getServiceSemaphore } from '../utils/semaphore';
+import { getServiceRateLimiter, resolveUploadRateLimit } from '../utils/rateLimiter';
+import { isOnline, recheckAfterFailure, waitUntilOnline } from '../utils/networkMonitor';
+import { isUploadQueuePaused, waitUntilQueueResumed } from '../utils/uploadPause';
+import { recordHostOutcome } from '../services/hostHealth';
+import { getCompletedUpload, runIdempotent, uploadIdempotencyKey } from '../utils/uploadIdempotency';
 import {
   SERVICE_REQUIRED_FIELDS,
   COOKIE_BASED_SERVICES,
@@ -23,11 +28,59 @@ const log = createLogger('MultiUploader');
 /** 每个图床的最大并发数 */
 const SERVICE_MAX_CONCURRENT = 2;
 
-/** 根据 serviceId 查找对应的配置对象（支持内置服务和 custom_s3:xxx） */
+/** 上传因断网失败后，等网络恢复重新上传的最多次数 */
+const NETWORK_RESUME_LIMIT = 2;
+
+/** 断网等待时的进度提示 */
+const WAITING_NETWORK_STEP = '网络已断开，恢复后自动上传...';
+
+/** 队列手动暂停时的进度提示 */
+const QUEUE_PAUSED_STEP = '队列已暂停，继续后上传...';
+
+/** 队列手动暂停期间挂起，继续后返回 */
+async function waitForQueueResume(serviceId: string, onWait?: () => void): Promise<void> {
+  if (!isUploadQueuePaused.value) return;
+  log.info(`${serviceId} 队列已暂停，等待继续后上传`);
+  onWait?.();
+  await waitUntilQueueResumed();
+}
+
+/** 断网期间挂起，网络恢复后返回 */
+async function waitForNetwork(serviceId: string, onWait?: () => void): Promise<void> {
+  if (isOnline.value) return;
+  log.info(`${serviceId} 等待网络恢复后上传`);
+  onWait?.();
+  await waitUntilOnline();
+}
+
+/**
+ * 上传失败且探测确认已断网时，等网络恢复后重新上传（最多 NETWORK_RESUME_LIMIT 次）
+ * 网络正常时的失败照常抛出，由调用方按图床错误处理
+ */
+async function uploadResumingAfterOffline<T>(
+  serviceId: string,
+  upload: () => Promise<T>,
+  onWait?: () => void
+): Promise<T> {
+  for (let resumes = 0; ; resumes++) {
+    try {
+      return await upload();
+    } catch (error) {
+      if (resumes >= NETWORK_RESUME_LIMIT || await recheckAfterFailure()) throw error;
+      log.warn(`${serviceId} 上传时网络断开，恢复后重新上传`);
+      await waitForNetwork(serviceId, onWait);
+    }
+  }
+}
+
+/** 根据 serviceId 查找对应的配置对象（支持内置服务、custom_s3:xxx 和 plugin:xxx） */
 function getServiceConfig(serviceId: string, config: UserConfig): Record<string, unknown> | undefined {
   if (isCustomS3Id(serviceId)) {
     return config.custom_s3_profiles?.find(p => p.id === getCustomS3ProfileId(serviceId)) as Record<string, unknown> | undefined;
   }
+  if (isPluginUploaderId(serviceId)) {
+    return config.pluginUploaders?.find(p => p.id === getPluginUploaderProfileId(serviceId)) as Record<string, unknown> | undefined;
+  }
   return config.services[serviceId as ServiceType] as Record<string, unknown> | undefined;
 }
 
@@ -81,6 +134,7 @@ export class MultiServiceUploader {
    * @param config 用户配置
    * @param onProgress 进度回调（每个图床独立进度）
    * @param onServiceResult 单个服务完成回调（实时通知）
+   * @param idempotencyScope 幂等范围（队列条目 ID）；传入后同一条目已上传成功的图床不再重复上传
    * @returns 多图床上传结果
    */
   async uploadToMultipleServices(
@@ -94,7 +148,8 @@ export class MultiServiceUploader {
       stepIndex?: number,
       totalSteps?: number
     ) => void,
-    onServiceResult?: (result: SingleServiceResult) => void | Promise<void>
+    onServiceResult?: (result: SingleServiceResult) => void | Promise<void>,
+    idempotencyScope?: string
   ): Promise<MultiUploadResult> {
     log.info('开始并行上传到:', enabledServices);
 
@@ -132,11 +187,27 @@ export class MultiServiceUploader {
 
     validServices.forEach((serviceId) => {
       const task = async () => {
+        const idempotencyKey = idempotencyScope ? uploadIdempotencyKey(idempotencyScope, serviceId) : undefined;
+
+        // 同一条目在该图床已上传成功（如部分失败后全量重试），复用结果，避免图床上出现重复副本
+        const reused = getCompletedUpload(idempotencyKey);
+        if (reused) {
+          log.info(`${serviceId} 已上传成功，复用上次结果`);
+          onProgress?.(serviceId, 100, '已上传（复用上次结果）', 2, 2);
+          const reusedResult: SingleServiceResult = { serviceId, result: reused, status: 'success' };
+          if (onServiceResult) {
+            await onServiceResult(reusedResult);
+          }
+          return reusedResult;
+        }
+
         // 获取该图床的信号量，限制并发数
         const semaphore = getServiceSemaphore(serviceId, SERVICE_MAX_CONCURRENT);
 
         return semaphore.withPermit(async () => {
           let taskResult: SingleServiceResult;
+          // 真正发起上传的时间（配置错误、限速排队不计入图床健康统计）
+          let uploadStartedAt: number | null = null;
 
           try {
             const uploader = UploaderFactory.create(serviceId);
@@ -157,21 +228,47 @@ export class MultiServiceUploader {
               throw new Error(`配置验证失败: ${validation.errors?.join(', ')}`);
             }
 
+            // 按每分钟上限排队，避免突发上传触发图床临时封禁
+            await getServiceRateLimiter(serviceId).acquire(
+              resolveUploadRateLimit(serviceId, safeConfig),
+              onProgress ? (waitMs) => {
+                onProgress(serviceId, 5, `限速排队中，约 ${Math.ceil(waitMs / 1000)} 秒后上传...`, 0, 2);
+              } : undefined
+            );
+
             // 配置验证通过,更新进度
             if (onProgress) {
               onProgress(serviceId, 10, '开始上传...', 1, 2);
             }
 
-            // 上传
-            const result = await uploader.upload(
-              filePath,
-              { config: serviceConfig },
-              onProgress ? (percent, step, stepIndex, totalSteps) => {
-                onProgress(serviceId, percent, step, stepIndex, totalSteps);
-              } : undefined
+            // 队列手动暂停时等待继续
+            await waitForQueueResume(
+              serviceId,
+              onProgress ? () => onProgress(serviceId, 10, QUEUE_PAUSED_STEP, 1, 2) : undefined
             );
 
+            // 断网时暂停，网络恢复后再上传
+            const onWaitNetwork = onProgress
+              ? () => onProgress(serviceId, 10, WAITING_NETWORK_STEP, 1, 2)
+              : undefined;
+            await waitForNetwork(serviceId, onWaitNetwork);
+
+            // 上传
+            uploadStartedAt = Date.now();
+            const result = await runIdempotent(idempotencyKey, () => uploadResumingAfterOffline(
+              serviceId,
+              () => uploader.upload(
+                filePath,
+                { config: serviceConfig, requestHeaders: safeConfig.requestHeaderOverrides?.[serviceId] },
+                onProgress ? (percent, step, stepIndex, totalSteps) => {
+                  onProgress(serviceId, percent, step, stepIndex, totalSteps);
+                } : undefined
+              ),
+              onWaitNetwork
+            ));
+
             log.info(`${serviceId} 上传成功`);
+            void recordHostOutcome(serviceId, true, Date.now() - uploadStartedAt);
             taskResult = {
               serviceId,
               result,
@@ -213,6 +310,9 @@ export class MultiServiceUploader {
             }
 
             log.error(`${serviceId} 上传失败:`, structuredError);
+            if (uploadStartedAt !== null) {
+              void recordHostOutcome(serviceId, false, Date.now() - uploadStartedAt, structuredError.code);
+            }
             taskResult = {
               serviceId,
               status: 'failed' as const,
@@ -284,16 +384,25 @@ export class MultiServiceUploader {
    * @param serviceId 图床ID
    * @param config 用户配置
    * @param onProgress 进度回调
+   * @param idempotencyScope 幂等范围（队列条目 ID）；该条目在此图床已上传成功时直接复用结果
    * @returns 上传结果
    */
   async retryUpload(
     filePath: string,
     serviceId: string,
     config: UserConfig,
-    onProgress?: (percent: number, step?: string, stepIndex?: number, totalSteps?: number) => void
+    onProgress?: (percent: number, step?: string, stepIndex?: number, totalSteps?: number) => void,
+    idempotencyScope?: string
   ): Promise<UploadResult> {
     log.info(`重试上传到 ${serviceId}`);
 
+    const idempotencyKey = idempotencyScope ? uploadIdempotencyKey(idempotencyScope, serviceId) : undefined;
+    const reused = getCompletedUpload(idempotencyKey);
+    if (reused) {
+      log.info(`${serviceId} 已上传成功，复用上次结果`);
+      return reused;
+    }
+
     // 使用安全副本，避免修改传入的 config 对象
     const safeConfig: UserConfig = {
       ...config,
@@ -313,12 +422,35 @@ export class MultiServiceUploader {
       throw new Error(`配置验证失败: ${validation.errors?.join(', ')}`);
     }
 
-    // 上传
-    return await uploader.upload(
-      filePath,
-      { config: serviceConfig },
-      onProgress
+    await getServiceRateLimiter(serviceId).acquire(
+      resolveUploadRateLimit(serviceId, safeConfig),
+      onProgress ? (waitMs) => {
+        onProgress(5, `限速排队中，约 ${Math.ceil(waitMs / 1000)} 秒后上传...`, 0, 2);
+      } : undefined
     );
+
+    const onWaitNetwork = onProgress ? () => onProgress(10, WAITING_NETWORK_STEP, 1, 2) : undefined;
+    await waitForNetwork(serviceId, onWaitNetwork);
+
+    // 上传
+    const startedAt = Date.now();
+    try {
+      const result = await runIdempotent(idempotencyKey, () => uploadResumingAfterOffline(
+        serviceId,
+        () => uploader.upload(
+          filePath,
+          { config: serviceConfig, requestHeaders: safeConfig.requestHeaderOverrides?.[serviceId] },
+          onProgress
+        ),
+        onWaitNetwork
+      ));
+      void recordHostOutcome(serviceId, true, Date.now() - startedAt);
+      return result;
+    } catch (error) {
+      const code = error && typeof error === 'object' && 'code' in error ? String(error.code) : undefined;
+      void recordHostOutcome(serviceId, false, Date.now() - startedAt, code ?? UploadErrorCode.UPLOAD_FAILED);
+      throw error;
+    }
   }
 
   // 常量已迁移到 src/constants/serviceRequiredFields.ts
@@ -351,6 +483,16 @@ export class MultiServiceUploader {
         return true;
       }
 
+      // 插件图床：只要求脚本路径
+      if (isPluginUploaderId(serviceId)) {
+        const profile = getServiceConfig(serviceId, config);
+        if (!(profile?.script as string | undefined)?.trim()) {
+          log.warn(`${serviceId} 插件脚本未配置，跳过`);
+          return false;
+        }
+        return true;
+      }
+
       // 无需配置的图床直接通过
       if (NO_CONFIG_SERVICES.includes(serviceId as ServiceType)) {
         return true;
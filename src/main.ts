@@ -12,7 +12,7 @@ import Tooltip from 'primevue/tooltip';
 import Ripple from 'primevue/ripple';
 
 // 上传器初始化
-import { initializeUploaders } from './uploaders';
+import { initializeUploaders, syncPluginUploaders } from './uploaders';
 
 // 配置和 Store 导入
 import { configStore } from './store/instances';
@@ -87,6 +87,7 @@ async function ensureConfigSync() {
       log.debug('已创建默认配置，启用的图床:', DEFAULT_CONFIG.enabledServices);
     } else {
       log.debug('配置已存在，启用的图床:', config.enabledServices);
+      syncPluginUploaders(config.pluginUploaders ?? []);
     }
   } catch (error) {
     log.error('配置同步失败:', error);
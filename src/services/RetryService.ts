@@ -124,7 +124,8 @@ export class RetryService {
             stepIndex,
             totalSteps
           );
-        }
+        },
+        itemId
       );
 
       // 处理成功
@@ -230,7 +231,10 @@ export class RetryService {
             stepIndex,
             totalSteps
           );
-        }
+        },
+        undefined,
+        // 部分失败后全量重试：已成功的图床复用结果，不再重复上传
+        itemId
       );
 
       // 处理成功
@@ -0,0 +1,58 @@
+// 替代文本建议服务 - 上传完成后为未填写 alt 的图片生成建议并写回历史记录
+
+import { invoke } from '@tauri-apps/api/core';
+import type { UserConfig } from '../config/types';
+import { withHistoryUpdateQueue } from '../composables/useHistorySaver';
+import { invalidateCache } from '../composables/useHistory';
+import { emitHistoryUpdated } from '../events/cacheEvents';
+import { historyDB } from './HistoryDatabase';
+import { createLogger } from '../utils/logger';
+
+const log = createLogger('AltTextSuggestion');
+
+/** 建议来源（与 Rust 侧 AltTextSuggestion.source 保持一致） */
+export type AltTextSuggestionSource = 'filename' | 'ocr';
+
+export interface AltTextSuggestion {
+  text: string;
+  source: AltTextSuggestionSource;
+}
+
+/**
+ * 生成替代文本建议（调用 Rust 侧 suggest_alt_text 命令）
+ */
+export async function suggestAltText(path: string): Promise<AltTextSuggestion | null> {
+  return invoke<AltTextSuggestion | null>('suggest_alt_text', { path });
+}
+
+/**
+ * 为历史记录生成替代文本建议并保存
+ *
+ * 仅在设置中开启且上传时未填写 alt 时生成；复制 Markdown / HTML 时作为 alt 使用。
+ * 失败只记录日志，不影响上传结果。
+ *
+ * @returns 生成的建议；未启用、已填写 alt 或失败时返回 undefined
+ */
+export async function suggestHistoryAltText(
+  historyId: string,
+  filePath: string,
+  altText: string | undefined,
+  config: UserConfig
+): Promise<string | undefined> {
+  if (!config.linkOutput?.suggestAltText || altText?.trim()) return undefined;
+
+  try {
+    const suggestion = await suggestAltText(filePath);
+    if (!suggestion) return undefined;
+
+    await withHistoryUpdateQueue(historyId, async () => {
+      await historyDB.update(historyId, { altSuggestion: suggestion.text });
+    });
+    invalidateCache();
+    await emitHistoryUpdated([historyId]);
+    return suggestion.text;
+  } catch (error) {
+    log.warn(`[替代文本] 生成建议失败: ${historyId}`, error);
+    return undefined;
+  }
+}
@@ -0,0 +1,164 @@
+// 配置预设 - 预览、确认并把团队预设的图床配置合并进本机配置
+//
+// Rust 侧只负责校验签名、剔除密钥字段与合并链接模板；图床字段由这里合并进 UserConfig，
+// 本机已有的密钥保持不变。未签名的预设若会改写已有的 endpoint / 域名 / 存储桶等字段，
+// 需先向用户展示差异并确认，再以预览时的签名调用 import_preset，避免确认后内容被替换。
+
+import { invoke } from '@tauri-apps/api/core';
+import type { CustomS3Profile, UserConfig } from '../config/types';
+import { getCustomS3ProfileId, isCustomS3Id } from '../config/serviceTypes';
+
+export interface PresetTemplate {
+  name: string;
+  template: string;
+}
+
+/** read_preset 的返回值 */
+export interface PresetPreview {
+  name: string;
+  description: string;
+  hosts: Record<string, Record<string, unknown>>;
+  linkTemplates: PresetTemplate[];
+  strippedFields: string[];
+  signed: boolean;
+  signature: string;
+}
+
+/** import_preset 的返回值 */
+export interface ImportedPreset {
+  name: string;
+  description: string;
+  hosts: Record<string, Record<string, unknown>>;
+  templatesAdded: number;
+  templatesUpdated: number;
+  strippedFields: string[];
+  signed: boolean;
+}
+
+/** 预设将改写的本机已有字段 */
+export interface PresetFieldChange {
+  hostId: string;
+  field: string;
+  before: unknown;
+  after: unknown;
+}
+
+/**
+ * 读取并校验预设（不落盘）
+ */
+export async function readPreset(source: string, passphrase?: string): Promise<PresetPreview> {
+  return invoke<PresetPreview>('read_preset', {
+    source: source.trim(),
+    passphrase: passphrase?.trim() || null,
+  });
+}
+
+/**
+ * 导入已预览的预设：合并链接模板并返回图床字段，内容与预览不一致时 Rust 侧会拒绝
+ */
+export async function importPreset(
+  source: string,
+  preview: PresetPreview,
+  passphrase?: string
+): Promise<ImportedPreset> {
+  return invoke<ImportedPreset>('import_preset', {
+    source: source.trim(),
+    passphrase: passphrase?.trim() || null,
+    expectedSignature: preview.signature,
+  });
+}
+
+function currentHostConfig(config: UserConfig, hostId: string): Record<string, unknown> | undefined {
+  if (isCustomS3Id(hostId)) {
+    const profileId = getCustomS3ProfileId(hostId);
+    const profile = config.custom_s3_profiles?.find(p => p.id === profileId);
+    return profile as unknown as Record<string, unknown> | undefined;
+  }
+  const services = config.services as Record<string, Record<string, unknown> | undefined>;
+  return services[hostId];
+}
+
+function isBlank(value: unknown): boolean {
+  return value === undefined || value === null || value === '';
+}
+
+/**
+ * 列出预设会改写的本机已有字段（本机为空的字段视为新增，不计入）
+ */
+export function diffPresetHosts(
+  config: UserConfig,
+  hosts: Record<string, Record<string, unknown>>
+): PresetFieldChange[] {
+  const changes: PresetFieldChange[] = [];
+  for (const [hostId, fields] of Object.entries(hosts)) {
+    const current = currentHostConfig(config, hostId);
+    if (!current) continue;
+    for (const [field, after] of Object.entries(fields)) {
+      const before = current[field];
+      if (isBlank(before) || JSON.stringify(before) === JSON.stringify(after)) continue;
+      changes.push({ hostId, field, before, after });
+    }
+  }
+  return changes;
+}
+
+/**
+ * 未签名且会改写已有字段的预设需要用户确认
+ */
+export function presetNeedsConfirmation(preview: PresetPreview, changes: PresetFieldChange[]): boolean {
+  return !preview.signed && changes.length > 0;
+}
+
+function formatValue(value: unknown): string {
+  if (isBlank(value)) return '（空）';
+  return typeof value === 'string' ? value : JSON.stringify(value);
+}
+
+/**
+ * 确认对话框中展示的单行差异
+ */
+export function describePresetChange(change: PresetFieldChange): string {
+  return `${change.hostId}.${change.field}: ${formatValue(change.before)} → ${formatValue(change.after)}`;
+}
+
+/**
+ * 把预设的图床字段合并进配置；预设不含密钥字段，本机密钥因此保留
+ */
+export function applyPresetHosts(
+  config: UserConfig,
+  hosts: Record<string, Record<string, unknown>>
+): UserConfig {
+  const services = { ...config.services } as Record<string, Record<string, unknown> | undefined>;
+  const profiles = [...(config.custom_s3_profiles ?? [])];
+
+  for (const [hostId, fields] of Object.entries(hosts)) {
+    if (isCustomS3Id(hostId)) {
+      const profileId = getCustomS3ProfileId(hostId);
+      const index = profiles.findIndex(p => p.id === profileId);
+      if (index >= 0) {
+        profiles[index] = { ...profiles[index], ...fields, id: profileId } as CustomS3Profile;
+      } else {
+        profiles.push({
+          name: profileId,
+          endpoint: '',
+          accessKeyId: '',
+          secretAccessKey: '',
+          region: '',
+          bucket: '',
+          path: '',
+          publicDomain: '',
+          ...fields,
+          id: profileId,
+        } as CustomS3Profile);
+      }
+      continue;
+    }
+    services[hostId] = { ...services[hostId], ...fields };
+  }
+
+  return {
+    ...config,
+    services: services as UserConfig['services'],
+    custom_s3_profiles: profiles,
+  };
+}
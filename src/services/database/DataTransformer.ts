@@ -28,6 +28,11 @@ export interface HistoryItemRow {
   success_count: number;
   successful_service_ids: string;
   migration_skip: number;
+  short_url: string | null;
+  alt_text: string | null;
+  title: string | null;
+  alt_suggestion: string | null;
+  capture_date: string | null;
 }
 
 export const ALL_COLUMNS = [
@@ -55,6 +60,11 @@ export const ALL_COLUMNS = [
   'success_count',
   'successful_service_ids',
   'migration_skip',
+  'short_url',
+  'alt_text',
+  'title',
+  'alt_suggestion',
+  'capture_date',
 ] as const;
 
 export const COLUMNS_SQL = ALL_COLUMNS.join(', ');
@@ -125,6 +135,11 @@ export function itemToRow(item: HistoryItem): HistoryItemRow {
       item.results.filter((result) => result.status === 'success').map((result) => result.serviceId),
     ),
     migration_skip: item.migrationSkip ? 1 : 0,
+    short_url: item.shortUrl || null,
+    alt_text: item.altText || null,
+    title: item.title || null,
+    alt_suggestion: item.altSuggestion || null,
+    capture_date: item.captureDate || null,
   };
 }
 
@@ -148,5 +163,10 @@ export function rowToItem(row: HistoryItemRow): HistoryItem {
     favoriteUpdatedAt: row.favorite_updated_at > 0 ? row.favorite_updated_at : undefined,
     favoriteUpdatedBy: row.favorite_updated_by || undefined,
     migrationSkip: row.migration_skip === 1,
+    shortUrl: row.short_url || undefined,
+    altText: row.alt_text || undefined,
+    title: row.title || undefined,
+    altSuggestion: row.alt_suggestion || undefined,
+    captureDate: row.capture_date || undefined,
   };
 }
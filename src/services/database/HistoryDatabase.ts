@@ -48,6 +48,7 @@ import {
 } from './TimelineQueryService';
 import { createTablesAndIndexes, runMigrations } from './SchemaManager';
 import { ConnectionManager } from './ConnectionManager';
+import { WriteLock } from './WriteLock';
 import { exportHistoryToJson, importHistoryFromJson } from './ImportExportService';
 
 // 类型重新导出
@@ -68,6 +69,14 @@ interface MetaRow {
   generated_link: string;
   results: string;
   is_favorited: number;
+  /** 仅 getMetasByIds 查询该列（批量复制需要） */
+  short_url?: string | null;
+  /** 仅 getMetasByIds 查询该列（批量复制需要） */
+  alt_text?: string | null;
+  /** 仅 getMetasByIds 查询该列（批量复制需要） */
+  title?: string | null;
+  /** 仅 getMetasByIds 查询该列（批量复制需要） */
+  alt_suggestion?: string | null;
 }
 
 export type {
@@ -95,10 +104,12 @@ function escapeLikePattern(s: string): string {
  * 历史记录数据库类
  *
  * 单例模式，确保全局只有一个数据库连接
+ * 所有写操作经 WriteLock 串行执行，读操作不受影响
  */
 class HistoryDatabase {
   private static instance: HistoryDatabase | null = null;
   private readonly connection: ConnectionManager;
+  private readonly writes = new WriteLock();
 
   private constructor() {
     this.connection = new ConnectionManager(DB_PATH, async (db) => {
@@ -151,18 +162,22 @@ class HistoryDatabase {
   // ============================================
 
   async setFavorite(id: string, favorited: boolean): Promise<void> {
-    const db = await this.connection.getDb();
-    await setFavoriteQuery(db, id, favorited, {
-      updatedAt: Date.now(),
-      updatedBy: await getSyncDeviceId(),
+    await this.writes.run(async () => {
+      const db = await this.connection.getDb();
+      await setFavoriteQuery(db, id, favorited, {
+        updatedAt: Date.now(),
+        updatedBy: await getSyncDeviceId(),
+      });
     });
   }
 
   async batchSetFavorite(ids: string[], favorited: boolean): Promise<void> {
-    const db = await this.connection.getDb();
-    await batchSetFavoriteQuery(db, ids, favorited, {
-      updatedAt: Date.now(),
-      updatedBy: await getSyncDeviceId(),
+    await this.writes.run(async () => {
+      const db = await this.connection.getDb();
+      await batchSetFavoriteQuery(db, ids, favorited, {
+        updatedAt: Date.now(),
+        updatedBy: await getSyncDeviceId(),
+      });
     });
   }
 
@@ -189,13 +204,15 @@ class HistoryDatabase {
    * 插入一条历史记录
    */
   async insert(item: HistoryItem): Promise<void> {
-    const db = await this.connection.getDb();
+    await this.writes.run(async () => {
+      const db = await this.connection.getDb();
 
-    const row = itemToRow(item);
-    await db.execute(
-      `INSERT INTO history_items (${COLUMNS_SQL}) VALUES (${columnPlaceholders()})`,
-      rowValues(row)
-    );
+      const row = itemToRow(item);
+      await db.execute(
+        `INSERT INTO history_items (${COLUMNS_SQL}) VALUES (${columnPlaceholders()})`,
+        rowValues(row)
+      );
+    });
     log.debug(`插入记录: ${item.id}`);
   }
 
@@ -205,13 +222,15 @@ class HistoryDatabase {
    * @returns 是否成功插入（false 表示记录已存在，被跳过）
    */
   async insertOrIgnore(item: HistoryItem): Promise<boolean> {
-    const db = await this.connection.getDb();
+    const result = await this.writes.run(async () => {
+      const db = await this.connection.getDb();
 
-    const row = itemToRow(item);
-    const result = await db.execute(
-      `INSERT OR IGNORE INTO history_items (${COLUMNS_SQL}) VALUES (${columnPlaceholders()})`,
-      rowValues(row)
-    );
+      const row = itemToRow(item);
+      return db.execute(
+        `INSERT OR IGNORE INTO history_items (${COLUMNS_SQL}) VALUES (${columnPlaceholders()})`,
+        rowValues(row)
+      );
+    });
 
     const inserted = result.rowsAffected > 0;
     if (inserted) {
@@ -226,10 +245,40 @@ class HistoryDatabase {
    * 更新一条历史记录
    */
   async update(id: string, updates: Partial<HistoryItem>): Promise<void> {
+    await this.writes.run(() => this.applyUpdate(id, updates));
+  }
+
+  /**
+   * 原子地读改写一条记录：在写锁内读取最新记录，由 mutator 基于它计算要更新的字段
+   *
+   * 调用方持有的记录可能已过时（上传、检测耗时较长），需要在旧值基础上追加 / 合并时使用，
+   * 避免覆盖期间其他流程写入的内容。mutator 返回 null 表示无需写入。
+   * @returns 写入后的记录；记录不存在时返回 null
+   */
+  async modify(
+    id: string,
+    mutator: (latest: HistoryItem) => Partial<HistoryItem> | null,
+  ): Promise<HistoryItem | null> {
+    return this.writes.run(async () => {
+      const latest = await this.getById(id);
+      if (!latest) return null;
+      const updates = mutator(latest);
+      if (!updates) return latest;
+      await this.applyUpdate(id, updates, latest);
+      return { ...latest, ...updates };
+    });
+  }
+
+  /** 写入部分字段（调用方需已持有写锁） */
+  private async applyUpdate(
+    id: string,
+    updates: Partial<HistoryItem>,
+    current?: HistoryItem,
+  ): Promise<void> {
     const db = await this.connection.getDb();
 
     // 先获取现有记录
-    const existing = await this.getById(id);
+    const existing = current ?? await this.getById(id);
     if (!existing) {
       throw new Error(`记录不存在: ${id}`);
     }
@@ -256,6 +305,11 @@ class HistoryDatabase {
       ['favoriteUpdatedAt', ['favorite_updated_at']],
       ['favoriteUpdatedBy', ['favorite_updated_by']],
       ['migrationSkip', ['migration_skip']],
+      ['shortUrl', ['short_url']],
+      ['altText', ['alt_text']],
+      ['title', ['title']],
+      ['altSuggestion', ['alt_suggestion']],
+      ['captureDate', ['capture_date']],
     ];
     const updateCols = fieldColumns.flatMap(([field, columns]) => (
       Object.prototype.hasOwnProperty.call(updates, field) ? columns : []
@@ -279,26 +333,28 @@ class HistoryDatabase {
    * 若目标镜像不存在或未成功上传则抛错；目标已是当前主服务则直接返回。
    */
   async switchPrimaryService(id: string, newServiceId: string): Promise<void> {
-    const existing = await this.getById(id);
-    if (!existing) {
-      throw new Error(`记录不存在: ${id}`);
-    }
-    if (existing.primaryService === newServiceId) {
-      return;
-    }
+    await this.writes.run(async () => {
+      const existing = await this.getById(id);
+      if (!existing) {
+        throw new Error(`记录不存在: ${id}`);
+      }
+      if (existing.primaryService === newServiceId) {
+        return;
+      }
 
-    const target = existing.results.find(
-      r => r.serviceId === newServiceId && r.status === 'success'
-    );
-    if (!target?.result?.url) {
-      throw new Error(`目标镜像不可用或未成功上传: ${newServiceId}`);
-    }
+      const target = existing.results.find(
+        r => r.serviceId === newServiceId && r.status === 'success'
+      );
+      if (!target?.result?.url) {
+        throw new Error(`目标镜像不可用或未成功上传: ${newServiceId}`);
+      }
 
-    await this.update(id, {
-      primaryService: newServiceId,
-      generatedLink: target.result.url,
+      await this.applyUpdate(id, {
+        primaryService: newServiceId,
+        generatedLink: target.result.url,
+      }, existing);
+      log.info(`切换主服务: ${id} → ${newServiceId}`);
     });
-    log.info(`切换主服务: ${id} → ${newServiceId}`);
   }
 
   /**
@@ -309,65 +365,71 @@ class HistoryDatabase {
    * - 删除后无剩余成功镜像 → 抛错，应改为删除整条记录
    */
   async removeMirror(id: string, serviceId: string): Promise<void> {
-    const existing = await this.getById(id);
-    if (!existing) {
-      throw new Error(`记录不存在: ${id}`);
-    }
+    await this.writes.run(async () => {
+      const existing = await this.getById(id);
+      if (!existing) {
+        throw new Error(`记录不存在: ${id}`);
+      }
 
-    const target = existing.results.find(r => r.serviceId === serviceId);
-    if (!target) {
-      throw new Error(`镜像不存在: ${serviceId}`);
-    }
+      const target = existing.results.find(r => r.serviceId === serviceId);
+      if (!target) {
+        throw new Error(`镜像不存在: ${serviceId}`);
+      }
 
-    if (existing.primaryService === serviceId) {
-      throw new Error('无法删除当前主服务镜像，请先切换到其他镜像');
-    }
+      if (existing.primaryService === serviceId) {
+        throw new Error('无法删除当前主服务镜像，请先切换到其他镜像');
+      }
 
-    const newResults = existing.results.filter(r => r.serviceId !== serviceId);
-    const remainingSuccess = newResults.filter(r => r.status === 'success').length;
-    if (remainingSuccess === 0) {
-      throw new Error('至少需要保留一条成功镜像，如需全删请直接删除整条记录');
-    }
+      const newResults = existing.results.filter(r => r.serviceId !== serviceId);
+      const remainingSuccess = newResults.filter(r => r.status === 'success').length;
+      if (remainingSuccess === 0) {
+        throw new Error('至少需要保留一条成功镜像，如需全删请直接删除整条记录');
+      }
 
-    const updates: Partial<HistoryItem> = { results: newResults };
-    let newStatus = existing.linkCheckStatus;
-    if (existing.linkCheckStatus && serviceId in existing.linkCheckStatus) {
-      const { [serviceId]: _removed, ...rest } = existing.linkCheckStatus;
-      newStatus = rest;
-      updates.linkCheckStatus = rest;
-    }
+      const updates: Partial<HistoryItem> = { results: newResults };
+      let newStatus = existing.linkCheckStatus;
+      if (existing.linkCheckStatus && serviceId in existing.linkCheckStatus) {
+        const { [serviceId]: _removed, ...rest } = existing.linkCheckStatus;
+        newStatus = rest;
+        updates.linkCheckStatus = rest;
+      }
 
-    // 与 useHistoryResultOps.stripServiceFromItem 保持一致：删镜像后同步 summary，
-    // 否则已被剥掉的镜像还会继续计入 totalLinks/validLinks/invalidLinks，直到下次
-    // 手动跑 link check 才被 linkCheckPersistence 覆盖
-    const nextSummary = recomputeLinkCheckSummary(newResults, newStatus, existing.linkCheckSummary);
-    if (nextSummary) {
-      updates.linkCheckSummary = nextSummary;
-    }
+      // 与 useHistoryResultOps.stripServiceFromItem 保持一致：删镜像后同步 summary，
+      // 否则已被剥掉的镜像还会继续计入 totalLinks/validLinks/invalidLinks，直到下次
+      // 手动跑 link check 才被 linkCheckPersistence 覆盖
+      const nextSummary = recomputeLinkCheckSummary(newResults, newStatus, existing.linkCheckSummary);
+      if (nextSummary) {
+        updates.linkCheckSummary = nextSummary;
+      }
 
-    await this.update(id, updates);
-    log.info(`删除镜像: ${id} / ${serviceId}`);
+      await this.applyUpdate(id, updates, existing);
+      log.info(`删除镜像: ${id} / ${serviceId}`);
+    });
   }
 
   /**
    * 插入或更新一条记录（UPSERT）
    */
   async upsert(item: HistoryItem): Promise<void> {
-    const db = await this.connection.getDb();
-    const row = itemToRow(item);
+    await this.writes.run(async () => {
+      const db = await this.connection.getDb();
+      const row = itemToRow(item);
 
-    await db.execute(
-      `INSERT OR REPLACE INTO history_items (${COLUMNS_SQL}) VALUES (${columnPlaceholders()})`,
-      rowValues(row)
-    );
+      await db.execute(
+        `INSERT OR REPLACE INTO history_items (${COLUMNS_SQL}) VALUES (${columnPlaceholders()})`,
+        rowValues(row)
+      );
+    });
   }
 
   /**
    * 删除一条历史记录
    */
   async delete(id: string): Promise<void> {
-    const db = await this.connection.getDb();
-    await db.execute('DELETE FROM history_items WHERE id = $1', [id]);
+    await this.writes.run(async () => {
+      const db = await this.connection.getDb();
+      await db.execute('DELETE FROM history_items WHERE id = $1', [id]);
+    });
     log.debug(`删除记录: ${id}`);
   }
 
@@ -377,10 +439,12 @@ class HistoryDatabase {
   async deleteMany(ids: string[]): Promise<void> {
     if (ids.length === 0) return;
 
-    const db = await this.connection.getDb();
-    // 生成 $1, $2, $3... 占位符
-    const placeholders = ids.map((_, i) => `$${i + 1}`).join(',');
-    await db.execute(`DELETE FROM history_items WHERE id IN (${placeholders})`, ids);
+    await this.writes.run(async () => {
+      const db = await this.connection.getDb();
+      // 生成 $1, $2, $3... 占位符
+      const placeholders = ids.map((_, i) => `$${i + 1}`).join(',');
+      await db.execute(`DELETE FROM history_items WHERE id IN (${placeholders})`, ids);
+    });
     log.info(`批量删除 ${ids.length} 条记录`);
   }
 
@@ -388,8 +452,10 @@ class HistoryDatabase {
    * 清空所有历史记录
    */
   async clear(): Promise<void> {
-    const db = await this.connection.getDb();
-    await db.execute('DELETE FROM history_items');
+    await this.writes.run(async () => {
+      const db = await this.connection.getDb();
+      await db.execute('DELETE FROM history_items');
+    });
     log.info('已清空所有记录');
   }
 
@@ -589,7 +655,9 @@ class HistoryDatabase {
   }
 
   async getItemsByIds(ids: string[]): Promise<HistoryItem[]> { return getItemsByIdsQuery(await this.connection.getDb(), ids); }
-  async setMigrationSkip(id: string, skip: boolean): Promise<void> { await setMigrationSkipQuery(await this.connection.getDb(), id, skip); }
+  async setMigrationSkip(id: string, skip: boolean): Promise<void> {
+    await this.writes.run(async () => setMigrationSkipQuery(await this.connection.getDb(), id, skip));
+  }
 
   /**
    * 获取所有时间段的统计信息（轻量级查询）
@@ -703,7 +771,8 @@ class HistoryDatabase {
     const placeholders = ids.map((_, i) => `$${i + 1}`).join(',');
     const rows = await db.select<MetaRow[]>(
       `SELECT id, timestamp, local_file_name, aspect_ratio,
-              primary_service, generated_link, results, is_favorited
+              primary_service, generated_link, results, is_favorited, short_url,
+              alt_text, title, alt_suggestion
        FROM history_items WHERE id IN (${placeholders})
        ORDER BY timestamp DESC, id DESC`,
       ids,
@@ -778,6 +847,10 @@ class HistoryDatabase {
       primaryFileKey,
       mirrorServices,
       isFavorited: row.is_favorited === 1,
+      shortUrl: row.short_url || undefined,
+      altText: row.alt_text || undefined,
+      title: row.title || undefined,
+      altSuggestion: row.alt_suggestion || undefined,
     };
   }
 
@@ -875,8 +948,10 @@ class HistoryDatabase {
       linkCheckSummary: string;
     }>,
   ): Promise<void> {
-    const db = await this.connection.getDb();
-    await batchUpdateLinkCheckStatusQuery(db, updates);
+    await this.writes.run(async () => {
+      const db = await this.connection.getDb();
+      await batchUpdateLinkCheckStatusQuery(db, updates);
+    });
   }
 
   /**
@@ -915,8 +990,10 @@ class HistoryDatabase {
     mergeStrategy: 'replace' | 'merge',
     onProgress?: (current: number, total: number) => void,
   ): Promise<number> {
-    const db = await this.connection.getDb();
-    return importHistoryFromJson(db, json, mergeStrategy, onProgress);
+    return this.writes.run(async () => {
+      const db = await this.connection.getDb();
+      return importHistoryFromJson(db, json, mergeStrategy, onProgress);
+    });
   }
 
   // ============================================
@@ -924,8 +1001,10 @@ class HistoryDatabase {
   // ============================================
 
   async addSyncLog(entry: SyncLogEntry): Promise<void> {
-    const db = await this.connection.getDb();
-    await addSyncLogQuery(db, entry);
+    await this.writes.run(async () => {
+      const db = await this.connection.getDb();
+      await addSyncLogQuery(db, entry);
+    });
   }
 
   async getSyncLogs(limit = 20): Promise<SyncLogEntry[]> {
@@ -934,8 +1013,10 @@ class HistoryDatabase {
   }
 
   async clearSyncLogs(): Promise<void> {
-    const db = await this.connection.getDb();
-    await clearSyncLogsQuery(db);
+    await this.writes.run(async () => {
+      const db = await this.connection.getDb();
+      await clearSyncLogsQuery(db);
+    });
   }
 }
 
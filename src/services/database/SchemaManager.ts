@@ -43,7 +43,12 @@ export async function createTablesAndIndexes(db: Database): Promise<void> {
       success_count INTEGER NOT NULL DEFAULT 0,
       successful_service_ids TEXT NOT NULL DEFAULT '[]',
       migration_skip INTEGER NOT NULL DEFAULT 0,
-      link_check_skip INTEGER NOT NULL DEFAULT 0
+      link_check_skip INTEGER NOT NULL DEFAULT 0,
+      short_url TEXT,
+      alt_text TEXT,
+      title TEXT,
+      alt_suggestion TEXT,
+      capture_date TEXT
     )
   `);
 
@@ -100,6 +105,10 @@ export async function runMigrations(db: Database): Promise<void> {
   await migrateAddSuccessfulServiceIdsColumn(db);
   await migrateAddMigrationSkipColumn(db);
   await migrateAddLinkCheckSkipColumn(db);
+  await migrateAddShortUrlColumn(db);
+  await migrateAddTextMetaColumns(db);
+  await migrateAddAltSuggestionColumn(db);
+  await migrateAddCaptureDateColumn(db);
 }
 
 /**
@@ -290,3 +299,85 @@ async function migrateAddLinkCheckSkipColumn(db: Database): Promise<void> {
     throw error;
   }
 }
+
+/**
+ * 迁移：添加 short_url 列（幂等）
+ * 短链接服务生成的短链，旧记录为 NULL
+ */
+async function migrateAddShortUrlColumn(db: Database): Promise<void> {
+  try {
+    if (await addColumnIfMissing(
+      db,
+      'short_url',
+      `ALTER TABLE history_items ADD COLUMN short_url TEXT`
+    )) {
+      log.info('迁移完成：添加 short_url 列');
+    }
+  } catch (error) {
+    log.error('迁移 short_url 列失败:', error);
+    throw error;
+  }
+}
+
+/**
+ * 迁移：添加 alt_text / title 列（幂等）
+ * 上传时填写的替代文本与标题，旧记录为 NULL
+ */
+async function migrateAddTextMetaColumns(db: Database): Promise<void> {
+  try {
+    const addedAlt = await addColumnIfMissing(
+      db,
+      'alt_text',
+      `ALTER TABLE history_items ADD COLUMN alt_text TEXT`
+    );
+    const addedTitle = await addColumnIfMissing(
+      db,
+      'title',
+      `ALTER TABLE history_items ADD COLUMN title TEXT`
+    );
+    if (addedAlt || addedTitle) {
+      log.info('迁移完成：添加 alt_text / title 列');
+    }
+  } catch (error) {
+    log.error('迁移 alt_text / title 列失败:', error);
+    throw error;
+  }
+}
+
+/**
+ * 迁移：添加 alt_suggestion 列（幂等）
+ * 上传后自动生成的替代文本建议，旧记录为 NULL
+ */
+async function migrateAddAltSuggestionColumn(db: Database): Promise<void> {
+  try {
+    if (await addColumnIfMissing(
+      db,
+      'alt_suggestion',
+      `ALTER TABLE history_items ADD COLUMN alt_suggestion TEXT`
+    )) {
+      log.info('迁移完成：添加 alt_suggestion 列');
+    }
+  } catch (error) {
+    log.error('迁移 alt_suggestion 列失败:', error);
+    throw error;
+  }
+}
+
+/**
+ * 迁移：添加 capture_date 列（幂等）
+ * 上传时从 JPEG EXIF 读取的拍摄时间，旧记录与无 EXIF 的图片为 NULL
+ */
+async function migrateAddCaptureDateColumn(db: Database): Promise<void> {
+  try {
+    if (await addColumnIfMissing(
+      db,
+      'capture_date',
+      `ALTER TABLE history_items ADD COLUMN capture_date TEXT`
+    )) {
+      log.info('迁移完成：添加 capture_date 列');
+    }
+  } catch (error) {
+    log.error('迁移 capture_date 列失败:', error);
+    throw error;
+  }
+}
@@ -0,0 +1,42 @@
+/**
+ * 历史记录单写者锁
+ *
+ * tauri-plugin-sql 基于 sqlx 连接池，BEGIN/COMMIT 无法跨调用保持在同一连接上（见 SchemaManager），
+ * 所以「读 → 合并 → 写」不能靠 SQLite 事务保证原子性。上传队列、修复流程、历史界面同时改同一条
+ * 记录时，两个写入都基于同一份旧数据合并，后写的会把先写的 results / linkCheckStatus 整字段覆盖。
+ *
+ * 这里把所有写操作排成一条队列：
+ * - 窗口内按调用顺序串行执行（Promise 链，前一个失败不影响后续）；
+ * - 支持 Web Locks 时再按锁名跨窗口互斥（多个窗口共用同一个数据库文件）。
+ *
+ * 单条 SQL 的锁等待由 sqlx 默认的 busy_timeout（5 秒）处理，这里不再重复设置。
+ * 注意：任务内不能再调用同一把锁保护的写方法，否则会互相等待。
+ */
+
+/** 跨窗口共用的锁名 */
+export const HISTORY_WRITE_LOCK = 'picnexus-history-write';
+
+function defaultLockManager(): LockManager | null {
+  return typeof navigator !== 'undefined' && navigator.locks ? navigator.locks : null;
+}
+
+export class WriteLock {
+  private tail: Promise<unknown> = Promise.resolve();
+
+  constructor(
+    private readonly name: string = HISTORY_WRITE_LOCK,
+    private readonly locks: LockManager | null = defaultLockManager(),
+  ) {}
+
+  /** 在锁内执行写任务，返回任务结果 */
+  run<T>(task: () => Promise<T>): Promise<T> {
+    const result = this.tail.then(() => this.acquire(task));
+    this.tail = result.catch(() => undefined);
+    return result;
+  }
+
+  private acquire<T>(task: () => Promise<T>): Promise<T> {
+    if (!this.locks) return task();
+    return this.locks.request(this.name, task) as Promise<T>;
+  }
+}
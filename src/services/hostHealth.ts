@@ -0,0 +1,53 @@
+// 图床健康记分板 - 上传结果上报与健康快照读取（统计保存在 Rust 端内存中）
+
+import { invoke } from '@tauri-apps/api/core';
+import type { HostHealth } from '../types/hostHealth';
+import { createLogger } from '../utils/logger';
+
+const log = createLogger('HostHealth');
+
+/**
+ * 上报一次上传结果（失败不影响上传流程）
+ */
+export async function recordHostOutcome(
+  service: string,
+  ok: boolean,
+  latencyMs: number,
+  errorKind?: string,
+): Promise<void> {
+  try {
+    await invoke('record_host_outcome', {
+      service,
+      ok,
+      latencyMs: Math.max(0, Math.round(latencyMs)),
+      errorKind: ok ? null : errorKind ?? null,
+    });
+  } catch (error) {
+    log.debug('上报图床健康数据失败:', error);
+  }
+}
+
+/**
+ * 读取各图床健康快照（最健康的在前）
+ */
+export async function getHostHealth(): Promise<HostHealth[]> {
+  const list = await invoke<HostHealth[]>('get_host_health');
+  return Array.isArray(list) ? list : [];
+}
+
+/**
+ * 生成一行健康说明，如 "近 1 小时 12 次上传 · 成功率 92% · 平均 1.4 s"
+ */
+export function describeHostHealth(health: HostHealth): string {
+  const parts = [
+    `近 1 小时 ${health.attempts} 次上传`,
+    `成功率 ${Math.round(health.successRate * 100)}%`,
+  ];
+  if (health.avgLatencyMs !== null) {
+    parts.push(`平均 ${(health.avgLatencyMs / 1000).toFixed(1)} s`);
+  }
+  if (health.recentErrors.length > 0) {
+    parts.push(`最近错误 ${health.recentErrors[0]}`);
+  }
+  return parts.join(' · ');
+}
@@ -0,0 +1,66 @@
+// Markdown 发布上传的历史记录
+//
+// process_markdown 在 Rust 侧直接上传图片，不经过前端上传队列；每张上传成功的图片
+// 发出 md-process://uploaded 事件，这里转换成队列同样的 MultiUploadResult，
+// 通过 saveHistoryItem 写入历史记录，之后可照常检测链接、迁移和删除。
+
+import { listen, type UnlistenFn } from '@tauri-apps/api/event';
+import type { MultiUploadResult } from '../core/MultiServiceUploader';
+import { useHistorySaver } from '../composables/useHistorySaver';
+import { createLogger } from '../utils/logger';
+
+const log = createLogger('MarkdownHistory');
+
+/** 后端事件名（与 md_processor.rs 保持一致） */
+export const MD_UPLOADED_EVENT = 'md-process://uploaded';
+
+/** md-process://uploaded 事件载荷 */
+export interface MarkdownImageUploaded {
+  localPath: string;
+  serviceId: string;
+  url: string;
+}
+
+/**
+ * 从链接推导文件标识：取 URL 路径（去掉开头的 /），无法解析时退回完整链接
+ */
+function fileKeyFromUrl(url: string): string {
+  try {
+    const key = decodeURIComponent(new URL(url).pathname).replace(/^\/+/, '');
+    return key || url;
+  } catch {
+    return url;
+  }
+}
+
+/**
+ * 把单张图片的上传结果转换为上传队列使用的 MultiUploadResult
+ */
+export function toMultiUploadResult(payload: MarkdownImageUploaded): MultiUploadResult {
+  const { serviceId, url } = payload;
+  return {
+    primaryService: serviceId,
+    primaryUrl: url,
+    results: [
+      {
+        serviceId,
+        status: 'success',
+        result: { serviceId, fileKey: fileKeyFromUrl(url), url },
+      },
+    ],
+  };
+}
+
+/**
+ * 开始监听 Markdown 发布的上传事件并写入历史记录
+ * @returns 停止监听的函数
+ */
+export async function startMarkdownHistoryListener(): Promise<UnlistenFn> {
+  const { saveHistoryItem } = useHistorySaver();
+  return listen<MarkdownImageUploaded>(MD_UPLOADED_EVENT, (event) => {
+    const payload = event.payload;
+    saveHistoryItem(payload.localPath, toMultiUploadResult(payload)).catch((e) =>
+      log.warn('写入 Markdown 上传历史失败:', e)
+    );
+  });
+}
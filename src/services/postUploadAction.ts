@@ -0,0 +1,138 @@
+// 上传后操作服务 - 上传完成后按设置复制链接 / 显示通知 / 打开链接
+//
+// 设置保存在 Rust 端（post-upload-action.json），CLI 上传共用同一份；
+// 主窗口上传队列与全局快捷键上传完成后都调用 runPostUploadAction。
+
+import { invoke } from '@tauri-apps/api/core';
+import { writeText } from '@tauri-apps/plugin-clipboard-manager';
+import {
+  isPermissionGranted,
+  requestPermission,
+  sendNotification,
+} from '@tauri-apps/plugin-notification';
+import { resolveResource } from '@tauri-apps/api/path';
+import type { UserConfig } from '../config/types';
+import {
+  applyConfiguredUrlWithConfig,
+  formatLinkWithConfig,
+  getLinkFormatConfig,
+  type CopyLinkItem,
+} from '../composables/useCopyLink';
+import { openUserExternalUrl } from '../security/shellOpen';
+import type { LinkFormat } from '../utils/linkFormatter';
+import { createLogger } from '../utils/logger';
+
+const log = createLogger('PostUploadAction');
+
+/** 上传后操作（与 Rust 侧 PostUploadAction 保持一致） */
+export type PostUploadAction = 'copyLink' | 'notify' | 'openUrl' | 'none';
+
+export const POST_UPLOAD_ACTION_OPTIONS: { value: PostUploadAction; label: string }[] = [
+  { value: 'copyLink', label: '复制链接' },
+  { value: 'notify', label: '显示通知' },
+  { value: 'openUrl', label: '在浏览器中打开' },
+  { value: 'none', label: '不做任何操作' },
+];
+
+export interface PostUploadOutcome {
+  action: PostUploadAction;
+  copiedCount: number;
+  copyFailed: boolean;
+  format: LinkFormat;
+}
+
+export interface PostUploadOptions {
+  /** 调用方已发送系统通知（如快捷键上传的结果通知），notify 操作不再重复发送 */
+  notified?: boolean;
+  /** 自定义复制方式（主窗口使用 useCopyLink，按当前配置格式化）；缺省按 config 格式化后写入剪贴板 */
+  copy?: (items: CopyLinkItem[]) => Promise<{ ok: boolean; copiedCount: number }>;
+}
+
+async function copyWithConfig(items: CopyLinkItem[], config: UserConfig): Promise<{ ok: boolean; copiedCount: number }> {
+  const formatted = items.map(item => formatLinkWithConfig(item, config)).filter(Boolean);
+  await writeText(formatted.join('\n'));
+  return { ok: true, copiedCount: formatted.length };
+}
+
+/**
+ * 读取已保存的上传后操作；从未设置过时返回 null
+ */
+export async function getStoredPostUploadAction(): Promise<PostUploadAction | null> {
+  return (await invoke<PostUploadAction | null>('get_post_upload_action')) ?? null;
+}
+
+/**
+ * 读取上传后操作；从未设置过（或读取失败）时沿用「上传后自动复制」开关
+ */
+export async function getPostUploadAction(config: UserConfig): Promise<PostUploadAction> {
+  try {
+    const action = await getStoredPostUploadAction();
+    if (action) return action;
+  } catch (error) {
+    log.warn('读取上传后操作失败，沿用自动复制设置:', error);
+  }
+  return config.linkOutput?.autoCopy !== false ? 'copyLink' : 'none';
+}
+
+/**
+ * 保存上传后操作
+ */
+export async function setPostUploadAction(action: PostUploadAction): Promise<void> {
+  await invoke('set_post_upload_action', { action });
+}
+
+async function notifyUploaded(items: CopyLinkItem[]): Promise<void> {
+  let granted = await isPermissionGranted();
+  if (!granted) {
+    granted = (await requestPermission()) === 'granted';
+  }
+  if (!granted) return;
+  const icon = await resolveResource('icons/icon.png');
+  const title = items.length === 1 ? '上传成功' : `${items.length} 张图片上传完成`;
+  sendNotification({ title, body: items[0].shortUrl || items[0].url, icon });
+}
+
+/**
+ * 执行上传后操作（items 为本次成功上传的链接，按上传顺序）
+ *
+ * 操作失败只记录日志，不影响上传结果；复制失败通过 copyFailed 返回给调用方提示
+ */
+export async function runPostUploadAction(
+  items: CopyLinkItem[],
+  config: UserConfig,
+  options: PostUploadOptions = {}
+): Promise<PostUploadOutcome> {
+  const action = await getPostUploadAction(config);
+  const outcome: PostUploadOutcome = {
+    action,
+    copiedCount: 0,
+    copyFailed: false,
+    format: getLinkFormatConfig(config).format,
+  };
+  if (items.length === 0) return outcome;
+
+  try {
+    switch (action) {
+      case 'copyLink': {
+        const result = await (options.copy ?? (links => copyWithConfig(links, config)))(items);
+        outcome.copiedCount = result.copiedCount;
+        outcome.copyFailed = !result.ok;
+        break;
+      }
+      case 'notify':
+        if (!options.notified) await notifyUploaded(items);
+        break;
+      case 'openUrl': {
+        const first = items[0];
+        await openUserExternalUrl(first.shortUrl || applyConfiguredUrlWithConfig(first.url, first.serviceId, config));
+        break;
+      }
+      case 'none':
+        break;
+    }
+  } catch (error) {
+    log.error(`上传后操作失败 (${action}):`, error);
+    outcome.copyFailed = action === 'copyLink';
+  }
+  return outcome;
+}
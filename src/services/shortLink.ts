@@ -0,0 +1,67 @@
+// 短链接服务 - 上传成功后缩短主链接并写回历史记录
+
+import { invoke } from '@tauri-apps/api/core';
+import type { ShortLinkConfig, UserConfig } from '../config/types';
+import { applyConfiguredUrlWithConfig } from '../composables/useCopyLink';
+import { withHistoryUpdateQueue } from '../composables/useHistorySaver';
+import { invalidateCache } from '../composables/useHistory';
+import { emitHistoryUpdated } from '../events/cacheEvents';
+import { historyDB } from './HistoryDatabase';
+import { createLogger } from '../utils/logger';
+
+const log = createLogger('ShortLink');
+
+/**
+ * 返回可用的短链接配置；未启用或未填写服务地址时返回 null
+ */
+export function getActiveShortLinkConfig(config: UserConfig): ShortLinkConfig | null {
+  const shortLink = config.shortLink;
+  if (!shortLink?.enabled || !shortLink.endpoint.trim()) return null;
+  return shortLink;
+}
+
+/**
+ * 缩短 URL（调用 Rust 侧 shorten_url 命令）
+ */
+export async function shortenUrl(url: string, shortLink: ShortLinkConfig): Promise<string> {
+  return invoke<string>('shorten_url', {
+    url,
+    config: {
+      provider: shortLink.provider,
+      endpoint: shortLink.endpoint.trim(),
+      token: shortLink.token?.trim() || null,
+      responseField: shortLink.responseField?.trim() || null,
+    },
+  });
+}
+
+/**
+ * 为历史记录的主链接生成短链接并保存
+ *
+ * 缩短的是应用过前缀/知乎 source 等变换后的最终链接，复制时短链接直接替代整条链接。
+ * 失败只记录日志，不影响上传结果。
+ *
+ * @returns 生成的短链接；未启用或失败时返回 undefined
+ */
+export async function shortenHistoryLink(
+  historyId: string,
+  url: string,
+  serviceId: string | undefined,
+  config: UserConfig
+): Promise<string | undefined> {
+  const shortLink = getActiveShortLinkConfig(config);
+  if (!shortLink) return undefined;
+
+  try {
+    const shortUrl = await shortenUrl(applyConfiguredUrlWithConfig(url, serviceId, config), shortLink);
+    await withHistoryUpdateQueue(historyId, async () => {
+      await historyDB.update(historyId, { shortUrl });
+    });
+    invalidateCache();
+    await emitHistoryUpdated([historyId]);
+    return shortUrl;
+  } catch (error) {
+    log.warn(`[短链接] 生成失败，保留原始链接: ${historyId}`, error);
+    return undefined;
+  }
+}
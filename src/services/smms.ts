@@ -0,0 +1,56 @@
+// SM.MS 账户服务 - Token 同步到系统钥匙串、按 hash 删除图片、查询剩余容量
+//
+// Token 保存在钥匙串后，注册表与 CLI 上传无需前端传入；删除与容量查询可显式传入设置页中尚未保存的 Token。
+
+import { invoke } from '@tauri-apps/api/core';
+import { formatFileSize } from '../utils/formatters';
+import { createLogger } from '../utils/logger';
+
+const log = createLogger('Smms');
+
+/** SM.MS 账户容量（与 Rust 侧 SmmsQuota 保持一致） */
+export interface SmmsQuota {
+  username: string;
+  role: string;
+  diskUsage: string;
+  diskLimit: string;
+  usedBytes: number;
+  limitBytes: number;
+  remainingBytes: number;
+}
+
+/**
+ * 同步 Token 到系统钥匙串（空 Token 清除）；失败只记录日志，不影响设置保存
+ */
+export async function saveSmmsToken(token: string): Promise<void> {
+  try {
+    await invoke<boolean>('save_smms_token', { token: token.trim() });
+  } catch (error) {
+    log.warn('同步 SM.MS Token 到钥匙串失败:', error);
+  }
+}
+
+/**
+ * 查询账户容量；token 缺省时使用已保存的 Token
+ */
+export async function getSmmsQuota(token?: string): Promise<SmmsQuota> {
+  return invoke<SmmsQuota>('get_smms_quota', { token: token?.trim() || null });
+}
+
+/**
+ * 按 hash 删除 SM.MS 上的图片；token 缺省时使用已保存的 Token
+ */
+export async function deleteSmmsImage(hash: string, token?: string): Promise<void> {
+  await invoke('delete_smms_image', { hash, token: token?.trim() || null });
+}
+
+/**
+ * 生成一行容量说明，如 "剩余 4.9 GB / 共 5.0 GB（已用 12.3 MB）"
+ */
+export function describeSmmsQuota(quota: SmmsQuota): string {
+  if (quota.limitBytes <= 0) {
+    return `已用 ${quota.diskUsage || formatFileSize(quota.usedBytes)}`;
+  }
+  return `剩余 ${formatFileSize(quota.remainingBytes)} / 共 ${formatFileSize(quota.limitBytes)}`
+    + `（已用 ${formatFileSize(quota.usedBytes)}）`;
+}
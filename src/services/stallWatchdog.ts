@@ -0,0 +1,98 @@
+// 上传看门狗 - 发现长时间没有进度的上传任务并强制中止
+//
+// 请求卡住（服务端不响应、连接半开）时 invoke 永远不会返回，队列里的条目会一直显示"上传中"。
+// 每个 Rust 上传任务在开始时登记，收到进度事件时刷新；超过 STALL_TIMEOUT_MS 没有进度的任务
+// 调用 cancel_stalled_task 中止，后端以可重试的 E_STALLED 错误结束 invoke，队列条目随之变为失败可重试。
+
+import { invoke } from '@tauri-apps/api/core';
+import { createLogger } from '../utils/logger';
+
+const log = createLogger('StallWatchdog');
+
+/** 无进度多久视为卡住 */
+export const STALL_TIMEOUT_MS = 5 * 60 * 1000;
+
+/** 检查间隔 */
+export const STALL_CHECK_INTERVAL_MS = 30 * 1000;
+
+interface WatchedTask {
+  label: string;
+  lastActivity: number;
+  progress?: number;
+  step?: string;
+  /** 已发出中止请求，避免重复中止 */
+  stalled: boolean;
+}
+
+/** 看门狗登记句柄 */
+export interface StallWatch {
+  /** 收到进度时调用，重新计时 */
+  touch(progress?: number, step?: string): void;
+  /** 任务结束（成功 / 失败）时调用 */
+  stop(): void;
+}
+
+const tasks = new Map<string, WatchedTask>();
+let timer: ReturnType<typeof setInterval> | null = null;
+
+async function abortStalledTask(taskId: string, task: WatchedTask, idleMs: number): Promise<void> {
+  const idleSecs = Math.round(idleMs / 1000);
+  log.warn(
+    `[看门狗] ${task.label} 已 ${idleSecs} 秒无进度，强制中止 (ID: ${taskId}, 进度: ${task.progress ?? '未知'}, 步骤: ${task.step ?? '无'})`
+  );
+  try {
+    await invoke<boolean>('cancel_stalled_task', {
+      taskId,
+      idleSecs,
+      progress: task.progress ?? null,
+      step: task.step ?? null,
+    });
+  } catch (error) {
+    log.warn(`[看门狗] 中止任务失败: ${taskId}`, error);
+  }
+}
+
+/**
+ * 检查一轮，中止超时的任务（由定时器调用，导出供测试）
+ */
+export async function checkStalledTasks(now = Date.now()): Promise<void> {
+  const pending: Promise<void>[] = [];
+  for (const [taskId, task] of tasks) {
+    const idleMs = now - task.lastActivity;
+    if (task.stalled || idleMs < STALL_TIMEOUT_MS) continue;
+    task.stalled = true;
+    pending.push(abortStalledTask(taskId, task, idleMs));
+  }
+  await Promise.all(pending);
+}
+
+function stopTimerIfIdle(): void {
+  if (tasks.size === 0 && timer !== null) {
+    clearInterval(timer);
+    timer = null;
+  }
+}
+
+/**
+ * 登记一个 Rust 上传任务（taskId 为上传命令的 id）
+ */
+export function watchTask(taskId: string, label: string): StallWatch {
+  tasks.set(taskId, { label, lastActivity: Date.now(), stalled: false });
+  if (timer === null) {
+    timer = setInterval(() => void checkStalledTasks(), STALL_CHECK_INTERVAL_MS);
+  }
+
+  return {
+    touch(progress?: number, step?: string) {
+      const task = tasks.get(taskId);
+      if (!task) return;
+      task.lastActivity = Date.now();
+      task.progress = progress;
+      task.step = step ?? task.step;
+    },
+    stop() {
+      tasks.delete(taskId);
+      stopTimerIfIdle();
+    },
+  };
+}
@@ -21,6 +21,13 @@ export const MAIN_TRAY_ID = 'main-tray';
 export const TRAY_MENU_WINDOW_LABEL = 'tray-menu';
 
 export type TrayUploadAction = 'upload_clipboard' | 'select_upload_files';
+/** 跳转列表 / macOS 菜单快捷操作（Rust 侧 quick_actions.rs 发出） */
+export type TrayQuickAction = TrayUploadAction | 'pause_queue';
+export type PendingQuickAction = 'upload_clipboard' | 'open_history' | 'pause_queue';
+/** picnexus:// 深度链接动作（Rust 侧 deep_link.rs 解析后排队） */
+export type DeepLinkAction =
+  | { action: 'upload'; paths: string[] }
+  | { action: 'rehost'; url: string };
 export type TrayMenuItem = { item: 'Separator' } | TrayMenuCommand | TrayMenuServiceSubmenu;
 
 export interface TrayMenuCommand {
@@ -16,6 +16,7 @@ import { readTextFile, writeTextFile, exists, mkdir, remove } from '@tauri-apps/
 import { join } from '@tauri-apps/api/path';
 import { secureStorage, isAnyEncryptedData, BackupPasswordRequiredError } from '../security/crypto';
 import { getUserDataDir } from '../utils/appPaths';
+import { writeTextFileAtomic } from '../utils/atomicWrite';
 import { createLogger } from '../utils/logger';
 import { StoreError } from '../utils/storeErrors';
 import { type StoreData, toErrorMessage } from './types';
@@ -373,7 +374,7 @@ export class EncryptedStore {
   }
 
   /**
-   * 把完整数据原子写入磁盘（加密 + writeTextFile 一气呵成）
+   * 把完整数据原子写入磁盘（加密后写临时文件再改名，写到一半崩溃不会留下半截文件）
    * 调用方必须保证 `data` 是"干净"的纯对象（不含 Vue 响应式 Proxy）
    * @param key 当前触发写入的 key（仅用于 StoreError 的 key 字段，便于 UI 层错误分派）
    */
@@ -407,9 +408,8 @@ export class EncryptedStore {
       }
     }
 
-    // Tauri 的 writeTextFile 本身是原子的，可以直接覆盖
     try {
-      await writeTextFile(dataPath, contentToWrite);
+      await writeTextFileAtomic(dataPath, contentToWrite);
       log.debug(`成功保存数据 (${this.filePath})`);
     } catch (writeError) {
       throw this.wrapWriteError(writeError, dataPath, 'write', key);
@@ -431,7 +431,7 @@ export class EncryptedStore {
       const contentToWrite = this.encrypted
         ? await secureStorage.encrypt(emptyJson)
         : emptyJson;
-      await writeTextFile(dataPath, contentToWrite);
+      await writeTextFileAtomic(dataPath, contentToWrite);
       log.info(`✓ 成功清空数据文件: ${this.filePath}`);
     } catch (writeError) {
       throw this.wrapWriteError(writeError, dataPath, 'clear');
@@ -18,6 +18,47 @@ export type AppErrorType =
   | 'WEBDAV'
   | 'STORAGE';
 
+/**
+ * 稳定的机器可读错误码，与 Rust 后端 ErrorCode 保持一致
+ * 按错误码分支处理与做国际化，不要匹配错误消息文本
+ */
+export type ErrorCode =
+  | 'E_NETWORK'
+  | 'E_NETWORK_TIMEOUT'
+  | 'E_NETWORK_CONNECT'
+  | 'E_AUTH'
+  | 'E_FILE_IO'
+  | 'E_FILE_NOT_FOUND'
+  | 'E_UPLOAD'
+  | 'E_UPLOAD_AUTH'
+  | 'E_UPLOAD_RATE_LIMITED'
+  | 'E_UPLOAD_SERVER'
+  | 'E_CONFIG'
+  | 'E_CLIPBOARD'
+  | 'E_EXTERNAL'
+  | 'E_VALIDATION'
+  | 'E_WEBDAV'
+  | 'E_STORAGE'
+  | 'E_TEMP_QUOTA'
+  | 'E_LINK_TIMEOUT'
+  | 'E_LINK_FORBIDDEN_TARGET'
+  | 'E_CANCELLED'
+  | 'E_TASK_PANICKED'
+  | 'E_DISK_FULL'
+  | 'E_READONLY'
+  | 'E_NOT_IMAGE'
+  | 'E_DEADLINE'
+  | 'E_STALLED';
+
+/**
+ * 错误的公共附加字段（后端总会返回 code / retriable，context 仅在有内容时返回）
+ */
+export interface AppErrorMeta {
+  code?: ErrorCode;
+  retriable?: boolean;
+  context?: Record<string, string>;
+}
+
 /**
  * 基础错误数据结构（只有 message）
  */
@@ -46,18 +87,20 @@ export interface ServiceUnavailableErrorData {
  * AppError 类型 - 与 Rust 后端结构匹配
  * Rust 使用 #[serde(tag = "type", content = "data")] 序列化
  */
-export type AppError =
-  | { type: 'NETWORK'; data: SimpleErrorData }
-  | { type: 'AUTH'; data: SimpleErrorData }
-  | { type: 'FILE_IO'; data: SimpleErrorData }
-  | { type: 'UPLOAD'; data: UploadErrorData }
-  | { type: 'CONFIG'; data: SimpleErrorData }
-  | { type: 'CLIPBOARD'; data: SimpleErrorData }
-  | { type: 'EXTERNAL'; data: SimpleErrorData }
-  | { type: 'SERVICE_UNAVAILABLE'; data: ServiceUnavailableErrorData }
-  | { type: 'VALIDATION'; data: SimpleErrorData }
-  | { type: 'WEBDAV'; data: SimpleErrorData }
-  | { type: 'STORAGE'; data: SimpleErrorData };
+export type AppError = AppErrorMeta &
+  (
+    | { type: 'NETWORK'; data: SimpleErrorData }
+    | { type: 'AUTH'; data: SimpleErrorData }
+    | { type: 'FILE_IO'; data: SimpleErrorData }
+    | { type: 'UPLOAD'; data: UploadErrorData }
+    | { type: 'CONFIG'; data: SimpleErrorData }
+    | { type: 'CLIPBOARD'; data: SimpleErrorData }
+    | { type: 'EXTERNAL'; data: SimpleErrorData }
+    | { type: 'SERVICE_UNAVAILABLE'; data: ServiceUnavailableErrorData }
+    | { type: 'VALIDATION'; data: SimpleErrorData }
+    | { type: 'WEBDAV'; data: SimpleErrorData }
+    | { type: 'STORAGE'; data: SimpleErrorData }
+  );
 
 /**
  * 检查错误是否为 AppError 结构
@@ -131,10 +174,47 @@ export function getErrorType(error: unknown): AppErrorType | 'UNKNOWN' {
 }
 
 /**
- * 检查是否为认证错误（Cookie 过期等）
+ * 获取错误码（非 AppError 或旧版后端未返回时为 null）
+ */
+export function getErrorCode(error: unknown): ErrorCode | null {
+  if (isAppError(error)) {
+    return error.code ?? null;
+  }
+  return null;
+}
+
+/**
+ * 检查错误是否值得重试（未返回 retriable 时按网络错误处理）
+ */
+export function isRetriableError(error: unknown): boolean {
+  if (!isAppError(error)) {
+    return false;
+  }
+  return error.retriable ?? error.type === 'NETWORK';
+}
+
+/**
+ * 获取错误上下文中的某个字段
+ */
+export function getErrorContext(error: unknown, key: string): string | null {
+  if (isAppError(error)) {
+    return error.context?.[key] ?? null;
+  }
+  return null;
+}
+
+/**
+ * 检查是否为用户主动取消（cancel_task）导致的错误，此类错误不应提示失败
+ */
+export function isCancelledError(error: unknown): boolean {
+  return getErrorCode(error) === 'E_CANCELLED';
+}
+
+/**
+ * 检查是否为认证错误（Cookie 过期、图床鉴权失败等）
  */
 export function isAuthError(error: unknown): boolean {
-  return isAppError(error) && error.type === 'AUTH';
+  return isAppError(error) && (error.type === 'AUTH' || error.code === 'E_UPLOAD_AUTH');
 }
 
 /**
@@ -179,6 +259,30 @@ export function getErrorHint(error: unknown): string | null {
     return null;
   }
 
+  switch (error.code) {
+    case 'E_NETWORK_TIMEOUT':
+    case 'E_LINK_TIMEOUT':
+      return '请求超时，请稍后重试';
+    case 'E_UPLOAD_RATE_LIMITED':
+      return '请求过于频繁，请稍后重试';
+    case 'E_LINK_FORBIDDEN_TARGET':
+      return '该地址指向本机或内网，已被拦截';
+    case 'E_TEMP_QUOTA':
+      return '临时文件占用过多，请等待当前任务完成后重试';
+    case 'E_TASK_PANICKED':
+      return '程序内部错误，已生成崩溃报告，可在诊断包中附带反馈';
+    case 'E_DISK_FULL':
+      return '磁盘空间不足，请清理磁盘后重试';
+    case 'E_READONLY':
+      return '目标位置只读或没有写入权限，请更换位置或检查权限';
+    case 'E_NOT_IMAGE':
+      return '链接返回的是网页而不是图片，可能需要登录或链接已失效';
+    case 'E_DEADLINE':
+      return '操作耗时过长已自动放弃，可能是网络不稳定，请稍后重试';
+    case 'E_STALLED':
+      return '上传长时间没有进度已自动中止，请重试';
+  }
+
   switch (error.type) {
     case 'AUTH':
       return '请检查登录状态或重新登录';
@@ -0,0 +1,15 @@
+// 图床能力描述（由 Rust 端 get_host_capabilities 提供，与后端准入校验同源）
+
+export interface HostCapabilities {
+  service: string;
+  displayName: string;
+  /** 单文件大小上限（字节），null 表示平台未公开限制 */
+  maxFileSize: number | null;
+  /** GIF 单独的大小上限，null 表示与普通图片相同 */
+  maxGifSize: number | null;
+  /** 允许的扩展名（小写），空数组表示不限制 */
+  allowedFormats: string[];
+  needsCookie: boolean;
+  supportsDelete: boolean;
+  supportsMultipart: boolean;
+}
@@ -0,0 +1,14 @@
+// 图床健康快照（由 Rust 端 get_host_health 提供，滚动窗口 1 小时）
+
+export interface HostHealth {
+  service: string;
+  /** 窗口内上传次数 */
+  attempts: number;
+  successes: number;
+  /** 成功率（0-1） */
+  successRate: number;
+  /** 成功上传的平均耗时（毫秒），没有成功样本时为 null */
+  avgLatencyMs: number | null;
+  /** 近期错误类型（UploadErrorCode），最新的在前 */
+  recentErrors: string[];
+}
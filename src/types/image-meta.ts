@@ -60,6 +60,18 @@ export interface ImageMeta {
 
   /** 是否已收藏 */
   isFavorited?: boolean;
+
+  /** 短链接（仅批量复制查询返回，复制主链接时优先使用） */
+  shortUrl?: string;
+
+  /** 替代文本（仅批量复制查询返回） */
+  altText?: string;
+
+  /** 替代文本建议（仅批量复制查询返回） */
+  altSuggestion?: string;
+
+  /** 图片标题（仅批量复制查询返回） */
+  title?: string;
 }
 
 /** 单个镜像图床的精简信息（用于列表缩略图的 fallback 链） */
@@ -5,6 +5,15 @@ export class AliyunUploader extends BaseS3Uploader<AliyunServiceConfig> {
   readonly serviceId = 'aliyun';
   readonly serviceName = '阿里云';
 
+  /** 走原生 OSS V4 签名上传，支持自定义域名与图片样式后缀 */
+  protected getRustCommand(): string {
+    return 'upload_to_aliyun_oss';
+  }
+
+  protected getExtraParams(config: AliyunServiceConfig): Record<string, unknown> {
+    return { styleSuffix: config.styleSuffix?.trim() || null };
+  }
+
   protected getEndpoint(config: AliyunServiceConfig): string {
     const region = config.region;
     return `https://oss-${region}.aliyuncs.com`;
@@ -12,6 +12,7 @@ import {
 } from './types';
 import { getErrorMessage, isAuthError } from '../../types/errors';
 import { createLogger } from '../../utils/logger';
+import { watchTask } from '../../services/stallWatchdog';
 
 const log = createLogger('BaseUploader');
 
@@ -137,52 +138,53 @@ export abstract class BaseUploader<TConfig = unknown> implements IUploader<TConf
       }, 200);
     }
 
-    // 4. 设置进度监听器
+    // 4. 设置进度监听器（看门狗依赖进度事件判断任务是否卡住，没有进度回调时也要监听）
     let unlisten: UnlistenFn | undefined;
+    const stallWatch = watchTask(uploadId, this.serviceName);
 
-    if (onProgress) {
-      try {
-        unlisten = await listen<ProgressEvent>('upload://progress', (event) => {
-          // 只处理当前上传任务的进度事件
-          if (event.payload.id === uploadId) {
-            // 如果有步骤信息，记录到控制台
-            if (event.payload.step) {
-              log.debug(
-                `${this.serviceName} ${event.payload.step}`,
-                `(步骤${event.payload.step_index}/${event.payload.total_steps})`
-              );
-            }
+    try {
+      unlisten = await listen<ProgressEvent>('upload://progress', (event) => {
+        // 只处理当前上传任务的进度事件
+        if (event.payload.id === uploadId) {
+          // 如果有步骤信息，记录到控制台
+          if (event.payload.step) {
+            log.debug(
+              `${this.serviceName} ${event.payload.step}`,
+              `(步骤${event.payload.step_index}/${event.payload.total_steps})`
+            );
+          }
 
-            // 计算后端真实百分比
-            const realPercent = event.payload.total > 0
-              ? Math.round((event.payload.progress / event.payload.total) * 100)
-              : 0;
+          // 计算后端真实百分比
+          const realPercent = event.payload.total > 0
+            ? Math.round((event.payload.progress / event.payload.total) * 100)
+            : 0;
+          stallWatch.touch(realPercent, event.payload.step);
 
-            // 更新步骤信息
-            lastStep = event.payload.step;
-            lastStepIndex = event.payload.step_index;
-            lastTotalSteps = event.payload.total_steps;
+          // 更新步骤信息
+          lastStep = event.payload.step;
+          lastStepIndex = event.payload.step_index;
+          lastTotalSteps = event.payload.total_steps;
 
-            // 核心逻辑：进度条永不倒退
-            // - 如果蠕动进度 > 真实进度：保持蠕动进度
-            // - 如果蠕动进度 < 真实进度：追上真实进度
-            const displayPercent = Math.max(realPercent, currentVisualPercent);
-            currentVisualPercent = displayPercent;
-            lastReportedPercent = displayPercent; // 更新蠕动基准为当前显示进度
+          // 核心逻辑：进度条永不倒退
+          // - 如果蠕动进度 > 真实进度：保持蠕动进度
+          // - 如果蠕动进度 < 真实进度：追上真实进度
+          const displayPercent = Math.max(realPercent, currentVisualPercent);
+          currentVisualPercent = displayPercent;
+          lastReportedPercent = displayPercent; // 更新蠕动基准为当前显示进度
 
-            // 传递显示进度给外部回调（永不倒退）
-            onProgress(
-              displayPercent,
-              event.payload.step,
-              event.payload.step_index,
-              event.payload.total_steps
-            );
-          }
-        });
-      } catch (error) {
-        log.warn(`${this.serviceName} 无法监听进度事件:`, error);
-        // 继续执行，不因为进度监听失败而中断上传
-      }
+          // 传递显示进度给外部回调（永不倒退）
+          onProgress?.(
+            displayPercent,
+            event.payload.step,
+            event.payload.step_index,
+            event.payload.total_steps
+          );
+        }
+      });
+    } catch (error) {
+      log.warn(`${this.serviceName} 无法监听进度事件:`, error);
+      // 继续执行，不因为进度监听失败而中断上传；收不到进度时看门狗会误判，不再看护
+      stallWatch.stop();
     }
 
     try {
@@ -214,6 +216,7 @@ export abstract class BaseUploader<TConfig = unknown> implements IUploader<TConf
       throw new Error(`${this.serviceName}上传失败: ${errorMessage}`);
     } finally {
       // 7. 清理资源（防止内存泄漏）
+      stallWatch.stop();
       if (autoCreepInterval !== null) {
         clearInterval(autoCreepInterval);
         autoCreepInterval = null;
@@ -1,5 +1,7 @@
 // 共享类型定义
 
+import type { RequestHeaderTemplate } from '../../config/configInterface';
+
 /**
  * 上传结果接口
  * 所有上传器返回的标准化结果格式
@@ -57,6 +59,9 @@ export interface UploadOptions {
 
   /** 最大重试次数 */
   maxRetries?: number;
+
+  /** 自定义请求头（来自 config.requestHeaderOverrides，由支持的上传器透传给 Rust） */
+  requestHeaders?: RequestHeaderTemplate;
 }
 
 /**
@@ -70,7 +70,7 @@ export class BilibiliUploader extends BaseUploader<BilibiliServiceConfig> {
       // 调用基类的 Rust 上传方法
       const rustResult = await this.uploadViaRust(
         filePath,
-        { bilibiliCookie: config.cookie },
+        { bilibiliCookie: config.cookie, requestHeaders: options.requestHeaders ?? null },
         onProgress
       ) as BilibiliRustResult;
 
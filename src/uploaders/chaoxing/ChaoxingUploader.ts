@@ -70,7 +70,7 @@ export class ChaoxingUploader extends BaseUploader<ChaoxingServiceConfig> {
       // 调用基类的 Rust 上传方法
       const rustResult = await this.uploadViaRust(
         filePath,
-        { chaoxingCookie: config.cookie },
+        { chaoxingCookie: config.cookie, requestHeaders: options.requestHeaders ?? null },
         onProgress
       ) as ChaoxingRustResult;
 
@@ -34,6 +34,10 @@ export class CustomS3Uploader extends BaseS3Uploader<CustomS3Profile> {
     return config.publicDomain || '';
   }
 
+  protected getAcl(config: CustomS3Profile): string | undefined {
+    return config.acl?.trim() || undefined;
+  }
+
   getThumbnailUrl(result: UploadResult): string {
     return result.url;
   }
@@ -19,7 +19,8 @@ import { AliyunUploader } from './aliyun/AliyunUploader';
 import { QiniuUploader } from './qiniu/QiniuUploader';
 import { UpyunUploader } from './upyun/UpyunUploader';
 import { CustomS3Uploader } from './custom-s3/CustomS3Uploader';
-import type { CustomS3Profile } from '../config/types';
+import { PluginUploader } from './plugin/PluginUploader';
+import type { CustomS3Profile, PluginUploaderProfile } from '../config/types';
 import { createLogger } from '../utils/logger';
 
 const log = createLogger('Uploaders');
@@ -102,6 +103,21 @@ export function syncCustomS3Uploaders(profiles: CustomS3Profile[]): void {
   }
 }
 
+/**
+ * 同步插件图床上传器注册
+ * 根据 profiles 列表注册/注销对应的 plugin:xxx 上传器
+ */
+export function syncPluginUploaders(profiles: PluginUploaderProfile[]): void {
+  for (const id of UploaderFactory.getAvailableServices()) {
+    if (id.startsWith('plugin:')) {
+      UploaderFactory.unregister(id);
+    }
+  }
+  for (const profile of profiles) {
+    UploaderFactory.register(`plugin:${profile.id}`, () => new PluginUploader());
+  }
+}
+
 // 导出所有上传器
 export { WeiboUploader } from './weibo';
 export { R2Uploader } from './r2';
@@ -121,5 +137,6 @@ export { AliyunUploader } from './aliyun';
 export { QiniuUploader } from './qiniu';
 export { UpyunUploader } from './upyun';
 export { CustomS3Uploader } from './custom-s3';
+export { PluginUploader } from './plugin';
 export { UploaderFactory } from './base/UploaderFactory';
 
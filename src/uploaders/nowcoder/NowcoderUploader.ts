@@ -63,7 +63,7 @@ export class NowcoderUploader extends BaseUploader<NowcoderServiceConfig> {
       // 调用基类的 Rust 上传方法
       const rustResult = await this.uploadViaRust(
         filePath,
-        { nowcoderCookie: config.cookie },
+        { nowcoderCookie: config.cookie, requestHeaders: options.requestHeaders ?? null },
         onProgress
       ) as NowcoderRustResult;
 
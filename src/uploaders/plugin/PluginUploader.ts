@@ -0,0 +1,64 @@
+import { BaseUploader } from '../base/BaseUploader';
+import { UploadResult, ValidationResult, UploadOptions, ProgressCallback } from '../base/types';
+import type { PluginUploaderProfile } from '../../config/types';
+
+interface PluginRustResult {
+  url: string;
+}
+
+/**
+ * 插件图床上传器
+ * 由 Rust 侧以子进程执行 PicGo 风格脚本（输入文件路径，stdout 输出链接）
+ */
+export class PluginUploader extends BaseUploader<PluginUploaderProfile> {
+  readonly serviceId = 'plugin';
+  readonly serviceName = '插件图床';
+
+  protected getRustCommand(): string {
+    return 'upload_via_plugin';
+  }
+
+  async validateConfig(config: PluginUploaderProfile): Promise<ValidationResult> {
+    if (this.isEmpty(config.script)) {
+      return {
+        valid: false,
+        missingFields: ['script'],
+        errors: ['插件脚本路径不能为空']
+      };
+    }
+    return { valid: true };
+  }
+
+  async upload(
+    filePath: string,
+    options: UploadOptions,
+    onProgress?: ProgressCallback
+  ): Promise<UploadResult> {
+    const config = options.config as PluginUploaderProfile;
+    this.log('info', `开始通过插件上传: ${config.name || config.id}`, { filePath });
+
+    const rustResult = await this.uploadViaRust(
+      filePath,
+      {
+        plugin: {
+          command: config.command?.trim() || null,
+          script: config.script.trim(),
+          args: config.args ?? []
+        }
+      },
+      onProgress
+    ) as PluginRustResult;
+
+    this.log('info', '插件上传成功', { url: rustResult.url });
+
+    return {
+      serviceId: this.serviceId,
+      fileKey: rustResult.url,
+      url: rustResult.url
+    };
+  }
+
+  getPublicUrl(result: UploadResult): string {
+    return result.url;
+  }
+}
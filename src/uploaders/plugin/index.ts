@@ -0,0 +1 @@
+export { PluginUploader } from './PluginUploader';
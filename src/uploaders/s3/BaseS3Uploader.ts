@@ -1,6 +1,7 @@
 // S3 兼容存储上传器基类
 // 支持：腾讯云 COS、阿里云 OSS、七牛云、又拍云、Cloudflare R2、自定义 S3
 
+import { invoke } from '@tauri-apps/api/core';
 import { BaseUploader } from '../base/BaseUploader';
 import { IUploader } from '../base/IUploader';
 import { S3BaseConfig } from './types';
@@ -23,6 +24,16 @@ export abstract class BaseS3Uploader<TConfig extends S3BaseConfig>
   protected abstract getPath(config: TConfig): string;
   protected abstract getPublicDomain(config: TConfig): string;
 
+  /** 预设 ACL（如 public-read），默认不设置，沿用存储桶策略 */
+  protected getAcl(_config: TConfig): string | undefined {
+    return undefined;
+  }
+
+  /** 额外传给 Rust 上传命令的参数（如阿里云的图片样式后缀） */
+  protected getExtraParams(_config: TConfig): Record<string, unknown> {
+    return {};
+  }
+
   protected getRustCommand(): string {
     return 'upload_to_s3_compatible';
   }
@@ -75,7 +86,12 @@ export abstract class BaseS3Uploader<TConfig extends S3BaseConfig>
     // options.config 仍是 any（UploadOptions 尚未泛型化），这里显式断言到 TConfig
     // 让读者明确：此处接收到的是具体派生类对应的 config 类型
     const config = options.config as TConfig;
-    const fileName = filePath.split(/[/\\]/).pop() || '';
+    const originalName = filePath.split(/[/\\]/).pop() || '';
+    // 按该图床的文件名规则改写（去空格 / emoji、转拼音等），未配置规则时原样返回
+    const fileName = await invoke<string>('sanitize_object_name', {
+      serviceId: this.serviceId,
+      fileName: originalName
+    });
     const path = this.getPath(config);
     // 确保 path 以 / 结尾（如果非空）
     const normalizedPath = path ? (path.endsWith('/') ? path : path + '/') : '';
@@ -90,7 +106,9 @@ export abstract class BaseS3Uploader<TConfig extends S3BaseConfig>
         region: this.getRegion(config),
         bucket: this.getBucket(config),
         key,
-        publicDomain: this.getPublicDomain(config)
+        publicDomain: this.getPublicDomain(config),
+        acl: this.getAcl(config) || null,
+        ...this.getExtraParams(config)
       },
       onProgress
     ) as S3RustResult;
@@ -0,0 +1,229 @@
+// 微博多账号轮换池
+// 主账号（services.weibo.cookie）与额外账号按 round-robin / LRU 轮换上传，分摊频率限制；
+// 每个账号单独记录健康状态：Cookie 失效的账号停用到重新登录，被限流的账号按指数退避暂停
+// 健康状态只记录账号 ID 与 Cookie 指纹，持久化到 syncStatusStore，不含 Cookie 明文
+
+import type { WeiboAccount, WeiboServiceConfig } from '../../config/types';
+import { syncStatusStore } from '../../store/instances';
+import { createLogger } from '../../utils/logger';
+
+const log = createLogger('WeiboAccountPool');
+
+export const PRIMARY_WEIBO_ACCOUNT_ID = 'primary';
+
+const PERSIST_KEY = 'weiboAccountHealth';
+const PERSIST_DEBOUNCE_MS = 500;
+/** 限流后的首次暂停时长（10 分钟），连续限流逐次翻倍 */
+const BASE_BAN_MS = 10 * 60 * 1000;
+/** 暂停时长上限（6 小时） */
+const MAX_BAN_MS = 6 * 60 * 60 * 1000;
+
+export type WeiboAccountStatus = 'ok' | 'expired' | 'banned';
+export type WeiboAccountOutcome = 'success' | 'expired' | 'rate-limited' | 'failed';
+
+export interface WeiboAccountHealth {
+  status: WeiboAccountStatus;
+  lastUsedAt: number | null;
+  consecutiveFailures: number;
+  /** 连续被限流次数，决定下一次暂停时长 */
+  banCount: number;
+  bannedUntil: number | null;
+  lastError: string | null;
+  /** Cookie 指纹：Cookie 更换（重新登录）后健康状态自动重置 */
+  cookieFingerprint: string;
+}
+
+// ==================== 模块级共享状态（单例） ====================
+
+let healthMap: Record<string, WeiboAccountHealth> = {};
+let loadPromise: Promise<void> | null = null;
+let persistTimer: ReturnType<typeof setTimeout> | null = null;
+let roundRobinCursor = 0;
+
+function loadHealth(): Promise<void> {
+  if (!loadPromise) {
+    loadPromise = (async () => {
+      try {
+        const persisted = await syncStatusStore.get<Record<string, WeiboAccountHealth>>(PERSIST_KEY);
+        if (persisted) healthMap = { ...persisted, ...healthMap };
+      } catch (error) {
+        log.warn('加载账号健康状态失败:', error);
+      }
+    })();
+  }
+  return loadPromise;
+}
+
+function debouncedPersist(): void {
+  if (persistTimer) clearTimeout(persistTimer);
+  persistTimer = setTimeout(() => {
+    persistTimer = null;
+    syncStatusStore.set(PERSIST_KEY, healthMap).catch((error: unknown) => {
+      log.warn('持久化账号健康状态失败:', error);
+    });
+  }, PERSIST_DEBOUNCE_MS);
+}
+
+/** FNV-1a 32 位指纹，只用于判断 Cookie 是否更换 */
+export function fingerprintCookie(cookie: string): string {
+  let hash = 0x811c9dc5;
+  for (let i = 0; i < cookie.length; i++) {
+    hash ^= cookie.charCodeAt(i);
+    hash = Math.imul(hash, 0x01000193);
+  }
+  return (hash >>> 0).toString(16).padStart(8, '0');
+}
+
+/**
+ * 参与轮换的账号：主账号在前，其后是启用且 Cookie 非空的额外账号（按 Cookie 去重）
+ */
+export function listWeiboAccounts(config: WeiboServiceConfig): WeiboAccount[] {
+  const accounts: WeiboAccount[] = [];
+  const primaryCookie = config.cookie?.trim();
+  if (primaryCookie) {
+    accounts.push({ id: PRIMARY_WEIBO_ACCOUNT_ID, name: '主账号', cookie: primaryCookie, enabled: true });
+  }
+  for (const account of config.accounts ?? []) {
+    const cookie = account.cookie?.trim();
+    if (!account.enabled || !cookie) continue;
+    if (accounts.some(existing => existing.cookie === cookie)) continue;
+    accounts.push({ ...account, cookie });
+  }
+  return accounts;
+}
+
+function healthFor(account: WeiboAccount): WeiboAccountHealth {
+  const fingerprint = fingerprintCookie(account.cookie);
+  const existing = healthMap[account.id];
+  if (existing && existing.cookieFingerprint === fingerprint) return existing;
+
+  // 新账号或 Cookie 已更换，之前的失效 / 限流记录不再成立
+  const fresh: WeiboAccountHealth = {
+    status: 'ok',
+    lastUsedAt: existing?.lastUsedAt ?? null,
+    consecutiveFailures: 0,
+    banCount: 0,
+    bannedUntil: null,
+    lastError: null,
+    cookieFingerprint: fingerprint,
+  };
+  healthMap[account.id] = fresh;
+  return fresh;
+}
+
+function isAvailable(health: WeiboAccountHealth, now: number): boolean {
+  if (health.status === 'expired') return false;
+  if (health.status === 'banned' && health.bannedUntil !== null && health.bannedUntil > now) return false;
+  return true;
+}
+
+/**
+ * 按轮换策略返回本次上传的候选账号（第一个为首选，其余用于失效 / 限流时切换）
+ * 没有可用账号时返回空数组
+ */
+export async function pickWeiboAccounts(
+  config: WeiboServiceConfig,
+  now = Date.now(),
+): Promise<WeiboAccount[]> {
+  await loadHealth();
+  const available = listWeiboAccounts(config).filter(account => isAvailable(healthFor(account), now));
+  if (available.length <= 1) return available;
+
+  if (config.rotation === 'lru') {
+    return [...available].sort(
+      (a, b) => (healthMap[a.id].lastUsedAt ?? 0) - (healthMap[b.id].lastUsedAt ?? 0),
+    );
+  }
+
+  const start = roundRobinCursor % available.length;
+  roundRobinCursor = start + 1;
+  return [...available.slice(start), ...available.slice(0, start)];
+}
+
+/**
+ * 没有可用账号时判断原因：全部 Cookie 失效返回 'expired'，否则为限流暂停中
+ */
+export function unavailableWeiboAccountsReason(config: WeiboServiceConfig): 'expired' | 'banned' {
+  const accounts = listWeiboAccounts(config);
+  return accounts.length > 0 && accounts.every(account => healthFor(account).status === 'expired')
+    ? 'expired'
+    : 'banned';
+}
+
+/**
+ * 记录一次上传结果，更新账号健康状态
+ */
+export function recordWeiboAccountResult(
+  account: WeiboAccount,
+  outcome: WeiboAccountOutcome,
+  error?: string,
+  now = Date.now(),
+): void {
+  const health = healthFor(account);
+  health.lastUsedAt = now;
+
+  switch (outcome) {
+    case 'success':
+      health.status = 'ok';
+      health.consecutiveFailures = 0;
+      health.banCount = 0;
+      health.bannedUntil = null;
+      health.lastError = null;
+      break;
+    case 'expired':
+      health.status = 'expired';
+      health.lastError = error ?? 'Cookie 已过期';
+      log.warn(`账号 ${account.name} Cookie 已失效，暂停轮换直到重新登录`);
+      break;
+    case 'rate-limited': {
+      const banMs = Math.min(BASE_BAN_MS * 2 ** health.banCount, MAX_BAN_MS);
+      health.status = 'banned';
+      health.banCount += 1;
+      health.bannedUntil = now + banMs;
+      health.lastError = error ?? '上传过于频繁';
+      log.warn(`账号 ${account.name} 被限流，暂停 ${Math.round(banMs / 60000)} 分钟`);
+      break;
+    }
+    case 'failed':
+      // 普通失败多为文件或网络问题，只计数不停用账号
+      health.consecutiveFailures += 1;
+      health.lastError = error ?? null;
+      break;
+  }
+  debouncedPersist();
+}
+
+/**
+ * 读取账号健康状态（设置页展示用）
+ */
+export async function getWeiboAccountHealth(config: WeiboServiceConfig): Promise<Record<string, WeiboAccountHealth>> {
+  await loadHealth();
+  const result: Record<string, WeiboAccountHealth> = {};
+  for (const account of listWeiboAccounts(config)) {
+    result[account.id] = { ...healthFor(account) };
+  }
+  return result;
+}
+
+/**
+ * 手动恢复账号（用户确认账号已解封）
+ */
+export function resetWeiboAccountHealth(accountId: string): void {
+  const health = healthMap[accountId];
+  if (!health) return;
+  health.status = 'ok';
+  health.consecutiveFailures = 0;
+  health.banCount = 0;
+  health.bannedUntil = null;
+  health.lastError = null;
+  debouncedPersist();
+}
+
+/** 仅供测试：清空内存状态 */
+export function __resetWeiboAccountPoolForTests(): void {
+  healthMap = {};
+  loadPromise = null;
+  roundRobinCursor = 0;
+  if (persistTimer) clearTimeout(persistTimer);
+  persistTimer = null;
+}
@@ -30,10 +30,22 @@ export function convertToWeiboError(error: unknown): WeiboUploadError {
 
   // Cookie 过期
   if (msg.includes('Cookie expired') || msg.includes('100006')) {
+    const httpStatus = msg.match(/HTTP (\d{3})/)?.[1];
+    const reason = httpStatus ? `HTTP ${httpStatus}` : '错误码：100006';
     return new WeiboUploadError(
-      'Cookie 已过期：请立即检查并更新 Cookie（错误码：100006）',
+      `Cookie 已过期：请立即检查并更新 Cookie（${reason}）`,
       'COOKIE_EXPIRED',
-      undefined,
+      httpStatus ? Number(httpStatus) : undefined,
+      error
+    );
+  }
+
+  // 上传过于频繁（HTTP 429 或接口提示）
+  if (msg.includes('HTTP 429') || msg.includes('频繁')) {
+    return new WeiboUploadError(
+      '上传过于频繁：该账号已被暂时限流',
+      'RATE_LIMITED',
+      msg.includes('HTTP 429') ? 429 : undefined,
       error
     );
   }
@@ -66,6 +78,12 @@ export function convertToStructuredWeiboError(error: unknown): StructuredError {
       code = UploadErrorCode.COOKIE_INVALID;
       solution = '请前往设置页面更新微博 Cookie';
       break;
+    case 'RATE_LIMITED':
+    case 'ACCOUNTS_UNAVAILABLE':
+      code = UploadErrorCode.RATE_LIMIT;
+      retryable = true;
+      solution = '稍后重试，或在设置中添加更多微博账号轮换上传';
+      break;
     case 'EMPTY_COOKIE':
       code = UploadErrorCode.COOKIE_EMPTY;
       solution = '请前往设置页面配置微博 Cookie';
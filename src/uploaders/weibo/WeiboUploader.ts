@@ -3,7 +3,15 @@
 import { BaseUploader } from '../base/BaseUploader';
 import { UploadResult, ValidationResult, UploadOptions, ProgressCallback } from '../base/types';
 import { WeiboServiceConfig } from '../../config/types';
-import { convertToWeiboError } from './WeiboError';
+import { convertToWeiboError, WeiboUploadError } from './WeiboError';
+import {
+  listWeiboAccounts,
+  pickWeiboAccounts,
+  recordWeiboAccountResult,
+  unavailableWeiboAccountsReason,
+  type WeiboAccountOutcome,
+} from './WeiboAccountPool';
+import { buildWeiboSizeUrl, buildWeiboSizeVariants } from './weiboSizes';
 
 /**
  * Rust 返回的微博上传结果
@@ -15,9 +23,15 @@ interface WeiboRustResult {
   size: number;
 }
 
+function accountOutcome(error: WeiboUploadError): WeiboAccountOutcome {
+  if (error.code === 'COOKIE_EXPIRED') return 'expired';
+  if (error.code === 'RATE_LIMITED') return 'rate-limited';
+  return 'failed';
+}
+
 /**
  * 微博图床上传器
- * 实现微博图片上传功能
+ * 实现微博图片上传功能，配置了多个账号时按轮换策略选择账号
  */
 export class WeiboUploader extends BaseUploader<WeiboServiceConfig> {
   readonly serviceId = 'weibo';
@@ -36,8 +50,8 @@ export class WeiboUploader extends BaseUploader<WeiboServiceConfig> {
   async validateConfig(config: WeiboServiceConfig): Promise<ValidationResult> {
     const weiboConfig = config;
 
-    // 检查 Cookie 是否存在
-    if (!weiboConfig.cookie || this.isEmpty(weiboConfig.cookie)) {
+    // 检查 Cookie 是否存在（主账号或任一轮换账号）
+    if (listWeiboAccounts(weiboConfig).length === 0) {
       return {
         valid: false,
         missingFields: ['Cookie'],
@@ -57,40 +71,64 @@ export class WeiboUploader extends BaseUploader<WeiboServiceConfig> {
     onProgress?: ProgressCallback
   ): Promise<UploadResult> {
     const config = options.config as WeiboServiceConfig;
+    const candidates = await pickWeiboAccounts(config);
+
+    if (candidates.length === 0) {
+      if (listWeiboAccounts(config).length === 0) {
+        throw new WeiboUploadError('请先在设置中配置微博 Cookie', 'EMPTY_COOKIE');
+      }
+      if (unavailableWeiboAccountsReason(config) === 'expired') {
+        throw new WeiboUploadError('Cookie 已过期：所有微博账号均需重新登录', 'COOKIE_EXPIRED');
+      }
+      throw new WeiboUploadError('所有微博账号均被暂时限流，请稍后重试或添加更多账号', 'ACCOUNTS_UNAVAILABLE');
+    }
 
-    this.log('info', '开始上传到微博', { filePath });
-
-    try {
-      // 调用基类的 Rust 上传方法
-      const rustResult = await this.uploadViaRust(
-        filePath,
-        { weiboCookie: config.cookie },
-        onProgress
-      ) as WeiboRustResult;
-
-      // 转换为标准 UploadResult
-      const pid = rustResult.pid;
-      const hashName = `${pid}.jpg`;
-      const url = `https://tvax1.sinaimg.cn/large/${hashName}`;
-
-      this.log('info', '微博上传成功', { pid, url });
-
-      return {
-        serviceId: 'weibo',
-        fileKey: pid,
-        url: url,
-        size: rustResult.size,
-        width: rustResult.width,
-        height: rustResult.height,
-        metadata: {
-          hashName: hashName,
-          pid: pid
-        }
-      };
-    } catch (error) {
-      this.log('error', '微博上传失败', error);
-      throw convertToWeiboError(error);
+    let lastError: WeiboUploadError | null = null;
+    for (const account of candidates) {
+      this.log('info', '开始上传到微博', { filePath, account: account.name });
+
+      try {
+        // 调用基类的 Rust 上传方法
+        const rustResult = await this.uploadViaRust(
+          filePath,
+          { weiboCookie: account.cookie, requestHeaders: options.requestHeaders ?? null },
+          onProgress
+        ) as WeiboRustResult;
+        recordWeiboAccountResult(account, 'success');
+
+        // 转换为标准 UploadResult
+        const pid = rustResult.pid;
+        const hashName = `${pid}.jpg`;
+        const variants = buildWeiboSizeVariants(pid);
+        const url = variants.large;
+
+        this.log('info', '微博上传成功', { pid, url });
+
+        return {
+          serviceId: 'weibo',
+          fileKey: pid,
+          url: url,
+          size: rustResult.size,
+          width: rustResult.width,
+          height: rustResult.height,
+          metadata: {
+            hashName: hashName,
+            pid: pid,
+            accountId: account.id,
+            variants
+          }
+        };
+      } catch (error) {
+        this.log('error', '微博上传失败', error);
+        lastError = convertToWeiboError(error);
+        const outcome = accountOutcome(lastError);
+        recordWeiboAccountResult(account, outcome, lastError.message);
+        // 只有账号本身的问题才切换到下一个账号，文件 / 网络错误换账号也无济于事
+        if (outcome === 'failed') break;
+      }
     }
+
+    throw lastError;
   }
 
   /**
@@ -104,15 +142,13 @@ export class WeiboUploader extends BaseUploader<WeiboServiceConfig> {
    * 生成微博缩略图 URL
    */
   getThumbnailUrl(result: UploadResult): string {
-    const pid = result.fileKey;
-    return `https://tvax1.sinaimg.cn/thumb150/${pid}.jpg`;
+    return buildWeiboSizeUrl(result.fileKey, 'thumb150');
   }
 
   /**
    * 生成微博原图 URL
    */
   getOriginalUrl(result: UploadResult): string {
-    const pid = result.fileKey;
-    return `https://tvax1.sinaimg.cn/large/${pid}.jpg`;
+    return buildWeiboSizeUrl(result.fileKey, 'large');
   }
 }
@@ -0,0 +1,171 @@
+// 微博 CDN 节点选择
+// 上传结果统一保存为 tvax1 链接，输出时（复制、预览、链接检测）按设置改写为指定节点；
+// 'auto' 模式下链接检测轮流测量各节点，按平均响应时间选出最快的节点
+// 延迟统计持久化到 syncStatusStore，不进入用户配置，避免频繁触发配置同步
+
+import type { UserConfig, WeiboCdnHost } from '../../config/types';
+import { syncStatusStore } from '../../store/instances';
+import { createLogger } from '../../utils/logger';
+
+const log = createLogger('WeiboCdn');
+
+export const WEIBO_CDN_HOSTS: readonly WeiboCdnHost[] = ['tvax1', 'wx1', 'wx2', 'wx3', 'wx4', 'ww1'];
+export const DEFAULT_WEIBO_CDN_HOST: WeiboCdnHost = 'tvax1';
+
+const PERSIST_KEY = 'weiboCdnLatency';
+/** 样本数达到该值的节点才参与自动选择 */
+const MIN_SAMPLES = 3;
+/** 指数滑动平均权重：新样本占 30% */
+const EWMA_ALPHA = 0.3;
+/** 检测失败按该延迟计入，使不稳定的节点排到后面 */
+const FAILURE_PENALTY_MS = 10_000;
+
+const SINAIMG_HOST_RE = /^(?:wx|ww|tvax|tva)\d\.sinaimg\.cn$/i;
+
+export interface WeiboCdnLatency {
+  avgMs: number;
+  samples: number;
+  updatedAt: number;
+}
+
+export interface WeiboCdnSample {
+  url: string;
+  responseTime?: number;
+  ok: boolean;
+}
+
+// ==================== 模块级共享状态（单例） ====================
+
+let latencyStats: Partial<Record<WeiboCdnHost, WeiboCdnLatency>> = {};
+let loadPromise: Promise<void> | null = null;
+
+function loadStats(): Promise<void> {
+  if (!loadPromise) {
+    loadPromise = (async () => {
+      try {
+        const persisted = await syncStatusStore.get<Partial<Record<WeiboCdnHost, WeiboCdnLatency>>>(PERSIST_KEY);
+        if (persisted) latencyStats = { ...persisted, ...latencyStats };
+      } catch (error) {
+        log.warn('加载 CDN 延迟统计失败:', error);
+      }
+    })();
+  }
+  return loadPromise;
+}
+
+function isCdnHost(value: string): value is WeiboCdnHost {
+  return (WEIBO_CDN_HOSTS as readonly string[]).includes(value);
+}
+
+/**
+ * 提取链接中的 sinaimg 节点（非微博链接返回 null）
+ */
+export function getWeiboCdnHostFromUrl(url: string): WeiboCdnHost | null {
+  try {
+    const { hostname } = new URL(url);
+    if (!SINAIMG_HOST_RE.test(hostname)) return null;
+    const sub = hostname.split('.')[0].toLowerCase();
+    return isCdnHost(sub) ? sub : null;
+  } catch {
+    return null;
+  }
+}
+
+/**
+ * 把 sinaimg 链接改写为指定节点，其他链接原样返回
+ */
+export function rewriteWeiboCdnHost(url: string, host: WeiboCdnHost): string {
+  try {
+    const parsed = new URL(url);
+    if (!SINAIMG_HOST_RE.test(parsed.hostname)) return url;
+    parsed.hostname = `${host}.sinaimg.cn`;
+    return parsed.toString();
+  } catch {
+    return url;
+  }
+}
+
+/**
+ * 按平均延迟选出最快的节点；样本不足时返回默认节点
+ */
+export function pickFastestWeiboCdnHost(): WeiboCdnHost {
+  void loadStats();
+  let best: WeiboCdnHost = DEFAULT_WEIBO_CDN_HOST;
+  let bestAvg = Number.POSITIVE_INFINITY;
+  for (const host of WEIBO_CDN_HOSTS) {
+    const stat = latencyStats[host];
+    if (!stat || stat.samples < MIN_SAMPLES) continue;
+    if (stat.avgMs < bestAvg) {
+      best = host;
+      bestAvg = stat.avgMs;
+    }
+  }
+  return best;
+}
+
+/**
+ * 当前配置下输出链接使用的节点；未设置时返回 null（保留链接原有节点）
+ */
+export function resolveWeiboCdnHost(config: UserConfig): WeiboCdnHost | null {
+  const setting = config.services?.weibo?.cdnHost;
+  if (setting === 'auto') return pickFastestWeiboCdnHost();
+  return setting && isCdnHost(setting) ? setting : null;
+}
+
+/**
+ * 对微博链接应用 CDN 节点设置（纯函数版本，直接传入 config）
+ */
+export function applyWeiboCdnFromConfig(url: string, config: UserConfig): string {
+  const host = resolveWeiboCdnHost(config);
+  if (!host || getWeiboCdnHostFromUrl(url) === host) return url;
+  return rewriteWeiboCdnHost(url, host);
+}
+
+/**
+ * 自动模式下链接检测按序号轮流使用各节点，为自动选择积累样本
+ */
+export function weiboCdnProbeHost(index: number): WeiboCdnHost {
+  return WEIBO_CDN_HOSTS[index % WEIBO_CDN_HOSTS.length];
+}
+
+/**
+ * 记录链接检测测得的节点延迟
+ */
+export async function recordWeiboCdnLatency(samples: WeiboCdnSample[], now = Date.now()): Promise<void> {
+  await loadStats();
+  let changed = false;
+  for (const sample of samples) {
+    const host = getWeiboCdnHostFromUrl(sample.url);
+    if (!host) continue;
+    const latency = sample.ok && sample.responseTime !== undefined ? sample.responseTime : FAILURE_PENALTY_MS;
+    const previous = latencyStats[host];
+    latencyStats[host] = previous
+      ? {
+        avgMs: previous.avgMs + EWMA_ALPHA * (latency - previous.avgMs),
+        samples: previous.samples + 1,
+        updatedAt: now,
+      }
+      : { avgMs: latency, samples: 1, updatedAt: now };
+    changed = true;
+  }
+  if (!changed) return;
+  try {
+    await syncStatusStore.set(PERSIST_KEY, latencyStats);
+  } catch (error) {
+    log.warn('持久化 CDN 延迟统计失败:', error);
+  }
+}
+
+/**
+ * 读取各节点延迟统计（设置页展示用）
+ */
+export async function getWeiboCdnLatency(): Promise<Partial<Record<WeiboCdnHost, WeiboCdnLatency>>> {
+  await loadStats();
+  return { ...latencyStats };
+}
+
+/** 仅供测试：清空内存状态 */
+export function __resetWeiboCdnForTests(): void {
+  latencyStats = {};
+  loadPromise = null;
+}
@@ -0,0 +1,53 @@
+// 微博图片尺寸变体
+// 同一 PID 通过 URL 中的尺寸段访问不同规格：/large/ 原图、/mw690/ 限宽 690 等
+// 上传成功时整组写入 result.metadata.variants，旧记录没有该字段时按 fileKey 现算
+
+import type { UploadResult } from '../base/types';
+
+export type WeiboSize = 'large' | 'mw2048' | 'mw690' | 'orj360' | 'thumb150';
+
+export interface WeiboSizeOption {
+  size: WeiboSize;
+  label: string;
+}
+
+/** 按尺寸从大到小排列，供复制菜单展示 */
+export const WEIBO_SIZE_OPTIONS: readonly WeiboSizeOption[] = [
+  { size: 'large', label: '原图' },
+  { size: 'mw2048', label: '限宽 2048' },
+  { size: 'mw690', label: '限宽 690' },
+  { size: 'orj360', label: '限宽 360' },
+  { size: 'thumb150', label: '方形缩略图 150' },
+];
+
+const WEIBO_IMAGE_HOST = 'tvax1.sinaimg.cn';
+
+export type WeiboSizeVariants = Record<WeiboSize, string>;
+
+export function buildWeiboSizeUrl(pid: string, size: WeiboSize): string {
+  return `https://${WEIBO_IMAGE_HOST}/${size}/${pid}.jpg`;
+}
+
+export function buildWeiboSizeVariants(pid: string): WeiboSizeVariants {
+  const variants = {} as WeiboSizeVariants;
+  for (const { size } of WEIBO_SIZE_OPTIONS) {
+    variants[size] = buildWeiboSizeUrl(pid, size);
+  }
+  return variants;
+}
+
+function isVariants(value: unknown): value is WeiboSizeVariants {
+  if (!value || typeof value !== 'object') return false;
+  return WEIBO_SIZE_OPTIONS.every(({ size }) => typeof (value as Record<string, unknown>)[size] === 'string');
+}
+
+/**
+ * 读取上传结果的尺寸变体：优先使用记录中保存的，旧记录按 fileKey（PID）生成
+ * 非微博结果或缺少 PID 时返回 null
+ */
+export function getWeiboSizeVariants(result: UploadResult | undefined): WeiboSizeVariants | null {
+  if (!result || result.serviceId !== 'weibo') return null;
+  const stored = result.metadata?.variants;
+  if (isVariants(stored)) return stored;
+  return result.fileKey ? buildWeiboSizeVariants(result.fileKey) : null;
+}
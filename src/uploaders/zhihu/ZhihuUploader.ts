@@ -63,7 +63,7 @@ export class ZhihuUploader extends BaseUploader<ZhihuServiceConfig> {
       // 调用基类的 Rust 上传方法
       const rustResult = await this.uploadViaRust(
         filePath,
-        { zhihuCookie: config.cookie },
+        { zhihuCookie: config.cookie, requestHeaders: options.requestHeaders ?? null },
         onProgress
       ) as ZhihuRustResult;
 
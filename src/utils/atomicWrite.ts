@@ -0,0 +1,27 @@
+// 原子写文本文件：先写同目录临时文件，再改名覆盖目标
+//
+// writeTextFile 会先截断目标文件再写入，写到一半应用崩溃或被结束，留下的就是半截 JSON，
+// 下次启动只能当作损坏文件重置（用户丢失设置多由此引起）。同一目录内改名是原子的：
+// 目标文件要么是旧内容，要么是完整的新内容。
+
+import { remove, rename, writeTextFile } from '@tauri-apps/plugin-fs';
+
+/**
+ * 原子写入文本文件（失败时删除临时文件，目标文件保持原样）
+ * @param path 目标文件绝对路径
+ * @param content 文件内容
+ */
+export async function writeTextFileAtomic(path: string, content: string): Promise<void> {
+  const tempPath = `${path}.${Date.now()}.tmp`;
+  try {
+    await writeTextFile(tempPath, content);
+    await rename(tempPath, path);
+  } catch (error) {
+    try {
+      await remove(tempPath);
+    } catch {
+      // 临时文件可能根本没有创建
+    }
+    throw error;
+  }
+}
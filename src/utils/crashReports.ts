@@ -0,0 +1,30 @@
+/**
+ * 崩溃报告（Rust panic hook 写入，下次启动时提示）
+ */
+
+import { invoke } from '@tauri-apps/api/core';
+
+export interface CrashReportSummary {
+  /** 报告文件名 */
+  id: string;
+  path: string;
+  /** panic 信息（已脱敏） */
+  message: string;
+  createdAt: string;
+}
+
+export interface PendingCrashReports {
+  /** 报告目录 */
+  dir: string;
+  reports: CrashReportSummary[];
+}
+
+/** 获取尚未查看的崩溃报告 */
+export async function getPendingCrashReports(): Promise<PendingCrashReports> {
+  return invoke<PendingCrashReports>('get_pending_crash_reports');
+}
+
+/** 把未查看的报告标记为已查看，下次启动不再提示 */
+export async function dismissCrashReports(): Promise<number> {
+  return invoke<number>('dismiss_crash_reports');
+}
@@ -0,0 +1,56 @@
+// 图片变换参数（宽度、质量、输出格式）
+// weserv/wsrv 图片代理支持通过查询参数实时缩放和转码，按复制格式分别配置后，
+// 生成链接时追加到代理地址上（例如 Markdown 统一输出 800px WebP，URL 保持原图）。
+// 其他图床不识别这些参数，原样返回。
+
+import type { UserConfig, ImageTransformParams } from '../config/types';
+import type { LinkFormat } from './linkFormatter';
+
+/** 支持变换参数的代理域名 */
+const TRANSFORM_HOSTS = ['wsrv.nl', 'images.weserv.nl'];
+
+/** 判断 URL 是否指向支持变换参数的代理 */
+export function supportsImageTransform(url: string): boolean {
+  if (!url) return false;
+  try {
+    return TRANSFORM_HOSTS.includes(new URL(url).hostname.toLowerCase());
+  } catch {
+    return false;
+  }
+}
+
+/**
+ * 给代理 URL 追加变换参数（w / q / output）。
+ * - 非代理域名、未设置任何参数时原样返回
+ * - 已有同名参数时覆盖（模板配置优先）
+ */
+export function applyImageTransform(url: string, params: ImageTransformParams | undefined): string {
+  if (!params || !supportsImageTransform(url)) return url;
+
+  const entries: Array<[string, string]> = [];
+  if (params.width && params.width > 0) entries.push(['w', String(Math.floor(params.width))]);
+  if (params.quality && params.quality > 0) {
+    entries.push(['q', String(Math.min(100, Math.floor(params.quality)))]);
+  }
+  if (params.format) entries.push(['output', params.format]);
+  if (entries.length === 0) return url;
+
+  try {
+    const u = new URL(url);
+    for (const [key, value] of entries) u.searchParams.set(key, value);
+    return u.toString();
+  } catch {
+    return url;
+  }
+}
+
+/** 从 config.linkTransform 读取当前复制格式的变换参数并应用 */
+export function applyImageTransformFromConfig(
+  url: string,
+  format: LinkFormat,
+  config: UserConfig | null | undefined
+): string {
+  const linkTransform = config?.linkTransform;
+  if (!linkTransform?.enabled) return url;
+  return applyImageTransform(url, linkTransform.templates?.[format]);
+}
@@ -9,6 +9,20 @@ export interface LinkFormatContext {
   filename: string;
   width?: number;
   height?: number;
+  /** 替代文本（未填写时为文件名） */
+  alt?: string;
+  /** 图片标题（未填写时为空） */
+  title?: string;
+}
+
+/** 链接格式化附加信息 */
+export interface LinkFormatDetails {
+  width?: number;
+  height?: number;
+  /** 替代文本，替代文件名作为 Markdown alt / HTML alt */
+  altText?: string;
+  /** 图片标题，渲染为 Markdown 链接标题 / HTML title 属性 */
+  title?: string;
 }
 
 /** 链接格式选项（用于 UI 展示） */
@@ -49,31 +63,45 @@ function escapeMarkdownUrl(str: string): string {
   return str.replace(/[()]/g, '\\$&');
 }
 
+function escapeMarkdownTitle(str: string): string {
+  return str.replace(/["\\]/g, '\\$&');
+}
+
 /**
  * 格式化链接
  * @param url 链接地址
  * @param fileName 文件名
  * @param format 格式类型
  * @param customTemplate 自定义模板（format 为 'custom' 时使用）
- * @param dimensions 图片尺寸（可选，用于自定义模板的 {width}/{height} 变量）
+ * @param details 图片尺寸、替代文本与标题（可选；尺寸用于自定义模板的 {width}/{height} 变量）
  */
 export function formatLink(
   url: string,
   fileName: string,
   format: LinkFormat,
   customTemplate?: string,
-  dimensions?: { width?: number; height?: number }
+  details?: LinkFormatDetails
 ): string {
+  const alt = details?.altText?.trim() || fileName;
+  const title = details?.title?.trim();
   switch (format) {
     case 'url': return url;
-    case 'markdown': return `![${escapeMarkdown(fileName)}](${escapeMarkdownUrl(url)})`;
-    case 'html': return `<img src="${escapeHtmlAttr(url)}" alt="${escapeHtmlAttr(fileName)}" />`;
+    case 'markdown': {
+      const titlePart = title ? ` "${escapeMarkdownTitle(title)}"` : '';
+      return `![${escapeMarkdown(alt)}](${escapeMarkdownUrl(url)}${titlePart})`;
+    }
+    case 'html': {
+      const titleAttr = title ? ` title="${escapeHtmlAttr(title)}"` : '';
+      return `<img src="${escapeHtmlAttr(url)}" alt="${escapeHtmlAttr(alt)}"${titleAttr} />`;
+    }
     case 'bbcode': return `[img]${url}[/img]`;
     case 'custom': return applyTemplate(customTemplate || '{url}', {
       url,
       filename: fileName,
-      width: dimensions?.width,
-      height: dimensions?.height,
+      width: details?.width,
+      height: details?.height,
+      alt,
+      title,
     });
     default: return url;
   }
@@ -81,12 +109,14 @@ export function formatLink(
 
 /**
  * 模板变量替换
- * 支持变量：{url}、{filename}、{width}、{height}
+ * 支持变量：{url}、{filename}、{width}、{height}、{alt}、{title}
  */
 export function applyTemplate(template: string, context: LinkFormatContext): string {
   return template
     .replace(/\{url\}/g, context.url)
     .replace(/\{filename\}/g, context.filename)
     .replace(/\{width\}/g, context.width != null ? String(context.width) : '')
-    .replace(/\{height\}/g, context.height != null ? String(context.height) : '');
+    .replace(/\{height\}/g, context.height != null ? String(context.height) : '')
+    .replace(/\{alt\}/g, context.alt ?? context.filename)
+    .replace(/\{title\}/g, context.title ?? '');
 }
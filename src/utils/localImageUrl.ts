@@ -0,0 +1,36 @@
+// 本地图片预览地址（picnexus-file:// 协议）
+// 由 Rust 侧 commands/file_protocol.rs 提供，<img> 直接按 URL 加载本地文件，
+// 不再把整张图读成 base64 经 IPC 传输；max 参数让 Rust 端先缩小再返回
+
+import { convertFileSrc, invoke } from '@tauri-apps/api/core';
+
+export const FILE_PROTOCOL_SCHEME = 'picnexus-file';
+
+let tokenPromise: Promise<string> | null = null;
+
+/** 协议令牌在进程内不变，只请求一次；失败时下次重试 */
+function getFileProtocolToken(): Promise<string> {
+  if (!tokenPromise) {
+    tokenPromise = invoke<string>('get_file_protocol_token').catch((e) => {
+      tokenPromise = null;
+      throw e;
+    });
+  }
+  return tokenPromise;
+}
+
+/**
+ * 生成本地图片的预览地址
+ * @param filePath 本地绝对路径
+ * @param options.maxSide 长边上限（像素），超过时由 Rust 缩小后返回；不传返回原图
+ */
+export async function getLocalImageUrl(filePath: string, options: { maxSide?: number } = {}): Promise<string> {
+  const params = new URLSearchParams({ token: await getFileProtocolToken() });
+  if (options.maxSide && options.maxSide > 0) params.set('max', String(Math.round(options.maxSide)));
+  return `${convertFileSrc(filePath, FILE_PROTOCOL_SCHEME)}?${params.toString()}`;
+}
+
+/** 仅测试使用：清空令牌缓存 */
+export function __resetLocalImageUrlForTests(): void {
+  tokenPromise = null;
+}
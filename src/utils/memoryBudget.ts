@@ -0,0 +1,15 @@
+/**
+ * 图片解码内存预算
+ */
+
+import { invoke } from '@tauri-apps/api/core';
+import type { MemoryBudgetConfig } from '../config/types';
+
+/**
+ * 把内存预算同步到 Rust 侧
+ * 未配置时保持 Rust 内置默认值；未启用时传 null 取消限制
+ */
+export async function applyMemoryBudget(budget: MemoryBudgetConfig | undefined): Promise<void> {
+  if (!budget) return;
+  await invoke('set_memory_budget', { limitMb: budget.enabled ? budget.limitMb : null });
+}
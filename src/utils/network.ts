@@ -2,6 +2,8 @@
  * 网络工具函数
  */
 
+import { invoke } from '@tauri-apps/api/core';
+import type { HttpClientTuningConfig } from '../config/types';
 import { createLogger } from './logger';
 
 const log = createLogger('Network');
@@ -72,3 +74,19 @@ export async function checkNetworkConnectivity(): Promise<boolean> {
     });
   });
 }
+
+/**
+ * 把 HTTP 客户端调优参数同步到 Rust 侧并重建全局客户端
+ * 未启用时传 null，Rust 恢复内置默认值
+ */
+export async function applyHttpClientTuning(tuning: HttpClientTuningConfig | undefined): Promise<void> {
+  const payload = tuning?.enabled
+    ? {
+        poolMaxIdlePerHost: tuning.poolMaxIdlePerHost,
+        connectTimeoutSecs: tuning.connectTimeoutSecs,
+        http2: tuning.http2,
+        tcpKeepaliveSecs: tuning.tcpKeepaliveSecs,
+      }
+    : null;
+  await invoke('set_http_client_tuning', { tuning: payload });
+}
@@ -0,0 +1,154 @@
+// 网络状态监听：断网时暂停上传队列和定时检测，网络恢复并通过连通性探测后再继续
+//
+// navigator.onLine 在 WebView2 下几乎总是 true（见 network.ts），offline / online 事件只当作
+// 「网络可能变了」的信号（飞行模式、切换 VPN 都会触发），是否真的断网以 checkNetworkConnectivity 为准：
+// - offline / online 事件、上传失败时探测一次，探测失败才进入离线状态；
+// - 离线期间按退避间隔反复探测，探测通过即恢复，唤醒所有等待网络的任务；
+// - 系统休眠唤醒后网卡往往还没连上，等待一段随机延迟再探测（见 wakeScheduler.ts）。
+
+import { readonly, ref } from 'vue';
+import { checkNetworkConnectivity } from './network';
+import { createLogger } from './logger';
+import { onSystemResume, resumeDelay } from './wakeScheduler';
+
+const log = createLogger('NetworkMonitor');
+
+/** 离线后首次重新探测的间隔，之后每次翻倍 */
+const PROBE_INITIAL_DELAY_MS = 5_000;
+
+/** 离线探测的最长间隔 */
+const PROBE_MAX_DELAY_MS = 60_000;
+
+/** 最近一次探测成功后的这段时间内，上传失败不再重复探测（批量失败时避免探测风暴） */
+const PROBE_FRESH_MS = 10_000;
+
+const online = ref(true);
+
+/** 网络是否可用（确认断网后为 false，恢复探测通过后为 true） */
+export const isOnline = readonly(online);
+
+let waiters: Array<() => void> = [];
+const listeners = new Set<(online: boolean) => void>();
+let inflightProbe: Promise<boolean> | null = null;
+let probeTimer: ReturnType<typeof setTimeout> | null = null;
+let probeDelay = PROBE_INITIAL_DELAY_MS;
+let lastOnlineAt = 0;
+
+function setOnline(value: boolean): void {
+  if (value) lastOnlineAt = Date.now();
+  if (online.value === value) return;
+  online.value = value;
+
+  if (value) {
+    log.info('网络已恢复，继续暂停的任务');
+    if (probeTimer !== null) {
+      clearTimeout(probeTimer);
+      probeTimer = null;
+    }
+    probeDelay = PROBE_INITIAL_DELAY_MS;
+    const pending = waiters;
+    waiters = [];
+    pending.forEach((resolve) => resolve());
+  } else {
+    log.warn('网络已断开，暂停上传和定时检测');
+    scheduleProbe();
+  }
+  listeners.forEach((listener) => listener(value));
+}
+
+function scheduleProbe(): void {
+  if (probeTimer !== null || online.value) return;
+  probeTimer = setTimeout(async () => {
+    probeTimer = null;
+    if (await checkOnline()) return;
+    probeDelay = Math.min(probeDelay * 2, PROBE_MAX_DELAY_MS);
+    scheduleProbe();
+  }, probeDelay);
+}
+
+/**
+ * 立即探测连通性并更新网络状态（并发调用共用同一次探测）
+ * @returns 网络是否可用
+ */
+export function checkOnline(): Promise<boolean> {
+  inflightProbe ??= checkNetworkConnectivity()
+    .catch(() => false)
+    .then((ok) => {
+      inflightProbe = null;
+      setOnline(ok);
+      return ok;
+    });
+  return inflightProbe;
+}
+
+/**
+ * 请求失败后确认是否断网：刚探测成功过时直接视为在线，否则重新探测
+ * @returns 网络是否可用
+ */
+export function recheckAfterFailure(): Promise<boolean> {
+  if (online.value && Date.now() - lastOnlineAt < PROBE_FRESH_MS) {
+    return Promise.resolve(true);
+  }
+  return checkOnline();
+}
+
+/**
+ * 等待网络可用（在线时立即返回）
+ */
+export function waitUntilOnline(): Promise<void> {
+  if (online.value) return Promise.resolve();
+  return new Promise<void>((resolve) => {
+    waiters.push(resolve);
+  });
+}
+
+/**
+ * 订阅网络状态变化（只在确认断网 / 恢复时通知）
+ * @returns 取消订阅的函数
+ */
+export function onNetworkChange(listener: (online: boolean) => void): () => void {
+  listeners.add(listener);
+  return () => {
+    listeners.delete(listener);
+  };
+}
+
+/** 系统唤醒后重新计时：随机延迟后探测一次，离线则继续按退避间隔探测 */
+function reprobeAfterResume(): void {
+  if (probeTimer !== null) clearTimeout(probeTimer);
+  probeDelay = PROBE_INITIAL_DELAY_MS;
+  probeTimer = setTimeout(async () => {
+    probeTimer = null;
+    if (!(await checkOnline())) scheduleProbe();
+  }, resumeDelay());
+}
+
+/**
+ * 开始监听系统网络事件与休眠唤醒
+ * @returns 停止监听的函数
+ */
+export function startNetworkMonitor(): () => void {
+  const handleChange = () => {
+    void checkOnline();
+  };
+  window.addEventListener('offline', handleChange);
+  window.addEventListener('online', handleChange);
+  const unsubscribeResume = onSystemResume(reprobeAfterResume);
+  return () => {
+    window.removeEventListener('offline', handleChange);
+    window.removeEventListener('online', handleChange);
+    unsubscribeResume();
+  };
+}
+
+/** 仅供测试：恢复初始状态 */
+export function __resetNetworkMonitorForTests(): void {
+  if (probeTimer !== null) clearTimeout(probeTimer);
+  probeTimer = null;
+  probeDelay = PROBE_INITIAL_DELAY_MS;
+  inflightProbe = null;
+  lastOnlineAt = 0;
+  waiters = [];
+  listeners.clear();
+  online.value = true;
+}
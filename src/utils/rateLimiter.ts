@@ -0,0 +1,96 @@
+// 图床级上传限速（每分钟上传次数）
+// 短时间内向同一图床突发大量上传容易触发临时封禁（如微博 10 秒内 50 张），
+// 上传队列在真正发请求前按滑动窗口排队，超出配额的任务等待最早一次上传滑出窗口
+
+import type { UserConfig } from '../config/types';
+
+/** 限速窗口长度 */
+export const RATE_LIMIT_WINDOW_MS = 60_000;
+
+/**
+ * 未单独配置时的默认限速（次/分钟），未列出的图床不限速
+ */
+export const DEFAULT_UPLOAD_RATE_LIMITS: Readonly<Record<string, number>> = {
+  weibo: 20,
+};
+
+/**
+ * 滑动窗口限速器
+ * 等待中的调用按 FIFO 顺序放行，避免后来者插队
+ */
+export class SlidingWindowRateLimiter {
+  private timestamps: number[] = [];
+  private tail: Promise<void> = Promise.resolve();
+
+  /**
+   * 获取一次上传配额（超出配额时等待）
+   * @param limit 每分钟允许的次数，<= 0 表示不限速
+   * @param onWait 需要等待时回调预计等待毫秒数
+   */
+  acquire(limit: number, onWait?: (waitMs: number) => void): Promise<void> {
+    if (!(limit > 0)) return Promise.resolve();
+    const turn = this.tail.then(() => this.take(limit, onWait));
+    this.tail = turn.catch(() => undefined);
+    return turn;
+  }
+
+  /**
+   * 当前窗口内已使用的次数
+   */
+  get used(): number {
+    this.prune(Date.now());
+    return this.timestamps.length;
+  }
+
+  private prune(now: number): void {
+    while (this.timestamps.length > 0 && now - this.timestamps[0] >= RATE_LIMIT_WINDOW_MS) {
+      this.timestamps.shift();
+    }
+  }
+
+  private async take(limit: number, onWait?: (waitMs: number) => void): Promise<void> {
+    for (;;) {
+      const now = Date.now();
+      this.prune(now);
+      if (this.timestamps.length < limit) {
+        this.timestamps.push(now);
+        return;
+      }
+      // 配额降低后窗口内可能超出多条，需等到第 (length - limit + 1) 条滑出
+      const waitMs = this.timestamps[this.timestamps.length - limit] + RATE_LIMIT_WINDOW_MS - now;
+      onWait?.(waitMs);
+      await new Promise<void>((resolve) => setTimeout(resolve, waitMs));
+    }
+  }
+}
+
+// ========== 图床级限速器 ==========
+
+const serviceRateLimiters: Map<string, SlidingWindowRateLimiter> = new Map();
+
+/**
+ * 获取指定图床的限速器（全局共享，跨批次累计）
+ * @param serviceId 图床服务 ID
+ */
+export function getServiceRateLimiter(serviceId: string): SlidingWindowRateLimiter {
+  if (!serviceRateLimiters.has(serviceId)) {
+    serviceRateLimiters.set(serviceId, new SlidingWindowRateLimiter());
+  }
+  return serviceRateLimiters.get(serviceId)!;
+}
+
+/**
+ * 解析图床的每分钟上传上限（0 表示不限速）
+ */
+export function resolveUploadRateLimit(serviceId: string, config: UserConfig): number {
+  const configured = config.uploadRateLimits?.[serviceId];
+  if (typeof configured === 'number' && Number.isFinite(configured)) {
+    return Math.max(0, Math.floor(configured));
+  }
+  return DEFAULT_UPLOAD_RATE_LIMITS[serviceId] ?? 0;
+}
+
+/** 仅供测试：清空所有限速器状态 */
+export function __resetRateLimitersForTests(): void {
+  serviceRateLimiters.clear();
+}
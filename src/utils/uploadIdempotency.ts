@@ -0,0 +1,101 @@
+// 上传幂等：同一队列条目在同一图床上只上传一次
+//
+// 幂等键由客户端生成（队列条目 ID + 图床 ID），同一条目的每次上传尝试共用：
+// - 部分图床失败后「全量重试」会把所有图床重新上传一遍，已成功的图床直接复用上次结果；
+// - 同一个键的上传还在进行时再次发起，等待进行中的那次，不并行重复上传。
+//
+// S3 类图床（R2 / COS / OSS / 七牛 / 自定义 S3）的对象 Key 由路径 + 文件名决定，
+// 重试会覆盖同一对象，本身即幂等；其余图床没有服务端幂等接口，只能靠这里的本地去重。
+
+import type { UploadResult } from '../uploaders/base/types';
+import { createLogger } from './logger';
+
+const log = createLogger('UploadIdempotency');
+
+/** 成功结果的保留时长（覆盖一次上传会话内的重试） */
+const RESULT_TTL_MS = 24 * 60 * 60 * 1000;
+
+/** 最多保留的成功结果数，超出时淘汰最早的 */
+const MAX_RESULTS = 1000;
+
+interface CompletedUpload {
+  result: UploadResult;
+  expiresAt: number;
+}
+
+const completed = new Map<string, CompletedUpload>();
+const pending = new Map<string, Promise<UploadResult>>();
+
+/**
+ * 生成幂等键
+ * @param scope 上传范围（通常是队列条目 ID）
+ * @param serviceId 图床 ID
+ */
+export function uploadIdempotencyKey(scope: string, serviceId: string): string {
+  return `${scope}:${serviceId}`;
+}
+
+/**
+ * 查找该键之前成功的上传结果；未传键或已过期时返回 undefined
+ */
+export function getCompletedUpload(key: string | undefined): UploadResult | undefined {
+  if (!key) return undefined;
+  const entry = completed.get(key);
+  if (!entry) return undefined;
+  if (entry.expiresAt <= Date.now()) {
+    completed.delete(key);
+    return undefined;
+  }
+  return entry.result;
+}
+
+function remember(key: string, result: UploadResult): void {
+  completed.delete(key);
+  completed.set(key, { result, expiresAt: Date.now() + RESULT_TTL_MS });
+  // Map 按插入顺序迭代，第一个即最早的结果
+  while (completed.size > MAX_RESULTS) {
+    const oldest = completed.keys().next().value;
+    if (oldest === undefined) break;
+    completed.delete(oldest);
+  }
+}
+
+/**
+ * 以幂等键执行上传：已成功过则复用结果，同键上传进行中则等待它完成
+ * 未传键时直接执行
+ */
+export function runIdempotent(
+  key: string | undefined,
+  upload: () => Promise<UploadResult>,
+): Promise<UploadResult> {
+  if (!key) return upload();
+
+  const reused = getCompletedUpload(key);
+  if (reused) {
+    log.info(`${key} 已上传成功，复用上次结果`);
+    return Promise.resolve(reused);
+  }
+
+  const inFlight = pending.get(key);
+  if (inFlight) {
+    log.info(`${key} 正在上传，等待进行中的上传完成`);
+    return inFlight;
+  }
+
+  const task = upload()
+    .then((result) => {
+      remember(key, result);
+      return result;
+    })
+    .finally(() => {
+      pending.delete(key);
+    });
+  pending.set(key, task);
+  return task;
+}
+
+/** 仅供测试：清空幂等记录 */
+export function __resetUploadIdempotencyForTests(): void {
+  completed.clear();
+  pending.clear();
+}
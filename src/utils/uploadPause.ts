@@ -0,0 +1,41 @@
+// 上传队列手动暂停：托盘 / 跳转列表 / macOS 菜单的「暂停队列」与队列面板的暂停按钮共用
+//
+// 暂停只拦截尚未开始传输的上传（在限速排队之后、发请求之前等待），已发出的请求照常完成；
+// 继续后唤醒所有等待中的上传。
+
+import { readonly, ref } from 'vue';
+import { createLogger } from './logger';
+
+const log = createLogger('UploadPause');
+
+const paused = ref(false);
+
+/** 上传队列是否被手动暂停 */
+export const isUploadQueuePaused = readonly(paused);
+
+let waiters: Array<() => void> = [];
+
+/** 暂停上传队列（已暂停时为空操作） */
+export function pauseUploadQueue(): void {
+  if (paused.value) return;
+  paused.value = true;
+  log.info('上传队列已暂停');
+}
+
+/** 继续上传队列，唤醒等待中的上传 */
+export function resumeUploadQueue(): void {
+  if (!paused.value) return;
+  paused.value = false;
+  log.info('上传队列已继续');
+  const pending = waiters;
+  waiters = [];
+  pending.forEach((resolve) => resolve());
+}
+
+/** 队列暂停期间挂起，继续后返回；未暂停时立即返回 */
+export function waitUntilQueueResumed(): Promise<void> {
+  if (!paused.value) return Promise.resolve();
+  return new Promise((resolve) => {
+    waiters.push(resolve);
+  });
+}
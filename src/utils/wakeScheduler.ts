@@ -0,0 +1,109 @@
+// 系统休眠 / 唤醒后的定时任务调度
+//
+// 后端 power_events 检测到整机休眠后发出 system-resumed 事件。唤醒时有两个问题：
+// - 休眠期间 WebView 内的定时器是否计时因平台而异，12 小时一次的检测可能被推迟好几个小时；
+// - 过期的定时检测、离线探测会在唤醒瞬间同时触发，而此时网卡往往还没连上，全部白白失败。
+//
+// scheduleInterval 按墙上时间（Date.now）记录下次执行时刻：唤醒时已过期的任务在随机延迟后执行，
+// 各任务错开；未过期的任务按剩余时间重新计时。
+
+import { listen } from '@tauri-apps/api/event';
+import { createLogger } from './logger';
+
+const log = createLogger('WakeScheduler');
+
+/** 后端唤醒事件名（与 power_events.rs 保持一致） */
+export const SYSTEM_RESUMED_EVENT = 'system-resumed';
+
+/** 唤醒后至少等待的时间（给网卡重新连接留出余量） */
+const RESUME_BASE_DELAY_MS = 5_000;
+
+/** 唤醒后在基础延迟上再随机分散的范围 */
+const RESUME_SPREAD_MS = 30_000;
+
+const resumeHandlers = new Set<(sleptMs: number) => void>();
+
+/**
+ * 唤醒后执行过期任务前的随机延迟
+ */
+export function resumeDelay(): number {
+  return RESUME_BASE_DELAY_MS + Math.floor(Math.random() * RESUME_SPREAD_MS);
+}
+
+/**
+ * 订阅系统唤醒
+ * @returns 取消订阅的函数
+ */
+export function onSystemResume(handler: (sleptMs: number) => void): () => void {
+  resumeHandlers.add(handler);
+  return () => {
+    resumeHandlers.delete(handler);
+  };
+}
+
+/**
+ * 通知所有订阅者系统已唤醒
+ * @param sleptMs 估算的休眠时长
+ */
+export function notifySystemResume(sleptMs: number): void {
+  log.info(`系统已唤醒（休眠约 ${Math.round(sleptMs / 1000)} 秒），错开执行定时任务`);
+  resumeHandlers.forEach((handler) => handler(sleptMs));
+}
+
+/**
+ * 开始监听后端唤醒事件
+ * @returns 停止监听的函数
+ */
+export async function startWakeListener(): Promise<() => void> {
+  return listen<{ sleptMs: number }>(SYSTEM_RESUMED_EVENT, (event) => {
+    notifySystemResume(event.payload.sleptMs);
+  });
+}
+
+/**
+ * 按墙上时间周期执行任务，系统唤醒后错开执行过期任务
+ *
+ * @param task 周期任务
+ * @param intervalMs 执行间隔
+ * @param initialDelayMs 首次执行的延迟（默认等于 intervalMs）
+ * @returns 停止调度的函数
+ */
+export function scheduleInterval(
+  task: () => void,
+  intervalMs: number,
+  initialDelayMs: number = intervalMs,
+): () => void {
+  let timer: ReturnType<typeof setTimeout> | null = null;
+  let dueAt = 0;
+
+  const arm = (delay: number) => {
+    if (timer !== null) clearTimeout(timer);
+    dueAt = Date.now() + delay;
+    timer = setTimeout(run, delay);
+  };
+
+  const run = () => {
+    timer = null;
+    arm(intervalMs);
+    task();
+  };
+
+  const unsubscribe = onSystemResume(() => {
+    const remaining = dueAt - Date.now();
+    // 未过期的任务保持原来的执行时刻，但不早于唤醒后的随机延迟
+    arm(Math.max(remaining, resumeDelay()));
+  });
+
+  arm(initialDelayMs);
+
+  return () => {
+    unsubscribe();
+    if (timer !== null) clearTimeout(timer);
+    timer = null;
+  };
+}
+
+/** 仅供测试：清空唤醒订阅 */
+export function __resetWakeSchedulerForTests(): void {
+  resumeHandlers.clear();
+}
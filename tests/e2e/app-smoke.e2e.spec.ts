@@ -135,7 +135,7 @@ test.describe('PicNexus Tauri smoke', () => {
     await expect.poll(async () => {
       const state = await getE2EState(page);
       return state.calls.some((call) => call.type === 'invoke' && call.command === 'plugin:autostart|enable')
-        && state.calls.some((call) => call.type === 'fs.writeTextFile' && call.path === SETTINGS_PATH);
+        && state.calls.some((call) => call.type === 'fs.rename' && call.to === SETTINGS_PATH);
     }).toBe(true);
   });
 
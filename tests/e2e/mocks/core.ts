@@ -10,6 +10,9 @@ export async function invoke<T = unknown>(command: string, args?: unknown): Prom
       return SECURE_KEY as T;
     case 'set_secure_key':
     case 'set_close_to_tray':
+    case 'set_http_client_tuning':
+    case 'set_memory_budget':
+    case 'clear_thumbnail_cache':
     case 'plugin:autostart|enable':
     case 'plugin:autostart|disable':
     case 'update_server_config':
@@ -19,11 +22,36 @@ export async function invoke<T = unknown>(command: string, args?: unknown): Prom
     case 'resume_batch_check':
     case 'register_global_shortcut':
     case 'unregister_global_shortcut':
+    case 'record_host_outcome':
+    case 'reveal_in_file_manager':
+    case 'open_with_default_app':
+    case 'trash_file':
+    case 'start_drag_out':
+    case 'record_recent_dir':
+    case 'set_object_name_rules':
+    case 'set_post_upload_action':
       return undefined as T;
+    case 'cancel_task':
+    case 'cancel_stalled_task':
+    case 'delete_link_template':
+      return false as T;
+    case 'list_link_templates':
+    case 'palette_query':
+      return [] as T;
+    case 'save_link_template':
+      return { id: 'mock-template', name: 'Mock', template: '{url}' } as T;
+    case 'render_link':
+      return 'https://mock.cdn/picnexus/release-smoke.png' as T;
+    case 'get_pending_crash_reports':
+      return { dir: '', reports: [] } as T;
+    case 'dismiss_crash_reports':
+      return 0 as T;
     case 'plugin:autostart|is_enabled':
       return false as T;
     case 'get_executable_path':
       return 'C:\\PicNexus\\PicNexus.exe' as T;
+    case 'export_diagnostics':
+      return 'C:\\PicNexus\\picnexus-diagnostics.zip' as T;
     case 'encrypt_webdav_password':
       return 'encrypted-webdav-password' as T;
     case 'decrypt_webdav_password':
@@ -37,6 +65,38 @@ export async function invoke<T = unknown>(command: string, args?: unknown): Prom
     case 'check_jd_available':
     case 'check_qiyu_available':
       return true as T;
+    case 'get_usage_stats':
+      return {
+        fromMs: null,
+        toMs: null,
+        uploads: 0,
+        bytes: 0,
+        averageBytes: 0,
+        activeDays: 0,
+        busiestDay: null,
+        firstUploadMs: null,
+        lastUploadMs: null,
+        hosts: [],
+      } as T;
+    case 'test_host_connection':
+      return {
+        profileId: 'r2',
+        ok: true,
+        probe: 'bucket',
+        latencyMs: 42,
+        message: '连接成功！',
+        errorCode: null,
+      } as T;
+    case 'stat_path':
+      return {
+        exists: true,
+        kind: 'file',
+        size: 24_576,
+        modifiedMs: Date.now(),
+        readable: true,
+        looksLikeImage: true,
+        format: 'png',
+      } as T;
     case 'get_image_metadata':
       return {
         width: 640,
@@ -44,12 +104,31 @@ export async function invoke<T = unknown>(command: string, args?: unknown): Prom
         aspect_ratio: 640 / 480,
         file_size: 24_576,
         format: 'png',
+        orientation: 1,
+      } as T;
+    case 'get_image_exif':
+      return null as T;
+    case 'compare_images':
+      return {
+        widthA: 640,
+        heightA: 480,
+        widthB: 640,
+        heightB: 480,
+        sameDimensions: true,
+        sameHash: true,
+        similarity: 1,
+        matches: true,
       } as T;
     case 'upload_to_jd':
       return {
         url: 'https://mock.cdn/picnexus/release-smoke.png',
         size: 24_576,
       } as T;
+    case 'upload_to_aliyun_oss':
+      return {
+        url: 'https://mock.oss.example.com/images/release-smoke.png',
+        key: 'images/release-smoke.png',
+      } as T;
     case 'batch_check_links':
       return {
         total: 0,
@@ -67,10 +146,98 @@ export async function invoke<T = unknown>(command: string, args?: unknown): Prom
       return '/mock/files/clipboard.png' as T;
     case 'get_clipboard_image':
       return null as T;
+    case 'rehost_clipboard_url':
+      return {
+        sourceUrl: 'https://example.com/mock.png',
+        service: 'r2',
+        url: 'https://mock.example.com/rehosted.png',
+      } as T;
     case 'download_url_image':
       return { file_path: '/mock/files/downloaded.png' } as T;
     case 'download_url_to_temp':
       return null as T;
+    case 'get_host_capabilities':
+    case 'get_host_health':
+    case 'list_uploaders':
+    case 'get_recent_dirs':
+    case 'get_cache_usage':
+    case 'format_timestamps':
+    case 'take_pending_retries':
+    case 'list_capturable_windows':
+    case 'list_upload_providers':
+      return [] as T;
+    case 'upload_image':
+      return { providerId: 'r2', url: 'https://mock.example.com/a.png' } as T;
+    case 'save_smms_token':
+      return true as T;
+    case 'delete_smms_image':
+      return undefined as T;
+    case 'get_smms_quota':
+      return {
+        username: 'mock',
+        role: 'user',
+        diskUsage: '1.00 MB',
+        diskLimit: '5.00 GB',
+        usedBytes: 1048576,
+        limitBytes: 5368709120,
+        remainingBytes: 5367660544,
+      } as T;
+    case 'capture_window':
+      return {
+        id: 1,
+        title: 'Mock Window',
+        appName: 'Mock',
+        width: 800,
+        height: 600,
+        filePath: '/mock/files/window_capture.png',
+        queued: false,
+      } as T;
+    case 'suggest_alt_text':
+      return { text: 'mock alt text', source: 'filename' } as T;
+    case 'retry_failed':
+      return { queued: 0, services: 0, skippedMissing: 0, deferred: 0, lastStartAt: null } as T;
+    case 'clear_cache':
+      return { kind: 'temp', files: 0, freedBytes: 0 } as T;
+    case 'prefetch_thumbnails':
+      return { generation: 1, queued: 0 } as T;
+    case 'get_object_name_rules':
+      return {} as T;
+    case 'get_post_upload_action':
+      return null as T;
+    case 'sanitize_object_name':
+      return (args as { fileName: string }).fileName as T;
+    case 'create_share_page':
+      return {
+        id: '0123456789abcdef0123456789abcdef',
+        url: 'https://mock.example.com/share/0123456789abcdef0123456789abcdef.html',
+        target: 'host',
+        service: 'r2',
+        images: 1,
+      } as T;
+    case 'export_preset':
+      return { path: '/mock/files/preset.json', hosts: 1, templates: 0, strippedFields: [], signed: false } as T;
+    case 'read_preset':
+      return {
+        name: 'Mock Preset',
+        description: '',
+        hosts: {},
+        linkTemplates: [],
+        strippedFields: [],
+        signed: false,
+        signature: 'sha256:mock',
+      } as T;
+    case 'import_preset':
+      return {
+        name: 'Mock Preset',
+        description: '',
+        hosts: {},
+        templatesAdded: 0,
+        templatesUpdated: 0,
+        strippedFields: [],
+        signed: false,
+      } as T;
+    case 'get_file_protocol_token':
+      return 'e2e-token' as T;
     default:
       return undefined as T;
   }
@@ -19,16 +19,31 @@ export async function readTextFile(path: string): Promise<string> {
   return getState().files[path];
 }
 
+/** 原子写入的临时文件（`<目标>.<时间戳>.tmp`）按目标路径匹配注入的写入失败 */
+function atomicTarget(path: string): string {
+  return path.replace(/\.\d+\.tmp$/, '');
+}
+
 export async function writeTextFile(path: string, contents: string): Promise<void> {
   record({ type: 'fs.writeTextFile', path, contents });
-  const failure = getState().failWriteTextFile[path];
+  const target = atomicTarget(path);
+  const failure = getState().failWriteTextFile[target];
   if (failure) {
-    delete getState().failWriteTextFile[path];
+    delete getState().failWriteTextFile[target];
     throw new Error(failure);
   }
   getState().files[path] = contents;
 }
 
+export async function rename(from: string, to: string): Promise<void> {
+  record({ type: 'fs.rename', from, to });
+  if (!(await exists(from))) {
+    throw new Error(`file not found: ${from}`);
+  }
+  getState().files[to] = getState().files[from];
+  delete getState().files[from];
+}
+
 export async function remove(path: string): Promise<void> {
   delete getState().files[path];
 }
@@ -23,6 +23,11 @@ const HISTORY_COLUMNS = [
   'successful_service_ids',
   'migration_skip',
   'link_check_skip',
+  'short_url',
+  'alt_text',
+  'title',
+  'alt_suggestion',
+  'capture_date',
 ];
 
 export default class Database {
@@ -6,6 +6,7 @@ type E2ECall =
   | { type: 'clipboard.writeText'; text: string }
   | { type: 'fs.readTextFile'; path: string }
   | { type: 'fs.writeTextFile'; path: string; contents: string }
+  | { type: 'fs.rename'; from: string; to: string }
   | { type: 'event.emit'; event: string; payload?: unknown };
 
 interface E2EState {
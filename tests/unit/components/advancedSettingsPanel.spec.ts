@@ -26,6 +26,18 @@ const CliCardStub = {
   template: '<div class="cli-stub">命令行 CLI</div>',
 };
 
+const ShortLinkCardStub = {
+  template: '<div class="short-link-stub">短链接</div>',
+};
+
+const LinkRepairWebhookCardStub = {
+  template: '<div class="link-repair-webhook-stub">链接修复 Webhook</div>',
+};
+
+const PluginUploadersCardStub = {
+  template: '<div class="plugin-uploaders-stub">插件图床</div>',
+};
+
 describe('AdvancedSettingsPanel', () => {
   const baseProps = {
     imageCompression: { ...DEFAULT_CONFIG.imageCompression! },
@@ -40,6 +52,9 @@ describe('AdvancedSettingsPanel', () => {
         stubs: {
           ImageCompressionPanel: ImageCompressionStub,
           CliCard: CliCardStub,
+          ShortLinkCard: ShortLinkCardStub,
+          LinkRepairWebhookCard: LinkRepairWebhookCardStub,
+          PluginUploadersCard: PluginUploadersCardStub,
           ExternalEditorPanel: ExternalEditorStub,
         },
       },
@@ -50,9 +65,13 @@ describe('AdvancedSettingsPanel', () => {
     expect(wrapper.text()).toContain('上传处理');
     expect(wrapper.text()).toContain('控制图片进入图床前的处理方式。');
     expect(wrapper.text()).toContain('图片压缩');
+    expect(wrapper.text()).toContain('链接输出');
+    expect(wrapper.text()).toContain('短链接');
     expect(wrapper.text()).toContain('外部集成');
     expect(wrapper.text()).toContain('让 PicNexus 从终端、脚本或编辑器中触发上传。');
     expect(wrapper.text()).toContain('命令行 CLI');
+    expect(wrapper.text()).toContain('链接修复 Webhook');
+    expect(wrapper.text()).toContain('插件图床');
     expect(wrapper.text()).not.toContain('定制你的工作流');
     expect(wrapper.text()).not.toContain('在终端指定图床上传，适合脚本和外部工具调用。');
     expect(wrapper.text()).not.toContain('外部编辑器');
@@ -65,6 +84,9 @@ describe('AdvancedSettingsPanel', () => {
         stubs: {
           ImageCompressionPanel: ImageCompressionStub,
           CliCard: CliCardStub,
+          ShortLinkCard: ShortLinkCardStub,
+          LinkRepairWebhookCard: LinkRepairWebhookCardStub,
+          PluginUploadersCard: PluginUploadersCardStub,
           ExternalEditorPanel: ExternalEditorStub,
         },
       },
@@ -82,6 +104,9 @@ describe('AdvancedSettingsPanel', () => {
         stubs: {
           ImageCompressionPanel: ImageCompressionStub,
           CliCard: CliCardStub,
+          ShortLinkCard: ShortLinkCardStub,
+          LinkRepairWebhookCard: LinkRepairWebhookCardStub,
+          PluginUploadersCard: PluginUploadersCardStub,
           ExternalEditorPanel: ExternalEditorStub,
         },
       },
@@ -92,6 +117,7 @@ describe('AdvancedSettingsPanel', () => {
     expect(html.indexOf('图片压缩')).toBeLessThan(html.indexOf('外部集成'));
     expect(html.indexOf('外部集成')).toBeLessThan(html.indexOf('命令行 CLI'));
     expect(html.indexOf('compression-stub')).toBeLessThan(html.indexOf('cli-stub'));
+    expect(html.indexOf('cli-stub')).toBeLessThan(html.indexOf('link-repair-webhook-stub'));
     expect(html.indexOf('cli-stub')).toBeLessThan(html.indexOf('editor-stub'));
   });
 });
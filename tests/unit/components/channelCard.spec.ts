@@ -151,4 +151,27 @@ describe('ChannelCard tooltip', () => {
       }],
     ]);
   });
+
+  it('格式菜单末尾的二维码项会透传 qr payload', async () => {
+    const wrapper = mountWithDefaults(ChannelCard, {
+      props: {
+        service: 'weibo',
+        status: '✓ 完成',
+        link: 'https://example.com/a.jpg',
+        fileName: 'a.jpg',
+      },
+    });
+
+    await wrapper.get('.copy-menu-btn').trigger('click');
+    await wrapper.findAll('.format-item').at(-1)!.trigger('click');
+
+    expect(wrapper.emitted('copy')).toBeUndefined();
+    expect(wrapper.emitted('qr')).toEqual([[
+      {
+        url: 'https://example.com/a.jpg',
+        serviceId: 'weibo',
+        fileName: 'a.jpg',
+      },
+    ]]);
+  });
 });
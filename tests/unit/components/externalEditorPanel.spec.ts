@@ -248,7 +248,7 @@ describe('ExternalEditorPanel', () => {
     await flush();
 
     const text = wrapper.text();
-    expect(text).toContain('--profile typora');
+    expect(text).toContain('--typora');
     expect(text).not.toContain('CLI 开启');
     expect(text).not.toContain('CLI 关闭');
     expect(text).not.toContain('--service <图床名>');
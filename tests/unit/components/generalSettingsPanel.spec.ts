@@ -1,6 +1,8 @@
 import { describe, expect, it } from 'vitest';
 import { defineComponent } from 'vue';
+import { flushPromises } from '@vue/test-utils';
 import { mountWithDefaults } from '../helpers/vueMount';
+import { getInvokeMock } from '../helpers/tauriMock';
 import GeneralSettingsPanel from '@/components/settings/GeneralSettingsPanel.vue';
 
 const ButtonStub = defineComponent({
@@ -24,6 +26,13 @@ const InputTextStub = defineComponent({
   template: '<input :value="modelValue" @input="$emit(\'update:modelValue\', $event.target.value)" />',
 });
 
+const SelectStub = defineComponent({
+  name: 'Select',
+  props: ['modelValue', 'options'],
+  emits: ['update:modelValue'],
+  template: '<select class="select-stub" :value="modelValue" @change="$emit(\'update:modelValue\', $event.target.value)"><option v-for="o in options" :key="o.value" :value="o.value">{{ o.label }}</option></select>',
+});
+
 const DividerStub = defineComponent({
   name: 'Divider',
   template: '<hr />',
@@ -59,6 +68,7 @@ function mountPanel(overrides: Record<string, unknown> = {}) {
         Button: ButtonStub,
         ToggleSwitch: ToggleSwitchStub,
         InputText: InputTextStub,
+        Select: SelectStub,
         Divider: DividerStub,
         ShortcutInput: ShortcutInputStub,
       },
@@ -79,10 +89,12 @@ describe('GeneralSettingsPanel', () => {
     const wrapper = mountPanel({ linkDefaultFormat: 'custom' });
 
     await wrapper.findAll('.format-card')[1].trigger('click');
-    await wrapper.findAll('.toggle-stub')[4].trigger('click');
+    await wrapper.get('.select-stub').setValue('none');
+    await flushPromises();
     await wrapper.get('.template-input').setValue('');
 
     expect(wrapper.emitted('update:linkDefaultFormat')).toEqual([['markdown']]);
+    expect(getInvokeMock()).toHaveBeenCalledWith('set_post_upload_action', { action: 'none' });
     expect(wrapper.emitted('update:linkAutoCopy')).toEqual([[false]]);
     expect(wrapper.emitted('update:linkCustomTemplate')).toEqual([['{url}']]);
     expect(wrapper.emitted('save')).toHaveLength(3);
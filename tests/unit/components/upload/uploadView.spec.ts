@@ -24,6 +24,8 @@ const mockState = vi.hoisted(() => ({
   retryAllFailed: vi.fn(),
   setRetryCallback: vi.fn(),
   configUnlisten: vi.fn(),
+  recoverInterruptedQueue: vi.fn(),
+  stopQueueJournal: vi.fn(),
   uploadManager: undefined as any,
   queueState: undefined as any,
   serviceHealth: undefined as any,
@@ -71,6 +73,11 @@ vi.mock('@/store/instances', () => ({
   configStore: { get: mockState.configGet },
 }));
 
+vi.mock('@/composables/upload/QueueRecovery', () => ({
+  recoverInterruptedQueue: mockState.recoverInterruptedQueue,
+  startQueueJournal: () => mockState.stopQueueJournal,
+}));
+
 vi.mock('@/composables/useConfig', () => ({
   useConfigManager: () => ({ saveConfig: mockState.saveConfig }),
 }));
@@ -210,6 +217,7 @@ beforeEach(() => {
     return true;
   });
   mockState.retryAllFailed.mockResolvedValue(undefined);
+  mockState.recoverInterruptedQueue.mockResolvedValue(null);
 
   mockState.uploadManager = {
     activePrefix: ref('markdown'),
@@ -379,5 +387,21 @@ describe('UploadView page interactions', () => {
     expect(mockState.setRetryCallback).toHaveBeenCalledWith(expect.any(Function));
     wrapper.unmount();
     expect(mockState.configUnlisten).toHaveBeenCalled();
+    expect(mockState.stopQueueJournal).toHaveBeenCalled();
+  });
+
+  it('offers to re-upload files left unfinished by the previous run', async () => {
+    mockState.recoverInterruptedQueue.mockResolvedValue({
+      recorded: [],
+      resumable: [{ id: 'queue-1', filePath: 'C:/tmp/left.png' }],
+      missing: [],
+    });
+    await mountView();
+
+    expect(mockState.showConfirm).toHaveBeenCalledWith(
+      expect.objectContaining({ header: '恢复未完成的上传', acceptLabel: '重新上传' }),
+    );
+    mockState.showConfirm.mock.calls[0][0].accept();
+    expect(mockState.handleFilesUpload).toHaveBeenCalledWith(['C:/tmp/left.png']);
   });
 });
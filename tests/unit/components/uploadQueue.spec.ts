@@ -38,6 +38,7 @@ vi.mock('@/composables/useConfig', async () => {
 vi.mock('@/composables/useCopyLink', () => ({
   useCopyLink: () => ({
     copyLink: copyLinkMock,
+    applyConfiguredUrl: (url: string) => url,
   }),
 }));
 
@@ -8,7 +8,7 @@ import { getInvokeMock } from '../../helpers/tauriMock';
 
 vi.mock('@/services/HistoryDatabase', () => ({
   historyDB: {
-    update: vi.fn(),
+    modify: vi.fn(),
   },
 }));
 
@@ -76,6 +76,10 @@ describe('migrateOneItem', () => {
   beforeEach(() => {
     vi.clearAllMocks();
     getInvokeMock().mockReset();
+    vi.mocked(historyDB.modify).mockImplementation(async (_id, mutator) => {
+      const latest = createItem();
+      return { ...latest, ...mutator(latest) };
+    });
   });
 
   it('skips item when every selected target already exists', async () => {
@@ -100,7 +104,7 @@ describe('migrateOneItem', () => {
 
     expect(status.status).toBe('skipped');
     expect(getInvokeMock()).not.toHaveBeenCalled();
-    expect(historyDB.update).not.toHaveBeenCalled();
+    expect(historyDB.modify).not.toHaveBeenCalled();
   });
 
   it('records success with per-target failure without blocking successful targets', async () => {
@@ -111,6 +115,17 @@ describe('migrateOneItem', () => {
     const item = createItem({
       linkCheckSummary: { totalLinks: 1, validLinks: 1, invalidLinks: 0, uncheckedLinks: 0 },
     });
+    // 迁移期间记录被其他流程追加了一条结果，写回时应基于最新记录合并
+    const latest = createItem({
+      results: [...item.results, { serviceId: 'jd', status: 'success', result: uploadResult('jd', 'https://jd.example/a.png') }],
+      linkCheckSummary: { totalLinks: 2, validLinks: 2, invalidLinks: 0, uncheckedLinks: 0 },
+    });
+    const writes: Array<Partial<HistoryItem> | null> = [];
+    vi.mocked(historyDB.modify).mockImplementationOnce(async (_id, mutator) => {
+      const updates = mutator(latest);
+      writes.push(updates);
+      return { ...latest, ...updates };
+    });
 
     await migrateOneItem(
       item,
@@ -127,13 +142,15 @@ describe('migrateOneItem', () => {
     expect(status.status).toBe('success');
     expect(status.serviceResults).toEqual({ r2: 'success', github: 'failed' });
     expect(onTargetSettled).toHaveBeenCalledTimes(2);
-    expect(historyDB.update).toHaveBeenCalledWith('h1', expect.objectContaining({
-      linkCheckSummary: expect.objectContaining({ totalLinks: 2, uncheckedLinks: 1 }),
-    }));
-    expect(vi.mocked(historyDB.update).mock.calls[0][1].results).toEqual(expect.arrayContaining([
+    expect(historyDB.modify).toHaveBeenCalledWith('h1', expect.any(Function));
+    expect(writes[0]).toMatchObject({
+      linkCheckSummary: { totalLinks: 3, uncheckedLinks: 1 },
+    });
+    expect(writes[0]?.results).toEqual([
+      ...latest.results,
       expect.objectContaining({ serviceId: 'r2', status: 'success' }),
       expect.objectContaining({ serviceId: 'github', status: 'failed', error: 'bad token' }),
-    ]));
+    ]);
   });
 
   it('marks upload failure when every target fails', async () => {
@@ -158,7 +175,7 @@ describe('migrateOneItem', () => {
       expect.objectContaining({ serviceId: 'r2' }),
       expect.objectContaining({ serviceId: 'github' }),
     ]);
-    expect(historyDB.update).not.toHaveBeenCalled();
+    expect(historyDB.modify).not.toHaveBeenCalled();
   });
 
   it('marks download failure and does not upload or update history', async () => {
@@ -180,7 +197,7 @@ describe('migrateOneItem', () => {
     expect(status.status).toBe('failed');
     expect(status.errorType).toBe('download');
     expect(uploader.retryUpload).not.toHaveBeenCalled();
-    expect(historyDB.update).not.toHaveBeenCalled();
+    expect(historyDB.modify).not.toHaveBeenCalled();
   });
 
   it('cleans downloaded temp file and returns to pending when paused after download', async () => {
@@ -418,7 +435,7 @@ describe('migrateOneItem', () => {
     expect(status.error).toBe('检测结果已更新，不再满足可恢复图片条件');
     expect(status.errorType).toBeUndefined();
     expect(getInvokeMock()).not.toHaveBeenCalled();
-    expect(historyDB.update).not.toHaveBeenCalled();
+    expect(historyDB.modify).not.toHaveBeenCalled();
   });
 });
 
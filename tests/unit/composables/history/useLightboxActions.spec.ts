@@ -177,6 +177,21 @@ describe('useLightboxActions', () => {
     expect(toastWarnMock).toHaveBeenCalledTimes(1);
   });
 
+  it('copies the requested Weibo size variant derived from the PID', async () => {
+    const harness = mountHarness();
+
+    await harness.api().handleCopyWeiboSize('mw690');
+
+    expect(copyLinkActionMock).toHaveBeenCalledWith({
+      url: 'https://tvax1.sinaimg.cn/mw690/demo-key.jpg',
+      fileName: 'demo.png',
+      serviceId: 'weibo',
+      width: 100,
+      height: 80,
+    }, { showSuccessToast: false });
+    expect(harness.api().copySuccess.value).toBe(true);
+  });
+
   it('opens the prefixed URL in the browser and reports failures through the toast', async () => {
     const harness = mountHarness();
 
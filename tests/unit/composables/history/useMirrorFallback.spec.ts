@@ -22,18 +22,39 @@ const {
   dbRemoveMirrorMock,
   dbGetByIdMock,
   dbUpdateMock,
-} = vi.hoisted(() => ({
-  toastSuccessMock: vi.fn(),
-  toastWarnMock: vi.fn(),
-  toastErrorMock: vi.fn(),
-  confirmDeleteMock: vi.fn(),
-  invalidateCacheMock: vi.fn(),
-  emitHistoryUpdatedMock: vi.fn().mockResolvedValue(undefined),
-  dbSwitchPrimaryMock: vi.fn().mockResolvedValue(undefined),
-  dbRemoveMirrorMock: vi.fn().mockResolvedValue(undefined),
-  dbGetByIdMock: vi.fn(),
-  dbUpdateMock: vi.fn().mockResolvedValue(undefined),
-}));
+  dbModifyMock,
+} = vi.hoisted(() => {
+  const dbGetByIdMock = vi.fn();
+  const dbUpdateMock = vi.fn().mockResolvedValue(undefined);
+  // 模拟 historyDB.modify 的契约：写锁内按调用顺序 读最新 → 合并 → 写
+  let writeChain: Promise<unknown> = Promise.resolve();
+  const dbModifyMock = vi.fn(
+    (id: string, mutator: (latest: HistoryItem) => Partial<HistoryItem> | null) => {
+      const task = writeChain.then(async () => {
+        const latest = await dbGetByIdMock(id);
+        if (!latest) return null;
+        const updates = mutator(latest);
+        if (updates) await dbUpdateMock(id, updates);
+        return { ...latest, ...updates };
+      });
+      writeChain = task.catch(() => undefined);
+      return task;
+    },
+  );
+  return {
+    toastSuccessMock: vi.fn(),
+    toastWarnMock: vi.fn(),
+    toastErrorMock: vi.fn(),
+    confirmDeleteMock: vi.fn(),
+    invalidateCacheMock: vi.fn(),
+    emitHistoryUpdatedMock: vi.fn().mockResolvedValue(undefined),
+    dbSwitchPrimaryMock: vi.fn().mockResolvedValue(undefined),
+    dbRemoveMirrorMock: vi.fn().mockResolvedValue(undefined),
+    dbGetByIdMock,
+    dbUpdateMock,
+    dbModifyMock,
+  };
+});
 
 vi.mock('@/composables/useToast', () => ({
   useToast: () => ({
@@ -65,6 +86,7 @@ vi.mock('@/services/HistoryDatabase', () => ({
     removeMirror: dbRemoveMirrorMock,
     getById: dbGetByIdMock,
     update: dbUpdateMock,
+    modify: dbModifyMock,
   },
 }));
 
@@ -444,10 +466,9 @@ describe('useMirrorFallback - checkMirror', () => {
     expect(dbUpdateMock).not.toHaveBeenCalled();
   });
 
-  it('两个不同 serviceId 并发：DB 写入串行化，前一笔结果不会被后一笔覆盖', async () => {
-    // 让两次 invoke 都立刻 resolve；getById 返回的 baseline 始终是空 linkCheckStatus
-    // （模拟"两个并发回写都拿到同一份 baseline"的最坏情况——若不串行化，
-    // 第二笔的 update 会把 linkCheckStatus 整字段替换，第一笔的写丢失）。
+  it('两个不同 serviceId 并发：经 historyDB.modify 基于最新记录合并，前一笔结果不会被后一笔覆盖', async () => {
+    // 让两次 invoke 都立刻 resolve；初始 baseline 是空 linkCheckStatus
+    // （若各自拿检测前读到的记录合并，第二笔的 update 会把 linkCheckStatus 整字段替换，第一笔的写丢失）。
     invokeMock
       .mockResolvedValueOnce({
         link: 'q', is_valid: true, error_type: 'success', browser_might_work: true,
@@ -472,6 +493,7 @@ describe('useMirrorFallback - checkMirror', () => {
       harness.api().checkMirror('weibo'),
     ]);
 
+    expect(dbModifyMock).toHaveBeenCalledTimes(2);
     expect(dbUpdateMock).toHaveBeenCalledTimes(2);
     // 关键断言：第二笔 update 的 linkCheckStatus 必须包含第一笔写入的 key（即看到了前一笔的合并结果）
     const second = updateSnapshots[1];
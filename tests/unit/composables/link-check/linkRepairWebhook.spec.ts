@@ -0,0 +1,128 @@
+import { describe, it, expect, beforeEach } from 'vitest';
+import { getInvokeMock } from '../../helpers/tauriMock';
+import {
+  buildLinkRepairPayload,
+  getActiveLinkRepairWebhook,
+  notifyLinkRepairWebhook,
+} from '@/composables/link-check/linkRepairWebhook';
+import type { UserConfig } from '@/config/types';
+import type { BatchCheckItemResult, BatchCheckResult, LinkCheckRow } from '@/types/linkCheck';
+
+const invokeMock = getInvokeMock();
+
+function item(overrides: Partial<BatchCheckItemResult>): BatchCheckItemResult {
+  return {
+    link: 'https://img.example.com/a.jpg',
+    is_valid: true,
+    error_type: 'success',
+    browser_might_work: false,
+    ...overrides,
+  };
+}
+
+function batch(results: BatchCheckItemResult[], invalid = 1): BatchCheckResult {
+  return {
+    results,
+    total: results.length,
+    valid: results.length - invalid,
+    invalid,
+    timeout: 0,
+    suspicious: 0,
+    elapsed_ms: 10,
+    cancelled: false,
+  };
+}
+
+function row(historyId: string, serviceId: string, url: string, isValid: boolean): LinkCheckRow {
+  return {
+    historyId,
+    serviceId,
+    url,
+    rawUrl: url,
+    fileName: `${historyId}.jpg`,
+    checkResult: {
+      link: url,
+      is_valid: isValid,
+      error_type: isValid ? 'success' : 'http_4xx',
+      browser_might_work: false,
+    },
+  };
+}
+
+const ENABLED_CONFIG = {
+  linkRepairWebhook: { enabled: true, url: ' https://ci.example.com/hook ', secret: '' },
+} as UserConfig;
+
+describe('buildLinkRepairPayload', () => {
+  it('按历史记录分组失效链接，并附带同条记录中有效的镜像', () => {
+    const rows = [
+      row('h1', 'weibo', 'https://weibo.example.com/a.jpg', false),
+      row('h1', 'github', 'https://github.example.com/a.jpg', true),
+      row('h2', 'r2', 'https://r2.example.com/b.jpg', true),
+    ];
+    const result = batch([
+      item({ link: 'https://weibo.example.com/a.jpg', history_id: 'h1', service_id: 'weibo', is_valid: false, error_type: 'http_4xx', status_code: 404 }),
+      item({ link: 'https://github.example.com/a.jpg', history_id: 'h1', service_id: 'github' }),
+      item({ link: 'https://r2.example.com/b.jpg', history_id: 'h2', service_id: 'r2' }),
+    ]);
+
+    const payload = buildLinkRepairPayload(rows, result);
+
+    expect(payload?.event).toBe('link-repair');
+    expect(payload?.invalid).toBe(1);
+    expect(payload?.records).toEqual([
+      {
+        historyId: 'h1',
+        fileName: 'h1.jpg',
+        broken: [{ serviceId: 'weibo', url: 'https://weibo.example.com/a.jpg', errorType: 'http_4xx', statusCode: 404 }],
+        suggestedMirrors: [{ serviceId: 'github', url: 'https://github.example.com/a.jpg' }],
+      },
+    ]);
+  });
+
+  it('超时、可疑和浏览器可能可访问的结果不计为失效', () => {
+    const result = batch([
+      item({ history_id: 'h1', service_id: 'weibo', is_valid: false, error_type: 'timeout' }),
+      item({ history_id: 'h2', service_id: 'weibo', is_valid: false, error_type: 'suspicious' }),
+      item({ history_id: 'h3', service_id: 'weibo', is_valid: false, error_type: 'http_4xx', browser_might_work: true }),
+    ], 0);
+
+    expect(buildLinkRepairPayload([], result)).toBeNull();
+  });
+});
+
+describe('notifyLinkRepairWebhook', () => {
+  beforeEach(() => {
+    invokeMock.mockClear();
+  });
+
+  it('未启用或地址为空时不发送', async () => {
+    expect(getActiveLinkRepairWebhook({} as UserConfig)).toBeNull();
+    expect(getActiveLinkRepairWebhook({
+      linkRepairWebhook: { enabled: true, url: '  ', secret: '' },
+    } as UserConfig)).toBeNull();
+
+    const sent = await notifyLinkRepairWebhook({} as UserConfig, [], batch([
+      item({ history_id: 'h1', service_id: 'weibo', is_valid: false, error_type: 'http_4xx' }),
+    ]));
+
+    expect(sent).toBe(0);
+    expect(invokeMock).not.toHaveBeenCalled();
+  });
+
+  it('有失效链接时调用 send_link_repair_webhook', async () => {
+    invokeMock.mockResolvedValueOnce(200);
+
+    const sent = await notifyLinkRepairWebhook(ENABLED_CONFIG, [], batch([
+      item({ link: 'https://weibo.example.com/a.jpg', history_id: 'h1', service_id: 'weibo', is_valid: false, error_type: 'http_5xx' }),
+    ]));
+
+    expect(sent).toBe(1);
+    expect(invokeMock).toHaveBeenCalledWith('send_link_repair_webhook', expect.objectContaining({
+      config: { url: 'https://ci.example.com/hook', secret: null },
+      payload: expect.objectContaining({
+        records: [expect.objectContaining({ historyId: 'h1' })],
+      }),
+    }));
+  });
+});
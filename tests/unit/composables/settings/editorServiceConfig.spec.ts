@@ -130,6 +130,43 @@ describe('editorServiceConfig', () => {
     expect(buildServiceConfigJson('custom_s3:docs', form)).toContain('"type":"customS3"');
   });
 
+  it('exports the custom S3 ACL only when one is set', () => {
+    const profile = {
+      id: 'minio',
+      name: 'MinIO',
+      endpoint: 'https://minio.example.com',
+      accessKeyId: 'key',
+      secretAccessKey: 'secret',
+      region: 'us-east-1',
+      bucket: 'bucket',
+      path: '',
+      publicDomain: '',
+    };
+
+    expect(buildServiceConfig('custom_s3:minio', makeForm({
+      custom_s3_profiles: [{ ...profile, acl: ' public-read ' }],
+    }))).toMatchObject({ acl: 'public-read' });
+    expect(buildServiceConfig('custom_s3:minio', makeForm({
+      custom_s3_profiles: [{ ...profile, acl: '' }],
+    }))).not.toHaveProperty('acl');
+  });
+
+  it('exports the Aliyun style suffix only when one is set', () => {
+    const aliyun = {
+      accessKeyId: 'key',
+      accessKeySecret: 'secret',
+      region: 'oss-cn-hangzhou',
+      bucket: 'bucket',
+      path: 'images/',
+      publicDomain: 'https://img.example.com',
+    };
+
+    expect(buildServiceConfig('aliyun', makeForm({
+      aliyun: { ...aliyun, styleSuffix: ' !webp ' },
+    }))).toMatchObject({ type: 'aliyun', public_domain: 'https://img.example.com', style_suffix: '!webp' });
+    expect(buildServiceConfig('aliyun', makeForm({ aliyun }))).not.toHaveProperty('style_suffix');
+  });
+
   it('keeps Zhihu source parameter fields compatible with Rust serde names', () => {
     const form = makeForm({
       zhihu: { cookie: 'z_c0=token', sourceParamEnabled: false, sourceParamValue: 'custom-source' },
@@ -3,7 +3,7 @@ import { nextTick } from 'vue';
 import { useSettingsForm } from '@/composables/settings/useSettingsForm';
 import { DEFAULT_CONFIG, makeCustomS3Id, type UserConfig } from '@/config/types';
 import { createConfig } from '../../factories/configFactory';
-import { resetTauriMocks, setupInvokeResponses } from '../../helpers/tauriMock';
+import { getInvokeMock, resetTauriMocks, setupInvokeResponses } from '../../helpers/tauriMock';
 
 const mockState = vi.hoisted(() => ({
   configStoreGet: vi.fn(),
@@ -153,6 +153,7 @@ describe('useSettingsForm', () => {
     expect(savedConfig.services.weibo.cookie).toBe('SUB=abc');
     expect(savedConfig.services.r2.accountId).toBe('a'.repeat(32));
     expect(savedConfig.services.smms.token).toBe('smms-token');
+    expect(getInvokeMock()).toHaveBeenCalledWith('save_smms_token', { token: 'smms-token' });
     expect(savedConfig.services.nami.authToken).toBe('NAMI123');
     expect(savedConfig.publicServiceRiskAccepted).toBe(true);
     expect(savedConfig.custom_s3_profiles).toEqual([customProfile]);
@@ -199,6 +200,38 @@ describe('useSettingsForm', () => {
     expect(api.availableServices.value).toEqual(['jd']);
   });
 
+  it('restores the Weibo cookie from the keychain when the config has none', async () => {
+    setupInvokeResponses({
+      'plugin:autostart|is_enabled': false,
+      get_keychain_cookie: 'SUB=keychain; SUBP=x',
+    });
+    mockState.configStoreGet.mockResolvedValue(createConfig({ availableServices: ['jd'] }));
+    const api = useSettingsForm();
+
+    await api.loadSettings();
+
+    expect(api.formData.value.weiboCookie).toBe('SUB=keychain; SUBP=x');
+    expect(getInvokeMock()).toHaveBeenCalledWith('get_keychain_cookie', { serviceId: 'weibo' });
+    api.clearTimers();
+  });
+
+  it('deletes the keychain copy when the user clears the Weibo cookie', async () => {
+    mockState.configStoreGet.mockResolvedValue(createConfig({
+      availableServices: ['jd'],
+      services: {
+        weibo: { enabled: true, cookie: 'SUB=old' },
+      },
+    }));
+    const api = useSettingsForm();
+    api.formData.value.weiboCookie = '';
+    api.availableServices.value = ['jd'];
+
+    await expect(api.saveSettings()).resolves.toBe(true);
+
+    expect(getInvokeMock()).toHaveBeenCalledWith('delete_keychain_cookie', { serviceId: 'weibo' });
+    api.clearTimers();
+  });
+
   it('restores DEFAULT_CONFIG without clearing history or cache state', async () => {
     const api = useSettingsForm();
     const customProfile = {
@@ -11,8 +11,18 @@ import {
 const mockDialogOpen = vi.mocked(dialogOpen);
 const mockInvoke = vi.mocked(invoke);
 
+const imageStat = {
+  exists: true,
+  kind: 'file',
+  size: 10,
+  modifiedMs: 0,
+  readable: true,
+  looksLikeImage: true,
+  format: 'png',
+};
+
 beforeEach(() => {
-  mockInvoke.mockReset().mockResolvedValue({ width: 100, height: 80 });
+  mockInvoke.mockReset().mockResolvedValue(imageStat);
 });
 
 describe('常量', () => {
@@ -68,6 +78,25 @@ describe('filterValidFiles', () => {
     expect(result.invalid).toEqual(['/tmp/fake.png']);
   });
 
+  it('每个文件只调用一次 stat_path', async () => {
+    await filterValidFiles(['/tmp/a.png']);
+
+    expect(mockInvoke).toHaveBeenCalledTimes(1);
+    expect(mockInvoke).toHaveBeenCalledWith('stat_path', { path: '/tmp/a.png' });
+  });
+
+  it('文件头不是图片、不可读或是目录 → invalid', async () => {
+    mockInvoke
+      .mockResolvedValueOnce({ ...imageStat, looksLikeImage: false, format: null })
+      .mockResolvedValueOnce({ ...imageStat, readable: false })
+      .mockResolvedValueOnce({ ...imageStat, kind: 'dir', looksLikeImage: false });
+
+    const result = await filterValidFiles(['/tmp/renamed.png', '/tmp/locked.png', '/tmp/album.png']);
+
+    expect(result.valid).toEqual([]);
+    expect(result.invalid.sort()).toEqual(['/tmp/album.png', '/tmp/locked.png', '/tmp/renamed.png']);
+  });
+
   it('达到单次上传上限后不继续读取图片头', async () => {
     const files = Array.from({ length: MAX_FILES_PER_UPLOAD + 5 }, (_, index) => `/tmp/${index}.jpg`);
 
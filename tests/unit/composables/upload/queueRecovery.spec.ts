@@ -0,0 +1,128 @@
+import { describe, it, expect, vi } from 'vitest';
+import {
+  reconcileQueueJournal,
+  toJournalEntry,
+  type QueueJournalEntry,
+  type QueueRecoveryDeps,
+} from '@/composables/upload/QueueRecovery';
+import type { HistoryItem } from '@/config/types';
+import { createQueueItem, createServiceProgress } from '../../factories/uploadFactory';
+import { createHistoryItem, createHistoryResult } from '../../factories/historyFactory';
+
+vi.mock('@/utils/logger', () => ({
+  createLogger: () => ({
+    debug: vi.fn(),
+    info: vi.fn(),
+    warn: vi.fn(),
+    error: vi.fn(),
+  }),
+}));
+
+function createEntry(overrides: Partial<QueueJournalEntry> = {}): QueueJournalEntry {
+  return {
+    id: 'queue-1',
+    filePath: '/mock/a.png',
+    fileName: 'a.png',
+    enabledServices: ['jd', 'github'],
+    completed: {},
+    ...overrides,
+  };
+}
+
+function createDeps(overrides: Partial<QueueRecoveryDeps> = {}) {
+  return {
+    fileExists: vi.fn(async () => true),
+    getHistory: vi.fn(async (): Promise<HistoryItem | null> => null),
+    verifyLink: vi.fn(async () => true),
+    recordResults: vi.fn(async () => undefined),
+    ...overrides,
+  };
+}
+
+describe('toJournalEntry', () => {
+  it('只保留拿到链接的图床', () => {
+    const item = {
+      ...createQueueItem({
+        enabledServices: ['jd', 'github'],
+        serviceProgress: {
+          jd: createServiceProgress({
+            serviceId: 'jd',
+            progress: 100,
+            link: 'https://jd.example.com/a.png',
+            metadata: { fileKey: 'jd-key' },
+          }),
+          github: createServiceProgress({ serviceId: 'github', progress: 40 }),
+        },
+      }),
+      historyId: 'hist-1',
+    };
+
+    expect(toJournalEntry(item)).toMatchObject({
+      historyId: 'hist-1',
+      completed: { jd: { url: 'https://jd.example.com/a.png', fileKey: 'jd-key' } },
+    });
+  });
+});
+
+describe('reconcileQueueJournal', () => {
+  it('已上传但未记录的结果校验通过后补写历史', async () => {
+    const deps = createDeps();
+    const entry = createEntry({
+      historyId: 'hist-1',
+      completed: { jd: { url: 'https://jd.example.com/a.png' } },
+    });
+
+    const report = await reconcileQueueJournal([entry], deps);
+
+    expect(deps.verifyLink).toHaveBeenCalledWith('https://jd.example.com/a.png');
+    expect(deps.recordResults).toHaveBeenCalledWith(
+      entry,
+      [expect.objectContaining({ serviceId: 'jd', status: 'success' })],
+      null,
+    );
+    expect(report.recorded).toEqual([entry]);
+    expect(deps.fileExists).not.toHaveBeenCalled();
+  });
+
+  it('历史中已有的图床结果不重复校验', async () => {
+    const existing = createHistoryItem({
+      id: 'hist-1',
+      results: [createHistoryResult({ serviceId: 'jd' })],
+    });
+    const deps = createDeps({ getHistory: vi.fn(async () => existing) });
+    const entry = createEntry({
+      historyId: 'hist-1',
+      completed: { jd: { url: 'https://jd.example.com/a.png' } },
+    });
+
+    const report = await reconcileQueueJournal([entry], deps);
+
+    expect(deps.verifyLink).not.toHaveBeenCalled();
+    expect(deps.recordResults).not.toHaveBeenCalled();
+    expect(report).toEqual({ recorded: [], resumable: [], missing: [] });
+  });
+
+  it('校验未通过的链接视为未上传，按源文件是否存在分类', async () => {
+    const deps = createDeps({
+      verifyLink: vi.fn(async () => false),
+      fileExists: vi.fn(async (path: string) => path === '/mock/a.png'),
+    });
+    const resumable = createEntry({ completed: { jd: { url: 'https://jd.example.com/a.png' } } });
+    const missing = createEntry({ id: 'queue-2', filePath: '/mock/gone.png' });
+
+    const report = await reconcileQueueJournal([resumable, missing], deps);
+
+    expect(deps.recordResults).not.toHaveBeenCalled();
+    expect(report.resumable).toEqual([resumable]);
+    expect(report.missing).toEqual([missing]);
+  });
+
+  it('补写失败时不计入报告', async () => {
+    const deps = createDeps({ recordResults: vi.fn(async () => { throw new Error('db locked'); }) });
+    const entry = createEntry({ completed: { jd: { url: 'https://jd.example.com/a.png' } } });
+
+    const report = await reconcileQueueJournal([entry], deps);
+
+    expect(report).toEqual({ recorded: [], resumable: [], missing: [] });
+  });
+});
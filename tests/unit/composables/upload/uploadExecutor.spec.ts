@@ -6,6 +6,7 @@ import { formatLinkWithConfig } from '@/composables/useCopyLink';
 import { generateThumbnailUrl } from '@/composables/useThumbCache';
 
 const uploadToMultipleServicesMock = vi.hoisted(() => vi.fn());
+const shortenHistoryLinkMock = vi.hoisted(() => vi.fn(async () => undefined as string | undefined));
 
 vi.mock('@/core/MultiServiceUploader', () => ({
   MultiServiceUploader: class {
@@ -13,6 +14,10 @@ vi.mock('@/core/MultiServiceUploader', () => ({
   },
 }));
 
+vi.mock('@/services/shortLink', () => ({
+  shortenHistoryLink: shortenHistoryLinkMock,
+}));
+
 vi.mock('@/composables/useServiceHealth', () => ({
   useServiceHealth: () => ({ markUploadError: vi.fn() }),
 }));
@@ -126,6 +131,36 @@ describe('processUploadQueue', () => {
     ]);
   });
 
+  it('attaches the generated short link to collected links', async () => {
+    const queueManager = createQueueManager();
+    queueManager.seed('q-short', 'short.jpg');
+    const collectedLinks: CopyLinkItem[] = [];
+    shortenHistoryLinkMock.mockResolvedValueOnce('https://s.example/abc');
+
+    await processUploadQueue(
+      [{ itemId: 'q-short', filePath: 'C:/tmp/short.jpg', uploadFilePath: 'C:/tmp/short.jpg', fileName: 'short.jpg' }],
+      { services: { jd: {} } } as any,
+      ['jd'],
+      1,
+      {
+        queueManager: queueManager as any,
+        saveHistoryItemImmediate: vi.fn(async () => undefined),
+        addResultToHistoryItem: vi.fn(async () => true),
+        reconcileHistoryPrimary: vi.fn(async () => true),
+        saveHistoryItem: vi.fn(async () => undefined),
+        toast: { showConfig: vi.fn() } as any,
+      },
+      collectedLinks,
+    );
+
+    const historyId = queueManager.updateItem.mock.calls.find(([, update]) => update.historyId)?.[1].historyId;
+    expect(shortenHistoryLinkMock).toHaveBeenCalledWith(historyId, 'https://img.example/short.jpg', 'jd', expect.anything());
+    expect(collectedLinks[0]).toMatchObject({
+      url: 'https://img.example/short.jpg',
+      shortUrl: 'https://s.example/abc',
+    });
+  });
+
   it('stores the generated historyId on the queue item and reuses it for history saves', async () => {
     const queueManager = createQueueManager();
     queueManager.seed('q-history', 'history.jpg');
@@ -368,6 +403,7 @@ describe('processUploadQueue', () => {
       expect.anything(),
       expect.any(Function),
       expect.any(Function),
+      'q-svg',
     );
     expect(queueManager.markItemComplete).toHaveBeenCalledWith('q-svg', githubUrl);
   });
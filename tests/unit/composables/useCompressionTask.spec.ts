@@ -8,6 +8,7 @@ import {
 import type { CompressionPreset } from '@/config/types';
 import type { CompressResult } from '@/composables/useCompressionTask';
 import { useCompressionTask } from '@/composables/useCompressionTask';
+import { __resetLocalImageUrlForTests } from '@/utils/localImageUrl';
 
 const dialogOpenMock = getDialogOpenMock();
 const invokeMock = getInvokeMock();
@@ -52,6 +53,7 @@ function deferred<T>() {
 describe('useCompressionTask', () => {
   beforeEach(() => {
     vi.clearAllMocks();
+    __resetLocalImageUrlForTests();
   });
 
   it('用户取消选择时返回 false，并保持重置后的空状态', async () => {
@@ -66,7 +68,7 @@ describe('useCompressionTask', () => {
     expect(invokeMock).not.toHaveBeenCalled();
   });
 
-  it('成功压缩：读取元数据计算缩放最长边、加载预览、清理临时文件并触发 onDone', async () => {
+  it('成功压缩：读取元数据计算缩放最长边、原图走 picnexus-file 协议预览、清理临时文件并触发 onDone', async () => {
     dialogOpenMock.mockResolvedValue('C:/photos/original.jpg');
     const onDone = vi.fn();
     const cleanupPaths: string[][] = [];
@@ -92,6 +94,7 @@ describe('useCompressionTask', () => {
         cleanupPaths.push((args as { filePaths: string[] }).filePaths);
         return undefined;
       }
+      if (cmd === 'get_file_protocol_token') return 'tok';
       throw new Error(`unexpected command: ${cmd}`);
     });
 
@@ -101,7 +104,9 @@ describe('useCompressionTask', () => {
 
     expect(api.fileName.value).toBe('original.jpg');
     expect(api.result.value).toEqual(compressResult);
-    expect(api.originalSrc.value).toBe('original-b64');
+    expect(api.originalSrc.value).toBe(
+      `picnexus-file://localhost/${encodeURIComponent('C:/photos/original.jpg')}?token=tok&max=1200`,
+    );
     expect(api.compressedSrc.value).toBe('compressed-b64');
     expect(api.status.value).toBe('done');
     expect(api.getSaved()).toBe(60);
@@ -122,6 +127,7 @@ describe('useCompressionTask', () => {
       }
       if (cmd === 'read_image_as_base64') return 'b64';
       if (cmd === 'cleanup_compressed_files') return undefined;
+      if (cmd === 'get_file_protocol_token') return 'tok';
       throw new Error(`unexpected command: ${cmd}`);
     });
 
@@ -136,6 +142,7 @@ describe('useCompressionTask', () => {
     setupInvokeHandler(async (cmd) => {
       if (cmd === 'compress_image') throw new Error('encoder crashed');
       if (cmd === 'read_image_as_base64') return 'original-b64';
+      if (cmd === 'get_file_protocol_token') return 'tok';
       throw new Error(`unexpected command: ${cmd}`);
     });
 
@@ -155,11 +162,14 @@ describe('useCompressionTask', () => {
       .mockResolvedValueOnce('C:/photos/new.jpg');
     const oldCompress = deferred<CompressResult>();
     const cleanupPaths: string[][] = [];
+    const cancelledTasks: string[] = [];
+    const compressTasks: string[] = [];
     const newResult = makeResult({ outputPath: 'C:/tmp/new.webp', ratio: 1.1 });
 
     setupInvokeHandler(async (cmd, args) => {
       if (cmd === 'compress_image') {
-        const filePath = (args as { filePath: string }).filePath;
+        const { filePath, taskId } = args as { filePath: string; taskId: string };
+        compressTasks.push(taskId);
         if (filePath.endsWith('old.jpg')) return oldCompress.promise;
         return newResult;
       }
@@ -172,6 +182,11 @@ describe('useCompressionTask', () => {
         cleanupPaths.push((args as { filePaths: string[] }).filePaths);
         return undefined;
       }
+      if (cmd === 'cancel_task') {
+        cancelledTasks.push((args as { taskId: string }).taskId);
+        return true;
+      }
+      if (cmd === 'get_file_protocol_token') return 'tok';
       throw new Error(`unexpected command: ${cmd}`);
     });
 
@@ -190,5 +205,9 @@ describe('useCompressionTask', () => {
     expect(api.getIsLarger()).toBe(true);
     expect(cleanupPaths).toContainEqual(['C:/tmp/new.webp']);
     expect(cleanupPaths).toContainEqual(['C:/tmp/old.webp']);
+    // 换一张时通知后端取消旧任务，任务 ID 与旧的 compress_image 调用一致
+    expect(cancelledTasks).toHaveLength(1);
+    expect(compressTasks).toContain(cancelledTasks[0]);
+    expect(compressTasks[compressTasks.length - 1]).not.toBe(cancelledTasks[0]);
   });
 });
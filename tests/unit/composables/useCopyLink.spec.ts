@@ -84,6 +84,22 @@ describe('useCopyLink helpers', () => {
       .toBe('https://picx.zhimg.com/v2-abc.webp?source=172ae18b');
   });
 
+  it('applyConfiguredUrlWithConfig rewrites the weibo CDN host before applying the prefix', () => {
+    const config = makeConfig({
+      services: { weibo: { enabled: true, cookie: '', cdnHost: 'wx2' } },
+      linkPrefixConfig: {
+        enabled: true,
+        selectedIndex: 0,
+        prefixList: [{ name: 'Proxy', template: 'https://proxy.example.com/' }],
+      },
+    });
+
+    expect(applyConfiguredUrlWithConfig('https://tvax1.sinaimg.cn/large/abc.jpg', 'weibo', config))
+      .toBe('https://proxy.example.com/https://wx2.sinaimg.cn/large/abc.jpg');
+    expect(applyConfiguredUrlWithConfig('https://tvax1.sinaimg.cn/large/abc.jpg', 'jd', config))
+      .toBe('https://tvax1.sinaimg.cn/large/abc.jpg');
+  });
+
   it('getLinkFormatConfig falls back to url format', () => {
     const config: UserConfig = {
       ...DEFAULT_CONFIG,
@@ -117,6 +133,46 @@ describe('useCopyLink helpers', () => {
     expect(output).toBe('![a.png](https://example.com/a.png)');
   });
 
+  it('formatLinkWithConfig prefers the stored short link over prefixed url', () => {
+    const config = makeConfig({
+      linkOutput: {
+        defaultFormat: 'markdown',
+        customTemplate: '{url}',
+        autoCopy: true,
+      },
+    });
+
+    const output = formatLinkWithConfig(
+      {
+        url: 'https://tvax1.sinaimg.cn/large/a.jpg',
+        fileName: 'a.jpg',
+        serviceId: 'weibo',
+        shortUrl: 'https://s.example.com/abc',
+      },
+      config
+    );
+
+    expect(output).toBe('![a.jpg](https://s.example.com/abc)');
+  });
+
+  it('formatLinkWithConfig falls back to the alt suggestion when no alt text was entered', () => {
+    const config = makeConfig();
+    const item = {
+      url: 'https://example.com/a.png',
+      fileName: 'IMG_0001.png',
+      serviceId: 'jd' as const,
+      altSuggestion: 'Build failed',
+    };
+
+    expect(formatLinkWithConfig(item, config, 'markdown')).toBe('![Build failed](https://example.com/a.png)');
+    expect(formatLinkWithConfig(item, config, 'html')).toBe(
+      '<img src="https://example.com/a.png" alt="Build failed" />'
+    );
+    expect(formatLinkWithConfig({ ...item, altText: '构建失败截图' }, config, 'markdown')).toBe(
+      '![构建失败截图](https://example.com/a.png)'
+    );
+  });
+
   it('formatLinkWithConfig supports bbcode override', () => {
     const config = makeConfig();
 
@@ -160,6 +216,28 @@ describe('useCopyLink helpers', () => {
 
     expect(output).toBe('[img=1200x800]https://proxy.example.com/https://example.com/a.png[/img]');
   });
+
+  it('formatLinkWithConfig appends transform params for the active format on weserv links', () => {
+    const config = makeConfig({
+      linkPrefixConfig: {
+        enabled: true,
+        selectedIndex: 0,
+        prefixList: [{ name: 'weserv', template: 'https://images.weserv.nl/?url={url_encoded}' }],
+      },
+      linkTransform: {
+        enabled: true,
+        templates: { markdown: { width: 800, format: 'webp' } },
+      },
+    });
+    const item = { url: 'https://example.com/a.png', fileName: 'a.png', serviceId: 'weibo' };
+
+    const markdown = formatLinkWithConfig(item, config, 'markdown');
+    expect(markdown).toMatch(/^!\[a\.png\]\(https:\/\/images\.weserv\.nl\/\?url=.*&w=800&output=webp\)$/);
+
+    expect(formatLinkWithConfig(item, config, 'url')).not.toContain('w=800');
+    expect(formatLinkWithConfig({ ...item, serviceId: 'jd' }, config, 'markdown'))
+      .toBe('![a.png](https://example.com/a.png)');
+  });
 });
 
 describe('useCopyLink composable', () => {
@@ -56,6 +56,10 @@ vi.mock('@/composables/useCopyLink', () => ({
   getLinkFormatConfig: getLinkFormatConfigMock,
 }));
 
+vi.mock('@/services/shortLink', () => ({
+  shortenHistoryLink: vi.fn(async () => undefined),
+}));
+
 vi.mock('@/utils/uploadSummary', () => ({
   buildUploadSummaryToast: buildUploadSummaryToastMock,
 }));
@@ -391,7 +395,8 @@ describe('useGlobalShortcut 触发上传', () => {
     await copyDone.promise;
     await flushAsyncWork();
 
-    expect(invokeMock).toHaveBeenCalledTimes(MAX_FILES_PER_UPLOAD);
+    const metadataCalls = invokeMock.mock.calls.filter(([cmd]) => cmd === 'get_image_metadata');
+    expect(metadataCalls).toHaveLength(MAX_FILES_PER_UPLOAD);
     expect(uploadToMultipleServicesMock).toHaveBeenCalledTimes(MAX_FILES_PER_UPLOAD - 1);
     const uploadedPaths = uploadToMultipleServicesMock.mock.calls.map(call => call[0]);
     expect(uploadedPaths).not.toContain(fakeFile);
@@ -7,6 +7,7 @@ const {
   getByIdMock,
   updateMock,
   getImageMetadataMock,
+  getImageExifMock,
   clearImageMetadataCacheMock,
   invalidateCacheMock,
   emitHistoryUpdatedMock,
@@ -15,6 +16,7 @@ const {
   getByIdMock: vi.fn(),
   updateMock: vi.fn(),
   getImageMetadataMock: vi.fn(),
+  getImageExifMock: vi.fn(),
   clearImageMetadataCacheMock: vi.fn(),
   invalidateCacheMock: vi.fn(),
   emitHistoryUpdatedMock: vi.fn(),
@@ -31,6 +33,7 @@ vi.mock('@/services/HistoryDatabase', () => ({
 
 vi.mock('@/composables/useImageMetadata', () => ({
   getImageMetadata: getImageMetadataMock,
+  getImageExif: getImageExifMock,
   clearImageMetadataCache: clearImageMetadataCacheMock,
 }));
 
@@ -74,6 +77,7 @@ describe('useHistorySaver', () => {
       file_size: 4096,
       format: 'jpg',
     });
+    getImageExifMock.mockReset().mockResolvedValue(null);
     clearImageMetadataCacheMock.mockReset();
     invalidateCacheMock.mockReset();
     emitHistoryUpdatedMock.mockReset().mockResolvedValue(undefined);
@@ -95,6 +99,39 @@ describe('useHistorySaver', () => {
     expect(insertedItem.results).toEqual([success, failed]);
   });
 
+  it('saveHistoryItemImmediate stores trimmed alt text and title', async () => {
+    const { useHistorySaver } = await import('@/composables/useHistorySaver');
+    const { saveHistoryItemImmediate } = useHistorySaver();
+
+    await saveHistoryItemImmediate('/tmp/test.jpg', makeSuccess('jd'), 'history-1', {
+      altText: '  海边日落  ',
+      title: '   ',
+    });
+
+    const insertedItem = insertOrIgnoreMock.mock.calls[0][0] as HistoryItem;
+    expect(insertedItem.altText).toBe('海边日落');
+    expect(insertedItem.title).toBeUndefined();
+    expect(insertedItem.captureDate).toBeUndefined();
+  });
+
+  it('saveHistoryItemImmediate records the EXIF capture date', async () => {
+    getImageExifMock.mockResolvedValue({
+      capture_date: '2024-05-01T08:30:15',
+      camera_make: 'Canon',
+      camera_model: 'Canon EOS R5',
+      has_gps: true,
+      orientation: 1,
+    });
+    const { useHistorySaver } = await import('@/composables/useHistorySaver');
+    const { saveHistoryItemImmediate } = useHistorySaver();
+
+    await saveHistoryItemImmediate('/tmp/photo.jpg', makeSuccess('jd'), 'history-exif');
+
+    expect(getImageExifMock).toHaveBeenCalledWith('/tmp/photo.jpg');
+    const insertedItem = insertOrIgnoreMock.mock.calls[0][0] as HistoryItem;
+    expect(insertedItem.captureDate).toBe('2024-05-01T08:30:15');
+  });
+
   it('saveHistoryItem updates existing record when customId points to one in DB (retry reuse path)', async () => {
     const { useHistorySaver } = await import('@/composables/useHistorySaver');
     const { saveHistoryItem } = useHistorySaver();
@@ -0,0 +1,68 @@
+import { beforeEach, describe, expect, it } from 'vitest';
+import { flushPromises } from '@vue/test-utils';
+import { getInvokeMock } from '../helpers/tauriMock';
+import {
+  describeHostCapabilities,
+  useHostCapabilities,
+  __resetHostCapabilitiesForTests,
+} from '@/composables/useHostCapabilities';
+import type { HostCapabilities } from '@/types/hostCapabilities';
+
+function makeCaps(overrides: Partial<HostCapabilities> = {}): HostCapabilities {
+  return {
+    service: 'jd',
+    displayName: '京东',
+    maxFileSize: 15 * 1024 * 1024,
+    maxGifSize: null,
+    allowedFormats: ['jpg', 'jpeg', 'png', 'gif'],
+    needsCookie: false,
+    supportsDelete: false,
+    supportsMultipart: false,
+    ...overrides,
+  };
+}
+
+describe('useHostCapabilities', () => {
+  beforeEach(() => {
+    __resetHostCapabilitiesForTests();
+    getInvokeMock().mockReset();
+  });
+
+  it('describes size limits, formats and optional features in one line', () => {
+    expect(describeHostCapabilities(makeCaps())).toBe('单张 ≤ 15 MB · JPG/PNG/GIF');
+    expect(describeHostCapabilities(makeCaps({
+      maxFileSize: 20 * 1024 * 1024,
+      maxGifSize: 200 * 1024 * 1024,
+      allowedFormats: [],
+      supportsDelete: true,
+      supportsMultipart: true,
+    }))).toBe('单张 ≤ 20 MB（GIF ≤ 200 MB） · 格式不限 · 支持删除 · 分片上传');
+  });
+
+  it('loads the registry once and resolves capabilities by service id', async () => {
+    getInvokeMock().mockResolvedValue([makeCaps(), makeCaps({ service: 'weibo', maxFileSize: null, allowedFormats: [] })]);
+
+    const jd = useHostCapabilities('jd');
+    const unknown = useHostCapabilities('custom_s3:abc');
+    await flushPromises();
+
+    expect(getInvokeMock()).toHaveBeenCalledTimes(1);
+    expect(getInvokeMock()).toHaveBeenCalledWith('get_host_capabilities');
+    expect(jd.summary.value).toBe('单张 ≤ 15 MB · JPG/PNG/GIF');
+    expect(unknown.capabilities.value).toBeNull();
+    expect(unknown.summary.value).toBe('');
+  });
+
+  it('retries loading after a failed invoke', async () => {
+    getInvokeMock().mockRejectedValueOnce(new Error('ipc down'));
+    useHostCapabilities('jd');
+    await flushPromises();
+
+    getInvokeMock().mockResolvedValueOnce([makeCaps()]);
+    const jd = useHostCapabilities('jd');
+    await flushPromises();
+
+    expect(getInvokeMock()).toHaveBeenCalledTimes(2);
+    expect(jd.capabilities.value?.displayName).toBe('京东');
+  });
+});
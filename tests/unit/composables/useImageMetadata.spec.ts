@@ -0,0 +1,53 @@
+import { beforeEach, describe, expect, it } from 'vitest';
+import { getInvokeMock, resetTauriMocks } from '../helpers/tauriMock';
+import type { ImageExif } from '@/config/types';
+import {
+  describeExifForPrivacy,
+  getImageExif,
+  summarizeExifForPrivacy,
+} from '@/composables/useImageMetadata';
+
+const exif: ImageExif = {
+  capture_date: '2024-05-01T08:30:15',
+  camera_make: 'Canon',
+  camera_model: 'Canon EOS R5',
+  has_gps: true,
+  orientation: 1,
+};
+
+describe('useImageMetadata EXIF helpers', () => {
+  beforeEach(() => {
+    resetTauriMocks();
+  });
+
+  it('returns null when EXIF cannot be read', async () => {
+    getInvokeMock().mockRejectedValue(new Error('文件不存在'));
+
+    await expect(getImageExif('/tmp/missing.jpg')).resolves.toBeNull();
+    expect(getInvokeMock()).toHaveBeenCalledWith('get_image_exif', { filePath: '/tmp/missing.jpg' });
+  });
+
+  it('lists the privacy fields that stripping removes', () => {
+    expect(describeExifForPrivacy(exif)).toEqual([
+      '拍摄时间 2024-05-01 08:30:15',
+      '相机 Canon EOS R5',
+      'GPS 位置',
+    ]);
+    expect(describeExifForPrivacy({
+      ...exif,
+      capture_date: null,
+      camera_model: 'X-T5',
+      camera_make: 'FUJIFILM',
+      has_gps: false,
+    })).toEqual(['相机 FUJIFILM X-T5']);
+    expect(describeExifForPrivacy(null)).toEqual([]);
+  });
+
+  it('summarizes a batch and calls out images with GPS', () => {
+    const noGps = { ...exif, has_gps: false };
+
+    expect(summarizeExifForPrivacy([exif])).toBe('已移除：拍摄时间 2024-05-01 08:30:15、相机 Canon EOS R5、GPS 位置');
+    expect(summarizeExifForPrivacy([exif, noGps, null])).toBe('2 张图片的 EXIF 隐私信息已移除，其中 1 张含 GPS 位置');
+    expect(summarizeExifForPrivacy([null, null])).toBeNull();
+  });
+});
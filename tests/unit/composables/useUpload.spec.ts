@@ -257,6 +257,23 @@ describe('useUploadManager', () => {
     expect(addResultToHistoryItemMock).not.toHaveBeenCalled();
   });
 
+  it('断网时照常入队，提示网络恢复后自动上传', async () => {
+    checkNetworkConnectivityMock.mockResolvedValueOnce(false);
+    const queueManager = createQueueManager();
+    const { useUploadManager } = await import('@/composables/useUpload');
+    const { __resetNetworkMonitorForTests } = await import('@/utils/networkMonitor');
+    const { handleFilesUpload } = useUploadManager(queueManager as never);
+
+    await handleFilesUpload(['C:/tmp/test.jpg']);
+    __resetNetworkMonitorForTests();
+
+    expect(toastShowConfigMock).toHaveBeenCalledWith(
+      'warn',
+      expect.objectContaining({ summary: '网络已断开' })
+    );
+    expect(uploadToMultipleServicesMock).toHaveBeenCalledTimes(1);
+  });
+
   it('切换图床后立刻上传使用当前界面选择快照', async () => {
     selectedServicesRef.value = ['jd'];
     availableServicesRef.value = ['jd', 'upyun'];
@@ -309,6 +326,7 @@ describe('useUploadManager', () => {
       expect.objectContaining({ enabledServices: ['jd'] }),
       expect.any(Function),
       expect.any(Function),
+      expect.any(String),
     );
     expect(selectedServicesRef.value).toEqual(['jd']);
   });
@@ -391,6 +409,7 @@ describe('useUploadManager', () => {
       expect.objectContaining({ enabledServices: ['upyun'] }),
       expect.any(Function),
       expect.any(Function),
+      expect.any(String),
     );
     expect(toastShowConfigMock).toHaveBeenCalledWith('warn', expect.objectContaining({
       summary: '已跳过未确认风险的图床',
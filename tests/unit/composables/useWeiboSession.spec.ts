@@ -0,0 +1,75 @@
+import { describe, it, expect, vi, beforeEach } from 'vitest';
+import { invoke } from '@tauri-apps/api/core';
+
+const markTestFailed = vi.hoisted(() => vi.fn());
+
+vi.mock('@/store/instances', () => ({
+  configStore: { get: vi.fn(), set: vi.fn(), save: vi.fn() },
+}));
+
+vi.mock('@/composables/useServiceHealth', () => ({
+  useServiceHealth: () => ({ markTestFailed }),
+}));
+
+import { configStore } from '@/store/instances';
+import { checkWeiboSession, WEIBO_SESSION_EXPIRED_MESSAGE } from '@/composables/useWeiboSession';
+import { createConfig } from '../factories/configFactory';
+
+const configStoreMock = configStore as any;
+const mockInvoke = vi.mocked(invoke);
+
+describe('checkWeiboSession', () => {
+  beforeEach(() => {
+    mockInvoke.mockReset();
+    markTestFailed.mockReset();
+    configStoreMock.get.mockReset();
+    configStoreMock.set.mockReset().mockResolvedValue(undefined);
+    configStoreMock.save.mockReset().mockResolvedValue(undefined);
+  });
+
+  it('未配置 Cookie 时不探测', async () => {
+    configStoreMock.get.mockResolvedValue(createConfig());
+
+    await expect(checkWeiboSession()).resolves.toBeNull();
+    expect(mockInvoke).not.toHaveBeenCalledWith('probe_weibo_session', expect.anything());
+  });
+
+  it('登录态失效 → 写入 weiboSession 并标记健康状态', async () => {
+    configStoreMock.get.mockResolvedValue(createConfig({
+      services: { weibo: { enabled: true, cookie: 'SUB=old' } },
+    }));
+    mockInvoke.mockResolvedValue({ loggedIn: false });
+
+    await expect(checkWeiboSession()).resolves.toBe(false);
+
+    expect(mockInvoke).toHaveBeenCalledWith('probe_weibo_session', { weiboCookie: 'SUB=old' });
+    const saved = configStoreMock.set.mock.calls[0][1];
+    expect(saved.weiboSession).toMatchObject({ expired: true });
+    expect(markTestFailed).toHaveBeenCalledWith('weibo', WEIBO_SESSION_EXPIRED_MESSAGE);
+  });
+
+  it('恢复有效后清除过期标记', async () => {
+    configStoreMock.get.mockResolvedValue(createConfig({
+      services: { weibo: { enabled: true, cookie: 'SUB=new' } },
+      weiboSession: { expired: true, checkedAt: 1, expiredAt: 1 },
+    }));
+    mockInvoke.mockResolvedValue({ loggedIn: true, uid: '42' });
+
+    await expect(checkWeiboSession()).resolves.toBe(true);
+
+    const saved = configStoreMock.set.mock.calls[0][1];
+    expect(saved.weiboSession).toMatchObject({ expired: false, expiredAt: null });
+    expect(markTestFailed).not.toHaveBeenCalled();
+  });
+
+  it('网络错误不判定为过期', async () => {
+    configStoreMock.get.mockResolvedValue(createConfig({
+      services: { weibo: { enabled: true, cookie: 'SUB=old' } },
+    }));
+    mockInvoke.mockRejectedValue(new Error('timeout'));
+
+    await expect(checkWeiboSession()).resolves.toBeNull();
+    expect(configStoreMock.set).not.toHaveBeenCalled();
+    expect(markTestFailed).not.toHaveBeenCalled();
+  });
+});
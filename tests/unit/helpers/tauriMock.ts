@@ -14,6 +14,7 @@ import {
   readDir,
   readTextFile,
   remove,
+  rename,
   stat,
   writeTextFile,
 } from '@tauri-apps/plugin-fs';
@@ -65,6 +66,7 @@ vi.mock('@tauri-apps/plugin-log', () => ({
 
 vi.mock('@tauri-apps/api/core', () => ({
   invoke: vi.fn(),
+  convertFileSrc: vi.fn((filePath: string, protocol = 'asset') => `${protocol}://localhost/${encodeURIComponent(filePath)}`),
 }));
 
 vi.mock('@tauri-apps/api/app', () => ({
@@ -137,6 +139,7 @@ vi.mock('@tauri-apps/plugin-fs', () => ({
   exists: vi.fn().mockResolvedValue(false),
   mkdir: vi.fn(),
   remove: vi.fn(),
+  rename: vi.fn(),
   readDir: vi.fn(),
   copyFile: vi.fn(),
   stat: vi.fn(),
@@ -333,6 +336,7 @@ export function resetTauriMocks(): void {
   fs.exists.mockResolvedValue(false);
   fs.mkdir.mockReset();
   fs.remove.mockReset();
+  fs.rename.mockReset();
   fs.readDir.mockReset();
   fs.readDir.mockResolvedValue([]);
   fs.copyFile.mockReset();
@@ -455,6 +459,7 @@ export function getFsMocks() {
     exists: vi.mocked(exists),
     mkdir: vi.mocked(mkdir),
     remove: vi.mocked(remove),
+    rename: vi.mocked(rename),
     readDir: vi.mocked(readDir),
     copyFile: vi.mocked(copyFile),
     stat: vi.mocked(stat),
@@ -0,0 +1,94 @@
+import { describe, expect, it } from 'vitest';
+import { DEFAULT_CONFIG, type UserConfig } from '@/config/types';
+import {
+  applyPresetHosts,
+  describePresetChange,
+  diffPresetHosts,
+  presetNeedsConfirmation,
+  type PresetPreview,
+} from '@/services/configPreset';
+
+function makeConfig(): UserConfig {
+  const config = structuredClone(DEFAULT_CONFIG);
+  config.services.r2 = {
+    ...config.services.r2!,
+    accountId: 'acc',
+    accessKeyId: 'local-key',
+    secretAccessKey: 'local-secret',
+    bucketName: 'mine',
+    publicDomain: '',
+  };
+  config.custom_s3_profiles = [{
+    id: 'team',
+    name: '团队 MinIO',
+    endpoint: 'https://s3.local.example.com',
+    accessKeyId: 'ak',
+    secretAccessKey: 'sk',
+    region: 'us-east-1',
+    bucket: 'assets',
+    path: '',
+    publicDomain: '',
+  }];
+  return config;
+}
+
+function makePreview(signed: boolean): PresetPreview {
+  return {
+    name: 'team',
+    description: '',
+    hosts: {},
+    linkTemplates: [],
+    strippedFields: [],
+    signed,
+    signature: 'sha256:x',
+  };
+}
+
+describe('configPreset', () => {
+  it('lists only fields that overwrite existing non-empty values', () => {
+    const changes = diffPresetHosts(makeConfig(), {
+      r2: { bucketName: 'team-assets', publicDomain: 'https://img.example.com', accountId: 'acc' },
+      'custom_s3:team': { endpoint: 'https://s3.example.com' },
+      'custom_s3:new': { endpoint: 'https://s3.other.com' },
+    });
+
+    expect(changes).toEqual([
+      { hostId: 'r2', field: 'bucketName', before: 'mine', after: 'team-assets' },
+      { hostId: 'custom_s3:team', field: 'endpoint', before: 'https://s3.local.example.com', after: 'https://s3.example.com' },
+    ]);
+    expect(describePresetChange(changes[0])).toBe('r2.bucketName: mine → team-assets');
+  });
+
+  it('merges host fields while keeping local secrets', () => {
+    const merged = applyPresetHosts(makeConfig(), {
+      r2: { bucketName: 'team-assets' },
+      'custom_s3:team': { endpoint: 'https://s3.example.com' },
+      'custom_s3:new': { endpoint: 'https://s3.other.com', bucket: 'shared' },
+    });
+
+    expect(merged.services.r2).toMatchObject({
+      bucketName: 'team-assets',
+      accessKeyId: 'local-key',
+      secretAccessKey: 'local-secret',
+    });
+    expect(merged.custom_s3_profiles?.[0]).toMatchObject({
+      id: 'team',
+      endpoint: 'https://s3.example.com',
+      secretAccessKey: 'sk',
+    });
+    expect(merged.custom_s3_profiles?.[1]).toMatchObject({
+      id: 'new',
+      name: 'new',
+      bucket: 'shared',
+      secretAccessKey: '',
+    });
+  });
+
+  it('requires confirmation only for unsigned presets that change existing fields', () => {
+    const change = { hostId: 'r2', field: 'bucketName', before: 'mine', after: 'team-assets' };
+
+    expect(presetNeedsConfirmation(makePreview(false), [change])).toBe(true);
+    expect(presetNeedsConfirmation(makePreview(true), [change])).toBe(false);
+    expect(presetNeedsConfirmation(makePreview(false), [])).toBe(false);
+  });
+});
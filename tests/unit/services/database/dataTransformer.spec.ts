@@ -65,6 +65,11 @@ function makeHistoryItem() {
     format: 'png',
     isFavorited: true,
     migrationSkip: true,
+    shortUrl: 'https://s.example.com/a',
+    altText: '海边日落',
+    title: 'Sunset',
+    altSuggestion: 'sunset beach',
+    captureDate: '2024-05-01T08:30:15',
   };
 }
 
@@ -81,6 +86,11 @@ describe('DataTransformer', () => {
     expect(row.favorite_updated_at).toBe(1000);
     expect(row.favorite_updated_by).toBe('legacy');
     expect(row.migration_skip).toBe(1);
+    expect(row.short_url).toBe('https://s.example.com/a');
+    expect(row.alt_text).toBe('海边日落');
+    expect(row.title).toBe('Sunset');
+    expect(row.alt_suggestion).toBe('sunset beach');
+    expect(row.capture_date).toBe('2024-05-01T08:30:15');
     expect(row.success_count).toBe(1);
     expect(JSON.parse(row.successful_service_ids)).toEqual(['weibo']);
   });
@@ -111,6 +121,11 @@ describe('DataTransformer', () => {
       success_count: 1,
       successful_service_ids: JSON.stringify(['weibo']),
       migration_skip: 1,
+      short_url: 'https://s.example.com/a',
+      alt_text: '海边日落',
+      title: 'Sunset',
+      alt_suggestion: 'sunset beach',
+      capture_date: '2024-05-01T08:30:15',
     });
 
     expect(item.localFileName).toBe('Alpha.PNG');
@@ -123,6 +138,11 @@ describe('DataTransformer', () => {
     expect(item.favoriteUpdatedAt).toBe(3000);
     expect(item.favoriteUpdatedBy).toBe('device-a');
     expect(item.migrationSkip).toBe(true);
+    expect(item.shortUrl).toBe('https://s.example.com/a');
+    expect(item.altText).toBe('海边日落');
+    expect(item.title).toBe('Sunset');
+    expect(item.altSuggestion).toBe('sunset beach');
+    expect(item.captureDate).toBe('2024-05-01T08:30:15');
   });
 
   it('falls back safely when stored JSON columns are malformed', () => {
@@ -151,12 +171,21 @@ describe('DataTransformer', () => {
       success_count: 0,
       successful_service_ids: '[]',
       migration_skip: 0,
+      short_url: null,
+      alt_text: null,
+      title: null,
+      alt_suggestion: null,
+      capture_date: null,
     });
 
     expect(item.results).toEqual([]);
     expect(item.linkCheckStatus).toBeUndefined();
     expect(item.linkCheckSummary).toBeUndefined();
     expect(item.filePath).toBeUndefined();
+    expect(item.shortUrl).toBeUndefined();
+    expect(item.altText).toBeUndefined();
+    expect(item.altSuggestion).toBeUndefined();
+    expect(item.captureDate).toBeUndefined();
   });
 
   it('exposes placeholder helpers in the same order as ALL_COLUMNS', () => {
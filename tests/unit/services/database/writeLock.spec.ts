@@ -0,0 +1,56 @@
+import { describe, expect, it, vi } from 'vitest';
+import { WriteLock } from '@/services/database/WriteLock';
+
+function createDeferred<T>() {
+  let resolve!: (value: T | PromiseLike<T>) => void;
+  const promise = new Promise<T>((res) => {
+    resolve = res;
+  });
+  return { promise, resolve };
+}
+
+describe('WriteLock', () => {
+  it('runs tasks one at a time in call order', async () => {
+    const lock = new WriteLock('test', null);
+    const gate = createDeferred<void>();
+    const order: string[] = [];
+
+    const first = lock.run(async () => {
+      order.push('first:start');
+      await gate.promise;
+      order.push('first:end');
+      return 1;
+    });
+    const second = lock.run(async () => {
+      order.push('second');
+      return 2;
+    });
+
+    await Promise.resolve();
+    expect(order).toEqual(['first:start']);
+
+    gate.resolve();
+    await expect(Promise.all([first, second])).resolves.toEqual([1, 2]);
+    expect(order).toEqual(['first:start', 'first:end', 'second']);
+  });
+
+  it('keeps the queue moving after a task fails', async () => {
+    const lock = new WriteLock('test', null);
+
+    const failed = lock.run(async () => {
+      throw new Error('db locked');
+    });
+    const next = lock.run(async () => 'ok');
+
+    await expect(failed).rejects.toThrow('db locked');
+    await expect(next).resolves.toBe('ok');
+  });
+
+  it('holds the named Web Lock while a task runs', async () => {
+    const request = vi.fn(async (_name: string, callback: () => Promise<unknown>) => callback());
+    const lock = new WriteLock('picnexus-test', { request } as unknown as LockManager);
+
+    await expect(lock.run(async () => 'done')).resolves.toBe('done');
+    expect(request).toHaveBeenCalledWith('picnexus-test', expect.any(Function));
+  });
+});
@@ -174,6 +174,11 @@ class MockDatabase {
       success_count: params[21],
       successful_service_ids: params[22],
       migration_skip: params[23],
+      short_url: params[24],
+      alt_text: params[25],
+      title: params[26],
+      alt_suggestion: params[27],
+      capture_date: params[28],
     });
     return { rowsAffected: 1 };
   }
@@ -275,6 +280,41 @@ describe('HistoryDatabase', () => {
     expect(found?.favoriteUpdatedBy).toBe('device-new');
   });
 
+  it('modify() serializes concurrent read-modify-writes so no appended result is lost', async () => {
+    const { historyDB } = await import('@/services/HistoryDatabase');
+    const item = makeHistoryItem({ id: 'concurrent-modify' });
+    await historyDB.insert(item);
+
+    const append = (serviceId: string) => historyDB.modify(item.id, (latest) => ({
+      results: [
+        ...latest.results,
+        {
+          serviceId,
+          status: 'success',
+          result: { serviceId, fileKey: `${serviceId}-key`, url: `https://example.com/${serviceId}.jpg` },
+        },
+      ],
+    }));
+    await Promise.all([append('r2'), append('github'), historyDB.update(item.id, { shortUrl: 'https://s.example/a' })]);
+
+    const found = await historyDB.getById(item.id);
+    expect(found?.results.map(r => r.serviceId)).toEqual(['weibo', 'r2', 'github']);
+    expect(found?.shortUrl).toBe('https://s.example/a');
+  });
+
+  it('modify() returns null for missing rows and skips the write when the mutator returns null', async () => {
+    const { historyDB } = await import('@/services/HistoryDatabase');
+    await historyDB.insert(makeHistoryItem({ id: 'modify-noop' }));
+    const mutator = vi.fn(() => null);
+
+    expect(await historyDB.modify('missing', () => ({ shortUrl: 'x' }))).toBeNull();
+    const unchanged = await historyDB.modify('modify-noop', mutator);
+
+    expect(mutator).toHaveBeenCalledTimes(1);
+    expect(unchanged?.id).toBe('modify-noop');
+    expect((await historyDB.getById('modify-noop'))?.shortUrl).toBeUndefined();
+  });
+
   it('getByFilePath() returns the newest matching row deterministically', async () => {
     const { historyDB } = await import('@/services/HistoryDatabase');
     await historyDB.insert(makeHistoryItem({
@@ -0,0 +1,47 @@
+import { beforeEach, describe, expect, it } from 'vitest';
+import { getInvokeMock } from '../helpers/tauriMock';
+import { describeHostHealth, getHostHealth, recordHostOutcome } from '@/services/hostHealth';
+
+describe('hostHealth', () => {
+  beforeEach(() => {
+    getInvokeMock().mockReset();
+  });
+
+  it('reports rounded latency and drops the error kind for successful uploads', async () => {
+    getInvokeMock().mockResolvedValue(undefined);
+
+    await recordHostOutcome('weibo', true, 1234.6, 'NETWORK_ERROR');
+    await recordHostOutcome('jd', false, -5, 'RATE_LIMITED');
+
+    expect(getInvokeMock()).toHaveBeenNthCalledWith(1, 'record_host_outcome', {
+      service: 'weibo', ok: true, latencyMs: 1235, errorKind: null,
+    });
+    expect(getInvokeMock()).toHaveBeenNthCalledWith(2, 'record_host_outcome', {
+      service: 'jd', ok: false, latencyMs: 0, errorKind: 'RATE_LIMITED',
+    });
+  });
+
+  it('swallows reporting failures so uploads are not affected', async () => {
+    getInvokeMock().mockRejectedValue(new Error('ipc down'));
+    await expect(recordHostOutcome('weibo', true, 10)).resolves.toBeUndefined();
+  });
+
+  it('returns an empty snapshot when the backend has no data', async () => {
+    getInvokeMock().mockResolvedValue(undefined);
+    await expect(getHostHealth()).resolves.toEqual([]);
+  });
+
+  it('describes attempts, success rate, latency and the latest error', () => {
+    expect(describeHostHealth({
+      service: 'weibo',
+      attempts: 12,
+      successes: 11,
+      successRate: 11 / 12,
+      avgLatencyMs: 1420,
+      recentErrors: ['RATE_LIMITED', 'NETWORK_ERROR'],
+    })).toBe('近 1 小时 12 次上传 · 成功率 92% · 平均 1.4 s · 最近错误 RATE_LIMITED');
+    expect(describeHostHealth({
+      service: 'jd', attempts: 2, successes: 0, successRate: 0, avgLatencyMs: null, recentErrors: [],
+    })).toBe('近 1 小时 2 次上传 · 成功率 0%');
+  });
+});
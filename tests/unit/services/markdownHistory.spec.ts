@@ -0,0 +1,69 @@
+import { beforeEach, describe, expect, it, vi } from 'vitest';
+import { listen } from '@tauri-apps/api/event';
+
+const saveHistoryItem = vi.fn().mockResolvedValue('history-id');
+
+vi.mock('@/composables/useHistorySaver', () => ({
+  useHistorySaver: () => ({ saveHistoryItem }),
+}));
+
+vi.mock('@/utils/logger', () => ({
+  createLogger: () => ({
+    debug: vi.fn(),
+    info: vi.fn(),
+    warn: vi.fn(),
+    error: vi.fn(),
+  }),
+}));
+
+import {
+  MD_UPLOADED_EVENT,
+  startMarkdownHistoryListener,
+  toMultiUploadResult,
+} from '@/services/markdownHistory';
+
+describe('markdownHistory', () => {
+  beforeEach(() => {
+    saveHistoryItem.mockClear();
+  });
+
+  it('maps an uploaded image to a single-service upload result', () => {
+    const result = toMultiUploadResult({
+      localPath: '/notes/img/图 1.png',
+      serviceId: 'r2',
+      url: 'https://img.example.com/blog/%E5%9B%BE%201.png',
+    });
+
+    expect(result).toEqual({
+      primaryService: 'r2',
+      primaryUrl: 'https://img.example.com/blog/%E5%9B%BE%201.png',
+      results: [
+        {
+          serviceId: 'r2',
+          status: 'success',
+          result: {
+            serviceId: 'r2',
+            fileKey: 'blog/图 1.png',
+            url: 'https://img.example.com/blog/%E5%9B%BE%201.png',
+          },
+        },
+      ],
+    });
+  });
+
+  it('falls back to the full url as file key when it cannot be parsed', () => {
+    const result = toMultiUploadResult({ localPath: '/a.png', serviceId: 'weibo', url: 'not a url' });
+    expect(result.results[0].result?.fileKey).toBe('not a url');
+  });
+
+  it('saves a history item for every uploaded event', async () => {
+    await startMarkdownHistoryListener();
+    const [eventName, callback] = vi.mocked(listen).mock.calls.at(-1)!;
+    expect(eventName).toBe(MD_UPLOADED_EVENT);
+
+    const payload = { localPath: '/notes/a.png', serviceId: 'github', url: 'https://cdn.example.com/a.png' };
+    (callback as (event: { payload: typeof payload }) => void)({ payload });
+
+    expect(saveHistoryItem).toHaveBeenCalledWith('/notes/a.png', toMultiUploadResult(payload));
+  });
+});
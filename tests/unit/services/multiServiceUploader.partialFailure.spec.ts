@@ -26,6 +26,12 @@ vi.mock('@/utils/semaphore', () => ({
   }),
 }));
 
+vi.mock('@/utils/networkMonitor', () => ({
+  isOnline: { value: true },
+  waitUntilOnline: vi.fn(async () => undefined),
+  recheckAfterFailure: vi.fn(async () => true),
+}));
+
 vi.mock('@/uploaders/weibo/WeiboError', () => ({
   convertToStructuredWeiboError: (err: unknown) => ({
     code: 'WEIBO_AUTH_FAILED',
@@ -3,6 +3,8 @@ import { MultiServiceUploader } from '@/core/MultiServiceUploader';
 import type { SingleServiceResult } from '@/core/MultiServiceUploader';
 import type { UserConfig, ServiceType } from '@/config/types';
 import type { UploadResult, ValidationResult } from '@/uploaders/base/types';
+import { getInvokeMock } from '../helpers/tauriMock';
+import { __resetUploadIdempotencyForTests } from '@/utils/uploadIdempotency';
 
 // Mock UploaderFactory
 vi.mock('@/uploaders/base/UploaderFactory', () => ({
@@ -28,6 +30,14 @@ vi.mock('@/utils/semaphore', () => ({
   }),
 }));
 
+// Mock 网络状态 — 默认在线，失败后复查仍在线
+const networkMock = vi.hoisted(() => ({
+  isOnline: { value: true },
+  waitUntilOnline: vi.fn(async () => undefined),
+  recheckAfterFailure: vi.fn(async () => true),
+}));
+vi.mock('@/utils/networkMonitor', () => networkMock);
+
 // Mock 结构化错误转换器（避免引入真实依赖）
 vi.mock('@/uploaders/weibo/WeiboError', () => ({
   convertToStructuredWeiboError: (err: unknown) => ({
@@ -129,6 +139,10 @@ describe('MultiServiceUploader', () => {
 
   beforeEach(() => {
     vi.clearAllMocks();
+    __resetUploadIdempotencyForTests();
+    networkMock.isOnline.value = true;
+    networkMock.waitUntilOnline.mockImplementation(async () => undefined);
+    networkMock.recheckAfterFailure.mockImplementation(async () => true);
     uploader = new MultiServiceUploader();
   });
 
@@ -321,6 +335,29 @@ describe('MultiServiceUploader', () => {
     expect(githubResult?.error).toContain('配置验证失败');
   });
 
+  it('上传结果上报到健康记分板，配置验证失败不计入', async () => {
+    const invalidMock = makeMockUploader({
+      validationResult: { valid: false, errors: ['token 为空'] },
+    });
+    const failingMock = makeMockUploader({ uploadError: new Error('boom') });
+
+    mockCreate.mockImplementation((serviceId: string) => {
+      if (serviceId === 'github') return invalidMock as never;
+      if (serviceId === 'smms') return failingMock as never;
+      throw new Error(`未注册: ${serviceId}`);
+    });
+
+    await expect(uploader.uploadToMultipleServices(
+      '/tmp/test.jpg',
+      ['smms', 'github'] as ServiceType[],
+      makeConfig(),
+    )).rejects.toThrow();
+
+    const reports = getInvokeMock().mock.calls.filter(([cmd]) => cmd === 'record_host_outcome');
+    expect(reports).toHaveLength(1);
+    expect(reports[0][1]).toMatchObject({ service: 'smms', ok: false, errorKind: 'UPLOAD_FAILED' });
+  });
+
   // ---------- 9. 未配置的服务被过滤掉 ----------
 
   it('未配置的服务被过滤，若全被过滤则抛出错误', async () => {
@@ -378,6 +415,69 @@ describe('MultiServiceUploader', () => {
     expect(collected[0].serviceId).toBe('smms');
   });
 
+  // ---------- 断网暂停与恢复 ----------
+
+  describe('网络断开', () => {
+    function goOnlineWhenAwaited() {
+      networkMock.waitUntilOnline.mockImplementation(async () => {
+        networkMock.isOnline.value = true;
+      });
+    }
+
+    it('断网时暂停上传，网络恢复后继续', async () => {
+      const mock = makeMockUploader({ url: 'https://smms.app/a.png' });
+      mockCreate.mockReturnValue(mock as never);
+      networkMock.isOnline.value = false;
+      goOnlineWhenAwaited();
+      const onProgress = vi.fn();
+
+      const result = await uploader.uploadToMultipleServices(
+        '/tmp/test.jpg',
+        ['smms'] as ServiceType[],
+        makeConfig(),
+        onProgress,
+      );
+
+      expect(networkMock.waitUntilOnline).toHaveBeenCalledTimes(1);
+      expect(onProgress).toHaveBeenCalledWith('smms', 10, expect.stringContaining('网络已断开'), 1, 2);
+      expect(mock.upload).toHaveBeenCalledTimes(1);
+      expect(result.primaryUrl).toBe('https://smms.app/a.png');
+    });
+
+    it('上传中途断网时等网络恢复后重新上传', async () => {
+      const mock = makeMockUploader({ url: 'https://smms.app/a.png' });
+      mock.upload.mockRejectedValueOnce(new Error('connection reset'));
+      mockCreate.mockReturnValue(mock as never);
+      networkMock.recheckAfterFailure.mockImplementationOnce(async () => {
+        networkMock.isOnline.value = false;
+        return false;
+      });
+      goOnlineWhenAwaited();
+
+      const result = await uploader.uploadToMultipleServices(
+        '/tmp/test.jpg',
+        ['smms'] as ServiceType[],
+        makeConfig(),
+      );
+
+      expect(mock.upload).toHaveBeenCalledTimes(2);
+      expect(networkMock.waitUntilOnline).toHaveBeenCalledTimes(1);
+      expect(result.results[0].status).toBe('success');
+    });
+
+    it('网络正常时的失败不重新上传', async () => {
+      const mock = makeMockUploader({ uploadError: new Error('token 无效') });
+      mockCreate.mockReturnValue(mock as never);
+
+      await expect(
+        uploader.retryUpload('/tmp/test.jpg', 'smms' as ServiceType, makeConfig()),
+      ).rejects.toThrow('token 无效');
+
+      expect(mock.upload).toHaveBeenCalledTimes(1);
+      expect(networkMock.recheckAfterFailure).toHaveBeenCalledTimes(1);
+    });
+  });
+
   // ---------- 12. retryUpload 单图床重试 ----------
 
   describe('retryUpload', () => {
@@ -427,6 +527,23 @@ describe('MultiServiceUploader', () => {
       );
     });
 
+    it('重试时透传该图床的自定义请求头', async () => {
+      const mock = makeMockUploader({ url: 'https://smms.app/retry.png' });
+      mockCreate.mockReturnValue(mock as never);
+      const requestHeaders = { userAgent: 'Custom/1.0', headers: [] };
+
+      await uploader.retryUpload('/tmp/test.jpg', 'smms' as ServiceType, {
+        ...makeConfig(),
+        requestHeaderOverrides: { smms: requestHeaders, github: { headers: [] } },
+      } as UserConfig);
+
+      expect(mock.upload).toHaveBeenCalledWith(
+        '/tmp/test.jpg',
+        expect.objectContaining({ requestHeaders }),
+        undefined,
+      );
+    });
+
     it.each(['jd', 'qiyu'] as const)('无需配置的服务 %s 重试时使用空配置继续上传', async (serviceId) => {
       const mock = makeMockUploader({ url: `https://${serviceId}.example.com/retry.png` });
       mockCreate.mockReturnValue(mock as never);
@@ -446,4 +563,57 @@ describe('MultiServiceUploader', () => {
       );
     });
   });
+
+  // ---------- 13. 幂等上传 ----------
+
+  describe('幂等上传', () => {
+    it('全量重试时已成功的图床复用结果，只重新上传失败的图床', async () => {
+      const smmsMock = makeMockUploader({ url: 'https://smms.app/a.png' });
+      const githubMock = makeMockUploader({ url: 'https://github.com/img.png' });
+      githubMock.upload.mockRejectedValueOnce(new Error('token 无效'));
+      mockCreate.mockImplementation((serviceId: string) =>
+        (serviceId === 'smms' ? smmsMock : githubMock) as never);
+      const onServiceResult = vi.fn();
+
+      const first = await uploader.uploadToMultipleServices(
+        '/tmp/test.jpg', ['smms', 'github'] as ServiceType[], makeConfig(), undefined, undefined, 'queue-1',
+      );
+      expect(first.isPartialSuccess).toBe(true);
+
+      const retried = await uploader.uploadToMultipleServices(
+        '/tmp/test.jpg', ['smms', 'github'] as ServiceType[], makeConfig(), undefined, onServiceResult, 'queue-1',
+      );
+
+      expect(smmsMock.upload).toHaveBeenCalledTimes(1);
+      expect(githubMock.upload).toHaveBeenCalledTimes(2);
+      expect(retried.results.every(r => r.status === 'success')).toBe(true);
+      expect(onServiceResult).toHaveBeenCalledWith(expect.objectContaining({
+        serviceId: 'smms',
+        status: 'success',
+        result: expect.objectContaining({ url: 'https://smms.app/a.png' }),
+      }));
+    });
+
+    it('单图床重试时该条目已成功则不再上传，其他条目照常上传', async () => {
+      const mock = makeMockUploader({ url: 'https://smms.app/a.png' });
+      mockCreate.mockReturnValue(mock as never);
+
+      await uploader.retryUpload('/tmp/test.jpg', 'smms' as ServiceType, makeConfig(), undefined, 'queue-1');
+      const reused = await uploader.retryUpload('/tmp/test.jpg', 'smms' as ServiceType, makeConfig(), undefined, 'queue-1');
+      await uploader.retryUpload('/tmp/test.jpg', 'smms' as ServiceType, makeConfig(), undefined, 'queue-2');
+
+      expect(reused.url).toBe('https://smms.app/a.png');
+      expect(mock.upload).toHaveBeenCalledTimes(2);
+    });
+
+    it('未传幂等范围时每次都重新上传', async () => {
+      const mock = makeMockUploader({ url: 'https://smms.app/a.png' });
+      mockCreate.mockReturnValue(mock as never);
+
+      await uploader.retryUpload('/tmp/test.jpg', 'smms' as ServiceType, makeConfig());
+      await uploader.retryUpload('/tmp/test.jpg', 'smms' as ServiceType, makeConfig());
+
+      expect(mock.upload).toHaveBeenCalledTimes(2);
+    });
+  });
 });
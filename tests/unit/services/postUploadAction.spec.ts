@@ -0,0 +1,72 @@
+import { beforeEach, describe, expect, it } from 'vitest';
+import { getClipboardMocks, getInvokeMock, getNotificationMocks, resetTauriMocks } from '../helpers/tauriMock';
+import { DEFAULT_CONFIG, type UserConfig } from '@/config/types';
+import { getPostUploadAction, runPostUploadAction } from '@/services/postUploadAction';
+
+const items = [
+  { url: 'https://img.example.com/a.png', fileName: 'a.png', serviceId: 'r2' },
+  { url: 'https://img.example.com/b.png', fileName: 'b.png', serviceId: 'r2', shortUrl: 'https://s.example.com/b' },
+];
+
+function makeConfig(autoCopy: boolean): UserConfig {
+  return { ...DEFAULT_CONFIG, linkOutput: { ...DEFAULT_CONFIG.linkOutput!, autoCopy, defaultFormat: 'url' } };
+}
+
+function mockAction(action: string | null) {
+  getInvokeMock().mockImplementation(async (cmd: string) => {
+    if (cmd === 'get_post_upload_action') return action;
+    return undefined;
+  });
+}
+
+describe('postUploadAction', () => {
+  beforeEach(() => {
+    resetTauriMocks();
+  });
+
+  it('falls back to the auto-copy switch when the action was never set', async () => {
+    mockAction(null);
+    await expect(getPostUploadAction(makeConfig(true))).resolves.toBe('copyLink');
+    await expect(getPostUploadAction(makeConfig(false))).resolves.toBe('none');
+
+    getInvokeMock().mockRejectedValue(new Error('ipc down'));
+    await expect(getPostUploadAction(makeConfig(true))).resolves.toBe('copyLink');
+  });
+
+  it('copies formatted links in upload order', async () => {
+    mockAction('copyLink');
+    const outcome = await runPostUploadAction(items, makeConfig(false));
+
+    expect(getClipboardMocks().writeText).toHaveBeenCalledWith(
+      'https://img.example.com/a.png\nhttps://s.example.com/b'
+    );
+    expect(outcome).toMatchObject({ action: 'copyLink', copiedCount: 2, copyFailed: false });
+  });
+
+  it('reports copy failures without throwing', async () => {
+    mockAction('copyLink');
+    getClipboardMocks().writeText.mockRejectedValue(new Error('clipboard busy'));
+    const outcome = await runPostUploadAction(items, makeConfig(true));
+    expect(outcome).toMatchObject({ action: 'copyLink', copiedCount: 0, copyFailed: true });
+  });
+
+  it('opens the first link instead of copying', async () => {
+    mockAction('openUrl');
+    await runPostUploadAction(items, makeConfig(true));
+
+    expect(getInvokeMock()).toHaveBeenCalledWith('open_path', { path: 'https://img.example.com/a.png' });
+    expect(getClipboardMocks().writeText).not.toHaveBeenCalled();
+  });
+
+  it('skips the notification when the caller already sent one', async () => {
+    mockAction('notify');
+    await runPostUploadAction(items, makeConfig(true), { notified: true });
+    expect(getNotificationMocks().sendNotification).not.toHaveBeenCalled();
+
+    await runPostUploadAction(items, makeConfig(true));
+    expect(getNotificationMocks().sendNotification).toHaveBeenCalledWith(expect.objectContaining({
+      title: '2 张图片上传完成',
+      body: 'https://img.example.com/a.png',
+    }));
+  });
+});
@@ -0,0 +1,42 @@
+import { beforeEach, describe, expect, it } from 'vitest';
+import { getInvokeMock, resetTauriMocks } from '../helpers/tauriMock';
+import { deleteSmmsImage, describeSmmsQuota, getSmmsQuota, saveSmmsToken, type SmmsQuota } from '@/services/smms';
+
+const quota: SmmsQuota = {
+  username: 'alice',
+  role: 'user',
+  diskUsage: '12.00 MB',
+  diskLimit: '5.00 GB',
+  usedBytes: 12 * 1024 * 1024,
+  limitBytes: 5 * 1024 * 1024 * 1024,
+  remainingBytes: 5 * 1024 * 1024 * 1024 - 12 * 1024 * 1024,
+};
+
+describe('smms service', () => {
+  beforeEach(() => {
+    resetTauriMocks();
+  });
+
+  it('passes the form token or falls back to the stored one', async () => {
+    getInvokeMock().mockResolvedValue(quota);
+
+    await getSmmsQuota(' form-token ');
+    expect(getInvokeMock()).toHaveBeenLastCalledWith('get_smms_quota', { token: 'form-token' });
+
+    await getSmmsQuota();
+    expect(getInvokeMock()).toHaveBeenLastCalledWith('get_smms_quota', { token: null });
+
+    await deleteSmmsImage('abc123', '');
+    expect(getInvokeMock()).toHaveBeenLastCalledWith('delete_smms_image', { hash: 'abc123', token: null });
+  });
+
+  it('does not throw when the keychain is unavailable', async () => {
+    getInvokeMock().mockRejectedValue(new Error('keychain locked'));
+    await expect(saveSmmsToken('token')).resolves.toBeUndefined();
+  });
+
+  it('describes remaining space', () => {
+    expect(describeSmmsQuota(quota)).toBe('剩余 5.0 GB / 共 5.0 GB（已用 12.0 MB）');
+    expect(describeSmmsQuota({ ...quota, limitBytes: 0, remainingBytes: 0 })).toBe('已用 12.00 MB');
+  });
+});
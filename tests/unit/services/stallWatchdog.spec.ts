@@ -0,0 +1,54 @@
+import { afterEach, beforeEach, describe, expect, it, vi } from 'vitest';
+import { getInvokeMock } from '../helpers/tauriMock';
+import { STALL_TIMEOUT_MS, checkStalledTasks, watchTask } from '@/services/stallWatchdog';
+
+describe('stallWatchdog', () => {
+  beforeEach(() => {
+    vi.useFakeTimers();
+    vi.setSystemTime(0);
+    getInvokeMock().mockReset();
+    getInvokeMock().mockResolvedValue(true);
+  });
+
+  afterEach(() => {
+    vi.useRealTimers();
+  });
+
+  it('aborts a task once it has had no progress for the timeout', async () => {
+    const watch = watchTask('weibo_1', '微博');
+    watch.touch(40, '上传中...');
+
+    await checkStalledTasks(STALL_TIMEOUT_MS - 1);
+    expect(getInvokeMock()).not.toHaveBeenCalled();
+
+    await checkStalledTasks(STALL_TIMEOUT_MS + 1000);
+    expect(getInvokeMock()).toHaveBeenCalledWith('cancel_stalled_task', {
+      taskId: 'weibo_1', idleSecs: 301, progress: 40, step: '上传中...',
+    });
+
+    // 已中止的任务不再重复中止
+    await checkStalledTasks(STALL_TIMEOUT_MS * 2);
+    expect(getInvokeMock()).toHaveBeenCalledTimes(1);
+    watch.stop();
+  });
+
+  it('restarts the timer on progress and forgets stopped tasks', async () => {
+    const active = watchTask('jd_1', '京东');
+    const finished = watchTask('jd_2', '京东');
+    finished.stop();
+
+    vi.setSystemTime(STALL_TIMEOUT_MS - 1000);
+    active.touch(80);
+    await checkStalledTasks(STALL_TIMEOUT_MS + 1000);
+
+    expect(getInvokeMock()).not.toHaveBeenCalled();
+    active.stop();
+  });
+
+  it('swallows backend failures', async () => {
+    getInvokeMock().mockRejectedValue(new Error('ipc down'));
+    const watch = watchTask('smms_1', 'SM.MS');
+    await expect(checkStalledTasks(STALL_TIMEOUT_MS)).resolves.toBeUndefined();
+    watch.stop();
+  });
+});
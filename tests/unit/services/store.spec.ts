@@ -1,11 +1,13 @@
 import { describe, it, expect, vi, beforeEach } from 'vitest';
 import { Store, StoreError } from '@/store';
-import { readTextFile, writeTextFile, exists, mkdir } from '@tauri-apps/plugin-fs';
+import { readTextFile, writeTextFile, exists, mkdir, remove, rename } from '@tauri-apps/plugin-fs';
 
 const mockReadTextFile = vi.mocked(readTextFile);
 const mockWriteTextFile = vi.mocked(writeTextFile);
 const mockExists = vi.mocked(exists);
 const mockMkdir = vi.mocked(mkdir);
+const mockRemove = vi.mocked(remove);
+const mockRename = vi.mocked(rename);
 
 describe('Store（非加密模式）', () => {
   let store: Store;
@@ -173,6 +175,23 @@ describe('Store（非加密模式）', () => {
       expect(parsed.newKey).toBe('newValue');
     });
 
+    it('先写临时文件再改名覆盖，不直接截断数据文件', async () => {
+      await store.set('color', 'blue');
+
+      const tempPath = (mockWriteTextFile.mock.calls[0] as unknown[])[0] as string;
+      expect(tempPath).toMatch(/test\.dat\.\d+\.tmp$/);
+      expect(mockRename).toHaveBeenCalledWith(tempPath, expect.stringMatching(/test\.dat$/));
+    });
+
+    it('改名失败时删除临时文件并抛出 StoreError', async () => {
+      mockRename.mockRejectedValueOnce(new Error('Permission denied'));
+
+      await expect(store.set('color', 'blue')).rejects.toBeInstanceOf(StoreError);
+
+      const tempPath = (mockWriteTextFile.mock.calls[0] as unknown[])[0] as string;
+      expect(mockRemove).toHaveBeenCalledWith(tempPath);
+    });
+
     it('空 key 抛出 StoreError（operation=write）', async () => {
       await expect(store.set('', 'value')).rejects.toThrow(StoreError);
 
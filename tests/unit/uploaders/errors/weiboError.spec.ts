@@ -43,6 +43,14 @@ describe('convertToWeiboError', () => {
     expect(err.code).toBe('COOKIE_EXPIRED');
   });
 
+  it('上传返回 401/403 → COOKIE_EXPIRED 并带上 HTTP 状态', () => {
+    const err = convertToWeiboError('Cookie expired (HTTP 403)');
+    expect(err.code).toBe('COOKIE_EXPIRED');
+    expect(err.httpStatus).toBe(403);
+    expect(err.message).toContain('HTTP 403');
+    expect(err.message).not.toContain('100006');
+  });
+
   it('原始 Error 对象被保留在 originalError', () => {
     const cause = new Error('Cookie expired xyz');
     const err = convertToWeiboError(cause);
@@ -9,7 +9,7 @@ vi.mock('@/utils/logger', () => ({
   }),
 }));
 
-import { initializeUploaders, syncCustomS3Uploaders } from '@/uploaders';
+import { initializeUploaders, syncCustomS3Uploaders, syncPluginUploaders } from '@/uploaders';
 import { UploaderFactory } from '@/uploaders/base/UploaderFactory';
 
 describe('initializeUploaders', () => {
@@ -81,3 +81,25 @@ describe('syncCustomS3Uploaders', () => {
     expect(u).toBeDefined();
   });
 });
+
+describe('syncPluginUploaders', () => {
+  beforeEach(() => {
+    for (const id of UploaderFactory.getAvailableServices()) {
+      UploaderFactory.unregister(id);
+    }
+  });
+
+  it('为每个 profile 注册 plugin:<id>，重复调用会替换旧注册', () => {
+    syncPluginUploaders([{ id: 'p1', name: 'A' } as any]);
+    syncPluginUploaders([{ id: 'p2', name: 'B' } as any]);
+    const ids = UploaderFactory.getAvailableServices();
+    expect(ids).not.toContain('plugin:p1');
+    expect(ids).toContain('plugin:p2');
+  });
+
+  it('不影响 custom_s3:* 注册', () => {
+    syncCustomS3Uploaders([{ id: 's1', name: 'S3' } as any]);
+    syncPluginUploaders([]);
+    expect(UploaderFactory.getAvailableServices()).toContain('custom_s3:s1');
+  });
+});
@@ -0,0 +1,80 @@
+// PluginUploader 测试：validateConfig / upload
+
+import { describe, it, expect, beforeEach, vi } from 'vitest';
+import { getInvokeMock } from '../helpers/tauriMock';
+import type { UploadOptions } from '@/uploaders/base/types';
+import type { PluginUploaderProfile } from '@/config/types';
+
+const invokeMock = getInvokeMock();
+
+vi.mock('@/utils/logger', () => ({
+  createLogger: () => ({
+    debug: vi.fn(),
+    info: vi.fn(),
+    warn: vi.fn(),
+    error: vi.fn(),
+  }),
+}));
+
+const { PluginUploader } = await import('@/uploaders/plugin/PluginUploader');
+
+function makeProfile(overrides: Partial<PluginUploaderProfile> = {}): PluginUploaderProfile {
+  return {
+    id: 'p1',
+    name: 'Lsky',
+    command: '',
+    script: '/plugins/lsky.js',
+    args: [],
+    ...overrides,
+  };
+}
+
+function makeOptions(profile = makeProfile()): UploadOptions {
+  return { config: profile } as UploadOptions;
+}
+
+describe('PluginUploader.validateConfig', () => {
+  const uploader = new PluginUploader();
+
+  it('有脚本路径 → valid', async () => {
+    expect(await uploader.validateConfig(makeProfile())).toEqual({ valid: true });
+  });
+
+  it('脚本路径为空 → invalid', async () => {
+    const r = await uploader.validateConfig(makeProfile({ script: '  ' }));
+    expect(r.valid).toBe(false);
+    expect(r.missingFields).toContain('script');
+  });
+});
+
+describe('PluginUploader.upload', () => {
+  beforeEach(() => {
+    invokeMock.mockReset();
+  });
+
+  it('调用 upload_via_plugin，空解释器传 null', async () => {
+    invokeMock.mockResolvedValue({ url: 'https://img.example.com/a.png' });
+
+    await new PluginUploader().upload('/tmp/a.png', makeOptions(makeProfile({ args: ['--album', '3'] })));
+
+    expect(invokeMock).toHaveBeenCalledWith('upload_via_plugin', expect.objectContaining({
+      filePath: '/tmp/a.png',
+      plugin: { command: null, script: '/plugins/lsky.js', args: ['--album', '3'] },
+    }));
+  });
+
+  it('返回脚本输出的链接作为 url 和 fileKey', async () => {
+    invokeMock.mockResolvedValue({ url: 'https://img.example.com/a.png' });
+
+    const r = await new PluginUploader().upload('/tmp/a.png', makeOptions(makeProfile({ command: 'python3' })));
+
+    expect(r).toEqual({
+      serviceId: 'plugin',
+      fileKey: 'https://img.example.com/a.png',
+      url: 'https://img.example.com/a.png',
+    });
+    expect(invokeMock).toHaveBeenCalledWith('upload_via_plugin', expect.objectContaining({
+      plugin: expect.objectContaining({ command: 'python3' }),
+    }));
+  });
+});
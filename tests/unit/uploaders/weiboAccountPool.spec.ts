@@ -0,0 +1,128 @@
+import { describe, it, expect, vi, beforeEach } from 'vitest';
+
+vi.mock('@/store/instances', () => ({
+  syncStatusStore: {
+    get: vi.fn().mockResolvedValue(null),
+    set: vi.fn().mockResolvedValue(undefined),
+  },
+}));
+
+import {
+  __resetWeiboAccountPoolForTests,
+  listWeiboAccounts,
+  pickWeiboAccounts,
+  PRIMARY_WEIBO_ACCOUNT_ID,
+  recordWeiboAccountResult,
+  resetWeiboAccountHealth,
+  unavailableWeiboAccountsReason,
+} from '@/uploaders/weibo/WeiboAccountPool';
+import type { WeiboServiceConfig } from '@/config/types';
+
+function makeConfig(overrides: Partial<WeiboServiceConfig> = {}): WeiboServiceConfig {
+  return {
+    enabled: true,
+    cookie: 'SUB=primary',
+    accounts: [
+      { id: 'a', name: '账号 A', cookie: 'SUB=a', enabled: true },
+      { id: 'b', name: '账号 B', cookie: 'SUB=b', enabled: true },
+    ],
+    ...overrides,
+  };
+}
+
+const ids = (accounts: { id: string }[]) => accounts.map(a => a.id);
+
+describe('WeiboAccountPool', () => {
+  beforeEach(() => {
+    __resetWeiboAccountPoolForTests();
+  });
+
+  it('主账号在前，跳过停用、空 Cookie 与重复 Cookie 的账号', () => {
+    const accounts = listWeiboAccounts(makeConfig({
+      accounts: [
+        { id: 'a', name: 'A', cookie: 'SUB=a', enabled: true },
+        { id: 'off', name: 'Off', cookie: 'SUB=off', enabled: false },
+        { id: 'empty', name: 'Empty', cookie: '  ', enabled: true },
+        { id: 'dup', name: 'Dup', cookie: 'SUB=primary', enabled: true },
+      ],
+    }));
+    expect(ids(accounts)).toEqual([PRIMARY_WEIBO_ACCOUNT_ID, 'a']);
+  });
+
+  it('round-robin 每次上传轮换首选账号', async () => {
+    const config = makeConfig();
+    expect(ids(await pickWeiboAccounts(config))).toEqual(['primary', 'a', 'b']);
+    expect(ids(await pickWeiboAccounts(config))).toEqual(['a', 'b', 'primary']);
+    expect(ids(await pickWeiboAccounts(config))).toEqual(['b', 'primary', 'a']);
+  });
+
+  it('lru 优先最久未使用的账号', async () => {
+    const config = makeConfig({ rotation: 'lru' });
+    const [primary, a, b] = listWeiboAccounts(config);
+    recordWeiboAccountResult(primary, 'success', undefined, 3000);
+    recordWeiboAccountResult(a, 'success', undefined, 1000);
+    recordWeiboAccountResult(b, 'success', undefined, 2000);
+
+    expect(ids(await pickWeiboAccounts(config))).toEqual(['a', 'b', 'primary']);
+  });
+
+  it('Cookie 失效的账号被排除，更换 Cookie 后恢复', async () => {
+    const config = makeConfig();
+    const [, a] = listWeiboAccounts(config);
+    recordWeiboAccountResult(a, 'expired');
+
+    expect(ids(await pickWeiboAccounts(config))).not.toContain('a');
+
+    const relogged = makeConfig({
+      accounts: [
+        { id: 'a', name: '账号 A', cookie: 'SUB=a2', enabled: true },
+        { id: 'b', name: '账号 B', cookie: 'SUB=b', enabled: true },
+      ],
+    });
+    expect(ids(await pickWeiboAccounts(relogged))).toContain('a');
+  });
+
+  it('限流暂停到期后重新参与轮换，连续限流暂停时长翻倍', async () => {
+    const config = makeConfig({ accounts: [] });
+    const [primary] = listWeiboAccounts(config);
+    const now = 1_000_000;
+
+    recordWeiboAccountResult(primary, 'rate-limited', undefined, now);
+    expect(await pickWeiboAccounts(config, now + 9 * 60 * 1000)).toEqual([]);
+    expect(ids(await pickWeiboAccounts(config, now + 10 * 60 * 1000 + 1))).toEqual(['primary']);
+
+    const later = now + 11 * 60 * 1000;
+    recordWeiboAccountResult(primary, 'rate-limited', undefined, later);
+    expect(await pickWeiboAccounts(config, later + 15 * 60 * 1000)).toEqual([]);
+    expect(ids(await pickWeiboAccounts(config, later + 20 * 60 * 1000 + 1))).toEqual(['primary']);
+  });
+
+  it('普通失败不停用账号', async () => {
+    const config = makeConfig({ accounts: [] });
+    const [primary] = listWeiboAccounts(config);
+    recordWeiboAccountResult(primary, 'failed', 'timeout');
+
+    expect(ids(await pickWeiboAccounts(config))).toEqual(['primary']);
+  });
+
+  it('无可用账号时区分全部失效与限流中', async () => {
+    const config = makeConfig({ accounts: [{ id: 'a', name: 'A', cookie: 'SUB=a', enabled: true }] });
+    const [primary, a] = listWeiboAccounts(config);
+
+    recordWeiboAccountResult(primary, 'expired');
+    recordWeiboAccountResult(a, 'rate-limited');
+    expect(unavailableWeiboAccountsReason(config)).toBe('banned');
+
+    recordWeiboAccountResult(a, 'expired');
+    expect(unavailableWeiboAccountsReason(config)).toBe('expired');
+  });
+
+  it('手动恢复清除失效与限流状态', async () => {
+    const config = makeConfig({ accounts: [] });
+    const [primary] = listWeiboAccounts(config);
+    recordWeiboAccountResult(primary, 'rate-limited');
+
+    resetWeiboAccountHealth(PRIMARY_WEIBO_ACCOUNT_ID);
+    expect(ids(await pickWeiboAccounts(config))).toEqual(['primary']);
+  });
+});
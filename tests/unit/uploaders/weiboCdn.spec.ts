@@ -0,0 +1,75 @@
+import { describe, it, expect, vi, beforeEach } from 'vitest';
+
+vi.mock('@/store/instances', () => ({
+  syncStatusStore: {
+    get: vi.fn().mockResolvedValue(null),
+    set: vi.fn().mockResolvedValue(undefined),
+  },
+}));
+
+import {
+  __resetWeiboCdnForTests,
+  applyWeiboCdnFromConfig,
+  getWeiboCdnHostFromUrl,
+  pickFastestWeiboCdnHost,
+  recordWeiboCdnLatency,
+  rewriteWeiboCdnHost,
+  weiboCdnProbeHost,
+} from '@/uploaders/weibo/weiboCdn';
+import type { UserConfig, WeiboCdnSetting } from '@/config/types';
+
+function configWith(cdnHost?: WeiboCdnSetting): UserConfig {
+  return { services: { weibo: { enabled: true, cookie: '', cdnHost } } } as UserConfig;
+}
+
+const URL_TVAX = 'https://tvax1.sinaimg.cn/large/abc.jpg';
+
+describe('weiboCdn', () => {
+  beforeEach(() => {
+    __resetWeiboCdnForTests();
+  });
+
+  it('只改写 sinaimg 节点，保留路径', () => {
+    expect(rewriteWeiboCdnHost(URL_TVAX, 'wx3')).toBe('https://wx3.sinaimg.cn/large/abc.jpg');
+    expect(rewriteWeiboCdnHost('https://fake.sinaimg.cn.evil.test/a.jpg', 'wx1'))
+      .toBe('https://fake.sinaimg.cn.evil.test/a.jpg');
+    expect(getWeiboCdnHostFromUrl('https://ww1.sinaimg.cn/mw690/a.jpg')).toBe('ww1');
+    expect(getWeiboCdnHostFromUrl('https://example.com/a.jpg')).toBeNull();
+  });
+
+  it('未设置节点时保持原链接', () => {
+    expect(applyWeiboCdnFromConfig('https://ww1.sinaimg.cn/large/abc.jpg', configWith()))
+      .toBe('https://ww1.sinaimg.cn/large/abc.jpg');
+    expect(applyWeiboCdnFromConfig(URL_TVAX, configWith('wx4'))).toBe('https://wx4.sinaimg.cn/large/abc.jpg');
+  });
+
+  it('自动模式选择样本充足且平均延迟最低的节点', async () => {
+    expect(pickFastestWeiboCdnHost()).toBe('tvax1');
+
+    const samples = (host: string, ms: number, count: number) =>
+      Array.from({ length: count }, () => ({ url: `https://${host}.sinaimg.cn/large/a.jpg`, responseTime: ms, ok: true }));
+    await recordWeiboCdnLatency([
+      ...samples('tvax1', 400, 3),
+      ...samples('wx2', 120, 3),
+      // 样本不足，不参与选择
+      ...samples('ww1', 10, 1),
+    ]);
+
+    expect(pickFastestWeiboCdnHost()).toBe('wx2');
+    expect(applyWeiboCdnFromConfig(URL_TVAX, configWith('auto'))).toBe('https://wx2.sinaimg.cn/large/abc.jpg');
+  });
+
+  it('检测失败按惩罚延迟计入', async () => {
+    const fast = { url: 'https://wx1.sinaimg.cn/a.jpg', responseTime: 50, ok: false };
+    const slow = { url: 'https://wx2.sinaimg.cn/a.jpg', responseTime: 800, ok: true };
+    await recordWeiboCdnLatency([fast, fast, fast, slow, slow, slow]);
+
+    expect(pickFastestWeiboCdnHost()).toBe('wx2');
+  });
+
+  it('测速轮流使用各节点', () => {
+    expect(weiboCdnProbeHost(0)).toBe('tvax1');
+    expect(weiboCdnProbeHost(1)).toBe('wx1');
+    expect(weiboCdnProbeHost(6)).toBe('tvax1');
+  });
+});
@@ -0,0 +1,34 @@
+import { describe, it, expect } from 'vitest';
+import {
+  buildWeiboSizeVariants,
+  getWeiboSizeVariants,
+  WEIBO_SIZE_OPTIONS,
+} from '@/uploaders/weibo/weiboSizes';
+
+describe('weiboSizes', () => {
+  it('为每个尺寸生成对应 URL', () => {
+    const variants = buildWeiboSizeVariants('006abc');
+    expect(Object.keys(variants)).toEqual(WEIBO_SIZE_OPTIONS.map(o => o.size));
+    expect(variants.large).toBe('https://tvax1.sinaimg.cn/large/006abc.jpg');
+    expect(variants.orj360).toBe('https://tvax1.sinaimg.cn/orj360/006abc.jpg');
+    expect(variants.thumb150).toBe('https://tvax1.sinaimg.cn/thumb150/006abc.jpg');
+  });
+
+  it('优先使用记录中保存的变体', () => {
+    const stored = buildWeiboSizeVariants('stored');
+    const variants = getWeiboSizeVariants({
+      serviceId: 'weibo',
+      fileKey: 'other',
+      url: stored.large,
+      metadata: { variants: stored },
+    });
+    expect(variants).toBe(stored);
+  });
+
+  it('旧记录按 fileKey 生成，非微博结果返回 null', () => {
+    expect(getWeiboSizeVariants({ serviceId: 'weibo', fileKey: 'old', url: '' })?.mw2048)
+      .toBe('https://tvax1.sinaimg.cn/mw2048/old.jpg');
+    expect(getWeiboSizeVariants({ serviceId: 'r2', fileKey: 'k', url: 'https://r2/k' })).toBeNull();
+    expect(getWeiboSizeVariants(undefined)).toBeNull();
+  });
+});
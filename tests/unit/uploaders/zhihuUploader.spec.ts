@@ -82,6 +82,22 @@ describe('ZhihuUploader.upload', () => {
     );
   });
 
+  it('透传自定义请求头，未配置时传 null', async () => {
+    invokeMock.mockResolvedValue({ url: 'u', size: 1 });
+    const requestHeaders = { userAgent: 'Custom/1.0', headers: [{ name: 'X-Test', value: '1' }] };
+    await new ZhihuUploader().upload('/tmp/y.jpg', { ...makeOptions(), requestHeaders });
+    expect(invokeMock).toHaveBeenLastCalledWith(
+      'upload_to_zhihu',
+      expect.objectContaining({ requestHeaders }),
+    );
+
+    await new ZhihuUploader().upload('/tmp/y.jpg', makeOptions());
+    expect(invokeMock).toHaveBeenLastCalledWith(
+      'upload_to_zhihu',
+      expect.objectContaining({ requestHeaders: null }),
+    );
+  });
+
   it('Rust 抛错时包装为带"知乎图床上传失败"前缀的 Error', async () => {
     invokeMock.mockRejectedValue(new Error('Cookie 失效'));
     await expect(new ZhihuUploader().upload('/tmp/x.jpg', makeOptions()))
@@ -0,0 +1,56 @@
+import { describe, it, expect } from 'vitest';
+import {
+  applyImageTransform,
+  applyImageTransformFromConfig,
+  supportsImageTransform,
+} from '@/utils/imageTransform';
+import type { UserConfig } from '@/config/types';
+
+const proxied = 'https://wsrv.nl/?url=https%3A%2F%2Fexample.com%2Fa.png';
+
+describe('supportsImageTransform', () => {
+  it('识别 wsrv.nl 与 images.weserv.nl', () => {
+    expect(supportsImageTransform(proxied)).toBe(true);
+    expect(supportsImageTransform('https://images.weserv.nl/?url=x')).toBe(true);
+    expect(supportsImageTransform('https://example.com/a.png')).toBe(false);
+    expect(supportsImageTransform('not a url')).toBe(false);
+  });
+});
+
+describe('applyImageTransform', () => {
+  it('追加 w / q / output 参数', () => {
+    const result = new URL(applyImageTransform(proxied, { width: 640.7, quality: 150, format: 'avif' }));
+    expect(result.searchParams.get('url')).toBe('https://example.com/a.png');
+    expect(result.searchParams.get('w')).toBe('640');
+    expect(result.searchParams.get('q')).toBe('100');
+    expect(result.searchParams.get('output')).toBe('avif');
+  });
+
+  it('覆盖已有的同名参数', () => {
+    const result = new URL(applyImageTransform(`${proxied}&w=75`, { width: 800 }));
+    expect(result.searchParams.getAll('w')).toEqual(['800']);
+  });
+
+  it('非代理链接或空参数时原样返回', () => {
+    expect(applyImageTransform('https://example.com/a.png', { width: 800 })).toBe('https://example.com/a.png');
+    expect(applyImageTransform(proxied, {})).toBe(proxied);
+    expect(applyImageTransform(proxied, undefined)).toBe(proxied);
+  });
+});
+
+describe('applyImageTransformFromConfig', () => {
+  const config = {
+    linkTransform: { enabled: true, templates: { html: { width: 320 } } },
+  } as unknown as UserConfig;
+
+  it('按复制格式取参数', () => {
+    expect(applyImageTransformFromConfig(proxied, 'html', config)).toContain('w=320');
+    expect(applyImageTransformFromConfig(proxied, 'url', config)).toBe(proxied);
+  });
+
+  it('未启用时不改写', () => {
+    const disabled = { linkTransform: { enabled: false, templates: { html: { width: 320 } } } } as unknown as UserConfig;
+    expect(applyImageTransformFromConfig(proxied, 'html', disabled)).toBe(proxied);
+    expect(applyImageTransformFromConfig(proxied, 'html', null)).toBe(proxied);
+  });
+});
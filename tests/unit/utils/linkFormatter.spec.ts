@@ -61,6 +61,22 @@ describe('formatLink', () => {
       .toBe('https://cdn.example.com/image.png?w=');
   });
 
+  it('markdown / html 使用上传时填写的替代文本与标题', () => {
+    const details = { altText: '海边日落', title: 'Sunset "golden" hour' };
+    expect(formatLink(url, fileName, 'markdown', undefined, details))
+      .toBe('![海边日落](https://cdn.example.com/image.png "Sunset \\"golden\\" hour")');
+    expect(formatLink(url, fileName, 'html', undefined, details))
+      .toBe('<img src="https://cdn.example.com/image.png" alt="海边日落" title="Sunset &quot;golden&quot; hour" />');
+  });
+
+  it('custom 格式支持 {alt} 和 {title}，未填写替代文本时 {alt} 为文件名', () => {
+    const template = '{{< figure src="{url}" alt="{alt}" title="{title}" >}}';
+    expect(formatLink(url, fileName, 'custom', template, { altText: '日落', title: '海边' }))
+      .toBe('{{< figure src="https://cdn.example.com/image.png" alt="日落" title="海边" >}}');
+    expect(formatLink(url, fileName, 'custom', template))
+      .toBe('{{< figure src="https://cdn.example.com/image.png" alt="image.png" title="" >}}');
+  });
+
   it('未知格式回退到原始 URL', () => {
     expect(formatLink(url, fileName, 'unknown' as never)).toBe(url);
   });
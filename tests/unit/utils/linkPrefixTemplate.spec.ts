@@ -4,6 +4,7 @@ import {
   applyPrefixTemplate,
   stripPrefixTemplate,
 } from '@/utils/linkPrefixTemplate';
+import { LINK_PREFIX_PRESETS } from '@/config/types';
 
 describe('hasPlaceholder', () => {
   it('识别 {url} 等占位符', () => {
@@ -139,3 +140,13 @@ describe('stripPrefixTemplate', () => {
     expect(stripPrefixTemplate('https://cdn.example.com/a.png', template)).toBeNull();
   });
 });
+
+describe('LINK_PREFIX_PRESETS', () => {
+  const ORIGINAL = 'https://tvax1.sinaimg.cn/large/abc.jpg?x=1&y=2';
+
+  it.each(LINK_PREFIX_PRESETS)('$name 预设可正向包装并反向还原', ({ template }) => {
+    const wrapped = applyPrefixTemplate(template, ORIGINAL);
+    expect(wrapped).toContain(encodeURIComponent(ORIGINAL));
+    expect(stripPrefixTemplate(wrapped, template)).toBe(ORIGINAL);
+  });
+});
@@ -0,0 +1,47 @@
+import { beforeEach, describe, expect, it, vi } from 'vitest';
+
+const { invokeMock } = vi.hoisted(() => ({
+  invokeMock: vi.fn(),
+}));
+
+vi.mock('@tauri-apps/api/core', () => ({
+  invoke: invokeMock,
+  convertFileSrc: (filePath: string, protocol = 'asset') =>
+    `${protocol}://localhost/${encodeURIComponent(filePath)}`,
+}));
+
+const { getLocalImageUrl, __resetLocalImageUrlForTests } = await import('@/utils/localImageUrl');
+
+describe('localImageUrl', () => {
+  beforeEach(() => {
+    vi.clearAllMocks();
+    __resetLocalImageUrlForTests();
+    invokeMock.mockResolvedValue('tok');
+  });
+
+  it('builds a picnexus-file url with token and optional max side', async () => {
+    const path = 'C:/photos/a b.png';
+    await expect(getLocalImageUrl(path)).resolves.toBe(
+      `picnexus-file://localhost/${encodeURIComponent(path)}?token=tok`
+    );
+    await expect(getLocalImageUrl(path, { maxSide: 1200 })).resolves.toBe(
+      `picnexus-file://localhost/${encodeURIComponent(path)}?token=tok&max=1200`
+    );
+  });
+
+  it('fetches the token only once per process', async () => {
+    await getLocalImageUrl('/a.png');
+    await getLocalImageUrl('/b.png');
+
+    expect(invokeMock).toHaveBeenCalledTimes(1);
+    expect(invokeMock).toHaveBeenCalledWith('get_file_protocol_token');
+  });
+
+  it('retries the token request after a failure', async () => {
+    invokeMock.mockRejectedValueOnce(new Error('not ready'));
+
+    await expect(getLocalImageUrl('/a.png')).rejects.toThrow('not ready');
+    await expect(getLocalImageUrl('/a.png')).resolves.toContain('token=tok');
+    expect(invokeMock).toHaveBeenCalledTimes(2);
+  });
+});
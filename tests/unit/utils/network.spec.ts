@@ -1,5 +1,15 @@
 import { describe, it, expect, vi, beforeEach } from 'vitest';
-import { checkNetworkConnectivity } from '@/utils/network';
+
+const { invokeMock } = vi.hoisted(() => ({
+  invokeMock: vi.fn(),
+}));
+
+vi.mock('@tauri-apps/api/core', () => ({
+  invoke: invokeMock,
+}));
+
+import { applyHttpClientTuning, checkNetworkConnectivity } from '@/utils/network';
+import { DEFAULT_CONFIG } from '@/config/types';
 
 describe('checkNetworkConnectivity', () => {
   beforeEach(() => {
@@ -43,3 +53,35 @@ describe('checkNetworkConnectivity', () => {
     );
   });
 });
+
+describe('applyHttpClientTuning', () => {
+  beforeEach(() => {
+    invokeMock.mockReset();
+  });
+
+  it('未启用时传 null，让 Rust 恢复默认参数', async () => {
+    await applyHttpClientTuning(DEFAULT_CONFIG.httpClientTuning);
+    await applyHttpClientTuning(undefined);
+
+    expect(invokeMock).toHaveBeenNthCalledWith(1, 'set_http_client_tuning', { tuning: null });
+    expect(invokeMock).toHaveBeenNthCalledWith(2, 'set_http_client_tuning', { tuning: null });
+  });
+
+  it('启用时只传调优字段', async () => {
+    await applyHttpClientTuning({
+      ...DEFAULT_CONFIG.httpClientTuning!,
+      enabled: true,
+      http2: false,
+      tcpKeepaliveSecs: 30,
+    });
+
+    expect(invokeMock).toHaveBeenCalledWith('set_http_client_tuning', {
+      tuning: {
+        poolMaxIdlePerHost: 10,
+        connectTimeoutSecs: 10,
+        http2: false,
+        tcpKeepaliveSecs: 30,
+      },
+    });
+  });
+});
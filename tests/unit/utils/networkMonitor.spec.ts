@@ -0,0 +1,129 @@
+import { afterEach, beforeEach, describe, expect, it, vi } from 'vitest';
+
+const { checkNetworkConnectivityMock } = vi.hoisted(() => ({
+  checkNetworkConnectivityMock: vi.fn(),
+}));
+
+vi.mock('@/utils/network', () => ({
+  checkNetworkConnectivity: checkNetworkConnectivityMock,
+}));
+
+vi.mock('@/utils/logger', () => ({
+  createLogger: () => ({
+    debug: vi.fn(),
+    info: vi.fn(),
+    warn: vi.fn(),
+    error: vi.fn(),
+  }),
+}));
+
+import {
+  checkOnline,
+  isOnline,
+  onNetworkChange,
+  recheckAfterFailure,
+  startNetworkMonitor,
+  waitUntilOnline,
+  __resetNetworkMonitorForTests,
+} from '@/utils/networkMonitor';
+import { notifySystemResume } from '@/utils/wakeScheduler';
+
+describe('networkMonitor', () => {
+  beforeEach(() => {
+    vi.useFakeTimers();
+    vi.setSystemTime(0);
+    __resetNetworkMonitorForTests();
+    checkNetworkConnectivityMock.mockReset().mockResolvedValue(true);
+  });
+
+  afterEach(() => {
+    __resetNetworkMonitorForTests();
+    vi.useRealTimers();
+  });
+
+  it('探测失败后进入离线状态并通知订阅者', async () => {
+    const listener = vi.fn();
+    onNetworkChange(listener);
+    checkNetworkConnectivityMock.mockResolvedValue(false);
+
+    await expect(checkOnline()).resolves.toBe(false);
+
+    expect(isOnline.value).toBe(false);
+    expect(listener).toHaveBeenCalledWith(false);
+  });
+
+  it('并发探测只发起一次请求', async () => {
+    await Promise.all([checkOnline(), checkOnline(), checkOnline()]);
+    expect(checkNetworkConnectivityMock).toHaveBeenCalledTimes(1);
+  });
+
+  it('离线期间按退避间隔探测，恢复后唤醒等待的任务', async () => {
+    checkNetworkConnectivityMock.mockResolvedValue(false);
+    await checkOnline();
+
+    let resumed = false;
+    const waiting = waitUntilOnline().then(() => {
+      resumed = true;
+    });
+
+    await vi.advanceTimersByTimeAsync(5_000);
+    expect(checkNetworkConnectivityMock).toHaveBeenCalledTimes(2);
+    expect(resumed).toBe(false);
+
+    // 第二次重新探测间隔翻倍为 10 秒
+    checkNetworkConnectivityMock.mockResolvedValue(true);
+    await vi.advanceTimersByTimeAsync(9_999);
+    expect(checkNetworkConnectivityMock).toHaveBeenCalledTimes(2);
+    await vi.advanceTimersByTimeAsync(1);
+    await waiting;
+
+    expect(checkNetworkConnectivityMock).toHaveBeenCalledTimes(3);
+    expect(resumed).toBe(true);
+    expect(isOnline.value).toBe(true);
+  });
+
+  it('刚探测成功过时，失败复查不再重复探测', async () => {
+    await checkOnline();
+    await expect(recheckAfterFailure()).resolves.toBe(true);
+    expect(checkNetworkConnectivityMock).toHaveBeenCalledTimes(1);
+
+    vi.setSystemTime(10_000);
+    checkNetworkConnectivityMock.mockResolvedValue(false);
+    await expect(recheckAfterFailure()).resolves.toBe(false);
+    expect(checkNetworkConnectivityMock).toHaveBeenCalledTimes(2);
+  });
+
+  it('系统网络事件触发探测', async () => {
+    const stop = startNetworkMonitor();
+    checkNetworkConnectivityMock.mockResolvedValue(false);
+
+    window.dispatchEvent(new Event('offline'));
+    await vi.advanceTimersByTimeAsync(0);
+    expect(isOnline.value).toBe(false);
+
+    checkNetworkConnectivityMock.mockResolvedValue(true);
+    window.dispatchEvent(new Event('online'));
+    await vi.advanceTimersByTimeAsync(0);
+    expect(isOnline.value).toBe(true);
+
+    stop();
+    window.dispatchEvent(new Event('offline'));
+    await vi.advanceTimersByTimeAsync(0);
+    expect(checkNetworkConnectivityMock).toHaveBeenCalledTimes(2);
+  });
+
+  it('系统唤醒后延迟一段时间再探测', async () => {
+    const randomSpy = vi.spyOn(Math, 'random').mockReturnValue(0);
+    const stop = startNetworkMonitor();
+
+    notifySystemResume(3_600_000);
+    await vi.advanceTimersByTimeAsync(4_999);
+    expect(checkNetworkConnectivityMock).not.toHaveBeenCalled();
+
+    await vi.advanceTimersByTimeAsync(1);
+    expect(checkNetworkConnectivityMock).toHaveBeenCalledTimes(1);
+
+    stop();
+    randomSpy.mockRestore();
+  });
+});
@@ -0,0 +1,93 @@
+import { afterEach, beforeEach, describe, expect, it, vi } from 'vitest';
+import {
+  SlidingWindowRateLimiter,
+  getServiceRateLimiter,
+  resolveUploadRateLimit,
+  __resetRateLimitersForTests,
+} from '@/utils/rateLimiter';
+import type { UserConfig } from '@/config/types';
+
+function makeConfig(uploadRateLimits?: Record<string, number>): UserConfig {
+  return { enabledServices: [], services: {}, weiboProxyMode: 'none', uploadRateLimits } as unknown as UserConfig;
+}
+
+describe('SlidingWindowRateLimiter', () => {
+  beforeEach(() => {
+    vi.useFakeTimers();
+    vi.setSystemTime(0);
+  });
+
+  afterEach(() => {
+    vi.useRealTimers();
+  });
+
+  it('配额内立即放行', async () => {
+    const limiter = new SlidingWindowRateLimiter();
+    await limiter.acquire(3);
+    await limiter.acquire(3);
+    await limiter.acquire(3);
+    expect(limiter.used).toBe(3);
+  });
+
+  it('超出配额时等待最早一次滑出窗口', async () => {
+    const limiter = new SlidingWindowRateLimiter();
+    await limiter.acquire(2);
+    vi.setSystemTime(10_000);
+    await limiter.acquire(2);
+
+    const onWait = vi.fn();
+    let released = false;
+    const pending = limiter.acquire(2, onWait).then(() => { released = true; });
+
+    await vi.advanceTimersByTimeAsync(0);
+    expect(onWait).toHaveBeenCalledWith(50_000);
+    expect(released).toBe(false);
+
+    await vi.advanceTimersByTimeAsync(49_999);
+    expect(released).toBe(false);
+
+    await vi.advanceTimersByTimeAsync(1);
+    await pending;
+    expect(released).toBe(true);
+  });
+
+  it('等待中的调用按 FIFO 顺序放行', async () => {
+    const limiter = new SlidingWindowRateLimiter();
+    await limiter.acquire(1);
+
+    const order: number[] = [];
+    const first = limiter.acquire(1).then(() => order.push(1));
+    const second = limiter.acquire(1).then(() => order.push(2));
+
+    await vi.advanceTimersByTimeAsync(120_000);
+    await Promise.all([first, second]);
+    expect(order).toEqual([1, 2]);
+  });
+
+  it('limit <= 0 不限速也不计数', async () => {
+    const limiter = new SlidingWindowRateLimiter();
+    for (let i = 0; i < 100; i++) await limiter.acquire(0);
+    expect(limiter.used).toBe(0);
+  });
+});
+
+describe('resolveUploadRateLimit', () => {
+  beforeEach(() => {
+    __resetRateLimitersForTests();
+  });
+
+  it('未配置时微博使用默认值，其他图床不限速', () => {
+    expect(resolveUploadRateLimit('weibo', makeConfig())).toBe(20);
+    expect(resolveUploadRateLimit('r2', makeConfig())).toBe(0);
+  });
+
+  it('配置值优先于默认值，0 表示关闭限速', () => {
+    expect(resolveUploadRateLimit('weibo', makeConfig({ weibo: 0 }))).toBe(0);
+    expect(resolveUploadRateLimit('zhihu', makeConfig({ zhihu: 12.7 }))).toBe(12);
+  });
+
+  it('同一图床共享限速器', () => {
+    expect(getServiceRateLimiter('weibo')).toBe(getServiceRateLimiter('weibo'));
+    expect(getServiceRateLimiter('weibo')).not.toBe(getServiceRateLimiter('zhihu'));
+  });
+});
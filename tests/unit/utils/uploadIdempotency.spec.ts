@@ -0,0 +1,66 @@
+import { afterEach, beforeEach, describe, expect, it, vi } from 'vitest';
+
+vi.mock('@/utils/logger', () => ({
+  createLogger: () => ({
+    debug: vi.fn(),
+    info: vi.fn(),
+    warn: vi.fn(),
+    error: vi.fn(),
+  }),
+}));
+
+import {
+  getCompletedUpload,
+  runIdempotent,
+  uploadIdempotencyKey,
+  __resetUploadIdempotencyForTests,
+} from '@/utils/uploadIdempotency';
+import type { UploadResult } from '@/uploaders/base/types';
+
+const result = (url: string): UploadResult => ({ serviceId: 'smms', fileKey: 'k', url });
+
+describe('uploadIdempotency', () => {
+  beforeEach(() => {
+    __resetUploadIdempotencyForTests();
+  });
+
+  afterEach(() => {
+    vi.useRealTimers();
+  });
+
+  it('同键上传进行中时再次发起，等待同一次上传', async () => {
+    let resolveUpload!: (value: UploadResult) => void;
+    const upload = vi.fn(() => new Promise<UploadResult>((resolve) => { resolveUpload = resolve; }));
+    const key = uploadIdempotencyKey('queue-1', 'smms');
+
+    const first = runIdempotent(key, upload);
+    const second = runIdempotent(key, upload);
+    resolveUpload(result('https://smms.app/a.png'));
+
+    await expect(first).resolves.toEqual(result('https://smms.app/a.png'));
+    await expect(second).resolves.toEqual(result('https://smms.app/a.png'));
+    expect(upload).toHaveBeenCalledTimes(1);
+  });
+
+  it('失败的上传不记录，重试时重新上传', async () => {
+    const key = uploadIdempotencyKey('queue-1', 'smms');
+    const upload = vi.fn()
+      .mockRejectedValueOnce(new Error('connection reset'))
+      .mockResolvedValueOnce(result('https://smms.app/a.png'));
+
+    await expect(runIdempotent(key, upload)).rejects.toThrow('connection reset');
+    await expect(runIdempotent(key, upload)).resolves.toEqual(result('https://smms.app/a.png'));
+    expect(upload).toHaveBeenCalledTimes(2);
+    expect(getCompletedUpload(key)?.url).toBe('https://smms.app/a.png');
+  });
+
+  it('成功结果过期后不再复用', async () => {
+    vi.useFakeTimers();
+    vi.setSystemTime(0);
+    const key = uploadIdempotencyKey('queue-1', 'smms');
+    await runIdempotent(key, async () => result('https://smms.app/a.png'));
+
+    vi.setSystemTime(24 * 60 * 60 * 1000);
+    expect(getCompletedUpload(key)).toBeUndefined();
+  });
+});
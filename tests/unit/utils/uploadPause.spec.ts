@@ -0,0 +1,36 @@
+import { afterEach, describe, expect, it } from 'vitest';
+import {
+  isUploadQueuePaused,
+  pauseUploadQueue,
+  resumeUploadQueue,
+  waitUntilQueueResumed,
+} from '@/utils/uploadPause';
+
+describe('uploadPause', () => {
+  afterEach(() => {
+    resumeUploadQueue();
+  });
+
+  it('未暂停时 waitUntilQueueResumed 立即返回', async () => {
+    expect(isUploadQueuePaused.value).toBe(false);
+    await expect(waitUntilQueueResumed()).resolves.toBeUndefined();
+  });
+
+  it('暂停期间挂起，继续后唤醒所有等待者', async () => {
+    pauseUploadQueue();
+    pauseUploadQueue();
+    expect(isUploadQueuePaused.value).toBe(true);
+
+    let resumed = 0;
+    const pending = [waitUntilQueueResumed(), waitUntilQueueResumed()].map(p => p.then(() => {
+      resumed++;
+    }));
+    await Promise.resolve();
+    expect(resumed).toBe(0);
+
+    resumeUploadQueue();
+    await Promise.all(pending);
+    expect(resumed).toBe(2);
+    expect(isUploadQueuePaused.value).toBe(false);
+  });
+});
@@ -0,0 +1,91 @@
+import { afterEach, beforeEach, describe, expect, it, vi } from 'vitest';
+import { listen } from '@tauri-apps/api/event';
+
+vi.mock('@/utils/logger', () => ({
+  createLogger: () => ({
+    debug: vi.fn(),
+    info: vi.fn(),
+    warn: vi.fn(),
+    error: vi.fn(),
+  }),
+}));
+
+import {
+  notifySystemResume,
+  onSystemResume,
+  scheduleInterval,
+  startWakeListener,
+  SYSTEM_RESUMED_EVENT,
+  __resetWakeSchedulerForTests,
+} from '@/utils/wakeScheduler';
+
+describe('wakeScheduler', () => {
+  let randomSpy: ReturnType<typeof vi.spyOn>;
+
+  beforeEach(() => {
+    vi.useFakeTimers();
+    vi.setSystemTime(0);
+    randomSpy = vi.spyOn(Math, 'random').mockReturnValue(0);
+    __resetWakeSchedulerForTests();
+  });
+
+  afterEach(() => {
+    __resetWakeSchedulerForTests();
+    randomSpy.mockRestore();
+    vi.useRealTimers();
+  });
+
+  it('按间隔周期执行，停止后不再执行', () => {
+    const task = vi.fn();
+    const stop = scheduleInterval(task, 1_000, 100);
+
+    vi.advanceTimersByTime(100);
+    expect(task).toHaveBeenCalledTimes(1);
+    vi.advanceTimersByTime(2_000);
+    expect(task).toHaveBeenCalledTimes(3);
+
+    stop();
+    vi.advanceTimersByTime(5_000);
+    expect(task).toHaveBeenCalledTimes(3);
+  });
+
+  it('唤醒时已过期的任务在随机延迟后执行，而不是立即执行', () => {
+    const task = vi.fn();
+    scheduleInterval(task, 60_000);
+
+    // 模拟休眠：墙上时间前进但定时器没有触发
+    vi.setSystemTime(3_600_000);
+    notifySystemResume(3_600_000);
+    expect(task).not.toHaveBeenCalled();
+
+    vi.advanceTimersByTime(4_999);
+    expect(task).not.toHaveBeenCalled();
+    vi.advanceTimersByTime(1);
+    expect(task).toHaveBeenCalledTimes(1);
+  });
+
+  it('唤醒时未过期的任务按墙上时间的剩余时长执行', () => {
+    const task = vi.fn();
+    scheduleInterval(task, 60_000);
+
+    vi.setSystemTime(20_000);
+    notifySystemResume(20_000);
+
+    vi.advanceTimersByTime(39_999);
+    expect(task).not.toHaveBeenCalled();
+    vi.advanceTimersByTime(1);
+    expect(task).toHaveBeenCalledTimes(1);
+  });
+
+  it('后端唤醒事件通知订阅者', async () => {
+    const handler = vi.fn();
+    onSystemResume(handler);
+
+    await startWakeListener();
+    const [eventName, callback] = vi.mocked(listen).mock.calls.at(-1)!;
+    expect(eventName).toBe(SYSTEM_RESUMED_EVENT);
+
+    (callback as (event: { payload: { sleptMs: number } }) => void)({ payload: { sleptMs: 1_234 } });
+    expect(handler).toHaveBeenCalledWith(1_234);
+  });
+});